@@ -0,0 +1,110 @@
+// engines.rs - The regex engines the grammars benchmark compares
+//
+// Every engine answers the question a TextMate tokenizer asks its scanner:
+// where is the earliest match of any rule at or after this position? To
+// compare another engine, implement `Engine` for it and add it to
+// `bench_workload` in main.rs.
+
+use ferroni::scanner::{Scanner, ScannerFindOptions};
+
+/// A multi-pattern matcher in the style of a TextMate scanner.
+pub trait Engine: Sized {
+    /// Name in benchmark ids and in `Workload::max_ratio`.
+    const NAME: &'static str;
+
+    /// Compile `patterns` (Oniguruma syntax).
+    fn new(patterns: &[&str]) -> Result<Self, String>;
+
+    /// Byte range of the earliest match of any pattern at or after `pos`
+    /// in `line`. Ties go to the pattern listed first.
+    fn find(&mut self, line: &str, pos: usize) -> Option<(usize, usize)>;
+}
+
+/// Tokenize `text` line by line: take the earliest match, continue after
+/// it (one byte further after an empty match). Returns the token count.
+pub fn tokenize<E: Engine>(engine: &mut E, text: &str) -> usize {
+    let mut tokens = 0;
+    for line in text.lines() {
+        let mut pos = 0;
+        while let Some((_, end)) = engine.find(line, pos) {
+            tokens += 1;
+            pos = if end > pos { end } else { pos + 1 };
+            if pos > line.len() {
+                break;
+            }
+        }
+    }
+    tokens
+}
+
+/// Ferroni's [`Scanner`].
+pub struct Ferroni(Scanner);
+
+impl Engine for Ferroni {
+    const NAME: &'static str = "ferroni";
+
+    fn new(patterns: &[&str]) -> Result<Self, String> {
+        Scanner::new(patterns)
+            .map(Ferroni)
+            .map_err(|e| e.to_string())
+    }
+
+    fn find(&mut self, line: &str, pos: usize) -> Option<(usize, usize)> {
+        let m = self
+            .0
+            .find_next_match(line, pos, ScannerFindOptions::NONE)?;
+        Some((m.capture_indices[0].start, m.capture_indices[0].end))
+    }
+}
+
+/// C Oniguruma, searching each pattern in turn.
+#[cfg(feature = "c-oniguruma")]
+pub struct Onig {
+    regs: Vec<ferroni::c_oniguruma::CRegex>,
+    region: ferroni::c_oniguruma::CRegion,
+}
+
+#[cfg(feature = "c-oniguruma")]
+impl Engine for Onig {
+    const NAME: &'static str = "onig";
+
+    fn new(patterns: &[&str]) -> Result<Self, String> {
+        let regs = patterns
+            .iter()
+            .map(|p| {
+                ferroni::c_oniguruma::CRegex::new(
+                    p.as_bytes(),
+                    ferroni::c_oniguruma::ONIG_OPTION_NONE,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|code| format!("C onig_new failed: {code}"))?;
+        Ok(Onig {
+            regs,
+            region: ferroni::c_oniguruma::CRegion::new(),
+        })
+    }
+
+    fn find(&mut self, line: &str, pos: usize) -> Option<(usize, usize)> {
+        let text = line.as_bytes();
+        let mut best: Option<(usize, usize)> = None;
+        for reg in &self.regs {
+            let r = reg.search(
+                text,
+                pos,
+                text.len(),
+                Some(&mut self.region),
+                ferroni::c_oniguruma::ONIG_OPTION_NONE,
+            );
+            if r >= 0 && best.map_or(true, |(start, _)| (r as usize) < start) {
+                let (beg, end) = self.region.regs()[0];
+                best = Some((beg as usize, end as usize));
+                // Nothing can start earlier than `pos`.
+                if beg as usize == pos {
+                    break;
+                }
+            }
+        }
+        best
+    }
+}
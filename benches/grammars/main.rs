@@ -0,0 +1,136 @@
+// Criterion benchmarks for TextMate grammar workloads across engines
+//
+// Each workload (workloads.rs) tokenizes a CSS, HTML or TypeScript source
+// with the top-level rules of a grammar. Every engine (engines.rs) runs
+// the same workload after checking that it finds the same tokens as
+// Ferroni; with the `c-oniguruma` feature that includes C Oniguruma.
+//
+// Run: cargo bench --bench grammars
+//      cargo bench --features c-oniguruma --bench grammars
+//      cargo bench --features c-oniguruma --bench grammars -- ts   # one workload
+//
+// After measuring, Ferroni's mean time on each workload is compared with
+// the other engines' (see `Workload::max_ratio`). The run exits with
+// status 1 if Ferroni is slower than a threshold allows.
+
+mod engines;
+mod workloads;
+
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use criterion::measurement::WallTime;
+use criterion::{black_box, BenchmarkGroup, Criterion, Throughput};
+
+use engines::{tokenize, Engine, Ferroni};
+use workloads::{Workload, WORKLOADS};
+
+/// Benchmark `E` on `workload`, after checking it finds `expected` tokens.
+fn bench_engine<E: Engine>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    workload: &Workload,
+    text: &str,
+    expected: usize,
+) {
+    let mut engine = E::new(workload.patterns).unwrap_or_else(|e| {
+        panic!(
+            "{}: {} cannot compile the rules: {e}",
+            workload.name,
+            E::NAME
+        )
+    });
+    let tokens = tokenize(&mut engine, text);
+    assert_eq!(
+        tokens,
+        expected,
+        "{}: {} and ferroni disagree on the token count",
+        workload.name,
+        E::NAME
+    );
+    group.bench_function(E::NAME, |b| {
+        b.iter(|| black_box(tokenize(&mut engine, black_box(text))));
+    });
+}
+
+fn bench_workload(c: &mut Criterion, workload: &Workload) {
+    let text = workload.text();
+    let mut reference = Ferroni::new(workload.patterns).unwrap();
+    let expected = tokenize(&mut reference, &text);
+
+    let mut group = c.benchmark_group(format!("grammars/{}", workload.name));
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    bench_engine::<Ferroni>(&mut group, workload, &text, expected);
+    #[cfg(feature = "c-oniguruma")]
+    bench_engine::<engines::Onig>(&mut group, workload, &text, expected);
+    group.finish();
+}
+
+// ---------------------------------------------------------------------------
+// Thresholds
+// ---------------------------------------------------------------------------
+
+/// Where criterion writes its results.
+fn criterion_dir() -> PathBuf {
+    if let Some(home) = std::env::var_os("CRITERION_HOME") {
+        return home.into();
+    }
+    let target = std::env::var_os("CARGO_TARGET_DIR").unwrap_or_else(|| "target".into());
+    PathBuf::from(target).join("criterion")
+}
+
+/// Mean time in nanoseconds of `engine` on `workload`, if it was measured
+/// by this run (results of earlier, filtered-out runs are ignored).
+fn mean_ns(workload: &Workload, engine: &str, since: SystemTime) -> Option<f64> {
+    let path = criterion_dir()
+        .join(format!("grammars_{}", workload.name))
+        .join(engine)
+        .join("new/estimates.json");
+    if std::fs::metadata(&path).ok()?.modified().ok()? < since {
+        return None;
+    }
+    let json: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).ok()?).ok()?;
+    json["mean"]["point_estimate"].as_f64()
+}
+
+/// Print Ferroni's ratio to every other engine measured and return the
+/// number of thresholds exceeded.
+fn check_thresholds(since: SystemTime) -> usize {
+    let mut exceeded = 0;
+    for workload in WORKLOADS {
+        let Some(ours) = mean_ns(workload, Ferroni::NAME, since) else {
+            continue;
+        };
+        for &(engine, max_ratio) in workload.max_ratio {
+            let Some(theirs) = mean_ns(workload, engine, since) else {
+                continue;
+            };
+            let ratio = ours / theirs;
+            let verdict = if ratio > max_ratio {
+                exceeded += 1;
+                "REGRESSION"
+            } else {
+                "ok"
+            };
+            println!(
+                "grammars/{:<6} ferroni/{engine}: {ratio:.2} (max {max_ratio:.2}) {verdict}",
+                workload.name
+            );
+        }
+    }
+    exceeded
+}
+
+fn main() {
+    let since = SystemTime::now();
+    let mut c = Criterion::default().configure_from_args();
+    for workload in WORKLOADS {
+        bench_workload(&mut c, workload);
+    }
+    c.final_summary();
+
+    let exceeded = check_thresholds(since);
+    if exceeded > 0 {
+        eprintln!("{exceeded} workload(s) over their threshold");
+        std::process::exit(1);
+    }
+}
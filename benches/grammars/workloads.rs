@@ -0,0 +1,163 @@
+// workloads.rs - TextMate grammar workloads for the grammars benchmark
+//
+// Each workload is the flattened rule set of a grammar (the patterns a
+// tokenizer hands its scanner at the top level) and a source text to
+// tokenize with it. To add one, append a `Workload` to `WORKLOADS`.
+
+/// A grammar rule set and the text it tokenizes.
+pub struct Workload {
+    /// Name of the workload; benchmark groups are `grammars/<name>`.
+    pub name: &'static str,
+    /// Rule patterns in Oniguruma syntax, in grammar order.
+    pub patterns: &'static [&'static str],
+    /// Source text, repeated `repeat` times.
+    pub source: &'static str,
+    pub repeat: usize,
+    /// For each other engine, the largest ratio of Ferroni's mean time to
+    /// that engine's mean time before the run counts as a regression.
+    pub max_ratio: &'static [(&'static str, f64)],
+}
+
+impl Workload {
+    /// The text to tokenize.
+    pub fn text(&self) -> String {
+        self.source.repeat(self.repeat)
+    }
+}
+
+pub const WORKLOADS: &[Workload] = &[CSS, HTML, TS];
+
+const CSS: Workload = Workload {
+    name: "css",
+    patterns: &[
+        r"/\*.*?\*/",
+        r"/\*.*$",
+        r"@(?:media|import|keyframes|font-face|supports|charset)\b",
+        r"(?<![\w-])[.#][A-Za-z_-][\w-]*",
+        r"(?<=[{;]|^)\s*(-{0,2}[A-Za-z][\w-]*)\s*(?=:)",
+        r"#(?:\h{6}|\h{3})\b",
+        r"(?<![\w-])-?(?:\d+(?:\.\d+)?|\.\d+)(?:px|em|rem|vh|vw|ms|s|deg|fr|%)?",
+        r"\b(?:rgba?|hsla?|var|calc|url|linear-gradient|translate[XY]?)(?=\()",
+        r#""(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*'"#,
+        r"!important\b",
+        r"\b(?:none|auto|inherit|initial|solid|block|inline-block|flex|grid|absolute|relative|ease-in-out)\b",
+        r"::?[A-Za-z-]+(?:\([^)]*\))?",
+        r"[{}();:,>+~*]",
+        r"[A-Za-z_-][\w-]*",
+    ],
+    source: r#"/* Layout for the settings page */
+@import url("theme.css");
+:root { --accent: #3b82f6; --radius: 6px; }
+.settings > .panel, #sidebar .item:hover {
+  display: grid;
+  grid-template-columns: 240px 1fr;
+  background-color: rgba(255, 255, 255, 0.92);
+  border: 1px solid #ddd;
+  border-radius: var(--radius) !important;
+  transition: transform 150ms ease-in-out, opacity .2s;
+}
+.panel h2::after { content: '\2014'; margin-left: 0.5em; }
+@media (max-width: 720px) {
+  .settings > .panel { grid-template-columns: 1fr; padding: 12px 4%; }
+  #sidebar { position: absolute; transform: translateX(-100%); }
+}
+input[type="checkbox"]:checked + label::before { color: var(--accent); }
+"#,
+    repeat: 20,
+    max_ratio: &[("onig", 1.5)],
+};
+
+const HTML: Workload = Workload {
+    name: "html",
+    patterns: &[
+        r"<!--.*?-->",
+        r"<!--.*$",
+        r"(?i)<!DOCTYPE\s+html\s*>",
+        r"</?([A-Za-z][\w:-]*)",
+        r"/?>",
+        r"\b([A-Za-z_:][\w:.-]*)\s*(?==)",
+        r#"=\s*(?:"[^"]*"|'[^']*'|[^\s>]+)"#,
+        r"&(?:[A-Za-z]\w*|#\d+|#x\h+);",
+        r"[^<&\s]+",
+    ],
+    source: r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>Release notes &mdash; Project</title>
+  <link rel="stylesheet" href="/assets/site.css?v=42">
+</head>
+<body class="docs">
+  <!-- navigation -->
+  <nav id="top" data-sticky='true'>
+    <a href="/" class=logo>Home</a> &amp; <a href="/docs/">Docs</a>
+  </nav>
+  <main>
+    <h1>Version 2.4 &#8211; what&#x27;s new</h1>
+    <p>Faster startup, <em>smaller</em> bundles and a new <code>--watch</code> flag.</p>
+    <img src="chart.png" alt="Startup time, before and after" width="640" height="320"/>
+    <ul><li>Fixed <b>3</b> crashes</li><li>Improved error messages</li></ul>
+  </main>
+</body>
+</html>
+"#,
+    repeat: 20,
+    max_ratio: &[("onig", 1.5)],
+};
+
+const TS: Workload = Workload {
+    name: "ts",
+    patterns: &[
+        r"//.*$",
+        r"/\*.*?\*/",
+        r#""(?:[^"\\]|\\.)*""#,
+        r"'(?:[^'\\]|\\.)*'",
+        r"`(?:[^`\\$]|\\.|\$(?!\{))*`",
+        r"@[A-Za-z_$][\w$]*",
+        r"\b(?:import|export|from|as|default|const|let|var|function|return|if|else|for|of|in|while|new|class|extends|implements|interface|type|enum|async|await|public|private|protected|readonly|static)\b",
+        r"\b(?:string|number|boolean|void|unknown|never|any|null|undefined|true|false|this)\b",
+        r"(?<![\w$.])(?:0[xX]\h+|\d+(?:\.\d+)?(?:[eE][-+]?\d+)?)n?(?![\w$])",
+        r"(?<=:)\s*([A-Z][\w$]*)(?:<[^<>]*>)?",
+        r"([A-Za-z_$][\w$]*)\s*(?=<[^<>]*>\s*\(|\()",
+        r"(?<=\.)\s*([A-Za-z_$][\w$]*)",
+        r"[A-Za-z_$][\w$]*",
+        r"===|!==|=>|\?\?|\?\.|&&|\|\||[-+*/%=<>!&|^~?:]",
+        r"[{}()\[\];,.]",
+    ],
+    source: r#"import { EventEmitter } from "events";
+import type { Config, Plugin } from './types';
+
+/** Loads plugins in dependency order. */
+@injectable()
+export class PluginHost<T extends Plugin = Plugin> extends EventEmitter implements Disposable {
+  private readonly plugins: Map<string, T> = new Map();
+  protected static instances = 0x10;
+
+  constructor(private config: Config, public timeout: number = 2.5e3) {
+    super();
+    PluginHost.instances += 1;
+  }
+
+  async load(name: string, options?: Partial<Config>): Promise<T | undefined> {
+    const cached = this.plugins.get(name) ?? null;
+    if (cached !== null && options?.reload !== true) {
+      return cached;
+    }
+    const module = await import(`./plugins/${name}`);
+    const plugin: T = new module.default({ ...this.config, ...options });
+    this.plugins.set(name, plugin); // keep for the next call
+    this.emit('loaded', { name, at: Date.now() });
+    return plugin;
+  }
+
+  dispose(): void {
+    for (const [key, plugin] of this.plugins) {
+      plugin.dispose?.();
+      this.plugins.delete(key);
+    }
+  }
+}
+"#,
+    repeat: 10,
+    max_ratio: &[("onig", 1.5)],
+};
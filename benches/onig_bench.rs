@@ -1,14 +1,14 @@
 // Criterion benchmark suite: Ferroni (Rust) vs Oniguruma (C)
 //
-// Run: cargo bench --features ffi
-// Specific group: cargo bench --features ffi -- compile
+// Run: cargo bench --features c-oniguruma
+// Specific group: cargo bench --features c-oniguruma -- compile
 // HTML report: target/criterion/report/index.html
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use std::os::raw::c_uint;
 
+use ferroni::c_oniguruma;
 use ferroni::encodings::utf8::ONIG_ENCODING_UTF8;
-use ferroni::ffi;
 use ferroni::oniguruma::{OnigOptionType, OnigRegion, ONIG_OPTION_IGNORECASE, ONIG_OPTION_NONE};
 use ferroni::regcomp::onig_new;
 use ferroni::regexec::{onig_match, onig_region_new, onig_search};
@@ -41,8 +41,8 @@ fn rust_search(
     )
 }
 
-fn c_compile(pattern: &[u8], option: c_uint) -> ffi::CRegex {
-    ffi::CRegex::new(pattern, option).expect("C compile failed")
+fn c_compile(pattern: &[u8], option: c_uint) -> c_oniguruma::CRegex {
+    c_oniguruma::CRegex::new(pattern, option).expect("C compile failed")
 }
 
 // Verify both engines agree on match position (debug only)
@@ -85,7 +85,7 @@ fn bench_compile(c: &mut Criterion) {
         });
         group.bench_with_input(BenchmarkId::new("c", name), pat, |b, pat| {
             b.iter(|| {
-                let reg = c_compile(black_box(pat), ffi::ONIG_OPTION_NONE);
+                let reg = c_compile(black_box(pat), c_oniguruma::ONIG_OPTION_NONE);
                 black_box(&reg);
             });
         });
@@ -109,11 +109,11 @@ fn bench_literal_match(c: &mut Criterion) {
     let mut group = c.benchmark_group("literal_match");
     for (name, pat) in cases {
         let r_reg = rust_compile(pat, ONIG_OPTION_NONE);
-        let c_reg = c_compile(pat, ffi::ONIG_OPTION_NONE);
+        let c_reg = c_compile(pat, c_oniguruma::ONIG_OPTION_NONE);
 
         // Verify agreement
         let (r_pos, _) = rust_search(&r_reg, text, None);
-        let c_pos = c_reg.search(text, 0, text.len(), None, ffi::ONIG_OPTION_NONE);
+        let c_pos = c_reg.search(text, 0, text.len(), None, c_oniguruma::ONIG_OPTION_NONE);
         assert_same_result(r_pos, c_pos, name);
 
         group.bench_with_input(BenchmarkId::new("rust", name), &text[..], |b, text| {
@@ -123,7 +123,7 @@ fn bench_literal_match(c: &mut Criterion) {
             });
         });
         group.bench_with_input(BenchmarkId::new("c", name), &text[..], |b, text| {
-            let mut region = ffi::CRegion::new();
+            let mut region = c_oniguruma::CRegion::new();
             b.iter(|| {
                 region.clear();
                 let pos = c_reg.search(
@@ -131,7 +131,7 @@ fn bench_literal_match(c: &mut Criterion) {
                     0,
                     text.len(),
                     Some(&mut region),
-                    ffi::ONIG_OPTION_NONE,
+                    c_oniguruma::ONIG_OPTION_NONE,
                 );
                 black_box(pos);
             });
@@ -156,10 +156,10 @@ fn bench_quantifiers(c: &mut Criterion) {
     let mut group = c.benchmark_group("quantifiers");
     for (name, pat) in cases {
         let r_reg = rust_compile(pat, ONIG_OPTION_NONE);
-        let c_reg = c_compile(pat, ffi::ONIG_OPTION_NONE);
+        let c_reg = c_compile(pat, c_oniguruma::ONIG_OPTION_NONE);
 
         let (r_pos, _) = rust_search(&r_reg, text, None);
-        let c_pos = c_reg.search(text, 0, text.len(), None, ffi::ONIG_OPTION_NONE);
+        let c_pos = c_reg.search(text, 0, text.len(), None, c_oniguruma::ONIG_OPTION_NONE);
         assert_same_result(r_pos, c_pos, name);
 
         group.bench_with_input(BenchmarkId::new("rust", name), &text[..], |b, text| {
@@ -169,7 +169,7 @@ fn bench_quantifiers(c: &mut Criterion) {
             });
         });
         group.bench_with_input(BenchmarkId::new("c", name), &text[..], |b, text| {
-            let mut region = ffi::CRegion::new();
+            let mut region = c_oniguruma::CRegion::new();
             b.iter(|| {
                 region.clear();
                 let pos = c_reg.search(
@@ -177,7 +177,7 @@ fn bench_quantifiers(c: &mut Criterion) {
                     0,
                     text.len(),
                     Some(&mut region),
-                    ffi::ONIG_OPTION_NONE,
+                    c_oniguruma::ONIG_OPTION_NONE,
                 );
                 black_box(pos);
             });
@@ -205,10 +205,10 @@ fn bench_alternation(c: &mut Criterion) {
     let mut group = c.benchmark_group("alternation");
     for (name, pat) in cases {
         let r_reg = rust_compile(pat, ONIG_OPTION_NONE);
-        let c_reg = c_compile(pat, ffi::ONIG_OPTION_NONE);
+        let c_reg = c_compile(pat, c_oniguruma::ONIG_OPTION_NONE);
 
         let (r_pos, _) = rust_search(&r_reg, text, None);
-        let c_pos = c_reg.search(text, 0, text.len(), None, ffi::ONIG_OPTION_NONE);
+        let c_pos = c_reg.search(text, 0, text.len(), None, c_oniguruma::ONIG_OPTION_NONE);
         assert_same_result(r_pos, c_pos, name);
 
         group.bench_with_input(BenchmarkId::new("rust", name), &text[..], |b, text| {
@@ -218,7 +218,7 @@ fn bench_alternation(c: &mut Criterion) {
             });
         });
         group.bench_with_input(BenchmarkId::new("c", name), &text[..], |b, text| {
-            let mut region = ffi::CRegion::new();
+            let mut region = c_oniguruma::CRegion::new();
             b.iter(|| {
                 region.clear();
                 let pos = c_reg.search(
@@ -226,7 +226,7 @@ fn bench_alternation(c: &mut Criterion) {
                     0,
                     text.len(),
                     Some(&mut region),
-                    ffi::ONIG_OPTION_NONE,
+                    c_oniguruma::ONIG_OPTION_NONE,
                 );
                 black_box(pos);
             });
@@ -250,10 +250,10 @@ fn bench_backreferences(c: &mut Criterion) {
     let mut group = c.benchmark_group("backreferences");
     for (name, pat) in cases {
         let r_reg = rust_compile(pat, ONIG_OPTION_NONE);
-        let c_reg = c_compile(pat, ffi::ONIG_OPTION_NONE);
+        let c_reg = c_compile(pat, c_oniguruma::ONIG_OPTION_NONE);
 
         let (r_pos, _) = rust_search(&r_reg, text, None);
-        let c_pos = c_reg.search(text, 0, text.len(), None, ffi::ONIG_OPTION_NONE);
+        let c_pos = c_reg.search(text, 0, text.len(), None, c_oniguruma::ONIG_OPTION_NONE);
         assert_same_result(r_pos, c_pos, name);
 
         group.bench_with_input(BenchmarkId::new("rust", name), &text[..], |b, text| {
@@ -263,7 +263,7 @@ fn bench_backreferences(c: &mut Criterion) {
             });
         });
         group.bench_with_input(BenchmarkId::new("c", name), &text[..], |b, text| {
-            let mut region = ffi::CRegion::new();
+            let mut region = c_oniguruma::CRegion::new();
             b.iter(|| {
                 region.clear();
                 let pos = c_reg.search(
@@ -271,7 +271,7 @@ fn bench_backreferences(c: &mut Criterion) {
                     0,
                     text.len(),
                     Some(&mut region),
-                    ffi::ONIG_OPTION_NONE,
+                    c_oniguruma::ONIG_OPTION_NONE,
                 );
                 black_box(pos);
             });
@@ -297,10 +297,10 @@ fn bench_lookaround(c: &mut Criterion) {
     let mut group = c.benchmark_group("lookaround");
     for (name, pat) in cases {
         let r_reg = rust_compile(pat, ONIG_OPTION_NONE);
-        let c_reg = c_compile(pat, ffi::ONIG_OPTION_NONE);
+        let c_reg = c_compile(pat, c_oniguruma::ONIG_OPTION_NONE);
 
         let (r_pos, _) = rust_search(&r_reg, text, None);
-        let c_pos = c_reg.search(text, 0, text.len(), None, ffi::ONIG_OPTION_NONE);
+        let c_pos = c_reg.search(text, 0, text.len(), None, c_oniguruma::ONIG_OPTION_NONE);
         assert_same_result(r_pos, c_pos, name);
 
         group.bench_with_input(BenchmarkId::new("rust", name), &text[..], |b, text| {
@@ -310,7 +310,7 @@ fn bench_lookaround(c: &mut Criterion) {
             });
         });
         group.bench_with_input(BenchmarkId::new("c", name), &text[..], |b, text| {
-            let mut region = ffi::CRegion::new();
+            let mut region = c_oniguruma::CRegion::new();
             b.iter(|| {
                 region.clear();
                 let pos = c_reg.search(
@@ -318,7 +318,7 @@ fn bench_lookaround(c: &mut Criterion) {
                     0,
                     text.len(),
                     Some(&mut region),
-                    ffi::ONIG_OPTION_NONE,
+                    c_oniguruma::ONIG_OPTION_NONE,
                 );
                 black_box(pos);
             });
@@ -344,10 +344,10 @@ fn bench_unicode_properties(c: &mut Criterion) {
     let mut group = c.benchmark_group("unicode_properties");
     for (name, pat) in cases {
         let r_reg = rust_compile(pat, ONIG_OPTION_NONE);
-        let c_reg = c_compile(pat, ffi::ONIG_OPTION_NONE);
+        let c_reg = c_compile(pat, c_oniguruma::ONIG_OPTION_NONE);
 
         let (r_pos, _) = rust_search(&r_reg, text, None);
-        let c_pos = c_reg.search(text, 0, text.len(), None, ffi::ONIG_OPTION_NONE);
+        let c_pos = c_reg.search(text, 0, text.len(), None, c_oniguruma::ONIG_OPTION_NONE);
         assert_same_result(r_pos, c_pos, name);
 
         group.bench_with_input(BenchmarkId::new("rust", name), text, |b, text| {
@@ -357,7 +357,7 @@ fn bench_unicode_properties(c: &mut Criterion) {
             });
         });
         group.bench_with_input(BenchmarkId::new("c", name), text, |b, text| {
-            let mut region = ffi::CRegion::new();
+            let mut region = c_oniguruma::CRegion::new();
             b.iter(|| {
                 region.clear();
                 let pos = c_reg.search(
@@ -365,7 +365,7 @@ fn bench_unicode_properties(c: &mut Criterion) {
                     0,
                     text.len(),
                     Some(&mut region),
-                    ffi::ONIG_OPTION_NONE,
+                    c_oniguruma::ONIG_OPTION_NONE,
                 );
                 black_box(pos);
             });
@@ -389,7 +389,7 @@ fn bench_case_insensitive(c: &mut Criterion) {
     let mut group = c.benchmark_group("case_insensitive");
     for (name, pat) in cases {
         let r_reg = rust_compile(pat, ONIG_OPTION_IGNORECASE);
-        let c_reg = c_compile(pat, ffi::ONIG_OPTION_IGNORECASE);
+        let c_reg = c_compile(pat, c_oniguruma::ONIG_OPTION_IGNORECASE);
 
         let (r_pos, _) = onig_search(
             &r_reg,
@@ -400,7 +400,7 @@ fn bench_case_insensitive(c: &mut Criterion) {
             None,
             ONIG_OPTION_NONE,
         );
-        let c_pos = c_reg.search(text, 0, text.len(), None, ffi::ONIG_OPTION_NONE);
+        let c_pos = c_reg.search(text, 0, text.len(), None, c_oniguruma::ONIG_OPTION_NONE);
         assert_same_result(r_pos, c_pos, name);
 
         group.bench_with_input(BenchmarkId::new("rust", name), &text[..], |b, text| {
@@ -418,7 +418,7 @@ fn bench_case_insensitive(c: &mut Criterion) {
             });
         });
         group.bench_with_input(BenchmarkId::new("c", name), &text[..], |b, text| {
-            let mut region = ffi::CRegion::new();
+            let mut region = c_oniguruma::CRegion::new();
             b.iter(|| {
                 region.clear();
                 let pos = c_reg.search(
@@ -426,7 +426,7 @@ fn bench_case_insensitive(c: &mut Criterion) {
                     0,
                     text.len(),
                     Some(&mut region),
-                    ffi::ONIG_OPTION_NONE,
+                    c_oniguruma::ONIG_OPTION_NONE,
                 );
                 black_box(pos);
             });
@@ -444,7 +444,7 @@ fn bench_named_captures(c: &mut Criterion) {
     let pat = b"(?<year>\\d{4})-(?<month>\\d{2})-(?<day>\\d{2})";
 
     let r_reg = rust_compile(pat, ONIG_OPTION_NONE);
-    let c_reg = c_compile(pat, ffi::ONIG_OPTION_NONE);
+    let c_reg = c_compile(pat, c_oniguruma::ONIG_OPTION_NONE);
 
     let mut group = c.benchmark_group("named_captures");
 
@@ -468,7 +468,7 @@ fn bench_named_captures(c: &mut Criterion) {
     });
 
     group.bench_function("c", |b| {
-        let mut region = ffi::CRegion::new();
+        let mut region = c_oniguruma::CRegion::new();
         b.iter(|| {
             region.clear();
             let pos = c_reg.search(
@@ -476,7 +476,7 @@ fn bench_named_captures(c: &mut Criterion) {
                 0,
                 text.len(),
                 Some(&mut region),
-                ffi::ONIG_OPTION_NONE,
+                c_oniguruma::ONIG_OPTION_NONE,
             );
             black_box(pos);
         });
@@ -524,7 +524,7 @@ fn bench_large_text(c: &mut Criterion) {
 
     for (name, pat) in cases {
         let r_reg = rust_compile(pat, ONIG_OPTION_NONE);
-        let c_reg = c_compile(pat, ffi::ONIG_OPTION_NONE);
+        let c_reg = c_compile(pat, c_oniguruma::ONIG_OPTION_NONE);
 
         // 10KB
         let label_10k = format!("{}_10k", name);
@@ -539,7 +539,7 @@ fn bench_large_text(c: &mut Criterion) {
             },
         );
         group.bench_with_input(BenchmarkId::new("c", &label_10k), &text_10k, |b, text| {
-            let mut region = ffi::CRegion::new();
+            let mut region = c_oniguruma::CRegion::new();
             b.iter(|| {
                 region.clear();
                 let pos = c_reg.search(
@@ -547,7 +547,7 @@ fn bench_large_text(c: &mut Criterion) {
                     0,
                     text.len(),
                     Some(&mut region),
-                    ffi::ONIG_OPTION_NONE,
+                    c_oniguruma::ONIG_OPTION_NONE,
                 );
                 black_box(pos);
             });
@@ -566,7 +566,7 @@ fn bench_large_text(c: &mut Criterion) {
             },
         );
         group.bench_with_input(BenchmarkId::new("c", &label_50k), &text_50k, |b, text| {
-            let mut region = ffi::CRegion::new();
+            let mut region = c_oniguruma::CRegion::new();
             b.iter(|| {
                 region.clear();
                 let pos = c_reg.search(
@@ -574,7 +574,7 @@ fn bench_large_text(c: &mut Criterion) {
                     0,
                     text.len(),
                     Some(&mut region),
-                    ffi::ONIG_OPTION_NONE,
+                    c_oniguruma::ONIG_OPTION_NONE,
                 );
                 black_box(pos);
             });
@@ -608,17 +608,17 @@ fn bench_regset(c: &mut Criterion) {
     let mut rust_set = rust_set.unwrap();
 
     // C regset -- compile individually, then hand raw pointers to regset
-    let c_regs_owned: Vec<ffi::CRegex> = patterns
+    let c_regs_owned: Vec<c_oniguruma::CRegex> = patterns
         .iter()
-        .map(|p| c_compile(p, ffi::ONIG_OPTION_NONE))
+        .map(|p| c_compile(p, c_oniguruma::ONIG_OPTION_NONE))
         .collect();
-    let c_raw_ptrs: Vec<ffi::OnigRegex> = c_regs_owned.iter().map(|r| r.raw()).collect();
+    let c_raw_ptrs: Vec<c_oniguruma::OnigRegex> = c_regs_owned.iter().map(|r| r.raw()).collect();
     // C regset takes ownership of the regex objects, so we must NOT free them.
     // Leak the CRegex wrappers to prevent double-free.
     for r in c_regs_owned {
         std::mem::forget(r);
     }
-    let mut c_set = ffi::CRegSet::new(&c_raw_ptrs).expect("C regset_new failed");
+    let mut c_set = c_oniguruma::CRegSet::new(&c_raw_ptrs).expect("C regset_new failed");
 
     let mut group = c.benchmark_group("regset");
 
@@ -644,8 +644,8 @@ fn bench_regset(c: &mut Criterion) {
                 black_box(text),
                 0,
                 text.len(),
-                ffi::ONIG_REGSET_POSITION_LEAD,
-                ffi::ONIG_OPTION_NONE,
+                c_oniguruma::ONIG_REGSET_POSITION_LEAD,
+                c_oniguruma::ONIG_OPTION_NONE,
             );
             black_box((idx, pos));
         });
@@ -673,8 +673,8 @@ fn bench_regset(c: &mut Criterion) {
                 black_box(text),
                 0,
                 text.len(),
-                ffi::ONIG_REGSET_REGEX_LEAD,
-                ffi::ONIG_OPTION_NONE,
+                c_oniguruma::ONIG_REGSET_REGEX_LEAD,
+                c_oniguruma::ONIG_OPTION_NONE,
             );
             black_box((idx, pos));
         });
@@ -691,11 +691,11 @@ fn bench_match_at_position(c: &mut Criterion) {
     let pat = b"\\d+";
 
     let r_reg = rust_compile(pat, ONIG_OPTION_NONE);
-    let c_reg = c_compile(pat, ffi::ONIG_OPTION_NONE);
+    let c_reg = c_compile(pat, c_oniguruma::ONIG_OPTION_NONE);
 
     // Verify: match at offset 4
     let (r_len, _) = onig_match(&r_reg, text, text.len(), 4, None, ONIG_OPTION_NONE);
-    let c_len = c_reg.match_at(text, 4, None, ffi::ONIG_OPTION_NONE);
+    let c_len = c_reg.match_at(text, 4, None, c_oniguruma::ONIG_OPTION_NONE);
     assert!(r_len == 4, "Rust match_at expected 4, got {r_len}");
     assert!(c_len == 4, "C match_at expected 4, got {c_len}");
 
@@ -717,7 +717,7 @@ fn bench_match_at_position(c: &mut Criterion) {
 
     group.bench_function("c", |b| {
         b.iter(|| {
-            let len = c_reg.match_at(black_box(text), 4, None, ffi::ONIG_OPTION_NONE);
+            let len = c_reg.match_at(black_box(text), 4, None, c_oniguruma::ONIG_OPTION_NONE);
             black_box(len);
         });
     });
@@ -776,7 +776,7 @@ fn bench_scanner(c: &mut Criterion) {
     // -- short_string_c: vscode-oniguruma C scanner (RegSet fast-path) --
     {
         let c_scanner =
-            ffi::CScanner::new(SCANNER_PATTERNS_BYTES).expect("C scanner create failed");
+            c_oniguruma::CScanner::new(SCANNER_PATTERNS_BYTES).expect("C scanner create failed");
 
         group.bench_function("short_string_c", |b| {
             b.iter(|| {
@@ -788,15 +788,16 @@ fn bench_scanner(c: &mut Criterion) {
 
     // -- short_string_c_raw: raw C RegSet (no scanner layer, pure engine) --
     {
-        let c_regs_owned: Vec<ffi::CRegex> = SCANNER_PATTERNS_BYTES
+        let c_regs_owned: Vec<c_oniguruma::CRegex> = SCANNER_PATTERNS_BYTES
             .iter()
-            .map(|p| c_compile(p, ffi::ONIG_OPTION_NONE))
+            .map(|p| c_compile(p, c_oniguruma::ONIG_OPTION_NONE))
             .collect();
-        let c_raw_ptrs: Vec<ffi::OnigRegex> = c_regs_owned.iter().map(|r| r.raw()).collect();
+        let c_raw_ptrs: Vec<c_oniguruma::OnigRegex> =
+            c_regs_owned.iter().map(|r| r.raw()).collect();
         for r in c_regs_owned {
             std::mem::forget(r);
         }
-        let mut c_set = ffi::CRegSet::new(&c_raw_ptrs).expect("C regset_new failed");
+        let mut c_set = c_oniguruma::CRegSet::new(&c_raw_ptrs).expect("C regset_new failed");
 
         group.bench_function("short_string_c_raw", |b| {
             b.iter(|| {
@@ -804,8 +805,8 @@ fn bench_scanner(c: &mut Criterion) {
                     black_box(SCANNER_TEXT_SHORT),
                     0,
                     SCANNER_TEXT_SHORT.len(),
-                    ffi::ONIG_REGSET_POSITION_LEAD,
-                    ffi::ONIG_OPTION_NONE,
+                    c_oniguruma::ONIG_REGSET_POSITION_LEAD,
+                    c_oniguruma::ONIG_OPTION_NONE,
                 );
                 black_box((idx, pos));
             });
@@ -831,7 +832,7 @@ fn bench_scanner(c: &mut Criterion) {
     {
         let long = make_long_text();
         let c_scanner =
-            ffi::CScanner::new(SCANNER_PATTERNS_BYTES).expect("C scanner create failed");
+            c_oniguruma::CScanner::new(SCANNER_PATTERNS_BYTES).expect("C scanner create failed");
         let mut cache_id = 100i32;
 
         group.bench_function("long_string_cold_c", |b| {
@@ -847,13 +848,13 @@ fn bench_scanner(c: &mut Criterion) {
     //    Mirrors what the scanner does internally: search each regex, pick earliest.
     {
         let long = make_long_text();
-        let c_regs: Vec<ffi::CRegex> = SCANNER_PATTERNS_BYTES
+        let c_regs: Vec<c_oniguruma::CRegex> = SCANNER_PATTERNS_BYTES
             .iter()
-            .map(|p| c_compile(p, ffi::ONIG_OPTION_NONE))
+            .map(|p| c_compile(p, c_oniguruma::ONIG_OPTION_NONE))
             .collect();
 
         group.bench_function("long_string_cold_c_raw", |b| {
-            let mut region = ffi::CRegion::new();
+            let mut region = c_oniguruma::CRegion::new();
             b.iter(|| {
                 let text = black_box(long.as_slice());
                 let mut best_pos: i32 = -1;
@@ -865,7 +866,7 @@ fn bench_scanner(c: &mut Criterion) {
                         0,
                         text.len(),
                         Some(&mut region),
-                        ffi::ONIG_OPTION_NONE,
+                        c_oniguruma::ONIG_OPTION_NONE,
                     );
                     if pos >= 0 && (best_pos < 0 || pos < best_pos) {
                         best_pos = pos;
@@ -906,7 +907,7 @@ fn bench_scanner(c: &mut Criterion) {
     {
         let long = make_long_text();
         let c_scanner =
-            ffi::CScanner::new(SCANNER_PATTERNS_BYTES).expect("C scanner create failed");
+            c_oniguruma::CScanner::new(SCANNER_PATTERNS_BYTES).expect("C scanner create failed");
 
         // Prime the cache
         c_scanner.find_next_match(&long, 1, 0);
@@ -936,6 +937,226 @@ fn bench_scanner(c: &mut Criterion) {
     group.finish();
 }
 
+// ---------------------------------------------------------------------------
+// 14. line_anchored_alternation -- `^\s*(?:kw1|kw2|...)` over many lines
+// ---------------------------------------------------------------------------
+
+const CSS_PROPERTIES: &[&str] = &[
+    "align-content",
+    "background",
+    "border",
+    "bottom",
+    "color",
+    "display",
+    "flex",
+    "font-size",
+    "height",
+    "left",
+    "margin",
+    "opacity",
+    "padding",
+    "position",
+    "right",
+    "text-align",
+    "top",
+    "transform",
+    "visibility",
+    "width",
+    "z-index",
+];
+
+fn make_css_text(num_rules: usize) -> Vec<u8> {
+    let mut text = String::new();
+    for i in 0..num_rules {
+        text.push_str(&format!(".rule-{i} > a:hover {{\n"));
+        for j in 0..6 {
+            let prop = CSS_PROPERTIES[(i * 7 + j) % CSS_PROPERTIES.len()];
+            text.push_str(&format!("  {prop}: {}px; /* see {prop} */\n", i + j));
+        }
+        text.push_str("}\n");
+    }
+    text.into_bytes()
+}
+
+fn bench_line_anchored_alternation(c: &mut Criterion) {
+    let text = make_css_text(200); // ~8KB
+    let pat = format!("^[ \\t]*(?:{})\\b", CSS_PROPERTIES.join("|"));
+    let pat = pat.as_bytes();
+
+    let r_reg = rust_compile(pat, ONIG_OPTION_NONE);
+    let c_reg = c_compile(pat, c_oniguruma::ONIG_OPTION_NONE);
+
+    let mut group = c.benchmark_group("line_anchored_alternation");
+
+    // Find every match, as a highlighter scanning a whole document would
+    group.bench_function("rust/scan_all", |b| {
+        b.iter(|| {
+            let mut start = 0;
+            let mut count = 0;
+            while start < text.len() {
+                let (pos, _) = onig_search(
+                    &r_reg,
+                    black_box(&text),
+                    text.len(),
+                    start,
+                    text.len(),
+                    None,
+                    ONIG_OPTION_NONE,
+                );
+                if pos < 0 {
+                    break;
+                }
+                start = pos as usize + 1;
+                count += 1;
+            }
+            black_box(count);
+        });
+    });
+    group.bench_function("c/scan_all", |b| {
+        b.iter(|| {
+            let mut start = 0;
+            let mut count = 0;
+            while start < text.len() {
+                let pos = c_reg.search(
+                    black_box(&text),
+                    start,
+                    text.len(),
+                    None,
+                    c_oniguruma::ONIG_OPTION_NONE,
+                );
+                if pos < 0 {
+                    break;
+                }
+                start = pos as usize + 1;
+                count += 1;
+            }
+            black_box(count);
+        });
+    });
+
+    // One search per line, as a line-based tokenizer would
+    let lines: Vec<&[u8]> = text.split_inclusive(|&b| b == b'\n').collect();
+    group.bench_function("rust/per_line", |b| {
+        b.iter(|| {
+            for line in &lines {
+                let (pos, _) = rust_search(&r_reg, black_box(line), None);
+                black_box(pos);
+            }
+        });
+    });
+    group.bench_function("c/per_line", |b| {
+        let mut region = c_oniguruma::CRegion::new();
+        b.iter(|| {
+            for line in &lines {
+                region.clear();
+                let pos = c_reg.search(
+                    black_box(line),
+                    0,
+                    line.len(),
+                    Some(&mut region),
+                    c_oniguruma::ONIG_OPTION_NONE,
+                );
+                black_box(pos);
+            }
+        });
+    });
+    group.finish();
+}
+
+// ---------------------------------------------------------------------------
+// 15. shift_or -- JSON token patterns: Shift-Or backend vs VM vs C
+// ---------------------------------------------------------------------------
+
+fn make_json_text(num_items: usize) -> Vec<u8> {
+    let mut text = String::from("[\n");
+    for i in 0..num_items {
+        text.push_str(&format!(
+            "  {{\"id\": {i}, \"name\": \"item\\u00{:02x} \\\"{i}\\\"\", \"price\": {}.{:02}, \"ratio\": {}e-{}}},\n",
+            0x41 + i % 26,
+            i * 3,
+            i % 100,
+            i % 7 + 1,
+            i % 5 + 1,
+        ));
+    }
+    text.push_str("]\n");
+    text.into_bytes()
+}
+
+fn bench_shift_or(c: &mut Criterion) {
+    let text = make_json_text(200); // ~16KB
+
+    // Tokens of the JSON grammar that are short fixed byte-set sequences
+    let cases: &[(&str, &[u8])] = &[
+        ("unicode_escape", b"\\\\u[0-9a-fA-F]{4}"),
+        ("char_escape", b"\\\\[\"\\\\/bfnrt]"),
+        ("fraction", b"[0-9][.][0-9]{2}"),
+        ("exponent", b"[0-9][eE][-+][0-9]"),
+    ];
+
+    let mut group = c.benchmark_group("shift_or");
+
+    for (name, pat) in cases {
+        let r_reg = rust_compile(pat, ONIG_OPTION_NONE);
+        ferroni::regcomp::onig_set_shift_or_backend(false);
+        let vm_reg = rust_compile(pat, ONIG_OPTION_NONE);
+        ferroni::regcomp::onig_set_shift_or_backend(true);
+        let c_reg = c_compile(pat, c_oniguruma::ONIG_OPTION_NONE);
+
+        let scan_all = |reg: &ferroni::regint::RegexType, text: &[u8]| {
+            let mut start = 0;
+            let mut count = 0;
+            while start < text.len() {
+                let (pos, _) = onig_search(
+                    reg,
+                    text,
+                    text.len(),
+                    start,
+                    text.len(),
+                    None,
+                    ONIG_OPTION_NONE,
+                );
+                if pos < 0 {
+                    break;
+                }
+                start = pos as usize + 1;
+                count += 1;
+            }
+            count
+        };
+        assert_eq!(scan_all(&r_reg, &text), scan_all(&vm_reg, &text), "{name}");
+
+        group.bench_function(BenchmarkId::new("rust", name), |b| {
+            b.iter(|| black_box(scan_all(&r_reg, black_box(&text))));
+        });
+        group.bench_function(BenchmarkId::new("rust_vm", name), |b| {
+            b.iter(|| black_box(scan_all(&vm_reg, black_box(&text))));
+        });
+        group.bench_function(BenchmarkId::new("c", name), |b| {
+            b.iter(|| {
+                let mut start = 0;
+                let mut count = 0;
+                while start < text.len() {
+                    let pos = c_reg.search(
+                        black_box(&text),
+                        start,
+                        text.len(),
+                        None,
+                        c_oniguruma::ONIG_OPTION_NONE,
+                    );
+                    if pos < 0 {
+                        break;
+                    }
+                    start = pos as usize + 1;
+                    count += 1;
+                }
+                black_box(count);
+            });
+        });
+    }
+    group.finish();
+}
+
 // ---------------------------------------------------------------------------
 // Criterion harness
 // ---------------------------------------------------------------------------
@@ -955,5 +1176,7 @@ criterion_group!(
     bench_regset,
     bench_match_at_position,
     bench_scanner,
+    bench_line_anchored_alternation,
+    bench_shift_or,
 );
 criterion_main!(benches);
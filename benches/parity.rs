@@ -0,0 +1,20 @@
+// Speed and result parity: Ferroni (Rust) vs Oniguruma (C)
+//
+// Run: cargo bench --features c-oniguruma --bench parity
+// Works with the oniguruma-orig submodule or the system libonig (set
+// ONIG_LIB_DIR if it is not on the default library path). Exits with
+// status 1 if the engines disagree on any case.
+
+use std::time::Duration;
+
+use ferroni::parity;
+
+fn main() {
+    let report = parity::run(&parity::default_corpus(), Duration::from_millis(200));
+    println!("{report}");
+    let mismatches = report.mismatches().count();
+    if mismatches > 0 {
+        eprintln!("{mismatches} case(s) differ from C");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,201 @@
+// Criterion benchmarks for the match_at() opcode loop (Rust only)
+//
+// Workloads where the VM, not the prefilters, dominates: tokenizing source
+// lines with TextMate-grammar-style scanners and backtracking-heavy
+// patterns. Compare two builds with criterion baselines:
+//
+//   cargo bench --bench vm_dispatch -- --save-baseline before
+//   (apply the change)
+//   cargo bench --bench vm_dispatch -- --baseline before
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use ferroni::prelude::*;
+use ferroni::scanner::{Scanner, ScannerFindOptions};
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Patterns in the style of a JavaScript TextMate grammar's top-level rules.
+const GRAMMAR_PATTERNS: &[&str] = &[
+    r"//.*$",
+    r"/\*",
+    r#""(?:[^"\\]|\\.)*""#,
+    r"'(?:[^'\\]|\\.)*'",
+    r"`",
+    r"\b(?:const|let|var|function|return|if|else|for|while|new|class|extends|import|export|from)\b",
+    r"\b(?:true|false|null|undefined|this)\b",
+    r"(?<![\w$])(?:0[xX][0-9a-fA-F]+|\d+(?:\.\d+)?(?:[eE][-+]?\d+)?)(?![\w$])",
+    r"([A-Za-z_$][\w$]*)\s*(?=\()",
+    r"(?<=\.)\s*([A-Za-z_$][\w$]*)",
+    r"[A-Za-z_$][\w$]*",
+    r"===|!==|=>|&&|\|\||[-+*/%=<>!&|^~?:]",
+    r"[{}()\[\];,.]",
+];
+
+const SOURCE: &str = r#"import { readFile } from "fs";
+export class Tokenizer extends Base {
+  constructor(options) {
+    super(options);
+    this.cache = new Map(); // per-line results
+    this.limit = options.limit || 0x400;
+  }
+  /* Scan one line and return the tokens found. */
+  scan(line, state) {
+    const tokens = [];
+    for (let i = 0; i < line.length; i += 1) {
+      if (line[i] === '"' && state.inString !== true) {
+        tokens.push({ kind: "string", start: i, value: 'quote' });
+      } else if (/\d/.test(line[i])) {
+        tokens.push({ kind: "number", value: parseFloat(line.slice(i)) * 1.5e3 });
+      }
+    }
+    return tokens.length > this.limit ? null : tokens;
+  }
+}
+"#;
+
+/// Tokenize every line of `text` the way a TextMate tokenizer does: take
+/// the earliest match of any rule, continue after it.
+fn tokenize(scanner: &mut Scanner, text: &str) -> usize {
+    let mut tokens = 0;
+    for line in text.lines() {
+        let mut pos = 0;
+        while let Some(m) = scanner.find_next_match(line, pos, ScannerFindOptions::NONE) {
+            tokens += 1;
+            let end = m.capture_indices[0].end;
+            pos = if end > pos { end } else { pos + 1 };
+            if pos > line.len() {
+                break;
+            }
+        }
+    }
+    tokens
+}
+
+// ---------------------------------------------------------------------------
+// 1. grammar -- TextMate-style line tokenization
+// ---------------------------------------------------------------------------
+
+fn bench_grammar(c: &mut Criterion) {
+    let mut group = c.benchmark_group("grammar");
+    let mut scanner = Scanner::new(GRAMMAR_PATTERNS).unwrap();
+    group.bench_function("js_source", |b| {
+        b.iter(|| black_box(tokenize(&mut scanner, black_box(SOURCE))));
+    });
+
+    let long_lines = SOURCE.replace('\n', " ").repeat(4);
+    group.bench_function("js_long_line", |b| {
+        b.iter(|| black_box(tokenize(&mut scanner, black_box(&long_lines))));
+    });
+    group.finish();
+}
+
+// ---------------------------------------------------------------------------
+// 2. backtrack -- patterns that spend their time in the opcode loop
+// ---------------------------------------------------------------------------
+
+fn bench_backtrack(c: &mut Criterion) {
+    let cases: &[(&str, &str)] = &[
+        (
+            "alternation",
+            r"\b(?:function|return|const|class|constructor)\s+\w+",
+        ),
+        ("lazy", r"\{.*?\}"),
+        ("captures", r"(\w+)\.(\w+)\(([^)]*)\)"),
+        ("lookaround", r"(?<=\()\w+(?=[,)])"),
+        ("backref", r#"(["'])(?:(?!\1).)*\1"#),
+    ];
+
+    let mut group = c.benchmark_group("backtrack");
+    for (name, pattern) in cases {
+        let re = Regex::new(pattern).unwrap();
+        group.bench_function(*name, |b| {
+            b.iter(|| black_box(re.find_iter(black_box(SOURCE)).count()));
+        });
+    }
+    group.finish();
+}
+
+// ---------------------------------------------------------------------------
+// 3. css -- long literal keywords (string opcodes and exact-string search)
+// ---------------------------------------------------------------------------
+
+fn make_css_text(num_rules: usize) -> String {
+    let mut text = String::new();
+    for i in 0..num_rules {
+        text.push_str(&format!(
+            ".item-{i} > .content {{ background-color: #{:06x}; border-radius: {}px; \
+             text-decoration: none; transition-timing-function: ease-in-out; }}\n",
+            i * 2654435 % 0xffffff,
+            i % 12,
+        ));
+    }
+    text
+}
+
+fn bench_css(c: &mut Criterion) {
+    let text = make_css_text(200);
+    let cases: &[(&str, &str)] = &[
+        (
+            "keywords",
+            r"\b(?:background-color|border-radius|text-decoration|transition-timing-function)\b",
+        ),
+        ("exact", r"transition-timing-function:\s*(\w+(?:-\w+)*)"),
+        ("rare_exact", r"grid-template-columns:\s*\w+"),
+    ];
+
+    let mut group = c.benchmark_group("css");
+    for (name, pattern) in cases {
+        let re = Regex::new(pattern).unwrap();
+        group.bench_function(*name, |b| {
+            b.iter(|| black_box(re.find_iter(black_box(&text)).count()));
+        });
+    }
+    group.finish();
+}
+
+// ---------------------------------------------------------------------------
+// 4. groups -- TypeScript-grammar-style rules with many capture groups
+// ---------------------------------------------------------------------------
+
+/// TypeScript keywords, one capture group each: 60 groups whose region is
+/// reset on every search.
+const TS_KEYWORDS: &str =
+    "abstract any as async await boolean break case catch class const constructor \
+     continue declare default delete do else enum export extends false finally for \
+     from function get if implements import in instanceof interface keyof let \
+     module namespace never new null number private protected public readonly \
+     return set static string super switch this throw true try type typeof \
+     undefined var void";
+
+fn bench_groups(c: &mut Criterion) {
+    let keywords = TS_KEYWORDS
+        .split_whitespace()
+        .map(|k| format!("({k})"))
+        .collect::<Vec<_>>()
+        .join("|");
+    let patterns = [
+        format!(r"\b(?:{keywords})\b"),
+        r"[A-Za-z_$][\w$]*".to_string(),
+        r"[{}()\[\];,.]".to_string(),
+    ];
+    let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+    let mut scanner = Scanner::new(&patterns).unwrap();
+
+    let mut group = c.benchmark_group("groups");
+    group.bench_function("ts_keywords_60", |b| {
+        b.iter(|| black_box(tokenize(&mut scanner, black_box(SOURCE))));
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_grammar,
+    bench_backtrack,
+    bench_css,
+    bench_groups
+);
+criterion_main!(benches);
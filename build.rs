@@ -1,15 +1,54 @@
-// build.rs -- Compile C Oniguruma from submodule (gated on `ffi` feature)
+// build.rs -- Compile C Oniguruma from submodule (gated on `c-oniguruma`
+// feature), or link the system libonig when the submodule is not checked out
 
 fn main() {
     // Allow the `coverage_nightly` cfg used by #[cfg_attr(coverage_nightly, coverage(off))].
     // This silences "unexpected cfg" warnings on stable while activating on nightly+coverage.
     println!("cargo::rustc-check-cfg=cfg(coverage_nightly)");
+    // Set when `c-oniguruma` links the system libonig instead of the submodule.
+    println!("cargo::rustc-check-cfg=cfg(onig_system)");
+    // Set when `ffi` exports the onig_* symbols under their C names.
+    // `c-oniguruma` links C Oniguruma, which defines the same names, so with
+    // both features the C ABI is compiled but only callable from Rust.
+    println!("cargo::rustc-check-cfg=cfg(ffi_export)");
+    // Set when sys.rs can use std's Mutex/RwLock: with `std`, except on
+    // wasm without threads, where the registries use the core-atomics
+    // locks instead of relying on std's threadless stand-ins.
+    println!("cargo::rustc-check-cfg=cfg(std_locks)");
+    let wasm_threads = std::env::var("CARGO_CFG_TARGET_FEATURE")
+        .is_ok_and(|f| f.split(',').any(|f| f == "atomics"));
+    let wasm =
+        std::env::var("CARGO_CFG_TARGET_FAMILY").is_ok_and(|f| f.split(',').any(|f| f == "wasm"));
+    if std::env::var_os("CARGO_FEATURE_STD").is_some() && (!wasm || wasm_threads) {
+        println!("cargo::rustc-cfg=std_locks");
+    }
+    if std::env::var_os("CARGO_FEATURE_FFI").is_some()
+        && std::env::var_os("CARGO_FEATURE_C_ONIGURUMA").is_none()
+    {
+        println!("cargo::rustc-cfg=ffi_export");
+    }
+
+    #[cfg(feature = "c-oniguruma")]
+    if std::path::Path::new("oniguruma-orig/src/regcomp.c").exists() {
+        build_oniguruma_c();
+    } else {
+        link_system_oniguruma();
+    }
+}
 
-    #[cfg(feature = "ffi")]
-    build_oniguruma_c();
+/// Without the submodule only the parity harness (src/parity.rs) is usable:
+/// the vscode scanner wrapper needs the C headers, so onig_bench does not
+/// build. Set ONIG_LIB_DIR if libonig is not on the default search path.
+#[cfg(feature = "c-oniguruma")]
+fn link_system_oniguruma() {
+    if let Ok(dir) = std::env::var("ONIG_LIB_DIR") {
+        println!("cargo::rustc-link-search=native={dir}");
+    }
+    println!("cargo::rustc-link-lib=dylib=onig");
+    println!("cargo::rustc-cfg=onig_system");
 }
 
-#[cfg(feature = "ffi")]
+#[cfg(feature = "c-oniguruma")]
 fn build_oniguruma_c() {
     use std::env;
     use std::path::PathBuf;
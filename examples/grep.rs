@@ -0,0 +1,194 @@
+// examples/grep.rs - A line-oriented grep loop.
+//
+// Each line is searched on its own, without its terminator, so `^`, `$`,
+// `\A` and `\z` anchor to the line and no match runs into the next line.
+// Searching the whole buffer and mapping matches back to lines looks
+// cheaper, but `\A` then only matches on the first line and a pattern such
+// as `a\s+b` reports lines that do not match on their own.
+//
+// A reference for embedders, built and tested with the crate; copy it as a
+// starting point. Run with:
+//
+//     cargo run --example grep -- PATTERN [FILE]
+//
+// Prints each matching line of FILE (or stdin) as `number:offset:line`,
+// followed by the byte ranges of its matches.
+
+use std::io::Read;
+use std::ops::Range;
+use std::process::ExitCode;
+
+use ferroni::api::Regex;
+
+/// A line with at least one match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineMatch<'t> {
+    /// 1-based line number.
+    pub number: usize,
+    /// Byte offset of the start of the line in the haystack.
+    pub offset: usize,
+    /// The line without its `\n` or `\r\n` terminator.
+    pub line: &'t [u8],
+    /// Byte ranges of the matches, relative to the start of `line`.
+    pub matches: Vec<Range<usize>>,
+}
+
+/// Iterator over the matching lines of a haystack, created by [`grep`].
+pub struct Grep<'r, 't> {
+    regex: &'r Regex,
+    text: &'t [u8],
+    pos: usize,
+    number: usize,
+}
+
+/// Return the lines of `text` that `regex` matches, in order.
+///
+/// A final line without a terminator is searched as well; the empty string
+/// after a final terminator is not a line.
+pub fn grep<'r, 't>(regex: &'r Regex, text: &'t [u8]) -> Grep<'r, 't> {
+    Grep {
+        regex,
+        text,
+        pos: 0,
+        number: 0,
+    }
+}
+
+impl<'r, 't> Iterator for Grep<'r, 't> {
+    type Item = LineMatch<'t>;
+
+    fn next(&mut self) -> Option<LineMatch<'t>> {
+        while self.pos < self.text.len() {
+            let offset = self.pos;
+            let rest = &self.text[offset..];
+            let len = memchr::memchr(b'\n', rest).unwrap_or(rest.len());
+            self.pos = offset + len + 1;
+            self.number += 1;
+
+            let line = &rest[..len];
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            // FindIter steps over empty matches, so `^` or `x*` report each
+            // line once instead of looping.
+            let matches: Vec<_> = self
+                .regex
+                .find_iter_bytes(line)
+                .map(|m| m.range())
+                .collect();
+            if !matches.is_empty() {
+                return Some(LineMatch {
+                    number: self.number,
+                    offset,
+                    line,
+                    matches,
+                });
+            }
+        }
+        None
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (pattern, path) = match args.as_slice() {
+        [pattern] => (pattern, None),
+        [pattern, path] => (pattern, Some(path)),
+        _ => {
+            eprintln!("usage: grep PATTERN [FILE]");
+            return ExitCode::from(2);
+        }
+    };
+    let re = match Regex::new(pattern) {
+        Ok(re) => re,
+        Err(e) => {
+            eprintln!("grep: {e}");
+            return ExitCode::from(2);
+        }
+    };
+    let text = match path {
+        Some(path) => std::fs::read(path),
+        None => {
+            let mut text = Vec::new();
+            std::io::stdin().read_to_end(&mut text).map(|_| text)
+        }
+    };
+    let text = match text {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("grep: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut found = false;
+    for hit in grep(&re, &text) {
+        found = true;
+        println!(
+            "{}:{}:{} {:?}",
+            hit.number,
+            hit.offset,
+            String::from_utf8_lossy(hit.line),
+            hit.matches
+        );
+    }
+    if found {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_and_numbers() {
+        let re = Regex::new(r"^\w+:").unwrap();
+        let text = b"name: ferroni\r\n  nested: no\nversion: 1";
+        let hits: Vec<_> = grep(&re, text).map(|l| (l.number, l.line)).collect();
+        assert_eq!(hits, [(1, &b"name: ferroni"[..]), (3, &b"version: 1"[..])]);
+        let offsets: Vec<_> = grep(&re, text).map(|l| l.offset).collect();
+        assert_eq!(offsets, [0, 28]);
+    }
+
+    fn lines(pattern: &str, text: &str) -> Vec<(usize, String, Vec<Range<usize>>)> {
+        let re = Regex::new(pattern).unwrap();
+        grep(&re, text.as_bytes())
+            .map(|l| {
+                (
+                    l.number,
+                    String::from_utf8_lossy(l.line).into_owned(),
+                    l.matches,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn anchors_are_per_line() {
+        let text = "ab\nba\nab";
+        assert_eq!(
+            lines(r"\Aa", text),
+            [(1, "ab".into(), vec![0..1]), (3, "ab".into(), vec![0..1])]
+        );
+        assert_eq!(lines(r"a\z", text), [(2, "ba".into(), vec![1..2])]);
+        assert_eq!(lines(r"b$", "ab\r\nb\r\n").len(), 2);
+    }
+
+    #[test]
+    fn matches_do_not_cross_lines() {
+        assert!(lines(r"a\s+b", "a\nb\n").is_empty());
+    }
+
+    #[test]
+    fn empty_matches_and_lines() {
+        assert_eq!(
+            lines(r"x*", "\nyxy\n"),
+            [
+                (1, "".into(), vec![0..0]),
+                (2, "yxy".into(), vec![0..0, 1..2, 2..2, 3..3]),
+            ]
+        );
+        assert!(lines(r"x*", "").is_empty());
+    }
+}
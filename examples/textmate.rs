@@ -0,0 +1,486 @@
+// examples/textmate.rs - A minimal begin/end TextMate tokenizer.
+//
+// Covers `match` rules and `begin`/`end` rules with nested `patterns`, the
+// core of every TextMate grammar; captures, `include` and `while` rules
+// are left out. Each rule context gets one Scanner holding the `end`
+// pattern of the open rule first and its nested rules after it, so an
+// `end` wins over a nested rule matching at the same position, as in
+// vscode-textmate.
+//
+// The parts that are easy to get wrong:
+//
+// - Lines are searched with a `\n` appended, so `$` and `\n` in patterns
+//   see the line end, and the resulting tokens are clipped to the line.
+// - `\A` only matches on the first line: later lines are searched with
+//   `ScannerFindOptions::NOT_BEGIN_STRING`.
+// - `\G` only matches where the last `begin` match ended, or at the start
+//   of each line inside a rule whose `begin` consumed the line end. Everywhere
+//   else the search runs with `NOT_BEGIN_POSITION`, since the scanner
+//   would otherwise anchor `\G` at every search start.
+// - A match that consumes nothing and does not change the rule stack, or a
+//   `begin` that consumes nothing where the previous empty `begin` did,
+//   would be found again at the same position forever; the search then
+//   steps over one character instead.
+//
+// A reference for embedders, built and tested with the crate; copy it as a
+// starting point. Run with:
+//
+//     cargo run --example textmate -- [FILE]
+//
+// Tokenizes FILE (or a built-in sample) with a small demo grammar and
+// prints each token with its scopes.
+
+use std::ops::Range;
+
+use ferroni::error::RegexError;
+use ferroni::scanner::{Scanner, ScannerFindOptions};
+
+/// A grammar rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// Text matched by `pattern` gets `scope`.
+    Match { scope: String, pattern: String },
+    /// Text from a `begin` match through the next `end` match gets `scope`,
+    /// and only `patterns` apply inside it. The region may span lines.
+    BeginEnd {
+        scope: String,
+        begin: String,
+        end: String,
+        patterns: Vec<Rule>,
+    },
+}
+
+/// A run of text and the scopes that apply to it, outermost first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// Byte range in the line.
+    pub range: Range<usize>,
+    pub scopes: Vec<String>,
+}
+
+/// Tokenizer state carried from one line to the next. Start a document
+/// with `LineState::default()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineState {
+    /// Open `begin`/`end` rules, innermost last.
+    stack: Vec<Frame>,
+    /// Whether a line was tokenized already (for `\A`).
+    started: bool,
+}
+
+impl LineState {
+    /// Number of `begin`/`end` rules still open.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Frame {
+    context: usize,
+    /// The `begin` match ended at the line end, so `\G` matches at the
+    /// start of each following line.
+    begin_captured_eol: bool,
+}
+
+/// What a scanner pattern of a context stands for.
+enum Target {
+    End,
+    Match(String),
+    Begin(usize),
+}
+
+struct Context {
+    scope: String,
+    scanner: Scanner,
+    targets: Vec<Target>,
+}
+
+/// A compiled grammar.
+pub struct Grammar {
+    contexts: Vec<Context>,
+    root: usize,
+}
+
+impl Grammar {
+    /// Compile a grammar whose top-level rules are `patterns`.
+    pub fn new(scope: &str, patterns: &[Rule]) -> Result<Grammar, RegexError> {
+        let mut contexts = Vec::new();
+        let root = add_context(&mut contexts, scope, None, patterns)?;
+        Ok(Grammar { contexts, root })
+    }
+
+    /// Tokenize one line (without its terminator), continuing from `state`
+    /// and updating it for the next line. Tokens cover the whole line, in
+    /// order, and are never empty.
+    pub fn tokenize_line(&mut self, line: &str, state: &mut LineState) -> Vec<Token> {
+        let text = format!("{line}\n");
+        let len = line.len();
+        let options = if state.started {
+            ScannerFindOptions::NOT_BEGIN_STRING
+        } else {
+            ScannerFindOptions::NONE
+        };
+        state.started = true;
+
+        let mut tokens = Vec::new();
+        let mut emitted = 0;
+        let mut pos = 0;
+        let mut anchor = state
+            .stack
+            .last()
+            .is_some_and(|f| f.begin_captured_eol)
+            .then_some(0);
+        let mut empty_begin_at = None;
+
+        while pos <= len {
+            let context = state.stack.last().map_or(self.root, |f| f.context);
+            let mut find = options;
+            if anchor != Some(pos) {
+                find |= ScannerFindOptions::NOT_BEGIN_POSITION;
+            }
+            let Some((index, region)) = self.contexts[context]
+                .scanner
+                .find_next_match_region(&text, pos, find)
+            else {
+                break;
+            };
+            let m = region.get(0).expect("group 0 of a match is set");
+            let (start, end) = (m.start.min(len), m.end.min(len));
+
+            let target = &self.contexts[context].targets[index];
+            let stuck = m.is_empty()
+                && match target {
+                    Target::End => false,
+                    Target::Match(_) => true,
+                    Target::Begin(_) => empty_begin_at == Some(m.start),
+                };
+            if stuck {
+                // Step over one character; it becomes part of the next gap.
+                pos = m.start + text[m.start..].chars().next().map_or(1, char::len_utf8);
+                continue;
+            }
+
+            self.push_token(&mut tokens, emitted..start, &state.stack);
+            match *target {
+                Target::End => {
+                    self.push_token(&mut tokens, start..end, &state.stack);
+                    state.stack.pop();
+                }
+                Target::Match(ref scope) => {
+                    let mut scopes = self.scopes(&state.stack);
+                    scopes.push(scope.clone());
+                    push(&mut tokens, start..end, scopes);
+                }
+                Target::Begin(inner) => {
+                    state.stack.push(Frame {
+                        context: inner,
+                        begin_captured_eol: m.end == text.len(),
+                    });
+                    self.push_token(&mut tokens, start..end, &state.stack);
+                    empty_begin_at = m.is_empty().then_some(m.start);
+                    anchor = Some(m.end);
+                }
+            }
+            emitted = end;
+            pos = m.end;
+        }
+
+        self.push_token(&mut tokens, emitted..len, &state.stack);
+        tokens
+    }
+
+    /// Scopes of the rule contexts on `stack`, outermost first.
+    fn scopes(&self, stack: &[Frame]) -> Vec<String> {
+        core::iter::once(self.root)
+            .chain(stack.iter().map(|f| f.context))
+            .map(|c| self.contexts[c].scope.clone())
+            .collect()
+    }
+
+    fn push_token(&self, tokens: &mut Vec<Token>, range: Range<usize>, stack: &[Frame]) {
+        if !range.is_empty() {
+            push(tokens, range, self.scopes(stack));
+        }
+    }
+}
+
+/// Append a token, extending the last one if it ends at `range` with the
+/// same scopes.
+fn push(tokens: &mut Vec<Token>, range: Range<usize>, scopes: Vec<String>) {
+    if range.is_empty() {
+        return;
+    }
+    match tokens.last_mut() {
+        Some(last) if last.range.end == range.start && last.scopes == scopes => {
+            last.range.end = range.end;
+        }
+        _ => tokens.push(Token { range, scopes }),
+    }
+}
+
+/// Compile the context for a rule with `end` (none for the grammar root)
+/// and nested `patterns`, after the contexts of its nested rules. Returns
+/// its index in `contexts`.
+fn add_context(
+    contexts: &mut Vec<Context>,
+    scope: &str,
+    end: Option<&str>,
+    patterns: &[Rule],
+) -> Result<usize, RegexError> {
+    let mut sources = Vec::new();
+    let mut targets = Vec::new();
+    if let Some(end) = end {
+        sources.push(end);
+        targets.push(Target::End);
+    }
+    for rule in patterns {
+        match rule {
+            Rule::Match { scope, pattern } => {
+                sources.push(pattern);
+                targets.push(Target::Match(scope.clone()));
+            }
+            Rule::BeginEnd {
+                scope,
+                begin,
+                end,
+                patterns,
+            } => {
+                let inner = add_context(contexts, scope, Some(end), patterns)?;
+                sources.push(begin);
+                targets.push(Target::Begin(inner));
+            }
+        }
+    }
+
+    contexts.push(Context {
+        scope: scope.to_string(),
+        scanner: Scanner::new(&sources)?,
+        targets,
+    });
+    Ok(contexts.len() - 1)
+}
+
+/// Keywords, and strings with escapes that may span lines.
+fn demo_grammar() -> Grammar {
+    Grammar::new(
+        "source.demo",
+        &[
+            Rule::Match {
+                scope: "keyword".into(),
+                pattern: r"\blet\b".into(),
+            },
+            Rule::BeginEnd {
+                scope: "string".into(),
+                begin: "\"".into(),
+                end: "\"".into(),
+                patterns: vec![Rule::Match {
+                    scope: "escape".into(),
+                    pattern: r"\\.".into(),
+                }],
+            },
+        ],
+    )
+    .expect("the demo grammar compiles")
+}
+
+const SAMPLE: &str = "let s = \"a\\\"b\nc\" let\n";
+
+fn main() -> std::io::Result<()> {
+    let text = match std::env::args().nth(1) {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => SAMPLE.to_string(),
+    };
+    let mut grammar = demo_grammar();
+    let mut state = LineState::default();
+    for (number, line) in text.lines().enumerate() {
+        println!("{}: {line}", number + 1);
+        for token in grammar.tokenize_line(line, &mut state) {
+            println!(
+                "  {:?} {:?} {}",
+                token.range.clone(),
+                &line[token.range],
+                token.scopes.join(" ")
+            );
+        }
+    }
+    if state.depth() > 0 {
+        println!("{} rule(s) still open at the end", state.depth());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn demo_grammar_across_lines() {
+        let mut grammar = demo_grammar();
+        let mut state = LineState::default();
+        let line = r#"let s = "a\"b"#;
+        let tokens = grammar.tokenize_line(line, &mut state);
+        let scoped: Vec<_> = tokens
+            .iter()
+            .map(|t| (&line[t.range.clone()], t.scopes.last().unwrap().as_str()))
+            .collect();
+        assert_eq!(
+            scoped,
+            [
+                ("let", "keyword"),
+                (" s = ", "source.demo"),
+                ("\"a", "string"),
+                ("\\\"", "escape"),
+                ("b", "string"),
+            ]
+        );
+
+        // The string is still open on the next line.
+        assert_eq!(state.depth(), 1);
+        let tokens = grammar.tokenize_line(r#"c" let"#, &mut state);
+        assert_eq!(tokens[0].scopes, ["source.demo", "string"]);
+        assert_eq!(tokens[0].range, 0..2);
+        assert_eq!(state.depth(), 0);
+    }
+
+    fn rule(scope: &str, pattern: &str) -> Rule {
+        Rule::Match {
+            scope: scope.into(),
+            pattern: pattern.into(),
+        }
+    }
+
+    fn begin_end(scope: &str, begin: &str, end: &str, patterns: Vec<Rule>) -> Rule {
+        Rule::BeginEnd {
+            scope: scope.into(),
+            begin: begin.into(),
+            end: end.into(),
+            patterns,
+        }
+    }
+
+    /// Each token as its text and innermost scope.
+    fn tokenize<'l>(
+        grammar: &mut Grammar,
+        state: &mut LineState,
+        line: &'l str,
+    ) -> Vec<(&'l str, String)> {
+        grammar
+            .tokenize_line(line, state)
+            .into_iter()
+            .map(|t| (&line[t.range], t.scopes.last().unwrap().clone()))
+            .collect()
+    }
+
+    fn owned<'a>(tokens: &[(&'a str, &str)]) -> Vec<(&'a str, String)> {
+        tokens.iter().map(|&(t, s)| (t, s.to_string())).collect()
+    }
+
+    #[test]
+    fn line_comment_ends_at_line_end() {
+        let mut g = Grammar::new("src", &[begin_end("comment", "#", "$", vec![])]).unwrap();
+        let mut state = LineState::default();
+        assert_eq!(
+            tokenize(&mut g, &mut state, "a # b"),
+            owned(&[("a ", "src"), ("# b", "comment")])
+        );
+        assert_eq!(state.depth(), 0);
+    }
+
+    #[test]
+    fn string_anchor_only_on_first_line() {
+        let mut g = Grammar::new("src", &[rule("shebang", r"\A#!.*")]).unwrap();
+        let mut state = LineState::default();
+        assert_eq!(
+            tokenize(&mut g, &mut state, "#!sh"),
+            owned(&[("#!sh", "shebang")])
+        );
+        assert_eq!(
+            tokenize(&mut g, &mut state, "#!sh"),
+            owned(&[("#!sh", "src")])
+        );
+    }
+
+    #[test]
+    fn g_anchor_only_after_begin() {
+        let mut g = Grammar::new(
+            "src",
+            &[begin_end(
+                "call",
+                r"\w+(?=\()",
+                r"\)",
+                vec![rule("paren", r"\G\("), rule("punct", r"\(")],
+            )],
+        )
+        .unwrap();
+        let mut state = LineState::default();
+        assert_eq!(
+            tokenize(&mut g, &mut state, "f((x"),
+            owned(&[("f", "call"), ("(", "paren"), ("(", "punct"), ("x", "call")])
+        );
+        assert_eq!(state.depth(), 1);
+        // `\G` does not match at the start of a continuation line.
+        assert_eq!(
+            tokenize(&mut g, &mut state, "()"),
+            owned(&[("(", "punct"), (")", "call")])
+        );
+        assert_eq!(state.depth(), 0);
+    }
+
+    #[test]
+    fn g_anchor_after_begin_consumed_line_end() {
+        let mut g = Grammar::new(
+            "src",
+            &[begin_end(
+                "block",
+                r":\n",
+                r"^end",
+                vec![rule("first", r"\G\w+")],
+            )],
+        )
+        .unwrap();
+        let mut state = LineState::default();
+        assert_eq!(
+            tokenize(&mut g, &mut state, "x:"),
+            owned(&[("x", "src"), (":", "block")])
+        );
+        assert_eq!(
+            tokenize(&mut g, &mut state, "a b"),
+            owned(&[("a", "first"), (" b", "block")])
+        );
+        assert_eq!(
+            tokenize(&mut g, &mut state, "end"),
+            owned(&[("end", "block")])
+        );
+        assert_eq!(state.depth(), 0);
+    }
+
+    #[test]
+    fn empty_matches_do_not_loop() {
+        let mut g = Grammar::new(
+            "src",
+            &[
+                rule("empty", r"x*"),
+                begin_end("nested", r"(?=y)", r"(?=y)", vec![]),
+            ],
+        )
+        .unwrap();
+        let mut state = LineState::default();
+        assert_eq!(
+            tokenize(&mut g, &mut state, "axxy"),
+            owned(&[("a", "src"), ("xx", "empty"), ("y", "src")])
+        );
+        assert_eq!(state.depth(), 0);
+    }
+
+    #[test]
+    fn empty_grammar() {
+        let mut g = Grammar::new("src", &[]).unwrap();
+        let mut state = LineState::default();
+        assert_eq!(
+            tokenize(&mut g, &mut state, "abc"),
+            owned(&[("abc", "src")])
+        );
+        assert!(tokenize(&mut g, &mut state, "").is_empty());
+    }
+}
@@ -0,0 +1,52 @@
+// Parse and compile arbitrary patterns, then search with the result.
+//
+// Input: syntax byte, options byte, pattern (up to NUL), text. Any panic,
+// overflow or hang is a bug: invalid patterns must come back as errors.
+//
+// Run: cargo +nightly fuzz run compile
+
+#![no_main]
+
+use ferroni::encodings::utf8::ONIG_ENCODING_UTF8;
+use ferroni::oniguruma::*;
+use ferroni::regcomp::onig_new;
+use ferroni::regexec::onig_search;
+use ferroni_fuzz::{options, utf8, Input, MAX_PATTERN_LEN, MAX_TEXT_LEN, SYNTAXES};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut input = Input::new(data);
+    let syntax = SYNTAXES[input.byte() as usize % SYNTAXES.len()];
+    let options = options(input.byte());
+    let pattern = input.field(MAX_PATTERN_LEN);
+    let text = utf8(input.rest(MAX_TEXT_LEN));
+
+    let Ok(reg) = onig_new(pattern, options, &ONIG_ENCODING_UTF8, syntax) else {
+        return;
+    };
+    let (r, region) = onig_search(
+        &reg,
+        &text,
+        text.len(),
+        0,
+        text.len(),
+        Some(OnigRegion::new()),
+        ONIG_OPTION_NONE,
+    );
+    if r < 0 {
+        return;
+    }
+    // `\K` moves the start of group 0 away from the return value, so only
+    // check that every group lies inside the text.
+    let region = region.unwrap();
+    let in_text = |pos: i32| 0 <= pos && pos as usize <= text.len();
+    for i in 0..region.num_regs as usize {
+        let (beg, end) = (region.beg(i), region.end(i));
+        assert!(
+            (beg == ONIG_REGION_NOTPOS && end == ONIG_REGION_NOTPOS)
+                || (in_text(beg) && in_text(end)),
+            "group {i} has range {beg}..{end} in a text of {} bytes",
+            text.len()
+        );
+    }
+});
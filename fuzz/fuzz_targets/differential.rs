@@ -0,0 +1,40 @@
+// Differential execution: Ferroni vs C Oniguruma on the same pattern and
+// text, compared with `ferroni::parity::check`.
+//
+// Input: mode byte, options byte, then either a `testgen` seed and
+// feature byte (even mode: patterns Ferroni is known to compile, to reach
+// the matcher quickly) or a pattern up to NUL (odd mode: any pattern, to
+// also compare compile errors), then the text. Any difference in compile
+// error codes, match positions or capture groups panics with the case.
+//
+// Run: cargo +nightly fuzz run --features c-oniguruma differential
+
+#![no_main]
+
+use ferroni::parity::{check, Case};
+use ferroni::testgen::{random_pattern, Features};
+use ferroni_fuzz::{options, utf8, Input, MAX_PATTERN_LEN, MAX_TEXT_LEN};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut input = Input::new(data);
+    let mode = input.byte();
+    let options = options(input.byte());
+    let pattern = if mode & 1 == 0 {
+        let seed = input.u64();
+        let features = Features::from_bits_truncate(u32::from(input.byte()));
+        random_pattern(seed, features).into_bytes()
+    } else {
+        utf8(input.field(MAX_PATTERN_LEN))
+    };
+    let text = utf8(input.rest(MAX_TEXT_LEN));
+
+    let case = Case::new("fuzz", &pattern, &text).options(options);
+    let outcome = check(&case);
+    assert!(
+        outcome.is_parity(),
+        "engines differ\n  pattern: {:?}\n  options: {options:?}\n  text:    {:?}\n  {outcome:?}",
+        String::from_utf8_lossy(&pattern),
+        String::from_utf8_lossy(&text),
+    );
+});
@@ -0,0 +1,96 @@
+// Input decoding shared by the fuzz targets.
+//
+// Every target turns the fuzzer's bytes into a pattern, options and text
+// the same way on every run and platform, so a saved artifact reproduces
+// its failure exactly.
+
+use ferroni::oniguruma::*;
+use ferroni::regsyntax::*;
+
+/// Patterns and texts longer than this are cut, to keep each run fast.
+pub const MAX_PATTERN_LEN: usize = 256;
+pub const MAX_TEXT_LEN: usize = 1024;
+
+/// Compile options selected by the bits of one input byte.
+const OPTIONS: [OnigOptionType; 8] = [
+    ONIG_OPTION_IGNORECASE,
+    ONIG_OPTION_EXTEND,
+    ONIG_OPTION_MULTILINE,
+    ONIG_OPTION_SINGLELINE,
+    ONIG_OPTION_FIND_LONGEST,
+    ONIG_OPTION_FIND_NOT_EMPTY,
+    ONIG_OPTION_NEGATE_SINGLELINE,
+    ONIG_OPTION_IGNORECASE_IS_ASCII,
+];
+
+/// Syntaxes selected by one input byte.
+pub const SYNTAXES: [&OnigSyntaxType; 12] = [
+    &OnigSyntaxOniguruma,
+    &OnigSyntaxRuby,
+    &OnigSyntaxPerl,
+    &OnigSyntaxPerl_NG,
+    &OnigSyntaxJava,
+    &OnigSyntaxPython,
+    &OnigSyntaxPosixBasic,
+    &OnigSyntaxPosixExtended,
+    &OnigSyntaxEmacs,
+    &OnigSyntaxGrep,
+    &OnigSyntaxGnuRegex,
+    &OnigSyntaxASIS,
+];
+
+/// The options whose bits are set in `bits`.
+pub fn options(bits: u8) -> OnigOptionType {
+    OPTIONS
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| bits & (1 << i) != 0)
+        .fold(ONIG_OPTION_NONE, |acc, (_, &o)| acc | o)
+}
+
+/// Reads the fuzzer's bytes front to back; reads past the end give zeros.
+pub struct Input<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Input<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Input { data }
+    }
+
+    pub fn byte(&mut self) -> u8 {
+        let (&b, rest) = self.data.split_first().unwrap_or((&0, &[]));
+        self.data = rest;
+        b
+    }
+
+    pub fn u64(&mut self) -> u64 {
+        (0..8).fold(0, |acc, i| acc | u64::from(self.byte()) << (8 * i))
+    }
+
+    /// Bytes up to the next NUL (dropped), at most `max` of them.
+    pub fn field(&mut self, max: usize) -> &'a [u8] {
+        let end = self
+            .data
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap_or(self.data.len());
+        let (field, rest) = self.data.split_at(end);
+        self.data = rest.get(1..).unwrap_or(&[]);
+        &field[..field.len().min(max)]
+    }
+
+    /// The remaining bytes, at most `max` of them.
+    pub fn rest(&mut self, max: usize) -> &'a [u8] {
+        let rest = &self.data[..self.data.len().min(max)];
+        self.data = &[];
+        rest
+    }
+}
+
+/// `bytes` as valid UTF-8, invalid sequences replaced by U+FFFD. Both
+/// engines assume valid UTF-8, so invalid text would only report
+/// differences in undefined behaviour.
+pub fn utf8(bytes: &[u8]) -> Vec<u8> {
+    String::from_utf8_lossy(bytes).into_owned().into_bytes()
+}
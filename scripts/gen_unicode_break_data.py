@@ -0,0 +1,111 @@
+#!/usr/bin/env python3
+"""
+Generate src/unicode/egcb_data.rs and src/unicode/wb_data.rs from
+oniguruma-orig/src/unicode_egcb_data.c and unicode_wb_data.c
+
+Extracts the EGCB_RANGE[] / WB_RANGE[] tables ({start, end, PREFIX_Type}
+entries) and emits them as sorted Rust range arrays.
+"""
+
+import re
+import sys
+import os
+
+SCRIPT_DIR = os.path.dirname(os.path.abspath(__file__))
+ROOT_DIR = os.path.dirname(SCRIPT_DIR)
+SRC = os.path.join(ROOT_DIR, "oniguruma-orig", "src")
+OUT_DIR = os.path.join(ROOT_DIR, "src", "unicode")
+
+# (C file, Rust file, C prefix, Rust type prefix, title, values, hex format)
+# Enum values match the C enums; 9-12 are unused in EGCB.
+TABLES = [
+    (
+        "unicode_egcb_data.c", "egcb_data.rs", "EGCB", "Egcb",
+        "Extended Grapheme Cluster Break data table",
+        ["Other", "CR", "LF", "Control", "Extend", "Prepend", "RegionalIndicator",
+         "SpacingMark", "ZWJ", None, None, None, None, "L", "LV", "LVT", "T", "V"],
+        "0x{:06x}",
+    ),
+    (
+        "unicode_wb_data.c", "wb_data.rs", "WB", "Wb",
+        "Word Break data table",
+        ["Any", "ALetter", "CR", "DoubleQuote", "Extend", "ExtendNumLet", "Format",
+         "HebrewLetter", "Katakana", "LF", "MidLetter", "MidNum", "MidNumLet",
+         "Newline", "Numeric", "RegionalIndicator", "SingleQuote", "WSegSpace", "ZWJ"],
+        "0x{:06X}",
+    ),
+]
+
+
+def parse_ranges(text, prefix):
+    """Parse {0xSTART, 0xEND, PREFIX_Name} entries into (start, end, Name)."""
+    entries = []
+    pattern = re.compile(
+        rf'\{{\s*0x([0-9a-fA-F]+)\s*,\s*0x([0-9a-fA-F]+)\s*,\s*{prefix}_(\w+)\s*\}}'
+    )
+    for m in pattern.finditer(text):
+        name = m.group(3).replace("_", "")
+        entries.append((int(m.group(1), 16), int(m.group(2), 16), name))
+    entries.sort()
+    for (_, end, _), (start, _, _) in zip(entries, entries[1:]):
+        assert end < start, f"{prefix}: overlapping ranges at 0x{start:x}"
+    return entries
+
+
+def generate_rust(rs_file, c_file, ty, title, values, hexfmt, entries):
+    lines = []
+    lines.append(f"// {rs_file} - {title}")
+    lines.append(f"// Ported from {c_file}")
+    lines.append("")
+    lines.append("#[derive(Debug, Clone, Copy, PartialEq, Eq)]")
+    lines.append("#[repr(u8)]")
+    lines.append(f"pub enum {ty}Type {{")
+    for i, v in enumerate(values):
+        if v is not None:
+            lines.append(f"    {v} = {i},")
+        elif i == 0 or values[i - 1] is not None:
+            last = i
+            while values[last + 1] is None:
+                last += 1
+            lines.append(f"    // {i}-{last} obsoleted")
+    lines.append("}")
+    lines.append("")
+    lines.append(f"pub struct {ty}Range {{")
+    lines.append("    pub start: u32,")
+    lines.append("    pub end: u32,")
+    lines.append(f"    pub prop: {ty}Type,")
+    lines.append("}")
+    lines.append("")
+    lines.append(f"pub static {ty.upper()}_RANGES: [{ty}Range; {len(entries)}] = [")
+    for start, end, name in entries:
+        if name not in values:
+            raise ValueError(f"{c_file}: unknown type {name}")
+        lines.append(f"    {ty}Range {{")
+        lines.append(f"        start: {hexfmt.format(start)},")
+        lines.append(f"        end: {hexfmt.format(end)},")
+        lines.append(f"        prop: {ty}Type::{name},")
+        lines.append("    },")
+    lines.append("];")
+    lines.append("")
+    return "\n".join(lines)
+
+
+def main():
+    for c_file, rs_file, prefix, ty, title, values, hexfmt in TABLES:
+        path = os.path.join(SRC, c_file)
+        with open(path, 'r') as f:
+            text = f.read()
+        print(f"Parsing {path}...")
+        entries = parse_ranges(text, prefix)
+        print(f"  Found {len(entries)} ranges")
+        if not entries:
+            sys.exit(f"  ERROR: no {prefix} ranges found")
+
+        out = os.path.join(OUT_DIR, rs_file)
+        with open(out, 'w') as f:
+            f.write(generate_rust(rs_file, c_file, ty, title, values, hexfmt, entries))
+        print(f"  Written {out}")
+
+
+if __name__ == '__main__':
+    main()
@@ -7,6 +7,12 @@ Extracts:
 2. CodeRanges[] index mapping (ctype index -> CR_* name)
 3. gperf wordlist (property name -> ctype index)
 
+Script_Extensions (not in C) are computed from the UCD's Scripts.txt and
+ScriptExtensions.txt, read from $UCD_DIR (default: ucd/). They must be the
+Unicode version the C file was generated from (ONIG_UNICODE_VERSION_*),
+which is also emitted as UNICODE_VERSION. Pass a version to check that the C
+file has it. scripts/update_unicode.sh runs all the table generators.
+
 Outputs a single Rust file with static data.
 """
 
@@ -18,6 +24,7 @@ SCRIPT_DIR = os.path.dirname(os.path.abspath(__file__))
 ROOT_DIR = os.path.dirname(SCRIPT_DIR)
 C_FILE = os.path.join(ROOT_DIR, "oniguruma-orig", "src", "unicode_property_data.c")
 OUT_FILE = os.path.join(ROOT_DIR, "src", "unicode", "property_data.rs")
+UCD_DIR = os.environ.get("UCD_DIR", os.path.join(ROOT_DIR, "ucd"))
 
 
 def parse_cr_aliases(text):
@@ -101,21 +108,118 @@ def parse_wordlist(text, pool):
     return entries
 
 
+def parse_ucd_file(path):
+    """Parse a UCD data file into (start, end, value) triples."""
+    entries = []
+    with open(path, 'r') as f:
+        for line in f:
+            line = line.split('#', 1)[0].strip()
+            if not line:
+                continue
+            cps, value = [x.strip() for x in line.split(';')]
+            if '..' in cps:
+                lo, hi = cps.split('..')
+            else:
+                lo = hi = cps
+            entries.append((int(lo, 16), int(hi, 16), value))
+    return entries
+
+
+def to_ranges(cps):
+    """Sorted code points -> flat [start, end, ...] list."""
+    vals = []
+    for c in sorted(cps):
+        if vals and vals[-1] == c - 1:
+            vals[-1] = c
+        else:
+            vals += [c, c]
+    return vals
+
+
+def parse_unicode_version(text):
+    """Parse ONIG_UNICODE_VERSION_{MAJOR,MINOR,TEENY} into a tuple."""
+    version = []
+    for part in ("MAJOR", "MINOR", "TEENY"):
+        m = re.search(rf'#define\s+ONIG_UNICODE_VERSION_{part}\s+(\d+)', text)
+        if not m:
+            raise ValueError(f"Could not find ONIG_UNICODE_VERSION_{part}")
+        version.append(int(m.group(1)))
+    return tuple(version)
+
+
+def check_ucd_version(path, version):
+    """UCD files start with '# Name-X.Y.Z.txt'."""
+    with open(path, 'r') as f:
+        first = f.readline()
+    m = re.match(r'#\s*\S+-(\d+)\.(\d+)\.(\d+)\.txt', first)
+    if not m or tuple(int(x) for x in m.groups()) != version:
+        raise ValueError(f"{path} is not Unicode {'.'.join(map(str, version))}: {first!r}")
+
+
+def compute_script_extensions(arrays, aliases, code_ranges, wordlist, version):
+    """Compute Script_Extensions ranges for every script.
+
+    Returns (new_names, new_arrays, table): the CR_Scx_* arrays for scripts
+    whose extension set differs from the plain Script ranges, and the
+    (script ctype, script-extensions ctype) pairs sorted by script ctype.
+    """
+    ctype_of = dict(wordlist)
+
+    def ctype(name):
+        return ctype_of[re.sub(r'[ _-]', '', name).lower()]
+
+    for name in ("Scripts.txt", "ScriptExtensions.txt"):
+        check_ucd_version(os.path.join(UCD_DIR, name), version)
+
+    members = {ctype("Unknown"): set()}
+    for lo, hi, value in parse_ucd_file(os.path.join(UCD_DIR, "Scripts.txt")):
+        members.setdefault(ctype(value), set()).update(range(lo, hi + 1))
+    for lo, hi, value in parse_ucd_file(os.path.join(UCD_DIR, "ScriptExtensions.txt")):
+        cps = set(range(lo, hi + 1))
+        for m in members.values():
+            m -= cps
+        for name in value.split():
+            members[ctype(name)] |= cps
+
+    new_names, new_arrays, table = [], {}, []
+    for sc in sorted(members):
+        name = code_ranges[sc]
+        vals = to_ranges(members[sc])
+        if sc == ctype("Unknown") or vals == arrays[aliases.get(name, name)]:
+            table.append((sc, sc))
+            continue
+        scx_name = "CR_Scx_" + name[3:]
+        table.append((sc, len(code_ranges) + len(new_names)))
+        new_names.append(scx_name)
+        new_arrays[scx_name] = vals
+    return new_names, new_arrays, table
+
+
 def cr_name_to_rust(name):
     """Convert CR_Foo_Bar to CR_FOO_BAR for Rust const naming."""
     # Keep original case for readability but ensure valid Rust ident
     return name
 
 
-def generate_rust(arrays, aliases, code_ranges, wordlist):
+def generate_rust(arrays, aliases, code_ranges, wordlist, scx, version):
     """Generate the Rust source file."""
+    scx_names, scx_arrays, scx_table = scx
+    arrays = {**arrays, **scx_arrays}
+    code_ranges = code_ranges + scx_names
     lines = []
     lines.append("//! Auto-generated Unicode property data. Do not edit.")
     lines.append("//! Generated from oniguruma-orig/src/unicode_property_data.c")
     lines.append("//! by scripts/gen_unicode_property_data.py")
+    lines.append("//! (CR_Scx_* and SCRIPT_EXTENSIONS from the UCD's ScriptExtensions.txt)")
     lines.append("")
     lines.append("#![allow(dead_code, non_upper_case_globals)]")
     lines.append("")
+    lines.append('#[cfg(feature = "compact-tables")]')
+    lines.append("use super::pack::{pack, packed_len};")
+    lines.append("")
+    lines.append("/// Unicode version of all the tables (ONIG_UNICODE_VERSION_* in C).")
+    lines.append(f"pub const UNICODE_VERSION: (u8, u8, u8) = {version};")
+    lines.append("")
     lines.append(f"pub const CODE_RANGES_NUM: usize = {len(code_ranges)};")
     lines.append("")
 
@@ -138,7 +242,9 @@ def generate_rust(arrays, aliases, code_ranges, wordlist):
             continue
         vals = arrays[real]
         n = len(vals)
-        lines.append(f"static {real}: [u32; {n}] = [")
+        # const, not static: with compact-tables only the packed form below
+        # ends up in the binary.
+        lines.append(f"const {real}: [u32; {n}] = [")
         # Format: 6 values per line
         for i in range(0, n, 6):
             chunk = vals[i:i+6]
@@ -150,10 +256,27 @@ def generate_rust(arrays, aliases, code_ranges, wordlist):
     # Emit CODE_RANGES index
     # For aliases, reference the target array
     lines.append("// --- Index: ctype -> code ranges ---")
+    lines.append('#[cfg(not(feature = "compact-tables"))]')
     lines.append(f"pub static CODE_RANGES: [&[u32]; {len(code_ranges)}] = [")
     for i, name in enumerate(code_ranges):
         real = aliases.get(name, name)
-        lines.append(f"    &{real},  // {i}: {name}")
+        lines.append(f"    &{real}, // {i}: {name}")
+    lines.append("];")
+    lines.append("")
+    lines.append('#[cfg(feature = "compact-tables")]')
+    lines.append("macro_rules! packed {")
+    lines.append("    ($cr:ident) => {{")
+    lines.append("        const P: [u8; packed_len(&$cr)] = pack(&$cr);")
+    lines.append("        &P")
+    lines.append("    }};")
+    lines.append("}")
+    lines.append("")
+    lines.append("// Same index, each array delta/varint packed (see unicode/pack.rs).")
+    lines.append('#[cfg(feature = "compact-tables")]')
+    lines.append(f"pub static CODE_RANGES_PACKED: [&[u8]; {len(code_ranges)}] = [")
+    for i, name in enumerate(code_ranges):
+        real = aliases.get(name, name)
+        lines.append(f"    packed!({real}), // {i}: {name}")
     lines.append("];")
     lines.append("")
 
@@ -168,6 +291,15 @@ def generate_rust(arrays, aliases, code_ranges, wordlist):
     lines.append("];")
     lines.append("")
 
+    # \p{scx=...}
+    lines.append("// --- Script -> Script_Extensions ctype (sorted by script ctype) ---")
+    lines.append("// Scripts whose Script_Extensions equal their Script ranges map to themselves.")
+    lines.append(f"pub static SCRIPT_EXTENSIONS: [(u16, u16); {len(scx_table)}] = [")
+    for sc, sx in scx_table:
+        lines.append(f"    ({sc}, {sx}),")
+    lines.append("];")
+    lines.append("")
+
     return "\n".join(lines)
 
 
@@ -176,6 +308,10 @@ def main():
         text = f.read()
 
     print(f"Parsing {C_FILE}...")
+    version = parse_unicode_version(text)
+    print(f"  Unicode {'.'.join(map(str, version))}")
+    if len(sys.argv) > 1 and sys.argv[1] != '.'.join(map(str, version)):
+        sys.exit(f"  ERROR: expected Unicode {sys.argv[1]}; regenerate the C tables first")
     arrays, aliases = parse_cr_arrays(text)
     print(f"  Found {len(arrays)} CR_* arrays ({len(aliases)} aliases)")
 
@@ -194,7 +330,10 @@ def main():
         if real not in arrays:
             print(f"  ERROR: {name} (-> {real}) in CodeRanges but not parsed!")
 
-    rust = generate_rust(arrays, aliases, code_ranges, wordlist)
+    scx = compute_script_extensions(arrays, aliases, code_ranges, wordlist, version)
+    print(f"  {len(scx[2])} scripts, {len(scx[0])} with extra Script_Extensions ranges")
+
+    rust = generate_rust(arrays, aliases, code_ranges, wordlist, scx, version)
 
     with open(OUT_FILE, 'w') as f:
         f.write(rust)
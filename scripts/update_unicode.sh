@@ -0,0 +1,30 @@
+#!/bin/sh
+# Regenerate every Unicode table in src/unicode/ for one Unicode version.
+#
+#   scripts/update_unicode.sh 16.0.0
+#
+# The property, fold, EGCB and WB tables are converted from Oniguruma's C
+# tables, so oniguruma-orig/ must hold an Oniguruma tree whose
+# unicode_*_data.c / unicode_fold*.c were generated for VERSION: each
+# Oniguruma release ships them, and upstream's make_unicode_*.py scripts
+# regenerate them for a newer UCD. The UCD files Ferroni reads itself
+# (Script_Extensions) are downloaded into $UCD_DIR (default: ucd/).
+set -eu
+
+VERSION=${1:?usage: $0 VERSION (e.g. 16.0.0)}
+ROOT=$(cd "$(dirname "$0")/.." && pwd)
+UCD_DIR=${UCD_DIR:-$ROOT/ucd}
+export UCD_DIR
+
+mkdir -p "$UCD_DIR"
+for f in Scripts.txt ScriptExtensions.txt; do
+    if ! head -n 1 "$UCD_DIR/$f" 2>/dev/null | grep -q -- "-$VERSION\.txt"; then
+        curl -fsSL "https://www.unicode.org/Public/$VERSION/ucd/$f" -o "$UCD_DIR/$f"
+    fi
+done
+
+# Checks the C tables are VERSION before writing anything.
+python3 "$ROOT/scripts/gen_unicode_property_data.py" "$VERSION"
+python3 "$ROOT/scripts/gen_unicode_fold_data.py"
+python3 "$ROOT/scripts/gen_unicode_break_data.py"
+cargo fmt --manifest-path "$ROOT/Cargo.toml"
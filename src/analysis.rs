@@ -0,0 +1,484 @@
+// analysis.rs - Static backtracking-risk (ReDoS) analysis of a pattern.
+//
+// Services that run user-submitted patterns want to reject the
+// ones that can backtrack exponentially before ever matching with them.
+// This walks the parse tree (Oniguruma syntax extensions included) looking
+// for the shapes that make a backtracking matcher blow up: repeats whose
+// iterations can be split in more than one way, and unbounded repeats that
+// compete for the same characters.
+
+use core::ops::Range;
+
+use crate::error::RegexError;
+use crate::oniguruma::*;
+use crate::regcomp::onig_parse_only;
+use crate::regenc::OnigEncoding;
+use crate::regint::*;
+use crate::regparse_types::*;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+
+/// Parse `pattern` and look for constructs that can make matching take
+/// polynomial or exponential time.
+///
+/// The analysis is a heuristic over the syntax tree: it looks at the first
+/// characters each construct can match, so it may flag patterns that are
+/// safe in practice (e.g. `(ab|ac)+`), but it finds the classic shapes such
+/// as `(a+)+`, `(\w|\d)*` and `\s*.*\s*$`. Atomic groups and possessive
+/// quantifiers are taken into account. Backreferences and subexpression
+/// calls are assumed to match anything.
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::analysis::{FindingKind, Risk};
+/// use ferroni::encodings::utf8::ONIG_ENCODING_UTF8;
+/// use ferroni::regsyntax::OnigSyntaxOniguruma;
+///
+/// let analyze = |p: &str| {
+///     ferroni::analysis::analyze_complexity(p.as_bytes(), &OnigSyntaxOniguruma, &ONIG_ENCODING_UTF8)
+///         .unwrap()
+/// };
+///
+/// let report = analyze(r"^(\w+\s?)+$");
+/// assert_eq!(report.risk, Risk::Exponential);
+/// assert_eq!(report.findings[0].kind, FindingKind::NestedQuantifier);
+/// assert_eq!((report.findings[0].span.clone(), report.findings[0].conflict.clone()), (1..10, 2..5));
+///
+/// assert_eq!(analyze(r"^(\w++\s?)+$").risk, Risk::Low);
+/// assert_eq!(analyze(r"\d+\.\d+").risk, Risk::Low);
+/// ```
+pub fn analyze_complexity(
+    pattern: &[u8],
+    syntax: &OnigSyntaxType,
+    enc: OnigEncoding,
+) -> Result<ComplexityReport, RegexError> {
+    let (root, _) = onig_parse_only(pattern, ONIG_OPTION_NONE, enc, syntax)?;
+    Ok(analyze_tree(&root, enc, pattern.len()))
+}
+
+/// Re-parse the pattern `reg` was compiled from and analyze it. A literal
+/// pattern cannot backtrack.
+pub(crate) fn analyze_regex(reg: &RegexType, pattern: &[u8], literal: bool) -> ComplexityReport {
+    let parsed = if literal {
+        None
+    } else {
+        onig_parse_only(pattern, reg.options, reg.enc, &reg.syntax).ok()
+    };
+    match parsed {
+        Some((root, _)) => analyze_tree(&root, reg.enc, pattern.len()),
+        None => ComplexityReport {
+            risk: Risk::Low,
+            findings: Vec::new(),
+        },
+    }
+}
+
+/// Analyze a parse tree of a pattern `pattern_len` bytes long.
+fn analyze_tree(root: &Node, enc: OnigEncoding, pattern_len: usize) -> ComplexityReport {
+    let mut cx = Analyzer {
+        enc,
+        whole: 0..pattern_len,
+        findings: Vec::new(),
+    };
+    cx.visit(root, false);
+    ComplexityReport {
+        risk: cx
+            .findings
+            .iter()
+            .map(|f| f.risk)
+            .max()
+            .unwrap_or(Risk::Low),
+        findings: cx.findings,
+    }
+}
+
+/// How badly matching a pattern can backtrack, from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Risk {
+    /// Nothing known to backtrack super-linearly was found.
+    Low,
+    /// Matching time can grow polynomially with the text length.
+    Polynomial,
+    /// Matching time can grow exponentially with the text length.
+    Exponential,
+}
+
+/// Result of [`analyze_complexity`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ComplexityReport {
+    /// The worst risk among the findings, or [`Risk::Low`] if there are none.
+    pub risk: Risk,
+    /// Offending subpatterns, in pattern order of their outer construct.
+    pub findings: Vec<Finding>,
+}
+
+/// One risky construct found by [`analyze_complexity`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Finding {
+    /// How bad this construct is.
+    pub risk: Risk,
+    /// What was found.
+    pub kind: FindingKind,
+    /// Byte range of the repeat (or, for adjacent quantifiers, the first
+    /// quantifier) in the pattern.
+    pub span: Range<usize>,
+    /// Byte range of the part that competes with it for the same text.
+    pub conflict: Range<usize>,
+}
+
+/// Kinds of [`Finding`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FindingKind {
+    /// A repeat ending in another repeat that can also match the start of
+    /// the next iteration, as in `(a+)+`.
+    NestedQuantifier,
+    /// A repeated alternation whose alternatives can start with the same
+    /// character, as in `(\w|\d)+`.
+    OverlappingAlternatives,
+    /// Two unbounded quantifiers in a row that can match the same
+    /// characters, as in `\d+\d+` or `\s*.*`.
+    AdjacentQuantifiers,
+}
+
+struct Analyzer {
+    enc: OnigEncoding,
+    whole: Range<usize>,
+    findings: Vec<Finding>,
+}
+
+impl Analyzer {
+    fn span(&self, node: &Node) -> Range<usize> {
+        node.span.map_or(self.whole.clone(), |(s, e)| s..e)
+    }
+
+    /// Record findings for `node` and everything below it. `possessive` is
+    /// set when `node` is the body of `X*+`-style atomic wrapping.
+    fn visit(&mut self, node: &Node, possessive: bool) {
+        match &node.inner {
+            NodeInner::List(_) => {
+                let items = list_items(node);
+                self.check_adjacent(&items);
+                for item in items {
+                    self.visit(item, false);
+                }
+            }
+            NodeInner::Alt(_) => {
+                for item in list_items(node) {
+                    self.visit(item, false);
+                }
+            }
+            NodeInner::Quant(qn) => {
+                let Some(ref body) = qn.body else {
+                    return;
+                };
+                // Only a repeat that can run a varying number of times
+                // lets the matcher try another split of the same text.
+                let varies = qn.upper == INFINITE_REPEAT || qn.upper > qn.lower.max(1);
+                if varies && !possessive {
+                    let risk = if qn.upper == INFINITE_REPEAT {
+                        Risk::Exponential
+                    } else {
+                        Risk::Polynomial
+                    };
+                    self.check_repeat(node, body, risk);
+                }
+                self.visit(body, false);
+            }
+            NodeInner::Bag(bn) => {
+                if let Some(ref body) = bn.body {
+                    let wraps_quant = matches!(body.inner, NodeInner::Quant(_));
+                    self.visit(body, bn.bag_type == BagType::StopBacktrack && wraps_quant);
+                }
+                if let BagData::IfElse {
+                    ref then_node,
+                    ref else_node,
+                } = bn.bag_data
+                {
+                    for branch in [then_node, else_node].into_iter().flatten() {
+                        self.visit(branch, false);
+                    }
+                }
+            }
+            NodeInner::Anchor(an) => {
+                if let Some(ref body) = an.body {
+                    self.visit(body, false);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Look for ways to split the text matched by several iterations of
+    /// `quant` (whose body is `body`) differently.
+    fn check_repeat(&mut self, quant: &Node, body: &Node, risk: Risk) {
+        let next = self.first(body).set;
+
+        let mut tails = Vec::new();
+        tail_repeats(body, &mut tails);
+        for tail in tails {
+            let Some(tail_body) = tail.body() else {
+                continue;
+            };
+            if overlaps(&self.first(tail_body).set, &next) {
+                self.findings.push(Finding {
+                    risk,
+                    kind: FindingKind::NestedQuantifier,
+                    span: self.span(quant),
+                    conflict: self.span(tail),
+                });
+                return;
+            }
+        }
+
+        let mut alt = body;
+        while let NodeInner::Bag(bn) = &alt.inner {
+            match (&bn.bag_type, &bn.body) {
+                (BagType::Memory | BagType::Option, Some(inner)) => alt = inner,
+                _ => break,
+            }
+        }
+        if let NodeInner::Alt(_) = alt.inner {
+            let branches = list_items(alt);
+            let mut seen: BitSet = [0; BITSET_REAL_SIZE];
+            for branch in branches {
+                let first = self.first(branch).set;
+                if overlaps(&first, &seen) {
+                    self.findings.push(Finding {
+                        risk,
+                        kind: FindingKind::OverlappingAlternatives,
+                        span: self.span(quant),
+                        conflict: self.span(branch),
+                    });
+                    return;
+                }
+                bitset_or(&mut seen, &first);
+            }
+        }
+    }
+
+    /// Look for unbounded quantifiers in `items` separated only by
+    /// constructs that can match empty and competing for the same text.
+    fn check_adjacent(&mut self, items: &[&Node]) {
+        for (i, &item) in items.iter().enumerate() {
+            let Some(left) = unbounded_repeat(item) else {
+                continue;
+            };
+            let Some(left_body) = left.body() else {
+                continue;
+            };
+            let left_set = self.first(left_body).set;
+            for &next in &items[i + 1..] {
+                if let Some(right) = unbounded_repeat(next) {
+                    let Some(right_body) = right.body() else {
+                        break;
+                    };
+                    if overlaps(&left_set, &self.first(right_body).set) {
+                        self.findings.push(Finding {
+                            risk: Risk::Polynomial,
+                            kind: FindingKind::AdjacentQuantifiers,
+                            span: self.span(left),
+                            conflict: self.span(right),
+                        });
+                    }
+                    break;
+                }
+                if !self.first(next).nullable {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// The bytes a match of `node` can start with (an over-approximation;
+    /// multi-byte characters contribute all non-ASCII bytes) and whether it
+    /// can match the empty string.
+    fn first(&self, node: &Node) -> First {
+        let mut set: BitSet = [0; BITSET_REAL_SIZE];
+        let single_byte = self.enc.max_enc_len() == 1;
+        let nullable = match &node.inner {
+            NodeInner::String(sn) => {
+                let Some(&b) = sn.s.first() else {
+                    return First::EMPTY;
+                };
+                bitset_set_bit(&mut set, b as usize);
+                if node.has_status(ND_ST_IGNORECASE) {
+                    if b < 0x80 {
+                        bitset_set_bit(&mut set, b.to_ascii_lowercase() as usize);
+                        bitset_set_bit(&mut set, b.to_ascii_uppercase() as usize);
+                    } else {
+                        bitset_set_range(&mut set, 0x80, 0xff);
+                    }
+                }
+                false
+            }
+            NodeInner::CClass(cc) => {
+                for b in 0..SINGLE_BYTE_SIZE {
+                    if bitset_at(&cc.bs, b) != cc.is_not() {
+                        bitset_set_bit(&mut set, b);
+                    }
+                }
+                if !single_byte && (cc.mbuf.is_some() || cc.is_not()) {
+                    bitset_set_range(&mut set, 0x80, 0xff);
+                }
+                false
+            }
+            NodeInner::CType(ct) => {
+                if ct.ctype == CTYPE_ANYCHAR {
+                    bitset_set_range(&mut set, 0, 0xff);
+                } else {
+                    for b in 0..SINGLE_BYTE_SIZE {
+                        let is_ctype = (b < 0x80 || (single_byte && !ct.ascii_mode))
+                            && self.enc.is_code_ctype(b as OnigCodePoint, ct.ctype as u32);
+                        if is_ctype != ct.not {
+                            bitset_set_bit(&mut set, b);
+                        }
+                    }
+                    if !single_byte && !ct.ascii_mode {
+                        bitset_set_range(&mut set, 0x80, 0xff);
+                    }
+                }
+                false
+            }
+            NodeInner::List(_) => {
+                let mut nullable = true;
+                for item in list_items(node) {
+                    let f = self.first(item);
+                    bitset_or(&mut set, &f.set);
+                    if !f.nullable {
+                        nullable = false;
+                        break;
+                    }
+                }
+                nullable
+            }
+            NodeInner::Alt(_) => {
+                let mut nullable = false;
+                for item in list_items(node) {
+                    let f = self.first(item);
+                    bitset_or(&mut set, &f.set);
+                    nullable |= f.nullable;
+                }
+                nullable
+            }
+            NodeInner::Quant(qn) => match qn.body {
+                Some(ref body) if qn.upper != 0 => {
+                    let f = self.first(body);
+                    set = f.set;
+                    f.nullable || qn.lower == 0
+                }
+                _ => true,
+            },
+            NodeInner::Bag(bn) => {
+                if let BagData::IfElse {
+                    ref then_node,
+                    ref else_node,
+                } = bn.bag_data
+                {
+                    let parts = [bn.body.as_ref(), then_node.as_ref(), else_node.as_ref()];
+                    for part in parts.into_iter().flatten() {
+                        bitset_or(&mut set, &self.first(part).set);
+                    }
+                    true
+                } else {
+                    match bn.body {
+                        Some(ref body) => {
+                            let f = self.first(body);
+                            set = f.set;
+                            f.nullable
+                        }
+                        None => true,
+                    }
+                }
+            }
+            // Zero-width
+            NodeInner::Anchor(_) => true,
+            NodeInner::Gimmick(gn) if gn.gimmick_type != GimmickType::Extension => true,
+            // Backreferences, calls and custom opcodes: anything
+            _ => {
+                bitset_set_range(&mut set, 0, 0xff);
+                true
+            }
+        };
+        First { set, nullable }
+    }
+}
+
+/// What [`Analyzer::first`] found.
+struct First {
+    set: BitSet,
+    nullable: bool,
+}
+
+impl First {
+    const EMPTY: First = First {
+        set: [0; BITSET_REAL_SIZE],
+        nullable: true,
+    };
+}
+
+fn overlaps(a: &BitSet, b: &BitSet) -> bool {
+    a.iter().zip(b).any(|(x, y)| x & y != 0)
+}
+
+/// The elements of a list or alternation node.
+fn list_items(node: &Node) -> Vec<&Node> {
+    let mut items = Vec::new();
+    let mut cur = Some(node);
+    while let Some(cons) = cur.and_then(|n| n.as_cons()) {
+        items.push(cons.car.as_ref());
+        cur = cons.cdr.as_deref();
+    }
+    items
+}
+
+/// `node` itself if it is an unbounded, non-possessive quantifier, looking
+/// through groups.
+fn unbounded_repeat(node: &Node) -> Option<&Node> {
+    match &node.inner {
+        NodeInner::Quant(qn) if qn.upper == INFINITE_REPEAT => Some(node),
+        NodeInner::Bag(bn) if matches!(bn.bag_type, BagType::Memory | BagType::Option) => {
+            unbounded_repeat(bn.body.as_deref()?)
+        }
+        _ => None,
+    }
+}
+
+/// Collect the repeats `node` can end with: quantifiers that may run more
+/// than once and are followed, within `node`, only by parts that can match
+/// empty. Atomic groups end the search.
+fn tail_repeats<'a>(node: &'a Node, out: &mut Vec<&'a Node>) -> bool {
+    match &node.inner {
+        NodeInner::Quant(qn) => {
+            if qn.upper == INFINITE_REPEAT || qn.upper > 1 && qn.upper > qn.lower {
+                out.push(node);
+            }
+            qn.lower == 0
+        }
+        NodeInner::List(_) => {
+            for item in list_items(node).into_iter().rev() {
+                if !tail_repeats(item, out) {
+                    return false;
+                }
+            }
+            true
+        }
+        NodeInner::Alt(_) => {
+            let mut nullable = false;
+            for item in list_items(node) {
+                nullable |= tail_repeats(item, out);
+            }
+            nullable
+        }
+        NodeInner::Bag(bn) if matches!(bn.bag_type, BagType::Memory | BagType::Option) => bn
+            .body
+            .as_deref()
+            .map_or(true, |body| tail_repeats(body, out)),
+        NodeInner::Anchor(_) => true,
+        NodeInner::String(sn) => sn.s.is_empty(),
+        _ => false,
+    }
+}
@@ -3,15 +3,75 @@
 // Wraps the C-ported internals (onig_new, onig_search, etc.) with
 // Rust-native types: Regex, RegexBuilder, Match, Captures, FindIter.
 
-use std::ops::Range;
-
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::ops::{Range, RangeInclusive};
+use core::time::Duration;
+
+use crate::analysis::{analyze_regex, ComplexityReport};
+use crate::charindex::CharIndex;
 use crate::encodings::utf8::ONIG_ENCODING_UTF8;
-use crate::error::RegexError;
+use crate::error::{RegexError, Warning};
+use crate::lint::{lint_regex, Lint};
 use crate::oniguruma::*;
-use crate::regcomp::onig_new;
-use crate::regexec::{onig_name_to_group_numbers, onig_search};
-use crate::regint::RegexType;
-use crate::regsyntax::OnigSyntaxOniguruma;
+use crate::regcomp::{
+    compile_error, onig_compile_literal_with, onig_compile_with, onig_debug_tree,
+    onig_get_default_case_fold_flag, onig_get_default_text_segment, onig_new, onig_reg_init,
+    print_compiled_byte_code_list, print_optimize_info,
+};
+use crate::regenc::OnigEncoding;
+use crate::regexec::{
+    onig_foreach_name, onig_match, onig_name_to_group_numbers, onig_search, onig_search_with_msa,
+    onig_search_with_state, ExecState, MatchArg,
+};
+use crate::regint::{
+    CompileBudget, CompileState, OptimizeType, RegexType, ANCR_ANYCHAR_INF, ANCR_ANYCHAR_INF_ML,
+    ANCR_BEGIN_BUF, ANCR_BEGIN_LINE, ANCR_BEGIN_POSITION, ANCR_END_BUF, ANCR_END_LINE,
+    ANCR_SEMI_END_BUF, INFINITE_LEN,
+};
+use crate::regparse_types::NameEntry;
+use crate::regsyntax::{OnigSyntaxASIS, OnigSyntaxOniguruma};
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+use crate::sys::Mutex;
+use crate::template::Template;
+
+/// Byte offset into a haystack, as used throughout the public API.
+///
+/// The engine stores positions as `i32` (as in C Oniguruma), so haystacks are
+/// limited to [`MAX_HAYSTACK_LEN`] bytes on every target. Region offsets are
+/// converted with checks at this boundary.
+pub type Position = usize;
+
+/// Longest haystack, in bytes, that can be searched.
+pub const MAX_HAYSTACK_LEN: Position = ONIG_MAX_INPUT_LEN;
+
+/// Panic if `text` is too long for the engine's `i32` positions.
+#[inline]
+pub(crate) fn check_haystack_len(text: &[u8]) {
+    assert!(
+        text.len() <= MAX_HAYSTACK_LEN,
+        "haystack of {} bytes exceeds MAX_HAYSTACK_LEN ({} bytes)",
+        text.len(),
+        MAX_HAYSTACK_LEN
+    );
+}
+
+/// Panic if the search start `at` is past the end of `text`.
+#[inline]
+fn check_start(text: &[u8], at: usize) {
+    assert!(
+        at <= text.len(),
+        "start {} is past the end of the haystack ({} bytes)",
+        at,
+        text.len()
+    );
+}
+
+/// Minimum haystack length, in bytes, for the literal pre-scan to run
+/// (see [`RegexBuilder::literal_prescan`]).
+pub const LITERAL_PRESCAN_MIN_LEN: usize = 1 << 20;
 
 /// A compiled regular expression.
 ///
@@ -28,8 +88,15 @@ use crate::regsyntax::OnigSyntaxOniguruma;
 /// assert_eq!(m.start(), 6);
 /// assert_eq!(m.end(), 8);
 /// ```
+///
+/// All search methods panic if the haystack is longer than
+/// [`MAX_HAYSTACK_LEN`].
 pub struct Regex {
-    inner: RegexType,
+    pub(crate) inner: RegexType,
+    literal_prescan: bool,
+    // Kept for `debug_ast()`, which re-parses the pattern.
+    pub(crate) pattern: Box<[u8]>,
+    pub(crate) literal: bool,
 }
 
 impl Regex {
@@ -46,7 +113,41 @@ impl Regex {
             &ONIG_ENCODING_UTF8,
             &OnigSyntaxOniguruma,
         )?;
-        Ok(Regex { inner })
+        Ok(Regex {
+            inner,
+            literal_prescan: false,
+            pattern: pattern.into(),
+            literal: false,
+        })
+    }
+
+    /// Compile a pattern with default options through the crate-wide
+    /// [`RegexCache::global`], so every caller asking for the same pattern
+    /// shares one compiled regex.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    /// use std::sync::Arc;
+    ///
+    /// let a = Regex::new_cached(r"\d+").unwrap();
+    /// let b = Regex::new_cached(r"\d+").unwrap();
+    /// assert!(Arc::ptr_eq(&a, &b));
+    /// ```
+    pub fn new_cached(pattern: &str) -> Result<Arc<Regex>, RegexError> {
+        RegexCache::global().get(pattern)
+    }
+
+    /// Compile `text` as a literal string, without interpreting metacharacters.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let re = Regex::literal("a.b[c]").unwrap();
+    /// assert!(re.is_match("x a.b[c] y"));
+    /// assert!(!re.is_match("axbc"));
+    /// ```
+    pub fn literal(text: &str) -> Result<Regex, RegexError> {
+        RegexBuilder::new(text).literal(true).build()
     }
 
     /// Create a [`RegexBuilder`] for fine-grained control over compilation.
@@ -61,24 +162,127 @@ impl Regex {
 
     /// Return the first match in `text` (as bytes), or `None` if no match.
     pub fn find_bytes<'t>(&self, text: &'t [u8]) -> Option<Match<'t>> {
+        self.find_at_bytes(text, 0)
+    }
+
+    /// Return the first match starting at or after byte offset `at`, or
+    /// `None`.
+    ///
+    /// Unlike searching `&text[at..]`, the whole of `text` stays visible:
+    /// look-behind and `\b` see what precedes `at`, `^` and `\A` do not
+    /// match there (unless `^` follows a newline), and `\G` matches at
+    /// `at`. Match offsets are into `text`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is past the end of `text`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let re = Regex::new(r"(?<=\$)\d+").unwrap();
+    /// let text = "cost: $42";
+    /// assert_eq!(re.find_at(text, 7).unwrap().range(), 7..9);
+    /// assert!(re.find(&text[7..]).is_none());
+    ///
+    /// let re = Regex::new(r"\G\w+").unwrap();
+    /// assert_eq!(re.find_at("foo bar", 4).unwrap().as_str(), "bar");
+    /// assert!(Regex::new(r"^bar").unwrap().find_at("foo bar", 4).is_none());
+    /// ```
+    pub fn find_at<'t>(&self, text: &'t str, at: usize) -> Option<Match<'t>> {
+        self.find_at_bytes(text.as_bytes(), at)
+    }
+
+    /// Like [`find_at`](Self::find_at), on bytes.
+    pub fn find_at_bytes<'t>(&self, text: &'t [u8], at: usize) -> Option<Match<'t>> {
+        check_haystack_len(text);
+        check_start(text, at);
         let (result, region) = onig_search(
             &self.inner,
             text,
             text.len(),
-            0,
+            at,
             text.len(),
             Some(OnigRegion::new()),
-            ONIG_OPTION_NONE,
+            ONIG_OPTION_DONT_CAPTURE_GROUP,
         );
         if result < 0 {
             return None;
         }
         let region = region?;
-        if region.num_regs < 1 {
+        let m = region.get(0)?;
+        Some(Match {
+            text,
+            start: m.start,
+            end: m.end,
+        })
+    }
+
+    /// Return the leftmost match that ends exactly at byte offset `end`, or
+    /// `None`.
+    ///
+    /// The whole of `text` stays visible to the pattern, so look-ahead and
+    /// `\b` see what follows `end`. Only starts within the pattern's
+    /// maximum match length before `end` are tried, so this is cheap for
+    /// bounded patterns even deep into a large text; for unbounded ones
+    /// (e.g. `\w+`) every start up to `end` is a candidate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `end` is past the end of `text`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let re = Regex::new(r"\w+").unwrap();
+    /// let text = "let total = price * count;";
+    /// assert_eq!(re.find_ending_at(text, 17).unwrap().as_str(), "price");
+    /// assert!(re.find_ending_at(text, 18).is_none());
+    ///
+    /// // Context after `end` still counts
+    /// let re = Regex::new(r"\w+(?=\()").unwrap();
+    /// assert_eq!(re.find_ending_at("f(g(x))", 3).unwrap().as_str(), "g");
+    /// ```
+    pub fn find_ending_at<'t>(&self, text: &'t str, end: usize) -> Option<Match<'t>> {
+        self.find_ending_at_bytes(text.as_bytes(), end)
+    }
+
+    /// Like [`find_ending_at`](Self::find_ending_at), on bytes.
+    pub fn find_ending_at_bytes<'t>(&self, text: &'t [u8], end: usize) -> Option<Match<'t>> {
+        check_haystack_len(text);
+        assert!(
+            end <= text.len(),
+            "end {} is past the end of the haystack ({} bytes)",
+            end,
+            text.len()
+        );
+        let reg = &self.inner;
+        let last = end.checked_sub(reg.match_len_min as usize)?;
+        let first = if reg.match_len_max == INFINITE_LEN {
+            0
+        } else {
+            let lo = end.saturating_sub(reg.match_len_max as usize);
+            reg.enc.left_adjust_char_head(0, lo, text)
+        };
+        let mut msa = MatchArg::new(
+            reg,
+            ONIG_OPTION_DONT_CAPTURE_GROUP,
+            Some(OnigRegion::new()),
+            first,
+        );
+        msa.match_end = Some(end);
+        // The optimized search treats the range as exclusive.
+        let range = (last + 1).min(text.len());
+        let (result, region) = onig_search_with_msa(reg, text, text.len(), first, range, &mut msa);
+        if result < 0 {
             return None;
         }
-        let start = region.beg[0] as usize;
-        let end = region.end[0] as usize;
+        let region = region?;
+        let start = region.get(0)?.start;
         Some(Match { text, start, end })
     }
 
@@ -89,16 +293,88 @@ impl Regex {
 
     /// Check whether `text` (as bytes) matches the pattern anywhere.
     pub fn is_match_bytes(&self, text: &[u8]) -> bool {
-        let (result, _) = onig_search(
-            &self.inner,
-            text,
-            text.len(),
-            0,
-            text.len(),
-            None,
-            ONIG_OPTION_NONE,
-        );
-        result >= 0
+        self.is_match_bytes_with_stats(text).0
+    }
+
+    /// Like [`is_match`](Self::is_match), also reporting how the search ran.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::api::{Regex, LITERAL_PRESCAN_MIN_LEN};
+    ///
+    /// let re = Regex::builder(r"[a-z]{1,8} ERROR \d+")
+    ///     .literal_prescan(true)
+    ///     .build()
+    ///     .unwrap();
+    /// let mut log = "x".repeat(LITERAL_PRESCAN_MIN_LEN);
+    /// log.push_str(" disk ERROR 42");
+    /// let (found, stats) = re.is_match_with_stats(&log);
+    /// assert!(found);
+    /// assert!(stats.prescan_used);
+    /// assert_eq!(stats.prescan_windows, 1);
+    /// ```
+    pub fn is_match_with_stats(&self, text: &str) -> (bool, MatchStats) {
+        self.is_match_bytes_with_stats(text.as_bytes())
+    }
+
+    /// Like [`is_match_bytes`](Self::is_match_bytes), also reporting how the
+    /// search ran.
+    pub fn is_match_bytes_with_stats(&self, text: &[u8]) -> (bool, MatchStats) {
+        check_haystack_len(text);
+        let mut stats = MatchStats::default();
+        let reg = &self.inner;
+        if self.literal_prescan
+            && text.len() >= LITERAL_PRESCAN_MIN_LEN
+            && !reg.req_exact.is_empty()
+            && (reg.anchor & ANCR_BEGIN_POSITION) == 0
+        {
+            stats.prescan_used = true;
+            let finder = memchr::memmem::Finder::new(&reg.req_exact);
+            if reg.req_dist_max == INFINITE_LEN {
+                // Unbounded distance to the literal: only its absence is useful.
+                if finder.find(text).is_none() {
+                    return (false, stats);
+                }
+                stats.prescan_windows = 1;
+            } else {
+                let (dist_min, dist_max) = (reg.req_dist_min as usize, reg.req_dist_max as usize);
+                // Start positions below `next` have already been tried.
+                let mut next = 0;
+                let mut at = 0;
+                while let Some(i) = finder.find(&text[at..]) {
+                    let p = at + i;
+                    at = p + 1;
+                    if p < dist_min {
+                        continue;
+                    }
+                    let lo = p.saturating_sub(dist_max).max(next);
+                    let hi = p - dist_min;
+                    if lo > hi {
+                        continue;
+                    }
+                    stats.prescan_windows += 1;
+                    // onig_match, not a ranged onig_search: a forward search
+                    // range also bounds the match end.
+                    for start in lo..=hi {
+                        if start < text.len() && (text[start] & 0xC0) == 0x80 {
+                            continue; // not a UTF-8 character head
+                        }
+                        stats.vm_searches += 1;
+                        let (r, _) =
+                            onig_match(reg, text, text.len(), start, None, ONIG_OPTION_NONE);
+                        if r >= 0 {
+                            return (true, stats);
+                        }
+                    }
+                    next = hi + 1;
+                }
+                return (false, stats);
+            }
+        }
+        stats.vm_searches += 1;
+        let (result, _) = onig_search(reg, text, text.len(), 0, text.len(), None, ONIG_OPTION_NONE);
+        (result >= 0, stats)
     }
 
     /// Return the first match with all capture groups, or `None`.
@@ -108,11 +384,39 @@ impl Regex {
 
     /// Return the first match with all capture groups (bytes), or `None`.
     pub fn captures_bytes<'t>(&'t self, text: &'t [u8]) -> Option<Captures<'t>> {
+        self.captures_at_bytes(text, 0)
+    }
+
+    /// Return the first match starting at or after byte offset `at` with
+    /// all capture groups, or `None`. `at` is a position inside `text`, as
+    /// in [`find_at`](Self::find_at).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is past the end of `text`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let re = Regex::new(r"\G\s*(?<key>\w+)=").unwrap();
+    /// let caps = re.captures_at("a=1 b=2", 3).unwrap();
+    /// assert_eq!(caps.name("key").unwrap().range(), 4..5);
+    /// ```
+    pub fn captures_at<'t>(&'t self, text: &'t str, at: usize) -> Option<Captures<'t>> {
+        self.captures_at_bytes(text.as_bytes(), at)
+    }
+
+    /// Like [`captures_at`](Self::captures_at), on bytes.
+    pub fn captures_at_bytes<'t>(&'t self, text: &'t [u8], at: usize) -> Option<Captures<'t>> {
+        check_haystack_len(text);
+        check_start(text, at);
         let (result, region) = onig_search(
             &self.inner,
             text,
             text.len(),
-            0,
+            at,
             text.len(),
             Some(OnigRegion::new()),
             ONIG_OPTION_NONE,
@@ -130,161 +434,1695 @@ impl Regex {
 
     /// Iterate over all non-overlapping matches in `text`.
     pub fn find_iter<'r, 't>(&'r self, text: &'t str) -> FindIter<'r, 't> {
-        FindIter {
-            regex: self,
-            text: text.as_bytes(),
-            last_end: 0,
-            last_was_empty: false,
-        }
+        self.find_iter_bytes(text.as_bytes())
     }
 
     /// Iterate over all non-overlapping matches in `text` (as bytes).
     pub fn find_iter_bytes<'r, 't>(&'r self, text: &'t [u8]) -> FindIter<'r, 't> {
+        check_haystack_len(text);
         FindIter {
             regex: self,
             text,
             last_end: 0,
             last_was_empty: false,
+            region: None,
+            state: ExecState::new(),
+            prev_end: None,
+            groups: Vec::new(),
+        }
+    }
+
+    /// Split `text` at each match, yielding the text between matches.
+    ///
+    /// Matches are those of [`find_iter`](Self::find_iter). A match at
+    /// either end yields an empty first or last segment, so `n` matches
+    /// always give `n + 1` segments.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let re = Regex::new(r"\s*,\s*").unwrap();
+    /// let parts: Vec<_> = re.split("a, b ,c,").collect();
+    /// assert_eq!(parts, ["a", "b", "c", ""]);
+    /// ```
+    pub fn split<'r, 't>(&'r self, text: &'t str) -> Split<'r, 't> {
+        check_haystack_len(text.as_bytes());
+        Split {
+            finder: self.find_iter(text),
+            text,
+            last: 0,
+            done: false,
+        }
+    }
+
+    /// Split `text` after each match, yielding segments that end with
+    /// their delimiter, like [`str::split_inclusive`]. The text after the
+    /// last match is the final segment unless it is empty.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let re = Regex::new(r"\r?\n").unwrap();
+    /// let lines: Vec<_> = re.split_inclusive("one\r\ntwo\nthree").collect();
+    /// assert_eq!(lines, ["one\r\n", "two\n", "three"]);
+    /// assert_eq!(re.split_inclusive("one\n").count(), 1);
+    /// ```
+    pub fn split_inclusive<'r, 't>(&'r self, text: &'t str) -> SplitInclusive<'r, 't> {
+        check_haystack_len(text.as_bytes());
+        SplitInclusive {
+            finder: self.find_iter(text),
+            text,
+            last: 0,
+        }
+    }
+
+    /// Split `text` at each match, keeping the delimiters: text segments
+    /// and the delimiter matches with their capture groups are yielded in
+    /// order, like JavaScript's `String.prototype.split` with a grouped
+    /// regex.
+    ///
+    /// Items alternate between [`SplitItem::Text`] and
+    /// [`SplitItem::Delimiter`], starting and ending with text; text
+    /// segments are empty between adjacent delimiters and where a delimiter
+    /// is at either end. Joining all items gives back `text`.
+    ///
+    /// ```
+    /// use ferroni::api::{Regex, SplitItem};
+    ///
+    /// let re = Regex::new(r"\s*([-+])\s*").unwrap();
+    /// let items: Vec<_> = re
+    ///     .split_keep("1 + 22-3")
+    ///     .map(|item| match item {
+    ///         SplitItem::Text(t) => t.to_string(),
+    ///         SplitItem::Delimiter(caps) => format!("<{}>", caps.get(1).unwrap().as_str()),
+    ///     })
+    ///     .collect();
+    /// assert_eq!(items, ["1", "<+>", "22", "<->", "3"]);
+    /// ```
+    pub fn split_keep<'t>(&'t self, text: &'t str) -> SplitKeep<'t> {
+        check_haystack_len(text.as_bytes());
+        SplitKeep {
+            split: self.split(text),
+            pending: None,
+        }
+    }
+
+    /// Replace the first match in `text` with `template` expanded for it.
+    /// Returns `text` itself, without copying, if nothing matches.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    /// use ferroni::template::Template;
+    ///
+    /// let re = Regex::new(r"(\w+) (\w+)").unwrap();
+    /// let swap = Template::new("$2 $1", &re).unwrap();
+    /// assert_eq!(re.replace("one two three four", &swap), "two one three four");
+    /// ```
+    pub fn replace<'t>(&self, text: &'t str, template: &Template) -> Cow<'t, str> {
+        self.replace_n(text, 1, template)
+    }
+
+    /// Replace every match [`find_iter`](Self::find_iter) finds in `text`
+    /// with `template` expanded for it. Returns `text` itself, without
+    /// copying, if nothing matches.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    /// use ferroni::template::Template;
+    ///
+    /// let re = Regex::new(r"(?<k>\w+)=(?<v>\w+)").unwrap();
+    /// let t = Template::new("${v}:${k}", &re).unwrap();
+    /// assert_eq!(re.replace_all("a=1 b=2", &t), "1:a 2:b");
+    /// ```
+    pub fn replace_all<'t>(&self, text: &'t str, template: &Template) -> Cow<'t, str> {
+        self.replace_n(text, usize::MAX, template)
+    }
+
+    fn replace_n<'t>(&self, text: &'t str, limit: usize, template: &Template) -> Cow<'t, str> {
+        check_haystack_len(text.as_bytes());
+        let mut it = self.find_iter(text);
+        let mut out = String::new();
+        let mut last = 0;
+        let mut n = 0;
+        while n < limit {
+            let Some(m) = it.next() else {
+                break;
+            };
+            out.push_str(&text[last..m.start()]);
+            // The iterator's region holds the groups of `m`
+            if let Some(region) = &it.region {
+                template.expand_with(|i| region.get(i).map(|r| &text[r]), &mut out);
+            }
+            last = m.end();
+            n += 1;
+        }
+        if n == 0 {
+            return Cow::Borrowed(text);
+        }
+        out.push_str(&text[last..]);
+        Cow::Owned(out)
+    }
+
+    /// [`replace_n`](Self::replace_n) on bytes, for [`crate::bytes::Regex`].
+    pub(crate) fn replace_n_bytes<'t>(
+        &self,
+        text: &'t [u8],
+        limit: usize,
+        template: &Template,
+    ) -> Cow<'t, [u8]> {
+        check_haystack_len(text);
+        let mut it = self.find_iter_bytes(text);
+        let mut out = Vec::new();
+        let mut last = 0;
+        let mut n = 0;
+        while n < limit {
+            let Some(m) = it.next() else {
+                break;
+            };
+            out.extend_from_slice(&text[last..m.start()]);
+            if let Some(region) = &it.region {
+                template.expand_bytes_with(|i| region.get(i).map(|r| &text[r]), &mut out);
+            }
+            last = m.end();
+            n += 1;
+        }
+        if n == 0 {
+            return Cow::Borrowed(text);
         }
+        out.extend_from_slice(&text[last..]);
+        Cow::Owned(out)
+    }
+
+    /// Return the first `n` matches [`find_iter`](Self::find_iter) would
+    /// yield, and whether there are more.
+    ///
+    /// Finding out whether there are more takes one more search, which
+    /// does not record capture groups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let re = Regex::new(r"\d+").unwrap();
+    /// let (matches, more) = re.find_at_most_n("1 22 333", 2);
+    /// let found: Vec<_> = matches.iter().map(|m| m.as_str()).collect();
+    /// assert_eq!(found, ["1", "22"]);
+    /// assert!(more);
+    /// assert!(!re.find_at_most_n("1 22 333", 3).1);
+    /// ```
+    pub fn find_at_most_n<'t>(&self, text: &'t str, n: usize) -> (Vec<Match<'t>>, bool) {
+        self.find_at_most_n_bytes(text.as_bytes(), n)
+    }
+
+    /// Like [`find_at_most_n`](Self::find_at_most_n), on bytes.
+    pub fn find_at_most_n_bytes<'t>(&self, text: &'t [u8], n: usize) -> (Vec<Match<'t>>, bool) {
+        check_haystack_len(text);
+        let mut it = self.find_iter_bytes(text);
+        let matches: Vec<_> = it.by_ref().take(n).collect();
+        let more = matches.len() == n && it.has_more();
+        (matches, more)
+    }
+
+    /// Return the capture groups of the first `n` matches
+    /// [`find_iter`](Self::find_iter) would yield, and whether there are
+    /// more.
+    ///
+    /// Finding out whether there are more takes one more search, which
+    /// does not record capture groups.
+    pub fn captures_at_most_n<'t>(&'t self, text: &'t str, n: usize) -> (Vec<Captures<'t>>, bool) {
+        self.captures_at_most_n_bytes(text.as_bytes(), n)
+    }
+
+    /// Like [`captures_at_most_n`](Self::captures_at_most_n), on bytes.
+    pub fn captures_at_most_n_bytes<'t>(
+        &'t self,
+        text: &'t [u8],
+        n: usize,
+    ) -> (Vec<Captures<'t>>, bool) {
+        check_haystack_len(text);
+        let mut it = self.find_iter_bytes(text);
+        let mut caps = Vec::new();
+        while caps.len() < n {
+            if it.next().is_none() {
+                return (caps, false);
+            }
+            if let Some(region) = &it.region {
+                caps.push(Captures {
+                    text,
+                    region: region.clone(),
+                    regex: self,
+                });
+            }
+        }
+        let more = it.has_more();
+        (caps, more)
+    }
+
+    /// Return every match [`find_iter`](Self::find_iter) would yield, as
+    /// long as storing them takes at most `max_bytes`.
+    ///
+    /// Each match counts `size_of::<Match>()` bytes. When the next match
+    /// would go over the budget, the search stops and the matches that fit
+    /// are returned in the [`BudgetExceeded`] error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::api::{Match, Regex};
+    ///
+    /// let re = Regex::new(r"\d+").unwrap();
+    /// let budget = 2 * core::mem::size_of::<Match>();
+    /// let err = re.find_all_within("1 22 333", budget).unwrap_err();
+    /// let found: Vec<_> = err.partial.iter().map(|m| m.as_str()).collect();
+    /// assert_eq!(found, ["1", "22"]);
+    /// assert_eq!(re.find_all_within("1 22", budget).unwrap().len(), 2);
+    /// ```
+    pub fn find_all_within<'t>(
+        &self,
+        text: &'t str,
+        max_bytes: usize,
+    ) -> Result<Vec<Match<'t>>, BudgetExceeded<Match<'t>>> {
+        self.find_all_within_bytes(text.as_bytes(), max_bytes)
+    }
+
+    /// Like [`find_all_within`](Self::find_all_within), on bytes.
+    pub fn find_all_within_bytes<'t>(
+        &self,
+        text: &'t [u8],
+        max_bytes: usize,
+    ) -> Result<Vec<Match<'t>>, BudgetExceeded<Match<'t>>> {
+        check_haystack_len(text);
+        let size = core::mem::size_of::<Match>();
+        let mut matches = Vec::new();
+        let mut used = 0;
+        for m in self.find_iter_bytes(text) {
+            used += size;
+            if used > max_bytes {
+                return Err(BudgetExceeded {
+                    partial: matches,
+                    limit: max_bytes,
+                });
+            }
+            matches.push(m);
+        }
+        Ok(matches)
+    }
+
+    /// Return the capture groups of every match
+    /// [`find_iter`](Self::find_iter) would yield, as long as storing them
+    /// takes at most `max_bytes`.
+    ///
+    /// Each match counts `size_of::<Captures>()` bytes plus its group
+    /// spans. When the next match would go over the budget, the search
+    /// stops and the captures that fit are returned in the
+    /// [`BudgetExceeded`] error.
+    pub fn captures_all_within<'t>(
+        &'t self,
+        text: &'t str,
+        max_bytes: usize,
+    ) -> Result<Vec<Captures<'t>>, BudgetExceeded<Captures<'t>>> {
+        self.captures_all_within_bytes(text.as_bytes(), max_bytes)
+    }
+
+    /// Like [`captures_all_within`](Self::captures_all_within), on bytes.
+    pub fn captures_all_within_bytes<'t>(
+        &'t self,
+        text: &'t [u8],
+        max_bytes: usize,
+    ) -> Result<Vec<Captures<'t>>, BudgetExceeded<Captures<'t>>> {
+        check_haystack_len(text);
+        let mut it = self.find_iter_bytes(text);
+        let mut caps = Vec::new();
+        let mut used = 0;
+        while it.next().is_some() {
+            let Some(region) = &it.region else { continue };
+            used += core::mem::size_of::<Captures>() + region.spans_size();
+            if used > max_bytes {
+                return Err(BudgetExceeded {
+                    partial: caps,
+                    limit: max_bytes,
+                });
+            }
+            caps.push(Captures {
+                text,
+                region: region.clone(),
+                regex: self,
+            });
+        }
+        Ok(caps)
+    }
+
+    /// The case fold rules case-insensitive parts of the pattern use: the
+    /// builder's [`case_fold_flag`](RegexBuilder::case_fold_flag), or the
+    /// global default when the regex was compiled.
+    pub fn case_fold_flag(&self) -> OnigCaseFoldType {
+        self.inner.case_fold_flag
+    }
+
+    /// The text segment type `\X`, `\y` and `\Y` use outside `(?y{..})`
+    /// groups: the builder's [`text_segment`](RegexBuilder::text_segment), or
+    /// the global default when the regex was compiled.
+    pub fn text_segment(&self) -> OnigTextSegment {
+        if self.inner.options.intersects(ONIG_OPTION_TEXT_SEGMENT_WORD) {
+            OnigTextSegment::Word
+        } else {
+            OnigTextSegment::ExtendedGraphemeCluster
+        }
+    }
+
+    /// Return the number of capture groups in the pattern (excluding group 0).
+    pub fn captures_len(&self) -> usize {
+        self.inner.num_mem as usize
+    }
+
+    /// For each capture group, the innermost capture group that encloses it
+    /// in the pattern, indexed by group number.
+    ///
+    /// Entry 0 (the whole match) and groups not nested in another capture
+    /// group are `None`. Non-capturing groups are transparent.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let re = Regex::new(r"(a(?:(b)|(c(d))))|(e)").unwrap();
+    /// assert_eq!(re.group_parents(), [None, None, Some(1), Some(1), Some(3), None]);
+    /// ```
+    pub fn group_parents(&self) -> Vec<Option<u32>> {
+        self.inner.group_parents.clone()
+    }
+
+    /// Iterate over the named groups as `(name, group_numbers)`, ordered by
+    /// their first group number.
+    ///
+    /// A name maps to several groups when it is defined more than once.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let re = Regex::new(r"(?<y>\d+)-(?<m>\d+)|(?<y>\w+)").unwrap();
+    /// let names: Vec<_> = re.name_table().collect();
+    /// assert_eq!(names, vec![("y", vec![1, 3]), ("m", vec![2])]);
+    /// ```
+    pub fn name_table(&self) -> GroupNames<'_> {
+        let mut entries: Vec<_> = match self.inner.name_table {
+            Some(ref nt) => nt.entries.values().collect(),
+            None => Vec::new(),
+        };
+        entries.sort_by_key(|e| e.back_refs.first().copied());
+        GroupNames {
+            entries: entries.into_iter(),
+        }
+    }
+
+    /// Iterate over the named groups as `(name, group_numbers)` without
+    /// copying, ordered by their first group number. Like
+    /// [`name_table`](Self::name_table), which collects the numbers into a
+    /// `Vec`.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let re = Regex::new(r"(?<y>\d+)-(?<m>\d+)|(?<y>\w+)").unwrap();
+    /// let names: Vec<_> = re
+    ///     .capture_names()
+    ///     .map(|(name, groups)| (name, groups.collect::<Vec<u32>>()))
+    ///     .collect();
+    /// assert_eq!(names, [("y", vec![1, 3]), ("m", vec![2])]);
+    /// ```
+    pub fn capture_names(&self) -> impl Iterator<Item = (&str, GroupNumbers<'_>)> + '_ {
+        let mut entries: Vec<_> = match self.inner.name_table {
+            Some(ref nt) => nt.entries.values().collect(),
+            None => Vec::new(),
+        };
+        entries.sort_by_key(|e| e.back_refs.first().copied());
+        entries.into_iter().filter_map(|entry| {
+            // Names were validated as UTF-8 when the pattern was parsed.
+            let name = core::str::from_utf8(&entry.name).ok()?;
+            let groups = GroupNumbers {
+                groups: entry.back_refs.iter(),
+            };
+            Some((name, groups))
+        })
+    }
+
+    /// Number of the capture group named `name`, or `None` if there is no
+    /// such group. For a name defined more than once this is its first
+    /// group; [`capture_names`](Self::capture_names) lists all of them.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let re = Regex::new(r"(?<a>a)(?<x>b)|(?<x>c)").unwrap();
+    /// assert_eq!(re.group_index("x"), Some(2));
+    /// assert_eq!(re.group_index("z"), None);
+    /// ```
+    pub fn group_index(&self, name: &str) -> Option<usize> {
+        let nums = onig_name_to_group_numbers(&self.inner, name.as_bytes()).ok()?;
+        nums.first().map(|&n| n as usize)
+    }
+
+    /// Name of capture group `i`, or `None` if the group is unnamed or does
+    /// not exist.
+    pub fn group_name(&self, i: usize) -> Option<&str> {
+        let nt = self.inner.name_table.as_ref()?;
+        let entry = nt
+            .entries
+            .values()
+            .find(|e| e.back_refs.iter().any(|&g| g as usize == i))?;
+        core::str::from_utf8(&entry.name).ok()
+    }
+
+    /// Dump what the optimizer inferred (anchors, exact prefix, character
+    /// map) followed by the compiled bytecode, one instruction per line.
+    ///
+    /// This is the output of C Oniguruma's `ONIG_DEBUG_COMPILE` build. The
+    /// format is meant for reading, not parsing, and may change.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let dump = Regex::new(r"\Aab").unwrap().debug_bytecode();
+    /// assert!(dump.contains("anchor: [begin-buf]"));
+    /// assert!(dump.contains("str_2:ab"));
+    /// ```
+    pub fn debug_bytecode(&self) -> String {
+        let mut out = print_optimize_info(&self.inner);
+        out.push_str(&print_compiled_byte_code_list(&self.inner));
+        out
+    }
+
+    /// Dump the parse tree as it stands just before code generation, after
+    /// group renumbering and `tune_tree()`.
+    ///
+    /// This is the output of C Oniguruma's `ONIG_DEBUG_PARSE` build; see
+    /// [`crate::ast`] for a structured view of the tree.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let dump = Regex::new(r"a|(b)").unwrap().debug_ast();
+    /// assert_eq!(dump, "<alt>\n   <string>a\n   <bag> memory:1\n      <string>b\n");
+    /// ```
+    pub fn debug_ast(&self) -> String {
+        onig_debug_tree(&self.inner, &self.pattern, self.literal)
+            .unwrap_or_else(|e| format!("{}\n", e))
+    }
+
+    /// Look for constructs in the pattern that can make matching backtrack
+    /// polynomially or exponentially; see
+    /// [`analyze_complexity`](crate::analysis::analyze_complexity).
+    ///
+    /// ```
+    /// use ferroni::analysis::Risk;
+    /// use ferroni::api::Regex;
+    ///
+    /// assert_eq!(Regex::new(r"(a|ab)*c").unwrap().analyze_complexity().risk, Risk::Exponential);
+    /// assert_eq!(Regex::new(r"(?>a|ab)*c").unwrap().analyze_complexity().risk, Risk::Low);
+    /// ```
+    pub fn analyze_complexity(&self) -> ComplexityReport {
+        analyze_regex(&self.inner, &self.pattern, self.literal)
+    }
+
+    /// Report constructs in the pattern that are valid but most likely
+    /// mistakes; see [`lint`](crate::lint::lint).
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    /// use ferroni::lint::LintKind;
+    ///
+    /// let lints = Regex::new(r"(?<num>\d+)[0-9\d]").unwrap().lint();
+    /// let kinds: Vec<_> = lints.iter().map(|l| l.kind).collect();
+    /// assert_eq!(kinds, [LintKind::UnusedName, LintKind::RedundantClassItem]);
+    /// ```
+    pub fn lint(&self) -> Vec<Lint> {
+        lint_regex(&self.inner, &self.pattern, self.literal)
+    }
+
+    /// Report the anchors, prefilter, match length bounds and search backend
+    /// the optimizer computed for this pattern.
+    ///
+    /// ```
+    /// use ferroni::api::{Anchors, Backend, Prefilter, Regex};
+    ///
+    /// let info = Regex::new(r"\Aab+").unwrap().optimize_info();
+    /// assert_eq!(info.anchors, Anchors::BEGIN_BUF);
+    /// assert_eq!((info.min_len, info.max_len), (2, None));
+    /// assert!(matches!(info.prefilter, Prefilter::Exact { ref literal, .. } if literal == b"ab"));
+    /// assert_eq!(info.backend, Backend::Vm);
+    ///
+    /// let info = Regex::new(r"\\u[0-9a-fA-F]{4}").unwrap().optimize_info();
+    /// assert_eq!(info.backend, Backend::ShiftOr { positions: 6 });
+    /// ```
+    pub fn optimize_info(&self) -> OptimizeInfo {
+        let reg = &self.inner;
+        let min_distance = reg.dist_min as usize;
+        let max_distance = finite_len(reg.dist_max);
+        let prefilter = match reg.optimize {
+            OptimizeType::None => Prefilter::None,
+            OptimizeType::Map => Prefilter::Map {
+                bytes: (0..=255u8).filter(|&b| reg.map[b as usize] != 0).collect(),
+                min_distance,
+                max_distance,
+            },
+            _ => Prefilter::Exact {
+                literal: reg.exact.clone(),
+                min_distance,
+                max_distance,
+            },
+        };
+        OptimizeInfo {
+            anchors: Anchors::from_raw(reg.anchor),
+            sub_anchors: Anchors::from_raw(reg.sub_anchor),
+            prefilter,
+            min_len: reg.match_len_min as usize,
+            max_len: finite_len(reg.match_len_max),
+            backend: match reg.shift_or {
+                Some(ref so) => Backend::ShiftOr {
+                    positions: so.len(),
+                },
+                None => Backend::Vm,
+            },
+        }
+    }
+
+    /// A hash of the compiled program: bytecode with its operands, options,
+    /// encoding and newline convention.
+    ///
+    /// Patterns that compile to the same program get the same fingerprint
+    /// however they are spelled, so caches keyed by it can share one entry.
+    /// The value is stable across platforms and runs of the same Ferroni
+    /// version; a release that changes how a pattern compiles may change
+    /// it. Group names are not part of the program, so `(?<x>a)` and `(a)`
+    /// share a fingerprint. Custom opcodes count by name and argument.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let fp = |p: &str| Regex::new(p).unwrap().fingerprint();
+    /// assert_eq!(fp("[a-c]x{2}"), fp("[abc]xx"));
+    /// assert_ne!(fp("[a-c]x{2}"), fp("[a-c]x{3}"));
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        crate::fingerprint::fingerprint(&self.inner)
+    }
+
+    /// Estimate the bytes this regex holds, by part: bytecode, character
+    /// classes, names, optimizer tables and the rest. Use
+    /// [`total`](MemoryBreakdown::total) to size a cache of compiled
+    /// patterns.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let small = Regex::new("abc").unwrap().memory_usage();
+    /// let large = Regex::new(r"(?<word>\p{L}+)\s*=\s*[0-9a-f]{8}").unwrap().memory_usage();
+    /// assert!(large.char_classes > small.char_classes);
+    /// assert!(large.names > small.names);
+    /// assert!(large.total() > small.total());
+    /// ```
+    pub fn memory_usage(&self) -> MemoryBreakdown {
+        let mut m = crate::regcomp::regex_memory_usage(&self.inner);
+        m.other +=
+            core::mem::size_of::<Regex>() - core::mem::size_of::<RegexType>() + self.pattern.len();
+        m
+    }
+
+    /// What the matcher did for this regex so far: match attempts, opcode
+    /// executions and backtrack depths, summed over all threads. Requires
+    /// the `exec-stats` feature.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let re = Regex::new(r"\w+\d").unwrap();
+    /// re.find("hello world 42");
+    /// let stats = re.exec_stats();
+    /// assert_eq!(stats.matches, 1);
+    /// println!("{stats}");
+    /// ```
+    #[cfg(feature = "exec-stats")]
+    pub fn exec_stats(&self) -> crate::stats::ExecStats {
+        crate::stats::exec_stats(&self.inner)
+    }
+
+    /// Zero the counters behind [`exec_stats`](Self::exec_stats).
+    #[cfg(feature = "exec-stats")]
+    pub fn reset_exec_stats(&self) {
+        crate::stats::reset_exec_stats(&self.inner)
+    }
+
+    /// Access the underlying `RegexType` for advanced / C-style usage.
+    pub fn as_raw(&self) -> &RegexType {
+        &self.inner
+    }
+}
+
+impl AsRef<Regex> for Regex {
+    fn as_ref(&self) -> &Regex {
+        self
+    }
+}
+
+impl core::fmt::Debug for Regex {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Regex").finish_non_exhaustive()
+    }
+}
+
+// === RegexBuilder ===
+
+/// Builder for compiling a [`Regex`] with custom options.
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::api::Regex;
+///
+/// let re = Regex::builder(r"hello world")
+///     .case_insensitive(true)
+///     .build()
+///     .unwrap();
+/// assert!(re.is_match("Hello World"));
+/// ```
+pub struct RegexBuilder {
+    pattern: Vec<u8>,
+    options: OnigOptionType,
+    syntax: SyntaxRef,
+    encoding: OnigEncoding,
+    literal: bool,
+    literal_prescan: bool,
+    newline: Option<OnigNewline>,
+    case_fold_flag: Option<OnigCaseFoldType>,
+    properties: Vec<(Vec<u8>, Vec<OnigCodePoint>)>,
+    max_compiled_bytes: usize,
+    compile_timeout: Option<Duration>,
+    #[cfg(feature = "unstable-opcodes")]
+    opcodes: crate::extension::OpcodeTable,
+}
+
+impl RegexBuilder {
+    /// Create a new builder for the given pattern.
+    pub fn new(pattern: &str) -> Self {
+        Self::new_bytes(pattern.as_bytes())
+    }
+
+    /// Create a new builder for a pattern given as raw bytes.
+    pub fn new_bytes(pattern: &[u8]) -> Self {
+        RegexBuilder {
+            pattern: pattern.to_vec(),
+            options: ONIG_OPTION_NONE,
+            syntax: SyntaxRef::Static(&OnigSyntaxOniguruma),
+            encoding: &ONIG_ENCODING_UTF8,
+            literal: false,
+            literal_prescan: false,
+            newline: None,
+            case_fold_flag: None,
+            properties: Vec::new(),
+            max_compiled_bytes: 0,
+            compile_timeout: None,
+            #[cfg(feature = "unstable-opcodes")]
+            opcodes: Default::default(),
+        }
+    }
+
+    /// Enable or disable case-insensitive matching.
+    pub fn case_insensitive(mut self, yes: bool) -> Self {
+        if yes {
+            self.options |= ONIG_OPTION_IGNORECASE;
+        } else {
+            self.options &= !ONIG_OPTION_IGNORECASE;
+        }
+        self
+    }
+
+    /// Enable or disable case-insensitive matching of ASCII letters only
+    /// (`ONIG_OPTION_IGNORECASE | ONIG_OPTION_IGNORECASE_IS_ASCII`): `a`
+    /// matches `A`, but `é` does not match `É` and `k` does not match the
+    /// Kelvin sign. Compiling skips the Unicode case fold tables. `false`
+    /// turns case-insensitive matching off.
+    ///
+    /// ```
+    /// use ferroni::prelude::*;
+    ///
+    /// let re = Regex::builder(r"straße [é]")
+    ///     .ascii_case_insensitive(true)
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(re.is_match("STRAßE é"));
+    /// assert!(!re.is_match("STRASSE é"));
+    /// assert!(!re.is_match("straße É"));
+    /// ```
+    pub fn ascii_case_insensitive(mut self, yes: bool) -> Self {
+        if yes {
+            self.options |= ONIG_OPTION_IGNORECASE | ONIG_OPTION_IGNORECASE_IS_ASCII;
+        } else {
+            self.options &= !(ONIG_OPTION_IGNORECASE | ONIG_OPTION_IGNORECASE_IS_ASCII);
+        }
+        self
+    }
+
+    /// Enable or disable multiline mode (`.` matches `\n`).
+    pub fn dot_matches_newline(mut self, yes: bool) -> Self {
+        if yes {
+            self.options |= ONIG_OPTION_MULTILINE;
+        } else {
+            self.options &= !ONIG_OPTION_MULTILINE;
+        }
+        self
+    }
+
+    /// Enable or disable `^`/`$` matching at every line boundary.
+    pub fn multi_line_anchors(mut self, yes: bool) -> Self {
+        if yes {
+            self.options |= ONIG_OPTION_SINGLELINE;
+        } else {
+            self.options &= !ONIG_OPTION_SINGLELINE;
+        }
+        self
+    }
+
+    /// Enable or disable extended mode (whitespace and `#` comments ignored).
+    pub fn extended(mut self, yes: bool) -> Self {
+        if yes {
+            self.options |= ONIG_OPTION_EXTEND;
+        } else {
+            self.options &= !ONIG_OPTION_EXTEND;
+        }
+        self
+    }
+
+    /// Enable or disable POSIX leftmost-longest matching: of the matches
+    /// at the leftmost start, the longest wins, whichever alternative or
+    /// repeat count produces it. By default the first one the backtracking
+    /// order reaches wins. Capture groups are those of the first path to
+    /// the longest match; POSIX's rule for the lengths of subexpressions is
+    /// not applied.
+    ///
+    /// This is `ONIG_OPTION_FIND_LONGEST` with `ONIG_OPTION_LEFTMOST_LONGEST`.
+    /// `FIND_LONGEST` alone (Oniguruma's `(?L)`) returns the longest match
+    /// anywhere in the searched range instead, even if it starts later.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let posix = |p: &str| Regex::builder(p).leftmost_longest(true).build().unwrap();
+    /// assert_eq!(Regex::new("a|ab|abc").unwrap().find("xabcd").unwrap().as_str(), "a");
+    /// assert_eq!(posix("a|ab|abc").find("xabcd").unwrap().as_str(), "abc");
+    /// // Leftmost first: "ab" at 1 wins over the longer "abcd" at 4
+    /// assert_eq!(posix(r"ab|\w{4}").find("-ab-abcd").unwrap().range(), 1..3);
+    /// ```
+    pub fn leftmost_longest(mut self, yes: bool) -> Self {
+        let flags = ONIG_OPTION_FIND_LONGEST | ONIG_OPTION_LEFTMOST_LONGEST;
+        if yes {
+            self.options |= flags;
+        } else {
+            self.options &= !flags;
+        }
+        self
+    }
+
+    /// Set a raw option flag. See `ONIG_OPTION_*` constants.
+    pub fn option(mut self, flag: OnigOptionType) -> Self {
+        self.options |= flag;
+        self
+    }
+
+    /// Make the given character classes ASCII-only; the others keep their
+    /// Unicode meaning (the default). Replaces what earlier calls set.
+    ///
+    /// This is `ONIG_OPTION_WORD_IS_ASCII` and its siblings, with their
+    /// Oniguruma scope: [`Classes::WORD`] covers `\w`, `\b`, `[[:word:]]`
+    /// and `\p{Word}` alike. Ruby makes only `\w`, `\d`, `\s` ASCII and
+    /// keeps `\b`, POSIX brackets and properties Unicode, so
+    /// `Classes::WORD | Classes::DIGIT | Classes::SPACE` matches Ruby for
+    /// the shorthands but not for `\b` or `\p{Word}`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::api::{Classes, Regex};
+    ///
+    /// let re = Regex::builder(r"^\w+ \d$")
+    ///     .ascii_classes(Classes::WORD | Classes::DIGIT)
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(re.is_match("abc 1"));
+    /// assert!(!re.is_match("äbc 1"));
+    /// assert!(!re.is_match("abc \u{0661}")); // ARABIC-INDIC DIGIT ONE
+    /// ```
+    pub fn ascii_classes(mut self, classes: Classes) -> Self {
+        self.options &= !Classes::all().to_options();
+        self.options |= classes.to_options();
+        self
+    }
+
+    /// Select the syntax definition to use (default: Oniguruma).
+    ///
+    /// Pass one of the `OnigSyntax*` statics from [`crate::regsyntax`], or
+    /// an owned syntax (plain or in an `Arc`, to share it between regexes),
+    /// e.g. one made with [`SyntaxBuilder`](crate::regsyntax::SyntaxBuilder).
+    pub fn syntax(mut self, syntax: impl Into<SyntaxRef>) -> Self {
+        self.syntax = syntax.into();
+        self
+    }
+
+    /// Select the encoding of the pattern and of the text searched
+    /// (default: UTF-8).
+    ///
+    /// With [`ONIG_ENCODING_ASCII`] every byte is one character, so `.`
+    /// and classes match any byte of binary data; see
+    /// [`bytes::Regex`](crate::bytes::Regex). The `&str` methods and
+    /// [`Match::as_str`] panic on a match that splits a UTF-8 sequence.
+    ///
+    /// [`ONIG_ENCODING_ASCII`]: crate::encodings::ascii::ONIG_ENCODING_ASCII
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::prelude::*;
+    /// use ferroni::encodings::ascii::ONIG_ENCODING_ASCII;
+    ///
+    /// let re = Regex::builder(r"^.$")
+    ///     .encoding(&ONIG_ENCODING_ASCII)
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(re.is_match_bytes(b"\xff"));
+    /// assert!(!re.is_match("é"));
+    /// ```
+    pub fn encoding(mut self, encoding: OnigEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Select what `.`, `^`, `$` and `\Z` treat as a line terminator
+    /// (default: `\n`, or CRLF with [`ONIG_OPTION_NEWLINE_CRLF`]). A
+    /// leading `(*CR)`, `(*LF)`, `(*CRLF)`, `(*ANYCRLF)` or `(*ANY)` verb in
+    /// the pattern takes precedence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::prelude::*;
+    /// use ferroni::oniguruma::OnigNewline;
+    ///
+    /// let re = Regex::builder(r"^b.$")
+    ///     .newline(OnigNewline::AnyCrlf)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(re.find("a\r\nbc\r\n").unwrap().as_str(), "bc");
+    /// ```
+    pub fn newline(mut self, newline: OnigNewline) -> Self {
+        self.newline = Some(newline);
+        self
+    }
+
+    /// Select the case fold rules used by case-insensitive matching
+    /// (default: [`onig_get_default_case_fold_flag`] at the time of
+    /// [`build`](Self::build)).
+    ///
+    /// Adding [`ONIGENC_CASE_FOLD_TURKISH_AZERI`] pairs `I` with dotless `ı`
+    /// and dotted `İ` with `i`, instead of `I` with `i`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::prelude::*;
+    /// use ferroni::oniguruma::{ONIGENC_CASE_FOLD_MIN, ONIGENC_CASE_FOLD_TURKISH_AZERI};
+    ///
+    /// let re = Regex::builder("istanbul")
+    ///     .case_insensitive(true)
+    ///     .case_fold_flag(ONIGENC_CASE_FOLD_MIN | ONIGENC_CASE_FOLD_TURKISH_AZERI)
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(re.is_match("İSTANBUL"));
+    /// assert!(!re.is_match("ISTANBUL"));
+    /// ```
+    pub fn case_fold_flag(mut self, flag: OnigCaseFoldType) -> Self {
+        self.case_fold_flag = Some(flag);
+        self
+    }
+
+    /// Select the text segments `\X` matches and `\y`/`\Y` find the
+    /// boundaries of (default: [`onig_get_default_text_segment`] at the
+    /// time of [`build`](Self::build)). `(?y{g})` or `(?y{w})` in the
+    /// pattern takes precedence.
+    ///
+    /// [`onig_get_default_text_segment`]: crate::regcomp::onig_get_default_text_segment
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::prelude::*;
+    /// use ferroni::oniguruma::OnigTextSegment;
+    ///
+    /// let re = Regex::builder(r"\X")
+    ///     .text_segment(OnigTextSegment::Word)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(re.find("hello world").unwrap().as_str(), "hello");
+    /// assert_eq!(re.text_segment(), OnigTextSegment::Word);
+    /// ```
+    pub fn text_segment(mut self, segment: OnigTextSegment) -> Self {
+        self.options &=
+            !(ONIG_OPTION_TEXT_SEGMENT_EXTENDED_GRAPHEME_CLUSTER | ONIG_OPTION_TEXT_SEGMENT_WORD);
+        self.options |= segment.option();
+        self
+    }
+
+    /// Treat the pattern as a literal string (no metacharacters, no escapes).
+    ///
+    /// The pattern bypasses the parser entirely, so [`syntax`](Self::syntax)
+    /// and [`extended`](Self::extended) are ignored; only
+    /// [`case_insensitive`](Self::case_insensitive) still affects matching.
+    pub fn literal(mut self, yes: bool) -> Self {
+        self.literal = yes;
+        self
+    }
+
+    /// Pre-scan long haystacks for the pattern's required literal in
+    /// [`Regex::is_match`].
+    ///
+    /// When the haystack is at least [`LITERAL_PRESCAN_MIN_LEN`] bytes and the
+    /// pattern contains a literal every match must include, `is_match` first
+    /// locates that literal with `memmem` and only runs the matcher on the
+    /// start positions each occurrence allows. Whether it ran is reported by
+    /// [`Regex::is_match_with_stats`].
+    pub fn literal_prescan(mut self, yes: bool) -> Self {
+        self.literal_prescan = yes;
+        self
+    }
+
+    /// Define `\p{name}` for this pattern only.
+    ///
+    /// Unlike [`onig_unicode_define_user_property`](crate::unicode::onig_unicode_define_user_property)
+    /// nothing is registered process-wide, so libraries cannot collide on
+    /// names. A builder property shadows a global one of the same name.
+    /// Without the `std` feature it is also visible to compiles that run
+    /// on other threads at the same time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::prelude::*;
+    ///
+    /// let re = Regex::builder(r"\p{Vowel}+")
+    ///     .property("Vowel", &['a'..='a', 'e'..='e', 'i'..='i', 'o'..='o', 'u'..='u'])
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(re.find("queue").unwrap().as_str(), "ueue");
+    /// assert!(Regex::new(r"\p{Vowel}").is_err());
+    /// ```
+    pub fn property(mut self, name: &str, ranges: &[RangeInclusive<char>]) -> Self {
+        let mut pairs: Vec<_> = ranges
+            .iter()
+            .filter(|r| !r.is_empty())
+            .map(|r| (*r.start() as OnigCodePoint, *r.end() as OnigCodePoint))
+            .collect();
+        pairs.sort_unstable();
+        // Code range lists must not overlap
+        let mut ranges: Vec<OnigCodePoint> = Vec::with_capacity(pairs.len() * 2);
+        for (from, to) in pairs {
+            match ranges.last_mut() {
+                Some(end) if from <= end.saturating_add(1) => *end = (*end).max(to),
+                _ => ranges.extend([from, to]),
+            }
+        }
+        self.properties.push((name.as_bytes().to_vec(), ranges));
+        self
+    }
+
+    /// Fail with [`RegexError::CompiledSizeLimitOver`] instead of building a
+    /// program larger than `bytes`. The size counts the bytecode and the
+    /// strings and class tables it owns, not the search optimizations.
+    ///
+    /// Together with [`compile_timeout`](Self::compile_timeout) this bounds
+    /// what compiling an untrusted pattern can cost; match cost is bounded
+    /// separately by the retry and time limits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::prelude::*;
+    ///
+    /// let build = |p: &str| Regex::builder(p).max_compiled_bytes(4096).build();
+    /// assert!(build(r"\w+@\w+\.com").is_ok());
+    /// assert_eq!(build(r"\p{L}{50}").unwrap_err(), RegexError::CompiledSizeLimitOver);
+    /// ```
+    pub fn max_compiled_bytes(mut self, bytes: usize) -> Self {
+        self.max_compiled_bytes = bytes;
+        self
+    }
+
+    /// Fail with [`RegexError::CompileTimeLimitOver`] when parsing and
+    /// compiling the pattern take longer than `timeout`. The clock is read
+    /// every few hundred parsed expressions, tree nodes and emitted
+    /// instructions, so the limit can be overrun by a little. Without the
+    /// `std` feature there is no clock and the limit never fires.
+    pub fn compile_timeout(mut self, timeout: Duration) -> Self {
+        self.compile_timeout = Some(timeout);
+        self
+    }
+
+    /// Make `(*name)` and `(*name{arg})` run `handler`; see
+    /// [`extension`](crate::extension).
+    ///
+    /// **Unstable**: requires the `unstable-opcodes` feature.
+    #[cfg(feature = "unstable-opcodes")]
+    pub fn opcode(
+        mut self,
+        name: &str,
+        handler: alloc::sync::Arc<dyn crate::extension::OpcodeHandler>,
+    ) -> Self {
+        self.opcodes.register(name.as_bytes(), handler);
+        self
+    }
+
+    /// Compile the pattern into a [`Regex`] and also return the warnings
+    /// the parser raised, such as nested repeat operators it rewrote.
+    ///
+    /// C Oniguruma only passes these to the process-wide
+    /// [`onig_set_warn_func`](crate::regparse::onig_set_warn_func) and
+    /// [`onig_set_verb_warn_func`](crate::regparse::onig_set_verb_warn_func)
+    /// callbacks, which still receive them. Which constructs warn depends on
+    /// the syntax's `ONIG_SYN_WARN_*` flags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::prelude::*;
+    ///
+    /// let (re, warnings) = Regex::builder(r"(?:a+)?[a-z-&&x]")
+    ///     .build_with_warnings()
+    ///     .unwrap();
+    /// assert!(re.is_match("x"));
+    /// let kinds: Vec<_> = warnings.iter().map(|w| w.kind()).collect();
+    /// assert_eq!(kinds, [WarningKind::NestedRepeat, WarningKind::UnescapedMetaChar]);
+    /// assert_eq!(
+    ///     warnings[0].message(),
+    ///     "nested repeat operator + and ? was replaced with '*' in regular expression"
+    /// );
+    /// assert_eq!(warnings[0].span(), 0..7);
+    /// ```
+    pub fn build_with_warnings(mut self) -> Result<(Regex, Vec<Warning>), RegexError> {
+        if self.properties.is_empty() {
+            return self.compile();
+        }
+        let properties = core::mem::take(&mut self.properties);
+        crate::unicode::with_scoped_properties(&properties, || self.compile())?
+    }
+
+    /// Compile the pattern into a [`bytes::Regex`](crate::bytes::Regex).
+    pub fn build_bytes(self) -> Result<crate::bytes::Regex, RegexError> {
+        self.build().map(crate::bytes::Regex::from)
+    }
+
+    /// Compile the pattern into a [`Regex`].
+    pub fn build(self) -> Result<Regex, RegexError> {
+        self.build_with_warnings().map(|(re, _)| re)
+    }
+
+    fn compile(self) -> Result<(Regex, Vec<Warning>), RegexError> {
+        let syntax = if self.literal {
+            SyntaxRef::Static(&OnigSyntaxASIS)
+        } else {
+            self.syntax
+        };
+        let mut inner = onig_reg_init(
+            self.options,
+            self.case_fold_flag
+                .unwrap_or_else(onig_get_default_case_fold_flag),
+            self.encoding,
+            syntax.get(),
+        )?;
+        #[cfg(feature = "unstable-opcodes")]
+        {
+            inner.opcodes = self.opcodes;
+        }
+        let mut state = CompileState {
+            budget: CompileBudget::new(self.max_compiled_bytes, self.compile_timeout),
+            ..Default::default()
+        };
+        let r = if self.literal {
+            onig_compile_literal_with(&mut inner, &self.pattern, &mut state)
+        } else {
+            if let Some(newline) = self.newline {
+                inner.newline = newline;
+            }
+            onig_compile_with(&mut inner, &self.pattern, &mut state)
+        };
+        if r != 0 {
+            return Err(compile_error(r, &state, &self.pattern));
+        }
+        let re = Regex {
+            inner,
+            literal_prescan: self.literal_prescan,
+            pattern: self.pattern.into_boxed_slice(),
+            literal: self.literal,
+        };
+        Ok((re, state.warnings))
+    }
+}
+
+// === SyntaxRef ===
+
+/// The syntax argument of [`RegexBuilder::syntax`]: a built-in static or
+/// an owned [`OnigSyntaxType`]. The compiled [`Regex`] keeps its own copy.
+#[derive(Clone, Debug)]
+pub enum SyntaxRef {
+    Static(&'static OnigSyntaxType),
+    Shared(Arc<OnigSyntaxType>),
+}
+
+impl SyntaxRef {
+    fn get(&self) -> &OnigSyntaxType {
+        match self {
+            SyntaxRef::Static(syntax) => syntax,
+            SyntaxRef::Shared(syntax) => syntax,
+        }
+    }
+}
+
+impl From<&'static OnigSyntaxType> for SyntaxRef {
+    fn from(syntax: &'static OnigSyntaxType) -> Self {
+        SyntaxRef::Static(syntax)
+    }
+}
+
+impl From<OnigSyntaxType> for SyntaxRef {
+    fn from(syntax: OnigSyntaxType) -> Self {
+        SyntaxRef::Shared(Arc::new(syntax))
+    }
+}
+
+impl From<Arc<OnigSyntaxType>> for SyntaxRef {
+    fn from(syntax: Arc<OnigSyntaxType>) -> Self {
+        SyntaxRef::Shared(syntax)
+    }
+}
+
+// === RegexCache ===
+
+/// A thread-safe cache of compiled regexes, keyed by pattern, options and
+/// syntax, that evicts the least recently used entry when full.
+///
+/// Errors are not cached: a pattern that fails to compile is
+/// compiled (and fails) again on the next request. Compiling happens
+/// outside the lock, so a slow pattern does not hold up other threads; when
+/// two threads compile the same pattern at once, both get the first one
+/// stored.
+///
+/// ```
+/// use ferroni::api::RegexCache;
+/// use ferroni::oniguruma::ONIG_OPTION_IGNORECASE;
+/// use ferroni::regsyntax::OnigSyntaxRuby;
+///
+/// let cache = RegexCache::new(2);
+/// let re = cache.get_with("ab+", ONIG_OPTION_IGNORECASE, &OnigSyntaxRuby).unwrap();
+/// assert!(re.is_match("xABB"));
+///
+/// cache.get("a").unwrap();
+/// cache.get("b").unwrap(); // evicts "ab+", the least recently used
+/// assert_eq!(cache.len(), 2);
+/// ```
+pub struct RegexCache {
+    capacity: usize,
+    state: Mutex<CacheState>,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct CacheKey {
+    pattern: Box<[u8]>,
+    options: u32,
+    // The syntax by value: op, op2, behavior, options and meta characters.
+    // The encoding is not part of the key; the cache compiles UTF-8 only.
+    syntax: [u32; 10],
+    // Process-wide settings the compile reads: the default case fold flag
+    // and text segment, and the generation of the user-defined properties.
+    case_fold_flag: OnigCaseFoldType,
+    text_segment: u8,
+    property_generation: u64,
+}
+
+struct CacheState {
+    entries: BTreeMap<Arc<CacheKey>, (Arc<Regex>, u64)>,
+    /// Keys by last use, oldest first.
+    lru: BTreeMap<u64, Arc<CacheKey>>,
+    clock: u64,
+}
+
+impl CacheState {
+    /// Mark `key` as just used and return its regex.
+    fn touch(&mut self, key: &CacheKey) -> Option<Arc<Regex>> {
+        let (regex, used) = self.entries.get_mut(key)?;
+        self.clock += 1;
+        if let Some(key) = self.lru.remove(used) {
+            self.lru.insert(self.clock, key);
+        }
+        *used = self.clock;
+        Some(regex.clone())
+    }
+}
+
+impl RegexCache {
+    /// A cache that holds at most `capacity` regexes. With capacity 0
+    /// nothing is kept and every request compiles.
+    pub const fn new(capacity: usize) -> Self {
+        RegexCache {
+            capacity,
+            state: Mutex::new(CacheState {
+                entries: BTreeMap::new(),
+                lru: BTreeMap::new(),
+                clock: 0,
+            }),
+        }
+    }
+
+    /// The crate-wide cache behind [`Regex::new_cached`], holding up to 256
+    /// regexes.
+    pub fn global() -> &'static RegexCache {
+        static GLOBAL: RegexCache = RegexCache::new(256);
+        &GLOBAL
+    }
+
+    /// The regex for `pattern` with default options and Oniguruma syntax,
+    /// compiled on first use.
+    pub fn get(&self, pattern: &str) -> Result<Arc<Regex>, RegexError> {
+        self.get_with(pattern, ONIG_OPTION_NONE, &OnigSyntaxOniguruma)
+    }
+
+    /// The regex for `pattern` with the given options and syntax, compiled
+    /// on first use. Syntaxes are compared by value, so equal syntaxes built
+    /// separately share entries.
+    ///
+    /// An entry is only reused under the global settings it was compiled
+    /// with: after `onig_set_default_case_fold_flag`,
+    /// `onig_set_default_text_segment` or a change to the user-defined
+    /// properties, the pattern is compiled again.
+    pub fn get_with(
+        &self,
+        pattern: &str,
+        options: OnigOptionType,
+        syntax: impl Into<SyntaxRef>,
+    ) -> Result<Arc<Regex>, RegexError> {
+        let syntax = syntax.into();
+        let key = CacheKey {
+            pattern: pattern.as_bytes().into(),
+            options: options.bits(),
+            syntax: syntax_key(syntax.get()),
+            case_fold_flag: onig_get_default_case_fold_flag(),
+            text_segment: match onig_get_default_text_segment() {
+                OnigTextSegment::ExtendedGraphemeCluster => 0,
+                OnigTextSegment::Word => 1,
+            },
+            property_generation: crate::unicode::user_property_generation(),
+        };
+        if let Some(regex) = self.state.lock().touch(&key) {
+            return Ok(regex);
+        }
+
+        let regex = Arc::new(
+            RegexBuilder::new(pattern)
+                .option(options)
+                .syntax(syntax)
+                .build()?,
+        );
+        if self.capacity == 0 {
+            return Ok(regex);
+        }
+
+        let mut state = self.state.lock();
+        // Another thread may have stored it meanwhile
+        if let Some(regex) = state.touch(&key) {
+            return Ok(regex);
+        }
+        if state.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = state.lru.pop_first() {
+                state.entries.remove(&oldest);
+            }
+        }
+        state.clock += 1;
+        let (key, clock) = (Arc::new(key), state.clock);
+        state.lru.insert(clock, key.clone());
+        state.entries.insert(key, (regex.clone(), clock));
+        Ok(regex)
+    }
+
+    /// Maximum number of regexes kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of regexes currently kept.
+    pub fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    /// Whether no regex is kept.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop all kept regexes. Regexes handed out stay valid.
+    pub fn clear(&self) {
+        let mut state = self.state.lock();
+        state.entries.clear();
+        state.lru.clear();
     }
+}
 
-    /// Return the number of capture groups in the pattern (excluding group 0).
-    pub fn captures_len(&self) -> usize {
-        self.inner.num_mem as usize
+impl core::fmt::Debug for RegexCache {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RegexCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish()
     }
+}
 
-    /// Access the underlying `RegexType` for advanced / C-style usage.
-    pub fn as_raw(&self) -> &RegexType {
-        &self.inner
+fn syntax_key(syntax: &OnigSyntaxType) -> [u32; 10] {
+    let meta = &syntax.meta_char_table;
+    [
+        syntax.op,
+        syntax.op2,
+        syntax.behavior,
+        syntax.options.bits(),
+        meta.esc,
+        meta.anychar,
+        meta.anytime,
+        meta.zero_or_one_time,
+        meta.one_or_more_time,
+        meta.anychar_anytime,
+    ]
+}
+
+// === Classes ===
+
+bitflags::bitflags! {
+    /// Character classes that [`RegexBuilder::ascii_classes`] can restrict
+    /// to ASCII.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub struct Classes: u32 {
+        /// `\w`, `\W`, `\b`, `\B`, `[[:word:]]` and `\p{Word}`.
+        const WORD = 1 << 0;
+        /// `\d`, `\D`, `[[:digit:]]` and `\p{Digit}`.
+        const DIGIT = 1 << 1;
+        /// `\s`, `\S`, `[[:space:]]` and `\p{Space}`.
+        const SPACE = 1 << 2;
+        /// All POSIX brackets (`[[:alpha:]]`, ...) and their `\p{...}`
+        /// names. As in Oniguruma this includes word, digit and space.
+        const POSIX = 1 << 3;
     }
 }
 
-impl std::fmt::Debug for Regex {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Regex").finish_non_exhaustive()
+impl Classes {
+    fn to_options(self) -> OnigOptionType {
+        const BITS: [(Classes, OnigOptionType); 4] = [
+            (Classes::WORD, ONIG_OPTION_WORD_IS_ASCII),
+            (Classes::DIGIT, ONIG_OPTION_DIGIT_IS_ASCII),
+            (Classes::SPACE, ONIG_OPTION_SPACE_IS_ASCII),
+            (Classes::POSIX, ONIG_OPTION_POSIX_IS_ASCII),
+        ];
+        BITS.iter()
+            .filter(|(class, _)| self.contains(*class))
+            .fold(ONIG_OPTION_NONE, |acc, (_, option)| acc | *option)
     }
 }
 
-// === RegexBuilder ===
+// === OptimizeInfo ===
+
+bitflags::bitflags! {
+    /// Position constraints the optimizer derived for every match.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub struct Anchors: u32 {
+        /// Matches start at the beginning of the text (`\A`).
+        const BEGIN_BUF = 1 << 0;
+        /// Matches start at the search start (`\G`).
+        const BEGIN_POSITION = 1 << 1;
+        /// Matches start at the beginning of a line (`^`).
+        const BEGIN_LINE = 1 << 2;
+        /// Matches end at the end of the text (`\z`).
+        const END_BUF = 1 << 3;
+        /// Matches end at the end of the text or before a final newline (`\Z`).
+        const SEMI_END_BUF = 1 << 4;
+        /// Matches end at the end of a line (`$`).
+        const END_LINE = 1 << 5;
+        /// The pattern starts with `.*`, so only line starts are tried.
+        const ANYCHAR_INF = 1 << 6;
+        /// The pattern starts with a multiline `.*`, so only the first
+        /// position is tried.
+        const ANYCHAR_INF_ML = 1 << 7;
+    }
+}
 
-/// Builder for compiling a [`Regex`] with custom options.
-///
-/// # Examples
-///
-/// ```
-/// use ferroni::api::Regex;
+impl Anchors {
+    fn from_raw(anchor: i32) -> Self {
+        const BITS: [(i32, Anchors); 8] = [
+            (ANCR_BEGIN_BUF, Anchors::BEGIN_BUF),
+            (ANCR_BEGIN_POSITION, Anchors::BEGIN_POSITION),
+            (ANCR_BEGIN_LINE, Anchors::BEGIN_LINE),
+            (ANCR_END_BUF, Anchors::END_BUF),
+            (ANCR_SEMI_END_BUF, Anchors::SEMI_END_BUF),
+            (ANCR_END_LINE, Anchors::END_LINE),
+            (ANCR_ANYCHAR_INF, Anchors::ANYCHAR_INF),
+            (ANCR_ANYCHAR_INF_ML, Anchors::ANYCHAR_INF_ML),
+        ];
+        BITS.iter()
+            .filter(|(raw, _)| anchor & raw != 0)
+            .fold(Anchors::empty(), |acc, &(_, flag)| acc | flag)
+    }
+}
+
+/// How the search finds candidate match positions before running the matcher.
 ///
-/// let re = Regex::builder(r"hello world")
-///     .case_insensitive(true)
-///     .build()
-///     .unwrap();
-/// assert!(re.is_match("Hello World"));
-/// ```
-pub struct RegexBuilder {
-    pattern: Vec<u8>,
-    options: OnigOptionType,
-    syntax: &'static OnigSyntaxType,
+/// Distances are byte offsets from the match start; a `None` maximum means
+/// unbounded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Prefilter {
+    /// No prefilter: every start position is tried.
+    None,
+    /// Every match contains `literal` this far from its start.
+    Exact {
+        literal: Vec<u8>,
+        min_distance: usize,
+        max_distance: Option<usize>,
+    },
+    /// Every match has one of `bytes` this far from its start.
+    Map {
+        bytes: Vec<u8>,
+        min_distance: usize,
+        max_distance: Option<usize>,
+    },
 }
 
-impl RegexBuilder {
-    /// Create a new builder for the given pattern.
-    pub fn new(pattern: &str) -> Self {
-        RegexBuilder {
-            pattern: pattern.as_bytes().to_vec(),
-            options: ONIG_OPTION_NONE,
-            syntax: &OnigSyntaxOniguruma,
-        }
-    }
+/// How forward searches run a pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Backend {
+    /// The backtracking VM, tried at each candidate position.
+    Vm,
+    /// A bit-parallel Shift-Or scan over the whole text, no VM involved.
+    ///
+    /// Chosen when the pattern is a fixed sequence of at most 64
+    /// single-byte positions (literal bytes, classes, character types limited
+    /// to ASCII such as `(?D)\d`, and fixed repeats of them) with no
+    /// captures, anchors, alternation,
+    /// look-around or case folding, and is not a plain literal. In UTF-8
+    /// every position must be ASCII; other multi-byte encodings always use
+    /// the VM. `FIND_LONGEST` and `MATCH_WHOLE_STRING` searches, backward
+    /// searches and anchored matches run the VM either way.
+    ShiftOr {
+        /// Number of positions, which is also the match length in bytes.
+        positions: usize,
+    },
+}
 
-    /// Enable or disable case-insensitive matching.
-    pub fn case_insensitive(mut self, yes: bool) -> Self {
-        if yes {
-            self.options |= ONIG_OPTION_IGNORECASE;
-        } else {
-            self.options &= !ONIG_OPTION_IGNORECASE;
-        }
-        self
-    }
+/// What the compiler's optimizer inferred about a pattern, returned by
+/// [`Regex::optimize_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct OptimizeInfo {
+    /// Anchors that constrain where a match can start or end.
+    pub anchors: Anchors,
+    /// Line anchors next to the prefilter's literal or byte set.
+    pub sub_anchors: Anchors,
+    /// How candidate positions are found.
+    pub prefilter: Prefilter,
+    /// Shortest possible match, in bytes.
+    pub min_len: usize,
+    /// Longest possible match, in bytes, or `None` if unbounded.
+    pub max_len: Option<usize>,
+    /// How forward searches run the pattern.
+    pub backend: Backend,
+}
 
-    /// Enable or disable multiline mode (`.` matches `\n`).
-    pub fn dot_matches_newline(mut self, yes: bool) -> Self {
-        if yes {
-            self.options |= ONIG_OPTION_MULTILINE;
-        } else {
-            self.options &= !ONIG_OPTION_MULTILINE;
+impl OptimizeInfo {
+    /// Bytes a non-empty match can start with, or `None` if any byte may.
+    ///
+    /// Useful for bucketing many patterns by the first byte of the text.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let info = Regex::new(r"[ab]|c").unwrap().optimize_info();
+    /// assert_eq!(info.first_bytes(), Some(vec![b'a', b'b', b'c']));
+    ///
+    /// // `\w+` gets no prefilter, so nothing is known.
+    /// let info = Regex::new(r"\w+").unwrap().optimize_info();
+    /// assert_eq!(info.first_bytes(), None);
+    /// ```
+    pub fn first_bytes(&self) -> Option<Vec<u8>> {
+        if self.min_len == 0 {
+            return None;
+        }
+        match &self.prefilter {
+            Prefilter::Exact {
+                literal,
+                min_distance: 0,
+                max_distance: Some(0),
+            } => literal.first().map(|&b| vec![b]),
+            Prefilter::Map {
+                bytes,
+                min_distance: 0,
+                max_distance: Some(0),
+            } => Some(bytes.clone()),
+            _ => None,
         }
-        self
     }
+}
 
-    /// Enable or disable `^`/`$` matching at every line boundary.
-    pub fn multi_line_anchors(mut self, yes: bool) -> Self {
-        if yes {
-            self.options |= ONIG_OPTION_SINGLELINE;
-        } else {
-            self.options &= !ONIG_OPTION_SINGLELINE;
-        }
-        self
+/// Bytes a compiled pattern and its search state hold, by part, returned by
+/// [`Regex::memory_usage`], [`Scanner::memory_usage`](crate::scanner::Scanner::memory_usage)
+/// and [`onig_regset_memory_usage`](crate::regset::onig_regset_memory_usage).
+///
+/// An estimate for sizing caches: vectors count their capacity, maps count
+/// their entries but not their nodes, and allocator overhead is left out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MemoryBreakdown {
+    /// Instructions, their literal strings, and the repeat and call tables.
+    pub bytecode: usize,
+    /// Character class bitsets and code point range buffers.
+    pub char_classes: usize,
+    /// The group name table and group nesting.
+    pub names: usize,
+    /// Prefilter literals, the byte map, the SIMD searcher and Shift-Or masks.
+    pub optimization: usize,
+    /// Capture regions, caches and VM stacks kept between searches. Zero for
+    /// a `Regex`, whose searches keep no state.
+    pub search_state: usize,
+    /// The structs themselves, the pattern text, warnings and callouts.
+    pub other: usize,
+}
+
+impl MemoryBreakdown {
+    /// All parts together, in bytes.
+    pub fn total(&self) -> usize {
+        self.bytecode
+            + self.char_classes
+            + self.names
+            + self.optimization
+            + self.search_state
+            + self.other
     }
+}
 
-    /// Enable or disable extended mode (whitespace and `#` comments ignored).
-    pub fn extended(mut self, yes: bool) -> Self {
-        if yes {
-            self.options |= ONIG_OPTION_EXTEND;
-        } else {
-            self.options &= !ONIG_OPTION_EXTEND;
+impl core::ops::Add for MemoryBreakdown {
+    type Output = MemoryBreakdown;
+
+    fn add(self, rhs: MemoryBreakdown) -> MemoryBreakdown {
+        MemoryBreakdown {
+            bytecode: self.bytecode + rhs.bytecode,
+            char_classes: self.char_classes + rhs.char_classes,
+            names: self.names + rhs.names,
+            optimization: self.optimization + rhs.optimization,
+            search_state: self.search_state + rhs.search_state,
+            other: self.other + rhs.other,
         }
-        self
     }
+}
 
-    /// Set a raw option flag. See `ONIG_OPTION_*` constants.
-    pub fn option(mut self, flag: OnigOptionType) -> Self {
-        self.options |= flag;
-        self
+impl core::ops::AddAssign for MemoryBreakdown {
+    fn add_assign(&mut self, rhs: MemoryBreakdown) {
+        *self = *self + rhs;
     }
+}
 
-    /// Select the syntax definition to use (default: Oniguruma).
-    ///
-    /// Pass one of the `OnigSyntax*` statics from [`crate::regsyntax`].
-    pub fn syntax(mut self, syntax: &'static OnigSyntaxType) -> Self {
-        self.syntax = syntax;
-        self
+impl core::iter::Sum for MemoryBreakdown {
+    fn sum<I: Iterator<Item = MemoryBreakdown>>(iter: I) -> MemoryBreakdown {
+        iter.fold(MemoryBreakdown::default(), |a, b| a + b)
     }
+}
 
-    /// Compile the pattern into a [`Regex`].
-    pub fn build(self) -> Result<Regex, RegexError> {
-        let inner = onig_new(
-            &self.pattern,
-            self.options,
-            &ONIG_ENCODING_UTF8,
-            self.syntax,
-        )?;
-        Ok(Regex { inner })
+fn finite_len(len: OnigLen) -> Option<usize> {
+    (len != INFINITE_LEN).then_some(len as usize)
+}
+
+// === MatchStats ===
+
+/// How a [`Regex::is_match_with_stats`] call was executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MatchStats {
+    /// Whether the literal pre-scan ran.
+    pub prescan_used: bool,
+    /// Candidate windows the pre-scan found for the matcher.
+    pub prescan_windows: usize,
+    /// Number of times the matcher was invoked.
+    pub vm_searches: usize,
+}
+
+// === BudgetExceeded ===
+
+/// Returned by [`Regex::find_all_within`] and
+/// [`Regex::captures_all_within`] when the results would take more than the
+/// allowed number of bytes.
+#[derive(Debug)]
+pub struct BudgetExceeded<T> {
+    /// The results collected before the budget ran out, in match order.
+    pub partial: Vec<T>,
+    /// The budget in bytes.
+    pub limit: usize,
+}
+
+impl<T> core::fmt::Display for BudgetExceeded<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "match memory budget of {} bytes exceeded after {} results",
+            self.limit,
+            self.partial.len()
+        )
     }
 }
 
+impl<T: core::fmt::Debug> core::error::Error for BudgetExceeded<T> {}
+
 // === Match ===
 
 /// A single match result referencing the original text.
 #[derive(Debug, Clone, Copy)]
 pub struct Match<'t> {
     text: &'t [u8],
-    start: usize,
-    end: usize,
+    start: Position,
+    end: Position,
 }
 
 impl<'t> Match<'t> {
     /// Byte offset of the start of the match.
-    pub fn start(&self) -> usize {
+    pub fn start(&self) -> Position {
         self.start
     }
 
     /// Byte offset of the end of the match (exclusive).
-    pub fn end(&self) -> usize {
+    pub fn end(&self) -> Position {
         self.end
     }
 
     /// Byte range of the match.
-    pub fn range(&self) -> Range<usize> {
+    pub fn range(&self) -> Range<Position> {
         self.start..self.end
     }
 
@@ -299,7 +2137,7 @@ impl<'t> Match<'t> {
     ///
     /// Panics if the matched bytes are not valid UTF-8.
     pub fn as_str(&self) -> &'t str {
-        std::str::from_utf8(self.as_bytes()).expect("match is not valid UTF-8")
+        core::str::from_utf8(self.as_bytes()).expect("match is not valid UTF-8")
     }
 
     /// Returns the length of the match in bytes.
@@ -311,6 +2149,32 @@ impl<'t> Match<'t> {
     pub fn is_empty(&self) -> bool {
         self.start == self.end
     }
+
+    /// Offset of the start of the match in the units `index` counts;
+    /// `index` must be built for the haystack the match is in.
+    pub fn char_start(&self, index: &CharIndex<'_>) -> usize {
+        index.to_char(self.start)
+    }
+
+    /// Offset of the end of the match in the units `index` counts.
+    pub fn char_end(&self, index: &CharIndex<'_>) -> usize {
+        index.to_char(self.end)
+    }
+
+    /// Range of the match in the units `index` counts (characters or
+    /// UTF-16 code units); see [`CharIndex`].
+    pub fn char_range(&self, index: &CharIndex<'_>) -> Range<usize> {
+        debug_assert_eq!(index.haystack().len(), self.text.len());
+        index.to_char_range(self.range())
+    }
+}
+
+// Serialized as `{"start": .., "end": ..}`, which deserializes as `Range<usize>`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Match<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&self.range(), serializer)
+    }
 }
 
 // === Captures ===
@@ -329,18 +2193,11 @@ impl<'t> Captures<'t> {
     ///
     /// Group 0 is the entire match.
     pub fn get(&self, i: usize) -> Option<Match<'t>> {
-        if i >= self.region.num_regs as usize {
-            return None;
-        }
-        let beg = self.region.beg[i];
-        let end = self.region.end[i];
-        if beg == ONIG_REGION_NOTPOS {
-            return None;
-        }
+        let m = self.region.get(i)?;
         Some(Match {
             text: self.text,
-            start: beg as usize,
-            end: end as usize,
+            start: m.start,
+            end: m.end,
         })
     }
 
@@ -373,10 +2230,103 @@ impl<'t> Captures<'t> {
             index: 0,
         }
     }
+
+    /// Detach the group spans and group names from the text and regex.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let re = Regex::new(r"(?<y>\d{4})-(?<m>\d\d)?").unwrap();
+    /// let spans = re.captures("on 2026-!").unwrap().to_spans();
+    /// assert_eq!(spans.spans, vec![Some((3, 8)), Some((3, 7)), None]);
+    /// assert_eq!(spans.name("y"), Some(3..7));
+    /// ```
+    pub fn to_spans(&self) -> CaptureSpans {
+        let mut names = Vec::new();
+        onig_foreach_name(&self.regex.inner, |name, groups| {
+            let groups = groups.iter().map(|&g| g as u32).collect();
+            names.push((String::from_utf8_lossy(name).into_owned(), groups));
+            0
+        });
+        names.sort();
+        CaptureSpans {
+            spans: self.region.to_spans(),
+            names,
+        }
+    }
+
+    /// The byte range of every group name, from the first group of that
+    /// name that participated, `None` if none did. One pass over the name
+    /// table; see [`OnigRegion::named_spans`]. A [`NamedSpans`] map, so
+    /// without the `std` feature the names come out sorted.
+    ///
+    /// ```
+    /// use ferroni::api::Regex;
+    ///
+    /// let re = Regex::new(r"(?<k>\w+)(?:=(?<v>\w+))?").unwrap();
+    /// let map = re.captures("flag").unwrap().to_map();
+    /// assert_eq!(map["k"], Some(0..4));
+    /// assert_eq!(map["v"], None);
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    pub fn to_map(&self) -> NamedSpans {
+        self.region.named_span_entries(&self.regex.inner).collect()
+    }
+}
+
+/// Map of group names to byte ranges returned by [`Captures::to_map`]: a
+/// `HashMap` with the `std` feature, a `BTreeMap` without.
+#[cfg(feature = "std")]
+pub type NamedSpans = std::collections::HashMap<String, Option<Range<Position>>>;
+/// Map of group names to byte ranges returned by [`Captures::to_map`]: a
+/// `HashMap` with the `std` feature, a `BTreeMap` without.
+#[cfg(not(feature = "std"))]
+pub type NamedSpans = BTreeMap<String, Option<Range<Position>>>;
+
+// === CaptureSpans ===
+
+/// Owned capture group spans of a single match, with the group names of the
+/// pattern that produced them.
+///
+/// Unlike [`Captures`], this borrows neither the text nor the regex, so it
+/// can be stored or (with the `serde` feature) sent to another process.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CaptureSpans {
+    /// `(start, end)` byte offsets per group, `None` if it did not participate.
+    pub spans: Vec<Option<(u32, u32)>>,
+    /// Group names with their group numbers, sorted by name.
+    pub names: Vec<(String, Vec<u32>)>,
+}
+
+impl CaptureSpans {
+    /// Byte range of group `i`, or `None` if the group did not participate.
+    pub fn get(&self, i: usize) -> Option<Range<Position>> {
+        let (start, end) = (*self.spans.get(i)?)?;
+        Some(start as Position..end as Position)
+    }
+
+    /// Byte range of the first participating group with the given name.
+    pub fn name(&self, name: &str) -> Option<Range<Position>> {
+        let (_, groups) = self.names.iter().find(|(n, _)| n == name)?;
+        groups.iter().find_map(|&g| self.get(g as usize))
+    }
+
+    /// Number of capture groups (including group 0).
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Returns `true` if there are no capture groups.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
 }
 
-impl std::fmt::Debug for Captures<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for Captures<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let mut list = f.debug_list();
         for i in 0..self.len() {
             list.entry(&self.get(i));
@@ -385,6 +2335,14 @@ impl std::fmt::Debug for Captures<'_> {
     }
 }
 
+// Serialized as its `CaptureSpans`, which is also the deserialization target.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Captures<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&self.to_spans(), serializer)
+    }
+}
+
 // === CapturesIter ===
 
 /// Iterator over capture groups in a [`Captures`].
@@ -413,14 +2371,144 @@ impl<'c, 't> Iterator for CapturesIter<'c, 't> {
 
 impl ExactSizeIterator for CapturesIter<'_, '_> {}
 
+// === GroupNames ===
+
+/// Iterator over the named groups of a [`Regex`], returned by
+/// [`Regex::name_table`].
+pub struct GroupNames<'r> {
+    entries: alloc::vec::IntoIter<&'r NameEntry>,
+}
+
+impl<'r> Iterator for GroupNames<'r> {
+    type Item = (&'r str, Vec<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for entry in self.entries.by_ref() {
+            // Names were validated as UTF-8 when the pattern was parsed.
+            if let Ok(name) = core::str::from_utf8(&entry.name) {
+                let groups = entry.back_refs.iter().map(|&g| g as usize).collect();
+                return Some((name, groups));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.entries.len()))
+    }
+}
+
+// === GroupNumbers ===
+
+/// The group numbers of one name, borrowed from the regex; yielded by
+/// [`Regex::capture_names`].
+#[derive(Clone, Debug)]
+pub struct GroupNumbers<'r> {
+    groups: core::slice::Iter<'r, i32>,
+}
+
+impl Iterator for GroupNumbers<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.groups.next().map(|&g| g as u32)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.groups.size_hint()
+    }
+}
+
+impl ExactSizeIterator for GroupNumbers<'_> {}
+
 // === FindIter ===
 
 /// Iterator over all non-overlapping matches in a text.
+///
+/// Besides yielding matches, the iterator remembers where the previous match
+/// ended and the last span each capture group took, so `\G`-style
+/// continuation or delta encoding needs no parallel bookkeeping.
+///
+/// # Example
+///
+/// ```
+/// use ferroni::prelude::*;
+///
+/// let re = Regex::new(r"(a)|(b)").unwrap();
+/// let mut it = re.find_iter("ab-a");
+/// it.next();
+/// it.next();
+/// assert_eq!(it.last_match_end(), Some(2));
+/// assert_eq!(it.last_group(1), Some(0..1));
+/// assert_eq!(it.last_group(2), Some(1..2));
+///
+/// it.reset("bb");
+/// assert_eq!(it.last_match_end(), None);
+/// assert_eq!(it.count(), 2);
+/// ```
 pub struct FindIter<'r, 't> {
     regex: &'r Regex,
     text: &'t [u8],
     last_end: usize,
     last_was_empty: bool,
+    /// Region and VM state, reused across searches and `reset`.
+    region: Option<OnigRegion>,
+    state: ExecState,
+    prev_end: Option<Position>,
+    groups: Vec<Option<Range<Position>>>,
+}
+
+impl<'r, 't> FindIter<'r, 't> {
+    /// End of the most recently yielded match, or `None` before the first.
+    pub fn last_match_end(&self) -> Option<Position> {
+        self.prev_end
+    }
+
+    /// Span of group `i` in the most recent match it participated in.
+    ///
+    /// Group 0 is the previous match itself. Returns `None` if the group has
+    /// not matched yet on this haystack.
+    pub fn last_group(&self, i: usize) -> Option<Range<Position>> {
+        self.groups.get(i).cloned().flatten()
+    }
+
+    /// Restart iteration on a new haystack, keeping allocated buffers.
+    pub fn reset(&mut self, text: &'t str) {
+        self.reset_bytes(text.as_bytes());
+    }
+
+    /// Restart iteration on a new haystack (as bytes), keeping allocated buffers.
+    pub fn reset_bytes(&mut self, text: &'t [u8]) {
+        check_haystack_len(text);
+        self.text = text;
+        self.last_end = 0;
+        self.last_was_empty = false;
+        self.prev_end = None;
+        self.groups.iter_mut().for_each(|g| *g = None);
+    }
+
+    /// Whether `next` would return another match. Searches without a
+    /// region unless the last match was empty, where `next` needs the span
+    /// of what it finds to decide.
+    fn has_more(&mut self) -> bool {
+        if self.last_was_empty {
+            return self.next().is_some();
+        }
+        if self.last_end > self.text.len() {
+            return false;
+        }
+        let len = self.text.len();
+        let (result, _) = onig_search(
+            &self.regex.inner,
+            self.text,
+            len,
+            self.last_end,
+            len,
+            None,
+            ONIG_OPTION_NONE,
+        );
+        result >= 0
+    }
 }
 
 impl<'r, 't> Iterator for FindIter<'r, 't> {
@@ -431,27 +2519,28 @@ impl<'r, 't> Iterator for FindIter<'r, 't> {
             return None;
         }
 
-        let (result, region) = onig_search(
-            &self.regex.inner,
+        let reg = &self.regex.inner;
+        let start = self.last_end;
+        let region = self.region.take().unwrap_or_default();
+        let end = self.text.len();
+        let (result, region) = onig_search_with_state(
+            reg,
+            &mut self.state,
             self.text,
-            self.text.len(),
-            self.last_end,
-            self.text.len(),
-            Some(OnigRegion::new()),
+            end,
+            start,
+            end,
+            Some(region),
             ONIG_OPTION_NONE,
         );
+        self.region = region;
 
         if result < 0 {
             return None;
         }
 
-        let region = region?;
-        if region.num_regs < 1 {
-            return None;
-        }
-
-        let start = region.beg[0] as usize;
-        let end = region.end[0] as usize;
+        let region = self.region.as_ref()?;
+        let Range { start, end } = region.get(0)?;
 
         // Handle empty matches: advance by one byte to avoid infinite loop.
         if start == end {
@@ -474,6 +2563,17 @@ impl<'r, 't> Iterator for FindIter<'r, 't> {
         }
 
         self.last_end = end;
+        self.prev_end = Some(end);
+
+        let n = region.num_regs as usize;
+        if self.groups.len() < n {
+            self.groups.resize(n, None);
+        }
+        for (i, r) in region.iter().enumerate() {
+            if r.is_some() {
+                self.groups[i] = r;
+            }
+        }
 
         Some(Match {
             text: self.text,
@@ -483,6 +2583,104 @@ impl<'r, 't> Iterator for FindIter<'r, 't> {
     }
 }
 
+// === Split ===
+
+/// Iterator over the text between matches, returned by [`Regex::split`].
+pub struct Split<'r, 't> {
+    finder: FindIter<'r, 't>,
+    text: &'t str,
+    last: usize,
+    done: bool,
+}
+
+impl<'r, 't> Split<'r, 't> {
+    /// The next segment, and the delimiter match ending it (none for the
+    /// last segment).
+    fn next_segment(&mut self) -> Option<(&'t str, Option<Match<'t>>)> {
+        if self.done {
+            return None;
+        }
+        match self.finder.next() {
+            Some(m) => {
+                let segment = &self.text[self.last..m.start()];
+                self.last = m.end();
+                Some((segment, Some(m)))
+            }
+            None => {
+                self.done = true;
+                Some((&self.text[self.last..], None))
+            }
+        }
+    }
+}
+
+impl<'r, 't> Iterator for Split<'r, 't> {
+    type Item = &'t str;
+
+    fn next(&mut self) -> Option<&'t str> {
+        self.next_segment().map(|(segment, _)| segment)
+    }
+}
+
+/// Iterator over segments ending with their delimiter, returned by
+/// [`Regex::split_inclusive`].
+pub struct SplitInclusive<'r, 't> {
+    finder: FindIter<'r, 't>,
+    text: &'t str,
+    last: usize,
+}
+
+impl<'r, 't> Iterator for SplitInclusive<'r, 't> {
+    type Item = &'t str;
+
+    fn next(&mut self) -> Option<&'t str> {
+        let start = self.last;
+        match self.finder.next() {
+            Some(m) => self.last = m.end(),
+            None if start < self.text.len() => self.last = self.text.len(),
+            None => return None,
+        }
+        Some(&self.text[start..self.last])
+    }
+}
+
+/// An item of [`Regex::split_keep`].
+#[derive(Debug)]
+pub enum SplitItem<'t> {
+    /// Text between delimiters; may be empty.
+    Text(&'t str),
+    /// A delimiter match; group 0 is the whole delimiter.
+    Delimiter(Captures<'t>),
+}
+
+/// Iterator over text segments and delimiters, returned by
+/// [`Regex::split_keep`].
+pub struct SplitKeep<'t> {
+    split: Split<'t, 't>,
+    pending: Option<Captures<'t>>,
+}
+
+impl<'t> Iterator for SplitKeep<'t> {
+    type Item = SplitItem<'t>;
+
+    fn next(&mut self) -> Option<SplitItem<'t>> {
+        if let Some(caps) = self.pending.take() {
+            return Some(SplitItem::Delimiter(caps));
+        }
+        let (segment, delimiter) = self.split.next_segment()?;
+        if delimiter.is_some() {
+            // The finder's region still holds the delimiter's groups
+            let finder = &self.split.finder;
+            self.pending = Some(Captures {
+                text: finder.text,
+                region: finder.region.clone()?,
+                regex: finder.regex,
+            });
+        }
+        Some(SplitItem::Text(segment))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -581,6 +2779,14 @@ mod tests {
         assert!(caps.name("day").is_none());
     }
 
+    #[test]
+    fn literal_ignores_metacharacters() {
+        let re = Regex::literal(r"(a+)\d").unwrap();
+        assert_eq!(re.captures_len(), 0);
+        assert_eq!(re.find(r"x(a+)\dy").unwrap().range(), 1..7);
+        assert!(!re.is_match("aa1"));
+    }
+
     #[test]
     fn empty_match_find_iter() {
         let re = Regex::new(r"").unwrap();
@@ -0,0 +1,686 @@
+// ast.rs - Read-only syntax tree for a pattern (parse without compiling).
+//
+// The parser's own node tree (regparse_types::Node) is an internal,
+// C-shaped structure that the compiler rewrites freely. This module copies
+// it into a small owned tree with stable node kinds and byte spans into the
+// pattern, for linters and grammar-analysis tools.
+
+use alloc::collections::BTreeMap;
+use core::ops::Range;
+
+use crate::encodings::utf8::ONIG_ENCODING_UTF8;
+use crate::error::RegexError;
+use crate::oniguruma::*;
+use crate::regcomp::onig_parse_only;
+use crate::regint::*;
+use crate::regparse_types::*;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+
+/// Parse `pattern` with `syntax` and return its syntax tree.
+///
+/// The pattern is only parsed, not compiled, so errors that the compiler
+/// reports (e.g. invalid look-behind bodies) are not detected here.
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::ast::{AstKind, GroupKind};
+/// use ferroni::regsyntax::OnigSyntaxOniguruma;
+///
+/// let ast = ferroni::parse(r"(?<year>\d{4})-\d\d", &OnigSyntaxOniguruma).unwrap();
+/// let AstKind::Concat(items) = &ast.root.kind else { panic!() };
+/// let AstKind::Group { kind: GroupKind::Capture { index, name }, .. } = &items[0].kind else {
+///     panic!()
+/// };
+/// assert_eq!((*index, name.as_deref()), (1, Some("year")));
+/// assert_eq!(items[0].span, Some(0..14));
+/// ```
+pub fn parse(pattern: &str, syntax: &OnigSyntaxType) -> Result<Ast, RegexError> {
+    parse_with_options(pattern.as_bytes(), ONIG_OPTION_NONE, syntax)
+}
+
+/// [`parse`] with compile options, which change how some patterns parse
+/// (e.g. `ONIG_OPTION_EXTEND`).
+pub(crate) fn parse_with_options(
+    pattern: &[u8],
+    options: OnigOptionType,
+    syntax: &OnigSyntaxType,
+) -> Result<Ast, RegexError> {
+    let (root, reg) = onig_parse_only(pattern, options, &ONIG_ENCODING_UTF8, syntax)?;
+
+    let mut names = BTreeMap::new();
+    if let Some(ref table) = reg.name_table {
+        for entry in table.entries.values() {
+            for &num in &entry.back_refs {
+                names.insert(num, String::from_utf8_lossy(&entry.name).into_owned());
+            }
+        }
+    }
+
+    // With CAPTURE_ONLY_NAMED_GROUP, plain groups stop capturing once a
+    // named group exists and the named ones are renumbered from 1.
+    let mut numbers = BTreeMap::new();
+    let named_only = !names.is_empty()
+        && is_syntax_bv(syntax, ONIG_SYN_CAPTURE_ONLY_NAMED_GROUP)
+        && !opton_capture_group(reg.options);
+    if named_only {
+        let mut named: Vec<i32> = names.keys().copied().collect();
+        named.sort_unstable();
+        for (i, num) in named.into_iter().enumerate() {
+            numbers.insert(num, i + 1);
+        }
+    }
+
+    let cx = Context {
+        names,
+        numbers,
+        named_only,
+    };
+
+    // \g<0> makes the parser wrap the whole pattern in an unnamed group 0.
+    let root = match &root.inner {
+        NodeInner::Bag(bn) if root.span.is_none() && bn.bag_type == BagType::Memory => {
+            bn.body.as_deref().unwrap_or(&root)
+        }
+        _ => &root,
+    };
+
+    Ok(Ast {
+        root: cx.convert(root),
+        captures: if named_only {
+            cx.numbers.len()
+        } else {
+            reg.num_mem as usize
+        },
+    })
+}
+
+/// Syntax tree of a pattern, as returned by [`parse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ast {
+    /// Top-level node.
+    pub root: AstNode,
+    /// Number of capture groups, not counting the whole match.
+    pub captures: usize,
+}
+
+impl Ast {
+    /// Iterate over all nodes in pre-order, starting with the root.
+    pub fn iter(&self) -> AstIter<'_> {
+        AstIter {
+            stack: vec![&self.root],
+        }
+    }
+}
+
+/// Pre-order iterator over the nodes of an [`Ast`].
+pub struct AstIter<'a> {
+    stack: Vec<&'a AstNode>,
+}
+
+impl<'a> Iterator for AstIter<'a> {
+    type Item = &'a AstNode;
+
+    fn next(&mut self) -> Option<&'a AstNode> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.children().into_iter().rev());
+        Some(node)
+    }
+}
+
+/// One node of an [`Ast`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AstNode {
+    /// What the node matches.
+    pub kind: AstKind,
+    /// Byte range of the pattern the node was parsed from. `None` for parts
+    /// of the tree that have no source text of their own.
+    pub span: Option<Range<usize>>,
+}
+
+impl AstNode {
+    /// Direct children, in pattern order.
+    pub fn children(&self) -> Vec<&AstNode> {
+        match &self.kind {
+            AstKind::Group { body, .. } | AstKind::Quantifier { body, .. } => vec![body],
+            AstKind::Conditional { condition, yes, no } => core::iter::once(condition.as_ref())
+                .chain(yes.as_deref())
+                .chain(no.as_deref())
+                .collect(),
+            AstKind::Concat(items) | AstKind::Alternation(items) => items.iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Node kinds of an [`Ast`].
+///
+/// Non-capturing groups `(?:...)` leave no node of their own: their body
+/// appears in their place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AstKind {
+    /// Matches the empty string (e.g. an empty alternative).
+    Empty,
+    /// A run of literal characters.
+    Literal(String),
+    /// A single character from a set.
+    Class(ClassKind),
+    /// A parenthesized group.
+    Group { kind: GroupKind, body: Box<AstNode> },
+    /// A repeated expression. `max` is `None` for unbounded repeats.
+    Quantifier {
+        min: u32,
+        max: Option<u32>,
+        greedy: bool,
+        possessive: bool,
+        body: Box<AstNode>,
+    },
+    /// A zero-width assertion.
+    Anchor(AnchorKind),
+    /// A backreference such as `\1` or `\k<name>`, with the group numbers it
+    /// may refer to.
+    Backref { groups: Vec<usize>, by_name: bool },
+    /// A subexpression call such as `\g<1>` or `\g<name>`.
+    Call {
+        group: Option<usize>,
+        name: Option<String>,
+    },
+    /// `(?(cond)yes|no)`.
+    Conditional {
+        condition: Box<AstNode>,
+        yes: Option<Box<AstNode>>,
+        no: Option<Box<AstNode>>,
+    },
+    /// A sequence of nodes matched one after another.
+    Concat(Vec<AstNode>),
+    /// Alternatives separated by `|`.
+    Alternation(Vec<AstNode>),
+    /// A construct without its own node kind (e.g. `\K`, absent groups,
+    /// callouts); see its span for the source text.
+    Other,
+}
+
+/// Kinds of [`AstKind::Class`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ClassKind {
+    /// A bracket class, shorthand (`\d`, `\s`, `\h`) or property (`\p{L}`).
+    Set { negated: bool },
+    /// `\w` or `\W`.
+    Word { negated: bool },
+    /// `.`, `\N` or `\O`.
+    Any,
+    /// `\R`.
+    Newline,
+    /// `\X`.
+    Grapheme,
+}
+
+/// Kinds of [`AstKind::Group`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GroupKind {
+    /// A capture group with its group number and optional name.
+    Capture { index: usize, name: Option<String> },
+    /// A plain `(...)` group that does not capture because the pattern has
+    /// named groups.
+    NonCapturing,
+    /// `(?>...)`.
+    Atomic,
+    /// `(?i:...)`, or the rest of the enclosing group after `(?i)`.
+    Options,
+    /// `(?=...)`.
+    LookAhead,
+    /// `(?!...)`.
+    NegativeLookAhead,
+    /// `(?<=...)`.
+    LookBehind,
+    /// `(?<!...)`.
+    NegativeLookBehind,
+}
+
+/// Kinds of [`AstKind::Anchor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AnchorKind {
+    /// `^`.
+    StartLine,
+    /// `$`.
+    EndLine,
+    /// `\A`.
+    StartText,
+    /// `\z`.
+    EndText,
+    /// `\Z`: end of text or before a final newline.
+    EndTextOptionalNewline,
+    /// `\G`.
+    SearchStart,
+    /// `\b`.
+    WordBoundary,
+    /// `\B`.
+    NotWordBoundary,
+    /// `\<` (GNU syntaxes).
+    WordStart,
+    /// `\>` (GNU syntaxes).
+    WordEnd,
+    /// `\y`.
+    TextSegmentBoundary,
+    /// `\Y`.
+    NotTextSegmentBoundary,
+}
+
+struct Context {
+    names: BTreeMap<i32, String>,
+    numbers: BTreeMap<i32, usize>,
+    named_only: bool,
+}
+
+impl Context {
+    fn group_number(&self, num: i32) -> usize {
+        self.numbers.get(&num).copied().unwrap_or(num as usize)
+    }
+
+    fn convert(&self, node: &Node) -> AstNode {
+        AstNode {
+            kind: self.convert_kind(node),
+            span: node.span.map(|(s, e)| s..e),
+        }
+    }
+
+    fn convert_box(&self, node: &Node) -> Box<AstNode> {
+        Box::new(self.convert(node))
+    }
+
+    fn convert_kind(&self, node: &Node) -> AstKind {
+        // Escapes the parser expands into a subtree (\R, \X, absent groups)
+        // carry a span only at the top.
+        if node.span.is_some() && has_synthesized_child(node) {
+            return match &node.inner {
+                NodeInner::Bag(bn) if bn.bag_type == BagType::IfElse => {
+                    AstKind::Class(ClassKind::Newline)
+                }
+                NodeInner::Bag(bn) if bn.bag_type == BagType::StopBacktrack => {
+                    AstKind::Class(ClassKind::Grapheme)
+                }
+                _ => AstKind::Other,
+            };
+        }
+
+        match &node.inner {
+            NodeInner::String(sn) => {
+                if sn.s.is_empty() {
+                    AstKind::Empty
+                } else {
+                    AstKind::Literal(String::from_utf8_lossy(&sn.s).into_owned())
+                }
+            }
+            NodeInner::CClass(cc) => AstKind::Class(ClassKind::Set {
+                negated: cc.is_not(),
+            }),
+            NodeInner::CType(ct) => {
+                if ct.ctype == CTYPE_ANYCHAR {
+                    AstKind::Class(ClassKind::Any)
+                } else {
+                    AstKind::Class(ClassKind::Word { negated: ct.not })
+                }
+            }
+            NodeInner::BackRef(br) => AstKind::Backref {
+                groups: br
+                    .back_refs()
+                    .iter()
+                    .map(|&n| self.group_number(n))
+                    .collect(),
+                by_name: node.has_status(ND_ST_BY_NAME),
+            },
+            NodeInner::Quant(qn) => match qn.body {
+                Some(ref body) => AstKind::Quantifier {
+                    min: qn.lower as u32,
+                    max: if qn.upper == INFINITE_REPEAT {
+                        None
+                    } else {
+                        Some(qn.upper as u32)
+                    },
+                    greedy: qn.greedy,
+                    possessive: false,
+                    body: self.convert_box(body),
+                },
+                None => AstKind::Other,
+            },
+            NodeInner::Bag(bn) => {
+                let Some(ref body) = bn.body else {
+                    return AstKind::Other;
+                };
+                let kind = match bn.bag_data {
+                    BagData::Memory { regnum, .. } => {
+                        let name = self.names.get(&regnum).cloned();
+                        if self.named_only && name.is_none() {
+                            GroupKind::NonCapturing
+                        } else {
+                            GroupKind::Capture {
+                                index: self.group_number(regnum),
+                                name,
+                            }
+                        }
+                    }
+                    BagData::Option { .. } => GroupKind::Options,
+                    BagData::StopBacktrack => {
+                        // a*+ is parsed as an atomic group around a*, both
+                        // covering the same text.
+                        if let NodeInner::Quant(_) = body.inner {
+                            if body.span == node.span {
+                                let mut kind = self.convert_kind(body);
+                                if let AstKind::Quantifier {
+                                    ref mut possessive, ..
+                                } = kind
+                                {
+                                    *possessive = true;
+                                }
+                                return kind;
+                            }
+                        }
+                        GroupKind::Atomic
+                    }
+                    BagData::IfElse {
+                        ref then_node,
+                        ref else_node,
+                    } => {
+                        return AstKind::Conditional {
+                            condition: self.convert_box(body),
+                            yes: then_node.as_deref().map(|n| self.convert_box(n)),
+                            no: else_node.as_deref().map(|n| self.convert_box(n)),
+                        };
+                    }
+                };
+                AstKind::Group {
+                    kind,
+                    body: self.convert_box(body),
+                }
+            }
+            NodeInner::Anchor(an) => {
+                if let Some(ref body) = an.body {
+                    let kind = match an.anchor_type {
+                        ANCR_PREC_READ => GroupKind::LookAhead,
+                        ANCR_PREC_READ_NOT => GroupKind::NegativeLookAhead,
+                        ANCR_LOOK_BEHIND => GroupKind::LookBehind,
+                        ANCR_LOOK_BEHIND_NOT => GroupKind::NegativeLookBehind,
+                        _ => return AstKind::Other,
+                    };
+                    return AstKind::Group {
+                        kind,
+                        body: self.convert_box(body),
+                    };
+                }
+                let kind = match an.anchor_type {
+                    ANCR_BEGIN_LINE => AnchorKind::StartLine,
+                    ANCR_END_LINE => AnchorKind::EndLine,
+                    ANCR_BEGIN_BUF => AnchorKind::StartText,
+                    ANCR_END_BUF => AnchorKind::EndText,
+                    ANCR_SEMI_END_BUF => AnchorKind::EndTextOptionalNewline,
+                    ANCR_BEGIN_POSITION => AnchorKind::SearchStart,
+                    ANCR_WORD_BOUNDARY => AnchorKind::WordBoundary,
+                    ANCR_NO_WORD_BOUNDARY => AnchorKind::NotWordBoundary,
+                    ANCR_WORD_BEGIN => AnchorKind::WordStart,
+                    ANCR_WORD_END => AnchorKind::WordEnd,
+                    ANCR_TEXT_SEGMENT_BOUNDARY => AnchorKind::TextSegmentBoundary,
+                    ANCR_NO_TEXT_SEGMENT_BOUNDARY => AnchorKind::NotTextSegmentBoundary,
+                    _ => return AstKind::Other,
+                };
+                AstKind::Anchor(kind)
+            }
+            // An alternation group at a group head is kept as a one-element
+            // list (see node_new_group in regparse.rs).
+            NodeInner::List(cons) if cons.cdr.is_none() => self.convert_kind(&cons.car),
+            NodeInner::List(_) => {
+                let mut items = Vec::new();
+                self.flatten_list(node, &mut items);
+                AstKind::Concat(items)
+            }
+            NodeInner::Alt(_) => {
+                let mut items = Vec::new();
+                let mut cur = Some(node);
+                while let Some(NodeInner::Alt(cons)) = cur.map(|n| &n.inner) {
+                    items.push(self.convert(&cons.car));
+                    cur = cons.cdr.as_deref();
+                }
+                AstKind::Alternation(items)
+            }
+            NodeInner::Call(cn) => AstKind::Call {
+                group: if cn.by_number {
+                    Some(cn.called_gnum as usize)
+                } else {
+                    None
+                },
+                name: if cn.by_number {
+                    None
+                } else {
+                    Some(String::from_utf8_lossy(&cn.name).into_owned())
+                },
+            },
+            NodeInner::Gimmick(_) => AstKind::Other,
+        }
+    }
+
+    /// Collect the elements of a List chain, inlining nested lists (from
+    /// `(?:...)` bodies and split strings like `ab*`).
+    fn flatten_list(&self, node: &Node, items: &mut Vec<AstNode>) {
+        let mut cur = Some(node);
+        while let Some(NodeInner::List(cons)) = cur.map(|n| &n.inner) {
+            if matches!(cons.car.inner, NodeInner::List(_)) && !has_synthesized_child(&cons.car) {
+                self.flatten_list(&cons.car, items);
+            } else {
+                items.push(self.convert(&cons.car));
+            }
+            cur = cons.cdr.as_deref();
+        }
+    }
+}
+
+/// Whether `node` has a child the parser built itself rather than from
+/// pattern text.
+fn has_synthesized_child(node: &Node) -> bool {
+    match &node.inner {
+        NodeInner::List(cons) | NodeInner::Alt(cons) => cons.car.span.is_none(),
+        // The condition of (?(1)...) has no span of its own; \R has a
+        // synthesized else branch and no then branch.
+        NodeInner::Bag(BagNode {
+            bag_data:
+                BagData::IfElse {
+                    then_node,
+                    else_node,
+                },
+            ..
+        }) => then_node.is_none() && else_node.as_ref().is_some_and(|n| n.span.is_none()),
+        _ => node.body().is_some_and(|b| b.span.is_none()),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::regsyntax::{OnigSyntaxOniguruma, OnigSyntaxPython};
+
+    fn kinds(pattern: &str) -> Vec<(AstKind, Option<Range<usize>>)> {
+        let ast = parse(pattern, &OnigSyntaxOniguruma).unwrap();
+        match ast.root.kind {
+            AstKind::Concat(items) => items.into_iter().map(|n| (n.kind, n.span)).collect(),
+            kind => vec![(kind, ast.root.span)],
+        }
+    }
+
+    #[test]
+    fn literals_classes_and_anchors() {
+        let items = kinds(r"^ab\.[^x]\w.\d\z");
+        assert_eq!(
+            items,
+            vec![
+                (AstKind::Anchor(AnchorKind::StartLine), Some(0..1)),
+                (AstKind::Literal("ab.".into()), Some(1..5)),
+                (AstKind::Class(ClassKind::Set { negated: true }), Some(5..9)),
+                (
+                    AstKind::Class(ClassKind::Word { negated: false }),
+                    Some(9..11)
+                ),
+                (AstKind::Class(ClassKind::Any), Some(11..12)),
+                (
+                    AstKind::Class(ClassKind::Set { negated: false }),
+                    Some(12..14)
+                ),
+                (AstKind::Anchor(AnchorKind::EndText), Some(14..16)),
+            ]
+        );
+    }
+
+    #[test]
+    fn quantifiers_split_strings() {
+        let items = kinds(r"ab+c{2,3}?d*+");
+        let spans: Vec<_> = items.iter().map(|(_, s)| s.clone().unwrap()).collect();
+        assert_eq!(spans, vec![0..1, 1..3, 3..10, 10..13]);
+        assert_eq!(items[0].0, AstKind::Literal("a".into()));
+        let AstKind::Quantifier {
+            min,
+            max,
+            greedy,
+            possessive,
+            ref body,
+        } = items[2].0
+        else {
+            panic!("{:?}", items[2]);
+        };
+        assert_eq!((min, max, greedy, possessive), (2, Some(3), false, false));
+        assert_eq!(body.span, Some(3..4));
+        let AstKind::Quantifier {
+            max, possessive, ..
+        } = items[3].0
+        else {
+            panic!("{:?}", items[3]);
+        };
+        assert_eq!((max, possessive), (None, true));
+    }
+
+    #[test]
+    fn groups_backrefs_and_calls() {
+        let items = kinds(r"(?:a|bc)(?<n>x)\k<n>(?=y)(?>z)\g<n>");
+        let AstKind::Alternation(ref alts) = items[0].0 else {
+            panic!("{:?}", items[0]);
+        };
+        assert_eq!(items[0].1, Some(3..7));
+        assert_eq!(alts[1].span, Some(5..7));
+        assert!(matches!(kinds("(?:a|bc)")[0].0, AstKind::Alternation(_)));
+        assert_eq!(
+            items[1].0,
+            AstKind::Group {
+                kind: GroupKind::Capture {
+                    index: 1,
+                    name: Some("n".into())
+                },
+                body: Box::new(AstNode {
+                    kind: AstKind::Literal("x".into()),
+                    span: Some(13..14)
+                }),
+            }
+        );
+        assert_eq!(
+            items[2],
+            (
+                AstKind::Backref {
+                    groups: vec![1],
+                    by_name: true
+                },
+                Some(15..20)
+            )
+        );
+        assert!(matches!(
+            items[3].0,
+            AstKind::Group {
+                kind: GroupKind::LookAhead,
+                ..
+            }
+        ));
+        assert!(matches!(
+            items[4].0,
+            AstKind::Group {
+                kind: GroupKind::Atomic,
+                ..
+            }
+        ));
+        assert_eq!(
+            items[5],
+            (
+                AstKind::Call {
+                    group: None,
+                    name: Some("n".into())
+                },
+                Some(30..35)
+            )
+        );
+    }
+
+    #[test]
+    fn named_groups_renumber_plain_groups() {
+        let ast = parse(r"(a)(?<x>b)\k<x>", &OnigSyntaxOniguruma);
+        let ast = ast.unwrap();
+        assert_eq!(ast.captures, 1);
+        let groups: Vec<_> = ast
+            .iter()
+            .filter_map(|n| match &n.kind {
+                AstKind::Group { kind, .. } => Some(kind.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                GroupKind::NonCapturing,
+                GroupKind::Capture {
+                    index: 1,
+                    name: Some("x".into())
+                },
+            ]
+        );
+
+        let ast = parse(r"(a)(?P<x>b)", &OnigSyntaxPython).unwrap();
+        assert_eq!(ast.captures, 2);
+    }
+
+    #[test]
+    fn expanded_escapes_are_opaque() {
+        let items = kinds(r"\R\X\K(?~ab)");
+        assert_eq!(
+            items,
+            vec![
+                (AstKind::Class(ClassKind::Newline), Some(0..2)),
+                (AstKind::Class(ClassKind::Grapheme), Some(2..4)),
+                (AstKind::Other, Some(4..6)),
+                (AstKind::Other, Some(6..12)),
+            ]
+        );
+    }
+
+    #[test]
+    fn conditionals_and_errors() {
+        let items = kinds(r"(a)?(?(1)b|c)");
+        let AstKind::Conditional {
+            ref condition,
+            ref yes,
+            ref no,
+        } = items[1].0
+        else {
+            panic!("{:?}", items[1]);
+        };
+        assert!(matches!(condition.kind, AstKind::Backref { .. }));
+        assert_eq!(yes.as_ref().unwrap().span, Some(9..10));
+        assert_eq!(no.as_ref().unwrap().span, Some(11..12));
+
+        assert!(parse("(a", &OnigSyntaxOniguruma).is_err());
+        assert_eq!(
+            parse("", &OnigSyntaxOniguruma).unwrap().root.kind,
+            AstKind::Empty
+        );
+    }
+}
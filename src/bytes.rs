@@ -0,0 +1,149 @@
+// bytes.rs - Regex over arbitrary byte slices.
+//
+// Oniguruma searches bytes in whatever encoding the regex was
+// compiled for, and the C API has no notion of text. This is the byte
+// counterpart of api::Regex, compiled for US-ASCII by default so every
+// byte is one character and `.` or `[^a]` can match any of them.
+
+use alloc::borrow::Cow;
+
+use crate::api::{self, Captures, FindIter, Match, RegexBuilder};
+use crate::encodings::ascii::ONIG_ENCODING_ASCII;
+use crate::error::RegexError;
+use crate::template::Template;
+
+/// A compiled regular expression for searching `&[u8]` haystacks that need
+/// not be UTF-8, such as binary logs with embedded text.
+///
+/// [`new`](Self::new) and [`builder`](Self::builder) compile for
+/// US-ASCII: `.` and negated classes match any single byte, `\xHH` in the
+/// pattern is the byte `HH`, and `\w`, `\s`, case folding and the like
+/// only know ASCII. For another encoding, set
+/// [`RegexBuilder::encoding`] on the builder.
+///
+/// Matches and captures are the [`Match`] and [`Captures`] of the `&str`
+/// API; use [`Match::as_bytes`], since [`Match::as_str`] panics on bytes
+/// that are not UTF-8.
+///
+/// All search methods panic if the haystack is longer than
+/// [`MAX_HAYSTACK_LEN`](crate::api::MAX_HAYSTACK_LEN).
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::bytes::Regex;
+/// use ferroni::template::Template;
+///
+/// let re = Regex::new(br"(?<tag>[A-Z]{3}):\xff(?<len>.)").unwrap();
+/// let hay = b"\x00\x01LOG:\xff\x05\xfe";
+/// let caps = re.captures(hay).unwrap();
+/// assert_eq!(caps.name("tag").unwrap().as_bytes(), b"LOG");
+/// assert_eq!(caps.name("len").unwrap().as_bytes(), b"\x05");
+///
+/// let t = Template::new("<${tag}>", &re).unwrap();
+/// assert_eq!(re.replace_all(hay, &t), &b"\x00\x01<LOG>\xfe"[..]);
+/// ```
+#[derive(Debug)]
+pub struct Regex(api::Regex);
+
+impl Regex {
+    /// Compile a pattern for US-ASCII, with Oniguruma syntax and no flags.
+    pub fn new(pattern: &[u8]) -> Result<Regex, RegexError> {
+        Self::builder(pattern).build_bytes()
+    }
+
+    /// Create a [`RegexBuilder`] for `pattern` with the encoding set to
+    /// US-ASCII. Finish it with [`build_bytes`](RegexBuilder::build_bytes).
+    ///
+    /// ```
+    /// use ferroni::bytes::Regex;
+    ///
+    /// let re = Regex::builder(b"gif8[79]a")
+    ///     .case_insensitive(true)
+    ///     .build_bytes()
+    ///     .unwrap();
+    /// assert!(re.is_match(b"\x80GIF89a\x00"));
+    /// ```
+    pub fn builder(pattern: &[u8]) -> RegexBuilder {
+        RegexBuilder::new_bytes(pattern).encoding(&ONIG_ENCODING_ASCII)
+    }
+
+    /// Whether `text` contains a match.
+    pub fn is_match(&self, text: &[u8]) -> bool {
+        self.0.is_match_bytes(text)
+    }
+
+    /// Return the first match in `text`, or `None`.
+    pub fn find<'t>(&self, text: &'t [u8]) -> Option<Match<'t>> {
+        self.0.find_bytes(text)
+    }
+
+    /// Return the first match starting at or after byte offset `at`, with
+    /// all of `text` visible to look-behind and anchors; see
+    /// [`api::Regex::find_at`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is past the end of `text`.
+    pub fn find_at<'t>(&self, text: &'t [u8], at: usize) -> Option<Match<'t>> {
+        self.0.find_at_bytes(text, at)
+    }
+
+    /// Iterate over all non-overlapping matches in `text`.
+    pub fn find_iter<'r, 't>(&'r self, text: &'t [u8]) -> FindIter<'r, 't> {
+        self.0.find_iter_bytes(text)
+    }
+
+    /// Return the first match with all capture groups, or `None`.
+    pub fn captures<'t>(&'t self, text: &'t [u8]) -> Option<Captures<'t>> {
+        self.0.captures_bytes(text)
+    }
+
+    /// Return the first match starting at or after byte offset `at` with
+    /// all capture groups, or `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is past the end of `text`.
+    pub fn captures_at<'t>(&'t self, text: &'t [u8], at: usize) -> Option<Captures<'t>> {
+        self.0.captures_at_bytes(text, at)
+    }
+
+    /// Replace the first match in `text` with `template` expanded for it.
+    /// Returns `text` itself, without copying, if nothing matches.
+    pub fn replace<'t>(&self, text: &'t [u8], template: &Template) -> Cow<'t, [u8]> {
+        self.0.replace_n_bytes(text, 1, template)
+    }
+
+    /// Replace every match [`find_iter`](Self::find_iter) finds in `text`
+    /// with `template` expanded for it. Returns `text` itself, without
+    /// copying, if nothing matches.
+    pub fn replace_all<'t>(&self, text: &'t [u8], template: &Template) -> Cow<'t, [u8]> {
+        self.0.replace_n_bytes(text, usize::MAX, template)
+    }
+
+    /// The underlying [`api::Regex`], for group names, introspection and
+    /// the `*_bytes` methods not repeated here.
+    pub fn as_regex(&self) -> &api::Regex {
+        &self.0
+    }
+
+    /// Unwrap into the underlying [`api::Regex`].
+    pub fn into_regex(self) -> api::Regex {
+        self.0
+    }
+}
+
+impl From<api::Regex> for Regex {
+    /// Search with `regex` as bytes; it keeps the encoding it was compiled
+    /// for.
+    fn from(regex: api::Regex) -> Regex {
+        Regex(regex)
+    }
+}
+
+impl AsRef<api::Regex> for Regex {
+    fn as_ref(&self) -> &api::Regex {
+        &self.0
+    }
+}
@@ -0,0 +1,230 @@
+// charindex.rs - Byte offset to character offset conversion.
+//
+// The engine reports byte offsets, but editors and JavaScript
+// hosts count characters or UTF-16 code units. Converting by re-scanning
+// the haystack for every match makes tokenizing a line quadratic; a
+// CharIndex scans it once and answers each conversion by walking at most
+// one short stretch of text.
+
+use core::ops::Range;
+
+use crate::encodings::utf8::ONIG_ENCODING_UTF8;
+use crate::regenc::OnigEncoding;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+
+/// Bytes of haystack between two checkpoints.
+const STRIDE: usize = 64;
+
+/// What a [`CharIndex`] counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CharUnit {
+    /// Characters of the encoding (code points, for Unicode encodings).
+    Char,
+    /// UTF-16 code units, as JavaScript string offsets: characters above
+    /// U+FFFF count twice.
+    Utf16,
+}
+
+/// Converts byte offsets in one haystack to character offsets and back.
+///
+/// Building the index scans the haystack once; every conversion after that
+/// walks at most 64 bytes. Offsets inside a character count that
+/// character, and offsets past the end are clamped to the end.
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::api::Regex;
+/// use ferroni::charindex::{CharIndex, CharUnit};
+///
+/// let text = "naïve 💻 code";
+/// let re = Regex::new(r"\w+").unwrap();
+/// let chars = CharIndex::utf8(text, CharUnit::Char);
+/// let utf16 = CharIndex::utf8(text, CharUnit::Utf16);
+///
+/// let last = re.find_iter(text).last().unwrap();
+/// assert_eq!(last.range(), 12..16);
+/// assert_eq!(last.char_range(&chars), 8..12);
+/// assert_eq!(last.char_range(&utf16), 9..13);
+/// assert_eq!(utf16.to_byte(9), 12);
+/// ```
+#[derive(Clone)]
+pub struct CharIndex<'t> {
+    haystack: &'t [u8],
+    enc: OnigEncoding,
+    unit: CharUnit,
+    /// For every STRIDE bytes, the first character boundary at or after
+    /// the stride's start and its character offset.
+    checkpoints: Vec<(usize, usize)>,
+    /// Character offset of the end of the haystack.
+    len: usize,
+}
+
+impl<'t> CharIndex<'t> {
+    /// Scan `haystack`, encoded in `enc`, counting `unit`s.
+    pub fn new(haystack: &'t [u8], enc: OnigEncoding, unit: CharUnit) -> Self {
+        let mut checkpoints = Vec::with_capacity(haystack.len() / STRIDE + 1);
+        let mut pos = 0;
+        let mut count = 0;
+        while pos < haystack.len() {
+            while checkpoints.len() * STRIDE <= pos {
+                checkpoints.push((pos, count));
+            }
+            let len = char_len(haystack, pos, enc);
+            count += units(haystack, pos, len, enc, unit);
+            pos += len;
+        }
+        // Strides inside the last character
+        while checkpoints.len() * STRIDE < haystack.len() {
+            checkpoints.push((pos, count));
+        }
+        CharIndex {
+            haystack,
+            enc,
+            unit,
+            checkpoints,
+            len: count,
+        }
+    }
+
+    /// Scan the UTF-8 string `text`, counting `unit`s.
+    pub fn utf8(text: &'t str, unit: CharUnit) -> Self {
+        Self::new(text.as_bytes(), &ONIG_ENCODING_UTF8, unit)
+    }
+
+    /// The haystack the index was built for.
+    pub fn haystack(&self) -> &'t [u8] {
+        self.haystack
+    }
+
+    /// What the index counts.
+    pub fn unit(&self) -> CharUnit {
+        self.unit
+    }
+
+    /// Length of the haystack in units.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the haystack is empty.
+    pub fn is_empty(&self) -> bool {
+        self.haystack.is_empty()
+    }
+
+    /// The character offset of byte offset `byte`.
+    pub fn to_char(&self, byte: usize) -> usize {
+        if byte >= self.haystack.len() {
+            return self.len;
+        }
+        let mut k = byte / STRIDE;
+        // A character running across the stride start puts the checkpoint
+        // after `byte`.
+        while k > 0 && self.checkpoints[k].0 > byte {
+            k -= 1;
+        }
+        let (mut pos, mut count) = self.checkpoints[k];
+        while pos < byte {
+            let len = char_len(self.haystack, pos, self.enc);
+            count += units(self.haystack, pos, len, self.enc, self.unit);
+            pos += len;
+        }
+        count
+    }
+
+    /// The byte offset of character offset `offset`. An offset in the
+    /// middle of a surrogate pair gives the end of its character.
+    pub fn to_byte(&self, offset: usize) -> usize {
+        if offset >= self.len {
+            return self.haystack.len();
+        }
+        let k = self.checkpoints.partition_point(|&(_, c)| c <= offset) - 1;
+        let (mut pos, mut count) = self.checkpoints[k];
+        while count < offset {
+            let len = char_len(self.haystack, pos, self.enc);
+            count += units(self.haystack, pos, len, self.enc, self.unit);
+            pos += len;
+        }
+        pos
+    }
+
+    /// The character range of byte range `range`.
+    pub fn to_char_range(&self, range: Range<usize>) -> Range<usize> {
+        self.to_char(range.start)..self.to_char(range.end)
+    }
+}
+
+/// Length of the character at `pos`, at least 1 and not past the end.
+fn char_len(haystack: &[u8], pos: usize, enc: OnigEncoding) -> usize {
+    let rest = &haystack[pos..];
+    enc.mbc_enc_len(rest).clamp(1, rest.len())
+}
+
+/// Units the `len`-byte character at `pos` counts as.
+fn units(haystack: &[u8], pos: usize, len: usize, enc: OnigEncoding, unit: CharUnit) -> usize {
+    match unit {
+        CharUnit::Char => 1,
+        CharUnit::Utf16 if len == 1 => 1,
+        CharUnit::Utf16 => {
+            if enc.mbc_to_code(&haystack[pos..], len) > 0xffff {
+                2
+            } else {
+                1
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Offsets by scanning from the start, for comparison.
+    fn naive(text: &str, unit: CharUnit) -> Vec<usize> {
+        let mut out = vec![0; text.len() + 1];
+        let mut count = 0;
+        for (i, c) in text.char_indices() {
+            let units = match unit {
+                CharUnit::Char => 1,
+                CharUnit::Utf16 => c.len_utf16(),
+            };
+            out[i] = count;
+            for b in 1..c.len_utf8() {
+                out[i + b] = count + units;
+            }
+            count += units;
+        }
+        out[text.len()] = count;
+        out
+    }
+
+    #[test]
+    fn matches_naive_scan() {
+        let text = "aé€💻".repeat(40) + "tail";
+        for unit in [CharUnit::Char, CharUnit::Utf16] {
+            let index = CharIndex::new(text.as_bytes(), &ONIG_ENCODING_UTF8, unit);
+            let expected = naive(&text, unit);
+            assert_eq!(index.len(), expected[text.len()]);
+            for (byte, &offset) in expected.iter().enumerate() {
+                assert_eq!(index.to_char(byte), offset, "{unit:?} {byte}");
+            }
+            for (byte, _) in text.char_indices() {
+                assert_eq!(index.to_byte(expected[byte]), byte, "{unit:?} {byte}");
+            }
+            assert_eq!(index.to_byte(index.len() + 5), text.len());
+        }
+    }
+
+    #[test]
+    fn empty_and_invalid_haystacks() {
+        let index = CharIndex::new(b"", &ONIG_ENCODING_UTF8, CharUnit::Utf16);
+        assert!(index.is_empty());
+        assert_eq!((index.to_char(3), index.to_byte(0)), (0, 0));
+
+        // A truncated sequence counts as one character.
+        let index = CharIndex::new(b"a\xe2\x82", &ONIG_ENCODING_UTF8, CharUnit::Char);
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.to_char(1), 1);
+    }
+}
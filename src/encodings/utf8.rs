@@ -130,6 +130,13 @@ impl Encoding for Utf8Encoding {
         fold_buf: &mut [u8],
     ) -> i32 {
         if source[*pp] < 128 {
+            // Turkish/Azeri: I folds to dotless i (U+0131)
+            if (flag & ONIGENC_CASE_FOLD_TURKISH_AZERI) != 0 && source[*pp] == 0x49 {
+                fold_buf[0] = 0xc4;
+                fold_buf[1] = 0xb1;
+                *pp += 1;
+                return 2;
+            }
             // ASCII range: direct lookup
             fold_buf[0] = onigenc_ascii_code_to_lower_case(source[*pp]);
             *pp += 1;
@@ -191,26 +198,31 @@ impl Encoding for Utf8Encoding {
     }
 
     fn is_valid_mbc_string(&self, s: &[u8]) -> bool {
+        self.validate(s).is_ok()
+    }
+
+    fn validate(&self, s: &[u8]) -> Result<(), FirstErrorOffset> {
         let mut p = 0;
         while p < s.len() {
+            let head = p;
             if s[p] > 0xf4 || (s[p] > 0x7f && s[p] < 0xc2) {
-                return false;
+                return Err(FirstErrorOffset(head));
             }
             let len = ENC_LEN_UTF8[s[p] as usize] as usize;
             p += 1;
             if len > 1 {
                 for _ in 1..len {
                     if p >= s.len() {
-                        return false;
+                        return Err(FirstErrorOffset(head));
                     }
                     if !utf8_istail(s[p]) {
-                        return false;
+                        return Err(FirstErrorOffset(head));
                     }
                     p += 1;
                 }
             }
         }
-        true
+        Ok(())
     }
 
     fn flag(&self) -> u32 {
@@ -3,13 +3,17 @@
 // Groups the ~100 C-style i32 error codes into semantic variants
 // while preserving the original code for interop.
 
-use std::fmt;
+use core::fmt;
+use core::ops::Range;
 
 use crate::oniguruma::*;
-use crate::regerror::onig_error_code_to_format;
+use crate::regerror::{onig_error_code_to_format, onig_error_code_to_str};
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
 
 /// Error type for regex compilation and matching operations.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RegexError {
     /// Memory allocation failure.
     Memory,
@@ -25,8 +29,26 @@ pub enum RegexError {
     TimeLimitOver,
     /// Parse depth limit exceeded.
     ParseDepthLimitOver,
+    /// Input longer than [`ONIG_MAX_INPUT_LEN`] bytes.
+    TooLongInput,
+    /// Compiled program larger than
+    /// [`RegexBuilder::max_compiled_bytes`](crate::api::RegexBuilder::max_compiled_bytes).
+    CompiledSizeLimitOver,
+    /// Compiling took longer than
+    /// [`RegexBuilder::compile_timeout`](crate::api::RegexBuilder::compile_timeout).
+    CompileTimeLimitOver,
     /// Syntax error in the pattern.
-    Syntax { code: i32, message: String },
+    ///
+    /// `span` is the byte range of the pattern where parsing failed: the
+    /// offending name for name errors, otherwise the token being parsed up
+    /// to where the parser gave up. It is `None` for errors found after
+    /// parsing, and `pattern` is `None` for errors built from a bare code.
+    Syntax {
+        code: i32,
+        message: String,
+        span: Option<Range<usize>>,
+        pattern: Option<String>,
+    },
     /// Invalid argument passed to a function.
     InvalidArgument,
     /// Internal engine bug (should not occur in correct usage).
@@ -49,7 +71,23 @@ impl fmt::Display for RegexError {
             RegexError::SubexpCallLimitOver => write!(f, "subexp-call-limit-in-search over"),
             RegexError::TimeLimitOver => write!(f, "time limit over"),
             RegexError::ParseDepthLimitOver => write!(f, "parse depth limit over"),
-            RegexError::Syntax { message, .. } => write!(f, "syntax error: {}", message),
+            RegexError::TooLongInput => write!(f, "input string too long"),
+            RegexError::CompiledSizeLimitOver => write!(f, "compiled size limit over"),
+            RegexError::CompileTimeLimitOver => write!(f, "compile time limit over"),
+            RegexError::Syntax {
+                message,
+                span,
+                pattern,
+                ..
+            } => {
+                write!(f, "syntax error: {}", message)?;
+                if let (Some(span), Some(pattern)) = (span, pattern) {
+                    if let Some((line, pad, width)) = caret_line(pattern, span) {
+                        write!(f, "\n    {}\n    {}{}", line, pad, "^".repeat(width))?;
+                    }
+                }
+                Ok(())
+            }
             RegexError::InvalidArgument => write!(f, "invalid argument"),
             RegexError::InternalBug { message, .. } => write!(f, "internal error: {}", message),
             RegexError::NotInitialized => write!(f, "library is not initialized"),
@@ -59,7 +97,7 @@ impl fmt::Display for RegexError {
     }
 }
 
-impl std::error::Error for RegexError {}
+impl core::error::Error for RegexError {}
 
 impl From<i32> for RegexError {
     fn from(code: i32) -> Self {
@@ -71,6 +109,9 @@ impl From<i32> for RegexError {
             ONIGERR_SUBEXP_CALL_LIMIT_IN_SEARCH_OVER => RegexError::SubexpCallLimitOver,
             ONIGERR_TIME_LIMIT_OVER => RegexError::TimeLimitOver,
             ONIGERR_PARSE_DEPTH_LIMIT_OVER => RegexError::ParseDepthLimitOver,
+            ONIGERR_TOO_LONG_INPUT => RegexError::TooLongInput,
+            ONIGERR_COMPILED_SIZE_LIMIT_OVER => RegexError::CompiledSizeLimitOver,
+            ONIGERR_COMPILE_TIME_LIMIT_OVER => RegexError::CompileTimeLimitOver,
             ONIGERR_INVALID_ARGUMENT => RegexError::InvalidArgument,
             ONIGERR_LIBRARY_IS_NOT_INITIALIZED => RegexError::NotInitialized,
 
@@ -96,6 +137,8 @@ impl From<i32> for RegexError {
             c if onig_is_pattern_error(c) => RegexError::Syntax {
                 code: c,
                 message: onig_error_code_to_format(c).to_string(),
+                span: None,
+                pattern: None,
             },
 
             _ => RegexError::Other(code),
@@ -103,7 +146,213 @@ impl From<i32> for RegexError {
     }
 }
 
+/// The line of `pattern` containing `span.start`, the padding that lines a
+/// caret up below `span.start` (keeping tabs), and the width in characters
+/// of the part of `span` on that line.
+fn caret_line<'a>(pattern: &'a str, span: &Range<usize>) -> Option<(&'a str, String, usize)> {
+    let before = pattern.get(..span.start)?;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let rest = &pattern[span.start..];
+    let line_end = span.start + rest.find('\n').unwrap_or(rest.len());
+    let marked = pattern.get(span.start..span.end.clamp(span.start, line_end))?;
+    let pad = pattern[line_start..span.start]
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    Some((
+        &pattern[line_start..line_end],
+        pad,
+        marked.chars().count().max(1),
+    ))
+}
+
+/// The construct a syntax error is about, as returned by
+/// [`RegexError::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SyntaxErrorKind {
+    /// An escape sequence or code point value.
+    Escape,
+    /// A bracket class or POSIX bracket.
+    CharClass,
+    /// A repeat operator or interval.
+    Quantifier,
+    /// Parentheses, group options, look-around or absent groups.
+    Group,
+    /// A group name.
+    GroupName,
+    /// A backreference or subexpression call.
+    Reference,
+    /// A character property name.
+    Property,
+    /// A callout.
+    Callout,
+    /// Anything else, e.g. a number that is too big.
+    Other,
+}
+
+/// A construct the parser accepted but rewrote or found suspicious, as
+/// returned by
+/// [`RegexBuilder::build_with_warnings`](crate::api::RegexBuilder::build_with_warnings).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Warning {
+    kind: WarningKind,
+    message: String,
+    span: Range<usize>,
+}
+
+impl Warning {
+    pub(crate) fn new(kind: WarningKind, message: String, span: (usize, usize)) -> Self {
+        Warning {
+            kind,
+            message,
+            span: span.0..span.1,
+        }
+    }
+
+    /// What the warning is about.
+    pub fn kind(&self) -> WarningKind {
+        self.kind
+    }
+
+    /// The message, in the wording of C Oniguruma's warnings.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Byte range of the pattern the warning refers to.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at {}..{}",
+            self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+/// The kind of a [`Warning`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum WarningKind {
+    /// A repeat operator applied to another one, like `(?:a+)?`, was
+    /// dropped or the pair replaced by a single operator.
+    NestedRepeat,
+    /// A `{` that does not start a valid interval was taken as a literal.
+    InvalidInterval,
+    /// A metacharacter such as `-` or `]` was taken as a literal although
+    /// it is not escaped.
+    UnescapedMetaChar,
+}
+
 impl RegexError {
+    /// Attach the location of a parse error to the error for `code`.
+    /// `span` and `name` are byte ranges of `pattern`; `name` fills the
+    /// `%n` of messages about a name, as C's OnigErrorInfo does.
+    pub(crate) fn with_location(
+        code: i32,
+        pattern: &[u8],
+        span: Option<(usize, usize)>,
+        name: Option<(usize, usize)>,
+    ) -> Self {
+        match RegexError::from(code) {
+            RegexError::Syntax { code, message, .. } => RegexError::Syntax {
+                code,
+                message: match name {
+                    Some((s, e)) => onig_error_code_to_str(code, pattern.get(s..e)),
+                    None => message,
+                },
+                span: name.or(span).map(|(s, e)| s..e),
+                pattern: Some(String::from_utf8_lossy(pattern).into_owned()),
+            },
+            err => err,
+        }
+    }
+
+    /// Byte range of the pattern where parsing failed, for syntax errors
+    /// found by the parser.
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            RegexError::Syntax { span, .. } => span.clone(),
+            _ => None,
+        }
+    }
+
+    /// The construct a syntax error is about, or `None` for other errors.
+    pub fn kind(&self) -> Option<SyntaxErrorKind> {
+        let RegexError::Syntax { code, .. } = self else {
+            return None;
+        };
+        Some(match *code {
+            ONIGERR_END_PATTERN_AT_ESCAPE
+            | ONIGERR_END_PATTERN_AT_META
+            | ONIGERR_END_PATTERN_AT_CONTROL
+            | ONIGERR_META_CODE_SYNTAX
+            | ONIGERR_CONTROL_CODE_SYNTAX
+            | ONIGERR_TOO_LONG_WIDE_CHAR_VALUE
+            | ONIGERR_UNDEFINED_OPERATOR
+            | ONIGERR_INVALID_CODE_POINT_VALUE
+            | ONIGERR_TOO_BIG_WIDE_CHAR_VALUE => SyntaxErrorKind::Escape,
+            ONIGERR_END_PATTERN_AT_LEFT_BRACKET
+            | ONIGERR_EMPTY_CHAR_CLASS
+            | ONIGERR_PREMATURE_END_OF_CHAR_CLASS
+            | ONIGERR_CHAR_CLASS_VALUE_AT_END_OF_RANGE
+            | ONIGERR_CHAR_CLASS_VALUE_AT_START_OF_RANGE
+            | ONIGERR_UNMATCHED_RANGE_SPECIFIER_IN_CHAR_CLASS
+            | ONIGERR_INVALID_POSIX_BRACKET_TYPE
+            | ONIGERR_EMPTY_RANGE_IN_CHAR_CLASS
+            | ONIGERR_MISMATCH_CODE_LENGTH_IN_CLASS_RANGE
+            | ONIGERR_TOO_MANY_MULTI_BYTE_RANGES
+            | ONIGERR_TOO_SHORT_MULTI_BYTE_STRING => SyntaxErrorKind::CharClass,
+            ONIGERR_END_PATTERN_AT_LEFT_BRACE
+            | ONIGERR_TARGET_OF_REPEAT_OPERATOR_NOT_SPECIFIED
+            | ONIGERR_TARGET_OF_REPEAT_OPERATOR_INVALID
+            | ONIGERR_NESTED_REPEAT_OPERATOR
+            | ONIGERR_INVALID_REPEAT_RANGE_PATTERN
+            | ONIGERR_TOO_BIG_NUMBER_FOR_REPEAT_RANGE
+            | ONIGERR_UPPER_SMALLER_THAN_LOWER_IN_REPEAT_RANGE => SyntaxErrorKind::Quantifier,
+            ONIGERR_UNMATCHED_CLOSE_PARENTHESIS
+            | ONIGERR_END_PATTERN_WITH_UNMATCHED_PARENTHESIS
+            | ONIGERR_END_PATTERN_IN_GROUP
+            | ONIGERR_UNDEFINED_GROUP_OPTION
+            | ONIGERR_INVALID_GROUP_OPTION
+            | ONIGERR_INVALID_LOOK_BEHIND_PATTERN
+            | ONIGERR_TOO_MANY_CAPTURES
+            | ONIGERR_GROUP_NUMBER_OVER_FOR_CAPTURE_HISTORY
+            | ONIGERR_INVALID_IF_ELSE_SYNTAX
+            | ONIGERR_INVALID_ABSENT_GROUP_PATTERN
+            | ONIGERR_INVALID_ABSENT_GROUP_GENERATOR_PATTERN => SyntaxErrorKind::Group,
+            ONIGERR_EMPTY_GROUP_NAME
+            | ONIGERR_INVALID_GROUP_NAME
+            | ONIGERR_INVALID_CHAR_IN_GROUP_NAME
+            | ONIGERR_MULTIPLEX_DEFINED_NAME => SyntaxErrorKind::GroupName,
+            ONIGERR_TOO_BIG_BACKREF_NUMBER
+            | ONIGERR_INVALID_BACKREF
+            | ONIGERR_NUMBERED_BACKREF_OR_CALL_NOT_ALLOWED
+            | ONIGERR_UNDEFINED_NAME_REFERENCE
+            | ONIGERR_UNDEFINED_GROUP_REFERENCE
+            | ONIGERR_MULTIPLEX_DEFINITION_NAME_CALL
+            | ONIGERR_NEVER_ENDING_RECURSION => SyntaxErrorKind::Reference,
+            ONIGERR_INVALID_CHAR_PROPERTY_NAME | ONIGERR_TOO_LONG_PROPERTY_NAME => {
+                SyntaxErrorKind::Property
+            }
+            ONIGERR_INVALID_CALLOUT_PATTERN
+            | ONIGERR_INVALID_CALLOUT_NAME
+            | ONIGERR_UNDEFINED_CALLOUT_NAME
+            | ONIGERR_INVALID_CALLOUT_BODY
+            | ONIGERR_INVALID_CALLOUT_TAG_NAME
+            | ONIGERR_INVALID_CALLOUT_ARG => SyntaxErrorKind::Callout,
+            _ => SyntaxErrorKind::Other,
+        })
+    }
+
     /// Returns the original C error code, if applicable.
     pub fn code(&self) -> i32 {
         match self {
@@ -114,6 +363,9 @@ impl RegexError {
             RegexError::SubexpCallLimitOver => ONIGERR_SUBEXP_CALL_LIMIT_IN_SEARCH_OVER,
             RegexError::TimeLimitOver => ONIGERR_TIME_LIMIT_OVER,
             RegexError::ParseDepthLimitOver => ONIGERR_PARSE_DEPTH_LIMIT_OVER,
+            RegexError::TooLongInput => ONIGERR_TOO_LONG_INPUT,
+            RegexError::CompiledSizeLimitOver => ONIGERR_COMPILED_SIZE_LIMIT_OVER,
+            RegexError::CompileTimeLimitOver => ONIGERR_COMPILE_TIME_LIMIT_OVER,
             RegexError::InvalidArgument => ONIGERR_INVALID_ARGUMENT,
             RegexError::NotInitialized => ONIGERR_LIBRARY_IS_NOT_INITIALIZED,
             RegexError::Syntax { code, .. } => *code,
@@ -144,6 +396,49 @@ mod tests {
         assert!(err.to_string().contains("syntax error"));
     }
 
+    #[test]
+    fn syntax_error_location() {
+        let err = RegexError::with_location(
+            ONIGERR_INVALID_CHAR_IN_GROUP_NAME,
+            b"x(?<a-b>y)",
+            Some((1, 8)),
+            Some((4, 7)),
+        );
+        assert_eq!(err.span(), Some(4..7));
+        assert_eq!(err.kind(), Some(SyntaxErrorKind::GroupName));
+        assert_eq!(
+            err.to_string(),
+            "syntax error: invalid char in group name <a-b>\n    x(?<a-b>y)\n        ^^^"
+        );
+    }
+
+    #[test]
+    fn caret_marks_the_failing_line() {
+        let err = RegexError::with_location(
+            ONIGERR_UNMATCHED_CLOSE_PARENTHESIS,
+            "a\n\tcé)\nd".as_bytes(),
+            Some((6, 7)),
+            None,
+        );
+        assert_eq!(
+            err.to_string(),
+            "syntax error: unmatched close parenthesis\n    \tcé)\n    \t  ^"
+        );
+        // An empty span at the end of the pattern still gets a caret.
+        let err = RegexError::with_location(
+            ONIGERR_END_PATTERN_WITH_UNMATCHED_PARENTHESIS,
+            b"(a",
+            Some((2, 2)),
+            None,
+        );
+        assert!(err.to_string().ends_with("\n    (a\n      ^"));
+        // Without a location the message is a single line.
+        let err = RegexError::from(ONIGERR_UNMATCHED_CLOSE_PARENTHESIS);
+        assert_eq!(err.span(), None);
+        assert_eq!(err.to_string(), "syntax error: unmatched close parenthesis");
+        assert_eq!(RegexError::Memory.kind(), None);
+    }
+
     #[test]
     fn from_internal_bug() {
         let err = RegexError::from(ONIGERR_PARSER_BUG);
@@ -156,6 +451,30 @@ mod tests {
         assert!(matches!(err, RegexError::Encoding { .. }));
     }
 
+    #[test]
+    fn from_too_long_input() {
+        let err = RegexError::from(ONIGERR_TOO_LONG_INPUT);
+        assert_eq!(err, RegexError::TooLongInput);
+        assert_eq!(err.code(), ONIGERR_TOO_LONG_INPUT);
+    }
+
+    #[test]
+    fn from_compile_limit_codes() {
+        for (code, err) in [
+            (
+                ONIGERR_COMPILED_SIZE_LIMIT_OVER,
+                RegexError::CompiledSizeLimitOver,
+            ),
+            (
+                ONIGERR_COMPILE_TIME_LIMIT_OVER,
+                RegexError::CompileTimeLimitOver,
+            ),
+        ] {
+            assert_eq!(RegexError::from(code), err);
+            assert_eq!(err.code(), code);
+        }
+    }
+
     #[test]
     fn from_unknown_code() {
         let err = RegexError::from(-9999);
@@ -170,7 +489,7 @@ mod tests {
 
     #[test]
     fn error_trait() {
-        let err: Box<dyn std::error::Error> = Box::new(RegexError::Memory);
+        let err: Box<dyn core::error::Error> = Box::new(RegexError::Memory);
         assert_eq!(err.to_string(), "memory allocation failed");
     }
 }
@@ -0,0 +1,169 @@
+// extension.rs - Custom VM opcodes (unstable, behind the
+// `unstable-opcodes` feature).
+//
+// An embedder registers a named handler on a RegexBuilder, and
+// the pattern invokes it with the callout-of-name syntax `(*NAME)` or
+// `(*NAME{arg})`. The parser turns each use into a gimmick node, the
+// compiler into an OP_EXTENSION, and match_at() calls the handler, which
+// may consume input and offer several end positions to backtrack over.
+
+//! Custom VM opcodes.
+//!
+//! **Unstable**: this module is only available with the `unstable-opcodes`
+//! feature and may change in any release.
+//!
+//! An [`OpcodeHandler`] registered with
+//! [`RegexBuilder::opcode`](crate::api::RegexBuilder::opcode) is invoked by
+//! `(*NAME)` or `(*NAME{arg})` in the pattern, where `NAME` is the
+//! registered name. The argument is passed to the handler verbatim and
+//! may not contain `}`.
+//!
+//! The construct is not allowed in look-behind. The optimizer treats it as
+//! matching any amount of text.
+//!
+//! ```
+//! use alloc::sync::Arc;
+//! use ferroni::api::Regex;
+//! use ferroni::extension::{OpcodeContext, OpcodeHandler};
+//!
+//! /// Matches any word from a fixed list, longest first.
+//! struct Dictionary(Vec<&'static str>);
+//!
+//! impl OpcodeHandler for Dictionary {
+//!     fn execute(&self, cx: &OpcodeContext<'_>, ends: &mut Vec<usize>) {
+//!         let rest = &cx.text()[cx.pos()..];
+//!         let mut words: Vec<_> = self.0.iter().filter(|w| rest.starts_with(w.as_bytes())).collect();
+//!         words.sort_by_key(|w| core::cmp::Reverse(w.len()));
+//!         ends.extend(words.iter().map(|w| cx.pos() + w.len()));
+//!     }
+//! }
+//!
+//! let re = Regex::builder(r"<(*WORD)s?>")
+//!     .opcode("WORD", Arc::new(Dictionary(vec!["cat", "cats", "dog"])))
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(re.find("a <cats> b").unwrap().as_str(), "<cats>");
+//! assert_eq!(re.find("a <dogs> b").unwrap().as_str(), "<dogs>");
+//! assert!(!re.is_match("<bird>"));
+//! ```
+
+use alloc::sync::Arc;
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+
+/// A custom opcode, called whenever matching reaches its use in a pattern.
+pub trait OpcodeHandler: Send + Sync {
+    /// Push the positions where the construct may end onto `ends`, most
+    /// preferred first. Matching continues at the first one and backtracks
+    /// to the others in order; an empty `ends` fails this path.
+    ///
+    /// Positions before [`OpcodeContext::pos`] or past the end of
+    /// [`OpcodeContext::text`] are ignored.
+    fn execute(&self, cx: &OpcodeContext<'_>, ends: &mut Vec<usize>);
+}
+
+/// What an [`OpcodeHandler`] sees of the match in progress.
+pub struct OpcodeContext<'a> {
+    pub(crate) text: &'a [u8],
+    pub(crate) start: usize,
+    pub(crate) pos: usize,
+    pub(crate) arg: &'a [u8],
+}
+
+impl<'a> OpcodeContext<'a> {
+    /// The subject string, up to the end of the search.
+    pub fn text(&self) -> &'a [u8] {
+        self.text
+    }
+
+    /// Where the current match attempt started.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// The current position.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// The text between the braces of `(*NAME{arg})`, empty for `(*NAME)`.
+    pub fn arg(&self) -> &'a [u8] {
+        self.arg
+    }
+}
+
+/// Handlers available to a pattern and the uses of them in it.
+#[derive(Default)]
+pub(crate) struct OpcodeTable {
+    handlers: Vec<(Vec<u8>, Arc<dyn OpcodeHandler>)>,
+    /// (handler index, argument) per use; OP_EXTENSION refers to its entry.
+    uses: Vec<(usize, Vec<u8>)>,
+}
+
+impl fmt::Debug for OpcodeTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.handlers
+                    .iter()
+                    .map(|(name, _)| String::from_utf8_lossy(name)),
+            )
+            .finish()
+    }
+}
+
+impl OpcodeTable {
+    /// Register `handler` under `name`, replacing an earlier one.
+    pub(crate) fn register(&mut self, name: &[u8], handler: Arc<dyn OpcodeHandler>) {
+        self.handlers.retain(|(n, _)| n != name);
+        self.handlers.push((name.to_vec(), handler));
+    }
+
+    pub(crate) fn contains(&self, name: &[u8]) -> bool {
+        self.handlers.iter().any(|(n, _)| n == name)
+    }
+
+    /// Record a use of the registered handler `name`; returns its use number.
+    pub(crate) fn add_use(&mut self, name: &[u8], arg: &[u8]) -> i32 {
+        let handler = self.handlers.iter().position(|(n, _)| n == name).unwrap();
+        self.uses.push((handler, arg.to_vec()));
+        self.uses.len() as i32 - 1
+    }
+
+    /// Handler name and argument of each use, in use number order.
+    pub(crate) fn uses(&self) -> impl Iterator<Item = (&[u8], &[u8])> + '_ {
+        self.uses
+            .iter()
+            .map(|(handler, arg)| (&self.handlers[*handler].0[..], &arg[..]))
+    }
+
+    /// Forget the uses recorded by an earlier parse.
+    pub(crate) fn clear_uses(&mut self) {
+        self.uses.clear();
+    }
+
+    /// Run use `num` at `pos`, leaving the valid end positions in `ends`.
+    pub(crate) fn execute(
+        &self,
+        num: i32,
+        text: &[u8],
+        start: usize,
+        pos: usize,
+        ends: &mut Vec<usize>,
+    ) {
+        ends.clear();
+        let Some((handler, arg)) = self.uses.get(num as usize) else {
+            return;
+        };
+        let cx = OpcodeContext {
+            text,
+            start,
+            pos,
+            arg,
+        };
+        self.handlers[*handler].1.execute(&cx, ends);
+        ends.retain(|&e| e >= pos && e <= text.len());
+    }
+}
@@ -1,40 +1,233 @@
-// FFI bindings for C Oniguruma (benchmark comparison only)
+// ffi.rs - C ABI exports for use as a drop-in libonig replacement.
 //
-// Minimal surface: just enough to compile patterns, run searches/matches,
-// and manage regions+regsets. Gated behind `ffi` Cargo feature.
+// Exports the core `onig_*` symbols, the `OnigSyntax*` and `OnigEncoding*`
+// data symbols, and C-layout `OnigRegion`/`OnigErrorInfo` structs, so C code
+// (or bindings such as the `onig` crate) written against oniguruma.h can
+// link against Ferroni instead. Gated behind the `ffi` Cargo feature. With
+// `c-oniguruma` enabled too, C Oniguruma is linked under the same names, so
+// these functions are compiled but not exported (see build.rs). Build a
+// shared library with:
+//
+//     cargo rustc --release --features ffi --crate-type cdylib
+//
+// Limitations:
+// - Only ONIG_ENCODING_UTF8 and ONIG_ENCODING_ASCII exist (ADR-002). The
+//   exported OnigEncodingType only carries its leading fields
+//   (mbc_enc_len, name, max_enc_len, min_enc_len).
+// - onig_error_code_to_str() is variadic in C. It is defined here with a
+//   single `OnigErrorInfo*` argument, which matches the variadic calling
+//   convention on x86_64 and Linux/aarch64 but not on Apple arm64.
+// - Capture history trees are not exposed (`history_root` is always NULL).
+// - With ONIG_OPTION_POSIX_REGION, onig_search()/onig_match() treat the
+//   region argument as a `regmatch_t[num_mem + 1]` with `int` offsets, as C
+//   does, and fill it without allocating an OnigRegion.
 
-#![allow(non_camel_case_types, non_upper_case_globals, dead_code)]
+#![allow(non_upper_case_globals)]
 
 use std::ffi::c_void;
 use std::os::raw::{c_char, c_int, c_uint};
 use std::ptr;
-use std::sync::Once;
 
-// --- Opaque types ---
+use crate::encodings::{ONIG_ENCODING_ASCII, ONIG_ENCODING_UTF8};
+use crate::oniguruma::{
+    OnigCodePoint, OnigOptionType, OnigRegion as RustRegion, OnigSyntaxType,
+    ONIGERR_INVALID_ARGUMENT, ONIGERR_NOT_SUPPORTED_ENCODING_COMBINATION,
+    ONIG_MAX_ERROR_MESSAGE_LEN, ONIG_NORMAL, ONIG_REGION_NOTPOS,
+};
+use crate::regenc::{Encoding, OnigEncoding as RustEncoding};
+use crate::regexec::RegMatch;
+use crate::regint::{opton_posix_region, CompileState, RegexType};
+use crate::regsyntax::OnigSyntaxOniguruma;
 
-#[repr(C)]
+/// A compiled regex as C callers hold it: the regex and the syntax it was
+/// compiled with, which [`onig_get_syntax`] hands back as C does.
 pub struct OnigRegexType {
-    _opaque: [u8; 0],
+    reg: RegexType,
+    syntax: *const OnigSyntaxType,
 }
-pub type OnigRegex = *mut OnigRegexType;
 
-#[repr(C)]
-pub struct OnigSyntaxType {
-    _opaque: [u8; 0],
+impl OnigRegexType {
+    fn boxed(reg: RegexType, syntax: *const OnigSyntaxType) -> OnigRegex {
+        Box::into_raw(Box::new(OnigRegexType { reg, syntax }))
+    }
 }
 
+pub type OnigRegex = *mut OnigRegexType;
+pub type OnigEncoding = *const OnigEncodingType;
+
+// === Encodings ===
+
+/// Leading fields of C's `OnigEncodingType`.
 #[repr(C)]
 pub struct OnigEncodingType {
-    _opaque: [u8; 0],
+    pub mbc_enc_len: extern "C" fn(p: *const u8) -> c_int,
+    pub name: *const c_char,
+    pub max_enc_len: c_int,
+    pub min_enc_len: c_int,
 }
-pub type OnigEncoding = *const OnigEncodingType;
 
-#[repr(C)]
-pub struct OnigRegSetType {
-    _opaque: [u8; 0],
+// Safety: all fields are immutable after initialization.
+unsafe impl Sync for OnigEncodingType {}
+
+extern "C" fn utf8_mbc_enc_len(p: *const u8) -> c_int {
+    let c = unsafe { *p };
+    ONIG_ENCODING_UTF8.mbc_enc_len(&[c]) as c_int
+}
+
+extern "C" fn ascii_mbc_enc_len(_p: *const u8) -> c_int {
+    1
+}
+
+#[cfg_attr(ffi_export, no_mangle)]
+pub static OnigEncodingUTF8: OnigEncodingType = OnigEncodingType {
+    mbc_enc_len: utf8_mbc_enc_len,
+    name: c"UTF-8".as_ptr(),
+    max_enc_len: 4,
+    min_enc_len: 1,
+};
+
+#[cfg_attr(ffi_export, no_mangle)]
+pub static OnigEncodingASCII: OnigEncodingType = OnigEncodingType {
+    mbc_enc_len: ascii_mbc_enc_len,
+    name: c"US-ASCII".as_ptr(),
+    max_enc_len: 1,
+    min_enc_len: 1,
+};
+
+fn rust_encoding(enc: OnigEncoding) -> Option<RustEncoding> {
+    if ptr::eq(enc, &OnigEncodingUTF8) {
+        Some(&ONIG_ENCODING_UTF8)
+    } else if ptr::eq(enc, &OnigEncodingASCII) {
+        Some(&ONIG_ENCODING_ASCII)
+    } else {
+        None
+    }
+}
+
+// === Syntax ===
+// The OnigSyntax* statics in regsyntax.rs are #[repr(C)] and exported under
+// their C names when this feature is enabled.
+
+#[cfg_attr(ffi_export, no_mangle)]
+pub static mut OnigDefaultSyntax: *const OnigSyntaxType = &OnigSyntaxOniguruma;
+
+/// Make `syntax` the default (`ONIG_SYNTAX_DEFAULT`); NULL restores
+/// `ONIG_SYNTAX_ONIGURUMA`.
+///
+/// # Safety
+///
+/// `syntax` must be NULL or outlive every regex compiled with the default,
+/// and no other thread may be reading `OnigDefaultSyntax` meanwhile.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_set_default_syntax(syntax: *const OnigSyntaxType) -> c_int {
+    let syntax: &'static OnigSyntaxType = if syntax.is_null() {
+        &OnigSyntaxOniguruma
+    } else {
+        &*syntax
+    };
+    OnigDefaultSyntax = syntax;
+    crate::regsyntax::onig_set_default_syntax(Some(syntax))
+}
+
+/// # Safety
+///
+/// `to` and `from` must be valid syntaxes; `to` must be writable.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_copy_syntax(to: *mut OnigSyntaxType, from: *const OnigSyntaxType) {
+    if !ptr::eq(to, from) {
+        crate::regsyntax::onig_copy_syntax(&mut *to, &*from);
+    }
+}
+
+/// # Safety
+///
+/// `syntax` must be a valid, writable syntax.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_set_syntax_op(syntax: *mut OnigSyntaxType, op: c_uint) {
+    crate::regsyntax::onig_set_syntax_op(&mut *syntax, op);
+}
+
+/// # Safety
+///
+/// `syntax` must be a valid, writable syntax.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_set_syntax_op2(syntax: *mut OnigSyntaxType, op2: c_uint) {
+    crate::regsyntax::onig_set_syntax_op2(&mut *syntax, op2);
+}
+
+/// # Safety
+///
+/// `syntax` must be a valid, writable syntax.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_set_syntax_behavior(syntax: *mut OnigSyntaxType, behavior: c_uint) {
+    crate::regsyntax::onig_set_syntax_behavior(&mut *syntax, behavior);
+}
+
+/// # Safety
+///
+/// `syntax` must be a valid, writable syntax.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_set_syntax_options(syntax: *mut OnigSyntaxType, options: c_uint) {
+    let options = OnigOptionType::from_bits_retain(options);
+    crate::regsyntax::onig_set_syntax_options(&mut *syntax, options);
+}
+
+/// # Safety
+///
+/// `syntax` must be a valid syntax.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_get_syntax_op(syntax: *const OnigSyntaxType) -> c_uint {
+    crate::regsyntax::onig_get_syntax_op(&*syntax)
+}
+
+/// # Safety
+///
+/// `syntax` must be a valid syntax.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_get_syntax_op2(syntax: *const OnigSyntaxType) -> c_uint {
+    crate::regsyntax::onig_get_syntax_op2(&*syntax)
+}
+
+/// # Safety
+///
+/// `syntax` must be a valid syntax.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_get_syntax_behavior(syntax: *const OnigSyntaxType) -> c_uint {
+    crate::regsyntax::onig_get_syntax_behavior(&*syntax)
+}
+
+/// # Safety
+///
+/// `syntax` must be a valid syntax.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_get_syntax_options(syntax: *const OnigSyntaxType) -> c_uint {
+    crate::regsyntax::onig_get_syntax_options(&*syntax).bits()
+}
+
+/// Set meta char `what` (`ONIG_META_CHAR_*`) to `code`, e.g. `%` as the
+/// escape. Takes effect with `ONIG_SYN_OP_VARIABLE_META_CHARACTERS`.
+///
+/// # Safety
+///
+/// `syntax` must be a valid, writable syntax.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_set_meta_char(
+    syntax: *mut OnigSyntaxType,
+    what: c_uint,
+    code: OnigCodePoint,
+) -> c_int {
+    crate::regsyntax::onig_set_meta_char(&mut *syntax, what, code)
+}
+
+/// # Safety
+///
+/// `reg` must be a live regex.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_get_syntax(reg: OnigRegex) -> *const OnigSyntaxType {
+    (*reg).syntax
 }
 
-// --- OnigRegion ---
+// === OnigRegion / OnigErrorInfo (C layout) ===
 
 #[repr(C)]
 pub struct OnigRegion {
@@ -45,363 +238,872 @@ pub struct OnigRegion {
     pub history_root: *mut c_void,
 }
 
-// --- OnigErrorInfo ---
-
 #[repr(C)]
 pub struct OnigErrorInfo {
     pub enc: OnigEncoding,
-    pub par: *const u8,
-    pub par_end: *const u8,
+    pub par: *mut u8,
+    pub par_end: *mut u8,
 }
 
-// --- Constants ---
-
-pub const ONIG_OPTION_NONE: c_uint = 0;
-pub const ONIG_OPTION_IGNORECASE: c_uint = 1;
-
-pub const ONIG_REGSET_POSITION_LEAD: c_int = 0;
-pub const ONIG_REGSET_REGEX_LEAD: c_int = 1;
-
-// --- Extern functions ---
+#[repr(C)]
+pub struct OnigCompileInfo {
+    pub num_of_elements: c_int,
+    pub pattern_enc: OnigEncoding,
+    pub target_enc: OnigEncoding,
+    pub syntax: *const OnigSyntaxType,
+    pub option: c_uint,
+    pub case_fold_flag: c_uint,
+}
 
-extern "C" {
-    pub static OnigEncodingUTF8: OnigEncodingType;
-    pub static OnigSyntaxOniguruma: OnigSyntaxType;
+/// Free the `beg`/`end` arrays of a region, which are always allocated by
+/// `region_alloc_arrays` as boxed slices of length `allocated`.
+unsafe fn region_free_arrays(r: &mut OnigRegion) {
+    if r.allocated > 0 {
+        let n = r.allocated as usize;
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(r.beg, n)));
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(r.end, n)));
+    }
+    r.beg = ptr::null_mut();
+    r.end = ptr::null_mut();
+    r.allocated = 0;
+}
 
-    pub fn onig_initialize(encodings: *const OnigEncoding, number_of_encodings: c_int) -> c_int;
+unsafe fn region_alloc_arrays(r: &mut OnigRegion, n: usize) {
+    let mut beg = vec![ONIG_REGION_NOTPOS; n].into_boxed_slice();
+    let mut end = vec![ONIG_REGION_NOTPOS; n].into_boxed_slice();
+    let old = r.num_regs.clamp(0, r.allocated) as usize;
+    if old > 0 {
+        beg[..old].copy_from_slice(std::slice::from_raw_parts(r.beg, old));
+        end[..old].copy_from_slice(std::slice::from_raw_parts(r.end, old));
+    }
+    region_free_arrays(r);
+    r.beg = Box::into_raw(beg) as *mut c_int;
+    r.end = Box::into_raw(end) as *mut c_int;
+    r.allocated = n as c_int;
+}
 
-    pub fn onig_end() -> c_int;
+/// Copy a Rust region into a C region, growing its arrays as needed.
+unsafe fn region_store(to: &mut OnigRegion, from: &RustRegion) {
+    let n = from.num_regs.max(0) as usize;
+    if (to.allocated as usize) < n {
+        region_alloc_arrays(to, n);
+    }
+    if n > 0 {
+        let beg = std::slice::from_raw_parts_mut(to.beg, n);
+        let end = std::slice::from_raw_parts_mut(to.end, n);
+        for i in 0..n {
+            beg[i] = from.beg(i);
+            end[i] = from.end(i);
+        }
+    }
+    to.num_regs = n as c_int;
+    to.history_root = ptr::null_mut();
+}
 
-    pub fn onig_new(
-        reg: *mut OnigRegex,
-        pattern: *const u8,
-        pattern_end: *const u8,
-        option: c_uint,
-        enc: OnigEncoding,
-        syntax: *const OnigSyntaxType,
-        einfo: *mut OnigErrorInfo,
-    ) -> c_int;
+unsafe fn region_load(from: &OnigRegion) -> RustRegion {
+    let mut r = RustRegion::new();
+    let n = from.num_regs.max(0) as usize;
+    r.resize(n as i32);
+    if n > 0 {
+        let beg = std::slice::from_raw_parts(from.beg, n);
+        let end = std::slice::from_raw_parts(from.end, n);
+        for i in 0..n {
+            r.set(i as i32, beg[i], end[i]);
+        }
+    }
+    r
+}
 
-    pub fn onig_free(reg: OnigRegex);
+/// In POSIX region mode, view `region` as the caller's `regmatch_t` array,
+/// which C requires to hold `num_mem + 1` entries.
+unsafe fn posix_regmatch<'a>(
+    reg: &RegexType,
+    region: *mut OnigRegion,
+    option: OnigOptionType,
+) -> Option<&'a mut [RegMatch<c_int>]> {
+    if region.is_null() || !opton_posix_region(option | reg.options) {
+        return None;
+    }
+    let n = reg.num_mem.max(0) as usize + 1;
+    Some(std::slice::from_raw_parts_mut(
+        region as *mut RegMatch<c_int>,
+        n,
+    ))
+}
 
-    pub fn onig_search(
-        reg: OnigRegex,
-        str: *const u8,
-        end: *const u8,
-        start: *const u8,
-        range: *const u8,
-        region: *mut OnigRegion,
-        option: c_uint,
-    ) -> c_int;
+/// View `[start, end)` as a byte slice; a NULL start yields an empty slice.
+unsafe fn bytes<'a>(start: *const u8, end: *const u8) -> Option<&'a [u8]> {
+    if start.is_null() {
+        return Some(&[]);
+    }
+    let len = end.offset_from(start);
+    if len < 0 {
+        return None;
+    }
+    Some(std::slice::from_raw_parts(start, len as usize))
+}
 
-    pub fn onig_match(
-        reg: OnigRegex,
-        str: *const u8,
-        end: *const u8,
-        at: *const u8,
-        region: *mut OnigRegion,
-        option: c_uint,
-    ) -> c_int;
+/// Offset of `p` within `data` (which starts at `base`), if in bounds.
+fn offset_in(base: *const u8, data: &[u8], p: *const u8) -> Option<usize> {
+    let off = (p as usize).checked_sub(base as usize)?;
+    (off <= data.len()).then_some(off)
+}
 
-    pub fn onig_region_new() -> *mut OnigRegion;
-    pub fn onig_region_free(region: *mut OnigRegion, free_self: c_int);
-    pub fn onig_region_clear(region: *mut OnigRegion);
+// === Library lifecycle ===
 
-    pub fn onig_regset_new(
-        rset: *mut *mut OnigRegSetType,
-        n: c_int,
-        regs: *const OnigRegex,
-    ) -> c_int;
+#[cfg_attr(ffi_export, no_mangle)]
+pub extern "C" fn onig_initialize(_encodings: *const OnigEncoding, _n: c_int) -> c_int {
+    ONIG_NORMAL
+}
 
-    pub fn onig_regset_free(set: *mut OnigRegSetType);
+#[cfg_attr(ffi_export, no_mangle)]
+pub extern "C" fn onig_init() -> c_int {
+    ONIG_NORMAL
+}
 
-    pub fn onig_regset_search(
-        set: *mut OnigRegSetType,
-        str: *const u8,
-        end: *const u8,
-        start: *const u8,
-        range: *const u8,
-        lead: c_int,
-        option: c_uint,
-        rmatch_pos: *mut c_int,
-    ) -> c_int;
+#[cfg_attr(ffi_export, no_mangle)]
+pub extern "C" fn onig_end() -> c_int {
+    ONIG_NORMAL
 }
 
-// --- RAII wrappers ---
+#[cfg_attr(ffi_export, no_mangle)]
+pub extern "C" fn onig_version() -> *const c_char {
+    c"6.9.10".as_ptr()
+}
 
-static C_INIT: Once = Once::new();
+// === Compile / free ===
 
-/// One-time init/end lifecycle for C Oniguruma.
-pub struct COnigInstance;
+/// # Safety
+///
+/// Pointers must be valid as documented for `onig_new` in oniguruma.h.
+/// `syntax` must outlive the returned regex.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_new(
+    reg: *mut OnigRegex,
+    pattern: *const u8,
+    pattern_end: *const u8,
+    option: c_uint,
+    enc: OnigEncoding,
+    syntax: *const OnigSyntaxType,
+    einfo: *mut OnigErrorInfo,
+) -> c_int {
+    if reg.is_null() || syntax.is_null() {
+        return ONIGERR_INVALID_ARGUMENT;
+    }
+    *reg = ptr::null_mut();
+    if !einfo.is_null() {
+        (*einfo).enc = enc;
+        (*einfo).par = ptr::null_mut();
+        (*einfo).par_end = ptr::null_mut();
+    }
+    let Some(rust_enc) = rust_encoding(enc) else {
+        return ONIGERR_NOT_SUPPORTED_ENCODING_COMBINATION;
+    };
+    let Some(pattern) = bytes(pattern, pattern_end) else {
+        return ONIGERR_INVALID_ARGUMENT;
+    };
+    let option = OnigOptionType::from_bits_retain(option);
+    let mut state = CompileState::default();
+    match crate::regcomp::onig_new_with(pattern, option, rust_enc, &*syntax, &mut state) {
+        Ok(r) => {
+            *reg = OnigRegexType::boxed(r, syntax);
+            ONIG_NORMAL
+        }
+        Err(e) => {
+            set_error_name(einfo, pattern, &state);
+            e.code()
+        }
+    }
+}
 
-impl COnigInstance {
-    pub fn new() -> Self {
-        C_INIT.call_once(|| unsafe {
-            let enc = &OnigEncodingUTF8 as OnigEncoding;
-            let r = onig_initialize(&enc as *const OnigEncoding, 1);
-            assert!(r == 0, "onig_initialize failed: {r}");
-        });
-        COnigInstance
+/// Unlike C, a pattern in another encoding than the target is transcoded
+/// instead of rejected.
+///
+/// # Safety
+///
+/// Pointer arguments follow the oniguruma.h contract.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_new_deluxe(
+    reg: *mut OnigRegex,
+    pattern: *const u8,
+    pattern_end: *const u8,
+    ci: *const OnigCompileInfo,
+    einfo: *mut OnigErrorInfo,
+) -> c_int {
+    if reg.is_null() || ci.is_null() || (*ci).syntax.is_null() {
+        return ONIGERR_INVALID_ARGUMENT;
+    }
+    *reg = ptr::null_mut();
+    let ci = &*ci;
+    if !einfo.is_null() {
+        (*einfo).enc = ci.pattern_enc;
+        (*einfo).par = ptr::null_mut();
+        (*einfo).par_end = ptr::null_mut();
+    }
+    let (Some(pattern_enc), Some(target_enc)) =
+        (rust_encoding(ci.pattern_enc), rust_encoding(ci.target_enc))
+    else {
+        return ONIGERR_NOT_SUPPORTED_ENCODING_COMBINATION;
+    };
+    let Some(pattern) = bytes(pattern, pattern_end) else {
+        return ONIGERR_INVALID_ARGUMENT;
+    };
+    let rust_ci = crate::oniguruma::OnigCompileInfo {
+        num_of_elements: ci.num_of_elements,
+        pattern_enc,
+        target_enc,
+        syntax: &*ci.syntax,
+        option: OnigOptionType::from_bits_retain(ci.option),
+        case_fold_flag: ci.case_fold_flag,
+    };
+    let mut state = CompileState::default();
+    match crate::regcomp::onig_new_deluxe_with(pattern, &rust_ci, &mut state) {
+        Ok(r) => {
+            *reg = OnigRegexType::boxed(r, ci.syntax);
+            ONIG_NORMAL
+        }
+        Err(e) => {
+            // A transcoded pattern's offsets do not point into the
+            // caller's buffer.
+            if ptr::eq(pattern_enc, target_enc) {
+                set_error_name(einfo, pattern, &state);
+            }
+            e.code()
+        }
     }
 }
 
-/// Compiled C regex with auto-free on drop.
-pub struct CRegex {
-    raw: OnigRegex,
+/// Point `einfo->par`/`par_end` at the name a compile error is about, so
+/// `onig_error_code_to_str` can fill in the `%n` of its message as C does.
+unsafe fn set_error_name(einfo: *mut OnigErrorInfo, pattern: &[u8], state: &CompileState) {
+    if einfo.is_null() {
+        return;
+    }
+    if let Some((s, e)) = state.error_name {
+        let base = pattern.as_ptr() as *mut u8;
+        (*einfo).par = base.add(s);
+        (*einfo).par_end = base.add(e);
+    }
 }
 
-impl CRegex {
-    pub fn new(pattern: &[u8], option: c_uint) -> Result<Self, c_int> {
-        let _inst = COnigInstance::new();
-        let mut reg: OnigRegex = ptr::null_mut();
-        let mut einfo = OnigErrorInfo {
-            enc: ptr::null(),
-            par: ptr::null(),
-            par_end: ptr::null(),
-        };
-        let r = unsafe {
-            onig_new(
-                &mut reg,
-                pattern.as_ptr(),
-                pattern.as_ptr().add(pattern.len()),
-                option,
-                &OnigEncodingUTF8 as OnigEncoding,
-                &OnigSyntaxOniguruma as *const OnigSyntaxType,
-                &mut einfo,
-            )
-        };
-        if r != 0 {
-            return Err(r);
-        }
-        Ok(CRegex { raw: reg })
-    }
-
-    pub fn search(
-        &self,
-        text: &[u8],
-        start: usize,
-        range: usize,
-        region: Option<&mut CRegion>,
-        option: c_uint,
-    ) -> c_int {
-        let str_ptr = text.as_ptr();
-        let end_ptr = unsafe { str_ptr.add(text.len()) };
-        let start_ptr = unsafe { str_ptr.add(start) };
-        let range_ptr = unsafe { str_ptr.add(range) };
-        let region_ptr = region.map_or(ptr::null_mut(), |r| r.raw);
-        unsafe {
-            onig_search(
-                self.raw, str_ptr, end_ptr, start_ptr, range_ptr, region_ptr, option,
-            )
-        }
+/// # Safety
+///
+/// `reg` must come from [`onig_new`] and not have been freed.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_free(reg: OnigRegex) {
+    if !reg.is_null() {
+        drop(Box::from_raw(reg));
     }
+}
+
+// === Search / match ===
 
-    pub fn match_at(
-        &self,
-        text: &[u8],
-        at: usize,
-        region: Option<&mut CRegion>,
-        option: c_uint,
-    ) -> c_int {
-        let str_ptr = text.as_ptr();
-        let end_ptr = unsafe { str_ptr.add(text.len()) };
-        let at_ptr = unsafe { str_ptr.add(at) };
-        let region_ptr = region.map_or(ptr::null_mut(), |r| r.raw);
-        unsafe { onig_match(self.raw, str_ptr, end_ptr, at_ptr, region_ptr, option) }
+/// # Safety
+///
+/// `str..end` must be a valid buffer containing `start` and `range`.
+/// With ONIG_OPTION_POSIX_REGION, a non-NULL `region` must point to
+/// `num_mem + 1` writable `regmatch_t` entries with `int` offsets.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_search(
+    reg: OnigRegex,
+    str: *const u8,
+    end: *const u8,
+    start: *const u8,
+    range: *const u8,
+    region: *mut OnigRegion,
+    option: c_uint,
+) -> c_int {
+    if reg.is_null() {
+        return ONIGERR_INVALID_ARGUMENT;
+    }
+    let Some(data) = bytes(str, end) else {
+        return ONIGERR_INVALID_ARGUMENT;
+    };
+    let (Some(s), Some(r)) = (offset_in(str, data, start), offset_in(str, data, range)) else {
+        return ONIGERR_INVALID_ARGUMENT;
+    };
+    let option = OnigOptionType::from_bits_retain(option);
+    if let Some(pmatch) = posix_regmatch(&(*reg).reg, region, option) {
+        return crate::regexec::onig_search_regmatch(
+            &(*reg).reg,
+            data,
+            data.len(),
+            s,
+            r,
+            pmatch,
+            option,
+        );
+    }
+    let rust_region = (!region.is_null()).then(RustRegion::new);
+    let (result, rust_region) =
+        crate::regexec::onig_search(&(*reg).reg, data, data.len(), s, r, rust_region, option);
+    if let (Some(rr), false) = (rust_region, region.is_null()) {
+        region_store(&mut *region, &rr);
     }
+    result
+}
 
-    pub fn raw(&self) -> OnigRegex {
-        self.raw
+/// # Safety
+///
+/// `str..end` must be a valid buffer containing `at`.
+/// With ONIG_OPTION_POSIX_REGION, a non-NULL `region` must point to
+/// `num_mem + 1` writable `regmatch_t` entries with `int` offsets.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_match(
+    reg: OnigRegex,
+    str: *const u8,
+    end: *const u8,
+    at: *const u8,
+    region: *mut OnigRegion,
+    option: c_uint,
+) -> c_int {
+    if reg.is_null() {
+        return ONIGERR_INVALID_ARGUMENT;
+    }
+    let Some(data) = bytes(str, end) else {
+        return ONIGERR_INVALID_ARGUMENT;
+    };
+    let Some(at) = offset_in(str, data, at) else {
+        return ONIGERR_INVALID_ARGUMENT;
+    };
+    let option = OnigOptionType::from_bits_retain(option);
+    if let Some(pmatch) = posix_regmatch(&(*reg).reg, region, option) {
+        return crate::regexec::onig_match_regmatch(
+            &(*reg).reg,
+            data,
+            data.len(),
+            at,
+            pmatch,
+            option,
+        );
+    }
+    let rust_region = (!region.is_null()).then(RustRegion::new);
+    let (result, rust_region) =
+        crate::regexec::onig_match(&(*reg).reg, data, data.len(), at, rust_region, option);
+    if let (Some(rr), false) = (rust_region, region.is_null()) {
+        region_store(&mut *region, &rr);
     }
+    result
+}
+
+// === Regions ===
+
+#[cfg_attr(ffi_export, no_mangle)]
+pub extern "C" fn onig_region_new() -> *mut OnigRegion {
+    Box::into_raw(Box::new(OnigRegion {
+        allocated: 0,
+        num_regs: 0,
+        beg: ptr::null_mut(),
+        end: ptr::null_mut(),
+        history_root: ptr::null_mut(),
+    }))
 }
 
-impl Drop for CRegex {
-    fn drop(&mut self) {
-        unsafe { onig_free(self.raw) }
+/// # Safety
+///
+/// `region` must be NULL or a region initialized by this library.
+/// With `free_self != 0` it must come from [`onig_region_new`].
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_region_free(region: *mut OnigRegion, free_self: c_int) {
+    if region.is_null() {
+        return;
+    }
+    region_free_arrays(&mut *region);
+    (*region).num_regs = 0;
+    if free_self != 0 {
+        drop(Box::from_raw(region));
     }
 }
 
-/// C region with auto-free on drop.
-pub struct CRegion {
-    raw: *mut OnigRegion,
+/// # Safety
+///
+/// `region` must point to writable, uninitialized or zeroed storage.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_region_init(region: *mut OnigRegion) {
+    if region.is_null() {
+        return;
+    }
+    region.write(OnigRegion {
+        allocated: 0,
+        num_regs: 0,
+        beg: ptr::null_mut(),
+        end: ptr::null_mut(),
+        history_root: ptr::null_mut(),
+    });
 }
 
-impl CRegion {
-    pub fn new() -> Self {
-        CRegion {
-            raw: unsafe { onig_region_new() },
-        }
+/// # Safety
+///
+/// `region` must be a region initialized by this library.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_region_clear(region: *mut OnigRegion) {
+    let r = &mut *region;
+    let n = r.num_regs.max(0) as usize;
+    if n > 0 {
+        std::slice::from_raw_parts_mut(r.beg, n).fill(ONIG_REGION_NOTPOS);
+        std::slice::from_raw_parts_mut(r.end, n).fill(ONIG_REGION_NOTPOS);
     }
+    r.history_root = ptr::null_mut();
+}
 
-    pub fn clear(&mut self) {
-        unsafe { onig_region_clear(self.raw) }
+/// # Safety
+///
+/// `region` must be a region initialized by this library.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_region_resize(region: *mut OnigRegion, n: c_int) -> c_int {
+    if n < 0 {
+        return ONIGERR_INVALID_ARGUMENT;
+    }
+    let r = &mut *region;
+    if r.allocated < n {
+        region_alloc_arrays(r, n as usize);
     }
+    r.num_regs = n;
+    ONIG_NORMAL
 }
 
-impl Drop for CRegion {
-    fn drop(&mut self) {
-        unsafe { onig_region_free(self.raw, 1) }
+/// # Safety
+///
+/// `region` must be a region initialized by this library.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_region_set(
+    region: *mut OnigRegion,
+    at: c_int,
+    beg: c_int,
+    end: c_int,
+) -> c_int {
+    if at < 0 {
+        return ONIGERR_INVALID_ARGUMENT;
     }
+    let r = &mut *region;
+    if at >= r.allocated {
+        region_alloc_arrays(r, at as usize + 1);
+        r.num_regs = at + 1;
+    }
+    *r.beg.add(at as usize) = beg;
+    *r.end.add(at as usize) = end;
+    ONIG_NORMAL
 }
 
-/// C RegSet with auto-free on drop.
-pub struct CRegSet {
-    raw: *mut OnigRegSetType,
+/// # Safety
+///
+/// Both regions must be initialized by this library.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_region_copy(to: *mut OnigRegion, from: *const OnigRegion) {
+    if ptr::eq(to, from) {
+        return;
+    }
+    let r = region_load(&*from);
+    region_store(&mut *to, &r);
 }
 
-impl CRegSet {
-    /// Create a new RegSet from pre-compiled CRegex handles.
-    /// IMPORTANT: The caller must keep the CRegex objects alive; the
-    /// C library does NOT copy them. The CRegex objects must NOT be
-    /// freed before the RegSet. Use `into_raw()` on CRegex to transfer ownership.
-    pub fn new(regs: &[OnigRegex]) -> Result<Self, c_int> {
-        let _inst = COnigInstance::new();
-        let mut set: *mut OnigRegSetType = ptr::null_mut();
-        let r = unsafe { onig_regset_new(&mut set, regs.len() as c_int, regs.as_ptr()) };
-        if r != 0 {
-            return Err(r);
+// === Names and captures ===
+
+/// # Safety
+///
+/// `name..name_end` must be a valid buffer; `nums` must be writable.
+/// The array stored in `*nums` lives as long as `reg`.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_name_to_group_numbers(
+    reg: OnigRegex,
+    name: *const u8,
+    name_end: *const u8,
+    nums: *mut *mut c_int,
+) -> c_int {
+    let Some(name) = bytes(name, name_end) else {
+        return ONIGERR_INVALID_ARGUMENT;
+    };
+    match crate::regexec::onig_name_to_group_numbers(&(*reg).reg, name) {
+        Ok(list) => {
+            if !nums.is_null() {
+                *nums = list.as_ptr() as *mut c_int;
+            }
+            list.len() as c_int
         }
-        Ok(CRegSet { raw: set })
-    }
-
-    pub fn search(
-        &mut self,
-        text: &[u8],
-        start: usize,
-        range: usize,
-        lead: c_int,
-        option: c_uint,
-    ) -> (c_int, c_int) {
-        let str_ptr = text.as_ptr();
-        let end_ptr = unsafe { str_ptr.add(text.len()) };
-        let start_ptr = unsafe { str_ptr.add(start) };
-        let range_ptr = unsafe { str_ptr.add(range) };
-        let mut match_pos: c_int = -1;
-        let idx = unsafe {
-            onig_regset_search(
-                self.raw,
-                str_ptr,
-                end_ptr,
-                start_ptr,
-                range_ptr,
-                lead,
-                option,
-                &mut match_pos,
-            )
-        };
-        (idx, match_pos)
+        Err(e) => e,
     }
 }
 
-impl Drop for CRegSet {
-    fn drop(&mut self) {
-        unsafe { onig_regset_free(self.raw) }
+/// # Safety
+///
+/// `name..name_end` must be a valid buffer; `region` may be NULL.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_name_to_backref_number(
+    reg: OnigRegex,
+    name: *const u8,
+    name_end: *const u8,
+    region: *const OnigRegion,
+) -> c_int {
+    let Some(name) = bytes(name, name_end) else {
+        return ONIGERR_INVALID_ARGUMENT;
+    };
+    let region = (!region.is_null()).then(|| region_load(&*region));
+    match crate::regexec::onig_name_to_backref_number(&(*reg).reg, name, region.as_ref()) {
+        Ok(n) | Err(n) => n,
     }
 }
 
-// --- vscode-oniguruma C Scanner ---
-//
-// FFI to the extracted scanner from vscode-oniguruma (benches/vscode_scanner_native.c).
+pub type OnigForeachNameFunc = unsafe extern "C" fn(
+    name: *const u8,
+    name_end: *const u8,
+    ngroup_num: c_int,
+    group_nums: *mut c_int,
+    reg: OnigRegex,
+    arg: *mut c_void,
+) -> c_int;
 
-/// Opaque C scanner type (OnigScanner_ struct).
-#[repr(C)]
-pub struct COnigScanner {
-    _opaque: [u8; 0],
-}
-
-extern "C" {
-    fn createOnigScanner(
-        patterns: *const *mut u8,
-        lengths: *const c_int,
-        count: c_int,
-        options: c_int,
-        syntax: *const OnigSyntaxType,
-    ) -> *mut COnigScanner;
-
-    fn findNextOnigScannerMatch(
-        scanner: *mut COnigScanner,
-        str_cache_id: c_int,
-        str_data: *const u8,
-        str_length: c_int,
-        position: c_int,
-        options: c_int,
-    ) -> *const c_int;
-
-    fn freeOnigScanner(scanner: *mut COnigScanner);
-}
-
-/// RAII wrapper around the vscode-oniguruma C scanner.
-pub struct CScanner {
-    handle: *mut COnigScanner,
-    /// Owned copies of pattern data (must outlive the scanner).
-    _patterns: Vec<Vec<u8>>,
-}
-
-impl CScanner {
-    /// Create a new C scanner from pattern byte slices.
-    pub fn new(patterns: &[&[u8]]) -> Result<Self, c_int> {
-        let _inst = COnigInstance::new();
-        let mut owned: Vec<Vec<u8>> = patterns.iter().map(|p| p.to_vec()).collect();
-        let ptrs: Vec<*mut u8> = owned.iter_mut().map(|v| v.as_mut_ptr()).collect();
-        let lengths: Vec<c_int> = patterns.iter().map(|p| p.len() as c_int).collect();
-
-        let handle = unsafe {
-            createOnigScanner(
-                ptrs.as_ptr(),
-                lengths.as_ptr(),
-                patterns.len() as c_int,
-                ONIG_OPTION_NONE as c_int,
-                &OnigSyntaxOniguruma as *const OnigSyntaxType,
-            )
-        };
-        if handle.is_null() {
-            return Err(-1);
+/// # Safety
+///
+/// `reg` must be a live regex; `func` is called with pointers into it.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_foreach_name(
+    reg: OnigRegex,
+    func: OnigForeachNameFunc,
+    arg: *mut c_void,
+) -> c_int {
+    crate::regexec::onig_foreach_name(&(*reg).reg, |name, group_nums| {
+        let range = name.as_ptr_range();
+        func(
+            range.start,
+            range.end,
+            group_nums.len() as c_int,
+            group_nums.as_ptr() as *mut c_int,
+            reg,
+            arg,
+        )
+    })
+}
+
+/// # Safety
+///
+/// `reg` must be a live regex.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_number_of_names(reg: OnigRegex) -> c_int {
+    crate::regexec::onig_number_of_names(&(*reg).reg)
+}
+
+/// # Safety
+///
+/// `reg` must be a live regex.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_number_of_captures(reg: OnigRegex) -> c_int {
+    crate::regexec::onig_number_of_captures(&(*reg).reg)
+}
+
+// === Errors ===
+
+/// Write the message for `code` into `s`, which must hold at least
+/// `ONIG_MAX_ERROR_MESSAGE_LEN` bytes. Returns the message length.
+///
+/// # Safety
+///
+/// `s` must be writable for `ONIG_MAX_ERROR_MESSAGE_LEN` bytes; `einfo`
+/// may be NULL.
+#[cfg_attr(ffi_export, no_mangle)]
+pub unsafe extern "C" fn onig_error_code_to_str(
+    s: *mut u8,
+    code: c_int,
+    einfo: *const OnigErrorInfo,
+) -> c_int {
+    let par = if einfo.is_null() || (*einfo).par.is_null() {
+        None
+    } else {
+        bytes((*einfo).par, (*einfo).par_end)
+    };
+    let msg = crate::regerror::onig_error_code_to_str(code, par);
+    let n = msg.len().min(ONIG_MAX_ERROR_MESSAGE_LEN - 1);
+    ptr::copy_nonoverlapping(msg.as_ptr(), s, n);
+    *s.add(n) = 0;
+    n as c_int
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oniguruma::{ONIGERR_UNDEFINED_NAME_REFERENCE, ONIG_MISMATCH};
+
+    unsafe fn compile(pattern: &[u8]) -> OnigRegex {
+        let mut reg: OnigRegex = ptr::null_mut();
+        let r = onig_new(
+            &mut reg,
+            pattern.as_ptr(),
+            pattern.as_ptr().add(pattern.len()),
+            0,
+            &OnigEncodingUTF8,
+            OnigDefaultSyntax,
+            ptr::null_mut(),
+        );
+        assert_eq!(r, ONIG_NORMAL);
+        reg
+    }
+
+    #[test]
+    fn c_search_fills_region() {
+        unsafe {
+            let reg = compile(b"(?<y>\\d+)-(\\d+)");
+            let text = b"on 2026-10!";
+            let region = onig_region_new();
+            let p = text.as_ptr();
+            let e = p.add(text.len());
+            let r = onig_search(reg, p, e, p, e, region, 0);
+            assert_eq!(r, 3);
+            assert_eq!((*region).num_regs, 2);
+            assert_eq!((*(*region).beg, *(*region).end), (3, 10));
+            assert_eq!((*(*region).beg.add(1), *(*region).end.add(1)), (3, 7));
+
+            let r = onig_match(reg, p, e, p, region, 0);
+            assert_eq!(r, ONIG_MISMATCH);
+
+            onig_region_free(region, 1);
+            onig_free(reg);
         }
-        Ok(CScanner {
-            handle,
-            _patterns: owned,
-        })
-    }
-
-    /// Find the next match. Returns `(pattern_index, [(beg, end), ...])` or `None`.
-    pub fn find_next_match(
-        &self,
-        text: &[u8],
-        str_cache_id: i32,
-        position: usize,
-    ) -> Option<(usize, Vec<(i32, i32)>)> {
-        let encoded = unsafe {
-            findNextOnigScannerMatch(
-                self.handle,
-                str_cache_id as c_int,
-                text.as_ptr(),
-                text.len() as c_int,
-                position as c_int,
-                ONIG_OPTION_NONE as c_int,
-            )
-        };
-        if encoded.is_null() {
-            return None;
+    }
+
+    #[test]
+    fn c_new_deluxe_transcodes_pattern() {
+        unsafe {
+            let ci = OnigCompileInfo {
+                num_of_elements: 5,
+                pattern_enc: &OnigEncodingUTF8,
+                target_enc: &OnigEncodingASCII,
+                syntax: OnigDefaultSyntax,
+                option: 0,
+                case_fold_flag: 0,
+            };
+            let pattern = "é+".as_bytes();
+            let mut reg: OnigRegex = ptr::null_mut();
+            let pp = pattern.as_ptr();
+            let r = onig_new_deluxe(&mut reg, pp, pp.add(pattern.len()), &ci, ptr::null_mut());
+            assert_eq!(r, ONIG_NORMAL);
+            let text = b"x\xe9\xe9";
+            let p = text.as_ptr();
+            let e = p.add(text.len());
+            assert_eq!(onig_search(reg, p, e, p, e, ptr::null_mut(), 0), 1);
+            onig_free(reg);
+
+            let ci = OnigCompileInfo {
+                target_enc: ptr::null(),
+                ..ci
+            };
+            let r = onig_new_deluxe(&mut reg, pp, pp.add(pattern.len()), &ci, ptr::null_mut());
+            assert_eq!(r, ONIGERR_NOT_SUPPORTED_ENCODING_COMBINATION);
+            assert!(reg.is_null());
+        }
+    }
+
+    #[test]
+    fn c_search_posix_region_fills_regmatch() {
+        unsafe {
+            let reg = compile(b"(a)|(b)");
+            let text = b"xb";
+            let p = text.as_ptr();
+            let e = p.add(text.len());
+            let mut pmatch = [RegMatch { rm_so: 7, rm_eo: 7 }; 3];
+            let region = pmatch.as_mut_ptr() as *mut OnigRegion;
+            let posix = crate::oniguruma::ONIG_OPTION_POSIX_REGION.bits();
+            let r = onig_search(reg, p, e, p, e, region, posix);
+            assert_eq!(r, 1);
+            let spans: Vec<_> = pmatch.iter().map(|m| (m.rm_so, m.rm_eo)).collect();
+            assert_eq!(spans, [(1, 2), (-1, -1), (1, 2)]);
+
+            let r = onig_match(reg, p, e, p, region, posix);
+            assert_eq!(r, ONIG_MISMATCH);
+            assert_eq!((pmatch[0].rm_so, pmatch[0].rm_eo), (1, 2));
+            onig_free(reg);
         }
-        // Decode the encoded region: [index, num_regs, beg0, end0, beg1, end1, ...]
+    }
+
+    #[test]
+    fn c_names() {
         unsafe {
-            let index = *encoded as usize;
-            let num_regs = *encoded.add(1) as usize;
-            let mut captures = Vec::with_capacity(num_regs);
-            for i in 0..num_regs {
-                let beg = *encoded.add(2 + 2 * i);
-                let end = *encoded.add(3 + 2 * i);
-                captures.push((beg, end));
+            let reg = compile(b"(?<a>x)(?<b>y)(?<a>z)");
+            let name = b"a";
+            let mut nums: *mut c_int = ptr::null_mut();
+            let n = onig_name_to_group_numbers(reg, name.as_ptr(), name.as_ptr().add(1), &mut nums);
+            assert_eq!(n, 2);
+            assert_eq!(std::slice::from_raw_parts(nums, 2), &[1, 3]);
+            assert_eq!(onig_number_of_names(reg), 2);
+
+            unsafe extern "C" fn count(
+                _name: *const u8,
+                _name_end: *const u8,
+                n: c_int,
+                _nums: *mut c_int,
+                _reg: OnigRegex,
+                arg: *mut c_void,
+            ) -> c_int {
+                *(arg as *mut c_int) += n;
+                0
             }
-            Some((index, captures))
+            let mut total: c_int = 0;
+            let r = onig_foreach_name(reg, count, &mut total as *mut c_int as *mut c_void);
+            assert_eq!(r, ONIG_NORMAL);
+            assert_eq!(total, 3);
+
+            let bad = b"zz";
+            let n = onig_name_to_group_numbers(reg, bad.as_ptr(), bad.as_ptr().add(2), &mut nums);
+            assert_eq!(n, ONIGERR_UNDEFINED_NAME_REFERENCE);
+            onig_free(reg);
         }
     }
-}
 
-impl Drop for CScanner {
-    fn drop(&mut self) {
+    #[test]
+    fn c_region_set_copy_and_error_str() {
         unsafe {
-            freeOnigScanner(self.handle);
+            let a = onig_region_new();
+            assert_eq!(onig_region_set(a, 2, 5, 9), ONIG_NORMAL);
+            assert_eq!((*a).num_regs, 3);
+            let b = onig_region_new();
+            onig_region_copy(b, a);
+            assert_eq!((*b).num_regs, 3);
+            assert_eq!(*(*b).end.add(2), 9);
+            onig_region_clear(b);
+            assert_eq!(*(*b).beg.add(2), ONIG_REGION_NOTPOS);
+            onig_region_free(a, 1);
+            onig_region_free(b, 1);
+
+            let mut buf = [0u8; ONIG_MAX_ERROR_MESSAGE_LEN];
+            let mut reg: OnigRegex = ptr::null_mut();
+            let pat = b"(";
+            let r = onig_new(
+                &mut reg,
+                pat.as_ptr(),
+                pat.as_ptr().add(1),
+                0,
+                &OnigEncodingUTF8,
+                &OnigSyntaxOniguruma,
+                ptr::null_mut(),
+            );
+            assert!(r < 0 && reg.is_null());
+            let n = onig_error_code_to_str(buf.as_mut_ptr(), r, ptr::null());
+            assert_eq!(
+                &buf[..n as usize],
+                b"end pattern with unmatched parenthesis"
+            );
+        }
+    }
+
+    unsafe fn error_message(pattern: &[u8]) -> Vec<u8> {
+        let mut reg: OnigRegex = ptr::null_mut();
+        let mut einfo = OnigErrorInfo {
+            enc: ptr::null(),
+            par: ptr::null_mut(),
+            par_end: ptr::null_mut(),
+        };
+        let r = onig_new(
+            &mut reg,
+            pattern.as_ptr(),
+            pattern.as_ptr().add(pattern.len()),
+            0,
+            &OnigEncodingUTF8,
+            &OnigSyntaxOniguruma,
+            &mut einfo,
+        );
+        assert!(r < 0 && reg.is_null());
+        let mut buf = [0u8; ONIG_MAX_ERROR_MESSAGE_LEN];
+        let n = onig_error_code_to_str(buf.as_mut_ptr(), r, &einfo);
+        buf[..n as usize].to_vec()
+    }
+
+    #[test]
+    fn c_error_info_names_the_group() {
+        unsafe {
+            assert_eq!(
+                error_message(b"a\\k<foo>"),
+                b"undefined name <foo> reference"
+            );
+            assert_eq!(error_message(b"(?<1a>y)"), b"invalid group name <1a>");
+            assert_eq!(
+                error_message(b"\\g<nope>"),
+                b"undefined name <nope> reference"
+            );
+        }
+    }
+
+    #[cfg(feature = "c-oniguruma")]
+    #[test]
+    fn c_error_info_matches_c_oniguruma() {
+        for pattern in [&b"a\\k<foo>"[..], b"(?<1a>y)", b"\\g<nope>", b"("] {
+            let c = crate::c_oniguruma::CRegex::error_message(pattern).unwrap();
+            assert_eq!(unsafe { error_message(pattern) }, c);
+        }
+    }
+
+    #[test]
+    fn c_custom_escape_syntax() {
+        use crate::oniguruma::{ONIG_META_CHAR_ESCAPE, ONIG_SYN_OP_VARIABLE_META_CHARACTERS};
+
+        unsafe {
+            // A config-file dialect with `%` as the escape
+            let syntax = Box::leak(Box::new(OnigSyntaxOniguruma.clone()));
+            onig_copy_syntax(syntax, &OnigSyntaxOniguruma);
+            let op = onig_get_syntax_op(syntax);
+            onig_set_syntax_op(syntax, op | ONIG_SYN_OP_VARIABLE_META_CHARACTERS);
+            assert_eq!(
+                onig_set_meta_char(syntax, ONIG_META_CHAR_ESCAPE, '%' as OnigCodePoint),
+                ONIG_NORMAL
+            );
+            assert_eq!(onig_set_meta_char(syntax, 99, 0), ONIGERR_INVALID_ARGUMENT);
+            onig_set_syntax_options(syntax, 0);
+            assert_eq!(onig_get_syntax_options(syntax), 0);
+
+            let pat = b"%d+";
+            let mut reg: OnigRegex = ptr::null_mut();
+            let r = onig_new(
+                &mut reg,
+                pat.as_ptr(),
+                pat.as_ptr().add(pat.len()),
+                0,
+                &OnigEncodingUTF8,
+                syntax,
+                ptr::null_mut(),
+            );
+            assert_eq!(r, ONIG_NORMAL);
+            assert!(ptr::eq(onig_get_syntax(reg), syntax));
+            let text = b"\\d 42";
+            let p = text.as_ptr();
+            let e = p.add(text.len());
+            assert_eq!(onig_search(reg, p, e, p, e, ptr::null_mut(), 0), 3);
+            onig_free(reg);
+
+            // A copy reads back the same settings and compiles the same way,
+            // and onig_new_deluxe() keeps the caller's pointer too
+            let copy = Box::leak(Box::new(OnigSyntaxOniguruma.clone()));
+            onig_copy_syntax(copy, syntax);
+            assert_eq!(onig_get_syntax_op(copy), onig_get_syntax_op(syntax));
+            assert_eq!(onig_get_syntax_op2(copy), onig_get_syntax_op2(syntax));
+            assert_eq!(
+                onig_get_syntax_behavior(copy),
+                onig_get_syntax_behavior(syntax)
+            );
+            assert_eq!(onig_get_syntax_options(copy), 0);
+            let ci = OnigCompileInfo {
+                num_of_elements: 5,
+                pattern_enc: &OnigEncodingUTF8,
+                target_enc: &OnigEncodingUTF8,
+                syntax: copy,
+                option: 0,
+                case_fold_flag: 0,
+            };
+            let r = onig_new_deluxe(
+                &mut reg,
+                pat.as_ptr(),
+                pat.as_ptr().add(pat.len()),
+                &ci,
+                ptr::null_mut(),
+            );
+            assert_eq!(r, ONIG_NORMAL);
+            assert!(ptr::eq(onig_get_syntax(reg), copy));
+            assert_eq!(onig_search(reg, p, e, p, e, ptr::null_mut(), 0), 3);
+            onig_free(reg);
+
+            // NULL restores the built-in default
+            assert_eq!(onig_set_default_syntax(ptr::null()), ONIG_NORMAL);
+            assert!(ptr::eq(OnigDefaultSyntax, &OnigSyntaxOniguruma));
         }
     }
 }
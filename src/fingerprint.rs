@@ -0,0 +1,257 @@
+// fingerprint.rs - Stable hash of a compiled regex.
+//
+// Caches keyed by pattern source (TextMate scanner caches, rule
+// tables built from many grammars) hold one entry per spelling of what is
+// often the same program. Hashing what the matcher actually runs -- the
+// bytecode with its operands, the options, the encoding and the few tables
+// the VM reads besides the bytecode -- lets them share one entry.
+//
+// The hash is FNV-1a over a fixed little-endian serialization, so it is
+// the same on every platform and run. It changes only when the compiled
+// form of a pattern changes, e.g. after an optimizer change in a release.
+
+use crate::regint::*;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a, fed integers as fixed-width little-endian bytes and byte strings
+/// with their length first, so adjacent fields cannot run into each other.
+struct Fnv(u64);
+
+impl Fnv {
+    fn bytes(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 ^ u64::from(b)).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn u32(&mut self, v: u32) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    fn i32(&mut self, v: i32) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    fn str(&mut self, s: &[u8]) {
+        self.u32(s.len() as u32);
+        self.bytes(s);
+    }
+
+    fn words(&mut self, words: &[u32]) {
+        self.u32(words.len() as u32);
+        for &w in words {
+            self.u32(w);
+        }
+    }
+}
+
+/// Hash of everything that decides what `reg` matches; see
+/// [`Regex::fingerprint`](crate::api::Regex::fingerprint).
+pub(crate) fn fingerprint(reg: &RegexType) -> u64 {
+    let mut h = Fnv(FNV_OFFSET);
+    h.str(reg.enc.name().as_bytes());
+    h.u32(reg.options.bits());
+    h.u32(reg.case_fold_flag);
+    h.u32(reg.newline as u32);
+    h.i32(reg.num_mem);
+    h.i32(reg.num_repeat);
+    h.i32(reg.num_empty_check);
+    h.i32(reg.num_call);
+    h.u32(reg.capture_history);
+    h.u32(reg.push_mem_start);
+    h.u32(reg.push_mem_end);
+    for r in &reg.repeat_range {
+        h.i32(r.lower);
+        h.i32(r.upper);
+    }
+
+    h.u32(reg.ops.len() as u32);
+    for op in &reg.ops {
+        h.u32(op.opcode as u32);
+        operands(&mut h, &op.payload);
+    }
+
+    if let Some(ref ext) = reg.extp {
+        h.i32(ext.callout_num);
+        for c in &ext.callout_list {
+            h.i32(c.of);
+            h.i32(c.callout_in);
+            h.i32(c.builtin_id);
+            h.str(c.tag.as_deref().unwrap_or_default());
+            h.str(c.content_end.as_deref().unwrap_or_default());
+            h.u32(c.args.len() as u32);
+            for arg in &c.args {
+                match arg {
+                    CalloutArg::Long(n) => h.bytes(&n.to_le_bytes()),
+                    CalloutArg::Char(c) => h.bytes(&[*c]),
+                    CalloutArg::Tag(s) | CalloutArg::Str(s) => h.str(s),
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "unstable-opcodes")]
+    for (name, arg) in reg.opcodes.uses() {
+        h.str(name);
+        h.str(arg);
+    }
+
+    h.0
+}
+
+/// Feed the operands of one instruction. The opcode is hashed before, and
+/// decides the payload variant, so the variant itself needs no tag.
+fn operands(h: &mut Fnv, payload: &OperationPayload) {
+    match payload {
+        OperationPayload::None | OperationPayload::Return => {}
+        OperationPayload::Exact { s } => h.bytes(s),
+        OperationPayload::ExactN { s, n } => {
+            h.str(s);
+            h.i32(*n);
+        }
+        OperationPayload::ExactLenN { s, n, len } => {
+            h.str(s);
+            h.i32(*n);
+            h.i32(*len);
+        }
+        OperationPayload::CClass { bsp } => h.words(&bsp[..]),
+        OperationPayload::CClassMb { mb } => h.words(mb),
+        OperationPayload::CClassMix { mb, bsp } => {
+            h.words(mb);
+            h.words(&bsp[..]);
+        }
+        OperationPayload::CClassSmall { bsp, codes } => {
+            h.words(&bsp[..]);
+            h.words(&codes[..]);
+        }
+        OperationPayload::AnyCharStarPeekNext { c } => h.bytes(&[*c]),
+        OperationPayload::WordBoundary { mode } => h.i32(*mode),
+        OperationPayload::TextSegmentBoundary { boundary_type, not } => {
+            h.u32(*boundary_type as u32);
+            h.bytes(&[*not as u8]);
+        }
+        OperationPayload::CheckPosition { check_type } => h.u32(*check_type as u32),
+        OperationPayload::BackRefN { n1 } => h.i32(*n1),
+        OperationPayload::BackRefGeneral {
+            num,
+            ns,
+            nest_level,
+        } => {
+            h.i32(*num);
+            h.u32(ns.len() as u32);
+            ns.iter().for_each(|&n| h.i32(n));
+            h.i32(*nest_level);
+        }
+        OperationPayload::MemoryStart { num } | OperationPayload::MemoryEnd { num } => h.i32(*num),
+        OperationPayload::Jump { addr } | OperationPayload::Push { addr } => h.i32(*addr),
+        OperationPayload::PushOrJumpExact1 { addr, c }
+        | OperationPayload::PushIfPeekNext { addr, c } => {
+            h.i32(*addr);
+            h.bytes(&[*c]);
+        }
+        OperationPayload::PopToMark { id } | OperationPayload::RepeatInc { id } => h.i32(*id),
+        OperationPayload::Repeat { id, addr } => {
+            h.i32(*id);
+            h.i32(*addr);
+        }
+        OperationPayload::EmptyCheckStart { mem } => h.i32(*mem),
+        OperationPayload::EmptyCheckEnd {
+            mem,
+            empty_status_mem,
+        } => {
+            h.i32(*mem);
+            h.u32(*empty_status_mem);
+        }
+        OperationPayload::Move { n } => h.i32(*n),
+        OperationPayload::StepBackStart {
+            initial,
+            remaining,
+            addr,
+        } => {
+            h.i32(*initial);
+            h.i32(*remaining);
+            h.i32(*addr);
+        }
+        OperationPayload::CutToMark { id, restore_pos } => {
+            h.i32(*id);
+            h.bytes(&[*restore_pos as u8]);
+        }
+        OperationPayload::Mark { id, save_pos } => {
+            h.i32(*id);
+            h.bytes(&[*save_pos as u8]);
+        }
+        OperationPayload::SaveVal { save_type, id } => {
+            h.u32(*save_type as u32);
+            h.i32(*id);
+        }
+        OperationPayload::UpdateVar {
+            var_type,
+            id,
+            clear,
+        } => {
+            h.u32(*var_type as u32);
+            h.i32(*id);
+            h.bytes(&[*clear as u8]);
+        }
+        OperationPayload::Call { addr } => h.i32(*addr),
+        OperationPayload::CalloutContents { num } | OperationPayload::Extension { num } => {
+            h.i32(*num)
+        }
+        OperationPayload::CalloutName { num, id } => {
+            h.i32(*num);
+            h.i32(*id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::Regex;
+    use crate::oniguruma::*;
+
+    fn fp(pattern: &str) -> u64 {
+        fingerprint(Regex::new(pattern).unwrap().as_raw())
+    }
+
+    #[test]
+    fn same_program_same_fingerprint() {
+        assert_eq!(fp("abc"), fp("abc"));
+        assert_eq!(fp("a(?:b)c"), fp("abc"));
+        assert_eq!(fp("[a-c]"), fp("[abc]"));
+        assert_eq!(fp("x{2}"), fp("xx"));
+        assert_eq!(fp(r"(?x) a b # c"), fp("ab"));
+    }
+
+    #[test]
+    fn different_program_different_fingerprint() {
+        let patterns = [
+            "abc", "abd", "ab", "[a-c]", "[a-d]", "(a)bc", "a|b", "b|a", "a*", "a*?", "a+", r"\d",
+            r"\w", "^a", "a$", r"(?i)abc", r"(?<=a)b", r"(a)\1",
+        ];
+        let mut seen: Vec<u64> = patterns.iter().map(|p| fp(p)).collect();
+        seen.sort_unstable();
+        seen.dedup();
+        assert_eq!(seen.len(), patterns.len());
+
+        // Options and encoding count even where the bytecode is the same
+        let re = Regex::builder("a").option(ONIG_OPTION_FIND_LONGEST).build();
+        assert_ne!(fingerprint(re.unwrap().as_raw()), fp("a"));
+        let ascii = crate::regcomp::onig_new(
+            b"a",
+            ONIG_OPTION_NONE,
+            &crate::encodings::ascii::ONIG_ENCODING_ASCII,
+            &crate::regsyntax::OnigSyntaxOniguruma,
+        );
+        assert_ne!(fingerprint(&ascii.unwrap()), fp("a"));
+    }
+
+    #[test]
+    fn fixed_value() {
+        // The serialization is part of the contract: the same compiled
+        // program hashes the same on every platform and run.
+        assert_eq!(fp("abc"), 0xf6341668361f02a7);
+    }
+}
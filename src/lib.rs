@@ -71,8 +71,12 @@
 //! | `regsyntax.c` | [`regsyntax`] | 12 syntax definitions |
 //! | `regset.c` | [`regset`] | Multi-regex search (RegSet) |
 //! | `regerror.c` | [`regerror`] | Error messages |
+//! | `reggnu.c` | [`reggnu`] | GNU regex API (`re_search`, `re_match`) |
 //! | `regtrav.c` | [`regtrav`] | Capture tree traversal |
 
+// Without the `std` feature the crate needs only `core` and
+// `alloc`; the global registries go through `sys` (see sys.rs).
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 // Allow patterns inherent to the C port.
 #![allow(dead_code)]
 #![allow(clippy::empty_line_after_doc_comments)]
@@ -81,15 +85,28 @@
 // Enable #[coverage(off)] attribute when running under cargo-llvm-cov on nightly.
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 
+#[macro_use]
+extern crate alloc;
+
+pub mod analysis;
 pub mod api;
+pub mod ast;
+pub mod bytes;
+pub mod charindex;
 pub mod encodings;
 pub mod error;
+#[cfg(feature = "unstable-opcodes")]
+pub mod extension;
+mod fingerprint;
+pub mod lint;
+pub mod memhook;
 pub mod oniguruma;
 pub mod prelude;
 pub mod regcomp;
 pub mod regenc;
 pub mod regerror;
 pub mod regexec;
+pub mod reggnu;
 pub mod regint;
 pub mod regparse;
 pub mod regparse_types;
@@ -97,7 +114,26 @@ pub mod regset;
 pub mod regsyntax;
 pub mod regtrav;
 pub mod scanner;
+pub mod session;
+mod shiftor;
+#[cfg(feature = "exec-stats")]
+pub mod stats;
+mod sys;
+pub mod template;
+#[cfg(feature = "testgen")]
+pub mod testgen;
 pub mod unicode;
+pub mod validate;
+
+pub use ast::parse;
+pub use validate::validate;
 
+#[cfg(feature = "c-oniguruma")]
+pub mod c_oniguruma;
 #[cfg(feature = "ffi")]
 pub mod ffi;
+#[cfg(feature = "c-oniguruma")]
+pub mod parity;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,281 @@
+// lint.rs - Hygiene checks on a pattern.
+//
+// Projects that maintain many patterns (TextMate grammars, log
+// parsers) want to catch leftovers and typos the engine happily accepts:
+// names nothing refers to, names defined twice, repeats of something that
+// can only match empty, and class items that add nothing. The checks use
+// the engine's own parser, so they see patterns exactly as matching does.
+
+use core::ops::Range;
+
+use crate::error::RegexError;
+use crate::oniguruma::*;
+use crate::regcomp::onig_parse_lint;
+use crate::regenc::OnigEncoding;
+use crate::regint::*;
+use crate::regparse_types::*;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+
+/// Parse `pattern` and report constructs that are valid but most likely
+/// mistakes.
+///
+/// A name counts as used only if a backreference, subexpression call or
+/// conditional in the pattern refers to it; code reading the group by name
+/// after a match is not visible here, so [`LintKind::UnusedName`] is a hint
+/// rather than an error. Redundant class items are found before case
+/// folding, so `(?i)[a-zA-Z]` is not reported.
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::encodings::utf8::ONIG_ENCODING_UTF8;
+/// use ferroni::lint::LintKind;
+/// use ferroni::regsyntax::OnigSyntaxOniguruma;
+///
+/// let lint = |p: &str| {
+///     ferroni::lint::lint(p.as_bytes(), &OnigSyntaxOniguruma, &ONIG_ENCODING_UTF8)
+///         .unwrap()
+///         .into_iter()
+///         .map(|l| (l.kind, l.span))
+///         .collect::<Vec<_>>()
+/// };
+///
+/// assert_eq!(lint(r"(?<q>['\x22])\w+\k<q>"), []);
+/// assert_eq!(lint(r"(?<key>\w+)=(?<key>\d+)"), [
+///     (LintKind::UnusedName, 0..11),
+///     (LintKind::DuplicateName, 12..23),
+/// ]);
+/// assert_eq!(lint(r"[a-z_\w](?:)*"), [
+///     (LintKind::RedundantClassItem, 1..4),
+///     (LintKind::RedundantClassItem, 4..5),
+///     (LintKind::EmptyRepeat, 8..13),
+/// ]);
+/// ```
+pub fn lint(
+    pattern: &[u8],
+    syntax: &OnigSyntaxType,
+    enc: OnigEncoding,
+) -> Result<Vec<Lint>, RegexError> {
+    let (root, reg, redundant) = onig_parse_lint(pattern, ONIG_OPTION_NONE, enc, syntax)?;
+    Ok(lint_tree(&root, &reg, redundant))
+}
+
+/// Re-parse the pattern `reg` was compiled from and lint it. A literal
+/// pattern has nothing to report.
+pub(crate) fn lint_regex(reg: &RegexType, pattern: &[u8], literal: bool) -> Vec<Lint> {
+    if literal {
+        return Vec::new();
+    }
+    match onig_parse_lint(pattern, reg.options, reg.enc, &reg.syntax) {
+        Ok((root, parsed, redundant)) => lint_tree(&root, &parsed, redundant),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Lint the parse tree `root` of `reg`, given the class items the parser
+/// found redundant.
+fn lint_tree(root: &Node, reg: &RegexType, redundant: Vec<(usize, usize)>) -> Vec<Lint> {
+    let mut cx = Linter {
+        groups: Vec::new(),
+        referenced: Vec::new(),
+        called_names: Vec::new(),
+        lints: Vec::new(),
+    };
+    cx.visit(root);
+
+    if let Some(ref nt) = reg.name_table {
+        let span_of = |num: i32| {
+            cx.groups
+                .iter()
+                .find(|&&(n, _)| n == num)
+                .map(|(_, span)| span.clone())
+        };
+        let mut lints = Vec::new();
+        for entry in nt.entries.values() {
+            let used = cx.called_names.contains(&entry.name)
+                || entry.back_refs.iter().any(|n| cx.referenced.contains(n));
+            if !used {
+                if let Some(span) = entry.back_refs.first().and_then(|&n| span_of(n)) {
+                    lints.push(Lint {
+                        kind: LintKind::UnusedName,
+                        span,
+                    });
+                }
+            }
+            for &n in entry.back_refs.iter().skip(1) {
+                if let Some(span) = span_of(n) {
+                    lints.push(Lint {
+                        kind: LintKind::DuplicateName,
+                        span,
+                    });
+                }
+            }
+        }
+        cx.lints.extend(lints);
+    }
+
+    cx.lints.extend(redundant.into_iter().map(|(s, e)| Lint {
+        kind: LintKind::RedundantClassItem,
+        span: s..e,
+    }));
+    cx.lints.sort_by_key(|l| (l.span.start, l.span.end, l.kind));
+    cx.lints
+}
+
+/// One suspicious construct found by [`lint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Lint {
+    /// What was found.
+    pub kind: LintKind,
+    /// Byte range of the construct in the pattern.
+    pub span: Range<usize>,
+}
+
+/// Kinds of [`Lint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum LintKind {
+    /// A named group no backreference, call or conditional refers to. The
+    /// span is the first group with the name.
+    UnusedName,
+    /// A group reusing the name of an earlier group. Syntaxes without
+    /// `ONIG_SYN_ALLOW_MULTIPLEX_DEFINITION_NAME` reject these outright.
+    DuplicateName,
+    /// A repeat of something that can only match the empty string, as in
+    /// `(?:)*` or `(|)+`.
+    EmptyRepeat,
+    /// An alternation whose alternatives all match only the empty string,
+    /// as in `(?:|)`.
+    EmptyAlternation,
+    /// A character class item the other items already cover, as in the
+    /// `_` of `[_\w]` or the second `a` of `[aa]`.
+    RedundantClassItem,
+}
+
+struct Linter {
+    /// Group numbers of the capture groups and their spans.
+    groups: Vec<(i32, Range<usize>)>,
+    /// Groups referred to by number (or by a name resolved to numbers).
+    referenced: Vec<i32>,
+    /// Names called with `\g<name>`.
+    called_names: Vec<Vec<u8>>,
+    lints: Vec<Lint>,
+}
+
+impl Linter {
+    /// Collect groups and references below `node`, which was reported as a
+    /// whole, without reporting the empty parts inside it again.
+    fn visit_quietly(&mut self, node: &Node) {
+        let reported = self.lints.len();
+        for item in list_items(node) {
+            self.visit(item);
+        }
+        if node.as_cons().is_none() {
+            self.visit(node);
+        }
+        self.lints.truncate(reported);
+    }
+
+    fn visit(&mut self, node: &Node) {
+        match &node.inner {
+            NodeInner::List(_) => {
+                for item in list_items(node) {
+                    self.visit(item);
+                }
+            }
+            NodeInner::Alt(_) => {
+                if let Some((s, e)) = node.span {
+                    if only_empty(node) {
+                        self.lints.push(Lint {
+                            kind: LintKind::EmptyAlternation,
+                            span: s..e,
+                        });
+                        self.visit_quietly(node);
+                        return;
+                    }
+                }
+                for item in list_items(node) {
+                    self.visit(item);
+                }
+            }
+            NodeInner::Quant(qn) => {
+                let Some(ref body) = qn.body else {
+                    return;
+                };
+                if let Some((s, e)) = node.span {
+                    if qn.upper != 0 && only_empty(body) {
+                        self.lints.push(Lint {
+                            kind: LintKind::EmptyRepeat,
+                            span: s..e,
+                        });
+                        self.visit_quietly(body);
+                        return;
+                    }
+                }
+                self.visit(body);
+            }
+            NodeInner::Bag(bn) => {
+                if let (BagType::Memory, Some((s, e))) = (&bn.bag_type, node.span) {
+                    self.groups.push((bn.regnum(), s..e));
+                }
+                if let Some(ref body) = bn.body {
+                    self.visit(body);
+                }
+                if let BagData::IfElse {
+                    ref then_node,
+                    ref else_node,
+                } = bn.bag_data
+                {
+                    for branch in [then_node, else_node].into_iter().flatten() {
+                        self.visit(branch);
+                    }
+                }
+            }
+            NodeInner::Anchor(an) => {
+                if let Some(ref body) = an.body {
+                    self.visit(body);
+                }
+            }
+            NodeInner::BackRef(br) => self.referenced.extend_from_slice(br.back_refs()),
+            NodeInner::Call(cn) => {
+                if cn.by_number {
+                    self.referenced.push(cn.called_gnum);
+                } else {
+                    self.called_names.push(cn.name.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether `node` can match only the empty string.
+fn only_empty(node: &Node) -> bool {
+    match &node.inner {
+        NodeInner::String(sn) => sn.s.is_empty(),
+        NodeInner::List(_) | NodeInner::Alt(_) => list_items(node).into_iter().all(only_empty),
+        NodeInner::Quant(qn) => qn.upper == 0 || qn.body.as_deref().map_or(true, only_empty),
+        NodeInner::Bag(bn) => match bn.bag_type {
+            BagType::Memory | BagType::Option | BagType::StopBacktrack => {
+                bn.body.as_deref().map_or(true, only_empty)
+            }
+            _ => false,
+        },
+        NodeInner::Anchor(_) => true,
+        NodeInner::Gimmick(gn) => gn.gimmick_type != GimmickType::Extension,
+        _ => false,
+    }
+}
+
+/// The elements of a list or alternation node.
+fn list_items(node: &Node) -> Vec<&Node> {
+    let mut items = Vec::new();
+    let mut cur = Some(node);
+    while let Some(cons) = cur.and_then(|n| n.as_cons()) {
+        items.push(cons.car.as_ref());
+        cur = cons.cdr.as_deref();
+    }
+    items
+}
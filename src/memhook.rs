@@ -0,0 +1,122 @@
+// memhook.rs - Size callbacks for Ferroni's large internal buffers.
+//
+// Stable Rust cannot hand an allocator to `Vec`, so instead of an
+// allocator handle embedders get a global hook that is asked before the
+// backtrack stack, code range buffers and case fold scratch grow, and told
+// when they are freed. Refusing a request fails the running compile or
+// search with ONIGERR_MEMORY; the host can use this to account regex memory
+// against its own arena and cap it.
+
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+use crate::oniguruma::ONIGERR_MEMORY;
+
+/// Internal buffer a [`OnigMemoryHook`] call is about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OnigBufferKind {
+    /// The backtracking stack of one search (reused by `Regex`/`Scanner`
+    /// iterators for the whole iteration).
+    BacktrackStack,
+    /// A multi-byte code range list built while parsing a character class.
+    CodeRangeBuffer,
+    /// Case fold candidates used to expand an ignore-case string.
+    CaseFoldScratch,
+}
+
+/// Called with `(kind, old_size, new_size)` in bytes.
+///
+/// When `new_size > old_size` the buffer is about to grow: return `false` to
+/// refuse, and the operation fails with `ONIGERR_MEMORY`. When `new_size` is
+/// 0 the buffer has been freed and the return value is ignored. Sizes are
+/// what Ferroni asked for; the allocator may round them up.
+pub type OnigMemoryHook = fn(kind: OnigBufferKind, old_size: usize, new_size: usize) -> bool;
+
+static MEMORY_HOOK: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Install (or with `None`, remove) the global memory hook.
+///
+/// Buffers that grew before the hook was installed are reported as growing
+/// from 0 the next time they grow, and are only reported freed if the hook
+/// saw them grow.
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::memhook::{onig_set_memory_hook, OnigBufferKind};
+/// use ferroni::prelude::*;
+///
+/// fn cap(kind: OnigBufferKind, _old: usize, new: usize) -> bool {
+///     kind != OnigBufferKind::BacktrackStack || new <= 64 * 1024
+/// }
+///
+/// onig_set_memory_hook(Some(cap));
+/// let re = Regex::new(r"(?:a|b)*c").unwrap();
+/// assert!(re.find("abc").is_some());
+/// // Backtracking over this needs more than 64 KiB of stack.
+/// assert!(re.find(&("ab".repeat(100_000) + "c")).is_none());
+/// onig_set_memory_hook(None);
+/// ```
+pub fn onig_set_memory_hook(hook: Option<OnigMemoryHook>) {
+    let p = hook.map_or(core::ptr::null_mut(), |f| f as *mut ());
+    MEMORY_HOOK.store(p, Ordering::Relaxed);
+}
+
+/// Get the global memory hook.
+pub fn onig_get_memory_hook() -> Option<OnigMemoryHook> {
+    let p = MEMORY_HOOK.load(Ordering::Relaxed);
+    if p.is_null() {
+        None
+    } else {
+        Some(unsafe { core::mem::transmute(p) })
+    }
+}
+
+/// Bytes of one buffer the hook has granted; reports them freed on drop.
+/// A clone starts at 0, since its allocation has not been granted.
+#[derive(Debug)]
+pub(crate) struct Tracked {
+    kind: OnigBufferKind,
+    bytes: usize,
+}
+
+impl Tracked {
+    pub(crate) const fn new(kind: OnigBufferKind) -> Self {
+        Tracked { kind, bytes: 0 }
+    }
+
+    #[inline]
+    pub(crate) fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Ask the hook to let the buffer grow to `new_size` bytes. Always
+    /// succeeds when no hook is installed or the size does not increase.
+    pub(crate) fn grow(&mut self, new_size: usize) -> Result<(), i32> {
+        if new_size <= self.bytes {
+            return Ok(());
+        }
+        if let Some(hook) = onig_get_memory_hook() {
+            if !hook(self.kind, self.bytes, new_size) {
+                return Err(ONIGERR_MEMORY);
+            }
+            self.bytes = new_size;
+        }
+        Ok(())
+    }
+}
+
+impl Clone for Tracked {
+    fn clone(&self) -> Self {
+        Tracked::new(self.kind)
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        if self.bytes > 0 {
+            if let Some(hook) = onig_get_memory_hook() {
+                hook(self.kind, self.bytes, 0);
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
 // oniguruma.rs - Port of oniguruma.h
 // Public types, option flags, error codes, syntax flags, structs.
 
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+
 // === Version ===
 pub const ONIGURUMA_VERSION_MAJOR: i32 = 6;
 pub const ONIGURUMA_VERSION_MINOR: i32 = 9;
@@ -18,6 +21,7 @@ pub type OnigCaseFoldType = u32;
 // All bitwise operations (|, &, !, |=, &=) work as before.
 bitflags::bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[repr(transparent)]
     pub struct OnigOptionType: u32 {
         const NONE              = 0;
         const IGNORECASE        = 1;
@@ -27,6 +31,7 @@ bitflags::bitflags! {
         const FIND_LONGEST      = 1 << 4;
         const FIND_NOT_EMPTY    = 1 << 5;
         const NEGATE_SINGLELINE = 1 << 6;
+        // Also a search option, recording only group 0 in the region.
         const DONT_CAPTURE_GROUP = 1 << 7;
         const CAPTURE_GROUP     = 1 << 8;
         // search time
@@ -48,6 +53,13 @@ bitflags::bitflags! {
         const NOT_BEGIN_POSITION = 1 << 24;
         const CALLBACK_EACH_MATCH = 1 << 25;
         const MATCH_WHOLE_STRING = 1 << 26;
+        // With FIND_LONGEST, the longest match at the leftmost
+        // start that matches (POSIX) instead of the longest in the range.
+        const LEFTMOST_LONGEST  = 1 << 27;
+        // Oniguruma 5's CRLF option, dropped in 6.9: CRLF line terminators, as
+        // OnigNewline::Crlf, for callers of onig_new(). A leading newline
+        // verb in the pattern takes precedence.
+        const NEWLINE_CRLF      = 1 << 28;
     }
 }
 
@@ -60,6 +72,16 @@ pub const ONIGENC_CASE_FOLD_TURKISH_AZERI: OnigCaseFoldType = 1 << 20;
 pub const INTERNAL_ONIGENC_CASE_FOLD_MULTI_CHAR: OnigCaseFoldType = 1 << 30;
 pub const ONIGENC_CASE_FOLD_MIN: OnigCaseFoldType = INTERNAL_ONIGENC_CASE_FOLD_MULTI_CHAR;
 
+// === Case Map Flags ===
+// Onigmo's case map flags (regenc::onig_case_map). They share the
+// flag word with the fold flags; ONIGENC_CASE_FOLD_TURKISH_AZERI applies too.
+pub const ONIGENC_CASE_UPCASE: OnigCaseFoldType = 1 << 13;
+pub const ONIGENC_CASE_DOWNCASE: OnigCaseFoldType = 1 << 14;
+pub const ONIGENC_CASE_TITLECASE: OnigCaseFoldType = 1 << 15;
+pub const ONIGENC_CASE_MODIFIED: OnigCaseFoldType = 1 << 18;
+pub const ONIGENC_CASE_FOLD: OnigCaseFoldType = 1 << 19;
+pub const ONIGENC_CASE_ASCII_ONLY: OnigCaseFoldType = 1 << 22;
+
 // === Work Size ===
 pub const ONIGENC_MAX_COMP_CASE_FOLD_CODE_LEN: usize = 3;
 pub const ONIGENC_GET_CASE_FOLD_CODES_MAX_NUM: usize = 13;
@@ -94,6 +116,7 @@ pub struct OnigCaseFoldCodeItem {
 
 // === Meta Char Table ===
 #[derive(Clone, Debug)]
+#[repr(C)]
 pub struct OnigMetaCharTableType {
     pub esc: OnigCodePoint,
     pub anychar: OnigCodePoint,
@@ -110,6 +133,9 @@ pub const ONIG_MAX_BACKREF_NUM: i32 = 1000;
 pub const ONIG_MAX_REPEAT_NUM: i32 = 100000;
 pub const ONIG_MAX_MULTI_BYTE_RANGES_NUM: i32 = 10000;
 pub const ONIG_MAX_ERROR_MESSAGE_LEN: usize = 90;
+// Longest `end` accepted by search/match, since region offsets
+// and return values are i32. Longer inputs give ONIGERR_TOO_LONG_INPUT.
+pub const ONIG_MAX_INPUT_LEN: usize = i32::MAX as usize;
 
 // === Option Flag Aliases ===
 // These provide the original C constant names for backward compatibility.
@@ -142,6 +168,8 @@ pub const ONIG_OPTION_NOT_END_STRING: OnigOptionType = OnigOptionType::NOT_END_S
 pub const ONIG_OPTION_NOT_BEGIN_POSITION: OnigOptionType = OnigOptionType::NOT_BEGIN_POSITION;
 pub const ONIG_OPTION_CALLBACK_EACH_MATCH: OnigOptionType = OnigOptionType::CALLBACK_EACH_MATCH;
 pub const ONIG_OPTION_MATCH_WHOLE_STRING: OnigOptionType = OnigOptionType::MATCH_WHOLE_STRING;
+pub const ONIG_OPTION_LEFTMOST_LONGEST: OnigOptionType = OnigOptionType::LEFTMOST_LONGEST;
+pub const ONIG_OPTION_NEWLINE_CRLF: OnigOptionType = OnigOptionType::NEWLINE_CRLF;
 
 pub const ONIG_OPTION_MAXBIT: OnigOptionType = OnigOptionType::MATCH_WHOLE_STRING;
 
@@ -162,6 +190,7 @@ pub fn onig_is_option_on(options: OnigOptionType, option: OnigOptionType) -> boo
 
 // === Syntax Type ===
 #[derive(Clone, Debug)]
+#[repr(C)]
 pub struct OnigSyntaxType {
     pub op: u32,
     pub op2: u32,
@@ -298,6 +327,12 @@ pub const ONIGERR_TIME_LIMIT_OVER: i32 = -20;
 pub const ONIGERR_DEFAULT_ENCODING_IS_NOT_SET: i32 = -21;
 pub const ONIGERR_SPECIFIED_ENCODING_CANT_CONVERT_TO_WIDE_CHAR: i32 = -22;
 pub const ONIGERR_FAIL_TO_INITIALIZE: i32 = -23;
+// `end` is beyond ONIG_MAX_INPUT_LEN.
+pub const ONIGERR_TOO_LONG_INPUT: i32 = -24;
+// A limit set with RegexBuilder::max_compiled_bytes or
+// RegexBuilder::compile_timeout was exceeded while compiling.
+pub const ONIGERR_COMPILED_SIZE_LIMIT_OVER: i32 = -25;
+pub const ONIGERR_COMPILE_TIME_LIMIT_OVER: i32 = -26;
 
 // general error
 pub const ONIGERR_INVALID_ARGUMENT: i32 = -30;
@@ -457,6 +492,75 @@ impl OnigRegion {
         self.num_regs = n as i32;
     }
 
+    /// Start offset of group `i`, `ONIG_REGION_NOTPOS` if it is not set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not below `num_regs`.
+    #[inline]
+    pub fn beg(&self, i: usize) -> i32 {
+        self.beg[i]
+    }
+
+    /// End offset of group `i`, `ONIG_REGION_NOTPOS` if it is not set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not below `num_regs`.
+    #[inline]
+    pub fn end(&self, i: usize) -> i32 {
+        self.end[i]
+    }
+
+    /// Byte range of group `i`, `None` if the group did not
+    /// participate or `i` is not below `num_regs`.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<core::ops::Range<usize>> {
+        if i >= self.num_regs as usize {
+            return None;
+        }
+        let beg = usize::try_from(self.beg(i)).ok()?;
+        let end = usize::try_from(self.end(i)).ok()?;
+        Some(beg..end)
+    }
+
+    /// Byte ranges of all groups in order, as returned by
+    /// [`get`](Self::get).
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Option<core::ops::Range<usize>>> + '_ {
+        (0..self.num_regs as usize).map(move |i| self.get(i))
+    }
+
+    /// The bytes of `haystack` group `i` matched, borrowed from
+    /// the haystack the region was filled from.
+    ///
+    /// Returns `None` if the group did not participate or its range lies
+    /// outside `haystack`.
+    pub fn slice<'h>(&self, i: usize, haystack: &'h [u8]) -> Option<&'h [u8]> {
+        haystack.get(self.get(i)?)
+    }
+
+    /// Ranges of all groups in order, like [`iter`](Self::iter),
+    /// in the units `index` counts (characters or UTF-16 code units) of the
+    /// haystack the region was filled from.
+    pub fn char_spans<'a>(
+        &'a self,
+        index: &'a crate::charindex::CharIndex<'_>,
+    ) -> impl ExactSizeIterator<Item = Option<core::ops::Range<usize>>> + 'a {
+        self.iter().map(|r| r.map(|r| index.to_char_range(r)))
+    }
+
+    /// Set group `i` without growing the region.
+    #[inline]
+    pub(crate) fn put(&mut self, i: usize, beg: i32, end: i32) {
+        self.beg[i] = beg;
+        self.end[i] = end;
+    }
+
+    /// Bytes the group offsets take on the heap.
+    pub(crate) fn spans_size(&self) -> usize {
+        (self.beg.len() + self.end.len()) * core::mem::size_of::<i32>()
+    }
+
     #[cfg_attr(coverage_nightly, coverage(off))]
     pub fn set(&mut self, at: i32, beg: i32, end: i32) -> i32 {
         if at < 0 {
@@ -465,8 +569,7 @@ impl OnigRegion {
         if at >= self.allocated {
             self.resize(at + 1);
         }
-        self.beg[at as usize] = beg;
-        self.end[at as usize] = end;
+        self.put(at as usize, beg, end);
         ONIG_NORMAL
     }
 
@@ -474,12 +577,61 @@ impl OnigRegion {
     pub fn copy_from(&mut self, from: &OnigRegion) {
         self.resize(from.num_regs);
         for i in 0..from.num_regs as usize {
-            self.beg[i] = from.beg[i];
-            self.end[i] = from.end[i];
+            self.put(i, from.beg(i), from.end(i));
         }
         self.num_regs = from.num_regs;
         self.history_root = from.history_root.clone();
     }
+
+    /// Group spans as `(beg, end)` byte offsets, `None` for groups that did
+    /// not participate. The capture history tree is not included.
+    pub fn to_spans(&self) -> Vec<Option<(u32, u32)>> {
+        self.iter()
+            .map(|r| r.map(|r| (r.start as u32, r.end as u32)))
+            .collect()
+    }
+
+    /// The span of each group name of `reg`, from its first group
+    /// that participated, `None` if none did. `reg` must be the regex that
+    /// filled the region.
+    ///
+    /// Names that are not UTF-8 are converted lossily.
+    pub fn named_spans(
+        &self,
+        reg: &crate::regint::RegexType,
+    ) -> alloc::collections::BTreeMap<String, Option<core::ops::Range<usize>>> {
+        self.named_span_entries(reg).collect()
+    }
+
+    /// The entries of [`named_spans`](Self::named_spans), for collecting
+    /// into another map type.
+    pub(crate) fn named_span_entries<'a>(
+        &'a self,
+        reg: &'a crate::regint::RegexType,
+    ) -> impl Iterator<Item = (String, Option<core::ops::Range<usize>>)> + 'a {
+        reg.name_table.iter().flat_map(move |nt| {
+            nt.entries.values().map(move |entry| {
+                let span = entry.back_refs.iter().find_map(|&g| self.get(g as usize));
+                (String::from_utf8_lossy(&entry.name).into_owned(), span)
+            })
+        })
+    }
+
+    /// Build a region from spans as returned by [`OnigRegion::to_spans`].
+    /// Fails with `ONIGERR_INVALID_ARGUMENT` if an offset exceeds `i32::MAX`.
+    pub fn from_spans(spans: &[Option<(u32, u32)>]) -> Result<Self, i32> {
+        let mut region = OnigRegion::new();
+        region.resize(spans.len() as i32);
+        for (i, span) in spans.iter().enumerate() {
+            if let Some((beg, end)) = *span {
+                let (Ok(beg), Ok(end)) = (i32::try_from(beg), i32::try_from(end)) else {
+                    return Err(ONIGERR_INVALID_ARGUMENT);
+                };
+                region.put(i, beg, end);
+            }
+        }
+        Ok(region)
+    }
 }
 
 impl Default for OnigRegion {
@@ -489,6 +641,119 @@ impl Default for OnigRegion {
     }
 }
 
+impl From<&OnigRegion> for Vec<Option<(u32, u32)>> {
+    fn from(region: &OnigRegion) -> Self {
+        region.to_spans()
+    }
+}
+
+impl TryFrom<&[Option<(u32, u32)>]> for OnigRegion {
+    type Error = i32;
+
+    fn try_from(spans: &[Option<(u32, u32)>]) -> Result<Self, i32> {
+        OnigRegion::from_spans(spans)
+    }
+}
+
+// Serialized as its span list (see `to_spans`).
+#[cfg(feature = "serde")]
+impl serde::Serialize for OnigRegion {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&self.to_spans(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for OnigRegion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let spans: Vec<Option<(u32, u32)>> = serde::Deserialize::deserialize(deserializer)?;
+        OnigRegion::from_spans(&spans)
+            .map_err(|_| serde::de::Error::custom("region offset exceeds i32::MAX"))
+    }
+}
+
+// === OnigRegionFixed (match result without heap) ===
+// Group offsets for patterns with at most N groups (group 0
+// included), stored inline. Filled by onig_search_fixed/onig_match_fixed;
+// there is no capture history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OnigRegionFixed<const N: usize> {
+    pub num_regs: i32,
+    beg: [i32; N],
+    end: [i32; N],
+}
+
+impl<const N: usize> OnigRegionFixed<N> {
+    pub const fn new() -> Self {
+        OnigRegionFixed {
+            num_regs: 0,
+            beg: [ONIG_REGION_NOTPOS; N],
+            end: [ONIG_REGION_NOTPOS; N],
+        }
+    }
+
+    /// Start offset of group `i`, `ONIG_REGION_NOTPOS` if it is not set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not below `N`.
+    #[inline]
+    pub fn beg(&self, i: usize) -> i32 {
+        self.beg[i]
+    }
+
+    /// End offset of group `i`, `ONIG_REGION_NOTPOS` if it is not set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not below `N`.
+    #[inline]
+    pub fn end(&self, i: usize) -> i32 {
+        self.end[i]
+    }
+
+    /// Byte range of group `i`, `None` if the group did not participate or
+    /// `i` is not below `num_regs`.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<core::ops::Range<usize>> {
+        if i >= self.num_regs as usize {
+            return None;
+        }
+        let beg = usize::try_from(self.beg[i]).ok()?;
+        let end = usize::try_from(self.end[i]).ok()?;
+        Some(beg..end)
+    }
+
+    /// Byte ranges of all groups in order, as returned by [`get`](Self::get).
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Option<core::ops::Range<usize>>> + '_ {
+        (0..self.num_regs as usize).map(move |i| self.get(i))
+    }
+
+    /// Copy the groups of `from`. Fails with `ONIGERR_INVALID_ARGUMENT`,
+    /// leaving `self` unchanged, if `from` has more than `N` groups.
+    pub fn copy_from(&mut self, from: &OnigRegion) -> i32 {
+        let n = from.num_regs.max(0) as usize;
+        if n > N {
+            return ONIGERR_INVALID_ARGUMENT;
+        }
+        for i in 0..N {
+            (self.beg[i], self.end[i]) = if i < n {
+                (from.beg(i), from.end(i))
+            } else {
+                (ONIG_REGION_NOTPOS, ONIG_REGION_NOTPOS)
+            };
+        }
+        self.num_regs = n as i32;
+        ONIG_NORMAL
+    }
+}
+
+impl<const N: usize> Default for OnigRegionFixed<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // === Capture Traverse Constants ===
 pub const ONIG_TRAVERSE_CALLBACK_AT_FIRST: i32 = 1;
 pub const ONIG_TRAVERSE_CALLBACK_AT_LAST: i32 = 2;
@@ -521,10 +786,57 @@ pub enum OnigRegSetLead {
     PriorityToRegexOrder = 2,
 }
 
+// === Newline Convention ===
+// PCRE's newline setting: which line terminators `.`, `\N`, `^`, `$` and
+// `\Z` recognize. `\R` keeps C's set, CRLF as one unit, under every
+// convention. Selected by a leading (*LF), (*CR), (*CRLF), (*ANYCRLF) or
+// (*ANY) verb, RegexBuilder::newline() or, for Crlf only,
+// ONIG_OPTION_NEWLINE_CRLF; `Lf` defers to the encoding's newline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum OnigNewline {
+    /// LF only.
+    #[default]
+    Lf,
+    /// CR only.
+    Cr,
+    /// The pair CRLF; `$` matches before it, never between CR and LF. A
+    /// lone CR or LF is an ordinary character.
+    Crlf,
+    /// CR, LF or CRLF.
+    AnyCrlf,
+    /// CR, LF, CRLF, VT, FF, NEL, LS and PS.
+    Any,
+}
+
+// === Text Segment ===
+// Which text segments `\X` matches and `\y`/`\Y` test the
+// boundaries of, as the ONIG_OPTION_TEXT_SEGMENT_* options select them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum OnigTextSegment {
+    #[default]
+    ExtendedGraphemeCluster,
+    Word,
+}
+
+impl OnigTextSegment {
+    /// The option that selects this segment type.
+    pub fn option(self) -> OnigOptionType {
+        match self {
+            OnigTextSegment::ExtendedGraphemeCluster => {
+                ONIG_OPTION_TEXT_SEGMENT_EXTENDED_GRAPHEME_CLUSTER
+            }
+            OnigTextSegment::Word => ONIG_OPTION_TEXT_SEGMENT_WORD,
+        }
+    }
+}
+
 // === Compile Info ===
-pub struct OnigCompileInfo {
+/// Arguments of `onig_new_deluxe()`.
+pub struct OnigCompileInfo<'a> {
     pub num_of_elements: i32,
-    pub syntax: *const OnigSyntaxType,
+    pub pattern_enc: crate::regenc::OnigEncoding,
+    pub target_enc: crate::regenc::OnigEncoding,
+    pub syntax: &'a OnigSyntaxType,
     pub option: OnigOptionType,
     pub case_fold_flag: OnigCaseFoldType,
 }
@@ -0,0 +1,423 @@
+// parity.rs - Speed and result parity against C Oniguruma (dev only).
+//
+// Behind the `c-oniguruma` feature: runs one pattern/input corpus through
+// Ferroni and through libonig (built from the submodule or, without it,
+// the system library; see build.rs), checks that both engines report the
+// same match regions and times a find-all scan with each. The criterion
+// suite in benches/onig_bench.rs measures single searches in detail; this
+// answers "did anything change against C" in one table.
+//
+// Run: cargo bench --features c-oniguruma --bench parity
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::c_oniguruma::{CRegex, CRegion};
+use crate::encodings::utf8::ONIG_ENCODING_UTF8;
+use crate::oniguruma::*;
+use crate::regcomp::onig_new;
+use crate::regexec::onig_search;
+use crate::regint::RegexType;
+use crate::regsyntax::OnigSyntaxOniguruma;
+
+/// Timing batches per engine; the fastest one is reported.
+const BATCHES: u32 = 5;
+
+/// A pattern and the text to scan with it.
+#[derive(Debug, Clone)]
+pub struct Case {
+    pub name: String,
+    pub pattern: Vec<u8>,
+    pub options: OnigOptionType,
+    pub text: Vec<u8>,
+}
+
+impl Case {
+    pub fn new(name: &str, pattern: &[u8], text: &[u8]) -> Self {
+        Case {
+            name: name.to_string(),
+            pattern: pattern.to_vec(),
+            options: ONIG_OPTION_NONE,
+            text: text.to_vec(),
+        }
+    }
+
+    /// Compile with `options` (the ONIG_OPTION_* values are the same in
+    /// both engines).
+    pub fn options(mut self, options: OnigOptionType) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+/// `(beg, end)` of every group of one match, -1 for unset groups.
+pub type Groups = Vec<(i32, i32)>;
+
+/// How the two engines' results compare for one case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Both found the same matches with the same groups.
+    Same { matches: usize },
+    /// Match `index` of the scan differs; `None` means that engine found
+    /// no further match.
+    Differs {
+        index: usize,
+        rust: Option<Groups>,
+        c: Option<Groups>,
+    },
+    /// At least one engine rejected the pattern; `None` means it compiled.
+    CompileError { rust: Option<i32>, c: Option<i32> },
+}
+
+impl Outcome {
+    /// Whether both engines behaved the same, including failing to compile
+    /// with the same error code.
+    pub fn is_parity(&self) -> bool {
+        match self {
+            Outcome::Same { .. } => true,
+            Outcome::Differs { .. } => false,
+            Outcome::CompileError { rust, c } => rust == c,
+        }
+    }
+}
+
+/// Result of one [`Case`].
+#[derive(Debug, Clone)]
+pub struct CaseReport {
+    pub name: String,
+    pub outcome: Outcome,
+    /// Time of one find-all scan, if the pattern compiled.
+    pub rust: Option<Duration>,
+    pub c: Option<Duration>,
+}
+
+impl CaseReport {
+    /// Rust time over C time: below 1.0 Ferroni is faster.
+    pub fn ratio(&self) -> Option<f64> {
+        match (self.rust, self.c) {
+            (Some(r), Some(c)) if !c.is_zero() => Some(r.as_secs_f64() / c.as_secs_f64()),
+            _ => None,
+        }
+    }
+}
+
+/// Results of [`run`]. `Display` renders the speed and parity tables as
+/// Markdown, in the format of the README benchmark tables.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub cases: Vec<CaseReport>,
+}
+
+impl Report {
+    /// Cases where the engines disagree.
+    pub fn mismatches(&self) -> impl Iterator<Item = &CaseReport> {
+        self.cases.iter().filter(|c| !c.outcome.is_parity())
+    }
+
+    /// Cases where Ferroni is more than `max_ratio` times slower than C.
+    pub fn regressions(&self, max_ratio: f64) -> impl Iterator<Item = &CaseReport> {
+        self.cases
+            .iter()
+            .filter(move |c| c.ratio().is_some_and(|r| r > max_ratio))
+    }
+}
+
+/// Check and time every case, spending roughly `budget` per engine and case.
+pub fn run(cases: &[Case], budget: Duration) -> Report {
+    Report {
+        cases: cases.iter().map(|case| run_case(case, budget)).collect(),
+    }
+}
+
+/// Compare the two engines' results for `case` without timing them.
+///
+/// Deterministic and quick for short texts, which makes it the oracle for
+/// differential fuzzing (fuzz/fuzz_targets/differential.rs).
+pub fn check(case: &Case) -> Outcome {
+    match compile(case) {
+        Ok((rust, c)) => compare(&rust, &c, &case.text),
+        Err(outcome) => outcome,
+    }
+}
+
+/// Compile `case` with both engines, or the `CompileError` outcome.
+fn compile(case: &Case) -> Result<(RegexType, CRegex), Outcome> {
+    let rust = onig_new(
+        &case.pattern,
+        case.options,
+        &ONIG_ENCODING_UTF8,
+        &OnigSyntaxOniguruma,
+    );
+    let c = CRegex::new(&case.pattern, case.options.bits());
+    match (rust, c) {
+        (Ok(rust), Ok(c)) => Ok((rust, c)),
+        (rust, c) => Err(Outcome::CompileError {
+            rust: rust.err().map(|e| e.code()),
+            c: c.err(),
+        }),
+    }
+}
+
+/// Find all matches with both engines and compare them.
+fn compare(rust: &RegexType, c: &CRegex, text: &[u8]) -> Outcome {
+    let mut rust_matches = Vec::new();
+    find_all(text, |start| {
+        let (beg, end, groups) = rust_search(rust, text, start)?;
+        rust_matches.push(groups);
+        Some((beg, end))
+    });
+    let mut c_matches = Vec::new();
+    find_all(text, |start| {
+        let (beg, end, groups) = c_search(c, text, start)?;
+        c_matches.push(groups);
+        Some((beg, end))
+    });
+    match (0..rust_matches.len().max(c_matches.len()))
+        .find(|&i| rust_matches.get(i) != c_matches.get(i))
+    {
+        Some(index) => Outcome::Differs {
+            index,
+            rust: rust_matches.get(index).cloned(),
+            c: c_matches.get(index).cloned(),
+        },
+        None => Outcome::Same {
+            matches: rust_matches.len(),
+        },
+    }
+}
+
+fn run_case(case: &Case, budget: Duration) -> CaseReport {
+    let (rust, c) = match compile(case) {
+        Ok(regs) => regs,
+        Err(outcome) => {
+            return CaseReport {
+                name: case.name.clone(),
+                outcome,
+                rust: None,
+                c: None,
+            }
+        }
+    };
+    let text = &case.text[..];
+    let outcome = compare(&rust, &c, text);
+
+    let rust_time = time(budget, || {
+        find_all(text, |start| {
+            rust_search(&rust, text, start).map(|(b, e, _)| (b, e))
+        })
+    });
+    let c_time = time(budget, || {
+        find_all(text, |start| {
+            c_search(&c, text, start).map(|(b, e, _)| (b, e))
+        })
+    });
+    CaseReport {
+        name: case.name.clone(),
+        outcome,
+        rust: Some(rust_time),
+        c: Some(c_time),
+    }
+}
+
+fn rust_search(reg: &RegexType, text: &[u8], start: usize) -> Option<(usize, usize, Groups)> {
+    let (r, region) = onig_search(
+        reg,
+        text,
+        text.len(),
+        start,
+        text.len(),
+        Some(OnigRegion::new()),
+        ONIG_OPTION_NONE,
+    );
+    if r < 0 {
+        return None;
+    }
+    let region = region?;
+    let n = region.num_regs as usize;
+    let groups: Groups = (0..n).map(|i| (region.beg(i), region.end(i))).collect();
+    Some((region.beg(0) as usize, region.end(0) as usize, groups))
+}
+
+fn c_search(reg: &CRegex, text: &[u8], start: usize) -> Option<(usize, usize, Groups)> {
+    let mut region = CRegion::new();
+    let r = reg.search(
+        text,
+        start,
+        text.len(),
+        Some(&mut region),
+        crate::c_oniguruma::ONIG_OPTION_NONE,
+    );
+    if r < 0 {
+        return None;
+    }
+    let groups = region.regs();
+    let (beg, end) = groups[0];
+    Some((beg as usize, end as usize, groups))
+}
+
+/// Search `text` from the start the way a find-all loop does, moving past
+/// empty matches by one character. Returns the number of matches.
+fn find_all(text: &[u8], mut search: impl FnMut(usize) -> Option<(usize, usize)>) -> usize {
+    let mut count = 0;
+    let mut start = 0;
+    while start <= text.len() {
+        let Some((beg, end)) = search(start) else {
+            break;
+        };
+        count += 1;
+        start = end + 1;
+        if end > beg {
+            start = end;
+        }
+        while start < text.len() && text[start] & 0xC0 == 0x80 {
+            start += 1;
+        }
+    }
+    count
+}
+
+/// Fastest mean time of `scan` over BATCHES batches filling `budget`.
+fn time(budget: Duration, mut scan: impl FnMut() -> usize) -> Duration {
+    let t = Instant::now();
+    black_box(scan());
+    let once = t.elapsed().max(Duration::from_nanos(1));
+    let per_batch = budget / BATCHES;
+    let iters = (per_batch.as_nanos() / once.as_nanos()).clamp(1, 1_000_000) as u32;
+
+    (0..BATCHES)
+        .map(|_| {
+            let t = Instant::now();
+            for _ in 0..iters {
+                black_box(scan());
+            }
+            t.elapsed() / iters
+        })
+        .min()
+        .unwrap()
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "### Speed (find all matches)")?;
+        writeln!(f)?;
+        writeln!(f, "| Case | Rust | C | Ratio |")?;
+        writeln!(f, "|------|-----:|--:|------:|")?;
+        for case in &self.cases {
+            let (Some(rust), Some(c), Some(ratio)) = (case.rust, case.c, case.ratio()) else {
+                writeln!(f, "| {} | - | - | - |", case.name)?;
+                continue;
+            };
+            let (rust, c) = (format_duration(rust), format_duration(c));
+            if ratio <= 1.0 {
+                writeln!(f, "| {} | **{rust}** | {c} | {ratio:.2} |", case.name)?;
+            } else {
+                writeln!(f, "| {} | {rust} | **{c}** | {ratio:.2} |", case.name)?;
+            }
+        }
+        writeln!(f)?;
+        writeln!(f, "### Result parity")?;
+        writeln!(f)?;
+        writeln!(f, "| Case | Result |")?;
+        writeln!(f, "|------|--------|")?;
+        for case in &self.cases {
+            let result = match &case.outcome {
+                Outcome::Same { matches } => format!("same ({matches} matches)"),
+                Outcome::Differs { index, rust, c } => {
+                    format!("**differs** at match {index}: rust {rust:?}, c {c:?}")
+                }
+                Outcome::CompileError { rust, c } if rust == c => {
+                    format!("same (compile error {})", rust.unwrap_or(0))
+                }
+                Outcome::CompileError { rust, c } => {
+                    format!("**differs**: compile error rust {rust:?}, c {c:?}")
+                }
+            };
+            writeln!(f, "| {} | {result} |", case.name)?;
+        }
+        Ok(())
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    let ns = d.as_nanos();
+    if ns < 1_000 {
+        format!("{ns} ns")
+    } else if ns < 1_000_000 {
+        format!("{:.1} us", ns as f64 / 1e3)
+    } else {
+        format!("{:.1} ms", ns as f64 / 1e6)
+    }
+}
+
+/// The built-in corpus: the constructs covered by benches/onig_bench.rs,
+/// each scanned over a generated log or JSON text.
+pub fn default_corpus() -> Vec<Case> {
+    let log = log_text(500);
+    let json = json_text(200);
+    let words = "Ελληνικά und Русский текст mit ÄÖÜ and plain ASCII words ".repeat(100);
+    let words = words.as_bytes();
+    vec![
+        Case::new("literal", b"/users/", &log),
+        Case::new("dot star", b"INFO.*users", &log),
+        Case::new("alternation", b"GET|POST|PUT|DELETE|status", &log),
+        Case::new("char class", b"[a-z]+=[0-9]+", &log),
+        Case::new("quantifier lazy", b"path=.+?/", &log),
+        Case::new("possessive", b"\\d++ms", &log),
+        Case::new("group", b"(?:\\d{2}:){2}\\d{2}", &log),
+        Case::new("backref", b"(\\d)\\1", &log),
+        Case::new("lookahead", b"\\w+(?==200)", &log),
+        Case::new("lookbehind", b"(?<=server\\[)\\d+", &log),
+        Case::new(
+            "named capture",
+            b"(?<year>\\d{4})-(?<month>\\d{2})-(?<day>\\d{2})",
+            &log,
+        ),
+        Case::new("case-insensitive", b"info|status", &log).options(ONIG_OPTION_IGNORECASE),
+        Case::new("anchored line", b"^2025-06-1\\d", &log),
+        Case::new("no match", b"ERROR\\s+\\w+", &log),
+        Case::new("json string", b"\"(?:[^\"\\\\]|\\\\.)*\"", &json),
+        Case::new(
+            "json number",
+            b"-?\\d+(?:\\.\\d+)?(?:[eE][-+]?\\d+)?",
+            &json,
+        ),
+        Case::new("shift-or escape", b"\\\\u[0-9a-fA-F]{4}", &json),
+        Case::new("\\p{Greek}+", b"\\p{Greek}+", words),
+        Case::new("\\p{Lu}\\w*", b"\\p{Lu}\\w*", words),
+        Case::new("empty matches", b"\\b", words),
+    ]
+}
+
+fn log_text(lines: usize) -> Vec<u8> {
+    let mut text = String::new();
+    for i in 0..lines {
+        text.push_str(&format!(
+            "2025-06-{:02} {:02}:{:02}:{:02} INFO server[{}] request path=/api/v1/users/{} status=200 duration={}ms\n",
+            (i % 28) + 1,
+            i % 24,
+            i % 60,
+            (i * 7) % 60,
+            1000 + (i % 50),
+            i * 3,
+            (i * 13) % 500,
+        ));
+    }
+    text.into_bytes()
+}
+
+fn json_text(items: usize) -> Vec<u8> {
+    let mut text = String::from("[\n");
+    for i in 0..items {
+        text.push_str(&format!(
+            "  {{\"id\": {i}, \"name\": \"item\\u00{:02x} \\\"{i}\\\"\", \"price\": {}.{:02}, \"ratio\": {}e-{}}},\n",
+            0x41 + i % 26,
+            i * 3,
+            i % 100,
+            i % 7 + 1,
+            i % 5 + 1,
+        ));
+    }
+    text.push_str("]\n");
+    text.into_bytes()
+}
@@ -10,9 +10,17 @@
 //! assert_eq!(m.as_str(), "42");
 //! ```
 
-pub use crate::api::{Captures, CapturesIter, FindIter, Match, Regex, RegexBuilder};
-pub use crate::error::RegexError;
+pub use crate::api::{
+    Anchors, Backend, BudgetExceeded, CaptureSpans, Captures, CapturesIter, Classes, FindIter,
+    GroupNames, Match, MatchStats, MemoryBreakdown, OptimizeInfo, Position, Prefilter, Regex,
+    RegexBuilder, RegexCache, Split, SplitInclusive, SplitItem, SplitKeep, MAX_HAYSTACK_LEN,
+};
+pub use crate::charindex::{CharIndex, CharUnit};
+pub use crate::error::{RegexError, SyntaxErrorKind, Warning, WarningKind};
+pub use crate::regenc::CaseMapper;
 pub use crate::scanner::{
-    CaptureIndex, OnigString, Scanner, ScannerConfig, ScannerFindOptions, ScannerMatch,
-    ScannerSyntax,
+    CaptureIndex, CaptureNames, LineCursor, OnigString, Scanner, ScannerConfig,
+    ScannerEmptyMatchAtEnd, ScannerFindOptions, ScannerMatch, ScannerSyntax,
 };
+pub use crate::session::{ScanSession, SessionMatch};
+pub use crate::template::{Template, TemplateError};
@@ -11,12 +11,16 @@
 #![allow(unused_assignments)]
 #![allow(unused_mut)]
 
-use std::sync::atomic::{AtomicU32, Ordering};
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, Ordering};
 
+use crate::api::MemoryBreakdown;
+use crate::memhook::{OnigBufferKind, Tracked};
 use crate::oniguruma::*;
 use crate::regenc::*;
 use crate::regint::*;
 use crate::regparse_types::*;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
 
 // ============================================================================
 // Global Default Case Fold Flag (port of C's OnigDefaultCaseFoldFlag)
@@ -24,17 +28,60 @@ use crate::regparse_types::*;
 
 static DEFAULT_CASE_FOLD_FLAG: AtomicU32 = AtomicU32::new(ONIGENC_CASE_FOLD_MIN);
 
+/// The case fold rules of patterns compiled without an explicit flag.
 #[cfg_attr(coverage_nightly, coverage(off))]
 pub fn onig_get_default_case_fold_flag() -> OnigCaseFoldType {
     DEFAULT_CASE_FOLD_FLAG.load(Ordering::Relaxed)
 }
 
+/// Set the case fold rules for patterns compiled from now on without an
+/// explicit flag (`RegexBuilder::case_fold_flag` takes precedence).
 #[cfg_attr(coverage_nightly, coverage(off))]
 pub fn onig_set_default_case_fold_flag(flag: OnigCaseFoldType) -> i32 {
     DEFAULT_CASE_FOLD_FLAG.store(flag, Ordering::Relaxed);
     0
 }
 
+// ============================================================================
+// Global Default Text Segment (not in C)
+// ============================================================================
+
+/// 0: extended grapheme cluster, 1: word.
+static DEFAULT_TEXT_SEGMENT: AtomicU8 = AtomicU8::new(0);
+
+/// The text segment type of patterns compiled without an
+/// ONIG_OPTION_TEXT_SEGMENT_* option, by their syntax or by the caller.
+pub fn onig_get_default_text_segment() -> OnigTextSegment {
+    match DEFAULT_TEXT_SEGMENT.load(Ordering::Relaxed) {
+        0 => OnigTextSegment::ExtendedGraphemeCluster,
+        _ => OnigTextSegment::Word,
+    }
+}
+
+/// Set the text segment type for patterns compiled from now on without an
+/// ONIG_OPTION_TEXT_SEGMENT_* option. `(?y{g})` and `(?y{w})` in a pattern
+/// still override it.
+pub fn onig_set_default_text_segment(segment: OnigTextSegment) {
+    let v = match segment {
+        OnigTextSegment::ExtendedGraphemeCluster => 0,
+        OnigTextSegment::Word => 1,
+    };
+    DEFAULT_TEXT_SEGMENT.store(v, Ordering::Relaxed);
+}
+
+// ============================================================================
+// Shift-Or backend switch (not in C)
+// ============================================================================
+
+static SHIFT_OR_BACKEND: AtomicBool = AtomicBool::new(true);
+
+/// Whether patterns compiled from now on may use the Shift-Or backend (see
+/// [`Backend::ShiftOr`](crate::api::Backend::ShiftOr)). On by default; turn
+/// it off to compare against the VM.
+pub fn onig_set_shift_or_backend(enable: bool) {
+    SHIFT_OR_BACKEND.store(enable, Ordering::Relaxed);
+}
+
 /// Get encoded character length from a byte slice (for optimization functions).
 fn enclen(enc: OnigEncoding, p: &[u8], _offset: usize) -> usize {
     if p.is_empty() {
@@ -96,6 +143,83 @@ fn add_op(reg: &mut RegexType, opcode: OpCode, payload: OperationPayload) -> i32
     idx as i32
 }
 
+/// Bytes an operation's payload owns outside the operation itself.
+/// Count the ops emitted since the last call against the compile budget.
+/// Returns the error once a limit is exceeded, otherwise 0.
+fn charge_emitted_ops(reg: &RegexType, env: &ParseEnv) -> i32 {
+    env.compile.borrow_mut().budget.emit(&reg.ops, |op| {
+        core::mem::size_of::<Operation>() + payload_heap_size(&op.payload)
+    })
+}
+
+fn payload_heap_size(payload: &OperationPayload) -> usize {
+    match payload {
+        OperationPayload::ExactN { s, .. } | OperationPayload::ExactLenN { s, .. } => s.len(),
+        OperationPayload::CClass { .. } => SIZE_BITSET,
+        OperationPayload::CClassMb { mb, .. } => mb.len() * core::mem::size_of::<u32>(),
+        OperationPayload::CClassMix { mb, .. } => {
+            SIZE_BITSET + mb.len() * core::mem::size_of::<u32>()
+        }
+        OperationPayload::BackRefGeneral { ns, .. } => {
+            ns.len() * core::mem::size_of::<MemNumType>()
+        }
+        OperationPayload::CClassSmall { .. } => SIZE_BITSET + core::mem::size_of::<SmallCodeSet>(),
+        _ => 0,
+    }
+}
+
+/// Heap and struct bytes of a compiled regex, for the `memory_usage()`
+/// methods.
+pub(crate) fn regex_memory_usage(reg: &RegexType) -> MemoryBreakdown {
+    use core::mem::{size_of, size_of_val};
+
+    let mut m = MemoryBreakdown {
+        bytecode: reg.ops.capacity() * size_of::<Operation>()
+            + reg.string_pool.capacity()
+            + reg.repeat_range.capacity() * size_of::<RepeatRange>()
+            + reg.called_addrs.capacity() * size_of::<i32>()
+            + reg.unset_call_addrs.capacity() * size_of::<(usize, i32)>(),
+        ..Default::default()
+    };
+    for op in &reg.ops {
+        let n = payload_heap_size(&op.payload);
+        match op.payload {
+            OperationPayload::CClass { .. }
+            | OperationPayload::CClassMb { .. }
+            | OperationPayload::CClassMix { .. }
+            | OperationPayload::CClassSmall { .. } => m.char_classes += n,
+            _ => m.bytecode += n,
+        }
+    }
+
+    if let Some(table) = &reg.name_table {
+        for (key, e) in &table.entries {
+            m.names += key.capacity()
+                + size_of::<NameEntry>()
+                + e.name.capacity()
+                + e.back_refs.capacity() * size_of::<i32>();
+        }
+    }
+    m.names += reg.group_parents.capacity() * size_of::<Option<u32>>();
+
+    // The byte map lives in the struct; it is counted here, not in `other`
+    m.optimization = size_of_val(&reg.map)
+        + reg.exact.capacity()
+        + reg.req_exact.capacity()
+        + reg.exact_finder.as_ref().map_or(0, |f| f.needle().len())
+        + reg.shift_or.as_ref().map_or(0, |s| s.heap_size());
+
+    m.other = size_of::<RegexType>() - size_of_val(&reg.map);
+    if let Some(ext) = &reg.extp {
+        m.other += ext.pattern.capacity()
+            + ext.callout_list.capacity() * size_of::<CalloutListEntry>()
+            + ext.tag_table.as_ref().map_or(0, |t| {
+                t.keys().map(|k| k.capacity() + size_of::<i32>()).sum()
+            });
+    }
+    m
+}
+
 /// Get the index of the current (last) operation.
 #[cfg_attr(coverage_nightly, coverage(off))]
 fn ops_curr_offset(reg: &RegexType) -> i32 {
@@ -521,9 +645,11 @@ fn tune_next(node: &mut Node, next_node: &Node, reg: &RegexType) -> i32 {
                                                     next_head_exact: qn.next_head_exact.take(),
                                                     include_referred: qn.include_referred,
                                                     empty_status_mem: qn.empty_status_mem,
+                                                    simple_empty_check: qn.simple_empty_check,
                                                 }),
                                                 status: n.status,
-                                                parent: std::ptr::null_mut(),
+                                                parent: core::ptr::null_mut(),
+                                                span: None,
                                             };
                                             // Replace node with Bag(StopBacktrack) wrapping the quant
                                             // C: node_swap(node, en); ND_BODY(node) = en;
@@ -754,11 +880,62 @@ fn bbuf_to_u32_vec(data: &[u8]) -> Vec<u32> {
         .collect()
 }
 
+/// Fuse a class whose multibyte ranges all lie below U+0800 into a
+/// bitmap over those code points. `bsp` answers single-byte characters and
+/// `codes` multibyte ones, so the result agrees with CClassMb/CClassMix for
+/// every input, including stray bytes of invalid UTF-8.
+fn small_cclass(cc: &CClassNode, mb: &[u32]) -> Option<(Box<BitSet>, Box<SmallCodeSet>)> {
+    let n = *mb.first()? as usize;
+    let ranges = mb.get(1..1 + n * 2)?;
+    if ranges
+        .chunks_exact(2)
+        .any(|r| r[1] as usize >= SMALL_CLASS_CODE_LIMIT)
+    {
+        return None;
+    }
+    let has_sb = !bitset_is_empty(&cc.bs);
+    let mut bsp = Box::new(cc.bs);
+    let mut codes: Box<SmallCodeSet> = Box::new([0; SMALL_CLASS_CODE_LIMIT / BITS_IN_ROOM]);
+    for r in ranges.chunks_exact(2) {
+        for code in r[0] as usize..=r[1] as usize {
+            codes[bs_room(code)] |= bs_bit(code);
+            // CClassMb decodes a single byte to its own value.
+            if !has_sb && code < SINGLE_BYTE_SIZE {
+                bitset_set_bit(&mut bsp, code);
+            }
+        }
+    }
+    // CClassMix also tests multibyte characters below 256 against the bitset.
+    for code in 0..SINGLE_BYTE_SIZE {
+        if bitset_at(&cc.bs, code) {
+            codes[bs_room(code)] |= bs_bit(code);
+        }
+    }
+    Some((bsp, codes))
+}
+
 /// Compile a character class node to bytecode.
 fn compile_cclass_node(cc: &CClassNode, reg: &mut RegexType) -> i32 {
     let has_mb = cc.mbuf.is_some();
     let has_sb = !bitset_is_empty(&cc.bs);
 
+    if has_mb {
+        let mb = cc
+            .mbuf
+            .as_ref()
+            .map(|b| bbuf_to_u32_vec(&b.data))
+            .unwrap_or_default();
+        if let Some((bsp, codes)) = small_cclass(cc, &mb) {
+            let opcode = if cc.is_not() {
+                OpCode::CClassSmallNot
+            } else {
+                OpCode::CClassSmall
+            };
+            add_op(reg, opcode, OperationPayload::CClassSmall { bsp, codes });
+            return 0;
+        }
+    }
+
     if has_mb && has_sb {
         // Mixed single-byte and multi-byte
         let opcode = if cc.is_not() {
@@ -879,17 +1056,23 @@ fn compile_quant_body_with_empty_check(
     node: &Node,
     reg: &mut RegexType,
     env: &ParseEnv,
-    emptiness: BodyEmptyType,
-    qn_empty_status_mem: u32,
+    qn: &QuantNode,
 ) -> i32 {
+    let emptiness = qn.emptiness;
+    let qn_empty_status_mem = qn.empty_status_mem;
     let is_empty = emptiness != BodyEmptyType::NotEmpty;
+    let simple = qn.simple_empty_check && emptiness == BodyEmptyType::MayBeEmpty;
     let saved_mem = reg.num_empty_check;
 
     if is_empty {
         reg.num_empty_check += 1;
         add_op(
             reg,
-            OpCode::EmptyCheckStart,
+            if simple {
+                OpCode::EmptyCheckStartSimple
+            } else {
+                OpCode::EmptyCheckStart
+            },
             OperationPayload::EmptyCheckStart { mem: saved_mem },
         );
     }
@@ -922,6 +1105,7 @@ fn compile_quant_body_with_empty_check(
                 }
             }
             BodyEmptyType::MayBeEmptyRec => OpCode::EmptyCheckEndMemstPush,
+            _ if simple => OpCode::EmptyCheckEndSimple,
             _ => OpCode::EmptyCheckEnd,
         };
         add_op(
@@ -1130,13 +1314,7 @@ fn compile_quantifier_node(qn: &QuantNode, reg: &mut RegexType, env: &ParseEnv)
                             c,
                         },
                     );
-                    let r = compile_quant_body_with_empty_check(
-                        body,
-                        reg,
-                        env,
-                        qn.emptiness,
-                        qn.empty_status_mem,
-                    );
+                    let r = compile_quant_body_with_empty_check(body, reg, env, qn);
                     if r != 0 {
                         return r;
                     }
@@ -1151,13 +1329,7 @@ fn compile_quantifier_node(qn: &QuantNode, reg: &mut RegexType, env: &ParseEnv)
                             c,
                         },
                     );
-                    let r = compile_quant_body_with_empty_check(
-                        body,
-                        reg,
-                        env,
-                        qn.emptiness,
-                        qn.empty_status_mem,
-                    );
+                    let r = compile_quant_body_with_empty_check(body, reg, env, qn);
                     if r != 0 {
                         return r;
                     }
@@ -1171,13 +1343,7 @@ fn compile_quantifier_node(qn: &QuantNode, reg: &mut RegexType, env: &ParseEnv)
                             addr: SIZE_INC + mod_tlen + OPSIZE_JUMP,
                         },
                     );
-                    let r = compile_quant_body_with_empty_check(
-                        body,
-                        reg,
-                        env,
-                        qn.emptiness,
-                        qn.empty_status_mem,
-                    );
+                    let r = compile_quant_body_with_empty_check(body, reg, env, qn);
                     if r != 0 {
                         return r;
                     }
@@ -1198,13 +1364,7 @@ fn compile_quantifier_node(qn: &QuantNode, reg: &mut RegexType, env: &ParseEnv)
                         addr: mod_tlen + SIZE_INC,
                     },
                 );
-                let r = compile_quant_body_with_empty_check(
-                    body,
-                    reg,
-                    env,
-                    qn.emptiness,
-                    qn.empty_status_mem,
-                );
+                let r = compile_quant_body_with_empty_check(body, reg, env, qn);
                 if r != 0 {
                     return r;
                 }
@@ -1231,13 +1391,7 @@ fn compile_quantifier_node(qn: &QuantNode, reg: &mut RegexType, env: &ParseEnv)
                         addr: SIZE_INC + mod_tlen + OPSIZE_JUMP,
                     },
                 );
-                let r = compile_quant_body_with_empty_check(
-                    body,
-                    reg,
-                    env,
-                    qn.emptiness,
-                    qn.empty_status_mem,
-                );
+                let r = compile_quant_body_with_empty_check(body, reg, env, qn);
                 if r != 0 {
                     return r;
                 }
@@ -1258,13 +1412,7 @@ fn compile_quantifier_node(qn: &QuantNode, reg: &mut RegexType, env: &ParseEnv)
                         addr: mod_tlen + SIZE_INC,
                     },
                 );
-                let r = compile_quant_body_with_empty_check(
-                    body,
-                    reg,
-                    env,
-                    qn.emptiness,
-                    qn.empty_status_mem,
-                );
+                let r = compile_quant_body_with_empty_check(body, reg, env, qn);
                 if r != 0 {
                     return r;
                 }
@@ -1299,8 +1447,7 @@ fn compile_quantifier_node(qn: &QuantNode, reg: &mut RegexType, env: &ParseEnv)
         );
         // Patch u_offset to point to the body start (op after REPEAT)
         reg.repeat_range[id as usize].u_offset = reg.ops.len() as i32;
-        let r =
-            compile_quant_body_with_empty_check(body, reg, env, qn.emptiness, qn.empty_status_mem);
+        let r = compile_quant_body_with_empty_check(body, reg, env, qn);
         if r != 0 {
             return r;
         }
@@ -1379,8 +1526,7 @@ fn compile_quantifier_node(qn: &QuantNode, reg: &mut RegexType, env: &ParseEnv)
         );
         // Patch u_offset to point to the body start (op after REPEAT)
         reg.repeat_range[id as usize].u_offset = reg.ops.len() as i32;
-        let r =
-            compile_quant_body_with_empty_check(body, reg, env, qn.emptiness, qn.empty_status_mem);
+        let r = compile_quant_body_with_empty_check(body, reg, env, qn);
         if r != 0 {
             return r;
         }
@@ -2493,6 +2639,7 @@ fn compile_length_gimmick_node(gn: &GimmickNode) -> i32 {
                 SIZE_INC // CalloutContents
             }
         }
+        GimmickType::Extension => SIZE_INC,
     }
 }
 
@@ -2554,6 +2701,13 @@ fn compile_gimmick_node(gn: &GimmickNode, reg: &mut RegexType, env: &ParseEnv) -
                 );
             }
         }
+        GimmickType::Extension => {
+            add_op(
+                reg,
+                OpCode::Extension,
+                OperationPayload::Extension { num: gn.num },
+            );
+        }
     }
     0
 }
@@ -2644,6 +2798,11 @@ pub fn compile_length_tree(node: &Node, reg: &RegexType, env: &ParseEnv) -> i32
 /// Pass 2: Generate bytecode operations from the node tree.
 /// Returns 0 on success or a negative error code.
 pub fn compile_tree(node: &Node, reg: &mut RegexType, env: &ParseEnv) -> i32 {
+    // A RegexBuilder limit stops code generation at the next node.
+    let r = charge_emitted_ops(reg, env);
+    if r != 0 {
+        return r;
+    }
     match &node.inner {
         NodeInner::List(cons) => {
             let r = compile_tree(&cons.car, reg, env);
@@ -2716,7 +2875,23 @@ pub fn compile_tree(node: &Node, reg: &mut RegexType, env: &ParseEnv) -> i32 {
                 if i < n - 1 {
                     // PUSH to next alternative (skip over body + JUMP)
                     let push_addr = SIZE_INC + branch_lens[i] + OPSIZE_JUMP;
-                    add_op(reg, push_opcode, OperationPayload::Push { addr: push_addr });
+                    // A branch that must start with a known byte is
+                    // skipped without a backtrack entry when the byte differs,
+                    // so keyword alternations cost one op per failing branch.
+                    let head = if is_super {
+                        None
+                    } else {
+                        get_head_literal_byte(branches[i], true, reg)
+                    };
+                    if let Some(c) = head {
+                        add_op(
+                            reg,
+                            OpCode::PushOrJumpExact1,
+                            OperationPayload::PushOrJumpExact1 { addr: push_addr, c },
+                        );
+                    } else {
+                        add_op(reg, push_opcode, OperationPayload::Push { addr: push_addr });
+                    }
                 }
 
                 let r = compile_tree(branches[i], reg, env);
@@ -3063,6 +3238,43 @@ fn node_min_byte_len(node: &Node, env: &ParseEnv) -> OnigLen {
     }
 }
 
+/// True if matching `node` leaves no backtrack entry on the stack
+/// once it is done, so a backtrack can never resume inside it. An empty
+/// check around such a body only needs the position it started at.
+fn node_leaves_no_backtrack(node: &Node) -> bool {
+    match &node.inner {
+        NodeInner::String(_) | NodeInner::CClass(_) | NodeInner::CType(_) => true,
+        NodeInner::Anchor(an) => an.body.is_none(),
+        NodeInner::List(cons) => {
+            node_leaves_no_backtrack(&cons.car)
+                && cons.cdr.as_deref().map_or(true, node_leaves_no_backtrack)
+        }
+        NodeInner::Bag(bn) => match bn.bag_type {
+            BagType::Option => bn.body.as_deref().map_or(true, node_leaves_no_backtrack),
+            // CutToMark voids the alternatives the body pushed.
+            BagType::StopBacktrack => bn.body.as_deref().map_or(true, cut_voids_all_alts),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// True if CutToMark after `node` voids every alternative it may
+/// push, i.e. it has no super alternatives (absent functions) and nothing
+/// whose stack use this check does not follow.
+fn cut_voids_all_alts(node: &Node) -> bool {
+    match &node.inner {
+        NodeInner::Alt(_) if node.has_status(ND_ST_SUPER) => false,
+        NodeInner::List(cons) | NodeInner::Alt(cons) => {
+            cut_voids_all_alts(&cons.car) && cons.cdr.as_deref().map_or(true, cut_voids_all_alts)
+        }
+        NodeInner::Bag(bn) if bn.bag_type == BagType::IfElse => false,
+        NodeInner::Anchor(an) if an.lead_node.is_some() => false,
+        NodeInner::Call(_) | NodeInner::Gimmick(_) => false,
+        _ => node.body().map_or(true, cut_voids_all_alts),
+    }
+}
+
 /// Check if a quantifier body contains capture groups (Memory bags).
 /// Returns the appropriate emptiness type. Mirrors C's quantifiers_memory_node_info().
 fn quantifiers_memory_node_info(node: &Node) -> BodyEmptyType {
@@ -3172,6 +3384,12 @@ fn unravel_case_fold_string(node: &mut Node, reg: &mut RegexType, state: i32) ->
     };
     node.status_remove(ND_ST_IGNORECASE);
 
+    let mut scratch = Tracked::new(OnigBufferKind::CaseFoldScratch);
+    if let Err(r) = scratch
+        .grow(ONIGENC_GET_CASE_FOLD_CODES_MAX_NUM * core::mem::size_of::<OnigCaseFoldCodeItem>())
+    {
+        return r;
+    }
     let mut items = vec![
         OnigCaseFoldCodeItem {
             byte_len: 0,
@@ -3480,8 +3698,8 @@ fn node_char_len(node: &Node, enc: OnigEncoding) -> CharLenResult {
                 // Success path: condition + then; Failure path: else
                 let success_min = distance_add(cond_len.0, then_len.0);
                 let success_max = distance_add(cond_len.1, then_len.1);
-                let min = std::cmp::min(success_min, else_len.0);
-                let max = std::cmp::max(success_max, else_len.1);
+                let min = core::cmp::min(success_min, else_len.0);
+                let max = core::cmp::max(success_max, else_len.1);
                 if min == max {
                     CharLenResult::Fixed(min)
                 } else {
@@ -3704,8 +3922,10 @@ fn check_called_node_in_look_behind(node: &Node, not: bool) -> i32 {
                 0
             }
         }
-        NodeInner::Gimmick(_) => {
-            if node.has_status(ND_ST_ABSENT_WITH_SIDE_EFFECTS) {
+        NodeInner::Gimmick(gn) => {
+            if node.has_status(ND_ST_ABSENT_WITH_SIDE_EFFECTS)
+                || gn.gimmick_type == GimmickType::Extension
+            {
                 1
             } else {
                 0
@@ -3810,7 +4030,10 @@ fn check_node_in_look_behind(node: &Node, not: bool, used: &mut bool) -> i32 {
             }
         }
         NodeInner::Gimmick(ref gn) => {
-            if node.has_status(ND_ST_ABSENT_WITH_SIDE_EFFECTS) {
+            // A custom opcode has no known length.
+            if node.has_status(ND_ST_ABSENT_WITH_SIDE_EFFECTS)
+                || gn.gimmick_type == GimmickType::Extension
+            {
                 return 1;
             }
             if gn.gimmick_type == GimmickType::Save && gn.detail_type == SaveType::Keep as i32 {
@@ -4038,6 +4261,7 @@ fn resolve_call_references(node: &mut Node, reg: &mut RegexType, env: &mut Parse
                 if let Some(ref nt) = reg.name_table {
                     if let Some(nums) = nt.name_to_group_numbers(&name) {
                         if nums.len() != 1 {
+                            env.error_name.set(Some(call.name_span));
                             return ONIGERR_MULTIPLEX_DEFINITION_NAME_CALL;
                         }
                         call.called_gnum = nums[0];
@@ -4048,9 +4272,11 @@ fn resolve_call_references(node: &mut Node, reg: &mut RegexType, env: &mut Parse
                             }
                         }
                     } else {
+                        env.error_name.set(Some(call.name_span));
                         return ONIGERR_UNDEFINED_NAME_REFERENCE;
                     }
                 } else {
+                    env.error_name.set(Some(call.name_span));
                     return ONIGERR_UNDEFINED_NAME_REFERENCE;
                 }
             }
@@ -4777,11 +5003,12 @@ fn disable_noname_group_capture(root: &mut Node, reg: &mut RegexType, env: &mut
             pos += 1;
         }
     }
+    reset_named_mem_nodes(root, env);
 
     // Update cap_history bitmap with renumbered groups
     let loc = env.cap_history;
     env.cap_history = 0;
-    for i in 1..=std::cmp::min(num_mem, 31) {
+    for i in 1..=core::cmp::min(num_mem, 31) {
         if (loc & (1u32 << i)) != 0 {
             let new_val = map[i].new_val;
             if new_val > 0 && new_val <= 31 {
@@ -4808,6 +5035,52 @@ fn disable_noname_group_capture(root: &mut Node, reg: &mut RegexType, env: &mut
     0
 }
 
+/// Point mem_env at the named groups' current nodes. Unwrapping an unnamed
+/// group moves its body into the group's own node (C relinks the parent's
+/// pointer instead), so the addresses recorded by the parser can dangle.
+fn reset_named_mem_nodes(node: &mut Node, env: &mut ParseEnv) {
+    let node_ptr = node as *mut Node;
+    match &mut node.inner {
+        NodeInner::List(_) | NodeInner::Alt(_) => {
+            let mut cur = Some(node);
+            while let Some(n) = cur {
+                match &mut n.inner {
+                    NodeInner::List(cons) | NodeInner::Alt(cons) => {
+                        reset_named_mem_nodes(&mut cons.car, env);
+                        cur = cons.cdr.as_deref_mut();
+                    }
+                    _ => break,
+                }
+            }
+        }
+        NodeInner::Bag(bn) => {
+            if let BagData::Memory { regnum, .. } = bn.bag_data {
+                env.set_mem_node(regnum, node_ptr);
+            }
+            if let Some(ref mut body) = bn.body {
+                reset_named_mem_nodes(body, env);
+            }
+            if let BagData::IfElse {
+                ref mut then_node,
+                ref mut else_node,
+            } = bn.bag_data
+            {
+                if let Some(ref mut tn) = then_node {
+                    reset_named_mem_nodes(tn, env);
+                }
+                if let Some(ref mut en) = else_node {
+                    reset_named_mem_nodes(en, env);
+                }
+            }
+        }
+        _ => {
+            if let Some(body) = node.body_mut() {
+                reset_named_mem_nodes(body, env);
+            }
+        }
+    }
+}
+
 // ============================================================================
 // infinite_recursive_call_check — detect never-ending recursion
 // Patterns like (?<abc>\g<abc>) or (()(?(2)\g<1>)) have no terminating path.
@@ -5624,6 +5897,11 @@ fn tune_called_state(node: &mut Node, state: i32) {
 /// Tree tuning pass - sets emptiness on quantifier nodes and propagates state.
 /// Mirrors C's tune_tree() from regcomp.c.
 pub fn tune_tree(node: &mut Node, reg: &mut RegexType, state: i32, env: &mut ParseEnv) -> i32 {
+    // Stop once a RegexBuilder compile limit is exceeded.
+    let r = env.compile.get_mut().budget.tick();
+    if r != 0 {
+        return r;
+    }
     // Case-fold expansion: before the main match to get full &mut Node access
     if let NodeInner::String(ref sn) = node.inner {
         if node.has_status(ND_ST_IGNORECASE) && !sn.is_crude() {
@@ -5641,7 +5919,7 @@ pub fn tune_tree(node: &mut Node, reg: &mut RegexType, state: i32, env: &mut Par
         NodeInner::List(_) => {
             // Walk the list: tune each element, then call tune_next for sequential pairs
             let mut cur: *mut Node = node;
-            let mut prev: *mut Node = std::ptr::null_mut();
+            let mut prev: *mut Node = core::ptr::null_mut();
             unsafe {
                 loop {
                     if let NodeInner::List(ref mut cons) = (*cur).inner {
@@ -5727,6 +6005,11 @@ pub fn tune_tree(node: &mut Node, reg: &mut RegexType, state: i32, env: &mut Par
                 }
             }
 
+            // Checked after the body is tuned (case-fold expansion may add
+            // alternatives).
+            qn.simple_empty_check = qn.emptiness == BodyEmptyType::MayBeEmpty
+                && qn.body.as_deref().is_some_and(node_leaves_no_backtrack);
+
             // Expand string: "abc"{3} => "abcabcabc"
             const EXPAND_STRING_MAX_LENGTH: i32 = 100;
             if let Some(ref body) = qn.body {
@@ -5853,7 +6136,7 @@ pub fn tune_tree(node: &mut Node, reg: &mut RegexType, state: i32, env: &mut Par
             // For lookbehind anchors, compute char lengths (may transform node into Alt)
             if at == ANCR_LOOK_BEHIND || at == ANCR_LOOK_BEHIND_NOT {
                 let enc = env.enc;
-                let r = tune_look_behind(node, enc, env.syntax);
+                let r = tune_look_behind(node, enc, &env.syntax);
                 if r != 0 {
                     return r;
                 }
@@ -5899,6 +6182,10 @@ pub fn tune_tree(node: &mut Node, reg: &mut RegexType, state: i32, env: &mut Par
         NodeInner::BackRef(ref br) => {
             // Set backrefed_mem for each referenced group
             for &back in br.back_refs() {
+                // `\8` and `\9` are backrefs even without that many groups
+                if back > env.num_mem {
+                    return ONIGERR_INVALID_BACKREF;
+                }
                 if back > 0 {
                     mem_status_on(&mut env.backrefed_mem, back as usize);
                 }
@@ -6163,7 +6450,8 @@ fn rebuild_list(items: Vec<Box<Node>>) -> Box<Node> {
     assert!(!items.is_empty());
     let mut result = Node {
         status: 0,
-        parent: std::ptr::null_mut(),
+        parent: core::ptr::null_mut(),
+        span: None,
         inner: NodeInner::List(ConsAltNode {
             car: items.pop().unwrap(),
             cdr: None,
@@ -6172,7 +6460,8 @@ fn rebuild_list(items: Vec<Box<Node>>) -> Box<Node> {
     while let Some(item) = items.pop() {
         result = Node {
             status: 0,
-            parent: std::ptr::null_mut(),
+            parent: core::ptr::null_mut(),
+            span: None,
             inner: NodeInner::List(ConsAltNode {
                 car: item,
                 cdr: Some(Box::new(result)),
@@ -6192,10 +6481,11 @@ pub fn reduce_string_list(node: &mut Node, enc: OnigEncoding) -> i32 {
                 s: Vec::new(),
                 flag: 0,
             });
-            let old_inner = std::mem::replace(&mut node.inner, placeholder);
+            let old_inner = core::mem::replace(&mut node.inner, placeholder);
             let list_node = Box::new(Node {
                 status: 0,
-                parent: std::ptr::null_mut(),
+                parent: core::ptr::null_mut(),
+                span: None,
                 inner: old_inner,
             });
             let mut items = flatten_list(list_node);
@@ -6259,10 +6549,11 @@ pub fn reduce_string_list(node: &mut Node, enc: OnigEncoding) -> i32 {
                 s: Vec::new(),
                 flag: 0,
             });
-            let old_inner = std::mem::replace(&mut node.inner, placeholder);
+            let old_inner = core::mem::replace(&mut node.inner, placeholder);
             let alt_node = Box::new(Node {
                 status: 0,
-                parent: std::ptr::null_mut(),
+                parent: core::ptr::null_mut(),
+                span: None,
                 inner: old_inner,
             });
 
@@ -6289,7 +6580,8 @@ pub fn reduce_string_list(node: &mut Node, enc: OnigEncoding) -> i32 {
                     // Rebuild alt chain and put back
                     let mut result = Node {
                         status: 0,
-                        parent: std::ptr::null_mut(),
+                        parent: core::ptr::null_mut(),
+                        span: None,
                         inner: NodeInner::Alt(ConsAltNode {
                             car: items.pop().unwrap(),
                             cdr: None,
@@ -6298,7 +6590,8 @@ pub fn reduce_string_list(node: &mut Node, enc: OnigEncoding) -> i32 {
                     while let Some(item) = items.pop() {
                         result = Node {
                             status: 0,
-                            parent: std::ptr::null_mut(),
+                            parent: core::ptr::null_mut(),
+                            span: None,
                             inner: NodeInner::Alt(ConsAltNode {
                                 car: item,
                                 cdr: Some(Box::new(result)),
@@ -6315,7 +6608,8 @@ pub fn reduce_string_list(node: &mut Node, enc: OnigEncoding) -> i32 {
             let last = items_rev.pop().unwrap();
             let mut result = Node {
                 status: 0,
-                parent: std::ptr::null_mut(),
+                parent: core::ptr::null_mut(),
+                span: None,
                 inner: NodeInner::Alt(ConsAltNode {
                     car: last,
                     cdr: None,
@@ -6324,7 +6618,8 @@ pub fn reduce_string_list(node: &mut Node, enc: OnigEncoding) -> i32 {
             while let Some(item) = items_rev.pop() {
                 result = Node {
                     status: 0,
-                    parent: std::ptr::null_mut(),
+                    parent: core::ptr::null_mut(),
+                    span: None,
                     inner: NodeInner::Alt(ConsAltNode {
                         car: item,
                         cdr: Some(Box::new(result)),
@@ -6386,6 +6681,239 @@ pub fn reduce_string_list(node: &mut Node, enc: OnigEncoding) -> i32 {
     }
 }
 
+// ============================================================================
+// Alternation factoring (not in C)
+// ============================================================================
+
+/// Factor the common literal prefix out of adjacent alternatives
+/// (`foobar|foobaz` to `foo(?:bar|baz)`) and drop alternatives that repeat
+/// an earlier literal alternative. Machine-generated patterns are full of
+/// both, and every alternative costs a backtrack push when tried.
+///
+/// Factoring keeps the order alternatives are tried in, and a literal
+/// prefix matches in exactly one way, so results do not change. A repeated
+/// literal alternative can only be tried in the state its first occurrence
+/// already failed in, so it is dropped unless a callout could observe the
+/// retry. Look-behind bodies are left alone: their alternatives must keep
+/// a fixed length each.
+fn factor_alternatives(node: &mut Node, enc: OnigEncoding, dedupe: bool) {
+    match &mut node.inner {
+        NodeInner::List(cons) => {
+            factor_alternatives(&mut cons.car, enc, dedupe);
+            if let Some(ref mut next) = cons.cdr {
+                factor_alternatives(next, enc, dedupe);
+            }
+        }
+        NodeInner::Alt(_) => {
+            if node.has_status(ND_ST_SUPER) {
+                return;
+            }
+            let mut items = take_alt_branches(node);
+            for item in items.iter_mut() {
+                factor_alternatives(item, enc, dedupe);
+            }
+            let mut items = factor_branches(items, enc, dedupe);
+            if items.len() == 1 {
+                // Only String and fresh List nodes collapse, so no capture
+                // node recorded by address in the parse env moves.
+                let status = node.status;
+                *node = *items.pop().unwrap();
+                node.status |= status;
+            } else {
+                node.inner = build_alt(items).inner;
+            }
+        }
+        NodeInner::Quant(qn) => {
+            if let Some(ref mut body) = qn.body {
+                factor_alternatives(body, enc, dedupe);
+            }
+        }
+        NodeInner::Anchor(an) => {
+            if an.anchor_type & (ANCR_LOOK_BEHIND | ANCR_LOOK_BEHIND_NOT) != 0 {
+                return;
+            }
+            if let Some(ref mut body) = an.body {
+                factor_alternatives(body, enc, dedupe);
+            }
+        }
+        NodeInner::Bag(bn) => {
+            if let Some(ref mut body) = bn.body {
+                factor_alternatives(body, enc, dedupe);
+            }
+            if let BagData::IfElse {
+                ref mut then_node,
+                ref mut else_node,
+            } = bn.bag_data
+            {
+                for branch in [then_node, else_node].into_iter().flatten() {
+                    factor_alternatives(branch, enc, dedupe);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Move the branches out of the Alt chain at `node`, leaving `node` with
+/// a placeholder to be overwritten.
+#[allow(clippy::vec_box)] // boxes keep capture node addresses stable
+fn take_alt_branches(node: &mut Node) -> Vec<Box<Node>> {
+    let placeholder = NodeInner::String(StrNode {
+        s: Vec::new(),
+        flag: 0,
+    });
+    let mut items = Vec::new();
+    let mut inner = core::mem::replace(&mut node.inner, placeholder);
+    loop {
+        match inner {
+            NodeInner::Alt(cons) => {
+                items.push(cons.car);
+                match cons.cdr {
+                    Some(next) => inner = next.inner,
+                    None => break,
+                }
+            }
+            other => {
+                items.push(Box::new(Node {
+                    status: 0,
+                    parent: core::ptr::null_mut(),
+                    span: None,
+                    inner: other,
+                }));
+                break;
+            }
+        }
+    }
+    items
+}
+
+/// Build an Alt chain from its branches.
+#[allow(clippy::vec_box)]
+fn build_alt(mut items: Vec<Box<Node>>) -> Box<Node> {
+    let mut result = node_new_alt(items.pop().unwrap(), None);
+    while let Some(item) = items.pop() {
+        result = node_new_alt(item, Some(result));
+    }
+    result
+}
+
+/// The literal string an alternative starts with, if factoring may split
+/// it: case-insensitive strings can match different lengths, and crude
+/// strings need not end on a character boundary.
+fn literal_head(node: &Node) -> Option<&Node> {
+    let head = match &node.inner {
+        NodeInner::String(_) => node,
+        NodeInner::List(cons) if cons.car.node_type() == NodeType::String => &cons.car,
+        _ => return None,
+    };
+    let sn = head.as_str()?;
+    if sn.flag != 0 || head.has_status(ND_ST_IGNORECASE) {
+        return None;
+    }
+    Some(head)
+}
+
+/// Length of the longest common prefix of `a` and `b` that ends on a
+/// character boundary.
+fn common_char_prefix(a: &[u8], b: &[u8], enc: OnigEncoding) -> usize {
+    let mut i = 0;
+    while i < a.len() {
+        let len = enclen(enc, &a[i..], 0);
+        if i + len > a.len() || i + len > b.len() || a[i..i + len] != b[i..i + len] {
+            break;
+        }
+        i += len;
+    }
+    i
+}
+
+/// Drop repeated literal branches and factor common prefixes out of runs
+/// of adjacent branches.
+#[allow(clippy::vec_box)]
+fn factor_branches(items: Vec<Box<Node>>, enc: OnigEncoding, dedupe: bool) -> Vec<Box<Node>> {
+    let mut items = items;
+    if dedupe {
+        let mut kept: Vec<Box<Node>> = Vec::with_capacity(items.len());
+        for item in items {
+            let repeated = item.as_str().is_some_and(|sn| {
+                kept.iter().any(|k| {
+                    k.status == item.status
+                        && k.as_str()
+                            .is_some_and(|ks| ks.flag == sn.flag && ks.s == sn.s)
+                })
+            });
+            if !repeated {
+                kept.push(item);
+            }
+        }
+        items = kept;
+    }
+
+    let mut out: Vec<Box<Node>> = Vec::with_capacity(items.len());
+    let mut rest = items.into_iter().peekable();
+    while let Some(first) = rest.next() {
+        let Some(head) = literal_head(&first) else {
+            out.push(first);
+            continue;
+        };
+        let (status, bytes) = (head.status, head.as_str().unwrap().s.clone());
+
+        let mut run = vec![first];
+        let mut prefix = bytes.len();
+        while let Some(next) = rest.peek() {
+            let len = match literal_head(next) {
+                Some(h) if h.status == status => {
+                    common_char_prefix(&bytes[..prefix], &h.as_str().unwrap().s, enc)
+                }
+                _ => 0,
+            };
+            if len == 0 {
+                break;
+            }
+            prefix = len;
+            run.push(rest.next().unwrap());
+        }
+        if run.len() < 2 {
+            out.extend(run);
+            continue;
+        }
+
+        let tails: Vec<_> = run
+            .into_iter()
+            .map(|b| strip_literal_head(b, prefix))
+            .collect();
+        let mut alt = build_alt(tails);
+        factor_alternatives(&mut alt, enc, dedupe);
+        let mut head = node_new_str(&bytes[..prefix]);
+        head.status = status;
+        out.push(node_new_list(head, Some(node_new_list(alt, None))));
+    }
+    out
+}
+
+/// Remove the first `len` bytes of the literal head of `branch`.
+fn strip_literal_head(mut branch: Box<Node>, len: usize) -> Box<Node> {
+    match branch.inner {
+        NodeInner::String(ref mut sn) => {
+            sn.s.drain(..len);
+            branch
+        }
+        NodeInner::List(ref mut cons) => {
+            let sn = cons.car.as_str_mut().unwrap();
+            sn.s.drain(..len);
+            if !sn.s.is_empty() || cons.cdr.is_none() {
+                return branch;
+            }
+            let next = cons.cdr.take().unwrap();
+            match next.inner {
+                NodeInner::List(ConsAltNode { car, cdr: None }) => car,
+                inner => Box::new(Node { inner, ..*next }),
+            }
+        }
+        _ => unreachable!("branch has no literal head"),
+    }
+}
+
 /// Simple compilation from a pre-parsed AST tree.
 /// Used internally and by tests that parse separately.
 #[cfg_attr(coverage_nightly, coverage(off))]
@@ -6922,6 +7450,7 @@ fn node_max_byte_len(node: &Node, env: &ParseEnv) -> OnigLen {
                 }
             }
         },
+        NodeInner::Gimmick(gn) if gn.gimmick_type == GimmickType::Extension => INFINITE_LEN,
         NodeInner::Anchor(_) | NodeInner::Gimmick(_) => 0,
     }
 }
@@ -7254,7 +7783,11 @@ fn optimize_nodes(
                 }
             }
         },
-        NodeInner::Gimmick(_) => {}
+        NodeInner::Gimmick(gn) => {
+            if gn.gimmick_type == GimmickType::Extension {
+                opt.len.set(0, INFINITE_LEN);
+            }
+        }
     }
     0
 }
@@ -7334,6 +7867,7 @@ fn set_optimize_exact(reg: &mut RegexType, e: &OptStr) -> i32 {
         if r != 0 {
             return r;
         }
+        reg.exact_finder = Some(memchr::memmem::Finder::new(&reg.exact).into_owned());
         reg.optimize = if allow_reverse {
             OptimizeType::StrFast
         } else {
@@ -7362,13 +7896,13 @@ fn set_optimize_map(reg: &mut RegexType, m: &OptMap) {
     }
 
     // Precompute distinct set bytes for SIMD-accelerated map_search.
-    // Only accelerate when all set bytes are ASCII (< 0x80) to avoid
-    // false positives from UTF-8 continuation bytes.
+    // Non-ASCII bytes are included too; map_search drops hits that are
+    // not at a character head.
     let mut bytes = [0u8; 3];
     let mut count: u8 = 0;
     for i in 0..CHAR_MAP_SIZE {
         if m.map[i] != 0 {
-            if count >= 3 || i >= 0x80 {
+            if count >= 3 {
                 count = 0;
                 break;
             }
@@ -7400,8 +7934,11 @@ fn set_optimize_info_from_tree(root: &Node, reg: &mut RegexType, scan_env: &Pars
         return r;
     }
 
+    // Unlike C, ANCR_BEGIN_LINE is kept so the search loop can skip from one
+    // line start to the next for patterns like `^[ \t]*(?:key1|key2)`.
     reg.anchor = opt.anc.left
         & (ANCR_BEGIN_BUF
+            | ANCR_BEGIN_LINE
             | ANCR_BEGIN_POSITION
             | ANCR_ANYCHAR_INF
             | ANCR_ANYCHAR_INF_ML
@@ -7412,6 +7949,8 @@ fn set_optimize_info_from_tree(root: &Node, reg: &mut RegexType, scan_env: &Pars
     }
 
     reg.anchor |= opt.anc.right & (ANCR_END_BUF | ANCR_SEMI_END_BUF | ANCR_PREC_READ_NOT);
+    reg.match_len_min = opt.len.min;
+    reg.match_len_max = opt.len.max;
 
     if (reg.anchor & (ANCR_END_BUF | ANCR_SEMI_END_BUF)) != 0 {
         reg.anc_dist_min = opt.len.min;
@@ -7420,6 +7959,9 @@ fn set_optimize_info_from_tree(root: &Node, reg: &mut RegexType, scan_env: &Pars
 
     if opt.sb.len > 0 || opt.sm.len > 0 {
         select_opt_exact(reg.enc, &mut opt.sb, &opt.sm);
+        reg.req_exact = opt.sb.s[..opt.sb.len].to_vec();
+        reg.req_dist_min = opt.sb.mm.min;
+        reg.req_dist_max = opt.sb.mm.max;
         if opt.map.value > 0 && comp_opt_exact_or_map(&opt.sb, &opt.map) > 0 {
             set_optimize_map(reg, &opt.map);
             set_sub_anchor(reg, &opt.map.anc);
@@ -7445,22 +7987,112 @@ fn set_optimize_info_from_tree(root: &Node, reg: &mut RegexType, scan_env: &Pars
 /// Full compilation entry point - mirrors C's onig_compile().
 /// Parses pattern, compiles to bytecode, sets up mem status and stack_pop_level.
 pub fn onig_compile(reg: &mut RegexType, pattern: &[u8]) -> i32 {
+    onig_compile_with(reg, pattern, &mut CompileState::default())
+}
+
+/// onig_compile() under the limits in `state.budget`. The error location
+/// and the warnings of the compile are left in `state`.
+pub(crate) fn onig_compile_with(
+    reg: &mut RegexType,
+    pattern: &[u8],
+    state: &mut CompileState,
+) -> i32 {
+    compile_with(reg, state, |reg, env| {
+        crate::regparse::onig_parse_tree(pattern, reg, env)
+    })
+}
+
+/// onig_compile(), handing the parse tree to `inspect` before the compiler
+/// rewrites it.
+/// Not in the C original: backs `validate()`.
+pub(crate) fn onig_compile_inspect(
+    reg: &mut RegexType,
+    pattern: &[u8],
+    state: &mut CompileState,
+    inspect: impl FnOnce(&Node),
+) -> i32 {
+    compile_with(reg, state, |reg, env| {
+        let root = crate::regparse::onig_parse_tree(pattern, reg, env)?;
+        inspect(&root);
+        Ok(root)
+    })
+}
+
+/// Compile `s` as an exact string, bypassing the parser.
+/// Not in the C original: backs `Regex::literal()`, where no byte of the
+/// input may be interpreted as a metacharacter or escape.
+pub fn onig_compile_literal(reg: &mut RegexType, s: &[u8]) -> i32 {
+    onig_compile_literal_with(reg, s, &mut CompileState::default())
+}
+
+/// onig_compile_literal() under the limits in `state.budget`.
+pub(crate) fn onig_compile_literal_with(
+    reg: &mut RegexType,
+    s: &[u8],
+    state: &mut CompileState,
+) -> i32 {
+    compile_with(reg, state, |reg, env| literal_tree(reg, env, s))
+}
+
+/// Build a tree with `parse` and compile it into `reg`. `state` is lent to
+/// the parse environment for the duration of the compile.
+fn compile_with(
+    reg: &mut RegexType,
+    state: &mut CompileState,
+    parse: impl FnOnce(&mut RegexType, &mut ParseEnv) -> Result<Box<Node>, i32>,
+) -> i32 {
     // Clear previous bytecode
     reg.ops.clear();
 
-    // Parse the pattern into AST
-    let mut env = ParseEnv {
+    let mut env = new_parse_env(reg);
+    *env.compile.get_mut() = core::mem::take(state);
+    let r = match parse(reg, &mut env) {
+        Ok(root) => compile_parsed_tree(root, reg, &mut env),
+        Err(e) => e,
+    };
+    *state = env.compile.into_inner();
+    r
+}
+
+/// Build the single string node onig_compile_literal() compiles.
+fn literal_tree(reg: &mut RegexType, env: &mut ParseEnv, s: &[u8]) -> Result<Box<Node>, i32> {
+    // Mirror the setup onig_parse_tree() performs before prs_regexp()
+    reg.num_mem = 0;
+    reg.num_repeat = 0;
+    reg.num_empty_check = 0;
+    reg.repeat_range = Vec::new();
+    reg.name_table = Some(NameTable::new());
+    env.clear();
+    env.options = reg.options;
+    env.case_fold_flag = reg.case_fold_flag;
+    env.enc = reg.enc;
+    env.syntax = reg.syntax.clone();
+    env.reg = reg as *mut RegexType;
+
+    if !env.enc.is_valid_mbc_string(s) {
+        return Err(ONIGERR_INVALID_WIDE_CHAR_VALUE);
+    }
+
+    let mut root = node_new_str(s);
+    if opton_ignorecase(env.options) {
+        root.status_add(ND_ST_IGNORECASE);
+    }
+    Ok(root)
+}
+
+fn new_parse_env(reg: &mut RegexType) -> ParseEnv {
+    ParseEnv {
         options: reg.options,
         case_fold_flag: reg.case_fold_flag,
         enc: reg.enc,
-        syntax: unsafe { &*reg.syntax },
+        syntax: reg.syntax.clone(),
         cap_history: 0,
         backtrack_mem: 0,
         backrefed_mem: 0,
-        pattern: std::ptr::null(),
-        pattern_end: std::ptr::null(),
-        error: std::ptr::null(),
-        error_end: std::ptr::null(),
+        pattern: core::ptr::null(),
+        pattern_end: core::ptr::null(),
+        error: core::ptr::null(),
+        error_end: core::ptr::null(),
         reg: reg as *mut RegexType,
         num_call: 0,
         num_mem: 0,
@@ -7476,22 +8108,27 @@ pub fn onig_compile(reg: &mut RegexType, pattern: &[u8]) -> i32 {
         unset_addr_list: None,
         parse_depth: 0,
         flags: 0,
-    };
-
-    let mut root = match crate::regparse::onig_parse_tree(pattern, reg, &mut env) {
-        Ok(node) => node,
-        Err(e) => return e,
-    };
+        token_start: Default::default(),
+        error_name: Default::default(),
+        cc_scratch: Default::default(),
+        cc_buf_hint: 0,
+        cc_redundant: None,
+        compile: Default::default(),
+    }
+}
 
+/// The tree passes of onig_compile() between parsing and code generation:
+/// named-group renumbering, string reduction, call resolution and tune_tree().
+fn tune_parsed_tree(root: &mut Box<Node>, reg: &mut RegexType, env: &mut ParseEnv) -> i32 {
     // CAPTURE_ONLY_NAMED_GROUP: when named groups exist, disable unnamed captures
     if env.num_named > 0
-        && is_syntax_bv(env.syntax, ONIG_SYN_CAPTURE_ONLY_NAMED_GROUP)
+        && is_syntax_bv(&env.syntax, ONIG_SYN_CAPTURE_ONLY_NAMED_GROUP)
         && !opton_capture_group(reg.options)
     {
         let r = if env.num_named != env.num_mem {
-            disable_noname_group_capture(&mut root, reg, &mut env)
+            disable_noname_group_capture(root, reg, env)
         } else {
-            numbered_ref_check(&root)
+            numbered_ref_check(root)
         };
         if r != 0 {
             return r;
@@ -7499,49 +8136,117 @@ pub fn onig_compile(reg: &mut RegexType, pattern: &[u8]) -> i32 {
     }
 
     // Optimize: consolidate adjacent string nodes (mirrors C's reduce_string_list)
-    let r = reduce_string_list(&mut root, reg.enc);
+    let r = reduce_string_list(root, reg.enc);
     if r != 0 {
         return r;
     }
 
+    // Factor common prefixes out of alternations.
+    let has_callouts = reg.extp.as_ref().is_some_and(|e| e.callout_num != 0);
+    factor_alternatives(root, reg.enc, !has_callouts);
+
     // Resolve subroutine call references before tune_tree
     if env.num_call > 0 {
-        let r = resolve_call_references(&mut root, reg, &mut env);
+        let r = resolve_call_references(root, reg, env);
         if r != 0 {
+            env.compile.get_mut().error_name = env.error_name.get();
             return r;
         }
         // Mark zero-repeat contexts and adjust entry counts
-        tune_call(&mut root, 0);
+        tune_call(root, 0);
         // Count entries on called targets
-        let r = tune_call2(&mut root);
+        let r = tune_call2(root);
         if r != 0 {
             return r;
         }
         // Detect recursion and set ND_ST_RECURSION on recursive capture groups
-        recursive_call_check_trav(&mut root, &mut env, 0);
+        recursive_call_check_trav(root, env, 0);
         // Check for never-ending recursion (e.g. (?<abc>\g<abc>))
-        let r = infinite_recursive_call_check_trav(&mut root, &env);
+        let r = infinite_recursive_call_check_trav(root, env);
         if r != 0 {
             return r;
         }
         // Propagate state flags (IN_ALT, IN_REAL_REPEAT, etc.) through called groups
-        tune_called_state(&mut root, 0);
+        tune_called_state(root, 0);
     }
 
     // Tune tree: detect empty loops, propagate state (mirrors C's tune_tree)
-    let r = tune_tree(&mut root, reg, 0, &mut env);
+    let r = tune_tree(root, reg, 0, env);
     if r != 0 {
         return r;
     }
 
     // Compute empty_status_mem for quantifiers (determines EmptyCheckEnd vs EmptyCheckEndMemst)
-    setup_empty_status_mem(&mut root, &mut env);
+    setup_empty_status_mem(root, env);
+
+    0
+}
+
+/// Record the innermost enclosing capture group of every capture group
+/// under `node`, whose own innermost enclosing group is `parent`.
+/// Backs `Regex::group_parents()`.
+fn collect_group_parents(node: &Node, parent: Option<u32>, out: &mut Vec<Option<u32>>) {
+    match &node.inner {
+        NodeInner::List(cons) | NodeInner::Alt(cons) => {
+            collect_group_parents(&cons.car, parent, out);
+            if let Some(ref next) = cons.cdr {
+                collect_group_parents(next, parent, out);
+            }
+        }
+        NodeInner::Quant(ref qn) => {
+            if let Some(ref body) = qn.body {
+                collect_group_parents(body, parent, out);
+            }
+        }
+        NodeInner::Anchor(ref a) => {
+            if let Some(ref body) = a.body {
+                collect_group_parents(body, parent, out);
+            }
+        }
+        NodeInner::Bag(ref bn) => {
+            let mut inner = parent;
+            if bn.bag_type == BagType::Memory {
+                // Group 0 is the implicit wrapper \g<0> adds around the pattern.
+                let regnum = bn.regnum();
+                if regnum > 0 && (regnum as usize) < out.len() {
+                    out[regnum as usize] = parent;
+                    inner = Some(regnum as u32);
+                }
+            }
+            if let Some(ref body) = bn.body {
+                collect_group_parents(body, inner, out);
+            }
+            if let BagData::IfElse {
+                ref then_node,
+                ref else_node,
+            } = bn.bag_data
+            {
+                for branch in [then_node, else_node].into_iter().flatten() {
+                    collect_group_parents(branch, inner, out);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Compile a parsed tree into `reg`: everything onig_compile() does after
+/// onig_parse_tree() returns.
+fn compile_parsed_tree(mut root: Box<Node>, reg: &mut RegexType, env: &mut ParseEnv) -> i32 {
+    let r = tune_parsed_tree(&mut root, reg, env);
+    if r != 0 {
+        return r;
+    }
 
     // Set capture/mem tracking from parse env (mirrors C's onig_compile post-parse setup)
     reg.capture_history = env.cap_history;
     reg.push_mem_start = env.backtrack_mem | env.cap_history;
     reg.num_mem = env.num_mem;
 
+    // Static nesting of the (possibly renumbered) capture groups.
+    reg.group_parents = vec![None; env.num_mem as usize + 1];
+    collect_group_parents(&root, None, &mut reg.group_parents);
+
     // Set push_mem_end
     if mem_status_is_all_on(reg.push_mem_start) {
         reg.push_mem_end = env.backrefed_mem | env.cap_history;
@@ -7554,7 +8259,7 @@ pub fn onig_compile(reg: &mut RegexType, pattern: &[u8]) -> i32 {
     reg.num_call = env.id_num;
 
     // Compile the tree to bytecode
-    let r = compile_tree(&root, reg, &env);
+    let r = compile_tree(&root, reg, env);
     if r != 0 {
         return r;
     }
@@ -7585,6 +8290,10 @@ pub fn onig_compile(reg: &mut RegexType, pattern: &[u8]) -> i32 {
 
     // Add OP_END
     add_op(reg, OpCode::End, OperationPayload::None);
+    let r = charge_emitted_ops(reg, env);
+    if r != 0 {
+        return r;
+    }
 
     // If callouts exist, set push_mem_end (C: callout_num != 0 → push_mem_end = push_mem_start)
     if let Some(ref ext) = reg.extp {
@@ -7609,11 +8318,20 @@ pub fn onig_compile(reg: &mut RegexType, pattern: &[u8]) -> i32 {
     }
 
     // Set optimization info (exact string, char map, anchors) from parse tree
-    let r = set_optimize_info_from_tree(&root, reg, &env);
+    let r = set_optimize_info_from_tree(&root, reg, env);
     if r != 0 {
         return r;
     }
 
+    // Run short fixed patterns without the VM.
+    reg.shift_or = if SHIFT_OR_BACKEND.load(Ordering::Relaxed) {
+        crate::shiftor::shift_or_from_tree(&root, reg)
+    } else {
+        None
+    };
+    // Let onig_match() skip the heap for small programs.
+    reg.small_match = crate::regexec::is_small_program(reg);
+
     0
 }
 
@@ -7624,6 +8342,162 @@ pub fn onig_new(
     option: OnigOptionType,
     enc: OnigEncoding,
     syntax: &OnigSyntaxType,
+) -> Result<RegexType, crate::error::RegexError> {
+    onig_new_with(pattern, option, enc, syntax, &mut CompileState::default())
+}
+
+/// [`onig_new`] that leaves the error location in `state`, for callers
+/// that report it the C way.
+pub(crate) fn onig_new_with(
+    pattern: &[u8],
+    option: OnigOptionType,
+    enc: OnigEncoding,
+    syntax: &OnigSyntaxType,
+    state: &mut CompileState,
+) -> Result<RegexType, crate::error::RegexError> {
+    let mut reg = onig_reg_init(option, onig_get_default_case_fold_flag(), enc, syntax)?;
+
+    let r = onig_compile_with(&mut reg, pattern, state);
+    if r != 0 {
+        return Err(compile_error(r, state, pattern));
+    }
+
+    Ok(reg)
+}
+
+/// Create a regex from a pattern written in `ci.pattern_enc` that matches
+/// text in `ci.target_enc`.
+///
+/// C fails with ONIGERR_NOT_SUPPORTED_ENCODING_COMBINATION when the
+/// two encodings differ. Here the pattern is transcoded to the target
+/// encoding first, so error positions refer to the transcoded pattern.
+pub fn onig_new_deluxe(
+    pattern: &[u8],
+    ci: &OnigCompileInfo,
+) -> Result<RegexType, crate::error::RegexError> {
+    onig_new_deluxe_with(pattern, ci, &mut CompileState::default())
+}
+
+/// [`onig_new_deluxe`] that leaves the error location in `state`. The
+/// location refers to the transcoded pattern when the encodings differ.
+pub(crate) fn onig_new_deluxe_with(
+    pattern: &[u8],
+    ci: &OnigCompileInfo,
+    state: &mut CompileState,
+) -> Result<RegexType, crate::error::RegexError> {
+    let transcoded;
+    let pattern = if core::ptr::eq(ci.pattern_enc, ci.target_enc) {
+        pattern
+    } else {
+        transcoded = transcode_pattern(pattern, ci.pattern_enc, ci.target_enc)?;
+        &transcoded[..]
+    };
+    let mut reg = onig_reg_init(ci.option, ci.case_fold_flag, ci.target_enc, ci.syntax)?;
+
+    let r = onig_compile_with(&mut reg, pattern, state);
+    if r != 0 {
+        return Err(compile_error(r, state, pattern));
+    }
+
+    Ok(reg)
+}
+
+/// Re-encode `pattern` character by character. Fails if `pattern` is not
+/// valid in `from` or a character has no encoding in `to`.
+fn transcode_pattern(pattern: &[u8], from: OnigEncoding, to: OnigEncoding) -> Result<Vec<u8>, i32> {
+    if !from.is_valid_mbc_string(pattern) {
+        return Err(ONIGERR_INVALID_WIDE_CHAR_VALUE);
+    }
+    let mut out = Vec::with_capacity(pattern.len());
+    let mut buf = [0u8; ONIGENC_CODE_TO_MBC_MAXLEN];
+    let mut p = 0;
+    while p < pattern.len() {
+        let code = from.mbc_to_code(&pattern[p..], pattern.len() - p);
+        let len = to.code_to_mbclen(code);
+        if len < 0 {
+            return Err(len);
+        }
+        let len = to.code_to_mbc(code, &mut buf);
+        out.extend_from_slice(&buf[..len as usize]);
+        p += from.mbc_enc_len(&pattern[p..]);
+    }
+    Ok(out)
+}
+
+/// Create a regex matching `pattern` as an exact string (see onig_compile_literal()).
+/// Only the IGNORECASE family of options has an effect on the result.
+pub fn onig_new_literal(
+    pattern: &[u8],
+    option: OnigOptionType,
+    enc: OnigEncoding,
+) -> Result<RegexType, crate::error::RegexError> {
+    let mut reg = onig_reg_init(
+        option,
+        onig_get_default_case_fold_flag(),
+        enc,
+        &crate::regsyntax::OnigSyntaxASIS,
+    )?;
+
+    let r = onig_compile_literal(&mut reg, pattern);
+    if r != 0 {
+        return Err(r.into());
+    }
+
+    Ok(reg)
+}
+
+/// Parse `pattern` without compiling it.
+/// Not in the C original: backs `ast::parse()`. Returns the raw parse tree
+/// together with the regex whose name table and group count it filled in.
+pub(crate) fn onig_parse_only(
+    pattern: &[u8],
+    option: OnigOptionType,
+    enc: OnigEncoding,
+    syntax: &OnigSyntaxType,
+) -> Result<(Box<Node>, RegexType), crate::error::RegexError> {
+    let mut reg = onig_reg_init(option, onig_get_default_case_fold_flag(), enc, syntax)?;
+    let mut env = new_parse_env(&mut reg);
+    let root = crate::regparse::onig_parse_tree(pattern, &mut reg, &mut env)
+        .map_err(|r| compile_error(r, &env.compile.borrow(), pattern))?;
+    Ok((root, reg))
+}
+
+/// A parse tree, its regex and the redundant class items found by the parser.
+type LintParse = (Box<Node>, RegexType, Vec<(usize, usize)>);
+
+/// Parse like `onig_parse_only`, and also return the pattern
+/// ranges of character class items that add nothing to their class.
+pub(crate) fn onig_parse_lint(
+    pattern: &[u8],
+    option: OnigOptionType,
+    enc: OnigEncoding,
+    syntax: &OnigSyntaxType,
+) -> Result<LintParse, crate::error::RegexError> {
+    let mut reg = onig_reg_init(option, onig_get_default_case_fold_flag(), enc, syntax)?;
+    let mut env = new_parse_env(&mut reg);
+    env.cc_redundant = Some(Vec::new());
+    let root = crate::regparse::onig_parse_tree(pattern, &mut reg, &mut env)
+        .map_err(|r| compile_error(r, &env.compile.borrow(), pattern))?;
+    let redundant = env.cc_redundant.take().unwrap_or_default();
+    Ok((root, reg, redundant))
+}
+
+/// The error for a failed parse or compile of `pattern`, with the location
+/// where parsing failed (see `CompileState::error_span`).
+pub(crate) fn compile_error(
+    r: i32,
+    state: &CompileState,
+    pattern: &[u8],
+) -> crate::error::RegexError {
+    crate::error::RegexError::with_location(r, pattern, state.error_span, state.error_name)
+}
+
+/// Validate options and build an empty regex - mirrors C's onig_reg_init().
+pub(crate) fn onig_reg_init(
+    option: OnigOptionType,
+    case_fold_flag: OnigCaseFoldType,
+    enc: OnigEncoding,
+    syntax: &OnigSyntaxType,
 ) -> Result<RegexType, crate::error::RegexError> {
     // Validate options
     if option.intersects(ONIG_OPTION_DONT_CAPTURE_GROUP)
@@ -7642,15 +8516,24 @@ pub fn onig_new(
         effective_option |= syn.options;
     }
 
+    // Without a text segment option, use the global default.
+    // Extended grapheme clusters need no option bit.
+    if !effective_option.intersects(
+        ONIG_OPTION_TEXT_SEGMENT_EXTENDED_GRAPHEME_CLUSTER | ONIG_OPTION_TEXT_SEGMENT_WORD,
+    ) && onig_get_default_text_segment() == OnigTextSegment::Word
+    {
+        effective_option |= ONIG_OPTION_TEXT_SEGMENT_WORD;
+    }
+
     // Case fold flag setup
-    let mut case_fold_flag = ONIGENC_CASE_FOLD_MIN;
+    let mut case_fold_flag = case_fold_flag;
     if effective_option.intersects(ONIG_OPTION_IGNORECASE_IS_ASCII) {
         case_fold_flag &=
             !(INTERNAL_ONIGENC_CASE_FOLD_MULTI_CHAR | ONIGENC_CASE_FOLD_TURKISH_AZERI);
         case_fold_flag |= ONIGENC_CASE_FOLD_ASCII_ONLY;
     }
 
-    let mut reg = RegexType {
+    Ok(RegexType {
         ops: Vec::new(),
         string_pool: Vec::new(),
         num_mem: 0,
@@ -7664,9 +8547,14 @@ pub fn onig_new(
         repeat_range: Vec::new(),
         enc,
         options: effective_option,
-        syntax: syntax as *const OnigSyntaxType,
+        syntax: syntax.clone(),
         case_fold_flag,
         name_table: None,
+        newline: if option.intersects(ONIG_OPTION_NEWLINE_CRLF) {
+            OnigNewline::Crlf
+        } else {
+            OnigNewline::Lf
+        },
         optimize: OptimizeType::None,
         threshold_len: 0,
         anchor: 0,
@@ -7674,23 +8562,644 @@ pub fn onig_new(
         anc_dist_max: 0,
         sub_anchor: 0,
         exact: Vec::new(),
+        exact_finder: None,
         map: [0u8; CHAR_MAP_SIZE],
         map_offset: 0,
         map_bytes: [0u8; 3],
         map_byte_count: 0,
         dist_min: 0,
         dist_max: 0,
+        req_exact: Vec::new(),
+        req_dist_min: 0,
+        req_dist_max: 0,
+        match_len_min: 0,
+        match_len_max: INFINITE_LEN,
+        group_parents: Vec::new(),
+        shift_or: None,
+        small_match: false,
+        #[cfg(feature = "unstable-opcodes")]
+        opcodes: Default::default(),
+        #[cfg(feature = "exec-stats")]
+        exec_stats: Default::default(),
         called_addrs: vec![],
         unset_call_addrs: vec![],
         extp: None,
+    })
+}
+
+// ============================================================================
+// Debug output (ONIG_DEBUG_COMPILE / ONIG_DEBUG_PARSE)
+// ============================================================================
+//
+// C writes these dumps to stderr when built with the debug macros. The port
+// returns them as strings so they are available at runtime. Node addresses
+// (`%p` in C) are omitted to keep the output stable.
+
+pub(crate) fn op2name(opcode: OpCode) -> &'static str {
+    match opcode {
+        OpCode::Finish => "finish",
+        OpCode::End => "end",
+        OpCode::Str1 => "str_1",
+        OpCode::Str2 => "str_2",
+        OpCode::Str3 => "str_3",
+        OpCode::Str4 => "str_4",
+        OpCode::Str5 => "str_5",
+        OpCode::StrN => "str_n",
+        OpCode::StrMb2n1 => "str_mb2n1",
+        OpCode::StrMb2n2 => "str_mb2n2",
+        OpCode::StrMb2n3 => "str_mb2n3",
+        OpCode::StrMb2n => "str_mb2n",
+        OpCode::StrMb3n => "str_mb3n",
+        OpCode::StrMbn => "str_mbn",
+        OpCode::CClass => "cclass",
+        OpCode::CClassMb => "cclass_mb",
+        OpCode::CClassMix => "cclass_mix",
+        OpCode::CClassNot => "cclass_not",
+        OpCode::CClassMbNot => "cclass_mb_not",
+        OpCode::CClassMixNot => "cclass_mix_not",
+        OpCode::CClassSmall => "cclass_small",
+        OpCode::CClassSmallNot => "cclass_small_not",
+        OpCode::AnyChar => "anychar",
+        OpCode::AnyCharMl => "anychar_ml",
+        OpCode::AnyCharStar => "anychar*",
+        OpCode::AnyCharMlStar => "anychar_ml*",
+        OpCode::AnyCharStarPeekNext => "anychar*peek_next",
+        OpCode::AnyCharMlStarPeekNext => "anychar_ml*peek_next",
+        OpCode::Word => "word",
+        OpCode::WordAscii => "word_ascii",
+        OpCode::NoWord => "not_word",
+        OpCode::NoWordAscii => "not_word_ascii",
+        OpCode::WordBoundary => "word_boundary",
+        OpCode::NoWordBoundary => "not_word_boundary",
+        OpCode::WordBegin => "word_begin",
+        OpCode::WordEnd => "word_end",
+        OpCode::TextSegmentBoundary => "text_segment_boundary",
+        OpCode::BeginBuf => "begin_buf",
+        OpCode::EndBuf => "end_buf",
+        OpCode::BeginLine => "begin_line",
+        OpCode::EndLine => "end_line",
+        OpCode::SemiEndBuf => "semi_end_buf",
+        OpCode::CheckPosition => "check_position",
+        OpCode::BackRef1 => "backref1",
+        OpCode::BackRef2 => "backref2",
+        OpCode::BackRefN => "backref_n",
+        OpCode::BackRefNIc => "backref_n_ic",
+        OpCode::BackRefMulti => "backref_multi",
+        OpCode::BackRefMultiIc => "backref_multi_ic",
+        OpCode::BackRefWithLevel => "backref_with_level",
+        OpCode::BackRefWithLevelIc => "backref_with_level_ic",
+        OpCode::BackRefCheck => "backref_check",
+        OpCode::BackRefCheckWithLevel => "backref_check_with_level",
+        OpCode::MemStart => "mem_start",
+        OpCode::MemStartPush => "mem_start_push",
+        OpCode::MemEndPush => "mem_end_push",
+        OpCode::MemEndPushRec => "mem_end_push_rec",
+        OpCode::MemEnd => "mem_end",
+        OpCode::MemEndRec => "mem_end_rec",
+        OpCode::Fail => "fail",
+        OpCode::Jump => "jump",
+        OpCode::Push => "push",
+        OpCode::PushSuper => "push_super",
+        OpCode::Pop => "pop",
+        OpCode::PopToMark => "pop_to_mark",
+        OpCode::PushOrJumpExact1 => "push_or_jump_e1",
+        OpCode::PushIfPeekNext => "push_if_peek_next",
+        OpCode::Repeat => "repeat",
+        OpCode::RepeatNg => "repeat_ng",
+        OpCode::RepeatInc => "repeat_inc",
+        OpCode::RepeatIncNg => "repeat_inc_ng",
+        OpCode::EmptyCheckStart => "empty_check_start",
+        OpCode::EmptyCheckEnd => "empty_check_end",
+        OpCode::EmptyCheckEndMemst => "empty_check_end_memst",
+        OpCode::EmptyCheckEndMemstPush => "empty_check_end_memst_push",
+        OpCode::EmptyCheckStartSimple => "empty_check_start_simple",
+        OpCode::EmptyCheckEndSimple => "empty_check_end_simple",
+        OpCode::Move => "move",
+        OpCode::StepBackStart => "step_back_start",
+        OpCode::StepBackNext => "step_back_next",
+        OpCode::CutToMark => "cut_to_mark",
+        OpCode::Mark => "mark",
+        OpCode::SaveVal => "save_val",
+        OpCode::UpdateVar => "update_var",
+        OpCode::Call => "call",
+        OpCode::Return => "return",
+        OpCode::CalloutContents => "callout_contents",
+        OpCode::CalloutName => "callout_name",
+        OpCode::Extension => "extension",
+    }
+}
+
+/// Append `s` after a ':', printable ASCII as-is and other bytes as `\xHH`.
+fn p_string(out: &mut String, s: &[u8]) {
+    out.push(':');
+    for &b in s {
+        if (0x20..0x7f).contains(&b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("\\x{:02x}", b));
+        }
+    }
+}
+
+fn p_rel_addr(out: &mut String, rel_addr: RelAddrType, curr: usize) {
+    let abs_addr = curr as i32 + rel_addr;
+    out.push_str(&format!(":{:+} => {}", rel_addr, abs_addr));
+}
+
+fn p_mems(out: &mut String, ns: &[MemNumType]) {
+    out.push(' ');
+    let mems: Vec<String> = ns.iter().map(|n| n.to_string()).collect();
+    out.push_str(&mems.join(", "));
+}
+
+fn print_compiled_byte_code(out: &mut String, reg: &RegexType, curr: usize) {
+    let op = &reg.ops[curr];
+    out.push_str(op2name(op.opcode));
+    match &op.payload {
+        OperationPayload::None | OperationPayload::Return => {}
+        OperationPayload::Exact { s } => {
+            let len = match op.opcode {
+                OpCode::Str1 => 1,
+                OpCode::Str2 => 2,
+                OpCode::Str3 => 3,
+                OpCode::Str4 => 4,
+                _ => 5,
+            };
+            p_string(out, &s[..len]);
+        }
+        OperationPayload::ExactN { s, n } => {
+            out.push_str(&format!(":{}", n));
+            p_string(out, s);
+        }
+        OperationPayload::ExactLenN { s, n, len } => {
+            out.push_str(&format!(":{}:{}", len, n));
+            p_string(out, s);
+        }
+        OperationPayload::CClass { bsp } => {
+            let n = (0..SINGLE_BYTE_SIZE).filter(|&i| bitset_at(bsp, i)).count();
+            out.push_str(&format!(":{}", n));
+        }
+        OperationPayload::CClassMb { mb } => {
+            out.push_str(&format!(":{}", mb.first().copied().unwrap_or(0)));
+        }
+        OperationPayload::CClassMix { mb, bsp } => {
+            let n = (0..SINGLE_BYTE_SIZE).filter(|&i| bitset_at(bsp, i)).count();
+            out.push_str(&format!(":{}:{}", n, mb.first().copied().unwrap_or(0)));
+        }
+        OperationPayload::CClassSmall { bsp, codes } => {
+            let n = (0..SINGLE_BYTE_SIZE).filter(|&i| bitset_at(bsp, i)).count();
+            let m = codes.iter().map(|w| w.count_ones()).sum::<u32>();
+            out.push_str(&format!(":{}:{}", n, m));
+        }
+        OperationPayload::AnyCharStarPeekNext { c } => p_string(out, &[*c]),
+        OperationPayload::WordBoundary { mode } => out.push_str(&format!(":{}", mode)),
+        OperationPayload::TextSegmentBoundary { boundary_type, not } => {
+            let t = match boundary_type {
+                TextSegmentBoundaryType::ExtendedGraphemeCluster => "EGCB",
+                TextSegmentBoundaryType::Word => "WB",
+            };
+            out.push_str(&format!(":{}:{}", t, *not as i32));
+        }
+        OperationPayload::CheckPosition { check_type } => {
+            let t = match check_type {
+                CheckPositionType::SearchStart => "search-start",
+                CheckPositionType::CurrentRightRange => "current-right-range",
+            };
+            out.push_str(&format!(":{}", t));
+        }
+        OperationPayload::BackRefN { n1 } => out.push_str(&format!(":{}", n1)),
+        OperationPayload::BackRefGeneral { ns, nest_level, .. } => {
+            if matches!(
+                op.opcode,
+                OpCode::BackRefWithLevel
+                    | OpCode::BackRefWithLevelIc
+                    | OpCode::BackRefCheckWithLevel
+            ) {
+                out.push_str(&format!(":{}", nest_level));
+            }
+            p_mems(out, ns);
+        }
+        OperationPayload::MemoryStart { num } | OperationPayload::MemoryEnd { num } => {
+            out.push_str(&format!(":{}", num));
+        }
+        OperationPayload::Jump { addr } | OperationPayload::Push { addr } => {
+            p_rel_addr(out, *addr, curr);
+        }
+        OperationPayload::PushOrJumpExact1 { addr, c }
+        | OperationPayload::PushIfPeekNext { addr, c } => {
+            p_rel_addr(out, *addr, curr);
+            p_string(out, &[*c]);
+        }
+        OperationPayload::PopToMark { id } => out.push_str(&format!(":{}", id)),
+        OperationPayload::Repeat { id, addr } => {
+            out.push_str(&format!(":{}", id));
+            p_rel_addr(out, *addr, curr);
+        }
+        OperationPayload::RepeatInc { id } => out.push_str(&format!(":{}", id)),
+        OperationPayload::EmptyCheckStart { mem } => out.push_str(&format!(":{}", mem)),
+        OperationPayload::EmptyCheckEnd {
+            mem,
+            empty_status_mem,
+        } => {
+            out.push_str(&format!(":{}", mem));
+            if op.opcode != OpCode::EmptyCheckEnd {
+                out.push_str(&format!(":0x{:x}", empty_status_mem));
+            }
+        }
+        OperationPayload::Move { n } => out.push_str(&format!(":{}", n)),
+        OperationPayload::StepBackStart {
+            initial,
+            remaining,
+            addr,
+        } => {
+            out.push_str(&format!(":{}:{}", initial, remaining));
+            p_rel_addr(out, *addr, curr);
+        }
+        OperationPayload::CutToMark { id, restore_pos } => {
+            out.push_str(&format!(":{}:{}", id, *restore_pos as i32));
+        }
+        OperationPayload::Mark { id, save_pos } => {
+            out.push_str(&format!(":{}:{}", id, *save_pos as i32));
+        }
+        OperationPayload::SaveVal { save_type, id } => {
+            out.push_str(&format!(":{}:{}", *save_type as i32, id));
+        }
+        OperationPayload::UpdateVar {
+            var_type,
+            id,
+            clear,
+        } => {
+            out.push_str(&format!(":{}:{}", *var_type as i32, id));
+            if *clear {
+                out.push_str(":clear");
+            }
+        }
+        OperationPayload::Call { addr } => out.push_str(&format!(":{}", addr)),
+        OperationPayload::CalloutContents { num } => out.push_str(&format!(":{}", num)),
+        OperationPayload::CalloutName { num, id } => {
+            out.push_str(&format!(":{}:{}", id, num));
+        }
+        OperationPayload::Extension { num } => out.push_str(&format!(":{}", num)),
+    }
+}
+
+/// Port of print_compiled_byte_code_list().
+pub(crate) fn print_compiled_byte_code_list(reg: &RegexType) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "push_mem_start: 0x{:x}, push_mem_end: 0x{:x}\n",
+        reg.push_mem_start, reg.push_mem_end
+    ));
+    out.push_str(&format!("code-length: {}\n", reg.ops.len()));
+    for addr in 0..reg.ops.len() {
+        out.push_str(&format!("{:4}: ", addr));
+        print_compiled_byte_code(&mut out, reg, addr);
+        out.push('\n');
+    }
+    out
+}
+
+fn print_distance_range(out: &mut String, a: OnigLen, b: OnigLen) {
+    let fmt = |d: OnigLen| {
+        if d == INFINITE_LEN {
+            "inf".to_string()
+        } else {
+            d.to_string()
+        }
+    };
+    out.push_str(&format!("({}, {})", fmt(a), fmt(b)));
+}
+
+fn print_anchor(out: &mut String, anchor: i32) {
+    const NAMES: [(i32, &str); 8] = [
+        (ANCR_BEGIN_BUF, "begin-buf"),
+        (ANCR_BEGIN_LINE, "begin-line"),
+        (ANCR_BEGIN_POSITION, "begin-pos"),
+        (ANCR_END_BUF, "end-buf"),
+        (ANCR_SEMI_END_BUF, "semi-end-buf"),
+        (ANCR_END_LINE, "end-line"),
+        (ANCR_ANYCHAR_INF, "anychar-inf"),
+        (ANCR_ANYCHAR_INF_ML, "anychar-inf-ml"),
+    ];
+    let names: Vec<&str> = NAMES
+        .iter()
+        .filter(|(bit, _)| anchor & bit != 0)
+        .map(|&(_, name)| name)
+        .collect();
+    out.push_str(&format!("[{}]", names.join(", ")));
+}
+
+/// Port of print_optimize_info().
+pub(crate) fn print_optimize_info(reg: &RegexType) -> String {
+    let mut out = String::new();
+    let name = match reg.optimize {
+        OptimizeType::None => "NONE",
+        OptimizeType::Str => "STR",
+        OptimizeType::StrFast => "STR_FAST",
+        OptimizeType::StrFastStepForward => "STR_FAST_STEP_FORWARD",
+        OptimizeType::Map => "MAP",
     };
+    out.push_str(&format!("optimize: {}\n", name));
+    out.push_str("  anchor: ");
+    print_anchor(&mut out, reg.anchor);
+    if reg.anchor & (ANCR_END_BUF | ANCR_SEMI_END_BUF) != 0 {
+        print_distance_range(&mut out, reg.anc_dist_min, reg.anc_dist_max);
+    }
+    out.push('\n');
+    if reg.optimize != OptimizeType::None {
+        out.push_str("  sub anchor: ");
+        print_anchor(&mut out, reg.sub_anchor);
+        out.push('\n');
+    }
+    out.push('\n');
+
+    if !reg.exact.is_empty() {
+        out.push_str("exact: [");
+        for &b in &reg.exact {
+            if (0x20..0x7f).contains(&b) {
+                out.push(b as char);
+            } else {
+                out.push_str(&format!("\\x{:02x}", b));
+            }
+        }
+        out.push_str(&format!("]: length: {}, ", reg.exact.len()));
+        out.push_str("dist: ");
+        print_distance_range(&mut out, reg.dist_min, reg.dist_max);
+        out.push('\n');
+    } else if reg.optimize == OptimizeType::Map {
+        let set: Vec<usize> = (0..CHAR_MAP_SIZE).filter(|&i| reg.map[i] != 0).collect();
+        out.push_str(&format!("map: n={}, dist: ", set.len()));
+        print_distance_range(&mut out, reg.dist_min, reg.dist_max);
+        out.push('\n');
+        if !set.is_empty() {
+            let chars: Vec<String> = set
+                .iter()
+                .map(|&i| {
+                    if (0x21..0x7f).contains(&i) {
+                        (i as u8 as char).to_string()
+                    } else {
+                        i.to_string()
+                    }
+                })
+                .collect();
+            out.push_str(&format!("[{}]\n", chars.join(", ")));
+        }
+    }
+    out
+}
+
+fn print_indent_tree(out: &mut String, node: &Node, indent: usize) {
+    const ADD: usize = 3;
+    out.push_str(&" ".repeat(indent));
+
+    match &node.inner {
+        NodeInner::List(_) | NodeInner::Alt(_) => {
+            let is_list = matches!(node.inner, NodeInner::List(_));
+            out.push_str(if is_list { "<list>\n" } else { "<alt>\n" });
+            let mut cur = Some(node);
+            while let Some(n) = cur {
+                match &n.inner {
+                    NodeInner::List(cons) | NodeInner::Alt(cons) => {
+                        print_indent_tree(out, &cons.car, indent + ADD);
+                        cur = cons.cdr.as_deref();
+                    }
+                    _ => break,
+                }
+            }
+            return;
+        }
+        NodeInner::String(sn) => {
+            let mode = if sn.is_crude() {
+                "-crude"
+            } else if node.has_status(ND_ST_IGNORECASE) {
+                "-ignorecase"
+            } else {
+                ""
+            };
+            let kind = if sn.s.is_empty() {
+                "empty-string"
+            } else {
+                "string"
+            };
+            out.push_str(&format!("<{}{}>", kind, mode));
+            for &b in &sn.s {
+                if (0x20..0x7f).contains(&b) {
+                    out.push(b as char);
+                } else {
+                    out.push_str(&format!(" 0x{:02x}", b));
+                }
+            }
+        }
+        NodeInner::CClass(cc) => {
+            out.push_str("<cclass>");
+            if cc.is_not() {
+                out.push_str(" not");
+            }
+            if let Some(ref mbuf) = cc.mbuf {
+                out.push_str(&format!(" mbuf({})", mbuf.used()));
+            }
+        }
+        NodeInner::CType(ct) => {
+            out.push_str("<ctype> ");
+            if ct.ctype == CTYPE_ANYCHAR {
+                out.push_str("anychar");
+            } else {
+                out.push_str(if ct.not { "not word" } else { "word" });
+                if ct.ascii_mode {
+                    out.push_str(" (ascii)");
+                }
+            }
+        }
+        NodeInner::Anchor(an) => {
+            out.push_str("<anchor> ");
+            let name = match an.anchor_type {
+                ANCR_BEGIN_BUF => "begin buf",
+                ANCR_END_BUF => "end buf",
+                ANCR_BEGIN_LINE => "begin line",
+                ANCR_END_LINE => "end line",
+                ANCR_SEMI_END_BUF => "semi end buf",
+                ANCR_BEGIN_POSITION => "begin position",
+                ANCR_WORD_BOUNDARY => "word boundary",
+                ANCR_NO_WORD_BOUNDARY => "not word boundary",
+                ANCR_WORD_BEGIN => "word begin",
+                ANCR_WORD_END => "word end",
+                ANCR_TEXT_SEGMENT_BOUNDARY => "text-segment boundary",
+                ANCR_NO_TEXT_SEGMENT_BOUNDARY => "no text-segment boundary",
+                ANCR_PREC_READ => "prec read",
+                ANCR_PREC_READ_NOT => "prec read not",
+                ANCR_LOOK_BEHIND => "look behind",
+                ANCR_LOOK_BEHIND_NOT => "look behind not",
+                _ => "undefined anchor type",
+            };
+            out.push_str(name);
+            if let Some(ref body) = an.body {
+                out.push('\n');
+                print_indent_tree(out, body, indent + ADD);
+                return;
+            }
+        }
+        NodeInner::BackRef(br) => {
+            let checker = if node.has_status(ND_ST_CHECKER) {
+                "-checker"
+            } else {
+                ""
+            };
+            out.push_str(&format!("<backref{}>", checker));
+            p_mems(out, br.back_refs());
+            if node.has_status(ND_ST_NEST_LEVEL) {
+                out.push_str(&format!(", level: {}", br.nest_level));
+            }
+        }
+        NodeInner::Call(cn) => {
+            out.push_str(&format!("<call> num: {}, name", cn.called_gnum));
+            p_string(out, &cn.name);
+            if node.has_status(ND_ST_RECURSION) {
+                out.push_str(", recursion");
+            }
+            if node.has_status(ND_ST_INPEEK) {
+                out.push_str(", in-peek");
+            }
+            if node.has_status(ND_ST_IN_REAL_REPEAT) {
+                out.push_str(", in-real-repeat");
+            }
+        }
+        NodeInner::Quant(qn) => {
+            let emptiness = match qn.emptiness {
+                BodyEmptyType::NotEmpty => "",
+                BodyEmptyType::MayBeEmpty => " empty",
+                BodyEmptyType::MayBeEmptyMem => " empty_mem",
+                BodyEmptyType::MayBeEmptyRec => " empty_rec",
+            };
+            out.push_str(&format!(
+                "<quantifier>{{{},{}}}{}{}{}",
+                qn.lower,
+                qn.upper,
+                if qn.greedy { "" } else { "?" },
+                if qn.include_referred == 0 {
+                    ""
+                } else {
+                    " referred"
+                },
+                emptiness
+            ));
+            if node.has_status(ND_ST_INPEEK) {
+                out.push_str(", in-peek");
+            }
+            out.push('\n');
+            if let Some(ref body) = qn.body {
+                print_indent_tree(out, body, indent + ADD);
+            }
+            return;
+        }
+        NodeInner::Bag(bn) => {
+            out.push_str("<bag> ");
+            if let BagData::IfElse {
+                then_node,
+                else_node,
+            } = &bn.bag_data
+            {
+                out.push_str("if-else\n");
+                if let Some(ref body) = bn.body {
+                    print_indent_tree(out, body, indent + ADD);
+                }
+                for (branch, label) in [(then_node, "THEN"), (else_node, "ELSE")] {
+                    match branch {
+                        Some(n) => print_indent_tree(out, n, indent + ADD),
+                        None => {
+                            out.push_str(&" ".repeat(indent + ADD));
+                            out.push_str(&format!("{} empty\n", label));
+                        }
+                    }
+                }
+                return;
+            }
+            match &bn.bag_data {
+                BagData::Option { options } => {
+                    out.push_str(&format!("option:{}", options.bits()));
+                }
+                BagData::Memory {
+                    regnum,
+                    called_state,
+                    ..
+                } => {
+                    out.push_str(&format!("memory:{}", regnum));
+                    if node.has_status(ND_ST_CALLED) {
+                        out.push_str(", called");
+                        if node.has_status(ND_ST_RECURSION) {
+                            out.push_str(", recursion");
+                        }
+                    } else if node.has_status(ND_ST_INPEEK) {
+                        out.push_str(", in-peek");
+                    }
+                    if node.has_status(ND_ST_FIXED_ADDR) {
+                        out.push_str(", fixed-addr");
+                    }
+                    if (called_state & IN_PEEK) != 0 {
+                        out.push_str(", in-peek");
+                    }
+                }
+                BagData::StopBacktrack => out.push_str("stop-bt"),
+                BagData::IfElse { .. } => {}
+            }
+            out.push('\n');
+            if let Some(ref body) = bn.body {
+                print_indent_tree(out, body, indent + ADD);
+            }
+            return;
+        }
+        NodeInner::Gimmick(gn) => {
+            out.push_str("<gimmick> ");
+            match gn.gimmick_type {
+                GimmickType::Fail => out.push_str("fail"),
+                GimmickType::Save => {
+                    out.push_str(&format!("save:{}:{}", gn.detail_type, gn.id));
+                }
+                GimmickType::UpdateVar => {
+                    out.push_str(&format!("update_var:{}:{}", gn.detail_type, gn.id));
+                }
+                GimmickType::Callout => {
+                    if gn.detail_type == OnigCalloutOf::Name as i32 {
+                        out.push_str(&format!("callout:name:{}:{}", gn.id, gn.num));
+                    } else {
+                        out.push_str(&format!("callout:contents:{}", gn.num));
+                    }
+                }
+                GimmickType::Extension => out.push_str(&format!("extension:{}", gn.num)),
+            }
+        }
+    }
+    out.push('\n');
+}
+
+/// Port of print_tree().
+pub(crate) fn print_tree(node: &Node) -> String {
+    let mut out = String::new();
+    print_indent_tree(&mut out, node, 0);
+    out
+}
 
-    let r = onig_compile(&mut reg, pattern);
+/// Re-parse `pattern` with the settings `reg` was compiled with and return
+/// the tree as onig_compile() leaves it before code generation. `literal`
+/// selects the onig_compile_literal() tree instead of the parser.
+/// Not in the C original: runtime form of ONIG_DEBUG_PARSE.
+pub(crate) fn onig_debug_tree(
+    reg: &RegexType,
+    pattern: &[u8],
+    literal: bool,
+) -> Result<String, crate::error::RegexError> {
+    let mut scratch = onig_reg_init(reg.options, reg.case_fold_flag, reg.enc, &reg.syntax)?;
+    scratch.newline = reg.newline;
+    let mut env = new_parse_env(&mut scratch);
+    let mut root = if literal {
+        literal_tree(&mut scratch, &mut env, pattern)?
+    } else {
+        crate::regparse::onig_parse_tree(pattern, &mut scratch, &mut env)?
+    };
+    let r = tune_parsed_tree(&mut root, &mut scratch, &mut env);
     if r != 0 {
         return Err(r.into());
     }
-
-    Ok(reg)
+    Ok(print_tree(&root))
 }
 
 // ============================================================================
@@ -7718,9 +9227,10 @@ mod tests {
             repeat_range: Vec::new(),
             enc: &crate::encodings::utf8::ONIG_ENCODING_UTF8,
             options: ONIG_OPTION_NONE,
-            syntax: &OnigSyntaxOniguruma,
+            syntax: OnigSyntaxOniguruma.clone(),
             case_fold_flag: ONIGENC_CASE_FOLD_MIN,
             name_table: None,
+            newline: OnigNewline::Lf,
             optimize: OptimizeType::None,
             threshold_len: 0,
             anchor: 0,
@@ -7728,12 +9238,25 @@ mod tests {
             anc_dist_max: 0,
             sub_anchor: 0,
             exact: Vec::new(),
+            exact_finder: None,
             map: [0u8; CHAR_MAP_SIZE],
             map_offset: 0,
             map_bytes: [0u8; 3],
             map_byte_count: 0,
             dist_min: 0,
             dist_max: 0,
+            req_exact: Vec::new(),
+            req_dist_min: 0,
+            req_dist_max: 0,
+            match_len_min: 0,
+            match_len_max: INFINITE_LEN,
+            group_parents: Vec::new(),
+            shift_or: None,
+            small_match: false,
+            #[cfg(feature = "unstable-opcodes")]
+            opcodes: Default::default(),
+            #[cfg(feature = "exec-stats")]
+            exec_stats: Default::default(),
             called_addrs: vec![],
             unset_call_addrs: vec![],
             extp: None,
@@ -7742,15 +9265,15 @@ mod tests {
             options: OnigOptionType::empty(),
             case_fold_flag: 0,
             enc: &crate::encodings::utf8::ONIG_ENCODING_UTF8,
-            syntax: &OnigSyntaxOniguruma,
+            syntax: OnigSyntaxOniguruma.clone(),
             cap_history: 0,
             backtrack_mem: 0,
             backrefed_mem: 0,
-            pattern: std::ptr::null(),
-            pattern_end: std::ptr::null(),
-            error: std::ptr::null(),
-            error_end: std::ptr::null(),
-            reg: std::ptr::null_mut(),
+            pattern: core::ptr::null(),
+            pattern_end: core::ptr::null(),
+            error: core::ptr::null(),
+            error_end: core::ptr::null(),
+            reg: core::ptr::null_mut(),
             num_call: 0,
             num_mem: 0,
             num_named: 0,
@@ -7765,6 +9288,12 @@ mod tests {
             unset_addr_list: None,
             parse_depth: 0,
             flags: 0,
+            token_start: Default::default(),
+            error_name: Default::default(),
+            cc_scratch: Default::default(),
+            cc_buf_hint: 0,
+            cc_redundant: None,
+            compile: Default::default(),
         };
         (reg, env)
     }
@@ -7791,10 +9320,14 @@ mod tests {
     #[test]
     fn compile_alternation() {
         let reg = parse_and_compile(b"a|b").unwrap();
-        // Should have PUSH + "a" + JUMP + "b" + END
+        // Should have PUSH_OR_JUMP_EXACT1 + "a" + JUMP + "b" + END
         assert!(reg.ops.len() >= 4);
-        assert_eq!(reg.ops[0].opcode, OpCode::Push);
+        assert_eq!(reg.ops[0].opcode, OpCode::PushOrJumpExact1);
         assert_eq!(reg.ops.last().unwrap().opcode, OpCode::End);
+
+        // A branch without a leading literal byte needs a plain PUSH
+        let reg = parse_and_compile(b"[ab]|c").unwrap();
+        assert_eq!(reg.ops[0].opcode, OpCode::Push);
     }
 
     #[test]
@@ -7840,6 +9373,44 @@ mod tests {
         assert!(has_cclass, "expected CClass for [abc]");
     }
 
+    #[test]
+    fn compile_simple_empty_check() {
+        let has = |pattern: &[u8], opcode: OpCode| {
+            let reg = onig_new(
+                pattern,
+                ONIG_OPTION_NONE,
+                &crate::encodings::utf8::ONIG_ENCODING_UTF8,
+                &crate::regsyntax::OnigSyntaxOniguruma,
+            )
+            .unwrap_or_else(|e| panic!("{:?}: {}", pattern, e.code()));
+            reg.ops.iter().any(|op| op.opcode == opcode)
+        };
+        for pattern in [&b"(?>a*)*b"[..], b"(?:a*+)+b", b"(?i:(?:b?+c*+)*)d"] {
+            assert!(has(pattern, OpCode::EmptyCheckStartSimple), "{:?}", pattern);
+            assert!(has(pattern, OpCode::EmptyCheckEndSimple), "{:?}", pattern);
+            assert!(!has(pattern, OpCode::EmptyCheckStart), "{:?}", pattern);
+        }
+        // Alternatives and captures in the body keep the stack-based check.
+        for pattern in [&b"(?:a|)*b"[..], b"(?:a?)*b", b"(a*)*b", b"(?>(a*))*b"] {
+            assert!(
+                !has(pattern, OpCode::EmptyCheckStartSimple),
+                "{:?}",
+                pattern
+            );
+        }
+    }
+
+    #[test]
+    fn compile_small_char_class() {
+        let reg = parse_and_compile("[-a-zA-Z_é-ÿ]".as_bytes()).unwrap();
+        assert_eq!(reg.ops[0].opcode, OpCode::CClassSmall);
+        let reg = parse_and_compile("[^ßΩ]".as_bytes()).unwrap();
+        assert_eq!(reg.ops[0].opcode, OpCode::CClassSmallNot);
+        // A range reaching U+0800 keeps the code-range table.
+        let reg = parse_and_compile("[a-z\u{7ff}-\u{800}]".as_bytes()).unwrap();
+        assert_eq!(reg.ops[0].opcode, OpCode::CClassMix);
+    }
+
     #[test]
     fn compile_anchor_begin() {
         let reg = parse_and_compile(b"^a").unwrap();
@@ -7991,6 +9562,51 @@ mod tests {
         assert_eq!(root.node_type(), NodeType::List);
     }
 
+    /// Parse `pattern`, reduce strings and factor alternations.
+    fn factored_tree(pattern: &[u8]) -> String {
+        let (mut reg, mut env) = make_test_context();
+        let mut root = regparse::onig_parse_tree(pattern, &mut reg, &mut env).unwrap();
+        assert_eq!(reduce_string_list(&mut root, env.enc), 0);
+        factor_alternatives(&mut root, env.enc, true);
+        print_tree(&root)
+    }
+
+    #[test]
+    fn factor_alternatives_common_prefix() {
+        assert_eq!(
+            factored_tree(b"foobar|foobaz|qux|quux"),
+            "<alt>\n   <list>\n      <string>fooba\n      <alt>\n         <string>r\n         \
+             <string>z\n   <list>\n      <string>qu\n      <alt>\n         <string>x\n         \
+             <string>ux\n"
+        );
+        // Splits on character boundaries only.
+        assert!(factored_tree("éa|éb".as_bytes()).starts_with("<list>\n   <string> 0xc3 0xa9\n"));
+        assert!(factored_tree("\u{e9}|\u{e8}".as_bytes()).starts_with("<alt>"));
+    }
+
+    #[test]
+    fn factor_alternatives_keeps_order_and_exceptions() {
+        // Only adjacent branches are factored.
+        assert!(factored_tree(b"ab|x|ac").starts_with("<alt>\n   <string>ab\n"));
+        // Case-insensitive strings and look-behind bodies are left alone.
+        assert!(!factored_tree(b"(?i:ab|ac)").contains("<list>"));
+        assert!(!factored_tree(b"(?<=ab|ac)x").contains("<string>a\n"));
+    }
+
+    #[test]
+    fn factor_alternatives_drops_repeated_literals() {
+        assert_eq!(
+            factored_tree(b"foo|bar|foo"),
+            "<alt>\n   <string>foo\n   <string>bar\n"
+        );
+        assert_eq!(factored_tree(b"(?:a|a)"), "<string>a\n");
+        // With callouts the retry is observable, so repeats are kept.
+        let (mut reg, mut env) = make_test_context();
+        let mut root = regparse::onig_parse_tree(b"a|a", &mut reg, &mut env).unwrap();
+        factor_alternatives(&mut root, env.enc, false);
+        assert!(print_tree(&root).contains("<alt>"));
+    }
+
     #[test]
     fn test_never_ending_recursion_direct() {
         let mut reg = make_test_context().0;
@@ -8004,4 +9620,55 @@ mod tests {
         let r = onig_compile(&mut reg, b"(()(?(2)\\g<1>))");
         assert_eq!(r, ONIGERR_NEVER_ENDING_RECURSION);
     }
+
+    #[test]
+    fn backref_past_last_group() {
+        // `\8` and `\9` parse as backrefs with any number of groups
+        for pattern in [&b"\\8"[..], b"(a)\\2", b"(?:a|)*\\9$"] {
+            let mut reg = make_test_context().0;
+            assert_eq!(onig_compile(&mut reg, pattern), ONIGERR_INVALID_BACKREF);
+        }
+        let mut reg = make_test_context().0;
+        assert_eq!(onig_compile(&mut reg, b"(a)\\1"), 0);
+    }
+
+    #[test]
+    fn new_deluxe_transcodes_pattern() {
+        use crate::encodings::{ONIG_ENCODING_ASCII, ONIG_ENCODING_UTF8};
+        use crate::regexec::onig_search;
+        let ci = |pattern_enc, target_enc| OnigCompileInfo {
+            num_of_elements: 5,
+            pattern_enc,
+            target_enc,
+            syntax: &crate::regsyntax::OnigSyntaxOniguruma,
+            option: ONIG_OPTION_IGNORECASE,
+            case_fold_flag: ONIGENC_CASE_FOLD_MIN,
+        };
+        let search = |reg: &RegexType, text: &[u8]| {
+            onig_search(reg, text, text.len(), 0, text.len(), None, ONIG_OPTION_NONE).0
+        };
+
+        // UTF-8 pattern, single-byte target text
+        let utf8_to_ascii = ci(&ONIG_ENCODING_UTF8, &ONIG_ENCODING_ASCII);
+        let reg = onig_new_deluxe("caf(é|e)".as_bytes(), &utf8_to_ascii).unwrap();
+        assert_eq!(search(&reg, b"a CAF\xe9 b"), 2);
+        assert_eq!(search(&reg, "café".as_bytes()), ONIG_MISMATCH);
+        let err = onig_new_deluxe("ж".as_bytes(), &utf8_to_ascii)
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(err.code(), ONIGERR_INVALID_CODE_POINT_VALUE);
+
+        // Single-byte pattern, UTF-8 target text
+        let ascii_to_utf8 = ci(&ONIG_ENCODING_ASCII, &ONIG_ENCODING_UTF8);
+        let reg = onig_new_deluxe(b"\xe9+", &ascii_to_utf8).unwrap();
+        assert_eq!(search(&reg, "xéé".as_bytes()), 1);
+        let err = onig_new_deluxe(b"\xff", &utf8_to_ascii)
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(err.code(), ONIGERR_INVALID_WIDE_CHAR_VALUE);
+
+        // Same encoding: the pattern is compiled as is
+        let reg = onig_new_deluxe(b"a+", &ci(&ONIG_ENCODING_UTF8, &ONIG_ENCODING_UTF8)).unwrap();
+        assert_eq!(search(&reg, b"xAa"), 1);
+    }
 }
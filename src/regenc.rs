@@ -1,7 +1,11 @@
 // regenc.rs - Port of regenc.h + regenc.c
 // Encoding trait (from OnigEncodingType) and shared encoding utility functions.
 
+use alloc::borrow::Cow;
+
 use crate::oniguruma::*;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
 
 // === Encoding type alias ===
 // In C: OnigEncoding = OnigEncodingType*
@@ -126,8 +130,71 @@ pub trait Encoding: Send + Sync {
     fn index(&self) -> i32 {
         0
     }
+
+    // --- Buffer helpers for embedders, built on the methods above ---
+
+    /// Validate `s`, reporting the offset of the first invalid character.
+    fn validate(&self, s: &[u8]) -> Result<(), FirstErrorOffset> {
+        let mut p = 0;
+        while p < s.len() {
+            let len = self.mbc_enc_len(&s[p..]);
+            if p + len > s.len() || !self.is_valid_mbc_string(&s[p..p + len]) {
+                return Err(FirstErrorOffset(p));
+            }
+            p += len;
+        }
+        Ok(())
+    }
+
+    /// Number of characters in `s`. A truncated final character counts as one.
+    fn char_count(&self, s: &[u8]) -> usize {
+        let mut p = 0;
+        let mut n = 0;
+        while p < s.len() {
+            p += self.mbc_enc_len(&s[p..]);
+            n += 1;
+        }
+        n
+    }
+
+    /// Start of the character containing byte `i`, or `s.len()` if `i` is
+    /// past the end (ONIGENC_LEFT_ADJUST_CHAR_HEAD).
+    fn floor_char_boundary(&self, s: &[u8], i: usize) -> usize {
+        if i >= s.len() {
+            return s.len();
+        }
+        self.left_adjust_char_head(0, i, s)
+    }
+
+    /// First character boundary at or after byte `i`, capped at `s.len()`
+    /// (onigenc_get_right_adjust_char_head).
+    fn ceil_char_boundary(&self, s: &[u8], i: usize) -> usize {
+        if i >= s.len() {
+            return s.len();
+        }
+        let head = self.left_adjust_char_head(0, i, s);
+        if head == i {
+            i
+        } else {
+            // A lead byte with missing tails can end before `i`.
+            (head + self.mbc_enc_len(&s[head..])).clamp(i, s.len())
+        }
+    }
 }
 
+/// Byte offset of the first invalid character, returned by
+/// [`Encoding::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FirstErrorOffset(pub usize);
+
+impl core::fmt::Display for FirstErrorOffset {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "invalid byte sequence at offset {}", self.0)
+    }
+}
+
+impl core::error::Error for FirstErrorOffset {}
+
 // === Encoding query helpers ===
 
 #[inline]
@@ -194,6 +261,17 @@ pub fn case_fold_is_not_ascii_only(flag: OnigCaseFoldType) -> bool {
     (flag & ONIGENC_CASE_FOLD_ASCII_ONLY) == 0
 }
 
+/// Whether `flag` folds A-Z with a-z and nothing else: ASCII
+/// only, without the Turkish or multi-char folds that reach past ASCII.
+/// ONIG_OPTION_IGNORECASE_IS_ASCII sets such a flag.
+#[inline]
+pub fn case_fold_is_ascii_letters_only(flag: OnigCaseFoldType) -> bool {
+    flag & (ONIGENC_CASE_FOLD_ASCII_ONLY
+        | ONIGENC_CASE_FOLD_TURKISH_AZERI
+        | INTERNAL_ONIGENC_CASE_FOLD_MULTI_CHAR)
+        == ONIGENC_CASE_FOLD_ASCII_ONLY
+}
+
 // === Ctype bit helpers (from regenc.h) ===
 
 pub const BIT_CTYPE_NEWLINE: u32 = 1 << ONIGENC_CTYPE_NEWLINE;
@@ -657,3 +735,407 @@ pub fn onigenc_is_mbc_word_ascii(enc: OnigEncoding, data: &[u8], s: usize, _end:
         false
     }
 }
+
+/// Whether `data[p..end]` case-folds to exactly `lowcase`, which must
+/// already be in folded form.
+pub fn onigenc_str_lower_case_match(
+    enc: OnigEncoding,
+    case_fold_flag: OnigCaseFoldType,
+    data: &[u8],
+    p: usize,
+    end: usize,
+    lowcase: &[u8],
+) -> bool {
+    let mut buf = [0u8; ONIGENC_MBC_CASE_FOLD_MAXLEN];
+    let mut p = p;
+    let mut rest = lowcase;
+    while p < end {
+        let len = enc.mbc_case_fold(case_fold_flag, &mut p, end, data, &mut buf) as usize;
+        match rest.strip_prefix(&buf[..len]) {
+            Some(r) => rest = r,
+            None => return false,
+        }
+    }
+    rest.is_empty()
+}
+
+/// Case fold `s` the way IGNORECASE matching does, so two strings the
+/// engine treats as equal under `flag` give the same bytes.
+/// A whole-string form of mbc_case_fold.
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::encodings::utf8::ONIG_ENCODING_UTF8;
+/// use ferroni::oniguruma::ONIGENC_CASE_FOLD_MIN;
+/// use ferroni::regenc::case_fold_str;
+///
+/// let key = case_fold_str(&ONIG_ENCODING_UTF8, ONIGENC_CASE_FOLD_MIN, "Straße".as_bytes());
+/// assert_eq!(key, b"strasse");
+/// ```
+pub fn case_fold_str(enc: OnigEncoding, flag: OnigCaseFoldType, s: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(s.len());
+    let mut buf = [0u8; ONIGENC_MBC_CASE_FOLD_MAXLEN];
+    let mut p = 0;
+    while p < s.len() {
+        // A truncated trailing sequence is copied as is.
+        if p + enc.mbc_enc_len(&s[p..]) > s.len() {
+            out.extend_from_slice(&s[p..]);
+            break;
+        }
+        let len = enc.mbc_case_fold(flag, &mut p, s.len(), s, &mut buf) as usize;
+        out.extend_from_slice(&buf[..len]);
+    }
+    out
+}
+
+// === Case Mapping ===
+// Port of Onigmo's case_map (onigenc_unicode_case_map), which Ruby uses for
+// String#upcase and friends. Unicode encodings use the full Unicode
+// mappings; other encodings swap case through their fold tables.
+
+/// Append the case mapping of `s` to `to` and return the number of bytes
+/// appended.
+///
+/// `*flagp` selects the mapping: ONIGENC_CASE_UPCASE, ONIGENC_CASE_DOWNCASE
+/// (both: swap case), ONIGENC_CASE_UPCASE | ONIGENC_CASE_TITLECASE
+/// (titlecase the first character, downcase the rest) or ONIGENC_CASE_FOLD,
+/// modified by ONIGENC_CASE_FOLD_TURKISH_AZERI and ONIGENC_CASE_ASCII_ONLY.
+/// On return ONIGENC_CASE_MODIFIED is set if any character changed, and a
+/// titlecase request has turned into a downcase one, so a text can be
+/// mapped in chunks. Invalid and truncated sequences are copied as is.
+pub fn onig_case_map(
+    flagp: &mut OnigCaseFoldType,
+    s: &[u8],
+    to: &mut Vec<u8>,
+    enc: OnigEncoding,
+) -> usize {
+    let start_len = to.len();
+    let mut flags = *flagp;
+    let mut buf = [0u8; ONIGENC_MBC_CASE_FOLD_MAXLEN];
+    let mut mapped = Vec::new();
+    let mut p = 0;
+    while p < s.len() {
+        let len = enc.mbc_enc_len(&s[p..]);
+        if p + len > s.len() {
+            to.extend_from_slice(&s[p..]);
+            break;
+        }
+        let src = &s[p..p + len];
+        if (flags & ONIGENC_CASE_FOLD) != 0 {
+            let mut fold_flag =
+                INTERNAL_ONIGENC_CASE_FOLD_MULTI_CHAR | (flags & ONIGENC_CASE_FOLD_TURKISH_AZERI);
+            if (flags & ONIGENC_CASE_ASCII_ONLY) != 0 {
+                fold_flag |= ONIGENC_CASE_FOLD_ASCII_ONLY;
+            }
+            let mut q = p;
+            let n = enc.mbc_case_fold(fold_flag, &mut q, s.len(), s, &mut buf) as usize;
+            if buf[..n] != s[p..q] {
+                flags |= ONIGENC_CASE_MODIFIED;
+            }
+            to.extend_from_slice(&buf[..n]);
+            p = q;
+        } else {
+            mapped.clear();
+            if enc.is_valid_mbc_string(src) {
+                case_map_code(enc, flags, enc.mbc_to_code(src, len), src, &mut mapped);
+            }
+            // Characters the encoding cannot represent keep their case.
+            let mark = to.len();
+            for &code in &mapped {
+                let n = enc.code_to_mbc(code, &mut buf);
+                if n <= 0 {
+                    to.truncate(mark);
+                    break;
+                }
+                to.extend_from_slice(&buf[..n as usize]);
+            }
+            if to.len() == mark || to[mark..] == *src {
+                to.truncate(mark);
+                to.extend_from_slice(src);
+            } else {
+                flags |= ONIGENC_CASE_MODIFIED;
+            }
+            p += len;
+        }
+        if (flags & ONIGENC_CASE_TITLECASE) != 0 {
+            flags =
+                (flags & !(ONIGENC_CASE_UPCASE | ONIGENC_CASE_TITLECASE)) | ONIGENC_CASE_DOWNCASE;
+        }
+    }
+    *flagp = flags;
+    to.len() - start_len
+}
+
+/// The case mapping of one code point for onig_case_map (not folding).
+fn case_map_code(
+    enc: OnigEncoding,
+    flags: OnigCaseFoldType,
+    code: OnigCodePoint,
+    src: &[u8],
+    out: &mut Vec<OnigCodePoint>,
+) {
+    if (flags & ONIGENC_CASE_ASCII_ONLY) != 0 && !onigenc_is_code_ascii(code) {
+        out.push(code);
+        return;
+    }
+    if onigenc_is_unicode_encoding(enc) {
+        match char::from_u32(code) {
+            Some(c) => crate::unicode::case_map_char(c, flags, out),
+            None => out.push(code),
+        }
+        return;
+    }
+
+    let want =
+        if (flags & ONIGENC_CASE_DOWNCASE) != 0 && enc.is_code_ctype(code, ONIGENC_CTYPE_UPPER) {
+            ONIGENC_CTYPE_LOWER
+        } else if (flags & (ONIGENC_CASE_UPCASE | ONIGENC_CASE_TITLECASE)) != 0
+            && enc.is_code_ctype(code, ONIGENC_CTYPE_LOWER)
+        {
+            ONIGENC_CTYPE_UPPER
+        } else {
+            out.push(code);
+            return;
+        };
+    let mut items = vec![
+        OnigCaseFoldCodeItem {
+            byte_len: 0,
+            code_len: 0,
+            code: [0; ONIGENC_MAX_COMP_CASE_FOLD_CODE_LEN],
+        };
+        ONIGENC_GET_CASE_FOLD_CODES_MAX_NUM
+    ];
+    let n = enc.get_case_fold_codes_by_str(0, src, src.len(), &mut items);
+    let other = items[..n.max(0) as usize]
+        .iter()
+        .find(|item| item.code_len == 1 && enc.is_code_ctype(item.code[0], want));
+    out.push(other.map_or(code, |item| item.code[0]));
+}
+
+/// A case conversion over an encoding: the reusable form of
+/// [`onig_case_map`], with the same behavior as case-insensitive matching
+/// for the characters it maps.
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::encodings::utf8::ONIG_ENCODING_UTF8;
+/// use ferroni::regenc::CaseMapper;
+///
+/// let upper = CaseMapper::upcase(&ONIG_ENCODING_UTF8);
+/// assert_eq!(upper.map("Straße".as_bytes()), "STRASSE".as_bytes());
+/// let title = CaseMapper::capitalize(&ONIG_ENCODING_UTF8);
+/// assert_eq!(title.map("ǆUNGLA".as_bytes()), "ǅungla".as_bytes());
+/// ```
+#[derive(Clone, Copy)]
+pub struct CaseMapper {
+    enc: OnigEncoding,
+    flags: OnigCaseFoldType,
+}
+
+impl CaseMapper {
+    /// A mapper with explicit ONIGENC_CASE_* flags (see [`onig_case_map`]).
+    pub fn new(enc: OnigEncoding, flags: OnigCaseFoldType) -> Self {
+        CaseMapper {
+            enc,
+            flags: flags & !ONIGENC_CASE_MODIFIED,
+        }
+    }
+
+    /// Uppercase every character.
+    pub fn upcase(enc: OnigEncoding) -> Self {
+        Self::new(enc, ONIGENC_CASE_UPCASE)
+    }
+
+    /// Lowercase every character.
+    pub fn downcase(enc: OnigEncoding) -> Self {
+        Self::new(enc, ONIGENC_CASE_DOWNCASE)
+    }
+
+    /// Titlecase the first character and lowercase the rest.
+    pub fn capitalize(enc: OnigEncoding) -> Self {
+        Self::new(enc, ONIGENC_CASE_UPCASE | ONIGENC_CASE_TITLECASE)
+    }
+
+    /// Lowercase uppercase characters and uppercase lowercase ones.
+    pub fn swapcase(enc: OnigEncoding) -> Self {
+        Self::new(enc, ONIGENC_CASE_UPCASE | ONIGENC_CASE_DOWNCASE)
+    }
+
+    /// Full case folding, as IGNORECASE matching compares characters.
+    pub fn fold(enc: OnigEncoding) -> Self {
+        Self::new(enc, ONIGENC_CASE_FOLD)
+    }
+
+    /// The flags passed to [`onig_case_map`].
+    pub fn flags(&self) -> OnigCaseFoldType {
+        self.flags
+    }
+
+    /// Map `s`, borrowing it when no character changes.
+    pub fn map<'a>(&self, s: &'a [u8]) -> Cow<'a, [u8]> {
+        let mut flags = self.flags;
+        let mut out = Vec::with_capacity(s.len());
+        onig_case_map(&mut flags, s, &mut out, self.enc);
+        if (flags & ONIGENC_CASE_MODIFIED) != 0 {
+            Cow::Owned(out)
+        } else {
+            Cow::Borrowed(s)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encodings::ascii::ONIG_ENCODING_ASCII;
+    use crate::encodings::utf8::ONIG_ENCODING_UTF8;
+
+    #[test]
+    fn test_validate_utf8() {
+        let enc = &ONIG_ENCODING_UTF8;
+        assert_eq!(enc.validate("aé💻".as_bytes()), Ok(()));
+        assert_eq!(enc.validate(b"ab\xffc"), Err(FirstErrorOffset(2)));
+        assert_eq!(enc.validate(b"a\xc3"), Err(FirstErrorOffset(1)));
+        assert_eq!(enc.validate(b"\xe2\x82a"), Err(FirstErrorOffset(0)));
+        assert_eq!(
+            FirstErrorOffset(2).to_string(),
+            "invalid byte sequence at offset 2"
+        );
+    }
+
+    #[test]
+    fn test_validate_ascii() {
+        assert_eq!(ONIG_ENCODING_ASCII.validate(b"a\xff"), Ok(()));
+    }
+
+    #[test]
+    fn test_char_count() {
+        let enc = &ONIG_ENCODING_UTF8;
+        assert_eq!(enc.char_count(b""), 0);
+        assert_eq!(enc.char_count("aé💻".as_bytes()), 3);
+        assert_eq!(enc.char_count(b"a\xf0\x9f"), 2);
+        assert_eq!(ONIG_ENCODING_ASCII.char_count(b"a\xff"), 2);
+    }
+
+    #[test]
+    fn test_char_boundaries() {
+        let enc = &ONIG_ENCODING_UTF8;
+        let s = "aé💻".as_bytes(); // 1 + 2 + 4 bytes
+        let floors: Vec<usize> = (0..=8).map(|i| enc.floor_char_boundary(s, i)).collect();
+        assert_eq!(floors, [0, 1, 1, 3, 3, 3, 3, 7, 7]);
+        let ceils: Vec<usize> = (0..=8).map(|i| enc.ceil_char_boundary(s, i)).collect();
+        assert_eq!(ceils, [0, 1, 3, 3, 7, 7, 7, 7, 7]);
+
+        // Truncated sequence: the ceiling stays within the buffer.
+        assert_eq!(enc.ceil_char_boundary(b"\xf0\x9f", 1), 2);
+        assert_eq!(ONIG_ENCODING_ASCII.floor_char_boundary(b"\xc3\xa9", 1), 1);
+    }
+
+    #[test]
+    fn test_case_fold_str() {
+        let enc = &ONIG_ENCODING_UTF8;
+        let tr = ONIGENC_CASE_FOLD_MIN | ONIGENC_CASE_FOLD_TURKISH_AZERI;
+        assert_eq!(
+            case_fold_str(enc, ONIGENC_CASE_FOLD_MIN, "ΣΑΣ Ǆ".as_bytes()),
+            "σασ ǆ".as_bytes()
+        );
+        assert_eq!(case_fold_str(enc, tr, "Iİ".as_bytes()), "ıi".as_bytes());
+        assert_eq!(
+            case_fold_str(enc, ONIGENC_CASE_FOLD_ASCII_ONLY, "ÄB".as_bytes()),
+            "Äb".as_bytes()
+        );
+        assert_eq!(
+            case_fold_str(enc, ONIGENC_CASE_FOLD_MIN, b"A\xc3"),
+            b"a\xc3"
+        );
+        assert_eq!(
+            case_fold_str(&ONIG_ENCODING_ASCII, ONIGENC_CASE_FOLD_MIN, b"Ab\xc4"),
+            b"ab\xc4"
+        );
+
+        let s = "STRASSE".as_bytes();
+        assert!(onigenc_str_lower_case_match(
+            enc,
+            ONIGENC_CASE_FOLD_MIN,
+            s,
+            0,
+            s.len(),
+            b"strasse"
+        ));
+        assert!(!onigenc_str_lower_case_match(
+            enc,
+            ONIGENC_CASE_FOLD_MIN,
+            s,
+            0,
+            s.len(),
+            b"stras"
+        ));
+        assert!(!onigenc_str_lower_case_match(
+            enc,
+            ONIGENC_CASE_FOLD_MIN,
+            s,
+            0,
+            3,
+            b"strasse"
+        ));
+    }
+
+    #[test]
+    fn test_case_map() {
+        let enc = &ONIG_ENCODING_UTF8;
+        let map = |flags, s: &str| {
+            String::from_utf8(CaseMapper::new(enc, flags).map(s.as_bytes()).into_owned()).unwrap()
+        };
+        let up = ONIGENC_CASE_UPCASE;
+        let down = ONIGENC_CASE_DOWNCASE;
+        let title = ONIGENC_CASE_UPCASE | ONIGENC_CASE_TITLECASE;
+        let tr = ONIGENC_CASE_FOLD_TURKISH_AZERI;
+        assert_eq!(map(up, "straße ǆ ﬁ"), "STRASSE Ǆ FI");
+        assert_eq!(map(down, "ÀÉ İ Ǆ"), "àé i\u{307} ǆ");
+        assert_eq!(map(up | down, "aBç Σ ǅ"), "AbÇ σ ǆ");
+        assert_eq!(map(title, "ßIG"), "Ssig");
+        assert_eq!(map(title, "ﬁne"), "Fine");
+        assert_eq!(map(title, "ŉ"), "\u{2bc}N");
+        assert_eq!(map(title, "ᾳ ᾲ"), "ᾼ ᾲ");
+        assert_eq!(map(title, "\u{1fb2}"), "\u{1fba}\u{345}");
+        assert_eq!(map(title, "ა"), "ა");
+        assert_eq!(map(up, "ა"), "Ა");
+        assert_eq!(map(up | tr, "iı"), "İI");
+        assert_eq!(map(down | tr, "Iİ"), "ıi");
+        assert_eq!(map(up | ONIGENC_CASE_ASCII_ONLY, "éa"), "éA");
+        assert_eq!(map(ONIGENC_CASE_FOLD, "Straße"), "strasse");
+        assert_eq!(map(ONIGENC_CASE_FOLD | tr, "I"), "ı");
+        assert_eq!(map(up, "A\u{1F600}"), "A\u{1F600}");
+
+        let upper = CaseMapper::upcase(enc);
+        assert!(matches!(upper.map(b"AB1"), Cow::Borrowed(_)));
+        assert_eq!(upper.map(b"a\xffb\xc3"), &b"A\xffB\xc3"[..]);
+    }
+
+    #[test]
+    fn test_case_map_flags() {
+        let enc = &ONIG_ENCODING_UTF8;
+        // Titlecase applies to the first character of the first chunk only.
+        let mut flags = ONIGENC_CASE_UPCASE | ONIGENC_CASE_TITLECASE;
+        let mut out = Vec::new();
+        assert_eq!(onig_case_map(&mut flags, b"hE", &mut out, enc), 2);
+        assert_eq!(onig_case_map(&mut flags, b"LLO", &mut out, enc), 3);
+        assert_eq!(out, b"Hello");
+        assert_eq!(flags, ONIGENC_CASE_DOWNCASE | ONIGENC_CASE_MODIFIED);
+
+        let mut flags = ONIGENC_CASE_DOWNCASE;
+        out.clear();
+        onig_case_map(&mut flags, "abc é".as_bytes(), &mut out, enc);
+        assert_eq!(flags & ONIGENC_CASE_MODIFIED, 0);
+    }
+
+    #[test]
+    fn test_case_map_non_unicode() {
+        let enc = &ONIG_ENCODING_ASCII;
+        let swap = CaseMapper::swapcase(enc);
+        assert_eq!(swap.map(b"aB-\xe9"), &b"Ab-\xe9"[..]);
+        assert_eq!(CaseMapper::fold(enc).map(b"ABc"), &b"abc"[..]);
+    }
+}
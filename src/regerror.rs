@@ -5,6 +5,8 @@
 // Maps error codes to human-readable messages.
 
 use crate::oniguruma::*;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
 
 /// Get the format string for an error code.
 /// Corresponds to C's onig_error_code_to_format().
@@ -29,6 +31,9 @@ pub fn onig_error_code_to_format(code: i32) -> &'static str {
             "can't convert to wide-char on specified multibyte-encoding"
         }
         ONIGERR_FAIL_TO_INITIALIZE => "fail to initialize",
+        ONIGERR_TOO_LONG_INPUT => "input string too long",
+        ONIGERR_COMPILED_SIZE_LIMIT_OVER => "compiled size limit over",
+        ONIGERR_COMPILE_TIME_LIMIT_OVER => "compile time limit over",
         ONIGERR_INVALID_ARGUMENT => "invalid argument",
         ONIGERR_END_PATTERN_AT_LEFT_BRACE => "end pattern at left brace",
         ONIGERR_END_PATTERN_AT_LEFT_BRACKET => "end pattern at left bracket",
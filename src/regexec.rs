@@ -10,17 +10,20 @@
 #![allow(unused_assignments)]
 #![allow(unused_mut)]
 
-use std::sync::atomic::{AtomicPtr, AtomicU32, AtomicU64, Ordering};
-use std::time::Instant;
+use core::sync::atomic::{AtomicPtr, AtomicU32, Ordering};
 
+use crate::memhook::{onig_get_memory_hook, OnigBufferKind, Tracked};
 use crate::oniguruma::*;
 use crate::regenc::*;
 use crate::regint::*;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+use crate::sys::{AtomicU64, Instant};
 
 /// Callout function type. Receives args and optional user data.
 /// Return ONIG_CALLOUT_SUCCESS (0) to continue, ONIG_CALLOUT_FAIL (1) to fail,
 /// or a negative error code.
-pub type OnigCalloutFunc = fn(args: &OnigCalloutArgs, user_data: *mut std::ffi::c_void) -> i32;
+pub type OnigCalloutFunc = fn(args: &OnigCalloutArgs, user_data: *mut core::ffi::c_void) -> i32;
 
 // ============================================================================
 // Global Limits (port of C's onig_retry_limit_in_match etc.)
@@ -79,8 +82,8 @@ pub fn onig_get_time_limit() -> u64 {
 // Global Progress/Retraction Callout (port of C's global callout funcs)
 // ============================================================================
 
-static PROGRESS_CALLOUT: AtomicPtr<()> = AtomicPtr::new(std::ptr::null_mut());
-static RETRACTION_CALLOUT: AtomicPtr<()> = AtomicPtr::new(std::ptr::null_mut());
+static PROGRESS_CALLOUT: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+static RETRACTION_CALLOUT: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
 
 /// Get the global progress callout function.
 #[cfg_attr(coverage_nightly, coverage(off))]
@@ -89,7 +92,7 @@ pub fn onig_get_progress_callout() -> Option<OnigCalloutFunc> {
     if p.is_null() {
         None
     } else {
-        Some(unsafe { std::mem::transmute(p) })
+        Some(unsafe { core::mem::transmute(p) })
     }
 }
 
@@ -108,7 +111,7 @@ pub fn onig_get_retraction_callout() -> Option<OnigCalloutFunc> {
     if p.is_null() {
         None
     } else {
-        Some(unsafe { std::mem::transmute(p) })
+        Some(unsafe { core::mem::transmute(p) })
     }
 }
 
@@ -125,9 +128,9 @@ pub fn onig_set_retraction_callout(f: OnigCalloutFunc) -> i32 {
 // ============================================================================
 
 pub type OnigCallbackEachMatchFunc =
-    fn(str_data: &[u8], region: &OnigRegion, user_data: *mut std::ffi::c_void) -> i32;
+    fn(str_data: &[u8], region: &OnigRegion, user_data: *mut core::ffi::c_void) -> i32;
 
-static CALLBACK_EACH_MATCH: AtomicPtr<()> = AtomicPtr::new(std::ptr::null_mut());
+static CALLBACK_EACH_MATCH: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
 
 #[cfg_attr(coverage_nightly, coverage(off))]
 pub fn onig_get_callback_each_match() -> Option<OnigCallbackEachMatchFunc> {
@@ -135,7 +138,7 @@ pub fn onig_get_callback_each_match() -> Option<OnigCallbackEachMatchFunc> {
     if p.is_null() {
         None
     } else {
-        Some(unsafe { std::mem::transmute(p) })
+        Some(unsafe { core::mem::transmute(p) })
     }
 }
 
@@ -202,7 +205,7 @@ pub fn onig_get_case_fold_flag(reg: &RegexType) -> OnigCaseFoldType {
 
 #[cfg_attr(coverage_nightly, coverage(off))]
 pub fn onig_get_syntax(reg: &RegexType) -> &OnigSyntaxType {
-    unsafe { &*reg.syntax }
+    &reg.syntax
 }
 
 #[cfg_attr(coverage_nightly, coverage(off))]
@@ -263,7 +266,7 @@ pub fn onig_name_to_backref_number(
     if let Some(region) = region {
         for i in (0..nums.len()).rev() {
             let idx = nums[i] as usize;
-            if idx < region.beg.len() && region.beg[idx] != ONIG_REGION_NOTPOS {
+            if idx < region.num_regs as usize && region.beg(idx) != ONIG_REGION_NOTPOS {
                 return Ok(nums[i]);
             }
         }
@@ -304,7 +307,7 @@ pub fn onig_noname_group_capture_is_active(reg: &RegexType) -> bool {
         return false;
     }
     if onig_number_of_names(reg) > 0 {
-        let syntax = unsafe { &*reg.syntax };
+        let syntax = &reg.syntax;
         if is_syntax_bv(syntax, ONIG_SYN_CAPTURE_ONLY_NAMED_GROUP)
             && !opton_capture_group(reg.options)
         {
@@ -407,13 +410,13 @@ where
             if rs != 0 {
                 return (rs, region);
             }
-            if region.num_regs > 0 && region.end[0] == start as i32 {
+            if region.num_regs > 0 && region.end(0) == start as i32 {
                 if start >= end {
                     break;
                 }
                 start += enclen(enc, str_data, start);
             } else if region.num_regs > 0 {
-                start = region.end[0] as usize;
+                start = region.end(0) as usize;
             } else {
                 break;
             }
@@ -442,7 +445,11 @@ pub struct OnigMatchParam {
     pub time_limit: u64,
     pub progress_callout: Option<OnigCalloutFunc>,
     pub retraction_callout: Option<OnigCalloutFunc>,
-    pub callout_user_data: *mut std::ffi::c_void,
+    pub callout_user_data: *mut core::ffi::c_void,
+    /// Callout data slots of the last search made with this param, for
+    /// `onig_get_callout_data_by_tag` and friends. TOTAL_COUNT data
+    /// accumulates over searches until the param is re-initialized.
+    pub callout_data: Vec<[i64; ONIG_CALLOUT_DATA_SLOT_NUM]>,
 }
 
 #[cfg_attr(coverage_nightly, coverage(off))]
@@ -454,7 +461,8 @@ pub fn onig_new_match_param() -> OnigMatchParam {
         time_limit: 0,
         progress_callout: None,
         retraction_callout: None,
-        callout_user_data: std::ptr::null_mut(),
+        callout_user_data: core::ptr::null_mut(),
+        callout_data: Vec::new(),
     };
     onig_initialize_match_param(&mut mp);
     mp
@@ -468,7 +476,8 @@ pub fn onig_initialize_match_param(mp: &mut OnigMatchParam) -> i32 {
     mp.time_limit = TIME_LIMIT.load(Ordering::Relaxed);
     mp.progress_callout = onig_get_progress_callout();
     mp.retraction_callout = onig_get_retraction_callout();
-    mp.callout_user_data = std::ptr::null_mut();
+    mp.callout_user_data = core::ptr::null_mut();
+    mp.callout_data.clear();
     ONIG_NORMAL
 }
 
@@ -517,7 +526,7 @@ pub fn onig_set_retraction_callout_of_match_param(
 #[cfg_attr(coverage_nightly, coverage(off))]
 pub fn onig_set_callout_user_data_of_match_param(
     mp: &mut OnigMatchParam,
-    user_data: *mut std::ffi::c_void,
+    user_data: *mut core::ffi::c_void,
 ) -> i32 {
     mp.callout_user_data = user_data;
     ONIG_NORMAL
@@ -576,8 +585,8 @@ impl OnigCalloutArgs {
             retry_in_match_counter: retry_counter,
             str_data: str_data.as_ptr(),
             str_len: str_data.len(),
-            callout_data: std::ptr::null_mut(),
-            msa: std::ptr::null_mut(),
+            callout_data: core::ptr::null_mut(),
+            msa: core::ptr::null_mut(),
         }
     }
 }
@@ -697,7 +706,7 @@ pub fn onig_get_contents_end_by_callout_args(args: &OnigCalloutArgs) -> *const u
             }
         }
     }
-    std::ptr::null()
+    core::ptr::null()
 }
 
 // ============================================================================
@@ -961,17 +970,17 @@ pub fn onig_get_used_stack_size_in_callout(_args: &OnigCalloutArgs) -> (i32, i32
 // ============================================================================
 
 #[cfg_attr(coverage_nightly, coverage(off))]
-pub fn onig_builtin_fail(_args: &OnigCalloutArgs, _user_data: *mut std::ffi::c_void) -> i32 {
+pub fn onig_builtin_fail(_args: &OnigCalloutArgs, _user_data: *mut core::ffi::c_void) -> i32 {
     ONIG_CALLOUT_FAIL
 }
 
 #[cfg_attr(coverage_nightly, coverage(off))]
-pub fn onig_builtin_mismatch(_args: &OnigCalloutArgs, _user_data: *mut std::ffi::c_void) -> i32 {
+pub fn onig_builtin_mismatch(_args: &OnigCalloutArgs, _user_data: *mut core::ffi::c_void) -> i32 {
     ONIG_MISMATCH
 }
 
 #[cfg_attr(coverage_nightly, coverage(off))]
-pub fn onig_builtin_error(args: &OnigCalloutArgs, _user_data: *mut std::ffi::c_void) -> i32 {
+pub fn onig_builtin_error(args: &OnigCalloutArgs, _user_data: *mut core::ffi::c_void) -> i32 {
     let reg = unsafe { &*args.regex };
     if let Some(ref ext) = reg.extp {
         let idx = (args.num - 1) as usize;
@@ -992,7 +1001,7 @@ pub fn onig_builtin_error(args: &OnigCalloutArgs, _user_data: *mut std::ffi::c_v
 }
 
 #[cfg_attr(coverage_nightly, coverage(off))]
-pub fn onig_builtin_count(args: &OnigCalloutArgs, _user_data: *mut std::ffi::c_void) -> i32 {
+pub fn onig_builtin_count(args: &OnigCalloutArgs, _user_data: *mut core::ffi::c_void) -> i32 {
     if args.callout_data.is_null() {
         return ONIG_CALLOUT_FAIL;
     }
@@ -1041,14 +1050,14 @@ pub fn onig_builtin_count(args: &OnigCalloutArgs, _user_data: *mut std::ffi::c_v
 }
 
 #[cfg_attr(coverage_nightly, coverage(off))]
-pub fn onig_builtin_total_count(args: &OnigCalloutArgs, _user_data: *mut std::ffi::c_void) -> i32 {
+pub fn onig_builtin_total_count(args: &OnigCalloutArgs, _user_data: *mut core::ffi::c_void) -> i32 {
     // total_count is the same as count but without clearing old data.
     // In Rust, count already doesn't clear old data, so they are equivalent.
     onig_builtin_count(args, _user_data)
 }
 
 #[cfg_attr(coverage_nightly, coverage(off))]
-pub fn onig_builtin_max(args: &OnigCalloutArgs, _user_data: *mut std::ffi::c_void) -> i32 {
+pub fn onig_builtin_max(args: &OnigCalloutArgs, _user_data: *mut core::ffi::c_void) -> i32 {
     if args.callout_data.is_null() {
         return ONIG_CALLOUT_FAIL;
     }
@@ -1115,7 +1124,7 @@ pub fn onig_builtin_max(args: &OnigCalloutArgs, _user_data: *mut std::ffi::c_voi
 /// to at least the position recorded by SKIP, avoiding redundant attempts.
 /// Port of C's onig_builtin_skip (regexec.c:6642-6648).
 #[cfg_attr(coverage_nightly, coverage(off))]
-pub fn onig_builtin_skip(args: &OnigCalloutArgs, _user_data: *mut std::ffi::c_void) -> i32 {
+pub fn onig_builtin_skip(args: &OnigCalloutArgs, _user_data: *mut core::ffi::c_void) -> i32 {
     if args.callout_in == OnigCalloutIn::Retraction {
         return ONIG_CALLOUT_SUCCESS;
     }
@@ -1137,7 +1146,7 @@ pub fn onig_builtin_skip(args: &OnigCalloutArgs, _user_data: *mut std::ffi::c_vo
 }
 
 #[cfg_attr(coverage_nightly, coverage(off))]
-pub fn onig_builtin_cmp(args: &OnigCalloutArgs, _user_data: *mut std::ffi::c_void) -> i32 {
+pub fn onig_builtin_cmp(args: &OnigCalloutArgs, _user_data: *mut core::ffi::c_void) -> i32 {
     if args.callout_data.is_null() {
         return ONIG_CALLOUT_FAIL;
     }
@@ -1281,8 +1290,10 @@ enum StackEntry {
         save_type: SaveType,
         v: usize,
     },
-    /// Call frame return address (STK_CALL_FRAME)
-    CallFrame { ret_addr: usize },
+    /// Call frame return address (STK_CALL_FRAME). `nest` is the
+    /// number of calls open once this one is entered, so the nest level
+    /// is right again after backtracking out of a call.
+    CallFrame { ret_addr: usize, nest: u32 },
     /// Return marker (STK_RETURN)
     Return,
     /// Callout entry (STK_CALLOUT) - for retraction callbacks
@@ -1338,6 +1349,8 @@ pub struct MatchArg {
     pub retry_limit_in_match: u64,
     pub retry_limit_in_search: u64,
     pub retry_limit_in_search_counter: u64,
+    pub subexp_call_limit_in_search: u64,
+    pub subexp_call_in_search_counter: u64,
     pub match_stack_limit: u32,
     pub time_limit: u64, // milliseconds, 0 = unlimited
     /// Lazily-initialized search start time for time-limit checking.
@@ -1347,10 +1360,29 @@ pub struct MatchArg {
     stack: Vec<StackEntry>,
     mem_start_stk: Vec<MemPtr>,
     mem_end_stk: Vec<MemPtr>,
+    /// Bytes of `stack` granted by the memory hook (see memhook.rs).
+    stack_mem: Tracked,
+    /// Callout data slots, indexed by callout num - 1. Lives here between
+    /// match_at calls so TOTAL_COUNT can count over the whole search.
+    callout_data: Vec<[i64; ONIG_CALLOUT_DATA_SLOT_NUM]>,
+    /// Iteration start of each simple empty check, indexed by
+    /// empty check id (see OpCode::EmptyCheckStartSimple).
+    empty_check_pos: Vec<usize>,
+    /// Where a match must end (`Regex::find_ending_at`). The
+    /// search range then only bounds the start; matching sees all the text.
+    pub(crate) match_end: Option<usize>,
+    /// The search was given `ONIG_OPTION_DONT_CAPTURE_GROUP`, so
+    /// the region only records group 0.
+    group0_only: bool,
 }
 
 const CHECK_TIME_INTERVAL: u64 = 512;
 
+/// Free stack entries `match_at` keeps at the start of every opcode. Only
+/// the `.*` loop opcodes and custom opcodes push more than this many
+/// entries, and those use the checked `Vec::push`.
+const STACK_HEADROOM: usize = 16;
+
 impl MatchArg {
     pub(crate) fn new(
         reg: &RegexType,
@@ -1368,12 +1400,19 @@ impl MatchArg {
             retry_limit_in_match: RETRY_LIMIT_IN_MATCH.load(Ordering::Relaxed),
             retry_limit_in_search: RETRY_LIMIT_IN_SEARCH.load(Ordering::Relaxed),
             retry_limit_in_search_counter: 0,
+            subexp_call_limit_in_search: SUBEXP_CALL_LIMIT_IN_SEARCH.load(Ordering::Relaxed),
+            subexp_call_in_search_counter: 0,
             match_stack_limit: MATCH_STACK_LIMIT.load(Ordering::Relaxed),
             time_limit: TIME_LIMIT.load(Ordering::Relaxed),
             time_start: None,
-            stack: Vec::with_capacity(INIT_MATCH_STACK_SIZE),
+            stack: Vec::new(),
             mem_start_stk: Vec::new(),
             mem_end_stk: Vec::new(),
+            stack_mem: Tracked::new(OnigBufferKind::BacktrackStack),
+            callout_data: Vec::new(),
+            empty_check_pos: Vec::new(),
+            match_end: None,
+            group0_only: option.intersects(ONIG_OPTION_DONT_CAPTURE_GROUP),
         }
     }
 
@@ -1395,12 +1434,19 @@ impl MatchArg {
             retry_limit_in_match: mp.retry_limit_in_match,
             retry_limit_in_search: mp.retry_limit_in_search,
             retry_limit_in_search_counter: 0,
+            subexp_call_limit_in_search: SUBEXP_CALL_LIMIT_IN_SEARCH.load(Ordering::Relaxed),
+            subexp_call_in_search_counter: 0,
             match_stack_limit: mp.match_stack_limit,
             time_limit: mp.time_limit,
             time_start: None,
-            stack: Vec::with_capacity(INIT_MATCH_STACK_SIZE),
+            stack: Vec::new(),
             mem_start_stk: Vec::new(),
             mem_end_stk: Vec::new(),
+            stack_mem: Tracked::new(OnigBufferKind::BacktrackStack),
+            callout_data: Vec::new(),
+            empty_check_pos: Vec::new(),
+            match_end: None,
+            group0_only: option.intersects(ONIG_OPTION_DONT_CAPTURE_GROUP),
         }
     }
 
@@ -1419,6 +1465,53 @@ impl MatchArg {
         self.best_s = 0;
         self.skip_search = 0;
         self.retry_limit_in_search_counter = 0;
+        self.subexp_call_in_search_counter = 0;
+        self.time_start = None;
+        self.callout_data.clear();
+        self.match_end = None;
+        self.group0_only = option.intersects(ONIG_OPTION_DONT_CAPTURE_GROUP);
+    }
+
+    /// Re-read the process-wide limits, for a state kept across searches.
+    fn reload_limits(&mut self) {
+        self.retry_limit_in_match = RETRY_LIMIT_IN_MATCH.load(Ordering::Relaxed);
+        self.retry_limit_in_search = RETRY_LIMIT_IN_SEARCH.load(Ordering::Relaxed);
+        self.subexp_call_limit_in_search = SUBEXP_CALL_LIMIT_IN_SEARCH.load(Ordering::Relaxed);
+        self.match_stack_limit = MATCH_STACK_LIMIT.load(Ordering::Relaxed);
+        self.time_limit = TIME_LIMIT.load(Ordering::Relaxed);
+    }
+
+    /// Size the region for a match of `reg` and unset all its groups.
+    fn reset_region(&mut self, reg: &RegexType) {
+        let groups = if self.group0_only { 0 } else { reg.num_mem };
+        if let Some(ref mut r) = self.region {
+            r.resize(groups + 1);
+            r.clear();
+        }
+    }
+
+    /// Reserve VM stack and capture-slot capacity ahead of the first search,
+    /// so warm-up can move allocation cost out of the first `match_at` call.
+    pub(crate) fn reserve(&mut self, reg: &RegexType, stack_entries: usize) {
+        self.stack.reserve(stack_entries);
+        let slots = reg.num_mem as usize + 1;
+        self.mem_start_stk.reserve(slots);
+        self.mem_end_stk.reserve(slots);
+    }
+
+    /// Capacity of the reusable VM stack, in entries.
+    pub(crate) fn stack_capacity(&self) -> usize {
+        self.stack.capacity()
+    }
+
+    /// Bytes of the reusable VM state and region on the heap.
+    pub(crate) fn heap_size(&self) -> usize {
+        use core::mem::size_of;
+        self.stack.capacity() * size_of::<StackEntry>()
+            + (self.mem_start_stk.capacity() + self.mem_end_stk.capacity()) * size_of::<MemPtr>()
+            + self.callout_data.capacity() * size_of::<[i64; ONIG_CALLOUT_DATA_SLOT_NUM]>()
+            + self.empty_check_pos.capacity() * size_of::<usize>()
+            + self.region.as_ref().map_or(0, OnigRegion::spans_size)
     }
 
     /// Check if the time limit has been exceeded. Returns true if over limit.
@@ -1432,22 +1525,157 @@ impl MatchArg {
         let start = self
             .time_start
             .get_or_insert_with(|| Box::new(Instant::now()));
-        start.elapsed() >= std::time::Duration::from_millis(self.time_limit)
+        start.elapsed() >= core::time::Duration::from_millis(self.time_limit)
     }
 }
 
 // ============================================================================
-// Stack limit check
+// Stack growth and limit check
 // ============================================================================
 
-/// Check stack size against match_stack_limit. Returns Err with error code if exceeded.
-#[inline]
-#[cfg_attr(coverage_nightly, coverage(off))]
-fn check_stack_limit(stack_len: usize, limit: u32) -> Result<(), i32> {
-    if limit != 0 && stack_len >= limit as usize {
+/// Make room for STACK_HEADROOM more entries, growing the stack
+/// geometrically (with the memory hook's approval when one is set), and
+/// return the stack length at which to call this again. C checks
+/// the limit as it doubles the stack; here match_at() compares against the
+/// returned length once per opcode so the pushes themselves never
+/// reallocate.
+#[cold]
+#[inline(never)]
+fn grow_stack(
+    stack: &mut Vec<StackEntry>,
+    limit: u32,
+    hooked: bool,
+    stack_mem: &mut Tracked,
+) -> Result<usize, i32> {
+    const ENTRY: usize = core::mem::size_of::<StackEntry>();
+    let limit = limit as usize;
+    if limit != 0 && stack.len() >= limit {
         return Err(ONIGERR_MATCH_STACK_LIMIT_OVER);
     }
-    Ok(())
+    let mut cap = stack.capacity();
+    if stack.len() + STACK_HEADROOM > cap {
+        cap = (cap * 2).max(INIT_MATCH_STACK_SIZE);
+        if limit != 0 {
+            // Never needs more than the limit plus one opcode's pushes
+            cap = cap.min(limit + STACK_HEADROOM);
+        }
+    }
+    if hooked && cap * ENTRY > stack_mem.bytes() {
+        stack_mem.grow(cap * ENTRY).map_err(|_| ONIGERR_MEMORY)?;
+    }
+    stack.reserve_exact(cap - stack.len());
+    Ok(stack_grow_at(stack.capacity(), limit))
+}
+
+/// Backtrack stack entries a search of `reg` over text of up to `max_len`
+/// bytes is expected to need, for warm-up.
+///
+/// A program without loops pushes at most a handful of entries, which the
+/// first allocation of grow_stack() covers. In a loop every step can leave
+/// a choice point, the capture entries `stack_pop_level` keeps on the
+/// stack, and one entry per repeat counter and empty check; a step consumes
+/// at least one byte. The result is capped like grow_stack() caps the
+/// stack at the match stack limit.
+pub(crate) fn stack_reservation(reg: &RegexType, max_len: usize) -> usize {
+    let loops = reg.num_repeat > 0
+        || reg.ops.iter().any(|op| {
+            matches!(
+                op.opcode,
+                OpCode::AnyCharStar
+                    | OpCode::AnyCharMlStar
+                    | OpCode::AnyCharStarPeekNext
+                    | OpCode::AnyCharMlStarPeekNext
+                    | OpCode::Call
+            ) || matches!(op.payload, OperationPayload::Jump { addr } if addr < 0)
+        });
+    let mut entries = INIT_MATCH_STACK_SIZE;
+    if loops {
+        let mems = reg.num_mem.max(0) as usize;
+        let captures = match reg.stack_pop_level {
+            StackPopLevel::Free => 0,
+            StackPopLevel::MemStart => mems,
+            StackPopLevel::All => 2 * mems,
+        };
+        let per_step =
+            1 + captures + reg.num_repeat.max(0) as usize + reg.num_empty_check.max(0) as usize;
+        entries = entries.max(
+            per_step
+                .saturating_mul(max_len)
+                .saturating_add(STACK_HEADROOM),
+        );
+    }
+    match MATCH_STACK_LIMIT.load(Ordering::Relaxed) as usize {
+        0 => entries,
+        limit => entries.min(limit + STACK_HEADROOM),
+    }
+}
+
+/// Stack length at which grow_stack() must run next for a stack with
+/// capacity `cap`.
+#[inline]
+fn stack_grow_at(cap: usize, limit: usize) -> usize {
+    let grow_at = cap.saturating_sub(STACK_HEADROOM);
+    if limit != 0 {
+        grow_at.min(limit)
+    } else {
+        grow_at
+    }
+}
+
+/// Fill `region` for a match of `keep..s` from the capture stacks. Returns
+/// the error from building the capture history, if any. C does this inline
+/// in OP_END; it is kept out of match_at() to keep the opcode loop small.
+#[inline(never)]
+#[allow(clippy::too_many_arguments)]
+fn set_match_region(
+    region: &mut OnigRegion,
+    reg: &RegexType,
+    stack: &[StackEntry],
+    mem_start_stk: &[MemPtr],
+    mem_end_stk: &[MemPtr],
+    keep: usize,
+    s: usize,
+    options: OnigOptionType,
+    group0_only: bool,
+) -> i32 {
+    let num_mem = if group0_only { 0 } else { reg.num_mem as usize };
+    region.resize(num_mem as i32 + 1);
+    region.put(0, keep as i32, s as i32); // offsets from str start
+
+    for i in 1..=num_mem {
+        if let Some(mem_end) = get_mem_end(reg, stack, mem_end_stk, i) {
+            let mem_start = get_mem_start(reg, stack, mem_start_stk, i);
+            let beg = mem_start.map(|v| v as i32).unwrap_or(ONIG_REGION_NOTPOS);
+            region.put(i, beg, mem_end as i32);
+        } else {
+            region.put(i, ONIG_REGION_NOTPOS, ONIG_REGION_NOTPOS);
+        }
+    }
+
+    // Build capture history tree (not for POSIX regions)
+    if USE_CAPTURE_HISTORY
+        && reg.capture_history != 0
+        && !opton_posix_region(options)
+        && !group0_only
+    {
+        let node = if region.history_root.is_none() {
+            region.history_root = Some(Box::new(OnigCaptureTreeNode::new()));
+            region.history_root.as_mut().unwrap()
+        } else {
+            let root = region.history_root.as_mut().unwrap();
+            root.clear();
+            root
+        };
+        node.group = 0;
+        node.beg = keep as i32;
+        node.end = s as i32;
+        let mut stkp = 0usize;
+        let r = make_capture_history_tree(node, &mut stkp, stack, stack.len(), reg);
+        if r < 0 {
+            return r;
+        }
+    }
+    0
 }
 
 // ============================================================================
@@ -1529,7 +1757,6 @@ fn stack_pop(
 /// Pop stack entries until a Mark with matching zid is found (STACK_POP_TO_MARK).
 /// Removes ALL entries. Restores mem_start_stk/mem_end_stk along the way.
 /// Returns the saved position from the Mark entry (if any).
-#[cfg_attr(coverage_nightly, coverage(off))]
 fn stack_pop_to_mark(
     stack: &mut Vec<StackEntry>,
     mark_id: usize,
@@ -1938,6 +2165,25 @@ fn backref_match_at_nested_level(
     false
 }
 
+/// Number of subexp calls that have not returned yet: the nest level of
+/// the innermost open call frame, found the way OP_RETURN finds it.
+fn call_nest_level(stack: &[StackEntry]) -> u32 {
+    let mut level = 0i32;
+    for e in stack.iter().rev() {
+        match e {
+            StackEntry::CallFrame { nest, .. } => {
+                if level == 0 {
+                    return *nest;
+                }
+                level -= 1;
+            }
+            StackEntry::Return => level += 1,
+            _ => {}
+        }
+    }
+    0
+}
+
 /// Check if a backref capture exists at a specific nesting level.
 fn backref_check_at_nested_level(
     stack: &[StackEntry],
@@ -2162,6 +2408,32 @@ fn enclen(enc: OnigEncoding, str_data: &[u8], s: usize) -> usize {
     }
 }
 
+/// Compare a literal run of a string opcode against the subject.
+/// Runs of 16 bytes or more go 16 bytes at a time, 8..16 as two words; the
+/// last chunk overlaps the previous one instead of falling back to a byte
+/// tail, so the compiler emits straight vector/word compares.
+#[inline(always)]
+fn literal_eq(a: &[u8], b: &[u8]) -> bool {
+    debug_assert_eq!(a.len(), b.len());
+    let n = a.len();
+    let w8 = |s: &[u8], i: usize| u64::from_ne_bytes(s[i..i + 8].try_into().unwrap());
+    let w16 = |s: &[u8], i: usize| u128::from_ne_bytes(s[i..i + 16].try_into().unwrap());
+    if n >= 16 {
+        let mut i = 0;
+        while i + 16 < n {
+            if w16(a, i) != w16(b, i) {
+                return false;
+            }
+            i += 16;
+        }
+        w16(a, n - 16) == w16(b, n - 16)
+    } else if n >= 8 {
+        w8(a, 0) == w8(b, 0) && w8(a, n - 8) == w8(b, n - 8)
+    } else {
+        a == b
+    }
+}
+
 /// Case-insensitive string comparison using encoding-aware case folding.
 /// Compares `mblen` bytes starting at `s1_pos` with bytes starting at `*s2_pos`.
 /// Advances `*s2_pos` past consumed bytes on success. Returns true if equal.
@@ -2231,7 +2503,9 @@ fn run_builtin_callout(
             let max_val = resolve_callout_arg(&entry.args[0], &ext.callout_list, callout_data);
             builtin_max(entry, is_retraction, &mut callout_data[idx], max_val)
         }
-        CALLOUT_BUILTIN_COUNT => builtin_count(entry, is_retraction, &mut callout_data[idx]),
+        CALLOUT_BUILTIN_COUNT | CALLOUT_BUILTIN_TOTAL_COUNT => {
+            builtin_count(entry, is_retraction, &mut callout_data[idx])
+        }
         CALLOUT_BUILTIN_CMP => {
             let lv = resolve_callout_arg(&entry.args[0], &ext.callout_list, callout_data);
             let rv = resolve_callout_arg(&entry.args[2], &ext.callout_list, callout_data);
@@ -2243,6 +2517,10 @@ fn run_builtin_callout(
             }
             ONIG_CALLOUT_SUCCESS
         }
+        CALLOUT_BUILTIN_ERROR => match entry.args.first() {
+            Some(CalloutArg::Long(n)) if *n < 0 => *n as i32,
+            _ => ONIGERR_INVALID_CALLOUT_BODY,
+        },
         _ => ONIG_CALLOUT_SUCCESS,
     }
 }
@@ -2291,7 +2569,7 @@ fn run_builtin_callout_retraction(
             }
             // retraction + '>': no-op
         }
-        CALLOUT_BUILTIN_COUNT => {
+        CALLOUT_BUILTIN_COUNT | CALLOUT_BUILTIN_TOTAL_COUNT => {
             let count_type = if !entry.args.is_empty() {
                 match &entry.args[0] {
                     CalloutArg::Char(c) => *c,
@@ -2494,14 +2772,16 @@ fn match_at(
     let num_mem = reg.num_mem as usize;
     let enc = reg.enc;
     let options = msa.options;
+    let match_end = msa.match_end;
+    let group0_only = msa.group0_only;
 
     // Reuse stack and capture-group arrays from MatchArg (avoids heap alloc per call)
-    let mut stack = std::mem::take(&mut msa.stack);
+    let mut stack = core::mem::take(&mut msa.stack);
     stack.clear();
-    let mut mem_start_stk = std::mem::take(&mut msa.mem_start_stk);
+    let mut mem_start_stk = core::mem::take(&mut msa.mem_start_stk);
     mem_start_stk.clear();
     mem_start_stk.resize(num_mem + 1, MemPtr::Invalid);
-    let mut mem_end_stk = std::mem::take(&mut msa.mem_end_stk);
+    let mut mem_end_stk = core::mem::take(&mut msa.mem_end_stk);
     mem_end_stk.clear();
     mem_end_stk.resize(num_mem + 1, MemPtr::Invalid);
 
@@ -2514,11 +2794,33 @@ fn match_at(
     let mut retry_in_match_counter: u64 = 0;
     let match_stack_limit = msa.match_stack_limit;
     let time_limit_ms = msa.time_limit;
+    let subexp_call_max_nest_level = SUBEXP_CALL_MAX_NEST_LEVEL.load(Ordering::Relaxed);
+    let mem_hooked = onig_get_memory_hook().is_some();
+
+    // Callout data: per-callout mutable slots (indexed by callout num - 1).
+    // TOTAL_COUNT keeps counting across match attempts (and across searches
+    // sharing an OnigMatchParam); everything else starts each attempt at 0.
+    let mut callout_data = core::mem::take(&mut msa.callout_data);
+    if let Some(ref ext) = reg.extp {
+        callout_data.resize(ext.callout_num as usize, [0; ONIG_CALLOUT_DATA_SLOT_NUM]);
+        for (slots, entry) in callout_data.iter_mut().zip(&ext.callout_list) {
+            if entry.builtin_id != CALLOUT_BUILTIN_TOTAL_COUNT {
+                *slots = [0; ONIG_CALLOUT_DATA_SLOT_NUM];
+            }
+        }
+    }
 
-    // Callout data: per-callout mutable slots (indexed by callout num - 1)
-    let callout_count = reg.extp.as_ref().map_or(0, |e| e.callout_num as usize);
-    let mut callout_data: Vec<[i64; ONIG_CALLOUT_DATA_SLOT_NUM]> =
-        vec![[0i64; ONIG_CALLOUT_DATA_SLOT_NUM]; callout_count];
+    // Start positions of simple empty checks (never on the stack)
+    let mut empty_check_pos = core::mem::take(&mut msa.empty_check_pos);
+    empty_check_pos.resize(reg.num_empty_check as usize, 0);
+
+    // End positions offered by a custom opcode handler
+    #[cfg(feature = "unstable-opcodes")]
+    let mut opcode_ends: Vec<usize> = Vec::new();
+
+    // Counters of this attempt (see stats.rs)
+    #[cfg(feature = "exec-stats")]
+    let mut tally = crate::stats::Tally::new();
 
     // Push bottom sentinel (like C's STACK_PUSH_BOTTOM with FinishCode)
     stack.push(StackEntry::Alt {
@@ -2528,428 +2830,443 @@ fn match_at(
         is_super: false,
     });
 
-    // ---- Main dispatch loop ----
-    loop {
-        if p >= reg.ops.len() {
-            break;
-        }
+    // Stack length at which the next opcode must call grow_stack(). A stack
+    // kept from the previous attempt usually has room already; with a memory
+    // hook the first opcode asks grow_stack() so the hook sees the capacity.
+    let mut grow_at = if mem_hooked {
+        0
+    } else {
+        stack_grow_at(stack.capacity(), match_stack_limit as usize)
+    };
 
-        // Stack limit check (checked once per opcode, like C's STACK_PUSH macro)
-        if match_stack_limit != 0 && stack.len() >= match_stack_limit as usize {
-            best_len = ONIGERR_MATCH_STACK_LIMIT_OVER;
-            break;
+    // ---- Main dispatch loop ----
+    'exec: while let Some(op) = reg.ops.get(p) {
+        // Stack room and limit, checked once per opcode (see grow_stack)
+        if stack.len() >= grow_at {
+            match grow_stack(
+                &mut stack,
+                match_stack_limit,
+                mem_hooked,
+                &mut msa.stack_mem,
+            ) {
+                Ok(at) => grow_at = at,
+                Err(e) => {
+                    best_len = e;
+                    break;
+                }
+            }
         }
 
-        let opcode = reg.ops[p].opcode;
+        let opcode = op.opcode;
         let mut goto_fail = false;
-
-        match opcode {
-            // ================================================================
-            // OP_FINISH - reached bottom sentinel, return result
-            // ================================================================
-            OpCode::Finish => {
-                break;
+        #[cfg(feature = "exec-stats")]
+        tally.op(opcode);
+
+        // Bind the operands of the current opcode: `operand!(OperationPayload::X
+        // { .. } = op.payload)`. regcomp pairs every opcode with its own
+        // payload; an opcode that arrives without it fails like a mismatch.
+        'op: {
+            macro_rules! operand {
+                ($pat:pat = $payload:expr) => {
+                    let $pat = $payload else {
+                        goto_fail = true;
+                        break 'op;
+                    };
+                };
             }
 
-            // ================================================================
-            // OP_END - successful match, populate region
-            // ================================================================
-            OpCode::End => {
-                // Check MATCH_WHOLE_STRING option
-                if opton_match_whole_string(options) && s < end {
-                    goto_fail = true;
-                } else {
-                    let n = (s - sstart) as i32;
-                    if n == 0 && opton_find_not_empty(options) {
-                        goto_fail = true;
-                    } else if n > best_len {
-                        best_len = n;
-
-                        // Populate region with capture groups
-                        if let Some(ref mut region) = msa.region {
-                            region.resize(num_mem as i32 + 1);
-                            region.beg[0] = (keep - 0) as i32; // offset from str start
-                            region.end[0] = s as i32;
-
-                            for i in 1..=num_mem {
-                                if let Some(mem_end) = get_mem_end(reg, &stack, &mem_end_stk, i) {
-                                    let mem_start = get_mem_start(reg, &stack, &mem_start_stk, i);
-                                    region.beg[i] =
-                                        mem_start.map(|v| v as i32).unwrap_or(ONIG_REGION_NOTPOS);
-                                    region.end[i] = mem_end as i32;
-                                } else {
-                                    region.beg[i] = ONIG_REGION_NOTPOS;
-                                    region.end[i] = ONIG_REGION_NOTPOS;
-                                }
-                            }
+            match opcode {
+                // ================================================================
+                // OP_FINISH - reached bottom sentinel, return result
+                // ================================================================
+                OpCode::Finish => {
+                    break 'exec;
+                }
 
-                            // Build capture history tree
-                            if USE_CAPTURE_HISTORY && reg.capture_history != 0 {
-                                let node = if region.history_root.is_none() {
-                                    region.history_root =
-                                        Some(Box::new(OnigCaptureTreeNode::new()));
-                                    region.history_root.as_mut().unwrap()
-                                } else {
-                                    let root = region.history_root.as_mut().unwrap();
-                                    root.clear();
-                                    root
-                                };
-                                node.group = 0;
-                                node.beg = keep as i32;
-                                node.end = s as i32;
-                                let mut stkp = 0usize;
-                                let stk_top = stack.len();
-                                let r = make_capture_history_tree(
-                                    node, &mut stkp, &stack, stk_top, reg,
+                // ================================================================
+                // OP_END - successful match, populate region
+                // ================================================================
+                OpCode::End => {
+                    // Check MATCH_WHOLE_STRING option
+                    if opton_match_whole_string(options) && s < end
+                        || match_end.is_some_and(|e| s != e)
+                    {
+                        goto_fail = true;
+                    } else {
+                        let n = (s - sstart) as i32;
+                        if n == 0 && opton_find_not_empty(options) {
+                            goto_fail = true;
+                        } else if n > best_len {
+                            best_len = n;
+
+                            // Populate region with capture groups
+                            if let Some(ref mut region) = msa.region {
+                                let r = set_match_region(
+                                    region,
+                                    reg,
+                                    &stack,
+                                    &mem_start_stk,
+                                    &mem_end_stk,
+                                    keep,
+                                    s,
+                                    options,
+                                    group0_only,
                                 );
                                 if r < 0 {
                                     best_len = r;
-                                    break;
+                                    break 'exec;
                                 }
                             }
-                        }
 
-                        // For non-FIND_LONGEST, return immediately
-                        if !opton_find_longest(options) {
-                            msa.stack = stack;
-                            msa.mem_start_stk = mem_start_stk;
-                            msa.mem_end_stk = mem_end_stk;
-                            return best_len;
-                        }
+                            // For non-FIND_LONGEST, return immediately
+                            if !opton_find_longest(options) {
+                                msa.stack = stack;
+                                msa.mem_start_stk = mem_start_stk;
+                                msa.mem_end_stk = mem_end_stk;
+                                msa.callout_data = callout_data;
+                                msa.empty_check_pos = empty_check_pos;
+                                #[cfg(feature = "exec-stats")]
+                                reg.exec_stats.add(&tally, best_len);
+                                return best_len;
+                            }
 
-                        // FIND_LONGEST: save best and continue searching
-                        msa.best_len = best_len;
-                        msa.best_s = sstart;
-                        goto_fail = true; // backtrack to try longer matches
-                    } else {
-                        // FIND_LONGEST but shorter/equal match: backtrack for more
-                        goto_fail = true;
+                            // FIND_LONGEST: save best and continue searching
+                            msa.best_len = best_len;
+                            msa.best_s = sstart;
+                            goto_fail = true; // backtrack to try longer matches
+                        } else {
+                            // FIND_LONGEST but shorter/equal match: backtrack for more
+                            goto_fail = true;
+                        }
                     }
                 }
-            }
 
-            // ================================================================
-            // OP_STR1..STR5 - match 1-5 literal bytes
-            // ================================================================
-            OpCode::Str1 => {
-                if right_range.saturating_sub(s) < 1 {
-                    goto_fail = true;
-                } else if let OperationPayload::Exact { s: ref exact } = reg.ops[p].payload {
-                    if exact[0] != str_data[s] {
+                // ================================================================
+                // OP_STR1..STR5 - match 1-5 literal bytes
+                // ================================================================
+                OpCode::Str1 => {
+                    if right_range.saturating_sub(s) < 1 {
                         goto_fail = true;
                     } else {
-                        s += 1;
-                        p += 1;
+                        operand!(OperationPayload::Exact { s: ref exact } = op.payload);
+                        if exact[0] != str_data[s] {
+                            goto_fail = true;
+                        } else {
+                            s += 1;
+                            p += 1;
+                        }
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::Str2 => {
-                if right_range.saturating_sub(s) < 2 {
-                    goto_fail = true;
-                } else if let OperationPayload::Exact { s: ref exact } = reg.ops[p].payload {
-                    if exact[0] != str_data[s] || exact[1] != str_data[s + 1] {
+                OpCode::Str2 => {
+                    if right_range.saturating_sub(s) < 2 {
                         goto_fail = true;
                     } else {
-                        s += 2;
-                        p += 1;
+                        operand!(OperationPayload::Exact { s: ref exact } = op.payload);
+                        if exact[0] != str_data[s] || exact[1] != str_data[s + 1] {
+                            goto_fail = true;
+                        } else {
+                            s += 2;
+                            p += 1;
+                        }
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::Str3 => {
-                if right_range.saturating_sub(s) < 3 {
-                    goto_fail = true;
-                } else if let OperationPayload::Exact { s: ref exact } = reg.ops[p].payload {
-                    if exact[0] != str_data[s]
-                        || exact[1] != str_data[s + 1]
-                        || exact[2] != str_data[s + 2]
-                    {
+                OpCode::Str3 => {
+                    if right_range.saturating_sub(s) < 3 {
                         goto_fail = true;
                     } else {
-                        s += 3;
-                        p += 1;
+                        operand!(OperationPayload::Exact { s: ref exact } = op.payload);
+                        if exact[0] != str_data[s]
+                            || exact[1] != str_data[s + 1]
+                            || exact[2] != str_data[s + 2]
+                        {
+                            goto_fail = true;
+                        } else {
+                            s += 3;
+                            p += 1;
+                        }
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::Str4 => {
-                if right_range.saturating_sub(s) < 4 {
-                    goto_fail = true;
-                } else if let OperationPayload::Exact { s: ref exact } = reg.ops[p].payload {
-                    if exact[0] != str_data[s]
-                        || exact[1] != str_data[s + 1]
-                        || exact[2] != str_data[s + 2]
-                        || exact[3] != str_data[s + 3]
-                    {
+                OpCode::Str4 => {
+                    if right_range.saturating_sub(s) < 4 {
                         goto_fail = true;
                     } else {
-                        s += 4;
-                        p += 1;
+                        operand!(OperationPayload::Exact { s: ref exact } = op.payload);
+                        if exact[0] != str_data[s]
+                            || exact[1] != str_data[s + 1]
+                            || exact[2] != str_data[s + 2]
+                            || exact[3] != str_data[s + 3]
+                        {
+                            goto_fail = true;
+                        } else {
+                            s += 4;
+                            p += 1;
+                        }
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::Str5 => {
-                if right_range.saturating_sub(s) < 5 {
-                    goto_fail = true;
-                } else if let OperationPayload::Exact { s: ref exact } = reg.ops[p].payload {
-                    if exact[0] != str_data[s]
-                        || exact[1] != str_data[s + 1]
-                        || exact[2] != str_data[s + 2]
-                        || exact[3] != str_data[s + 3]
-                        || exact[4] != str_data[s + 4]
-                    {
+                OpCode::Str5 => {
+                    if right_range.saturating_sub(s) < 5 {
                         goto_fail = true;
                     } else {
-                        s += 5;
-                        p += 1;
+                        operand!(OperationPayload::Exact { s: ref exact } = op.payload);
+                        if exact[0] != str_data[s]
+                            || exact[1] != str_data[s + 1]
+                            || exact[2] != str_data[s + 2]
+                            || exact[3] != str_data[s + 3]
+                            || exact[4] != str_data[s + 4]
+                        {
+                            goto_fail = true;
+                        } else {
+                            s += 5;
+                            p += 1;
+                        }
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::StrN => {
-                if let OperationPayload::ExactN { s: ref exact, n } = reg.ops[p].payload {
+                OpCode::StrN => {
+                    operand!(OperationPayload::ExactN { s: ref exact, n } = op.payload);
                     let n = n as usize;
                     if right_range.saturating_sub(s) < n {
                         goto_fail = true;
-                    } else if str_data[s..s + n] != exact[..n] {
+                    } else if !literal_eq(&str_data[s..s + n], &exact[..n]) {
                         goto_fail = true;
                     } else {
                         s += n;
                         p += 1;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // MB string opcodes (for multibyte encodings)
-            OpCode::StrMb2n1
-            | OpCode::StrMb2n2
-            | OpCode::StrMb2n3
-            | OpCode::StrMb2n
-            | OpCode::StrMb3n
-            | OpCode::StrMbn => {
-                // Multi-byte string comparison. ExactLenN.n = total byte count.
-                if let OperationPayload::ExactLenN {
+                // MB string opcodes (for multibyte encodings)
+                OpCode::StrMb2n1
+                | OpCode::StrMb2n2
+                | OpCode::StrMb2n3
+                | OpCode::StrMb2n
+                | OpCode::StrMb3n
+                | OpCode::StrMbn => {
+                    // Multi-byte string comparison. ExactLenN.n = total byte count.
+                    operand!(OperationPayload::ExactLenN {
                     s: ref exact, n, ..
-                } = reg.ops[p].payload
-                {
+                } = op.payload);
                     let byte_len = n as usize;
                     if right_range.saturating_sub(s) < byte_len {
                         goto_fail = true;
-                    } else if str_data[s..s + byte_len] != exact[..byte_len] {
+                    } else if !literal_eq(&str_data[s..s + byte_len], &exact[..byte_len]) {
                         goto_fail = true;
                     } else {
                         s += byte_len;
                         p += 1;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // OP_CCLASS / OP_CCLASS_NOT - character class matching
-            // ================================================================
-            OpCode::CClass => {
-                if right_range.saturating_sub(s) < 1 {
-                    goto_fail = true;
-                } else if let OperationPayload::CClass { ref bsp } = reg.ops[p].payload {
-                    if !bitset_at(bsp, str_data[s] as usize) {
+                // ================================================================
+                // OP_CCLASS / OP_CCLASS_NOT - character class matching
+                // ================================================================
+                OpCode::CClass => {
+                    if right_range.saturating_sub(s) < 1 {
                         goto_fail = true;
                     } else {
-                        s += enclen(enc, str_data, s);
-                        p += 1;
+                        operand!(OperationPayload::CClass { ref bsp } = op.payload);
+                        if !bitset_at(bsp, str_data[s] as usize) {
+                            goto_fail = true;
+                        } else {
+                            s += enclen(enc, str_data, s);
+                            p += 1;
+                        }
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::CClassNot => {
-                if right_range.saturating_sub(s) < 1 {
-                    goto_fail = true;
-                } else if let OperationPayload::CClass { ref bsp } = reg.ops[p].payload {
-                    if bitset_at(bsp, str_data[s] as usize) {
+                OpCode::CClassNot => {
+                    if right_range.saturating_sub(s) < 1 {
                         goto_fail = true;
                     } else {
-                        s += enclen(enc, str_data, s);
-                        p += 1;
+                        operand!(OperationPayload::CClass { ref bsp } = op.payload);
+                        if bitset_at(bsp, str_data[s] as usize) {
+                            goto_fail = true;
+                        } else {
+                            s += enclen(enc, str_data, s);
+                            p += 1;
+                        }
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // MB character class (multibyte)
-            OpCode::CClassMb => {
-                if s >= right_range {
-                    goto_fail = true;
-                } else if let OperationPayload::CClassMb { ref mb } = reg.ops[p].payload {
-                    let mb_len = enclen(enc, str_data, s);
-                    if right_range.saturating_sub(s) < mb_len {
+                // MB character class (multibyte)
+                OpCode::CClassMb => {
+                    if s >= right_range {
                         goto_fail = true;
                     } else {
-                        let code = enc.mbc_to_code(&str_data[s..], end);
-                        if !is_in_code_range(mb, code) {
+                        operand!(OperationPayload::CClassMb { ref mb } = op.payload);
+                        let mb_len = enclen(enc, str_data, s);
+                        if right_range.saturating_sub(s) < mb_len {
                             goto_fail = true;
                         } else {
-                            s += mb_len;
-                            p += 1;
+                            let code = enc.mbc_to_code(&str_data[s..], end);
+                            if !is_in_code_range(mb, code) {
+                                goto_fail = true;
+                            } else {
+                                s += mb_len;
+                                p += 1;
+                            }
                         }
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::CClassMbNot => {
-                if s >= right_range {
-                    goto_fail = true;
-                } else if let OperationPayload::CClassMb { ref mb } = reg.ops[p].payload {
-                    let mb_len = enclen(enc, str_data, s);
-                    if right_range.saturating_sub(s) < mb_len {
+                OpCode::CClassMbNot => {
+                    if s >= right_range {
                         goto_fail = true;
                     } else {
-                        let code = enc.mbc_to_code(&str_data[s..], end);
-                        if is_in_code_range(mb, code) {
+                        operand!(OperationPayload::CClassMb { ref mb } = op.payload);
+                        let mb_len = enclen(enc, str_data, s);
+                        if right_range.saturating_sub(s) < mb_len {
                             goto_fail = true;
                         } else {
-                            s += mb_len;
-                            p += 1;
+                            let code = enc.mbc_to_code(&str_data[s..], end);
+                            if is_in_code_range(mb, code) {
+                                goto_fail = true;
+                            } else {
+                                s += mb_len;
+                                p += 1;
+                            }
                         }
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // Mixed character class (single-byte bitset + multibyte ranges)
-            OpCode::CClassMix | OpCode::CClassMixNot => {
-                let not = opcode == OpCode::CClassMixNot;
-                if s >= right_range {
-                    goto_fail = true;
-                } else if let OperationPayload::CClassMix { ref bsp, ref mb } = reg.ops[p].payload {
-                    let in_class = if enc.mbc_enc_len(&str_data[s..]) > 1 {
-                        let code = enc.mbc_to_code(&str_data[s..], end);
-                        if is_in_code_range(mb, code) {
-                            true
-                        } else if (code as usize) < SINGLE_BYTE_SIZE {
-                            bitset_at(bsp, code as usize)
-                        } else {
-                            false
-                        }
+                // Mixed character class (single-byte bitset + multibyte ranges)
+                OpCode::CClassMix | OpCode::CClassMixNot => {
+                    let not = opcode == OpCode::CClassMixNot;
+                    if s >= right_range {
+                        goto_fail = true;
                     } else {
-                        let c = str_data[s];
-                        if (c as usize) < SINGLE_BYTE_SIZE {
-                            bitset_at(bsp, c as usize)
+                        operand!(OperationPayload::CClassMix { ref bsp, ref mb } = op.payload);
+                        let in_class = if enc.mbc_enc_len(&str_data[s..]) > 1 {
+                            let code = enc.mbc_to_code(&str_data[s..], end);
+                            if is_in_code_range(mb, code) {
+                                true
+                            } else if (code as usize) < SINGLE_BYTE_SIZE {
+                                bitset_at(bsp, code as usize)
+                            } else {
+                                false
+                            }
                         } else {
-                            false
+                            let c = str_data[s];
+                            if (c as usize) < SINGLE_BYTE_SIZE {
+                                bitset_at(bsp, c as usize)
+                            } else {
+                                false
+                            }
+                        };
+                        if in_class == not {
+                            goto_fail = true;
+                        } else {
+                            s += enclen(enc, str_data, s);
+                            p += 1;
                         }
-                    };
-                    if in_class == not {
-                        goto_fail = true;
-                    } else {
-                        s += enclen(enc, str_data, s);
-                        p += 1;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // OP_ANYCHAR / OP_ANYCHAR_ML - match any character
-            // ================================================================
-            OpCode::AnyChar => {
-                if right_range.saturating_sub(s) < 1 {
-                    goto_fail = true;
-                } else {
-                    let n = enclen(enc, str_data, s);
-                    if right_range.saturating_sub(s) < n {
+                // Class with every code point below U+0800 (bitmaps only)
+                OpCode::CClassSmall | OpCode::CClassSmallNot => {
+                    let not = opcode == OpCode::CClassSmallNot;
+                    if s >= right_range {
                         goto_fail = true;
-                    } else if enc.is_mbc_newline(&str_data[s..], end) {
-                        goto_fail = true; // ANYCHAR doesn't match newline
                     } else {
-                        s += n;
-                        p += 1;
+                        operand!(OperationPayload::CClassSmall { ref bsp, ref codes } = op.payload);
+                        let mb_len = enclen(enc, str_data, s);
+                        if right_range - s < mb_len {
+                            goto_fail = true;
+                        } else {
+                            let in_class = if mb_len == 1 {
+                                bitset_at(bsp, str_data[s] as usize)
+                            } else {
+                                small_code_set_at(codes, enc.mbc_to_code(&str_data[s..], end))
+                            };
+                            if in_class == not {
+                                goto_fail = true;
+                            } else {
+                                s += mb_len;
+                                p += 1;
+                            }
+                        }
                     }
                 }
-            }
 
-            OpCode::AnyCharMl => {
-                if right_range.saturating_sub(s) < 1 {
-                    goto_fail = true;
-                } else {
-                    let n = enclen(enc, str_data, s);
-                    if right_range.saturating_sub(s) < n {
+                // ================================================================
+                // OP_ANYCHAR / OP_ANYCHAR_ML - match any character
+                // ================================================================
+                OpCode::AnyChar => {
+                    if right_range.saturating_sub(s) < 1 {
                         goto_fail = true;
                     } else {
-                        s += n; // ANYCHAR_ML matches newlines too
-                        p += 1;
-                    }
-                }
-            }
+                        let n = enclen(enc, str_data, s);
+                        if right_range.saturating_sub(s) < n {
+                            goto_fail = true;
+                        } else if is_newline(reg, str_data, s, end) {
+                            goto_fail = true; // ANYCHAR doesn't match newline
+                        } else {
+                            s += n;
+                            p += 1;
+                        }
+                    }
+                }
 
-            // ================================================================
-            // OP_ANYCHAR_STAR / OP_ANYCHAR_ML_STAR - .* optimization
-            // ================================================================
-            OpCode::AnyCharStar => {
-                // Use SIMD to find newline boundary, then push Alt entries up to that point.
-                // In UTF-8/ASCII, 0x0a can only appear as a complete single-byte character,
-                // so memchr finds the exact newline position.
-                let nl_limit =
-                    memchr::memchr(b'\n', &str_data[s..right_range]).map_or(right_range, |i| s + i);
-                while s < nl_limit {
-                    let n = enclen(enc, str_data, s);
-                    if s + n > nl_limit {
-                        break;
+                OpCode::AnyCharMl => {
+                    if right_range.saturating_sub(s) < 1 {
+                        goto_fail = true;
+                    } else {
+                        let n = enclen(enc, str_data, s);
+                        if right_range.saturating_sub(s) < n {
+                            goto_fail = true;
+                        } else {
+                            s += n; // ANYCHAR_ML matches newlines too
+                            p += 1;
+                        }
                     }
-                    stack.push(StackEntry::Alt {
-                        pcode: p + 1,
-                        pstr: s,
-                        zid: -1,
-                        is_super: false,
-                    });
-                    s += n;
                 }
-                p += 1;
-            }
 
-            OpCode::AnyCharMlStar => {
-                while s < right_range {
-                    let n = enclen(enc, str_data, s);
-                    if s + n > right_range {
-                        break;
+                // ================================================================
+                // OP_ANYCHAR_STAR / OP_ANYCHAR_ML_STAR - .* optimization
+                // ================================================================
+                OpCode::AnyCharStar => {
+                    // Find the newline boundary, then push Alt entries up to that point.
+                    let nl_limit = find_newline(reg, str_data, s, right_range, end);
+                    while s < nl_limit {
+                        let n = enclen(enc, str_data, s);
+                        if s + n > nl_limit {
+                            break;
+                        }
+                        stack.push(StackEntry::Alt {
+                            pcode: p + 1,
+                            pstr: s,
+                            zid: -1,
+                            is_super: false,
+                        });
+                        s += n;
                     }
-                    stack.push(StackEntry::Alt {
-                        pcode: p + 1,
-                        pstr: s,
-                        zid: -1,
-                        is_super: false,
-                    });
-                    s += n;
+                    p += 1;
+                }
+
+                OpCode::AnyCharMlStar => {
+                    while s < right_range {
+                        let n = enclen(enc, str_data, s);
+                        if s + n > right_range {
+                            break;
+                        }
+                        stack.push(StackEntry::Alt {
+                            pcode: p + 1,
+                            pstr: s,
+                            zid: -1,
+                            is_super: false,
+                        });
+                        s += n;
+                    }
+                    p += 1;
                 }
-                p += 1;
-            }
 
-            OpCode::AnyCharStarPeekNext => {
-                if let OperationPayload::AnyCharStarPeekNext { c } = reg.ops[p].payload {
-                    // Find newline boundary with SIMD
-                    let nl_limit = memchr::memchr(b'\n', &str_data[s..right_range])
-                        .map_or(right_range, |i| s + i);
+                OpCode::AnyCharStarPeekNext => {
+                    operand!(OperationPayload::AnyCharStarPeekNext { c } = op.payload);
+                    let nl_limit = find_newline(reg, str_data, s, right_range, end);
                     if c < 0x80 {
                         // ASCII peek byte: use SIMD to find all occurrences directly.
                         // In UTF-8, bytes < 0x80 can only be leading (single-byte) characters,
@@ -2985,13 +3302,10 @@ fn match_at(
                         }
                     }
                     p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::AnyCharMlStarPeekNext => {
-                if let OperationPayload::AnyCharStarPeekNext { c } = reg.ops[p].payload {
+                OpCode::AnyCharMlStarPeekNext => {
+                    operand!(OperationPayload::AnyCharStarPeekNext { c } = op.payload);
                     if c < 0x80 {
                         // ASCII peek byte: use SIMD to find all occurrences directly.
                         // No newline check needed in multiline mode.
@@ -3026,117 +3340,98 @@ fn match_at(
                         }
                     }
                     p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // Word / NoWord - \w and \W character type matching
-            // ================================================================
-            OpCode::Word => {
-                if s >= right_range {
-                    goto_fail = true;
-                } else if !is_word_char_at(enc, str_data, s, end) {
-                    goto_fail = true;
-                } else {
-                    s += enclen(enc, str_data, s);
-                    p += 1;
+                // ================================================================
+                // Word / NoWord - \w and \W character type matching
+                // ================================================================
+                OpCode::Word => {
+                    if s >= right_range {
+                        goto_fail = true;
+                    } else if !is_word_char_at(enc, str_data, s, end) {
+                        goto_fail = true;
+                    } else {
+                        s += enclen(enc, str_data, s);
+                        p += 1;
+                    }
                 }
-            }
 
-            OpCode::WordAscii => {
-                if right_range.saturating_sub(s) < 1 {
-                    goto_fail = true;
-                } else if !is_word_ascii(str_data[s]) {
-                    goto_fail = true;
-                } else {
-                    s += enclen(enc, str_data, s);
-                    p += 1;
+                OpCode::WordAscii => {
+                    if right_range.saturating_sub(s) < 1 {
+                        goto_fail = true;
+                    } else if !is_word_ascii(str_data[s]) {
+                        goto_fail = true;
+                    } else {
+                        s += enclen(enc, str_data, s);
+                        p += 1;
+                    }
                 }
-            }
 
-            OpCode::NoWord => {
-                if s >= right_range {
-                    goto_fail = true;
-                } else if is_word_char_at(enc, str_data, s, end) {
-                    goto_fail = true;
-                } else {
-                    s += enclen(enc, str_data, s);
-                    p += 1;
+                OpCode::NoWord => {
+                    if s >= right_range {
+                        goto_fail = true;
+                    } else if is_word_char_at(enc, str_data, s, end) {
+                        goto_fail = true;
+                    } else {
+                        s += enclen(enc, str_data, s);
+                        p += 1;
+                    }
                 }
-            }
 
-            OpCode::NoWordAscii => {
-                if right_range.saturating_sub(s) < 1 {
-                    goto_fail = true;
-                } else if is_word_ascii(str_data[s]) {
-                    goto_fail = true;
-                } else {
-                    s += enclen(enc, str_data, s);
-                    p += 1;
+                OpCode::NoWordAscii => {
+                    if right_range.saturating_sub(s) < 1 {
+                        goto_fail = true;
+                    } else if is_word_ascii(str_data[s]) {
+                        goto_fail = true;
+                    } else {
+                        s += enclen(enc, str_data, s);
+                        p += 1;
+                    }
                 }
-            }
 
-            // ================================================================
-            // Word boundary opcodes
-            // ================================================================
-            OpCode::WordBoundary => {
-                let mode = if let OperationPayload::WordBoundary { mode } = reg.ops[p].payload {
-                    mode
-                } else {
-                    0
-                };
-                if !is_word_boundary(enc, str_data, s, end, mode) {
-                    goto_fail = true;
-                } else {
-                    p += 1;
+                // ================================================================
+                // Word boundary opcodes
+                // ================================================================
+                OpCode::WordBoundary => {
+                    operand!(OperationPayload::WordBoundary { mode } = op.payload);
+                    if !is_word_boundary(enc, str_data, s, end, mode) {
+                        goto_fail = true;
+                    } else {
+                        p += 1;
+                    }
                 }
-            }
 
-            OpCode::NoWordBoundary => {
-                let mode = if let OperationPayload::WordBoundary { mode } = reg.ops[p].payload {
-                    mode
-                } else {
-                    0
-                };
-                if is_word_boundary(enc, str_data, s, end, mode) {
-                    goto_fail = true;
-                } else {
-                    p += 1;
+                OpCode::NoWordBoundary => {
+                    operand!(OperationPayload::WordBoundary { mode } = op.payload);
+                    if is_word_boundary(enc, str_data, s, end, mode) {
+                        goto_fail = true;
+                    } else {
+                        p += 1;
+                    }
                 }
-            }
 
-            OpCode::WordBegin => {
-                let mode = if let OperationPayload::WordBoundary { mode } = reg.ops[p].payload {
-                    mode
-                } else {
-                    0
-                };
-                if !is_word_begin(enc, str_data, s, end, mode) {
-                    goto_fail = true;
-                } else {
-                    p += 1;
+                OpCode::WordBegin => {
+                    operand!(OperationPayload::WordBoundary { mode } = op.payload);
+                    if !is_word_begin(enc, str_data, s, end, mode) {
+                        goto_fail = true;
+                    } else {
+                        p += 1;
+                    }
                 }
-            }
 
-            OpCode::WordEnd => {
-                let mode = if let OperationPayload::WordBoundary { mode } = reg.ops[p].payload {
-                    mode
-                } else {
-                    0
-                };
-                if !is_word_end(enc, str_data, s, end, mode) {
-                    goto_fail = true;
-                } else {
-                    p += 1;
+                OpCode::WordEnd => {
+                    operand!(OperationPayload::WordBoundary { mode } = op.payload);
+                    if !is_word_end(enc, str_data, s, end, mode) {
+                        goto_fail = true;
+                    } else {
+                        p += 1;
+                    }
                 }
-            }
 
-            OpCode::TextSegmentBoundary => {
-                if let OperationPayload::TextSegmentBoundary { boundary_type, not } =
-                    reg.ops[p].payload
-                {
+                OpCode::TextSegmentBoundary => {
+                    operand!(
+                        OperationPayload::TextSegmentBoundary { boundary_type, not } = op.payload
+                    );
                     let is_break = match boundary_type {
                         TextSegmentBoundaryType::ExtendedGraphemeCluster => {
                             crate::unicode::onigenc_egcb_is_break_position(enc, str_data, s, 0, end)
@@ -3151,87 +3446,86 @@ fn match_at(
                     } else {
                         goto_fail = true;
                     }
-                } else {
-                    goto_fail = true;
-                }
-            }
-
-            // ================================================================
-            // Position anchors
-            // ================================================================
-            OpCode::BeginBuf => {
-                if s != 0 {
-                    goto_fail = true;
-                } else if opton_notbol(options) {
-                    goto_fail = true;
-                } else if opton_not_begin_string(options) {
-                    goto_fail = true;
-                } else {
-                    p += 1;
-                }
-            }
-
-            OpCode::EndBuf => {
-                if s != end {
-                    goto_fail = true;
-                } else if opton_noteol(options) {
-                    goto_fail = true;
-                } else if opton_not_end_string(options) {
-                    goto_fail = true;
-                } else {
-                    p += 1;
                 }
-            }
 
-            OpCode::BeginLine => {
-                if s == 0 {
-                    if opton_notbol(options) {
+                // ================================================================
+                // Position anchors
+                // ================================================================
+                OpCode::BeginBuf => {
+                    if s != 0 {
+                        goto_fail = true;
+                    } else if opton_notbol(options) {
+                        goto_fail = true;
+                    } else if opton_not_begin_string(options) {
                         goto_fail = true;
                     } else {
                         p += 1;
                     }
-                } else if s > 0 && str_data[s - 1] == b'\n' {
-                    p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::EndLine => {
-                if s == end {
-                    if opton_noteol(options) {
+                OpCode::EndBuf => {
+                    if s != end {
+                        goto_fail = true;
+                    } else if opton_noteol(options) {
+                        goto_fail = true;
+                    } else if opton_not_end_string(options) {
                         goto_fail = true;
                     } else {
                         p += 1;
                     }
-                } else if enc.is_mbc_newline(&str_data[s..], end) {
-                    p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::SemiEndBuf => {
-                // Match end of string or before final newline
-                if s == end {
-                    if opton_noteol(options) || opton_not_end_string(options) {
-                        goto_fail = true;
-                    } else {
+                OpCode::BeginLine => {
+                    if s == 0 {
+                        if opton_notbol(options) {
+                            goto_fail = true;
+                        } else {
+                            p += 1;
+                        }
+                    } else if is_line_start(reg, str_data, s, end) {
                         p += 1;
+                    } else {
+                        goto_fail = true;
                     }
-                } else if s + 1 == end && str_data[s] == b'\n' {
-                    if opton_noteol(options) || opton_not_end_string(options) {
+                }
+
+                OpCode::EndLine => {
+                    if s == end {
+                        if opton_noteol(options) {
+                            goto_fail = true;
+                        } else {
+                            p += 1;
+                        }
+                    } else if is_line_end(reg, str_data, s, end) {
+                        p += 1;
+                    } else {
                         goto_fail = true;
+                    }
+                }
+
+                OpCode::SemiEndBuf => {
+                    // Match end of string or before final newline
+                    if s == end {
+                        if opton_noteol(options) || opton_not_end_string(options) {
+                            goto_fail = true;
+                        } else {
+                            p += 1;
+                        }
+                    } else if is_line_end(reg, str_data, s, end)
+                        && s + newline_len(reg, str_data, s, end) == end
+                    {
+                        if opton_noteol(options) || opton_not_end_string(options) {
+                            goto_fail = true;
+                        } else {
+                            p += 1;
+                        }
                     } else {
-                        p += 1;
+                        goto_fail = true;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::CheckPosition => {
-                if let OperationPayload::CheckPosition { check_type } = reg.ops[p].payload {
+                OpCode::CheckPosition => {
+                    operand!(OperationPayload::CheckPosition { check_type } = op.payload);
                     match check_type {
                         CheckPositionType::SearchStart => {
                             if s != msa.start || opton_not_begin_position(options) {
@@ -3248,62 +3542,59 @@ fn match_at(
                             }
                         }
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // Back references
-            // ================================================================
-            OpCode::BackRef1 => {
-                if num_mem >= 1 {
-                    if let (Some(ms), Some(me)) = (
-                        get_mem_start(reg, &stack, &mem_start_stk, 1),
-                        get_mem_end(reg, &stack, &mem_end_stk, 1),
-                    ) {
-                        let ref_len = me - ms;
-                        if right_range.saturating_sub(s) < ref_len {
-                            goto_fail = true;
-                        } else if str_data[s..s + ref_len] != str_data[ms..me] {
-                            goto_fail = true;
+                // ================================================================
+                // Back references
+                // ================================================================
+                OpCode::BackRef1 => {
+                    if num_mem >= 1 {
+                        if let (Some(ms), Some(me)) = (
+                            get_mem_start(reg, &stack, &mem_start_stk, 1),
+                            get_mem_end(reg, &stack, &mem_end_stk, 1),
+                        ) {
+                            let ref_len = me - ms;
+                            if right_range.saturating_sub(s) < ref_len {
+                                goto_fail = true;
+                            } else if str_data[s..s + ref_len] != str_data[ms..me] {
+                                goto_fail = true;
+                            } else {
+                                s += ref_len;
+                                p += 1;
+                            }
                         } else {
-                            s += ref_len;
-                            p += 1;
+                            goto_fail = true; // group not yet matched
                         }
                     } else {
-                        goto_fail = true; // group not yet matched
+                        goto_fail = true;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::BackRef2 => {
-                if num_mem >= 2 {
-                    if let (Some(ms), Some(me)) = (
-                        get_mem_start(reg, &stack, &mem_start_stk, 2),
-                        get_mem_end(reg, &stack, &mem_end_stk, 2),
-                    ) {
-                        let ref_len = me - ms;
-                        if right_range.saturating_sub(s) < ref_len {
-                            goto_fail = true;
-                        } else if str_data[s..s + ref_len] != str_data[ms..me] {
-                            goto_fail = true;
+                OpCode::BackRef2 => {
+                    if num_mem >= 2 {
+                        if let (Some(ms), Some(me)) = (
+                            get_mem_start(reg, &stack, &mem_start_stk, 2),
+                            get_mem_end(reg, &stack, &mem_end_stk, 2),
+                        ) {
+                            let ref_len = me - ms;
+                            if right_range.saturating_sub(s) < ref_len {
+                                goto_fail = true;
+                            } else if str_data[s..s + ref_len] != str_data[ms..me] {
+                                goto_fail = true;
+                            } else {
+                                s += ref_len;
+                                p += 1;
+                            }
                         } else {
-                            s += ref_len;
-                            p += 1;
+                            goto_fail = true;
                         }
                     } else {
                         goto_fail = true;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::BackRefN => {
-                if let OperationPayload::BackRefN { n1 } = reg.ops[p].payload {
+                OpCode::BackRefN => {
+                    operand!(OperationPayload::BackRefN { n1 } = op.payload);
                     let n1 = n1 as usize;
                     if n1 <= num_mem {
                         if let (Some(ms), Some(me)) = (
@@ -3325,13 +3616,10 @@ fn match_at(
                     } else {
                         goto_fail = true;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::BackRefNIc => {
-                if let OperationPayload::BackRefN { n1 } = reg.ops[p].payload {
+                OpCode::BackRefNIc => {
+                    operand!(OperationPayload::BackRefN { n1 } = op.payload);
                     let n1 = n1 as usize;
                     if n1 <= num_mem {
                         if let (Some(ms), Some(me)) = (
@@ -3363,13 +3651,10 @@ fn match_at(
                     } else {
                         goto_fail = true;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::BackRefMulti => {
-                if let OperationPayload::BackRefGeneral { num, ref ns, .. } = &reg.ops[p].payload {
+                OpCode::BackRefMulti => {
+                    operand!(OperationPayload::BackRefGeneral { num, ref ns, .. } = &op.payload);
                     let tlen = *num as usize;
                     let mut matched = false;
                     for i in 0..tlen {
@@ -3400,13 +3685,10 @@ fn match_at(
                     } else {
                         goto_fail = true;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::BackRefMultiIc => {
-                if let OperationPayload::BackRefGeneral { num, ref ns, .. } = &reg.ops[p].payload {
+                OpCode::BackRefMultiIc => {
+                    operand!(OperationPayload::BackRefGeneral { num, ref ns, .. } = &op.payload);
                     let tlen = *num as usize;
                     let mut matched = false;
                     for i in 0..tlen {
@@ -3445,13 +3727,10 @@ fn match_at(
                     } else {
                         goto_fail = true;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::BackRefCheck => {
-                if let OperationPayload::BackRefGeneral { num, ref ns, .. } = &reg.ops[p].payload {
+                OpCode::BackRefCheck => {
+                    operand!(OperationPayload::BackRefGeneral { num, ref ns, .. } = &op.payload);
                     let tlen = *num as usize;
                     let mut found = false;
                     for i in 0..tlen {
@@ -3471,18 +3750,14 @@ fn match_at(
                     } else {
                         goto_fail = true;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::BackRefWithLevel | OpCode::BackRefWithLevelIc => {
-                if let OperationPayload::BackRefGeneral {
+                OpCode::BackRefWithLevel | OpCode::BackRefWithLevelIc => {
+                    operand!(OperationPayload::BackRefGeneral {
                     num,
                     ref ns,
                     nest_level,
-                } = &reg.ops[p].payload
-                {
+                } = &op.payload);
                     let ignore_case = reg.ops[p].opcode == OpCode::BackRefWithLevelIc;
                     if backref_match_at_nested_level(
                         reg,
@@ -3500,18 +3775,14 @@ fn match_at(
                     } else {
                         goto_fail = true;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::BackRefCheckWithLevel => {
-                if let OperationPayload::BackRefGeneral {
+                OpCode::BackRefCheckWithLevel => {
+                    operand!(OperationPayload::BackRefGeneral {
                     num,
                     ref ns,
                     nest_level,
-                } = &reg.ops[p].payload
-                {
+                } = &op.payload);
                     let found = if backref_check_at_nested_level(&stack, *nest_level, *num, ns) {
                         true
                     } else if *nest_level == 0 {
@@ -3540,27 +3811,21 @@ fn match_at(
                     } else {
                         goto_fail = true;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // Memory (capture group) operations
-            // ================================================================
-            OpCode::MemStart => {
-                if let OperationPayload::MemoryStart { num } = reg.ops[p].payload {
+                // ================================================================
+                // Memory (capture group) operations
+                // ================================================================
+                OpCode::MemStart => {
+                    operand!(OperationPayload::MemoryStart { num } = op.payload);
                     let num = num as usize;
                     mem_start_stk[num] = MemPtr::Pos(s);
                     mem_end_stk[num] = MemPtr::Invalid;
                     p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::MemStartPush => {
-                if let OperationPayload::MemoryStart { num } = reg.ops[p].payload {
+                OpCode::MemStartPush => {
+                    operand!(OperationPayload::MemoryStart { num } = op.payload);
                     let num = num as usize;
                     let prev_start = mem_start_stk[num];
                     let prev_end = mem_end_stk[num];
@@ -3574,23 +3839,17 @@ fn match_at(
                     mem_start_stk[num] = MemPtr::StackIdx(si);
                     mem_end_stk[num] = MemPtr::Invalid;
                     p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::MemEnd => {
-                if let OperationPayload::MemoryEnd { num } = reg.ops[p].payload {
+                OpCode::MemEnd => {
+                    operand!(OperationPayload::MemoryEnd { num } = op.payload);
                     let num = num as usize;
                     mem_end_stk[num] = MemPtr::Pos(s);
                     p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::MemEndPush => {
-                if let OperationPayload::MemoryEnd { num } = reg.ops[p].payload {
+                OpCode::MemEndPush => {
+                    operand!(OperationPayload::MemoryEnd { num } = op.payload);
                     let num = num as usize;
                     let prev_start = mem_start_stk[num];
                     let prev_end = mem_end_stk[num];
@@ -3603,15 +3862,12 @@ fn match_at(
                     });
                     mem_end_stk[num] = MemPtr::StackIdx(si);
                     p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::MemEndPushRec => {
-                // Recursive capture end (push variant): find matching MEM_START,
-                // push MEM_END, update start/end tracking
-                if let OperationPayload::MemoryEnd { num } = reg.ops[p].payload {
+                OpCode::MemEndPushRec => {
+                    // Recursive capture end (push variant): find matching MEM_START,
+                    // push MEM_END, update start/end tracking
+                    operand!(OperationPayload::MemoryEnd { num } = op.payload);
                     let mem = num as usize;
                     let (start_ptr, _) =
                         stack_get_mem_start_for_rec(&stack, mem, reg.push_mem_start);
@@ -3625,15 +3881,12 @@ fn match_at(
                     mem_start_stk[mem] = start_ptr;
                     mem_end_stk[mem] = MemPtr::StackIdx(si);
                     p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::MemEndRec => {
-                // Recursive capture end (non-push variant): find matching MEM_START,
-                // update start/end, push MemEndMark for level tracking
-                if let OperationPayload::MemoryEnd { num } = reg.ops[p].payload {
+                OpCode::MemEndRec => {
+                    // Recursive capture end (non-push variant): find matching MEM_START,
+                    // update start/end, push MemEndMark for level tracking
+                    operand!(OperationPayload::MemoryEnd { num } = op.payload);
                     let mem = num as usize;
                     mem_end_stk[mem] = MemPtr::Pos(s);
                     let (start_ptr, _) =
@@ -3641,34 +3894,28 @@ fn match_at(
                     mem_start_stk[mem] = start_ptr;
                     stack.push(StackEntry::MemEndMark { zid: mem });
                     p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // OP_FAIL - backtrack
-            // ================================================================
-            OpCode::Fail => {
-                goto_fail = true;
-            }
+                // ================================================================
+                // OP_FAIL - backtrack
+                // ================================================================
+                OpCode::Fail => {
+                    goto_fail = true;
+                }
 
-            // ================================================================
-            // OP_JUMP - unconditional jump
-            // ================================================================
-            OpCode::Jump => {
-                if let OperationPayload::Jump { addr } = reg.ops[p].payload {
+                // ================================================================
+                // OP_JUMP - unconditional jump
+                // ================================================================
+                OpCode::Jump => {
+                    operand!(OperationPayload::Jump { addr } = op.payload);
                     p = (p as i32 + addr) as usize;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // OP_PUSH / OP_PUSH_SUPER - push choice point (alternation)
-            // ================================================================
-            OpCode::Push | OpCode::PushSuper => {
-                if let OperationPayload::Push { addr } = reg.ops[p].payload {
+                // ================================================================
+                // OP_PUSH / OP_PUSH_SUPER - push choice point (alternation)
+                // ================================================================
+                OpCode::Push | OpCode::PushSuper => {
+                    operand!(OperationPayload::Push { addr } = op.payload);
                     let alt_target = (p as i32 + addr) as usize;
                     let is_super = reg.ops[p].opcode == OpCode::PushSuper;
                     stack.push(StackEntry::Alt {
@@ -3678,49 +3925,37 @@ fn match_at(
                         is_super,
                     });
                     p += 1; // try main path first
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // OP_POP - discard top stack entry
-            // ================================================================
-            OpCode::Pop => {
-                stack.pop();
-                p += 1;
-            }
+                // ================================================================
+                // OP_POP - discard top stack entry
+                // ================================================================
+                OpCode::Pop => {
+                    stack.pop();
+                    p += 1;
+                }
 
-            // ================================================================
-            // OP_POP_TO_MARK - pop until Mark with matching id
-            // ================================================================
-            OpCode::PopToMark => {
-                if let OperationPayload::PopToMark { id } = reg.ops[p].payload {
-                    let id = id as usize;
-                    // Pop entries until we find the matching Mark, but don't
-                    // restore positions (unlike CutToMark)
-                    loop {
-                        if let Some(entry) = stack.pop() {
-                            if let StackEntry::Mark { zid, .. } = &entry {
-                                if *zid == id {
-                                    break;
-                                }
-                            }
-                        } else {
-                            break;
-                        }
-                    }
+                // ================================================================
+                // OP_POP_TO_MARK - pop until Mark with matching id
+                // ================================================================
+                OpCode::PopToMark => {
+                    operand!(OperationPayload::PopToMark { id } = op.payload);
+                    // Pop entries until the matching Mark, restoring captures
+                    // made inside (they would point at popped entries)
+                    stack_pop_to_mark(
+                        &mut stack,
+                        id as usize,
+                        &mut mem_start_stk,
+                        &mut mem_end_stk,
+                    );
                     p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // OP_PUSH_OR_JUMP_EXACT1 - optimized push for exact char
-            // ================================================================
-            OpCode::PushOrJumpExact1 => {
-                if let OperationPayload::PushOrJumpExact1 { addr, c } = reg.ops[p].payload {
+                // ================================================================
+                // OP_PUSH_OR_JUMP_EXACT1 - optimized push for exact char
+                // ================================================================
+                OpCode::PushOrJumpExact1 => {
+                    operand!(OperationPayload::PushOrJumpExact1 { addr, c } = op.payload);
                     if s < right_range && str_data[s] == c {
                         // Character matches: push alternative and continue
                         let alt_target = (p as i32 + addr) as usize;
@@ -3735,16 +3970,13 @@ fn match_at(
                         // Character doesn't match: jump
                         p = (p as i32 + addr) as usize;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // OP_PUSH_IF_PEEK_NEXT - push only if next char matches
-            // ================================================================
-            OpCode::PushIfPeekNext => {
-                if let OperationPayload::PushIfPeekNext { addr, c } = reg.ops[p].payload {
+                // ================================================================
+                // OP_PUSH_IF_PEEK_NEXT - push only if next char matches
+                // ================================================================
+                OpCode::PushIfPeekNext => {
+                    operand!(OperationPayload::PushIfPeekNext { addr, c } = op.payload);
                     if s < right_range && str_data[s] == c {
                         let alt_target = (p as i32 + addr) as usize;
                         stack.push(StackEntry::Alt {
@@ -3755,16 +3987,13 @@ fn match_at(
                         });
                     }
                     p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // OP_REPEAT / OP_REPEAT_NG - initialize repeat counter
-            // ================================================================
-            OpCode::Repeat | OpCode::RepeatNg => {
-                if let OperationPayload::Repeat { id, addr } = reg.ops[p].payload {
+                // ================================================================
+                // OP_REPEAT / OP_REPEAT_NG - initialize repeat counter
+                // ================================================================
+                OpCode::Repeat | OpCode::RepeatNg => {
+                    operand!(OperationPayload::Repeat { id, addr } = op.payload);
                     let id = id as usize;
                     // Push initial repeat count = 0
                     stack.push(StackEntry::RepeatInc { zid: id, count: 0 });
@@ -3789,20 +4018,17 @@ fn match_at(
                                 is_super: false,
                             });
                             p = alt_target;
-                            continue;
+                            continue 'exec;
                         }
                     }
                     p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // OP_REPEAT_INC / OP_REPEAT_INC_NG - increment and check repeat
-            // ================================================================
-            OpCode::RepeatInc => {
-                if let OperationPayload::RepeatInc { id } = reg.ops[p].payload {
+                // ================================================================
+                // OP_REPEAT_INC / OP_REPEAT_INC_NG - increment and check repeat
+                // ================================================================
+                OpCode::RepeatInc => {
+                    operand!(OperationPayload::RepeatInc { id } = op.payload);
                     let id = id as usize;
                     let count = stack_get_repeat_count(&stack, id) + 1;
                     let lower = reg.repeat_range[id].lower;
@@ -3826,13 +4052,10 @@ fn match_at(
                     }
                     // Count pushed AFTER Alt — gets popped on backtrack (correct for greedy)
                     stack.push(StackEntry::RepeatInc { zid: id, count });
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::RepeatIncNg => {
-                if let OperationPayload::RepeatInc { id } = reg.ops[p].payload {
+                OpCode::RepeatIncNg => {
+                    operand!(OperationPayload::RepeatInc { id } = op.payload);
                     let id = id as usize;
                     let count = stack_get_repeat_count(&stack, id) + 1;
                     let lower = reg.repeat_range[id].lower;
@@ -3856,26 +4079,20 @@ fn match_at(
                     } else {
                         p = body_start;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // OP_EMPTY_CHECK_START / END - detect empty match in loops
-            // ================================================================
-            OpCode::EmptyCheckStart => {
-                if let OperationPayload::EmptyCheckStart { mem } = reg.ops[p].payload {
+                // ================================================================
+                // OP_EMPTY_CHECK_START / END - detect empty match in loops
+                // ================================================================
+                OpCode::EmptyCheckStart => {
+                    operand!(OperationPayload::EmptyCheckStart { mem } = op.payload);
                     let mem = mem as usize;
                     stack.push(StackEntry::EmptyCheckStart { zid: mem, pstr: s });
                     p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::EmptyCheckEnd => {
-                if let OperationPayload::EmptyCheckEnd { mem, .. } = reg.ops[p].payload {
+                OpCode::EmptyCheckEnd => {
+                    operand!(OperationPayload::EmptyCheckEnd { mem, .. } = op.payload);
                     let mem = mem as usize;
                     let is_empty = stack_empty_check(&stack, mem, s);
                     p += 1;
@@ -3885,17 +4102,30 @@ fn match_at(
                         // Mirrors C: empty_check_found: INC_OP;
                         p += 1;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::EmptyCheckEndMemst | OpCode::EmptyCheckEndMemstPush => {
-                if let OperationPayload::EmptyCheckEnd {
-                    mem,
-                    empty_status_mem,
-                } = reg.ops[p].payload
-                {
+                OpCode::EmptyCheckStartSimple => {
+                    operand!(OperationPayload::EmptyCheckStart { mem } = op.payload);
+                    empty_check_pos[mem as usize] = s;
+                    p += 1;
+                }
+
+                OpCode::EmptyCheckEndSimple => {
+                    operand!(OperationPayload::EmptyCheckEnd { mem, .. } = op.payload);
+                    p += 1;
+                    if empty_check_pos[mem as usize] == s {
+                        // Empty loop: skip the op that would repeat it
+                        p += 1;
+                    }
+                }
+
+                OpCode::EmptyCheckEndMemst | OpCode::EmptyCheckEndMemstPush => {
+                    operand!(
+                        OperationPayload::EmptyCheckEnd {
+                            mem,
+                            empty_status_mem,
+                        } = op.payload
+                    );
                     let mem = mem as usize;
                     let is_empty = stack_empty_check_mem(
                         &stack,
@@ -3911,16 +4141,13 @@ fn match_at(
                         // Truly empty → skip next op (JUMP back)
                         p += 1;
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // OP_MOVE - move string position
-            // ================================================================
-            OpCode::Move => {
-                if let OperationPayload::Move { n } = reg.ops[p].payload {
+                // ================================================================
+                // OP_MOVE - move string position
+                // ================================================================
+                OpCode::Move => {
+                    operand!(OperationPayload::Move { n } = op.payload);
                     if n < 0 {
                         // Step back n characters (encoding-aware)
                         match onigenc_step_back(enc, 0, s, str_data, (-n) as usize) {
@@ -3944,21 +4171,19 @@ fn match_at(
                             }
                         }
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // OP_STEP_BACK_START / NEXT - lookbehind support
-            // ================================================================
-            OpCode::StepBackStart => {
-                if let OperationPayload::StepBackStart {
-                    initial,
-                    remaining,
-                    addr,
-                } = reg.ops[p].payload
-                {
+                // ================================================================
+                // OP_STEP_BACK_START / NEXT - lookbehind support
+                // ================================================================
+                OpCode::StepBackStart => {
+                    operand!(
+                        OperationPayload::StepBackStart {
+                            initial,
+                            remaining,
+                            addr,
+                        } = op.payload
+                    );
                     let initial = initial as usize;
                     // Step back 'initial' characters (encoding-aware)
                     if initial != 0 {
@@ -3985,58 +4210,52 @@ fn match_at(
                             p += 1;
                         }
                     }
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::StepBackNext => {
-                // last_alt_zid was set by the backtrack that jumped here
-                let mut remaining = last_alt_zid;
-                if remaining != INFINITE_LEN as i32 {
-                    remaining -= 1;
-                }
-                match onigenc_step_back(enc, 0, s, str_data, 1) {
-                    Some(new_s) => {
-                        s = new_s;
+                OpCode::StepBackNext => {
+                    // last_alt_zid was set by the backtrack that jumped here
+                    let mut remaining = last_alt_zid;
+                    if remaining != INFINITE_LEN as i32 {
+                        remaining -= 1;
                     }
-                    None => {
-                        goto_fail = true;
+                    match onigenc_step_back(enc, 0, s, str_data, 1) {
+                        Some(new_s) => {
+                            s = new_s;
+                        }
+                        None => {
+                            goto_fail = true;
+                        }
                     }
-                }
-                if !goto_fail {
-                    if remaining != 0 {
-                        stack.push(StackEntry::Alt {
-                            pcode: p,
-                            pstr: s,
-                            zid: remaining,
-                            is_super: false,
-                        });
+                    if !goto_fail {
+                        if remaining != 0 {
+                            stack.push(StackEntry::Alt {
+                                pcode: p,
+                                pstr: s,
+                                zid: remaining,
+                                is_super: false,
+                            });
+                        }
+                        p += 1;
                     }
-                    p += 1;
                 }
-            }
 
-            // ================================================================
-            // OP_MARK - push a named checkpoint
-            // ================================================================
-            OpCode::Mark => {
-                if let OperationPayload::Mark { id, save_pos } = reg.ops[p].payload {
+                // ================================================================
+                // OP_MARK - push a named checkpoint
+                // ================================================================
+                OpCode::Mark => {
+                    operand!(OperationPayload::Mark { id, save_pos } = op.payload);
                     let id = id as usize;
                     let pos = if save_pos { Some(s) } else { None };
                     stack.push(StackEntry::Mark { zid: id, pos });
                     p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // OP_CUT_TO_MARK - void entries to mark, optionally restore position
-            // C always uses STACK_TO_VOID_TO_MARK (not POP_TO_MARK)
-            // ================================================================
-            OpCode::CutToMark => {
-                if let OperationPayload::CutToMark { id, restore_pos } = reg.ops[p].payload {
+                // ================================================================
+                // OP_CUT_TO_MARK - void entries to mark, optionally restore position
+                // C always uses STACK_TO_VOID_TO_MARK (not POP_TO_MARK)
+                // ================================================================
+                OpCode::CutToMark => {
+                    operand!(OperationPayload::CutToMark { id, restore_pos } = op.payload);
                     let id = id as usize;
                     let saved_pos = stack_void_to_mark(&mut stack, id);
                     if restore_pos {
@@ -4045,16 +4264,13 @@ fn match_at(
                         }
                     }
                     p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // OP_SAVE_VAL - save a value on the stack
-            // ================================================================
-            OpCode::SaveVal => {
-                if let OperationPayload::SaveVal { save_type, id } = reg.ops[p].payload {
+                // ================================================================
+                // OP_SAVE_VAL - save a value on the stack
+                // ================================================================
+                OpCode::SaveVal => {
+                    operand!(OperationPayload::SaveVal { save_type, id } = op.payload);
                     let id = id as usize;
                     let v = match save_type {
                         SaveType::Keep => s,
@@ -4067,21 +4283,19 @@ fn match_at(
                         v,
                     });
                     p += 1;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            // ================================================================
-            // OP_UPDATE_VAR - update a variable from the stack
-            // ================================================================
-            OpCode::UpdateVar => {
-                if let OperationPayload::UpdateVar {
-                    var_type,
-                    id,
-                    clear,
-                } = reg.ops[p].payload
-                {
+                // ================================================================
+                // OP_UPDATE_VAR - update a variable from the stack
+                // ================================================================
+                OpCode::UpdateVar => {
+                    operand!(
+                        OperationPayload::UpdateVar {
+                            var_type,
+                            id,
+                            clear,
+                        } = op.payload
+                    );
                     let id = id as usize;
                     match var_type {
                         UpdateVarType::KeepFromStackLast => {
@@ -4114,100 +4328,138 @@ fn match_at(
                         }
                     }
                     p += 1;
-                } else {
-                    goto_fail = true;
-                }
-            }
-
-            // ================================================================
-            // OP_CALL / OP_RETURN - subroutine call/return
-            // ================================================================
-            OpCode::Call => {
-                if let OperationPayload::Call { addr } = reg.ops[p].payload {
-                    let addr = addr as usize;
-                    stack.push(StackEntry::CallFrame { ret_addr: p + 1 });
-                    p = addr;
-                } else {
-                    goto_fail = true;
                 }
-            }
 
-            OpCode::Return => {
-                // Search backwards for CallFrame, skipping nested Return markers.
-                // Each STK_RETURN increments the level; each STK_CALL_FRAME decrements.
-                let mut level = 0i32;
-                let mut ret_addr = None;
-                for i in (0..stack.len()).rev() {
-                    match &stack[i] {
-                        StackEntry::CallFrame { ret_addr: ra } => {
-                            if level == 0 {
-                                ret_addr = Some(*ra);
-                                break;
+                // ================================================================
+                // OP_CALL / OP_RETURN - subroutine call/return
+                // ================================================================
+                OpCode::Call => {
+                    operand!(OperationPayload::Call { addr } = op.payload);
+                    let nest = call_nest_level(&stack);
+                    if nest >= subexp_call_max_nest_level {
+                        goto_fail = true;
+                    } else {
+                        if msa.subexp_call_limit_in_search != 0 {
+                            msa.subexp_call_in_search_counter += 1;
+                            if msa.subexp_call_in_search_counter > msa.subexp_call_limit_in_search {
+                                best_len = ONIGERR_SUBEXP_CALL_LIMIT_IN_SEARCH_OVER;
+                                break 'exec;
                             }
-                            level -= 1;
-                        }
-                        StackEntry::Return => {
-                            level += 1;
                         }
-                        _ => {}
+                        stack.push(StackEntry::CallFrame {
+                            ret_addr: p + 1,
+                            nest: nest + 1,
+                        });
+                        p = addr as usize;
                     }
                 }
-                if let Some(ra) = ret_addr {
-                    stack.push(StackEntry::Return);
-                    p = ra;
-                } else {
-                    goto_fail = true;
-                }
-            }
 
-            // ================================================================
-            // Callout opcodes
-            // ================================================================
-            OpCode::CalloutContents => {
-                // Callout of contents: always succeeds (we don't execute user code)
-                let num = match &reg.ops[p].payload {
-                    OperationPayload::CalloutContents { num } => *num,
-                    _ => 0,
-                };
-                if let Some(ref ext) = reg.extp {
-                    if num >= 1 && (num as usize) <= ext.callout_list.len() {
-                        let entry = &ext.callout_list[(num - 1) as usize];
-                        if (entry.callout_in & CALLOUT_IN_RETRACTION) != 0 {
-                            stack.push(StackEntry::Callout {
-                                num,
-                                id: ONIG_NON_NAME_ID,
-                            });
+                OpCode::Return => {
+                    // Search backwards for CallFrame, skipping nested Return markers.
+                    // Each STK_RETURN increments the level; each STK_CALL_FRAME decrements.
+                    let mut level = 0i32;
+                    let mut ret_addr = None;
+                    for i in (0..stack.len()).rev() {
+                        match &stack[i] {
+                            StackEntry::CallFrame { ret_addr: ra, .. } => {
+                                if level == 0 {
+                                    ret_addr = Some(*ra);
+                                    break;
+                                }
+                                level -= 1;
+                            }
+                            StackEntry::Return => {
+                                level += 1;
+                            }
+                            _ => {}
                         }
                     }
+                    if let Some(ra) = ret_addr {
+                        stack.push(StackEntry::Return);
+                        p = ra;
+                    } else {
+                        goto_fail = true;
+                    }
                 }
-                p += 1;
-            }
-            OpCode::CalloutName => {
-                let (num, id) = match &reg.ops[p].payload {
-                    OperationPayload::CalloutName { num, id } => (*num, *id),
-                    _ => (0, 0),
-                };
-                let call_result =
-                    run_builtin_callout(reg, num, id, false, &mut callout_data, s, msa);
-                if call_result == ONIG_CALLOUT_FAIL {
-                    goto_fail = true;
-                } else {
-                    // Push retraction entry if needed
+
+                // ================================================================
+                // Callout opcodes
+                // ================================================================
+                OpCode::CalloutContents => {
+                    // Callout of contents: always succeeds (we don't execute user code)
+                    operand!(OperationPayload::CalloutContents { num } = op.payload);
                     if let Some(ref ext) = reg.extp {
                         if num >= 1 && (num as usize) <= ext.callout_list.len() {
                             let entry = &ext.callout_list[(num - 1) as usize];
                             if (entry.callout_in & CALLOUT_IN_RETRACTION) != 0 {
-                                stack.push(StackEntry::Callout { num, id });
+                                stack.push(StackEntry::Callout {
+                                    num,
+                                    id: ONIG_NON_NAME_ID,
+                                });
                             }
                         }
                     }
                     p += 1;
                 }
+                OpCode::CalloutName => {
+                    operand!(OperationPayload::CalloutName { num, id } = op.payload);
+                    let call_result =
+                        run_builtin_callout(reg, num, id, false, &mut callout_data, s, msa);
+                    if call_result == ONIG_CALLOUT_FAIL {
+                        goto_fail = true;
+                    } else if call_result < 0 {
+                        // (*ERROR): abort the search with the callout's code
+                        best_len = call_result;
+                        break 'exec;
+                    } else {
+                        // Push retraction entry if needed
+                        if let Some(ref ext) = reg.extp {
+                            if num >= 1 && (num as usize) <= ext.callout_list.len() {
+                                let entry = &ext.callout_list[(num - 1) as usize];
+                                if (entry.callout_in & CALLOUT_IN_RETRACTION) != 0 {
+                                    stack.push(StackEntry::Callout { num, id });
+                                }
+                            }
+                        }
+                        p += 1;
+                    }
+                }
+
+                // ================================================================
+                // OP_EXTENSION - custom opcode handler
+                // ================================================================
+                #[cfg(feature = "unstable-opcodes")]
+                OpCode::Extension => {
+                    operand!(OperationPayload::Extension { num } = op.payload);
+                    reg.opcodes
+                        .execute(num, &str_data[..end], sstart, s, &mut opcode_ends);
+                    opcode_ends.retain(|&e| e <= right_range);
+                    if let Some((&first, rest)) = opcode_ends.split_first() {
+                        // Push the alternatives so that the second one is tried first
+                        for &e in rest.iter().rev() {
+                            stack.push(StackEntry::Alt {
+                                pcode: p + 1,
+                                pstr: e,
+                                zid: -1,
+                                is_super: false,
+                            });
+                        }
+                        s = first;
+                        p += 1;
+                    } else {
+                        goto_fail = true;
+                    }
+                }
+                #[cfg(not(feature = "unstable-opcodes"))]
+                OpCode::Extension => goto_fail = true,
             }
         }
 
         // Handle failure (backtracking)
         if goto_fail {
+            // The bottom sentinel is not part of the depth
+            #[cfg(feature = "exec-stats")]
+            tally.backtrack(stack.len() - 1);
             // Retry limit check
             retry_in_match_counter += 1;
             if retry_limit_in_match != 0 && retry_in_match_counter > retry_limit_in_match {
@@ -4254,6 +4506,11 @@ fn match_at(
     msa.stack = stack;
     msa.mem_start_stk = mem_start_stk;
     msa.mem_end_stk = mem_end_stk;
+    msa.callout_data = callout_data;
+    msa.empty_check_pos = empty_check_pos;
+
+    #[cfg(feature = "exec-stats")]
+    reg.exec_stats.add(&tally, best_len);
 
     best_len
 }
@@ -4272,6 +4529,10 @@ fn match_at(
 /// - at: position to try matching at
 /// - region: optional region to fill with capture group positions
 /// - option: match options
+///
+/// Without a region, a pattern with no capture groups, backrefs,
+/// loops or lookarounds runs on a small fixed backtrack stack and does not
+/// allocate.
 pub fn onig_match(
     reg: &RegexType,
     str_data: &[u8],
@@ -4281,6 +4542,34 @@ pub fn onig_match(
     option: OnigOptionType,
 ) -> (i32, Option<OnigRegion>) {
     let mut msa = MatchArg::new(reg, option, region, at);
+    onig_match_inner(reg, str_data, end, at, &mut msa)
+}
+
+/// onig_match() with the scratch memory of `state` (see [`ExecState`]).
+#[allow(clippy::too_many_arguments)]
+pub fn onig_match_with_state(
+    reg: &CompiledPattern,
+    state: &mut ExecState,
+    str_data: &[u8],
+    end: usize,
+    at: usize,
+    region: Option<OnigRegion>,
+    option: OnigOptionType,
+) -> (i32, Option<OnigRegion>) {
+    let msa = state.prepare(reg, option, region, at);
+    onig_match_inner(reg, str_data, end, at, msa)
+}
+
+pub(crate) fn onig_match_inner(
+    reg: &RegexType,
+    str_data: &[u8],
+    end: usize,
+    at: usize,
+    msa: &mut MatchArg,
+) -> (i32, Option<OnigRegion>) {
+    if end > ONIG_MAX_INPUT_LEN {
+        return (ONIGERR_TOO_LONG_INPUT, msa.region.take());
+    }
 
     if opton_check_validity_of_string(msa.options) {
         if !reg.enc.is_valid_mbc_string(&str_data[..end]) {
@@ -4288,12 +4577,19 @@ pub fn onig_match(
         }
     }
 
-    if let Some(ref mut r) = msa.region {
-        r.resize(reg.num_mem + 1);
-        r.clear();
-    }
+    msa.reset_region(reg);
 
-    let result = match_at(reg, str_data, end, end, at, &mut msa);
+    // Without a region a small program needs no heap at all
+    let small = msa.region.is_none()
+        && reg.small_match
+        && !opton_find_longest(msa.options)
+        && (msa.match_stack_limit == 0
+            || msa.match_stack_limit as usize > SMALL_MATCH_STACK_SIZE + 1);
+    let result = if small {
+        match_at_small(reg, str_data, end, at, msa)
+    } else {
+        match_at(reg, str_data, end, end, at, msa)
+    };
 
     // Handle FIND_LONGEST
     let result = if opton_find_longest(msa.options) && result == ONIG_MISMATCH {
@@ -4306,7 +4602,7 @@ pub fn onig_match(
         result
     };
 
-    (result, msa.region)
+    (result, msa.region.take())
 }
 
 #[cfg_attr(coverage_nightly, coverage(off))]
@@ -4317,46 +4613,455 @@ pub fn onig_match_with_param(
     at: usize,
     region: Option<OnigRegion>,
     option: OnigOptionType,
-    mp: &OnigMatchParam,
+    mp: &mut OnigMatchParam,
 ) -> (i32, Option<OnigRegion>) {
     let mut msa = MatchArg::from_param(reg, option, region, at, mp);
 
-    if opton_check_validity_of_string(msa.options) {
-        if !reg.enc.is_valid_mbc_string(&str_data[..end]) {
-            return (ONIGERR_INVALID_WIDE_CHAR_VALUE, msa.region.take());
-        }
-    }
+    if end > ONIG_MAX_INPUT_LEN {
+        return (ONIGERR_TOO_LONG_INPUT, msa.region);
+    }
+
+    if opton_check_validity_of_string(msa.options) {
+        if !reg.enc.is_valid_mbc_string(&str_data[..end]) {
+            return (ONIGERR_INVALID_WIDE_CHAR_VALUE, msa.region.take());
+        }
+    }
+
+    msa.reset_region(reg);
+
+    msa.callout_data = core::mem::take(&mut mp.callout_data);
+    let result = match_at(reg, str_data, end, end, at, &mut msa);
+    mp.callout_data = core::mem::take(&mut msa.callout_data);
+
+    let result = if opton_find_longest(msa.options) && result == ONIG_MISMATCH {
+        if msa.best_len >= 0 {
+            msa.best_len
+        } else {
+            result
+        }
+    } else {
+        result
+    };
+
+    (result, msa.region)
+}
+
+// ============================================================================
+// Small programs - onig_match without the heap (not in C)
+// ============================================================================
+
+/// Most choice points a small program may push; also the size of the
+/// backtrack stack match_at_small() keeps on the call stack.
+const SMALL_MATCH_STACK_SIZE: usize = 32;
+
+/// Whether onig_match() may run `reg` with match_at_small(): no capture
+/// groups, only character, anchor and choice opcodes (so no backrefs,
+/// repeat counters, lookarounds or callouts), and every jump forward. The
+/// match length is then bounded and each choice point is pushed at most
+/// once per path, so at most SMALL_MATCH_STACK_SIZE of them fit the stack.
+pub(crate) fn is_small_program(reg: &RegexType) -> bool {
+    if reg.num_mem != 0 || opton_find_longest(reg.options) {
+        return false;
+    }
+    let mut pushes = 0;
+    for op in &reg.ops {
+        let addr = match op.payload {
+            OperationPayload::Jump { addr }
+            | OperationPayload::Push { addr }
+            | OperationPayload::PushOrJumpExact1 { addr, .. }
+            | OperationPayload::PushIfPeekNext { addr, .. } => addr,
+            _ => 1,
+        };
+        if addr <= 0 {
+            return false;
+        }
+        match op.opcode {
+            OpCode::Push | OpCode::PushOrJumpExact1 | OpCode::PushIfPeekNext => pushes += 1,
+            OpCode::Finish
+            | OpCode::End
+            | OpCode::Str1
+            | OpCode::Str2
+            | OpCode::Str3
+            | OpCode::Str4
+            | OpCode::Str5
+            | OpCode::StrN
+            | OpCode::StrMb2n1
+            | OpCode::StrMb2n2
+            | OpCode::StrMb2n3
+            | OpCode::StrMb2n
+            | OpCode::StrMb3n
+            | OpCode::StrMbn
+            | OpCode::CClass
+            | OpCode::CClassNot
+            | OpCode::CClassMb
+            | OpCode::CClassMbNot
+            | OpCode::CClassMix
+            | OpCode::CClassMixNot
+            | OpCode::CClassSmall
+            | OpCode::CClassSmallNot
+            | OpCode::AnyChar
+            | OpCode::AnyCharMl
+            | OpCode::Word
+            | OpCode::WordAscii
+            | OpCode::NoWord
+            | OpCode::NoWordAscii
+            | OpCode::WordBoundary
+            | OpCode::NoWordBoundary
+            | OpCode::WordBegin
+            | OpCode::WordEnd
+            | OpCode::BeginBuf
+            | OpCode::EndBuf
+            | OpCode::BeginLine
+            | OpCode::EndLine
+            | OpCode::SemiEndBuf
+            | OpCode::Fail
+            | OpCode::Jump
+            | OpCode::Pop => {}
+            _ => return false,
+        }
+    }
+    pushes <= SMALL_MATCH_STACK_SIZE
+}
+
+/// match_at() for a program accepted by is_small_program(), with the
+/// backtrack stack in a fixed array and no region to fill. Options, the
+/// required match end and the retry and time limits work as in match_at().
+fn match_at_small(
+    reg: &RegexType,
+    str_data: &[u8],
+    end: usize,
+    sstart: usize,
+    msa: &mut MatchArg,
+) -> i32 {
+    let enc = reg.enc;
+    let options = msa.options;
+    let right_range = end;
+    let retry_limit_in_match = msa.retry_limit_in_match;
+    let mut retry_in_match_counter: u64 = 0;
+
+    let mut stack = [(0usize, 0usize); SMALL_MATCH_STACK_SIZE]; // (pcode, pstr)
+    let mut depth = 0;
+    let mut p: usize = 0;
+    let mut s: usize = sstart;
+    let mut result = ONIG_MISMATCH;
+    #[cfg(feature = "exec-stats")]
+    let mut tally = crate::stats::Tally::new();
+
+    'exec: while let Some(op) = reg.ops.get(p) {
+        let opcode = op.opcode;
+        #[cfg(feature = "exec-stats")]
+        tally.op(opcode);
+        let matched = 'op: {
+            // As in match_at(): a payload of the wrong shape fails the opcode.
+            macro_rules! operand {
+                ($pat:pat = $payload:expr) => {
+                    let $pat = $payload else {
+                        break 'op false;
+                    };
+                };
+            }
+
+            match opcode {
+                OpCode::Finish => break 'exec,
+
+                OpCode::End => {
+                    let n = (s - sstart) as i32;
+                    if opton_match_whole_string(options) && s < end
+                        || msa.match_end.is_some_and(|e| s != e)
+                        || n == 0 && opton_find_not_empty(options)
+                    {
+                        false
+                    } else {
+                        result = n;
+                        break 'exec;
+                    }
+                }
+
+                OpCode::Str1 | OpCode::Str2 | OpCode::Str3 | OpCode::Str4 | OpCode::Str5 => {
+                    operand!(OperationPayload::Exact { s: ref exact } = op.payload);
+                    let n = opcode as usize - OpCode::Str1 as usize + 1;
+                    if right_range.saturating_sub(s) >= n && str_data[s..s + n] == exact[..n] {
+                        s += n;
+                        true
+                    } else {
+                        false
+                    }
+                }
+
+                OpCode::StrN => {
+                    operand!(OperationPayload::ExactN { s: ref exact, n } = op.payload);
+                    let n = n as usize;
+                    if right_range.saturating_sub(s) >= n
+                        && literal_eq(&str_data[s..s + n], &exact[..n])
+                    {
+                        s += n;
+                        true
+                    } else {
+                        false
+                    }
+                }
+
+                OpCode::StrMb2n1
+                | OpCode::StrMb2n2
+                | OpCode::StrMb2n3
+                | OpCode::StrMb2n
+                | OpCode::StrMb3n
+                | OpCode::StrMbn => {
+                    operand!(OperationPayload::ExactLenN {
+                    s: ref exact, n, ..
+                } = op.payload);
+                    let n = n as usize;
+                    if right_range.saturating_sub(s) >= n
+                        && literal_eq(&str_data[s..s + n], &exact[..n])
+                    {
+                        s += n;
+                        true
+                    } else {
+                        false
+                    }
+                }
+
+                OpCode::CClass | OpCode::CClassNot => {
+                    operand!(OperationPayload::CClass { ref bsp } = op.payload);
+                    let not = opcode == OpCode::CClassNot;
+                    if s < right_range && bitset_at(bsp, str_data[s] as usize) != not {
+                        s += enclen(enc, str_data, s);
+                        true
+                    } else {
+                        false
+                    }
+                }
+
+                OpCode::CClassMb | OpCode::CClassMbNot => {
+                    operand!(OperationPayload::CClassMb { ref mb } = op.payload);
+                    let not = opcode == OpCode::CClassMbNot;
+                    if s >= right_range {
+                        false
+                    } else {
+                        let mb_len = enclen(enc, str_data, s);
+                        if right_range - s >= mb_len
+                            && is_in_code_range(mb, enc.mbc_to_code(&str_data[s..], end)) != not
+                        {
+                            s += mb_len;
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                }
+
+                OpCode::CClassMix | OpCode::CClassMixNot => {
+                    operand!(OperationPayload::CClassMix { ref bsp, ref mb } = op.payload);
+                    let not = opcode == OpCode::CClassMixNot;
+                    if s >= right_range {
+                        false
+                    } else {
+                        let in_class = if enc.mbc_enc_len(&str_data[s..]) > 1 {
+                            let code = enc.mbc_to_code(&str_data[s..], end);
+                            is_in_code_range(mb, code)
+                                || (code as usize) < SINGLE_BYTE_SIZE
+                                    && bitset_at(bsp, code as usize)
+                        } else {
+                            let c = str_data[s] as usize;
+                            c < SINGLE_BYTE_SIZE && bitset_at(bsp, c)
+                        };
+                        if in_class != not {
+                            s += enclen(enc, str_data, s);
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                }
+
+                OpCode::CClassSmall | OpCode::CClassSmallNot => {
+                    operand!(OperationPayload::CClassSmall { ref bsp, ref codes } = op.payload);
+                    let not = opcode == OpCode::CClassSmallNot;
+                    if s >= right_range {
+                        false
+                    } else {
+                        let mb_len = enclen(enc, str_data, s);
+                        let in_class = || {
+                            if mb_len == 1 {
+                                bitset_at(bsp, str_data[s] as usize)
+                            } else {
+                                small_code_set_at(codes, enc.mbc_to_code(&str_data[s..], end))
+                            }
+                        };
+                        if right_range - s >= mb_len && in_class() != not {
+                            s += mb_len;
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                }
+
+                OpCode::AnyChar | OpCode::AnyCharMl => {
+                    if s >= right_range {
+                        false
+                    } else {
+                        let n = enclen(enc, str_data, s);
+                        if right_range - s >= n
+                            && (opcode == OpCode::AnyCharMl || !is_newline(reg, str_data, s, end))
+                        {
+                            s += n;
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                }
+
+                OpCode::Word | OpCode::NoWord => {
+                    let not = opcode == OpCode::NoWord;
+                    if s < right_range && is_word_char_at(enc, str_data, s, end) != not {
+                        s += enclen(enc, str_data, s);
+                        true
+                    } else {
+                        false
+                    }
+                }
+
+                OpCode::WordAscii | OpCode::NoWordAscii => {
+                    let not = opcode == OpCode::NoWordAscii;
+                    if s < right_range && is_word_ascii(str_data[s]) != not {
+                        s += enclen(enc, str_data, s);
+                        true
+                    } else {
+                        false
+                    }
+                }
+
+                OpCode::WordBoundary | OpCode::NoWordBoundary => {
+                    operand!(OperationPayload::WordBoundary { mode } = op.payload);
+                    is_word_boundary(enc, str_data, s, end, mode)
+                        == (opcode == OpCode::WordBoundary)
+                }
+
+                OpCode::WordBegin => {
+                    operand!(OperationPayload::WordBoundary { mode } = op.payload);
+                    is_word_begin(enc, str_data, s, end, mode)
+                }
+
+                OpCode::WordEnd => {
+                    operand!(OperationPayload::WordBoundary { mode } = op.payload);
+                    is_word_end(enc, str_data, s, end, mode)
+                }
+
+                OpCode::BeginBuf => {
+                    s == 0 && !opton_notbol(options) && !opton_not_begin_string(options)
+                }
+
+                OpCode::EndBuf => {
+                    s == end && !opton_noteol(options) && !opton_not_end_string(options)
+                }
+
+                OpCode::BeginLine => {
+                    if s == 0 {
+                        !opton_notbol(options)
+                    } else {
+                        is_line_start(reg, str_data, s, end)
+                    }
+                }
+
+                OpCode::EndLine => {
+                    if s == end {
+                        !opton_noteol(options)
+                    } else {
+                        is_line_end(reg, str_data, s, end)
+                    }
+                }
+
+                OpCode::SemiEndBuf => {
+                    (s == end
+                        || is_line_end(reg, str_data, s, end)
+                            && s + newline_len(reg, str_data, s, end) == end)
+                        && !opton_noteol(options)
+                        && !opton_not_end_string(options)
+                }
+
+                OpCode::Jump => {
+                    operand!(OperationPayload::Jump { addr } = op.payload);
+                    p = (p as i32 + addr) as usize;
+                    continue 'exec;
+                }
+
+                OpCode::Push => {
+                    operand!(OperationPayload::Push { addr } = op.payload);
+                    stack[depth] = ((p as i32 + addr) as usize, s);
+                    depth += 1;
+                    true
+                }
+
+                OpCode::Pop => {
+                    depth -= 1;
+                    true
+                }
+
+                OpCode::PushOrJumpExact1 => {
+                    operand!(OperationPayload::PushOrJumpExact1 { addr, c } = op.payload);
+                    if s < right_range && str_data[s] == c {
+                        stack[depth] = ((p as i32 + addr) as usize, s);
+                        depth += 1;
+                        p += 1;
+                    } else {
+                        p = (p as i32 + addr) as usize;
+                    }
+                    continue 'exec;
+                }
+
+                OpCode::PushIfPeekNext => {
+                    operand!(OperationPayload::PushIfPeekNext { addr, c } = op.payload);
+                    if s < right_range && str_data[s] == c {
+                        stack[depth] = ((p as i32 + addr) as usize, s);
+                        depth += 1;
+                    }
+                    true
+                }
 
-    if let Some(ref mut r) = msa.region {
-        r.resize(reg.num_mem + 1);
-        r.clear();
-    }
+                // Fail, and everything is_small_program() rejects
+                _ => false,
+            }
+        };
 
-    let result = match_at(reg, str_data, end, end, at, &mut msa);
+        if matched {
+            p += 1;
+            continue;
+        }
 
-    let result = if opton_find_longest(msa.options) && result == ONIG_MISMATCH {
-        if msa.best_len >= 0 {
-            msa.best_len
-        } else {
-            result
+        // Backtrack, counting retries as match_at() does
+        #[cfg(feature = "exec-stats")]
+        tally.backtrack(depth);
+        retry_in_match_counter += 1;
+        if retry_limit_in_match != 0 && retry_in_match_counter > retry_limit_in_match {
+            result = ONIGERR_RETRY_LIMIT_IN_MATCH_OVER;
+            break;
         }
-    } else {
-        result
-    };
+        if msa.time_limit > 0
+            && (retry_in_match_counter % CHECK_TIME_INTERVAL) == 0
+            && msa.check_time_limit()
+        {
+            result = ONIGERR_TIME_LIMIT_OVER;
+            break;
+        }
+        if depth == 0 {
+            break;
+        }
+        depth -= 1;
+        (p, s) = stack[depth];
+    }
 
-    (result, msa.region)
+    msa.retry_limit_in_search_counter += retry_in_match_counter;
+    #[cfg(feature = "exec-stats")]
+    reg.exec_stats.add(&tally, result);
+    result
 }
 
 // ============================================================================
 // onig_search - search for a match anywhere in the string
 // ============================================================================
 
-/// Search for the regex pattern in the string, trying each position from
-/// `start` to `range`.
-/// Returns the match position on success, ONIG_MISMATCH (-1) on failure.
-///
-/// Parameters:
-/// - reg: compiled regex
 // ============================================================================
 // Search optimization functions — mirrors C's regexec.c lines 5168-5645
 // ============================================================================
@@ -4394,9 +5099,9 @@ fn slow_search(
 }
 
 /// Sunday quick search (BMH variant). Mirrors C's sunday_quick_search.
-/// Uses SIMD-accelerated memchr::memmem for the actual byte search.
+/// Uses the regex's prebuilt memchr::memmem::Finder for the actual byte search.
 fn sunday_quick_search(
-    _reg: &RegexType,
+    reg: &RegexType,
     target: &[u8],
     text: &[u8],
     text_start: usize,
@@ -4422,6 +5127,8 @@ fn sunday_quick_search(
     let haystack = &text[text_start..search_end];
     if tlen == 1 {
         memchr::memchr(target[0], haystack).map(|i| text_start + i)
+    } else if let Some(ref finder) = reg.exact_finder {
+        finder.find(haystack).map(|i| text_start + i)
     } else {
         memchr::memmem::find(haystack, target).map(|i| text_start + i)
     }
@@ -4480,7 +5187,7 @@ fn sunday_quick_search_step_forward(
 }
 
 /// Character map search. Mirrors C's map_search.
-/// Uses SIMD-accelerated memchr when the map has 1-3 distinct ASCII bytes.
+/// Uses SIMD-accelerated memchr when the map has 1-3 distinct bytes.
 fn map_search(
     enc: OnigEncoding,
     reg: &RegexType,
@@ -4488,28 +5195,49 @@ fn map_search(
     text_start: usize,
     text_range: usize,
 ) -> Option<usize> {
-    let haystack = &text[text_start..text_range];
-    match reg.map_byte_count {
-        1 => memchr::memchr(reg.map_bytes[0], haystack).map(|i| text_start + i),
-        2 => memchr::memchr2(reg.map_bytes[0], reg.map_bytes[1], haystack).map(|i| text_start + i),
-        3 => memchr::memchr3(
-            reg.map_bytes[0],
-            reg.map_bytes[1],
-            reg.map_bytes[2],
-            haystack,
-        )
-        .map(|i| text_start + i),
-        _ => {
-            let map = &reg.map;
-            let mut s = text_start;
-            while s < text_range {
-                if map[text[s] as usize] != 0 {
-                    return Some(s);
-                }
-                s += enclen(enc, text, s);
+    if reg.map_byte_count > 0 {
+        let mut from = text_start;
+        while let Some(i) = map_bytes_find(reg, &text[from..text_range]) {
+            let s = from + i;
+            // A byte >= 0x80 can be inside a multibyte character.
+            if text[s] < 0x80 || left_adjust_char_head(enc, text, text_start, s) == s {
+                return Some(s);
             }
-            None
+            from = s + 1;
+        }
+        return None;
+    }
+
+    let map = &reg.map;
+    let mut s = text_start;
+    while s < text_range {
+        if map[text[s] as usize] != 0 {
+            return Some(s);
         }
+        s += enclen(enc, text, s);
+    }
+    None
+}
+
+/// First occurrence of one of the regex's map bytes in `haystack`.
+#[inline]
+fn map_bytes_find(reg: &RegexType, haystack: &[u8]) -> Option<usize> {
+    let b = &reg.map_bytes;
+    match reg.map_byte_count {
+        1 => memchr::memchr(b[0], haystack),
+        2 => memchr::memchr2(b[0], b[1], haystack),
+        _ => memchr::memchr3(b[0], b[1], b[2], haystack),
+    }
+}
+
+/// Last occurrence of one of the regex's map bytes in `haystack`.
+#[inline]
+fn map_bytes_rfind(reg: &RegexType, haystack: &[u8]) -> Option<usize> {
+    let b = &reg.map_bytes;
+    match reg.map_byte_count {
+        1 => memchr::memrchr(b[0], haystack),
+        2 => memchr::memrchr2(b[0], b[1], haystack),
+        _ => memchr::memrchr3(b[0], b[1], b[2], haystack),
     }
 }
 
@@ -4559,58 +5287,51 @@ fn slow_search_backward(
 }
 
 /// Backward character map search. Mirrors C's map_search_backward.
-/// Uses SIMD-accelerated memrchr when the map has 1-3 distinct ASCII bytes.
+/// Uses SIMD-accelerated memrchr when the map has 1-3 distinct bytes.
 #[cfg_attr(coverage_nightly, coverage(off))]
 fn map_search_backward(
     enc: OnigEncoding,
     reg: &RegexType,
     text: &[u8],
     text_start: usize,
-    _adjust_text: usize,
+    adjust_text: usize,
     search_start: usize,
 ) -> Option<usize> {
-    let haystack = &text[text_start..search_start + 1];
-    match reg.map_byte_count {
-        1 => memchr::memrchr(reg.map_bytes[0], haystack).map(|i| text_start + i),
-        2 => memchr::memrchr2(reg.map_bytes[0], reg.map_bytes[1], haystack).map(|i| text_start + i),
-        3 => memchr::memrchr3(
-            reg.map_bytes[0],
-            reg.map_bytes[1],
-            reg.map_bytes[2],
-            haystack,
-        )
-        .map(|i| text_start + i),
-        _ => {
-            let map = &reg.map;
-            let mut s = search_start;
-            loop {
-                if map[text[s] as usize] != 0 {
-                    return Some(s);
-                }
-                if s <= text_start {
-                    break;
-                }
-                s = onigenc_get_prev_char_head(enc, text, _adjust_text, s);
-                if s < text_start {
-                    break;
-                }
+    if reg.map_byte_count > 0 {
+        let mut to = search_start + 1;
+        while let Some(i) = map_bytes_rfind(reg, &text[text_start..to]) {
+            let s = text_start + i;
+            if text[s] < 0x80 || left_adjust_char_head(enc, text, adjust_text, s) == s {
+                return Some(s);
             }
-            None
+            to = s;
+        }
+        return None;
+    }
+
+    let map = &reg.map;
+    let mut s = search_start;
+    loop {
+        if map[text[s] as usize] != 0 {
+            return Some(s);
+        }
+        if s <= text_start {
+            break;
+        }
+        s = onigenc_get_prev_char_head(enc, text, adjust_text, s);
+        if s < text_start {
+            break;
         }
     }
+    None
 }
 
-/// Left-adjust char head (ONIGENC_LEFT_ADJUST_CHAR_HEAD).
-/// For UTF-8: walk backward from pos to find char boundary at or before pos.
-fn left_adjust_char_head(_enc: OnigEncoding, _text: &[u8], start: usize, pos: usize) -> usize {
+/// Left-adjust char head (ONIGENC_LEFT_ADJUST_CHAR_HEAD) within [start..].
+fn left_adjust_char_head(enc: OnigEncoding, text: &[u8], start: usize, pos: usize) -> usize {
     if pos <= start {
         return start;
     }
-    let mut p = pos;
-    while p > start && _text[p] & 0xC0 == 0x80 {
-        p -= 1;
-    }
-    p
+    start + enc.floor_char_boundary(&text[start..], pos - start)
 }
 
 /// Backward search using optimization strategy.
@@ -4646,11 +5367,8 @@ fn backward_search(
         if reg.sub_anchor != 0 {
             let mut retry = false;
             if (reg.sub_anchor & ANCR_BEGIN_LINE) != 0 {
-                if p > 0 {
-                    let prev = onigenc_get_prev_char_head(reg.enc, str_data, 0, p);
-                    if !is_mbc_newline(reg.enc, str_data, prev, end) {
-                        retry = true;
-                    }
+                if p > 0 && !is_line_start(reg, str_data, p, end) {
+                    retry = true;
                 }
             }
             if !retry && (reg.sub_anchor & ANCR_END_LINE) != 0 {
@@ -4660,11 +5378,11 @@ fn backward_search(
                     if prev < adjrange {
                         return None;
                     }
-                    if is_mbc_newline(reg.enc, str_data, prev, end) {
+                    if is_newline(reg, str_data, prev, end) {
                         p = prev;
                         continue;
                     }
-                } else if !is_mbc_newline(reg.enc, str_data, p, end) {
+                } else if !is_line_end(reg, str_data, p, end) {
                     retry = true;
                 }
             }
@@ -4715,12 +5433,10 @@ fn onigenc_get_right_adjust_char_head(
     start: usize,
     s: usize,
 ) -> usize {
-    let p = left_adjust_char_head(enc, text, start, s);
-    if p < s {
-        p + enclen(enc, text, p)
-    } else {
-        p
+    if s <= start {
+        return start;
     }
+    start + enc.ceil_char_boundary(&text[start..], s - start)
 }
 
 /// Forward search using optimization strategy.
@@ -4777,18 +5493,14 @@ fn forward_search(
         if reg.sub_anchor != 0 {
             let mut retry = false;
             if (reg.sub_anchor & ANCR_BEGIN_LINE) != 0 {
-                if p > 0 {
-                    let prev = pprev.unwrap_or(0);
-                    let prev_pos = onigenc_get_prev_char_head(reg.enc, str_data, prev, p);
-                    if !is_mbc_newline(reg.enc, str_data, prev_pos, end) {
-                        retry = true;
-                    }
+                if p > 0 && !is_line_start(reg, str_data, p, end) {
+                    retry = true;
                 }
             }
             if !retry && (reg.sub_anchor & ANCR_END_LINE) != 0 {
                 if p >= end {
                     // at end - OK for some cases
-                } else if !is_mbc_newline(reg.enc, str_data, p, end) {
+                } else if !is_line_end(reg, str_data, p, end) {
                     retry = true;
                 }
             }
@@ -4851,14 +5563,211 @@ fn onigenc_get_prev_char_head(
     p
 }
 
-/// Check if position is a newline.
-fn is_mbc_newline(_enc: OnigEncoding, text: &[u8], pos: usize, end: usize) -> bool {
+/// Length of the line terminator starting at `pos`, or 0. Under the default
+/// `OnigNewline::Lf` the encoding decides what a newline is.
+fn newline_len(reg: &RegexType, text: &[u8], pos: usize, end: usize) -> usize {
     if pos >= end {
-        return false;
+        return 0;
+    }
+    let rest = &text[pos..end];
+    let crlf = rest.starts_with(b"\r\n");
+    match reg.newline {
+        OnigNewline::Lf => reg.enc.is_mbc_newline(rest, end) as usize,
+        OnigNewline::Cr => (rest[0] == b'\r') as usize,
+        OnigNewline::Crlf => 2 * crlf as usize,
+        OnigNewline::AnyCrlf => match rest[0] {
+            b'\r' if crlf => 2,
+            b'\r' | b'\n' => 1,
+            _ => 0,
+        },
+        OnigNewline::Any => match rest[0] {
+            b'\r' if crlf => 2,
+            b'\r' | b'\n' | 0x0b | 0x0c => 1,
+            0x85 if enc_is_singlebyte(reg.enc) => 1,
+            0xc2 if rest.starts_with(b"\xc2\x85") => 2,
+            0xe2 if rest.starts_with(b"\xe2\x80\xa8") || rest.starts_with(b"\xe2\x80\xa9") => 3,
+            _ => 0,
+        },
+    }
+}
+
+/// Length of the line terminator ending at `pos`, or 0.
+pub(crate) fn newline_before(reg: &RegexType, text: &[u8], pos: usize) -> usize {
+    let head = &text[..pos];
+    let crlf = head.ends_with(b"\r\n");
+    match reg.newline {
+        OnigNewline::Lf => head.ends_with(b"\n") as usize,
+        OnigNewline::Cr => head.ends_with(b"\r") as usize,
+        OnigNewline::Crlf => 2 * crlf as usize,
+        OnigNewline::AnyCrlf => match head.last() {
+            _ if crlf => 2,
+            Some(b'\r' | b'\n') => 1,
+            _ => 0,
+        },
+        OnigNewline::Any => match head.last() {
+            _ if crlf => 2,
+            Some(b'\r' | b'\n' | 0x0b | 0x0c) => 1,
+            Some(0x85) if enc_is_singlebyte(reg.enc) => 1,
+            Some(0x85) if head.ends_with(b"\xc2\x85") => 2,
+            Some(0xa8 | 0xa9) if head[..pos - 1].ends_with(b"\xe2\x80") => 3,
+            _ => 0,
+        },
+    }
+}
+
+/// Whether `pos` splits a CRLF that the convention treats as one newline.
+fn in_crlf(reg: &RegexType, text: &[u8], pos: usize, end: usize) -> bool {
+    matches!(reg.newline, OnigNewline::AnyCrlf | OnigNewline::Any)
+        && pos > 0
+        && pos < end
+        && text[pos - 1] == b'\r'
+        && text[pos] == b'\n'
+}
+
+/// Whether the character at `pos` is (the start of) a newline, i.e. `.` fails there.
+fn is_newline(reg: &RegexType, text: &[u8], pos: usize, end: usize) -> bool {
+    newline_len(reg, text, pos, end) > 0
+}
+
+/// Whether `^` matches at `pos > 0`.
+fn is_line_start(reg: &RegexType, text: &[u8], pos: usize, end: usize) -> bool {
+    newline_before(reg, text, pos) > 0 && !in_crlf(reg, text, pos, end)
+}
+
+/// Whether `$` matches at `pos < end`.
+fn is_line_end(reg: &RegexType, text: &[u8], pos: usize, end: usize) -> bool {
+    is_newline(reg, text, pos, end) && !in_crlf(reg, text, pos, end)
+}
+
+/// Whether the character before `pos` is a newline. Used by the
+/// ANCR_ANYCHAR_INF optimization, which only tries `.*` after newlines.
+pub(crate) fn follows_newline(reg: &RegexType, text: &[u8], pos: usize, end: usize) -> bool {
+    let prev = onigenc_get_prev_char_head(reg.enc, text, 0, pos);
+    is_newline(reg, text, prev, end)
+}
+
+/// First newline position in `s..limit` (the end of a `.*` run), or `limit`.
+fn find_newline(reg: &RegexType, text: &[u8], mut s: usize, limit: usize, end: usize) -> usize {
+    // In UTF-8/ASCII, 0x0a can only appear as a complete single-byte character,
+    // so memchr finds the exact newline position.
+    match reg.newline {
+        OnigNewline::Lf => memchr::memchr(b'\n', &text[s..limit]).map_or(limit, |i| s + i),
+        OnigNewline::Cr => memchr::memchr(b'\r', &text[s..limit]).map_or(limit, |i| s + i),
+        _ => {
+            while s < limit && !is_newline(reg, text, s, end) {
+                s += enclen(reg.enc, text, s);
+            }
+            s.min(limit)
+        }
+    }
+}
+
+// ============================================================================
+// ExecState - per-thread search state
+// ============================================================================
+
+/// The scratch memory of searches: the backtrack stack, capture slots and
+/// callout data that [`onig_search`] allocates on every call.
+///
+/// A [`CompiledPattern`] is only read while searching, so one pattern can
+/// be shared by many threads, e.g. behind an `Arc`, each searching with its
+/// own `ExecState`. One state serves any number of patterns, one search at
+/// a time, and keeps its buffers between searches. The limits set with
+/// `onig_set_retry_limit_in_match` and friends are read at the start of
+/// every search.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use ferroni::encodings::utf8::ONIG_ENCODING_UTF8;
+/// use ferroni::oniguruma::ONIG_OPTION_NONE;
+/// use ferroni::regcomp::onig_new;
+/// use ferroni::regexec::{onig_search_with_state, ExecState};
+/// use ferroni::regint::CompiledPattern;
+/// use ferroni::regsyntax::OnigSyntaxOniguruma;
+///
+/// let pattern: Arc<CompiledPattern> = Arc::new(
+///     onig_new(br"\d+", ONIG_OPTION_NONE, &ONIG_ENCODING_UTF8, &OnigSyntaxOniguruma).unwrap(),
+/// );
+/// let workers: Vec<_> = ["a1", "bb22", "ccc333"]
+///     .into_iter()
+///     .map(|text| {
+///         let pattern = Arc::clone(&pattern);
+///         std::thread::spawn(move || {
+///             let mut state = ExecState::new();
+///             let text = text.as_bytes();
+///             let end = text.len();
+///             onig_search_with_state(&pattern, &mut state, text, end, 0, end, None, ONIG_OPTION_NONE).0
+///         })
+///     })
+///     .collect();
+/// let found: Vec<i32> = workers.into_iter().map(|w| w.join().unwrap()).collect();
+/// assert_eq!(found, [1, 2, 3]);
+/// ```
+#[derive(Default)]
+pub struct ExecState {
+    msa: Option<MatchArg>,
+}
+
+impl ExecState {
+    /// An empty state. The first search allocates its buffers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The match state for a search of `reg`, reset for `start` and with
+    /// the current limits.
+    pub(crate) fn prepare(
+        &mut self,
+        reg: &RegexType,
+        option: OnigOptionType,
+        region: Option<OnigRegion>,
+        start: usize,
+    ) -> &mut MatchArg {
+        match self.msa {
+            Some(ref mut msa) => {
+                msa.reset_for_search(reg, option, region, start);
+                msa.reload_limits();
+                msa
+            }
+            None => self.msa.insert(MatchArg::new(reg, option, region, start)),
+        }
+    }
+
+    /// Reserve the backtrack stack and capture slots a search of `reg`
+    /// over text of up to `max_len` bytes is expected to need (see
+    /// [`stack_reservation`]) ahead of the first search.
+    pub(crate) fn reserve(&mut self, reg: &RegexType, max_len: usize) {
+        self.msa
+            .get_or_insert_with(|| MatchArg::new(reg, ONIG_OPTION_NONE, None, 0))
+            .reserve(reg, stack_reservation(reg, max_len));
+    }
+
+    /// Capacity of the backtrack stack, in entries.
+    pub(crate) fn stack_capacity(&self) -> usize {
+        self.msa.as_ref().map_or(0, MatchArg::stack_capacity)
+    }
+
+    /// Bytes of the state, its buffers included.
+    pub fn heap_size(&self) -> usize {
+        self.msa
+            .as_ref()
+            .map_or(0, |msa| core::mem::size_of::<MatchArg>() + msa.heap_size())
     }
-    text[pos] == b'\n'
 }
 
+/// Search for the regex pattern in the string, trying each position from
+/// `start` to `range`.
+/// Returns the match position on success, ONIG_MISMATCH (-1) on failure.
+///
+/// Bytes at or past `end` are never examined, so searching `&str_data[a..b]`
+/// gives the same result (shifted by `a`) as `end = b, start = a, range = b`
+/// on the full buffer. Only constructs that look before `start` (`^`, `\A`,
+/// `\b`, `\B`, look-behind) can differ, since they see the real context.
+///
+/// Parameters:
+/// - reg: compiled regex
 /// - str_data: the input string bytes
 /// - end: end position (typically str_data.len())
 /// - start: starting search position
@@ -4878,6 +5787,24 @@ pub fn onig_search(
     onig_search_inner(reg, str_data, end, start, range, &mut msa)
 }
 
+/// onig_search() with the scratch memory of `state`, which keeps its
+/// buffers for the next search. `reg` is only read, so it can be shared
+/// between threads that each have their own state (see [`ExecState`]).
+#[allow(clippy::too_many_arguments)]
+pub fn onig_search_with_state(
+    reg: &CompiledPattern,
+    state: &mut ExecState,
+    str_data: &[u8],
+    end: usize,
+    start: usize,
+    range: usize,
+    region: Option<OnigRegion>,
+    option: OnigOptionType,
+) -> (i32, Option<OnigRegion>) {
+    let msa = state.prepare(reg, option, region, start);
+    onig_search_inner(reg, str_data, end, start, range, msa)
+}
+
 /// Search reusing a pre-allocated MatchArg. Preserves buffer capacity.
 pub(crate) fn onig_search_with_msa(
     reg: &RegexType,
@@ -4899,10 +5826,13 @@ pub fn onig_search_with_param(
     range: usize,
     region: Option<OnigRegion>,
     option: OnigOptionType,
-    mp: &OnigMatchParam,
+    mp: &mut OnigMatchParam,
 ) -> (i32, Option<OnigRegion>) {
     let mut msa = MatchArg::from_param(reg, option, region, start, mp);
-    onig_search_inner(reg, str_data, end, start, range, &mut msa)
+    msa.callout_data = core::mem::take(&mut mp.callout_data);
+    let r = onig_search_inner(reg, str_data, end, start, range, &mut msa);
+    mp.callout_data = core::mem::take(&mut msa.callout_data);
+    r
 }
 
 fn onig_search_inner(
@@ -4914,10 +5844,17 @@ fn onig_search_inner(
     msa: &mut MatchArg,
 ) -> (i32, Option<OnigRegion>) {
     let enc = reg.enc;
-    let find_longest = opton_find_longest(msa.options);
+    // match_at() finds the longest match at each start either way. Not in
+    // C: with LEFTMOST_LONGEST the first start that matches wins, as
+    // without FIND_LONGEST, instead of the longest over the whole range.
+    let find_longest = opton_find_longest(msa.options) && !opton_leftmost_longest(msa.options);
     let mut best_start: i32 = ONIG_MISMATCH;
     let mut best_len: i32 = ONIG_MISMATCH;
 
+    if end > ONIG_MAX_INPUT_LEN {
+        return (ONIGERR_TOO_LONG_INPUT, msa.region.take());
+    }
+
     if opton_check_validity_of_string(msa.options) {
         if !enc.is_valid_mbc_string(&str_data[..end]) {
             return (ONIGERR_INVALID_WIDE_CHAR_VALUE, msa.region.take());
@@ -4941,13 +5878,21 @@ fn onig_search_inner(
         // s starts at start (same as C: s = (UChar*)start)
         let mut s = start;
 
+        // A match needs at least match_len_min bytes before
+        // orig_start, so start no later than that.
+        match orig_start.checked_sub(reg.match_len_min as usize) {
+            Some(last) if last >= range => {
+                if s > last {
+                    s = left_adjust_char_head(enc, str_data, 0, last);
+                }
+            }
+            _ => return (ONIG_MISMATCH, msa.region.take()),
+        }
+
         // Macro-like helper for match_at + result handling in backward search
         macro_rules! backward_match_and_check {
             ($s:expr, $orig_start:expr) => {{
-                if let Some(ref mut r) = msa.region {
-                    r.resize(reg.num_mem + 1);
-                    r.clear();
-                }
+                msa.reset_region(reg);
                 msa.best_len = ONIG_MISMATCH;
                 msa.best_s = 0;
                 let r = match_at(reg, str_data, end, $orig_start, $s, msa);
@@ -5055,7 +6000,11 @@ fn onig_search_inner(
 
     let mut cur_start = start;
     let mut cur_range = range;
-    let data_range = if range > start { range } else { end };
+    let data_range = if range > start && msa.match_end.is_none() {
+        range
+    } else {
+        end
+    };
 
     // === Anchor optimization: narrow search range ===
     if reg.anchor != 0 && start < end {
@@ -5105,9 +6054,7 @@ fn onig_search_inner(
             let mut min_semi_end = end;
             let max_semi_end = end;
             // Check if last char before end is newline
-            if end > 0 && str_data[end - 1] == b'\n' {
-                min_semi_end = end - 1;
-            }
+            min_semi_end -= newline_before(reg, str_data, end);
             if (max_semi_end as OnigLen) < reg.anc_dist_min {
                 return (ONIG_MISMATCH, msa.region.take());
             }
@@ -5140,10 +6087,7 @@ fn onig_search_inner(
         // Empty string
         if reg.threshold_len == 0 {
             let mut s = start;
-            if let Some(ref mut r) = msa.region {
-                r.resize(reg.num_mem + 1);
-                r.clear();
-            }
+            msa.reset_region(reg);
             msa.best_len = ONIG_MISMATCH;
             msa.best_s = 0;
             let r = match_at(reg, str_data, end, end, s, msa);
@@ -5162,8 +6106,37 @@ fn onig_search_inner(
         return (ONIG_MISMATCH, msa.region.take());
     }
 
+    // A match needs at least match_len_min bytes before
+    // data_range, so later start positions are never tried.
+    let last_start = match data_range.checked_sub(reg.match_len_min as usize) {
+        Some(last) if last >= cur_start => last,
+        _ => return (ONIG_MISMATCH, msa.region.take()),
+    };
+
     // === Forward search ===
     let mut s = cur_start;
+    // With an unbounded distance to the optimization target, the
+    // last start that can still reach the target found so far, and the
+    // window to look for the next one in.
+    let mut target_bound: Option<(usize, usize)> = None;
+
+    // Shift-Or scan for short fixed patterns. A match can only
+    // start in [cur_start, cur_range] and must end by data_range.
+    if let Some(ref so) = reg.shift_or {
+        if !find_longest && !opton_match_whole_string(msa.options) && msa.match_end.is_none() {
+            let scan_end = data_range.min(cur_range + so.len());
+            if cur_start < scan_end {
+                if let Some(found) = so.find(str_data, cur_start, scan_end) {
+                    msa.reset_region(reg);
+                    if let Some(ref mut r) = msa.region {
+                        r.put(0, found as i32, (found + so.len()) as i32);
+                    }
+                    return (found as i32, msa.region.take());
+                }
+            }
+            return (ONIG_MISMATCH, msa.region.take());
+        }
+    }
 
     // Use optimization if available
     if reg.optimize != OptimizeType::None {
@@ -5190,11 +6163,8 @@ fn onig_search_inner(
                 if s < low {
                     s = low;
                 }
-                while s <= high {
-                    if let Some(ref mut r) = msa.region {
-                        r.resize(reg.num_mem + 1);
-                        r.clear();
-                    }
+                while s <= high.min(last_start) {
+                    msa.reset_region(reg);
                     msa.best_len = ONIG_MISMATCH;
                     msa.best_s = 0;
                     let r = match_at(reg, str_data, end, data_range, s, msa);
@@ -5222,7 +6192,7 @@ fn onig_search_inner(
                         s = msa.skip_search;
                     }
                 }
-                if s >= cur_range {
+                if s >= cur_range || s > last_start {
                     break;
                 }
             }
@@ -5231,18 +6201,16 @@ fn onig_search_inner(
             return finish_search(find_longest, best_start, best_len, reg, str_data, end, msa);
         } else {
             // Infinite dist_max: just check once, then fall through to normal loop
-            if forward_search(reg, str_data, end, s, sch_range).is_none() {
+            let Some((_, high)) = forward_search(reg, str_data, end, s, sch_range) else {
                 return finish_search(find_longest, best_start, best_len, reg, str_data, end, msa);
-            }
+            };
+            target_bound = Some((high, sch_range));
             // ANCR_ANYCHAR_INF: skip past newlines
             if (reg.anchor & ANCR_ANYCHAR_INF) != 0
                 && (reg.anchor & (ANCR_LOOK_BEHIND | ANCR_PREC_READ_NOT)) == 0
             {
-                while s < cur_range {
-                    if let Some(ref mut r) = msa.region {
-                        r.resize(reg.num_mem + 1);
-                        r.clear();
-                    }
+                while s < cur_range && s <= last_start {
+                    msa.reset_region(reg);
                     msa.best_len = ONIG_MISMATCH;
                     msa.best_s = 0;
                     let r = match_at(reg, str_data, end, data_range, s, msa);
@@ -5271,10 +6239,10 @@ fn onig_search_inner(
                         s = msa.skip_search;
                     }
                     // Skip past non-newline chars
-                    while s < cur_range && !is_mbc_newline(enc, str_data, prev, end) {
+                    while s < cur_range && !is_newline(reg, str_data, prev, end) {
                         let prev2 = s;
                         s += enclen(enc, str_data, s);
-                        if is_mbc_newline(enc, str_data, prev2, end) {
+                        if is_newline(reg, str_data, prev2, end) {
                             break;
                         }
                     }
@@ -5288,10 +6256,29 @@ fn onig_search_inner(
     // Normal position-by-position search (no optimization or fallthrough)
     if best_start == ONIG_MISMATCH {
         loop {
-            if let Some(ref mut r) = msa.region {
-                r.resize(reg.num_mem + 1);
-                r.clear();
+            // A pattern starting with `^` only needs line starts.
+            if (reg.anchor & ANCR_BEGIN_LINE) != 0
+                && s > 0
+                && s < cur_range
+                && !is_line_start(reg, str_data, s, end)
+            {
+                let nl = find_newline(reg, str_data, s, cur_range, end);
+                s = (nl + newline_len(reg, str_data, nl, end)).min(cur_range);
+                if s > last_start {
+                    break;
+                }
+            }
+            // Past the last start that reaches the target, look
+            // for the next occurrence; without one nothing can match.
+            if let Some((high, sch_range)) = target_bound {
+                if s > high {
+                    match forward_search(reg, str_data, end, s, sch_range) {
+                        Some((_, next)) => target_bound = Some((next, sch_range)),
+                        None => break,
+                    }
+                }
             }
+            msa.reset_region(reg);
             msa.best_len = ONIG_MISMATCH;
             msa.best_s = 0;
             let r = match_at(reg, str_data, end, data_range, s, msa);
@@ -5314,7 +6301,7 @@ fn onig_search_inner(
             {
                 return (ONIGERR_RETRY_LIMIT_IN_SEARCH_OVER, msa.region.take());
             }
-            if s >= cur_range {
+            if s >= cur_range || s >= last_start {
                 break;
             }
             if s >= end {
@@ -5326,30 +6313,223 @@ fn onig_search_inner(
             }
         }
     }
-
-    finish_search(find_longest, best_start, best_len, reg, str_data, end, msa)
+
+    finish_search(find_longest, best_start, best_len, reg, str_data, end, msa)
+}
+
+fn finish_search(
+    find_longest: bool,
+    best_start: i32,
+    best_len: i32,
+    reg: &RegexType,
+    str_data: &[u8],
+    end: usize,
+    msa: &mut MatchArg,
+) -> (i32, Option<OnigRegion>) {
+    if find_longest && best_start != ONIG_MISMATCH {
+        msa.reset_region(reg);
+        msa.best_len = ONIG_MISMATCH;
+        msa.best_s = 0;
+        match_at(reg, str_data, end, end, best_start as usize, msa);
+        return (best_start, msa.region.take());
+    }
+    (ONIG_MISMATCH, msa.region.take())
+}
+
+// ============================================================================
+// POSIX regmatch_t output (ONIG_OPTION_POSIX_REGION)
+// ============================================================================
+
+/// One `regmatch_t` slot: start and end offset of a group, or
+/// `ONIG_REGION_NOTPOS` for both when the group did not participate.
+/// `T` is the `regoff_t` width of the consumer (`i32`, `i64` or `isize`).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RegMatch<T> {
+    pub rm_so: T,
+    pub rm_eo: T,
+}
+
+/// Integer type usable as `regoff_t` in a [`RegMatch`] array.
+pub trait RegOff: Copy {
+    fn from_offset(off: i32) -> Self;
+}
+
+impl RegOff for i32 {
+    fn from_offset(off: i32) -> Self {
+        off
+    }
+}
+
+impl RegOff for i64 {
+    fn from_offset(off: i32) -> Self {
+        off as i64
+    }
+}
+
+impl RegOff for isize {
+    fn from_offset(off: i32) -> Self {
+        off as isize
+    }
+}
+
+crate::sys::per_thread! {
+    /// Match state reused by the `regmatch_t` entry points, so repeated
+    /// calls on a thread stop allocating once the buffers have grown.
+    static REGMATCH_SCRATCH: core::cell::Cell<Option<(MatchArg, OnigRegion)>> =
+        const { core::cell::Cell::new(None) };
+}
+
+/// Copy `region` into `pmatch`. Slots past the last group are set to
+/// `ONIG_REGION_NOTPOS`, as POSIX `regexec` does.
+pub fn onig_region_to_regmatch<T: RegOff>(region: &OnigRegion, pmatch: &mut [RegMatch<T>]) {
+    let n = (region.num_regs.max(0) as usize).min(pmatch.len());
+    for (i, m) in pmatch.iter_mut().enumerate() {
+        let (so, eo) = if i < n {
+            (region.beg(i), region.end(i))
+        } else {
+            (ONIG_REGION_NOTPOS, ONIG_REGION_NOTPOS)
+        };
+        m.rm_so = T::from_offset(so);
+        m.rm_eo = T::from_offset(eo);
+    }
+}
+
+/// Run `f` with a thread-cached MatchArg in POSIX region mode and pass the
+/// region to `out` on success.
+fn with_scratch_msa(
+    reg: &RegexType,
+    option: OnigOptionType,
+    start: usize,
+    f: impl FnOnce(&mut MatchArg) -> (i32, Option<OnigRegion>),
+    out: impl FnOnce(&OnigRegion),
+) -> i32 {
+    let option = option | ONIG_OPTION_POSIX_REGION;
+    // Taken out of the cell, so a nested call (e.g. from a callout) just
+    // builds its own state instead of aliasing ours.
+    let mut msa = match REGMATCH_SCRATCH.with(|c| c.take()) {
+        Some((mut msa, region)) => {
+            msa.reset_for_search(reg, option, Some(region), start);
+            msa.retry_limit_in_match = RETRY_LIMIT_IN_MATCH.load(Ordering::Relaxed);
+            msa.retry_limit_in_search = RETRY_LIMIT_IN_SEARCH.load(Ordering::Relaxed);
+            msa.match_stack_limit = MATCH_STACK_LIMIT.load(Ordering::Relaxed);
+            msa.time_limit = TIME_LIMIT.load(Ordering::Relaxed);
+            msa
+        }
+        None => MatchArg::new(reg, option, Some(OnigRegion::new()), start),
+    };
+    let (r, region) = f(&mut msa);
+    let region = region.unwrap_or_default();
+    if r >= 0 {
+        out(&region);
+    }
+    REGMATCH_SCRATCH.with(|c| c.set(Some((msa, region))));
+    r
+}
+
+/// `onig_search` writing group offsets straight into a `regmatch_t`-shaped
+/// array instead of returning an `OnigRegion`. Implies
+/// `ONIG_OPTION_POSIX_REGION` (no capture history is built). After the
+/// first call on a thread, no heap allocation happens unless the pattern
+/// needs a deeper backtrack stack than any earlier one.
+pub fn onig_search_regmatch<T: RegOff>(
+    reg: &RegexType,
+    str_data: &[u8],
+    end: usize,
+    start: usize,
+    range: usize,
+    pmatch: &mut [RegMatch<T>],
+    option: OnigOptionType,
+) -> i32 {
+    with_scratch_msa(
+        reg,
+        option,
+        start,
+        |msa| onig_search_inner(reg, str_data, end, start, range, msa),
+        |region| onig_region_to_regmatch(region, pmatch),
+    )
+}
+
+/// `onig_match` counterpart of [`onig_search_regmatch`].
+pub fn onig_match_regmatch<T: RegOff>(
+    reg: &RegexType,
+    str_data: &[u8],
+    end: usize,
+    at: usize,
+    pmatch: &mut [RegMatch<T>],
+    option: OnigOptionType,
+) -> i32 {
+    with_scratch_msa(
+        reg,
+        option,
+        at,
+        |msa| onig_match_inner(reg, str_data, end, at, msa),
+        |region| onig_region_to_regmatch(region, pmatch),
+    )
+}
+
+/// Whether an `OnigRegionFixed<N>` holds every group a search of `reg`
+/// with `option` reports.
+fn fits_fixed_region<const N: usize>(reg: &RegexType, option: OnigOptionType) -> bool {
+    let groups = if option.intersects(ONIG_OPTION_DONT_CAPTURE_GROUP) {
+        1
+    } else {
+        reg.num_mem as usize + 1
+    };
+    groups <= N
+}
+
+/// `onig_search` filling an inline [`OnigRegionFixed`] instead of
+/// a heap-backed `OnigRegion`. Fails with `ONIGERR_INVALID_ARGUMENT` if the
+/// pattern has more than `N` groups (group 0 included). Like
+/// [`onig_search_regmatch`], no capture history is built and, after the
+/// first call on a thread, no heap allocation happens unless the pattern
+/// needs a deeper backtrack stack than any earlier one. `region` is left
+/// untouched unless the search matches.
+pub fn onig_search_fixed<const N: usize>(
+    reg: &RegexType,
+    str_data: &[u8],
+    end: usize,
+    start: usize,
+    range: usize,
+    region: &mut OnigRegionFixed<N>,
+    option: OnigOptionType,
+) -> i32 {
+    if !fits_fixed_region::<N>(reg, option) {
+        return ONIGERR_INVALID_ARGUMENT;
+    }
+    with_scratch_msa(
+        reg,
+        option,
+        start,
+        |msa| onig_search_inner(reg, str_data, end, start, range, msa),
+        |r| {
+            region.copy_from(r);
+        },
+    )
 }
 
-fn finish_search(
-    find_longest: bool,
-    best_start: i32,
-    best_len: i32,
+/// `onig_match` counterpart of [`onig_search_fixed`].
+pub fn onig_match_fixed<const N: usize>(
     reg: &RegexType,
     str_data: &[u8],
     end: usize,
-    msa: &mut MatchArg,
-) -> (i32, Option<OnigRegion>) {
-    if find_longest && best_start != ONIG_MISMATCH {
-        if let Some(ref mut r) = msa.region {
-            r.resize(reg.num_mem + 1);
-            r.clear();
-        }
-        msa.best_len = ONIG_MISMATCH;
-        msa.best_s = 0;
-        match_at(reg, str_data, end, end, best_start as usize, msa);
-        return (best_start, msa.region.take());
+    at: usize,
+    region: &mut OnigRegionFixed<N>,
+    option: OnigOptionType,
+) -> i32 {
+    if !fits_fixed_region::<N>(reg, option) {
+        return ONIGERR_INVALID_ARGUMENT;
     }
-    (ONIG_MISMATCH, msa.region.take())
+    with_scratch_msa(
+        reg,
+        option,
+        at,
+        |msa| onig_match_inner(reg, str_data, end, at, msa),
+        |r| {
+            region.copy_from(r);
+        },
+    )
 }
 
 // ============================================================================
@@ -5380,9 +6560,10 @@ mod tests {
             repeat_range: Vec::new(),
             enc,
             options: ONIG_OPTION_NONE,
-            syntax: &OnigSyntaxOniguruma,
+            syntax: OnigSyntaxOniguruma.clone(),
             case_fold_flag: ONIGENC_CASE_FOLD_MIN,
             name_table: None,
+            newline: OnigNewline::Lf,
             optimize: OptimizeType::None,
             threshold_len: 0,
             anchor: 0,
@@ -5390,12 +6571,25 @@ mod tests {
             anc_dist_max: 0,
             sub_anchor: 0,
             exact: Vec::new(),
+            exact_finder: None,
             map: [0u8; CHAR_MAP_SIZE],
             map_offset: 0,
             map_bytes: [0u8; 3],
             map_byte_count: 0,
             dist_min: 0,
             dist_max: 0,
+            req_exact: Vec::new(),
+            req_dist_min: 0,
+            req_dist_max: 0,
+            match_len_min: 0,
+            match_len_max: INFINITE_LEN,
+            group_parents: Vec::new(),
+            shift_or: None,
+            small_match: false,
+            #[cfg(feature = "unstable-opcodes")]
+            opcodes: Default::default(),
+            #[cfg(feature = "exec-stats")]
+            exec_stats: Default::default(),
             called_addrs: vec![],
             unset_call_addrs: vec![],
             extp: None,
@@ -5404,15 +6598,15 @@ mod tests {
             options: OnigOptionType::empty(),
             case_fold_flag: 0,
             enc,
-            syntax: &OnigSyntaxOniguruma,
+            syntax: OnigSyntaxOniguruma.clone(),
             cap_history: 0,
             backtrack_mem: 0,
             backrefed_mem: 0,
-            pattern: std::ptr::null(),
-            pattern_end: std::ptr::null(),
-            error: std::ptr::null(),
-            error_end: std::ptr::null(),
-            reg: std::ptr::null_mut(),
+            pattern: core::ptr::null(),
+            pattern_end: core::ptr::null(),
+            error: core::ptr::null(),
+            error_end: core::ptr::null(),
+            reg: core::ptr::null_mut(),
             num_call: 0,
             num_mem: 0,
             num_named: 0,
@@ -5427,6 +6621,12 @@ mod tests {
             unset_addr_list: None,
             parse_depth: 0,
             flags: 0,
+            token_start: Default::default(),
+            error_name: Default::default(),
+            cc_scratch: Default::default(),
+            cc_buf_hint: 0,
+            cc_redundant: None,
+            compile: Default::default(),
         };
         (reg, env)
     }
@@ -5439,7 +6639,7 @@ mod tests {
             r,
             0,
             "compile failed for {:?}",
-            std::str::from_utf8(pattern)
+            core::str::from_utf8(pattern)
         );
         onig_match(
             &reg,
@@ -5459,7 +6659,7 @@ mod tests {
             r,
             0,
             "compile failed for {:?}",
-            std::str::from_utf8(pattern)
+            core::str::from_utf8(pattern)
         );
         onig_search(
             &reg,
@@ -5866,6 +7066,36 @@ mod tests {
         onig_set_time_limit(old_time);
     }
 
+    #[test]
+    fn stack_reservation_follows_the_program() {
+        let _lock = LIMIT_TEST_LOCK.lock().unwrap();
+        let compile = |pattern: &[u8]| {
+            regcomp::onig_new(
+                pattern,
+                ONIG_OPTION_NONE,
+                &crate::encodings::utf8::ONIG_ENCODING_UTF8,
+                &crate::regsyntax::OnigSyntaxOniguruma,
+            )
+            .unwrap()
+        };
+
+        // No loop: the line length does not matter
+        let literal = compile(b"ab|cd");
+        assert_eq!(stack_reservation(&literal, 100_000), INIT_MATCH_STACK_SIZE);
+
+        // A loop step keeps a choice point, more with captures a backref
+        // needs kept on the stack
+        let star = compile(b"(?:a|b)*c");
+        let captured = compile(b"(a|b)*c\\1");
+        assert!(stack_reservation(&star, 10_000) >= 10_000);
+        assert!(stack_reservation(&captured, 10_000) > stack_reservation(&star, 10_000));
+
+        let old_stack = onig_get_match_stack_limit();
+        onig_set_match_stack_limit(500);
+        assert_eq!(stack_reservation(&star, 10_000), 500 + STACK_HEADROOM);
+        onig_set_match_stack_limit(old_stack);
+    }
+
     #[test]
     fn stack_limit_over() {
         let _lock = LIMIT_TEST_LOCK.lock().unwrap();
@@ -5904,6 +7134,207 @@ mod tests {
     }
 
     #[test]
+    fn region_clear_unsets_slots() {
+        let reg = regcomp::onig_new(
+            b"(a)|(b)",
+            ONIG_OPTION_NONE,
+            &crate::encodings::utf8::ONIG_ENCODING_UTF8,
+            &crate::regsyntax::OnigSyntaxOniguruma,
+        )
+        .unwrap();
+        let (r, region) = onig_search(
+            &reg,
+            b"a",
+            1,
+            0,
+            1,
+            Some(OnigRegion::new()),
+            ONIG_OPTION_NONE,
+        );
+        assert_eq!(r, 0);
+        let mut region = region.unwrap();
+        assert_eq!((region.beg(1), region.end(1)), (0, 1));
+
+        // Reusing the region, group 1 does not participate this time.
+        let (r, region2) = onig_search(&reg, b"b", 1, 0, 1, Some(region.clone()), ONIG_OPTION_NONE);
+        assert_eq!(r, 0);
+        let region2 = region2.unwrap();
+        assert_eq!(region2.beg(1), ONIG_REGION_NOTPOS);
+        assert_eq!((region2.beg(2), region2.end(2)), (0, 1));
+        assert_eq!((region2.beg[2], region2.end[2]), (0, 1));
+
+        onig_region_clear(&mut region);
+        assert_eq!(region.num_regs, 3);
+        assert!((0..3).all(|i| region.beg(i) == ONIG_REGION_NOTPOS));
+        assert!((0..3).all(|i| region.end(i) == ONIG_REGION_NOTPOS));
+        assert_eq!(onig_region_set(&mut region, 1, 4, 5), ONIG_NORMAL);
+        assert_eq!((region.beg(1), region.end(1)), (4, 5));
+        assert_eq!(region.beg(2), ONIG_REGION_NOTPOS);
+    }
+
+    #[test]
+    fn dont_capture_group_search_records_group0_only() {
+        let mut syntax = crate::regsyntax::OnigSyntaxOniguruma.clone();
+        syntax.op2 |= ONIG_SYN_OP2_ATMARK_CAPTURE_HISTORY;
+        let reg = regcomp::onig_new(
+            br"(a)(?@b)\1",
+            ONIG_OPTION_NONE,
+            &crate::encodings::utf8::ONIG_ENCODING_UTF8,
+            &syntax,
+        )
+        .unwrap();
+        let (r, region) = onig_search(
+            &reg,
+            b"xaba",
+            4,
+            0,
+            4,
+            Some(OnigRegion::new()),
+            ONIG_OPTION_NONE,
+        );
+        assert_eq!(r, 1);
+        let region = region.unwrap();
+        assert_eq!(region.num_regs, 3);
+        assert!(region.history_root.is_some());
+
+        // The same region shrinks to group 0, and backreferences still work.
+        let (r, region) = onig_search(
+            &reg,
+            b"xaba",
+            4,
+            0,
+            4,
+            Some(region),
+            ONIG_OPTION_DONT_CAPTURE_GROUP,
+        );
+        assert_eq!(r, 1);
+        let region = region.unwrap();
+        assert_eq!(region.num_regs, 1);
+        assert_eq!(region.get(0), Some(1..4));
+        assert!(region.history_root.is_none());
+    }
+
+    fn nested_parens(depth: usize) -> Vec<u8> {
+        let mut s = vec![b'('; depth];
+        s.push(b'x');
+        s.extend(core::iter::repeat_n(b')', depth));
+        s
+    }
+
+    #[test]
+    fn subexp_call_max_nest_level() {
+        let reg = regcomp::onig_new(
+            br"\A(?<a>\(\g<a>\)|x)\z",
+            ONIG_OPTION_NONE,
+            &crate::encodings::utf8::ONIG_ENCODING_UTF8,
+            &crate::regsyntax::OnigSyntaxOniguruma,
+        )
+        .unwrap();
+        assert_eq!(onig_get_subexp_call_max_nest_level(), 20);
+        // The group itself is one call, so depth 19 nests 20 calls.
+        let input = nested_parens(19);
+        let (r, _) = onig_search(
+            &reg,
+            &input,
+            input.len(),
+            0,
+            input.len(),
+            None,
+            ONIG_OPTION_NONE,
+        );
+        assert_eq!(r, 0);
+        let input = nested_parens(20);
+        let (r, _) = onig_search(
+            &reg,
+            &input,
+            input.len(),
+            0,
+            input.len(),
+            None,
+            ONIG_OPTION_NONE,
+        );
+        assert_eq!(r, ONIG_MISMATCH);
+
+        // Calls left by backtracking do not count towards the nest level.
+        let reg = regcomp::onig_new(
+            br"\A(?:\g<a>|bd)*\g<e>\z(?<a>bc){0}(?<e>e){0}",
+            ONIG_OPTION_NONE,
+            &crate::encodings::utf8::ONIG_ENCODING_UTF8,
+            &crate::regsyntax::OnigSyntaxOniguruma,
+        )
+        .unwrap();
+        let input = [b"bd".repeat(30), b"e".to_vec()].concat();
+        let (r, _) = onig_search(
+            &reg,
+            &input,
+            input.len(),
+            0,
+            input.len(),
+            None,
+            ONIG_OPTION_NONE,
+        );
+        assert_eq!(r, 0);
+    }
+
+    #[test]
+    fn subexp_call_limit_in_search_over() {
+        let reg = regcomp::onig_new(
+            br"\A(?<a>\(\g<a>\)|x)\z",
+            ONIG_OPTION_NONE,
+            &crate::encodings::utf8::ONIG_ENCODING_UTF8,
+            &crate::regsyntax::OnigSyntaxOniguruma,
+        )
+        .unwrap();
+        for (depth, expected) in [(9, 19), (10, ONIGERR_SUBEXP_CALL_LIMIT_IN_SEARCH_OVER)] {
+            let input = nested_parens(depth);
+            let mut msa = MatchArg::new(&reg, ONIG_OPTION_NONE, None, 0);
+            msa.subexp_call_limit_in_search = 10;
+            let r = match_at(&reg, &input, input.len(), input.len(), 0, &mut msa);
+            assert_eq!(r, expected, "depth {depth}");
+        }
+    }
+
+    #[test]
+    fn stack_grows_on_demand_up_to_param_limit() {
+        let (mut reg, mut env) = make_test_context();
+        let root = regparse::onig_parse_tree(b"(?:a|b)*c", &mut reg, &mut env).unwrap();
+        assert_eq!(regcomp::compile_from_tree(&root, &mut reg, &env), 0);
+        let input = [b'a'; 1000];
+
+        // Nothing is allocated until a match attempt needs the stack
+        let mut msa = MatchArg::new(&reg, ONIG_OPTION_NONE, None, 0);
+        assert_eq!(msa.stack_capacity(), 0);
+        assert_eq!(
+            match_at(&reg, &input, input.len(), input.len(), 0, &mut msa),
+            ONIG_MISMATCH
+        );
+        assert!(msa.stack_capacity() > 1000);
+
+        let mut mp = onig_new_match_param();
+        mp.retry_limit_in_match = 0;
+        mp.retry_limit_in_search = 0;
+        mp.time_limit = 0;
+        for (limit, expected) in [
+            (100, ONIGERR_MATCH_STACK_LIMIT_OVER),
+            (1000, ONIGERR_MATCH_STACK_LIMIT_OVER),
+            (5000, ONIG_MISMATCH),
+        ] {
+            onig_set_match_stack_limit_size_of_match_param(&mut mp, limit);
+            let (r, _) = onig_match_with_param(
+                &reg,
+                &input,
+                input.len(),
+                0,
+                None,
+                ONIG_OPTION_NONE,
+                &mut mp,
+            );
+            assert_eq!(r, expected, "limit {limit}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
     fn time_limit_over() {
         let _lock = LIMIT_TEST_LOCK.lock().unwrap();
 
@@ -6014,4 +7445,494 @@ mod tests {
         );
         assert_eq!(result, ONIG_MISMATCH);
     }
+
+    #[test]
+    fn search_regmatch_widths_and_padding() {
+        let (mut reg, mut env) = make_test_context();
+        let root = regparse::onig_parse_tree(b"(\\d+)(x)?", &mut reg, &mut env).unwrap();
+        assert_eq!(regcomp::compile_from_tree(&root, &mut reg, &env), 0);
+        let input = b"ab 42;";
+
+        let mut narrow = [RegMatch::<i32>::default(); 4];
+        let r = onig_search_regmatch(&reg, input, 6, 0, 6, &mut narrow, ONIG_OPTION_NONE);
+        assert_eq!(r, 3);
+        let spans: Vec<_> = narrow.iter().map(|m| (m.rm_so, m.rm_eo)).collect();
+        assert_eq!(spans, [(3, 5), (3, 5), (-1, -1), (-1, -1)]);
+
+        let mut wide = [RegMatch::<i64>::default(); 1];
+        assert_eq!(
+            onig_match_regmatch(&reg, input, 6, 3, &mut wide, ONIG_OPTION_NONE),
+            2
+        );
+        assert_eq!(wide[0], RegMatch { rm_so: 3, rm_eo: 5 });
+
+        // No match leaves the array alone.
+        assert_eq!(
+            onig_match_regmatch(&reg, input, 6, 0, &mut wide, ONIG_OPTION_NONE),
+            ONIG_MISMATCH
+        );
+        assert_eq!(wide[0], RegMatch { rm_so: 3, rm_eo: 5 });
+    }
+
+    #[test]
+    fn search_and_match_fixed_region() {
+        let (mut reg, mut env) = make_test_context();
+        let root = regparse::onig_parse_tree(b"(\\d+)(x)?", &mut reg, &mut env).unwrap();
+        assert_eq!(regcomp::compile_from_tree(&root, &mut reg, &env), 0);
+        let input = b"ab 42;";
+
+        let mut region = OnigRegionFixed::<4>::new();
+        let r = onig_search_fixed(&reg, input, 6, 0, 6, &mut region, ONIG_OPTION_NONE);
+        assert_eq!(r, 3);
+        assert_eq!(region.num_regs, 3);
+        assert_eq!(
+            region.iter().collect::<Vec<_>>(),
+            [Some(3..5), Some(3..5), None]
+        );
+        assert_eq!((region.beg(3), region.end(3)), (-1, -1));
+
+        // Agrees with the heap-backed region
+        let (_, heap) = onig_search(
+            &reg,
+            input,
+            6,
+            0,
+            6,
+            Some(OnigRegion::new()),
+            ONIG_OPTION_NONE,
+        );
+        assert!(region.iter().eq(heap.unwrap().iter()));
+
+        // No match leaves the region alone.
+        let r = onig_match_fixed(&reg, input, 6, 0, &mut region, ONIG_OPTION_NONE);
+        assert_eq!(r, ONIG_MISMATCH);
+        assert_eq!(region.get(0), Some(3..5));
+
+        // Too few slots for the groups, unless only group 0 is reported
+        let mut small = OnigRegionFixed::<1>::new();
+        let r = onig_match_fixed(&reg, input, 6, 3, &mut small, ONIG_OPTION_NONE);
+        assert_eq!(r, ONIGERR_INVALID_ARGUMENT);
+        let r = onig_match_fixed(
+            &reg,
+            input,
+            6,
+            3,
+            &mut small,
+            ONIG_OPTION_DONT_CAPTURE_GROUP,
+        );
+        assert_eq!(r, 2);
+        assert_eq!(small.iter().collect::<Vec<_>>(), [Some(3..5)]);
+    }
+
+    #[test]
+    fn literal_eq_chunk_boundaries() {
+        let a: Vec<u8> = (0..40u8).collect();
+        for n in 0..=40 {
+            assert!(literal_eq(&a[..n], &a[..n]));
+            // A difference at every position, including the overlapped tail.
+            for i in 0..n {
+                let mut b = a[..n].to_vec();
+                b[i] ^= 0x20;
+                assert!(!literal_eq(&a[..n], &b), "n={n} i={i}");
+            }
+        }
+    }
+
+    #[test]
+    fn map_search_with_non_ascii_bytes() {
+        let reg = regcomp::onig_new(
+            "(?:é|ж|<)\\d".as_bytes(),
+            ONIG_OPTION_NONE,
+            &crate::encodings::utf8::ONIG_ENCODING_UTF8,
+            &crate::regsyntax::OnigSyntaxOniguruma,
+        )
+        .unwrap();
+        assert_eq!(reg.optimize, OptimizeType::Map);
+        assert_eq!(reg.map_byte_count, 3);
+        let input = "é ж< жx ж1 é2".as_bytes();
+        let end = input.len();
+        let (r, _) = onig_search(&reg, input, end, 0, end, None, ONIG_OPTION_NONE);
+        assert_eq!(r, 11);
+        let (r, _) = onig_search(&reg, input, end, end, 0, None, ONIG_OPTION_NONE);
+        assert_eq!(r, 15);
+        let (r, _) = onig_search(&reg, input, end, 10, 0, None, ONIG_OPTION_NONE);
+        assert_eq!(r, ONIG_MISMATCH);
+    }
+
+    #[test]
+    fn long_literal_search_and_match() {
+        let reg = regcomp::onig_new(
+            b"transition-timing-function:(\\w+)",
+            ONIG_OPTION_NONE,
+            &crate::encodings::utf8::ONIG_ENCODING_UTF8,
+            &crate::regsyntax::OnigSyntaxOniguruma,
+        )
+        .unwrap();
+        assert!(reg.exact_finder.is_some());
+        let input = b"a { transition-timing-functio:x; transition-timing-function:ease }";
+        let end = input.len();
+        let (r, region) = onig_search(
+            &reg,
+            input,
+            end,
+            0,
+            end,
+            Some(OnigRegion::new()),
+            ONIG_OPTION_NONE,
+        );
+        assert_eq!(r, 33);
+        let region = region.unwrap();
+        assert_eq!((region.beg[1], region.end[1]), (60, 64));
+        let (r, _) = onig_search(&reg, input, end, 34, end, None, ONIG_OPTION_NONE);
+        assert_eq!(r, ONIG_MISMATCH);
+    }
+
+    #[test]
+    fn simple_empty_check_loops() {
+        let search = |pattern: &str, input: &str| {
+            let reg = regcomp::onig_new(
+                pattern.as_bytes(),
+                ONIG_OPTION_NONE,
+                &crate::encodings::utf8::ONIG_ENCODING_UTF8,
+                &crate::regsyntax::OnigSyntaxOniguruma,
+            )
+            .unwrap();
+            let (r, region) = onig_search(
+                &reg,
+                input.as_bytes(),
+                input.len(),
+                0,
+                input.len(),
+                Some(OnigRegion::new()),
+                ONIG_OPTION_NONE,
+            );
+            (r, region.map_or(-1, |region| region.end[0]))
+        };
+        assert_eq!(search("(?>a*)*b", "aaab"), (0, 4));
+        assert_eq!(search("(?:a*+)+b", "xb"), (1, 2));
+        assert_eq!(search("(?:b?+c*+)*d", "bccbd"), (0, 5));
+        assert_eq!(search("(?>a*){2,5}b", "aab"), (0, 3));
+        assert_eq!(search("(?>a*)*?c", "aac"), (0, 3));
+        // Backtracking leaves the loop at an earlier iteration.
+        assert_eq!(search("(?:(?>a*)*a|x)", "aaa"), (0, 1));
+        assert_eq!(search("((?>a*)*)(b|ab)", "aab"), (0, 3));
+    }
+
+    #[test]
+    fn small_class_single_and_multibyte() {
+        let search = |pattern: &str, input: &[u8]| {
+            let reg = regcomp::onig_new(
+                pattern.as_bytes(),
+                ONIG_OPTION_NONE,
+                &crate::encodings::utf8::ONIG_ENCODING_UTF8,
+                &crate::regsyntax::OnigSyntaxOniguruma,
+            )
+            .unwrap();
+            onig_search(
+                &reg,
+                input,
+                input.len(),
+                0,
+                input.len(),
+                None,
+                ONIG_OPTION_NONE,
+            )
+            .0
+        };
+        assert_eq!(search("[a-z©]+", "ABC©ab".as_bytes()), 3);
+        assert_eq!(search("[©Ω]", "x\u{800}Ω".as_bytes()), 4);
+        assert_eq!(search("[^a-zΩ]", "abΩ\u{800}".as_bytes()), 4);
+        // A stray continuation byte is its own code point without a bitset
+        // half, and never in the class with one (as CClassMb / CClassMix).
+        assert_eq!(search("[©]", b"x\xa9"), 1);
+        assert_eq!(search("[a©]", b"x\xa9"), ONIG_MISMATCH);
+    }
+
+    fn callout_regex(pattern: &[u8]) -> Result<RegexType, i32> {
+        regcomp::onig_new(
+            pattern,
+            ONIG_OPTION_NONE,
+            &crate::encodings::utf8::ONIG_ENCODING_UTF8,
+            &crate::regsyntax::OnigSyntaxOniguruma,
+        )
+        .map_err(|e| e.code())
+    }
+
+    #[test]
+    fn total_count_callout_spans_match_attempts() {
+        let reg = callout_regex(b"(*COUNT[C])(*TOTAL_COUNT[T])\\w\\d").unwrap();
+        let input = b"abc1";
+        let mut mp = onig_new_match_param();
+        let search = |mp: &mut OnigMatchParam| {
+            onig_search_with_param(&reg, input, 4, 0, 4, None, ONIG_OPTION_NONE, mp).0
+        };
+        // Attempts at 0 and 1 fail, the one at 2 matches.
+        assert_eq!(search(&mut mp), 2);
+        assert_eq!(
+            onig_get_callout_data_by_tag(&reg, &mp.callout_data, b"C", 0),
+            Some(1)
+        );
+        assert_eq!(
+            onig_get_callout_data_by_tag(&reg, &mp.callout_data, b"T", 0),
+            Some(3)
+        );
+        // TOTAL_COUNT keeps counting while the param is reused.
+        assert_eq!(search(&mut mp), 2);
+        assert_eq!(
+            onig_get_callout_data_by_tag(&reg, &mp.callout_data, b"T", 0),
+            Some(6)
+        );
+        assert_eq!(
+            onig_set_callout_data_by_tag(&reg, &mut mp.callout_data, b"T", 0, 100),
+            ONIG_NORMAL
+        );
+        assert_eq!(search(&mut mp), 2);
+        assert_eq!(
+            onig_get_callout_data_by_tag(&reg, &mp.callout_data, b"T", 0),
+            Some(103)
+        );
+        onig_initialize_match_param(&mut mp);
+        assert!(mp.callout_data.is_empty());
+
+        // Plain searches start from zero every time.
+        let (r, _) = onig_search(&reg, input, 4, 0, 4, None, ONIG_OPTION_NONE);
+        assert_eq!(r, 2);
+    }
+
+    #[test]
+    fn search_skips_starts_that_cannot_match() {
+        // TOTAL_COUNT at the start of the pattern counts match attempts.
+        let attempts = |pattern: &str, input: &[u8], start: usize, range: usize| {
+            let reg = callout_regex(format!("(*TOTAL_COUNT[T]){pattern}").as_bytes()).unwrap();
+            let mut mp = onig_new_match_param();
+            let end = input.len();
+            let r = onig_search_with_param(
+                &reg,
+                input,
+                end,
+                start,
+                range,
+                None,
+                ONIG_OPTION_NONE,
+                &mut mp,
+            )
+            .0;
+            let n = onig_get_callout_data_by_tag(&reg, &mp.callout_data, b"T", 0);
+            (r, n.unwrap_or(0))
+        };
+        // The only `foo` is too close to the start for `\w+` to precede it:
+        // once past it, no start can match.
+        let text = [&b"-foo"[..], &[b'b'; 100]].concat();
+        assert_eq!(
+            attempts("\\w+foo", &text, 0, text.len()),
+            (ONIG_MISMATCH, 1)
+        );
+        // Too short for three characters anywhere.
+        assert_eq!(
+            attempts("(?:a|.)(?:b|.)(?:c|.)", b"1-", 0, 2),
+            (ONIG_MISMATCH, 0)
+        );
+        // Backward, the first start that leaves room for three characters.
+        assert_eq!(attempts("(?:a|.)(?:b|.)(?:c|.)", b"xxxxx", 4, 0), (2, 1));
+    }
+
+    #[test]
+    fn not_options_at_window_edges() {
+        // Search doc[..6] as a window into a larger document: "d" is past
+        // its end.
+        let doc = b"a\nb\nc\nd";
+        let search = |pattern: &[u8], start: usize, option: OnigOptionType| {
+            let reg = callout_regex(pattern).unwrap();
+            onig_search(&reg, doc, 6, start, 6, None, option).0
+        };
+        let not_begin = ONIG_OPTION_NOT_BEGIN_STRING;
+        let not_end = ONIG_OPTION_NOT_END_STRING;
+        assert_eq!(search(b"\\A", 0, ONIG_OPTION_NONE), 0);
+        assert_eq!(search(b"\\A", 0, not_begin), ONIG_MISMATCH);
+        assert_eq!(search(b"c\\n\\z", 4, ONIG_OPTION_NONE), 4);
+        assert_eq!(search(b"c\\n\\z", 4, not_end), ONIG_MISMATCH);
+        assert_eq!(search(b"c\\n?\\Z", 4, ONIG_OPTION_NONE), 4);
+        assert_eq!(search(b"c\\n?\\Z", 4, not_end), ONIG_MISMATCH);
+        assert_eq!(search(b"\\Z", 4, not_end), ONIG_MISMATCH);
+        // Line anchors only follow NOTBOL/NOTEOL.
+        assert_eq!(search(b"^b", 0, not_begin), 2);
+        assert_eq!(search(b"c$", 0, not_end), 4);
+
+        assert_eq!(search(b"\\Gc", 4, ONIG_OPTION_NONE), 4);
+        assert_eq!(
+            search(b"\\Gc", 4, ONIG_OPTION_NOT_BEGIN_POSITION),
+            ONIG_MISMATCH
+        );
+        assert_eq!(search(b"\\Gc|\\n", 4, ONIG_OPTION_NOT_BEGIN_POSITION), 5);
+        assert_eq!(search(b"\\Gc", 2, ONIG_OPTION_NONE), ONIG_MISMATCH);
+    }
+
+    #[test]
+    fn error_callout_aborts_search() {
+        let search = |pattern: &[u8], input: &[u8]| {
+            let reg = callout_regex(pattern).unwrap();
+            onig_search(
+                &reg,
+                input,
+                input.len(),
+                0,
+                input.len(),
+                None,
+                ONIG_OPTION_NONE,
+            )
+            .0
+        };
+        assert_eq!(search(b"a(*ERROR)", b"xa"), ONIG_ABORT);
+        assert_eq!(search(b"a(*ERROR{-1000})", b"xa"), -1000);
+        assert_eq!(search(b"a(*ERROR{1})", b"xa"), ONIGERR_INVALID_CALLOUT_BODY);
+        // Not reached: no error.
+        assert_eq!(search(b"b|a(*ERROR)", b"xb"), 1);
+        assert_eq!(search(b"a(*ERROR)", b"xyz"), ONIG_MISMATCH);
+
+        assert_eq!(
+            callout_regex(b"(*ERROR{x})").err(),
+            Some(ONIGERR_INVALID_CALLOUT_ARG)
+        );
+        assert_eq!(
+            callout_regex(b"(*ERROR{-1,-2})").err(),
+            Some(ONIGERR_INVALID_CALLOUT_ARG)
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn input_longer_than_i32_max_is_rejected() {
+        let (mut reg, mut env) = make_test_context();
+        let root = regparse::onig_parse_tree(b"a", &mut reg, &mut env).unwrap();
+        assert_eq!(regcomp::compile_from_tree(&root, &mut reg, &env), 0);
+        // Zeroed, so the pages are never touched.
+        let mut hay = vec![0u8; ONIG_MAX_INPUT_LEN + 1];
+        hay[5] = b'a';
+        let end = hay.len();
+
+        let (r, region) = onig_search(
+            &reg,
+            &hay,
+            end,
+            0,
+            end,
+            Some(OnigRegion::new()),
+            ONIG_OPTION_NONE,
+        );
+        assert_eq!(r, ONIGERR_TOO_LONG_INPUT);
+        assert!(region.is_some());
+        let (r, _) = onig_match(&reg, &hay, end, 5, None, ONIG_OPTION_NONE);
+        assert_eq!(r, ONIGERR_TOO_LONG_INPUT);
+        let mut mp = onig_new_match_param();
+        let (r, _) = onig_match_with_param(&reg, &hay, end, 5, None, ONIG_OPTION_NONE, &mut mp);
+        assert_eq!(r, ONIGERR_TOO_LONG_INPUT);
+        let (r, _) = onig_scan(
+            &reg,
+            &hay,
+            end,
+            OnigRegion::new(),
+            ONIG_OPTION_NONE,
+            |_, _, _| 0,
+        );
+        assert_eq!(r, ONIGERR_TOO_LONG_INPUT);
+
+        // A shorter `end` into the same buffer is fine.
+        let (r, _) = onig_search(&reg, &hay, 10, 0, 10, None, ONIG_OPTION_NONE);
+        assert_eq!(r, 5);
+    }
+
+    fn utf8_regex(pattern: &str) -> RegexType {
+        regcomp::onig_new(
+            pattern.as_bytes(),
+            ONIG_OPTION_NONE,
+            &crate::encodings::utf8::ONIG_ENCODING_UTF8,
+            &crate::regsyntax::OnigSyntaxOniguruma,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn small_programs() {
+        for pattern in [
+            "abc",
+            "colou?r",
+            r"\bfoo\b|^bar$",
+            "[a-z]é[^x]\\w\\W.",
+            r"ERROR \d\d?\d?\z",
+        ] {
+            assert!(utf8_regex(pattern).small_match, "{pattern}");
+        }
+        for pattern in ["(a)b", "a+", "x.*y", "(?=a)b", "(a)\\1", "(?>ab|a)c"] {
+            assert!(!utf8_regex(pattern).small_match, "{pattern}");
+        }
+    }
+
+    #[test]
+    fn small_match_agrees_with_vm() {
+        let texts = [
+            "", "colour", "color\n", "foo bar", "bar", "aéyz!.", "ERROR 42", "xé1_ \n",
+        ];
+        let options = [
+            ONIG_OPTION_NONE,
+            ONIG_OPTION_NOTBOL,
+            ONIG_OPTION_NOTEOL,
+            ONIG_OPTION_FIND_NOT_EMPTY,
+            ONIG_OPTION_MATCH_WHOLE_STRING,
+        ];
+        for pattern in [
+            "colou?r",
+            r"\bfoo\b|^bar$",
+            "[a-z]é[^x]\\w\\W.",
+            r"ERROR \d\d?\d?\z",
+            r"\Z|\A",
+            "(?i)COLOR",
+            "[^\\n]?",
+        ] {
+            let reg = utf8_regex(pattern);
+            assert!(reg.small_match, "{pattern}");
+            for text in texts {
+                let text = text.as_bytes();
+                for option in options {
+                    for at in 0..=text.len() {
+                        let (small, _) = onig_match(&reg, text, text.len(), at, None, option);
+                        let (vm, _) =
+                            onig_match(&reg, text, text.len(), at, Some(OnigRegion::new()), option);
+                        assert_eq!(small, vm, "{pattern} at {at} in {text:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn small_match_retry_limit() {
+        let reg = utf8_regex("(?:a|ab)(?:c|bcd)(?:d|de)x");
+        assert!(reg.small_match);
+        let text = b"abcdex";
+        let mut msa = MatchArg::new(&reg, ONIG_OPTION_NONE, None, 0);
+        msa.retry_limit_in_match = 1;
+        let (r, _) = onig_match_inner(&reg, text, text.len(), 0, &mut msa);
+        assert_eq!(r, ONIGERR_RETRY_LIMIT_IN_MATCH_OVER);
+        let mut msa = MatchArg::new(&reg, ONIG_OPTION_NONE, None, 0);
+        let (r, _) = onig_match_inner(&reg, text, text.len(), 0, &mut msa);
+        assert_eq!(r, 6);
+        assert_eq!(msa.retry_limit_in_search_counter, 2);
+    }
+
+    #[test]
+    fn operand_mismatch_fails_the_opcode() {
+        let mut reg = utf8_regex("abc|.bc");
+        let str3 = reg
+            .ops
+            .iter_mut()
+            .find(|op| op.opcode == OpCode::Str3)
+            .unwrap();
+        str3.payload = OperationPayload::None;
+        assert!(reg.small_match);
+        let text = b"abc";
+        for region in [None, Some(OnigRegion::new())] {
+            let (r, _) = onig_match(&reg, text, text.len(), 0, region, ONIG_OPTION_NONE);
+            assert_eq!(r, 3);
+        }
+    }
 }
@@ -0,0 +1,191 @@
+// reggnu.rs - Port of reggnu.c
+// GNU regex API compatibility layer (re_compile_pattern, re_search, re_match).
+//
+// `struct re_registers` is `OnigRegion` and `regex_t` is `RegexType`, exactly
+// as in oniggnu.h. Regions follow the Rust port's convention of being passed
+// in by value and handed back alongside the result code.
+//
+// Not ported: re_free_pattern() and re_free_registers() (Drop, see ADR-004),
+// re_set_casetable() and re_mbcinit(), which only switch between legacy
+// encodings (see ADR-002), and re_recompile_pattern(), which is behind
+// USE_RECOMPILE_API and disabled in the C build.
+
+#![allow(non_camel_case_types)]
+
+use crate::encodings::utf8::ONIG_ENCODING_UTF8;
+use crate::oniguruma::*;
+use crate::regcomp::{onig_compile, onig_get_default_case_fold_flag, onig_reg_init};
+use crate::regenc::onigenc_get_right_adjust_char_head;
+use crate::regerror::onig_error_code_to_str;
+use crate::regexec::{onig_match, onig_search};
+use crate::regint::RegexType;
+use crate::regsyntax::onig_get_default_syntax;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+
+/// GNU name for the match region (`struct re_registers` in oniggnu.h).
+pub type re_registers = OnigRegion;
+
+/// Clamp a GNU `int` position into `[0, size]`.
+#[inline]
+fn gnu_pos(pos: i32, size: usize) -> usize {
+    if pos < 0 {
+        0
+    } else {
+        (pos as usize).min(size)
+    }
+}
+
+/// Move `startpos` onto a character head, in the direction of `range`.
+pub fn re_adjust_startpos(
+    reg: &RegexType,
+    string: &[u8],
+    size: i32,
+    startpos: i32,
+    range: i32,
+) -> i32 {
+    let size = gnu_pos(size, string.len());
+    if startpos > 0 && reg.enc.max_enc_len() != 1 && (startpos as usize) < size {
+        let s = startpos as usize;
+        let p = if range > 0 {
+            onigenc_get_right_adjust_char_head(reg.enc, 0, s, string)
+        } else {
+            reg.enc.left_adjust_char_head(0, s, string)
+        };
+        return p as i32;
+    }
+    startpos
+}
+
+/// Match `reg` anchored at `pos`. Returns the match length or `ONIG_MISMATCH`.
+pub fn re_match(
+    reg: &RegexType,
+    str: &[u8],
+    size: i32,
+    pos: i32,
+    regs: Option<re_registers>,
+) -> (i32, Option<re_registers>) {
+    let end = gnu_pos(size, str.len());
+    if pos < 0 || pos as usize > end {
+        return (ONIG_MISMATCH, regs);
+    }
+    onig_match(reg, str, end, pos as usize, regs, ONIG_OPTION_NONE)
+}
+
+/// Search from `startpos` over `range` bytes; a negative `range` searches backward.
+/// Returns the match start or `ONIG_MISMATCH`.
+pub fn re_search(
+    bufp: &RegexType,
+    string: &[u8],
+    size: i32,
+    startpos: i32,
+    range: i32,
+    regs: Option<re_registers>,
+) -> (i32, Option<re_registers>) {
+    let end = gnu_pos(size, string.len());
+    if startpos < 0 || startpos as usize > end {
+        return (ONIG_MISMATCH, regs);
+    }
+    let range_pos = gnu_pos(startpos.saturating_add(range), end);
+    onig_search(
+        bufp,
+        string,
+        end,
+        startpos as usize,
+        range_pos,
+        regs,
+        ONIG_OPTION_NONE,
+    )
+}
+
+/// Compile `pattern` into `reg`, which must come from [`re_alloc_pattern`].
+/// On failure the error message is written to `ebuf` when given.
+pub fn re_compile_pattern(pattern: &[u8], reg: &mut RegexType, ebuf: Option<&mut String>) -> i32 {
+    let r = onig_compile(reg, pattern);
+    if r != ONIG_NORMAL {
+        if let Some(ebuf) = ebuf {
+            *ebuf = onig_error_code_to_str(r, None);
+        }
+    }
+    r
+}
+
+/// Allocate an empty pattern using the default syntax and UTF-8.
+pub fn re_alloc_pattern() -> Result<RegexType, i32> {
+    onig_reg_init(
+        ONIG_OPTION_DEFAULT,
+        onig_get_default_case_fold_flag(),
+        &ONIG_ENCODING_UTF8,
+        onig_get_default_syntax(),
+    )
+    .map_err(|e| e.code())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compile(pattern: &[u8]) -> RegexType {
+        let mut reg = re_alloc_pattern().unwrap();
+        assert_eq!(re_compile_pattern(pattern, &mut reg, None), ONIG_NORMAL);
+        reg
+    }
+
+    #[test]
+    fn gnu_search_forward() {
+        let reg = compile(b"b+");
+        let s = b"aabbbc";
+        let (r, regs) = re_search(
+            &reg,
+            s,
+            s.len() as i32,
+            0,
+            s.len() as i32,
+            Some(OnigRegion::new()),
+        );
+        assert_eq!(r, 2);
+        let regs = regs.unwrap();
+        assert_eq!((regs.beg[0], regs.end[0]), (2, 5));
+    }
+
+    #[test]
+    fn gnu_search_backward() {
+        let reg = compile(b"a");
+        let s = b"abca";
+        let (r, _) = re_search(&reg, s, s.len() as i32, 3, -3, None);
+        assert_eq!(r, 3);
+        let (r, _) = re_search(&reg, s, s.len() as i32, 2, -2, None);
+        assert_eq!(r, 0);
+    }
+
+    #[test]
+    fn gnu_match_returns_length() {
+        let reg = compile(b"\\d+");
+        let s = b"ab123c";
+        assert_eq!(re_match(&reg, s, s.len() as i32, 2, None).0, 3);
+        assert_eq!(re_match(&reg, s, s.len() as i32, 0, None).0, ONIG_MISMATCH);
+        assert_eq!(re_match(&reg, s, s.len() as i32, 99, None).0, ONIG_MISMATCH);
+    }
+
+    #[test]
+    fn gnu_compile_error_message() {
+        let mut reg = re_alloc_pattern().unwrap();
+        let mut ebuf = String::new();
+        let r = re_compile_pattern(b"(", &mut reg, Some(&mut ebuf));
+        assert_eq!(r, ONIGERR_END_PATTERN_WITH_UNMATCHED_PARENTHESIS);
+        assert_eq!(ebuf, "end pattern with unmatched parenthesis");
+    }
+
+    #[test]
+    fn gnu_adjust_startpos() {
+        let reg = compile(b"x");
+        let s = "aé".as_bytes(); // 'é' occupies bytes 1..3
+        assert_eq!(re_adjust_startpos(&reg, s, 3, 2, 1), 3);
+        assert_eq!(re_adjust_startpos(&reg, s, 3, 2, -1), 1);
+        assert_eq!(re_adjust_startpos(&reg, s, 3, 1, 1), 1);
+    }
+}
@@ -1,10 +1,14 @@
 // regint.rs - Port of regint.h
 // Internal types, OpCode, Operation, BitSet, MemStatus, regex_t.
 
-use std::collections::HashMap;
+use alloc::collections::BTreeMap;
+use core::time::Duration;
 
 use crate::oniguruma::*;
 use crate::regenc::OnigEncoding;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+use crate::sys::Instant;
 
 // === Feature Flags (C #define USE_*) ===
 pub const USE_CALL: bool = true;
@@ -130,7 +134,7 @@ pub const BITSET_REAL_SIZE: usize = SINGLE_BYTE_SIZE / BITS_IN_ROOM;
 pub type Bits = u32;
 pub type BitSet = [Bits; BITSET_REAL_SIZE];
 
-pub const SIZE_BITSET: usize = std::mem::size_of::<BitSet>();
+pub const SIZE_BITSET: usize = core::mem::size_of::<BitSet>();
 
 #[inline]
 pub fn bitset_clear(bs: &mut BitSet) {
@@ -159,6 +163,17 @@ pub fn bitset_set_bit(bs: &mut BitSet, pos: usize) {
     bs[bs_room(pos)] |= bs_bit(pos);
 }
 
+// Bitmap over every code point below SMALL_CLASS_CODE_LIMIT (256
+// bytes), indexed with the same bs_room/bs_bit helpers as BitSet.
+pub const SMALL_CLASS_CODE_LIMIT: usize = 0x800;
+pub type SmallCodeSet = [Bits; SMALL_CLASS_CODE_LIMIT / BITS_IN_ROOM];
+
+#[inline]
+pub fn small_code_set_at(cs: &SmallCodeSet, code: OnigCodePoint) -> bool {
+    let code = code as usize;
+    code < SMALL_CLASS_CODE_LIMIT && (cs[bs_room(code)] & bs_bit(code)) != 0
+}
+
 #[inline]
 #[cfg_attr(coverage_nightly, coverage(off))]
 pub fn bitset_clear_bit(bs: &mut BitSet, pos: usize) {
@@ -295,6 +310,16 @@ pub enum OpCode {
     Return = 81,
     CalloutContents = 82,
     CalloutName = 83,
+    // Custom opcode (unstable-opcodes feature).
+    Extension = 84,
+    // Character class whose code points all lie below U+0800,
+    // tested with a bitmap instead of a code-range binary search.
+    CClassSmall = 85,
+    CClassSmallNot = 86,
+    // Empty check of a loop whose body leaves no backtrack entry
+    // (QuantNode::simple_empty_check); the start lives in a register.
+    EmptyCheckStartSimple = 87,
+    EmptyCheckEndSimple = 88,
 }
 
 // === SaveType ===
@@ -484,6 +509,13 @@ pub enum OperationPayload {
         num: MemNumType,
         id: MemNumType,
     },
+    Extension {
+        num: MemNumType,
+    },
+    CClassSmall {
+        bsp: Box<BitSet>,
+        codes: Box<SmallCodeSet>,
+    },
 }
 
 // === Callout constants ===
@@ -497,6 +529,8 @@ pub const CALLOUT_BUILTIN_MAX: i32 = 1;
 pub const CALLOUT_BUILTIN_COUNT: i32 = 2;
 pub const CALLOUT_BUILTIN_CMP: i32 = 3;
 pub const CALLOUT_BUILTIN_SKIP: i32 = 4;
+pub const CALLOUT_BUILTIN_TOTAL_COUNT: i32 = 5;
+pub const CALLOUT_BUILTIN_ERROR: i32 = 6;
 
 /// Result codes for callout functions
 pub const ONIG_CALLOUT_FAIL: i32 = 1;
@@ -534,7 +568,7 @@ pub struct RepeatRange {
 // === RegexExt (Callout Extension) ===
 pub struct RegexExt {
     pub pattern: Vec<u8>,
-    pub tag_table: Option<HashMap<Vec<u8>, i32>>,
+    pub tag_table: Option<BTreeMap<Vec<u8>, i32>>,
     pub callout_num: i32,
     pub callout_list: Vec<CalloutListEntry>,
 }
@@ -559,9 +593,11 @@ pub struct RegexType {
     // metadata
     pub(crate) enc: OnigEncoding,
     pub(crate) options: OnigOptionType,
-    pub(crate) syntax: *const OnigSyntaxType,
+    pub(crate) syntax: OnigSyntaxType,
     pub(crate) case_fold_flag: OnigCaseFoldType,
     pub(crate) name_table: Option<crate::regparse_types::NameTable>,
+    // Line terminator convention (see OnigNewline).
+    pub(crate) newline: OnigNewline,
 
     // optimization
     pub(crate) optimize: OptimizeType,
@@ -571,12 +607,37 @@ pub struct RegexType {
     pub(crate) anc_dist_max: OnigLen,
     pub(crate) sub_anchor: i32,
     pub(crate) exact: Vec<u8>,
+    // Prebuilt SIMD searcher for `exact`, used by forward_search
+    // instead of the Sunday skip table.
+    pub(crate) exact_finder: Option<memchr::memmem::Finder<'static>>,
     pub(crate) map: [u8; CHAR_MAP_SIZE],
     pub(crate) map_offset: i32,
     pub(crate) map_bytes: [u8; 3],
     pub(crate) map_byte_count: u8,
     pub(crate) dist_min: OnigLen,
     pub(crate) dist_max: OnigLen,
+    // The required literal selected by the optimizer, kept even when
+    // the map optimization wins. Used by the API's literal pre-scan.
+    pub(crate) req_exact: Vec<u8>,
+    pub(crate) req_dist_min: OnigLen,
+    pub(crate) req_dist_max: OnigLen,
+    // Length bounds of a whole match, kept for Regex::optimize_info().
+    pub(crate) match_len_min: OnigLen,
+    pub(crate) match_len_max: OnigLen,
+    // Innermost enclosing capture group of each group, for
+    // Regex::group_parents().
+    pub(crate) group_parents: Vec<Option<u32>>,
+    // Shift-Or program for short fixed patterns (see shiftor.rs).
+    pub(crate) shift_or: Option<crate::shiftor::ShiftOr>,
+    // onig_match() may run the program on a fixed-size stack
+    // without touching the heap (see regexec::is_small_program).
+    pub(crate) small_match: bool,
+    // Custom opcode handlers and their uses (see extension.rs).
+    #[cfg(feature = "unstable-opcodes")]
+    pub(crate) opcodes: crate::extension::OpcodeTable,
+    // What the VM ran for this regex (see stats.rs).
+    #[cfg(feature = "exec-stats")]
+    pub(crate) exec_stats: crate::stats::ExecCounters,
 
     // subroutine call support
     pub(crate) called_addrs: Vec<i32>, // group_num -> called entry address
@@ -586,6 +647,104 @@ pub struct RegexType {
     pub(crate) extp: Option<RegexExt>,
 }
 
+/// A compiled pattern: the program and the optimizer data searches read.
+///
+/// Searching never writes to it, so one pattern can be shared between
+/// threads behind an `Arc` while each thread searches with its own
+/// [`ExecState`](crate::regexec::ExecState). What only compiling needs
+/// (error locations, warnings, limits) is kept apart and dropped once the
+/// pattern is built.
+pub type CompiledPattern = RegexType;
+
+/// What one compile produces besides the program: where parsing failed,
+/// the warnings raised, and the limits it ran under. The parser and
+/// compiler reach it through `ParseEnv::compile`.
+#[derive(Default)]
+pub(crate) struct CompileState {
+    // Where the parse failed, and the name a name error refers to
+    // (C reports only the name, through OnigErrorInfo).
+    pub(crate) error_span: Option<(usize, usize)>,
+    pub(crate) error_name: Option<(usize, usize)>,
+    // Warnings raised while parsing, for
+    // RegexBuilder::build_with_warnings().
+    pub(crate) warnings: Vec<crate::error::Warning>,
+    // Size and time limits of the compile.
+    pub(crate) budget: CompileBudget,
+}
+
+/// Units of compile work (parsed expressions, tuned nodes, emitted ops)
+/// between two reads of the clock.
+const COMPILE_TIME_CHECK_INTERVAL: u32 = 256;
+
+/// Limits on the cost of compiling one pattern, set by
+/// `RegexBuilder::max_compiled_bytes` and `RegexBuilder::compile_timeout`.
+/// The parser, the tree tuner and the code emitter report their work here;
+/// once a limit is exceeded every later report returns the same error.
+#[derive(Default)]
+pub(crate) struct CompileBudget {
+    /// Largest program in bytes, 0 for no limit.
+    max_bytes: usize,
+    /// Bytes of program emitted so far.
+    used_bytes: usize,
+    /// Longest compile time and when compiling started.
+    deadline: Option<(Duration, Instant)>,
+    /// Work done since the clock was last read.
+    ticks: u32,
+    /// Ops of the program already counted in `used_bytes`.
+    charged_ops: usize,
+    /// The error for the exceeded limit, or 0.
+    pub(crate) exceeded: i32,
+}
+
+impl CompileBudget {
+    /// A budget for a compile starting now.
+    pub(crate) fn new(max_bytes: usize, timeout: Option<Duration>) -> Self {
+        CompileBudget {
+            max_bytes,
+            deadline: timeout.map(|t| (t, Instant::now())),
+            ..Default::default()
+        }
+    }
+
+    /// Whether any limit is set.
+    pub(crate) fn is_limited(&self) -> bool {
+        self.max_bytes != 0 || self.deadline.is_some()
+    }
+
+    /// Count one unit of work, reading the clock every
+    /// COMPILE_TIME_CHECK_INTERVAL units. Returns the error once a limit
+    /// is exceeded, otherwise 0.
+    pub(crate) fn tick(&mut self) -> i32 {
+        if let Some((timeout, start)) = &self.deadline {
+            self.ticks += 1;
+            if self.ticks >= COMPILE_TIME_CHECK_INTERVAL {
+                self.ticks = 0;
+                if start.elapsed() >= *timeout {
+                    self.exceeded = ONIGERR_COMPILE_TIME_LIMIT_OVER;
+                }
+            }
+        }
+        self.exceeded
+    }
+
+    /// Count the ops appended to `ops` since the last call, `size(op)`
+    /// bytes and one unit of work each. Returns the error once a limit is
+    /// exceeded, otherwise 0.
+    pub(crate) fn emit(&mut self, ops: &[Operation], size: impl Fn(&Operation) -> usize) -> i32 {
+        if self.is_limited() {
+            for op in ops.get(self.charged_ops..).unwrap_or_default() {
+                self.used_bytes += size(op);
+                if self.max_bytes != 0 && self.used_bytes > self.max_bytes {
+                    self.exceeded = ONIGERR_COMPILED_SIZE_LIMIT_OVER;
+                }
+                self.tick();
+            }
+        }
+        self.charged_ops = ops.len();
+        self.exceeded
+    }
+}
+
 // === Optimization data structures ===
 pub const OPT_EXACT_MAXLEN: usize = 24;
 
@@ -767,6 +926,11 @@ pub fn opton_find_longest(option: OnigOptionType) -> bool {
     option.contains(ONIG_OPTION_FIND_LONGEST)
 }
 
+#[inline]
+pub fn opton_leftmost_longest(option: OnigOptionType) -> bool {
+    option.contains(ONIG_OPTION_LEFTMOST_LONGEST)
+}
+
 #[inline]
 pub fn opton_find_not_empty(option: OnigOptionType) -> bool {
     option.contains(ONIG_OPTION_FIND_NOT_EMPTY)
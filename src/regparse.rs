@@ -10,14 +10,17 @@
 #![allow(unused_assignments)]
 #![allow(unused_mut)]
 
-use std::sync::atomic::{AtomicPtr, Ordering};
-use std::sync::Mutex;
+use core::sync::atomic::{AtomicPtr, Ordering};
 
+use crate::error::{Warning, WarningKind};
 use crate::oniguruma::*;
 use crate::regenc::*;
 use crate::regexec::OnigCalloutFunc;
 use crate::regint::*;
 use crate::regparse_types::*;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+use crate::sys::Mutex;
 
 // ============================================================================
 // Global Warn Functions (port of C's onig_warn / onig_verb_warn)
@@ -25,8 +28,8 @@ use crate::regparse_types::*;
 
 pub type OnigWarnFunc = fn(s: &str);
 
-static WARN_FUNC: AtomicPtr<()> = AtomicPtr::new(std::ptr::null_mut());
-static VERB_WARN_FUNC: AtomicPtr<()> = AtomicPtr::new(std::ptr::null_mut());
+static WARN_FUNC: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+static VERB_WARN_FUNC: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
 
 #[cfg_attr(coverage_nightly, coverage(off))]
 pub fn onig_set_warn_func(f: OnigWarnFunc) {
@@ -40,6 +43,67 @@ pub fn onig_set_verb_warn_func(f: OnigWarnFunc) {
     VERB_WARN_FUNC.store(p, Ordering::Relaxed);
 }
 
+/// Port of onig_syntax_warn(): pass `message` about `pattern` to the global
+/// warn function `func`, if one is set, formatted as C does.
+///
+/// The warning is also recorded in the compile state, even
+/// when `func` is None or unset.
+fn syntax_warn(
+    env: &ParseEnv,
+    pattern: &[u8],
+    func: Option<&AtomicPtr<()>>,
+    kind: WarningKind,
+    span: (usize, usize),
+    message: String,
+) {
+    if let Some(f) = func.map(|f| f.load(Ordering::Relaxed)) {
+        if !f.is_null() {
+            let f: OnigWarnFunc = unsafe { core::mem::transmute(f) };
+            f(&format!(
+                "{}: /{}/",
+                message,
+                String::from_utf8_lossy(pattern)
+            ));
+        }
+    }
+    env.compile
+        .borrow_mut()
+        .warnings
+        .push(Warning::new(kind, message, span));
+}
+
+/// Port of CC_ESC_WARN: `c` taken literally in a character class.
+fn cc_esc_warn(env: &ParseEnv, pattern: &[u8], c: &str, span: (usize, usize)) {
+    if is_syntax_bv(&env.syntax, ONIG_SYN_WARN_CC_OP_NOT_ESCAPED)
+        && is_syntax_bv(&env.syntax, ONIG_SYN_BACKSLASH_ESCAPE_IN_CC)
+    {
+        let message = format!("character class has '{}' without escape", c);
+        syntax_warn(
+            env,
+            pattern,
+            Some(&WARN_FUNC),
+            WarningKind::UnescapedMetaChar,
+            span,
+            message,
+        );
+    }
+}
+
+/// Port of CLOSE_BRACKET_WITHOUT_ESC_WARN.
+fn close_bracket_without_esc_warn(env: &ParseEnv, pattern: &[u8], span: (usize, usize)) {
+    if is_syntax_bv(&env.syntax, ONIG_SYN_WARN_CC_OP_NOT_ESCAPED) {
+        let message = "regular expression has ']' without escape".to_string();
+        syntax_warn(
+            env,
+            pattern,
+            Some(&WARN_FUNC),
+            WarningKind::UnescapedMetaChar,
+            span,
+            message,
+        );
+    }
+}
+
 // ============================================================================
 // Global Callout Name Registry (port of C's GlobalCalloutNameList)
 // ============================================================================
@@ -106,7 +170,7 @@ pub fn onig_set_callout_of_name(
         name: name.to_vec(),
     };
 
-    let mut registry = CALLOUT_NAME_REGISTRY.lock().unwrap();
+    let mut registry = CALLOUT_NAME_REGISTRY.lock();
     // Check if name already exists — update in place
     for (i, existing) in registry.iter_mut().enumerate() {
         if existing.name == name {
@@ -123,7 +187,7 @@ pub fn onig_set_callout_of_name(
 /// Port of C's onig_get_callout_name_by_name_id.
 #[cfg_attr(coverage_nightly, coverage(off))]
 pub fn onig_get_callout_name_by_name_id(name_id: i32) -> Option<Vec<u8>> {
-    let registry = CALLOUT_NAME_REGISTRY.lock().unwrap();
+    let registry = CALLOUT_NAME_REGISTRY.lock();
     if name_id < 0 || name_id as usize >= registry.len() {
         return None;
     }
@@ -166,7 +230,7 @@ const PEND_VALUE: OnigCodePoint = 0;
 // Global State (matching C module-level statics)
 // ============================================================================
 
-use std::sync::atomic::{AtomicI32, AtomicU32};
+use core::sync::atomic::{AtomicI32, AtomicU32};
 
 static MAX_CAPTURE_NUM: AtomicI32 = AtomicI32::new(DEFAULT_MAX_CAPTURE_NUM);
 static PARSE_DEPTH_LIMIT: AtomicU32 = AtomicU32::new(DEFAULT_PARSE_DEPTH_LIMIT);
@@ -420,8 +484,10 @@ impl ParseEnv {
         self.cap_history = 0;
         self.backtrack_mem = 0;
         self.backrefed_mem = 0;
-        self.error = std::ptr::null();
-        self.error_end = std::ptr::null();
+        self.error = core::ptr::null();
+        self.error_end = core::ptr::null();
+        self.token_start.set(0);
+        self.error_name.set(None);
         self.num_call = 0;
         self.num_mem = 0;
         self.num_named = 0;
@@ -448,11 +514,11 @@ impl ParseEnv {
         if need as usize >= PARSEENV_MEMENV_SIZE {
             if let Some(ref mut dyn_env) = self.mem_env_dynamic {
                 if need as usize >= dyn_env.len() {
-                    let new_alloc = std::cmp::max(dyn_env.len() * 2, need as usize + 1);
+                    let new_alloc = core::cmp::max(dyn_env.len() * 2, need as usize + 1);
                     dyn_env.resize_with(new_alloc, MemEnv::default);
                 }
             } else {
-                let alloc = std::cmp::max(INIT_PARSEENV_MEMENV_ALLOC_SIZE, need as usize + 1);
+                let alloc = core::cmp::max(INIT_PARSEENV_MEMENV_ALLOC_SIZE, need as usize + 1);
                 let mut dyn_env = Vec::with_capacity(alloc);
                 // Copy static entries
                 for entry in &self.mem_env_static {
@@ -803,7 +869,7 @@ fn get_next_code_point(
 // Code range operations (BBuf-based multi-byte ranges)
 // ============================================================================
 
-const SIZE_CODE_POINT: usize = std::mem::size_of::<OnigCodePoint>();
+const SIZE_CODE_POINT: usize = core::mem::size_of::<OnigCodePoint>();
 
 fn bbuf_write_code_point(bbuf: &mut BBuf, pos: usize, code: OnigCodePoint) {
     let bytes = code.to_ne_bytes();
@@ -822,16 +888,26 @@ fn bbuf_read_code_point(bbuf: &BBuf, pos: usize) -> OnigCodePoint {
 }
 
 fn new_code_range() -> BBuf {
-    let mut bbuf = BBuf::with_capacity(SIZE_CODE_POINT * 5);
+    new_code_range_with_capacity(SIZE_CODE_POINT * 5)
+}
+
+fn new_code_range_with_capacity(cap: usize) -> BBuf {
+    let mut bbuf = BBuf::with_capacity(cap.max(SIZE_CODE_POINT * 5));
     bbuf_write_code_point(&mut bbuf, 0, 0); // n = 0
     bbuf
 }
 
+/// Initial byte capacity of a class's code range buffer for a pattern of
+/// `pattern_len` bytes: room for one range per eight pattern bytes, 2..=32.
+fn cc_buf_hint(pattern_len: usize) -> usize {
+    SIZE_CODE_POINT * (1 + 2 * (pattern_len / 8).clamp(2, 32))
+}
+
 fn add_code_range_to_buf(pbuf: &mut Option<BBuf>, from: OnigCodePoint, to: OnigCodePoint) -> i32 {
     let mut from = from;
     let mut to = to;
     if from > to {
-        std::mem::swap(&mut from, &mut to);
+        core::mem::swap(&mut from, &mut to);
     }
 
     if pbuf.is_none() {
@@ -839,19 +915,15 @@ fn add_code_range_to_buf(pbuf: &mut Option<BBuf>, from: OnigCodePoint, to: OnigC
     }
     let bbuf = pbuf.as_mut().unwrap();
     let n = bbuf_read_code_point(bbuf, 0) as usize;
-
-    // Read existing range data
-    let mut data = Vec::with_capacity(n * 2);
-    for i in 0..n * 2 {
-        data.push(bbuf_read_code_point(bbuf, SIZE_CODE_POINT * (1 + i)));
-    }
+    // Ranges are read in place: data[i] is the i-th code point after n.
+    let data = |i: usize| bbuf_read_code_point(bbuf, SIZE_CODE_POINT * (1 + i));
 
     // Binary search for insertion point
     let mut low = 0usize;
     let mut bound = n;
     while low < bound {
         let x = (low + bound) >> 1;
-        if from > data[x * 2 + 1] {
+        if from > data(x * 2 + 1) {
             low = x + 1;
         } else {
             bound = x;
@@ -862,7 +934,7 @@ fn add_code_range_to_buf(pbuf: &mut Option<BBuf>, from: OnigCodePoint, to: OnigC
     bound = n;
     while high < bound {
         let x = (high + bound) >> 1;
-        if to + 1 >= data[x * 2] {
+        if to + 1 >= data(x * 2) {
             high = x + 1;
         } else {
             bound = x;
@@ -875,35 +947,28 @@ fn add_code_range_to_buf(pbuf: &mut Option<BBuf>, from: OnigCodePoint, to: OnigC
     }
 
     if inc_n != 1 {
-        if low < data.len() / 2 && from > data[low * 2] {
-            from = data[low * 2];
+        if low < n && from > data(low * 2) {
+            from = data(low * 2);
         }
-        if high > 0 && high - 1 < data.len() / 2 && to < data[(high - 1) * 2 + 1] {
-            to = data[(high - 1) * 2 + 1];
+        if high > 0 && high - 1 < n && to < data((high - 1) * 2 + 1) {
+            to = data((high - 1) * 2 + 1);
         }
     }
 
-    // Rebuild the data array with the merged range
+    // Replace ranges low..high with the merged range, without a copy.
     let new_n = (n as i32 + inc_n) as usize;
-    let mut new_data = Vec::with_capacity(new_n * 2);
-    for i in 0..low {
-        new_data.push(data[i * 2]);
-        new_data.push(data[i * 2 + 1]);
-    }
-    new_data.push(from);
-    new_data.push(to);
-    for i in high..n {
-        new_data.push(data[i * 2]);
-        new_data.push(data[i * 2 + 1]);
-    }
-
-    // Write back to bbuf
     let total_size = SIZE_CODE_POINT * (1 + new_n * 2);
-    bbuf.data.resize(total_size, 0);
-    bbuf_write_code_point(bbuf, 0, new_n as OnigCodePoint);
-    for i in 0..new_data.len() {
-        bbuf_write_code_point(bbuf, SIZE_CODE_POINT * (1 + i), new_data[i]);
+    if let Err(r) = bbuf.mem.grow(total_size) {
+        return r;
     }
+    let mut merged = [0u8; SIZE_CODE_POINT * 2];
+    merged[..SIZE_CODE_POINT].copy_from_slice(&from.to_ne_bytes());
+    merged[SIZE_CODE_POINT..].copy_from_slice(&to.to_ne_bytes());
+    bbuf.data.splice(
+        SIZE_CODE_POINT * (1 + low * 2)..SIZE_CODE_POINT * (1 + high * 2),
+        merged,
+    );
+    bbuf_write_code_point(bbuf, 0, new_n as OnigCodePoint);
 
     0
 }
@@ -915,12 +980,15 @@ fn add_code_range(
     to: OnigCodePoint,
 ) -> i32 {
     if from > to {
-        if is_syntax_bv(env.syntax, ONIG_SYN_ALLOW_EMPTY_RANGE_IN_CC) {
+        if is_syntax_bv(&env.syntax, ONIG_SYN_ALLOW_EMPTY_RANGE_IN_CC) {
             return 0;
         } else {
             return ONIGERR_EMPTY_RANGE_IN_CHAR_CLASS;
         }
     }
+    if pbuf.is_none() {
+        *pbuf = Some(new_code_range_with_capacity(env.cc_buf_hint));
+    }
     add_code_range_to_buf(pbuf, from, to)
 }
 
@@ -1135,8 +1203,8 @@ fn and_code_range_buf(
                 if to2 < from1 {
                     continue;
                 }
-                let from = std::cmp::max(from1, from2);
-                let to = std::cmp::min(to1, to2);
+                let from = core::cmp::max(from1, from2);
+                let to = core::cmp::min(to1, to2);
                 let r = add_code_range_to_buf(&mut result, from, to);
                 if r != 0 {
                     return (result, r);
@@ -1246,13 +1314,23 @@ fn add_ctype_to_cc_by_range(
     enc: OnigEncoding,
     sb_out: OnigCodePoint,
 ) -> i32 {
-    let mut r: i32;
     let range_opt = enc.get_ctype_code_range(ctype as u32, &mut 0);
     if range_opt.is_none() {
         return ONIGERR_TYPE_BUG;
     }
     let range = range_opt.unwrap();
+    add_code_ranges_to_cc(cc, range, not, sb_out)
+}
 
+/// Body of add_ctype_to_cc_by_range, split out so user-defined properties
+/// (whose ranges are not `'static`) can share it.
+fn add_code_ranges_to_cc(
+    cc: &mut CClassNode,
+    range: &[OnigCodePoint],
+    not: bool,
+    sb_out: OnigCodePoint,
+) -> i32 {
+    let mut r: i32;
     let n = range.len() / 2;
     if not {
         // Inverted: add everything NOT in the ranges
@@ -1262,7 +1340,7 @@ fn add_ctype_to_cc_by_range(
             let to = range[i * 2 + 1];
             if prev < from {
                 if prev < sb_out {
-                    let end = std::cmp::min(from - 1, sb_out - 1);
+                    let end = core::cmp::min(from - 1, sb_out - 1);
                     bitset_set_range(&mut cc.bs, prev as usize, end as usize);
                 }
                 if from > sb_out {
@@ -1288,11 +1366,11 @@ fn add_ctype_to_cc_by_range(
             let from = range[i * 2];
             let to = range[i * 2 + 1];
             if from < sb_out {
-                let end = std::cmp::min(to, sb_out - 1);
+                let end = core::cmp::min(to, sb_out - 1);
                 bitset_set_range(&mut cc.bs, from as usize, end as usize);
             }
             if to >= sb_out {
-                let start = std::cmp::max(from, sb_out);
+                let start = core::cmp::max(from, sb_out);
                 r = add_code_range_to_buf(&mut cc.mbuf, start, to);
                 if r != 0 {
                     return r;
@@ -1331,6 +1409,12 @@ fn add_ctype_to_cc(cc: &mut CClassNode, ctype: i32, not: bool, env: &ParseEnv) -
     if let Some(_) = range {
         return add_ctype_to_cc_by_range(cc, ctype, not, enc, sb_out);
     }
+    // User-defined Unicode properties have no 'static range table
+    if onigenc_is_unicode_encoding(enc) {
+        if let Some(range) = crate::unicode::user_property_code_range(ctype as u32) {
+            return add_code_ranges_to_cc(cc, &range, not, sb_out);
+        }
+    }
 
     // Fallback: iterate over single-byte range
     let max_code = if enc.min_enc_len() > 1 {
@@ -1361,13 +1445,161 @@ fn add_ctype_to_cc(cc: &mut CClassNode, ctype: i32, not: bool, env: &ParseEnv) -
 
 /// Adds code point to character class (bitset or mbuf)
 pub(crate) fn add_code_into_cc(cc: &mut CClassNode, code: OnigCodePoint, enc: OnigEncoding) {
-    if code < SINGLE_BYTE_SIZE as u32 {
-        bitset_set_bit(&mut cc.bs, code as usize);
-    } else {
+    if enc.min_enc_len() > 1 || enc.code_to_mbclen(code) != 1 {
         add_code_range_to_buf(&mut cc.mbuf, code, code);
+    } else {
+        bitset_set_bit(&mut cc.bs, code as usize);
     }
 }
 
+// ============================================================================
+// Case fold closure for character classes
+// ============================================================================
+
+/// Every fold pair of one encoding and fold flag, sorted by source code.
+///
+/// C walks all pairs through apply_all_case_fold for each ignorecase class
+/// and inserts matches one at a time. The table is built once per
+/// (encoding, flag) instead, so a class only looks up the codes it holds.
+struct CaseFoldTable {
+    /// Single-code folds `(from, to)`.
+    singles: Vec<(OnigCodePoint, OnigCodePoint)>,
+    /// Multi-char folds `(from, order, to)`; `order` is the position in
+    /// apply_all_case_fold, which fixes the order of the alternatives.
+    multis: Vec<(OnigCodePoint, usize, Vec<OnigCodePoint>)>,
+}
+
+type CaseFoldTableEntry = (&'static str, OnigCaseFoldType, &'static CaseFoldTable);
+
+static CASE_FOLD_TABLES: Mutex<Vec<CaseFoldTableEntry>> = Mutex::new(Vec::new());
+
+fn case_fold_table(enc: OnigEncoding, flag: OnigCaseFoldType) -> &'static CaseFoldTable {
+    let mut tables = CASE_FOLD_TABLES.lock();
+    if let Some(&(_, _, table)) = tables
+        .iter()
+        .find(|(name, f, _)| *name == enc.name() && *f == flag)
+    {
+        return table;
+    }
+
+    let mut singles = Vec::new();
+    let mut multis = Vec::new();
+    enc.apply_all_case_fold(flag, &mut |from: OnigCodePoint,
+                                        to: &[OnigCodePoint]|
+     -> i32 {
+        if to.len() == 1 {
+            singles.push((from, to[0]));
+        } else {
+            multis.push((from, multis.len(), to.to_vec()));
+        }
+        0
+    });
+    singles.sort_unstable();
+    multis.sort_by_key(|m| m.0);
+
+    let table: &'static CaseFoldTable = Box::leak(Box::new(CaseFoldTable { singles, multis }));
+    tables.push((enc.name(), flag, table));
+    table
+}
+
+/// Fold targets of every code in `cc`: single codes (sorted, deduplicated)
+/// and multi-char sequences in fold table order.
+fn cc_case_fold_targets(
+    cc: &CClassNode,
+    enc: OnigEncoding,
+    flag: OnigCaseFoldType,
+) -> (Vec<OnigCodePoint>, Vec<&'static [OnigCodePoint]>) {
+    let table = case_fold_table(enc, flag);
+    let mut codes = Vec::new();
+    let mut multis: Vec<(usize, &'static [OnigCodePoint])> = Vec::new();
+
+    let mut add_range = |from: OnigCodePoint, to: OnigCodePoint| {
+        let lo = table.singles.partition_point(|p| p.0 < from);
+        let hi = table.singles.partition_point(|p| p.0 <= to);
+        codes.extend(table.singles[lo..hi].iter().map(|p| p.1));
+        let lo = table.multis.partition_point(|m| m.0 < from);
+        let hi = table.multis.partition_point(|m| m.0 <= to);
+        multis.extend(table.multis[lo..hi].iter().map(|m| (m.1, m.2.as_slice())));
+    };
+
+    for c in 0..SINGLE_BYTE_SIZE {
+        if bitset_at(&cc.bs, c) {
+            add_range(c as OnigCodePoint, c as OnigCodePoint);
+        }
+    }
+    // Multi-byte codes have no ASCII-only folds: skip their ranges, which
+    // for a Unicode property can be hundreds
+    if let Some(mbuf) = cc
+        .mbuf
+        .as_ref()
+        .filter(|_| !case_fold_is_ascii_letters_only(flag))
+    {
+        let n = bbuf_read_code_point(mbuf, 0) as usize;
+        for i in 0..n {
+            let from = bbuf_read_code_point(mbuf, SIZE_CODE_POINT * (1 + i * 2));
+            let to = bbuf_read_code_point(mbuf, SIZE_CODE_POINT * (2 + i * 2));
+            add_range(from, to);
+        }
+    }
+
+    codes.sort_unstable();
+    codes.dedup();
+    multis.sort_by_key(|m| m.0);
+    multis.dedup_by_key(|m| m.0);
+    (codes, multis.into_iter().map(|m| m.1).collect())
+}
+
+/// Add sorted codes to a class, rebuilding its range buffer once instead
+/// of once per code. `ranges` is scratch space, emptied first.
+fn add_codes_into_cc(
+    cc: &mut CClassNode,
+    codes: &[OnigCodePoint],
+    ranges: &mut Vec<(OnigCodePoint, OnigCodePoint)>,
+) {
+    let split = codes.partition_point(|&c| c < SINGLE_BYTE_SIZE as OnigCodePoint);
+    for &c in &codes[..split] {
+        bitset_set_bit(&mut cc.bs, c as usize);
+    }
+    if split == codes.len() {
+        return;
+    }
+
+    ranges.clear();
+    if let Some(ref mbuf) = cc.mbuf {
+        let n = bbuf_read_code_point(mbuf, 0) as usize;
+        for i in 0..n {
+            ranges.push((
+                bbuf_read_code_point(mbuf, SIZE_CODE_POINT * (1 + i * 2)),
+                bbuf_read_code_point(mbuf, SIZE_CODE_POINT * (2 + i * 2)),
+            ));
+        }
+    }
+    ranges.extend(codes[split..].iter().map(|&c| (c, c)));
+    ranges.sort_unstable();
+
+    // Merge overlapping and adjacent ranges in place.
+    let mut len = 0;
+    for i in 0..ranges.len() {
+        let (from, to) = ranges[i];
+        if len > 0 && from <= ranges[len - 1].1.saturating_add(1) {
+            ranges[len - 1].1 = ranges[len - 1].1.max(to);
+        } else {
+            ranges[len] = (from, to);
+            len += 1;
+        }
+    }
+    ranges.truncate(len);
+    let merged = &ranges[..];
+
+    let mut bbuf = BBuf::with_capacity(SIZE_CODE_POINT * (1 + merged.len() * 2));
+    bbuf_write_code_point(&mut bbuf, 0, merged.len() as OnigCodePoint);
+    for (i, &(from, to)) in merged.iter().enumerate() {
+        bbuf_write_code_point(&mut bbuf, SIZE_CODE_POINT * (1 + i * 2), from);
+        bbuf_write_code_point(&mut bbuf, SIZE_CODE_POINT * (2 + i * 2), to);
+    }
+    cc.mbuf = Some(bbuf);
+}
+
 // ============================================================================
 // CC state machine helpers
 // ============================================================================
@@ -1406,7 +1638,7 @@ fn cc_char_next(
                         return ONIGERR_INVALID_CODE_POINT_VALUE;
                     }
                     if *from > to {
-                        if is_syntax_bv(env.syntax, ONIG_SYN_ALLOW_EMPTY_RANGE_IN_CC) {
+                        if is_syntax_bv(&env.syntax, ONIG_SYN_ALLOW_EMPTY_RANGE_IN_CC) {
                             *state = CS_COMPLETE;
                             *from_raw = to_raw;
                             *from = to;
@@ -1425,7 +1657,7 @@ fn cc_char_next(
                 }
             } else {
                 if *from > to {
-                    if is_syntax_bv(env.syntax, ONIG_SYN_ALLOW_EMPTY_RANGE_IN_CC) {
+                    if is_syntax_bv(&env.syntax, ONIG_SYN_ALLOW_EMPTY_RANGE_IN_CC) {
                         *state = CS_COMPLETE;
                         *from_raw = to_raw;
                         *from = to;
@@ -1494,6 +1726,125 @@ fn cc_cprop_next(
     0
 }
 
+/// An item of a character class and the code points it adds, for
+/// the linter's redundant item check. Items after each `&&` belong to the
+/// next operand.
+struct CcItem {
+    span: (usize, usize),
+    ranges: Vec<(OnigCodePoint, OnigCodePoint)>,
+    operand: u32,
+}
+
+/// Record the character `code` just added to a class: a single character,
+/// or the end of a range whose start is the last item. `prev_state` is the
+/// state before it was read.
+fn note_cc_value(
+    items: &mut Option<Vec<CcItem>>,
+    prev_state: i32,
+    state: i32,
+    code: OnigCodePoint,
+    span: (usize, usize),
+    operand: u32,
+) {
+    let Some(items) = items else {
+        return;
+    };
+    if prev_state != CS_RANGE {
+        items.push(CcItem {
+            span,
+            ranges: vec![(code, code)],
+            operand,
+        });
+        return;
+    }
+    // An empty range allowed by the syntax adds nothing.
+    let Some(start) = items.pop() else {
+        return;
+    };
+    let from = start.ranges[0].0;
+    if state == CS_COMPLETE && from <= code {
+        items.push(CcItem {
+            span: (start.span.0, span.1),
+            ranges: vec![(from, code)],
+            operand,
+        });
+    }
+}
+
+/// Record a set item (`\w`, `\p{L}`, `[:alpha:]`) just added to a class;
+/// `add` adds it again to an empty class to find its code points.
+fn note_cc_set(
+    items: &mut Option<Vec<CcItem>>,
+    span: (usize, usize),
+    operand: u32,
+    add: impl FnOnce(&mut CClassNode) -> i32,
+) {
+    let Some(items) = items else {
+        return;
+    };
+    let mut cc = CClassNode {
+        flags: 0,
+        bs: [0; BITSET_REAL_SIZE],
+        mbuf: None,
+    };
+    if add(&mut cc) < 0 {
+        return;
+    }
+    let mut ranges = Vec::new();
+    for b in 0..SINGLE_BYTE_SIZE {
+        if bitset_at(&cc.bs, b) {
+            ranges.push((b as OnigCodePoint, b as OnigCodePoint));
+        }
+    }
+    if let Some(ref mbuf) = cc.mbuf {
+        let n = bbuf_read_code_point(mbuf, 0) as usize;
+        for i in 0..n {
+            ranges.push((
+                bbuf_read_code_point(mbuf, SIZE_CODE_POINT * (1 + i * 2)),
+                bbuf_read_code_point(mbuf, SIZE_CODE_POINT * (2 + i * 2)),
+            ));
+        }
+    }
+    items.push(CcItem {
+        span,
+        ranges,
+        operand,
+    });
+}
+
+/// Collect the spans of the items of a class that the other items of the
+/// same operand already cover. Later items are checked first, so of two
+/// equal items the second one is reported.
+fn note_redundant_cc_items(items: Option<Vec<CcItem>>, env: &mut ParseEnv) {
+    let (Some(items), Some(out)) = (items, env.cc_redundant.as_mut()) else {
+        return;
+    };
+    let mut redundant = vec![false; items.len()];
+    for i in (0..items.len()).rev() {
+        let mut others: Vec<_> = items
+            .iter()
+            .enumerate()
+            .filter(|&(j, item)| j != i && !redundant[j] && item.operand == items[i].operand)
+            .flat_map(|(_, item)| item.ranges.iter().copied())
+            .collect();
+        others.sort_unstable();
+        let mut merged: Vec<(OnigCodePoint, OnigCodePoint)> = Vec::new();
+        for (from, to) in others {
+            match merged.last_mut() {
+                Some(last) if from <= last.1.saturating_add(1) => last.1 = last.1.max(to),
+                _ => merged.push((from, to)),
+            }
+        }
+        redundant[i] = items[i]
+            .ranges
+            .iter()
+            .all(|&(from, to)| merged.iter().any(|&(lo, hi)| lo <= from && to <= hi));
+        if redundant[i] {
+            out.push(items[i].span);
+        }
+    }
+}
+
 /// Check if a code point exists in pattern from position
 fn code_exist_check(
     c: OnigCodePoint,
@@ -1514,7 +1865,7 @@ fn code_exist_check(
             if code == c {
                 return true;
             }
-            if code == mc_esc(env.syntax) {
+            if code == mc_esc(&env.syntax) {
                 in_esc = true;
             }
         }
@@ -1641,6 +1992,9 @@ fn fetch_char_property_to_ctype(
         }
         pfetch_s(p, pattern, end, enc);
         let r = enc.property_name_to_ctype(&pattern[start..*p]);
+        if r < 0 {
+            env.error_name.set(Some((start, *p)));
+        }
         return r;
     }
 
@@ -1650,6 +2004,9 @@ fn fetch_char_property_to_ctype(
         let c = pfetch_s(p, pattern, end, enc);
         if c == '}' as u32 {
             let r = enc.property_name_to_ctype(&pattern[start..prev]);
+            if r < 0 {
+                env.error_name.set(Some((start, prev)));
+            }
             return r;
         } else if c == '(' as u32 || c == ')' as u32 || c == '{' as u32 || c == '|' as u32 {
             break;
@@ -1722,7 +2079,7 @@ fn is_posix_bracket_start(p: usize, end: usize, pattern: &[u8], enc: OnigEncodin
 // ============================================================================
 
 fn conv_backslash_value(c: OnigCodePoint, env: &ParseEnv) -> OnigCodePoint {
-    if is_syntax_op(env.syntax, ONIG_SYN_OP_ESC_CONTROL_CHARS) {
+    if is_syntax_op(&env.syntax, ONIG_SYN_OP_ESC_CONTROL_CHARS) {
         match c {
             0x6E => return '\n' as u32, // 'n'
             0x74 => return '\t' as u32, // 't'
@@ -1733,7 +2090,7 @@ fn conv_backslash_value(c: OnigCodePoint, env: &ParseEnv) -> OnigCodePoint {
             0x65 => return 0x1B,        // 'e' -> escape
             0x76 => {
                 // 'v'
-                if is_syntax_op2(env.syntax, ONIG_SYN_OP2_ESC_V_VTAB) {
+                if is_syntax_op2(&env.syntax, ONIG_SYN_OP2_ESC_V_VTAB) {
                     return 0x0B; // vertical tab
                 }
             }
@@ -1758,7 +2115,7 @@ fn fetch_escaped_value_raw(
     match c {
         0x4D => {
             // 'M'
-            if is_syntax_op2(env.syntax, ONIG_SYN_OP2_ESC_CAPITAL_M_BAR_META) {
+            if is_syntax_op2(&env.syntax, ONIG_SYN_OP2_ESC_CAPITAL_M_BAR_META) {
                 if p_end(*p, end) {
                     return Err(ONIGERR_END_PATTERN_AT_META);
                 }
@@ -1770,7 +2127,7 @@ fn fetch_escaped_value_raw(
                     return Err(ONIGERR_END_PATTERN_AT_META);
                 }
                 let c3 = pfetch_s(p, pattern, end, enc);
-                let val = if c3 == mc_esc(env.syntax) {
+                let val = if c3 == mc_esc(&env.syntax) {
                     fetch_escaped_value_raw(p, end, pattern, env)?
                 } else {
                     c3
@@ -1781,7 +2138,7 @@ fn fetch_escaped_value_raw(
         }
         0x43 => {
             // 'C'
-            if is_syntax_op2(env.syntax, ONIG_SYN_OP2_ESC_CAPITAL_C_BAR_CONTROL) {
+            if is_syntax_op2(&env.syntax, ONIG_SYN_OP2_ESC_CAPITAL_C_BAR_CONTROL) {
                 if p_end(*p, end) {
                     return Err(ONIGERR_END_PATTERN_AT_CONTROL);
                 }
@@ -1797,7 +2154,7 @@ fn fetch_escaped_value_raw(
                 if c3 == '?' as u32 {
                     return Ok(0x7F);
                 }
-                let val = if c3 == mc_esc(env.syntax) {
+                let val = if c3 == mc_esc(&env.syntax) {
                     fetch_escaped_value_raw(p, end, pattern, env)?
                 } else {
                     c3
@@ -1808,7 +2165,7 @@ fn fetch_escaped_value_raw(
         }
         0x63 => {
             // 'c'
-            if is_syntax_op(env.syntax, ONIG_SYN_OP_ESC_C_CONTROL) {
+            if is_syntax_op(&env.syntax, ONIG_SYN_OP_ESC_C_CONTROL) {
                 if p_end(*p, end) {
                     return Err(ONIGERR_END_PATTERN_AT_CONTROL);
                 }
@@ -1816,7 +2173,7 @@ fn fetch_escaped_value_raw(
                 if c2 == '?' as u32 {
                     return Ok(0x7F);
                 }
-                let val = if c2 == mc_esc(env.syntax) {
+                let val = if c2 == mc_esc(&env.syntax) {
                     fetch_escaped_value_raw(p, end, pattern, env)?
                 } else {
                     c2
@@ -1988,6 +2345,7 @@ fn fetch_name(
         }
 
         if r != 0 {
+            set_error_name(env, name_start, end, pattern, end_code);
             return Err(r);
         }
 
@@ -2001,6 +2359,7 @@ fn fetch_name(
                 return Err(ONIGERR_TOO_BIG_NUMBER);
             }
             if back_num == 0 && num_type == IS_REL_NUM {
+                env.error_name.set(Some((name_start, name_end)));
                 return Err(ONIGERR_INVALID_GROUP_NAME);
             }
             back_num *= sign;
@@ -2017,16 +2376,44 @@ fn fetch_name(
             break;
         }
     }
+    env.error_name.set(Some((name_start, name_end)));
     Err(r)
 }
 
+/// Record the name a name error refers to, up to its closing delimiter.
+/// Mirrors C's onig_scan_env_set_error_string() calls in fetch_name().
+fn set_error_name(
+    env: &ParseEnv,
+    name_start: usize,
+    end: usize,
+    pattern: &[u8],
+    end_code: OnigCodePoint,
+) {
+    let mut p = name_start;
+    let mut name_end = end;
+    while !p_end(p, end) {
+        let prev = p;
+        let c = pfetch_s(&mut p, pattern, end, env.enc);
+        if c == end_code || c == ')' as u32 {
+            name_end = prev;
+            break;
+        }
+    }
+    env.error_name.set(Some((name_start, name_end)));
+}
+
 // ============================================================================
 // Quantifier helpers
 // ============================================================================
 
 fn is_invalid_quantifier_target(node: &Node) -> bool {
     match node.node_type() {
-        NodeType::Anchor | NodeType::Gimmick => true,
+        // A custom opcode consumes input like any other atom.
+        NodeType::Gimmick => !matches!(
+            &node.inner,
+            NodeInner::Gimmick(gn) if gn.gimmick_type == GimmickType::Extension
+        ),
+        NodeType::Anchor => true,
         NodeType::Bag => false,
         NodeType::List => {
             // Check all elements
@@ -2112,6 +2499,11 @@ static REDUCE_TYPE_TABLE: [[u8; 6]; 6] = [
     [RQ_ASIS, RQ_A, RQ_P, RQ_AQ, RQ_AQ, RQ_DEL],        // '+?'
 ];
 
+// Quantifier spellings for the nested repeat warning, by type number and
+// by ReduceType.
+static POPULAR_Q_STR: [&str; 6] = ["?", "*", "+", "??", "*?", "+?"];
+static REDUCE_Q_STR: [&str; 7] = ["", "", "*", "+", "*?", "??", "+ and ??"];
+
 /// Port of C onig_reduce_nested_quantifier.
 /// pnode is the outer (parent) quantifier node whose body is the inner (child) quantifier.
 fn onig_reduce_nested_quantifier(pnode: &mut Box<Node>) -> Result<(), i32> {
@@ -2255,7 +2647,7 @@ fn fetch_interval(
     env: &ParseEnv,
 ) -> i32 {
     let enc = env.enc;
-    let syn = env.syntax;
+    let syn = &env.syntax;
     let mut pfetch_prev = *p;
     let mut non_low = false;
     let syn_allow = is_syntax_bv(syn, ONIG_SYN_ALLOW_INVALID_INTERVAL);
@@ -2379,7 +2771,11 @@ fn fetch_interval(
 
     if up != INFINITE_REPEAT && low > up {
         // {n,m}+ supported case: return error
-        if is_syntax_op2(syn, ONIG_SYN_OP2_PLUS_POSSESSIVE_INTERVAL) {
+        // BRE intervals (\{n,m\}) have no possessive reading,
+        // and POSIX makes a reversed range an error.
+        if is_syntax_op2(syn, ONIG_SYN_OP2_PLUS_POSSESSIVE_INTERVAL)
+            || is_syntax_op(syn, ONIG_SYN_OP_ESC_BRACE_INTERVAL)
+        {
             return ONIGERR_UPPER_SMALLER_THAN_LOWER_IN_REPEAT_RANGE;
         }
         // Otherwise: swap bounds and make possessive
@@ -2412,11 +2808,11 @@ fn is_head_of_bre_subexp(
             if p1 > start {
                 let code1 = pattern[p1] as u32;
                 if code1 == '(' as u32
-                    || (code1 == '|' as u32 && is_syntax_op(env.syntax, ONIG_SYN_OP_ESC_VBAR_ALT))
+                    || (code1 == '|' as u32 && is_syntax_op(&env.syntax, ONIG_SYN_OP_ESC_VBAR_ALT))
                 {
                     if let Some(p2) = onigenc_get_prev_char_head(enc, start, p1, pattern) {
                         let code2 = pattern[p2] as u32;
-                        if is_mc_esc_code(code2, env.syntax) {
+                        if is_mc_esc_code(code2, &env.syntax) {
                             let mut count = 0;
                             let mut pp = p2;
                             while pp > start {
@@ -2425,7 +2821,7 @@ fn is_head_of_bre_subexp(
                                 {
                                     pp = prev;
                                     let cc = pattern[pp] as u32;
-                                    if !is_mc_esc_code(cc, env.syntax) {
+                                    if !is_mc_esc_code(cc, &env.syntax) {
                                         break;
                                     }
                                     count += 1;
@@ -2458,12 +2854,12 @@ fn is_end_of_bre_subexp(
         return true;
     }
     let code = pattern[pos] as u32;
-    if is_mc_esc_code(code, env.syntax) {
+    if is_mc_esc_code(code, &env.syntax) {
         let next = pos + enc.mbc_enc_len(&pattern[pos..]);
         if next < end {
             let code2 = pattern[next] as u32;
             if code2 == ')' as u32
-                || (code2 == '|' as u32 && is_syntax_op(env.syntax, ONIG_SYN_OP_ESC_VBAR_ALT))
+                || (code2 == '|' as u32 && is_syntax_op(&env.syntax, ONIG_SYN_OP_ESC_VBAR_ALT))
             {
                 return true;
             }
@@ -2478,8 +2874,10 @@ fn is_end_of_bre_subexp(
 
 fn fetch_token(tok: &mut PToken, p: &mut usize, end: usize, pattern: &[u8], env: &ParseEnv) -> i32 {
     let enc = env.enc;
-    let syn = env.syntax;
+    let syn = &env.syntax;
     let mut pfetch_prev = *p;
+    tok.start = *p;
+    env.token_start.set(*p);
 
     if tok.code_point_continue {
         let mut code = 0u32;
@@ -2554,6 +2952,7 @@ fn fetch_token(tok: &mut PToken, p: &mut usize, end: usize, pattern: &[u8], env:
                     if !is_syntax_op(syn, ONIG_SYN_OP_ESC_BRACE_INTERVAL) {
                         return tok.token_type as i32;
                     }
+                    let brace = (env.token_start.get(), *p);
                     let r = fetch_interval(p, end, pattern, tok, env);
                     if r < 0 {
                         return r;
@@ -2568,6 +2967,15 @@ fn fetch_token(tok: &mut PToken, p: &mut usize, end: usize, pattern: &[u8], env:
                         return greedy_check2(tok, p, end, pattern, enc, syn);
                     }
                     // r == 1: normal char
+                    let message = "invalid interval '{' was taken as a literal".to_string();
+                    syntax_warn(
+                        env,
+                        pattern,
+                        None,
+                        WarningKind::InvalidInterval,
+                        brace,
+                        message,
+                    );
                 }
                 '|' => {
                     if !is_syntax_op(syn, ONIG_SYN_OP_ESC_VBAR_ALT) {
@@ -2617,6 +3025,22 @@ fn fetch_token(tok: &mut PToken, p: &mut usize, end: usize, pattern: &[u8], env:
                     tok.token_type = TokenType::Anchor;
                     tok.anchor = ANCR_NO_WORD_BOUNDARY;
                 }
+                // C compiles these only under USE_WORD_BEGIN_END, which is
+                // off by default; ferroni always honours the syntax bit.
+                '<' => {
+                    if !is_syntax_op(syn, ONIG_SYN_OP_ESC_LTGT_WORD_BEGIN_END) {
+                        return tok.token_type as i32;
+                    }
+                    tok.token_type = TokenType::Anchor;
+                    tok.anchor = ANCR_WORD_BEGIN;
+                }
+                '>' => {
+                    if !is_syntax_op(syn, ONIG_SYN_OP_ESC_LTGT_WORD_BEGIN_END) {
+                        return tok.token_type as i32;
+                    }
+                    tok.token_type = TokenType::Anchor;
+                    tok.anchor = ANCR_WORD_END;
+                }
                 's' => {
                     if !is_syntax_op(syn, ONIG_SYN_OP_ESC_S_WHITE_SPACE) {
                         return tok.token_type as i32;
@@ -2718,9 +3142,11 @@ fn fetch_token(tok: &mut PToken, p: &mut usize, end: usize, pattern: &[u8], env:
                                                     tok.backref_refs = entry.back_refs.clone();
                                                 }
                                             } else {
+                                                env.error_name.set(Some((name_start, name_end)));
                                                 return ONIGERR_UNDEFINED_NAME_REFERENCE;
                                             }
                                         } else {
+                                            env.error_name.set(Some((name_start, name_end)));
                                             return ONIGERR_UNDEFINED_NAME_REFERENCE;
                                         }
                                     }
@@ -3123,6 +3549,7 @@ fn fetch_token(tok: &mut PToken, p: &mut usize, end: usize, pattern: &[u8], env:
                     if !is_syntax_op(syn, ONIG_SYN_OP_BRACE_INTERVAL) {
                         return tok.token_type as i32;
                     }
+                    let brace = (env.token_start.get(), *p);
                     let r = fetch_interval(p, end, pattern, tok, env);
                     if r < 0 {
                         return r;
@@ -3137,6 +3564,15 @@ fn fetch_token(tok: &mut PToken, p: &mut usize, end: usize, pattern: &[u8], env:
                         return greedy_check2(tok, p, end, pattern, enc, syn);
                     }
                     // r == 1: normal char
+                    let message = "invalid interval '{' was taken as a literal".to_string();
+                    syntax_warn(
+                        env,
+                        pattern,
+                        None,
+                        WarningKind::InvalidInterval,
+                        brace,
+                        message,
+                    );
                 }
                 '|' => {
                     if !is_syntax_op(syn, ONIG_SYN_OP_VBAR_ALT) {
@@ -3210,6 +3646,7 @@ fn fetch_token(tok: &mut PToken, p: &mut usize, end: usize, pattern: &[u8], env:
                                         return ONIGERR_UNDEFINED_GROUP_OPTION;
                                     }
                                     tok.call_name_end = *p;
+                                    pinc(p, pattern, enc); // skip ')'
                                 }
                                 '-' | '+' => {
                                     if !p_end(*p, end) {
@@ -3351,7 +3788,11 @@ fn fetch_token(tok: &mut PToken, p: &mut usize, end: usize, pattern: &[u8], env:
                 }
                 ']' => {
                     // Normally this is handled in CC context.
-                    // Outside CC: warn or treat as literal
+                    // Outside CC: warn and treat as literal (/].../ is allowed)
+                    let start = env.token_start.get();
+                    if start > 0 {
+                        close_bracket_without_esc_warn(env, pattern, (start, *p));
+                    }
                 }
                 '#' => {
                     if opton_extend(env.options) {
@@ -3477,8 +3918,9 @@ fn fetch_token_cc(
     state: i32,
 ) -> i32 {
     let enc = env.enc;
-    let syn = env.syntax;
+    let syn = &env.syntax;
     let mut pfetch_prev = *p;
+    env.token_start.set(*p);
 
     if tok.code_point_continue {
         let mut code = 0u32;
@@ -3766,10 +4208,10 @@ fn fetch_token_cc(
                     tok.token_type = TokenType::CcOpenCC;
                 }
             }
+        } else if is_syntax_op2(syn, ONIG_SYN_OP2_CCLASS_SET_OP) {
+            tok.token_type = TokenType::CcOpenCC;
         } else {
-            if is_syntax_op2(syn, ONIG_SYN_OP2_CCLASS_SET_OP) {
-                tok.token_type = TokenType::CcOpenCC;
-            }
+            cc_esc_warn(env, pattern, "[", (env.token_start.get(), *p));
         }
     } else if c == '&' as u32 {
         if is_syntax_op2(syn, ONIG_SYN_OP2_CCLASS_SET_OP)
@@ -3844,10 +4286,14 @@ fn prs_cc(
         bs: [0; BITSET_REAL_SIZE],
         mbuf: None,
     };
+    let mut items = env.cc_redundant.is_some().then(Vec::new);
+    let mut operand = 0;
 
     // Main loop
     loop {
         let mut fetched = false;
+        let tok_start = env.token_start.get();
+        let prev_state = state;
 
         // Get cc pointer (either from node or work_cc)
         let use_work = work_cc_active;
@@ -3883,6 +4329,14 @@ fn prs_cc(
                     env.parse_depth -= 1;
                     return Err(cr);
                 }
+                note_cc_value(
+                    &mut items,
+                    prev_state,
+                    state,
+                    in_code,
+                    (tok_start, *p),
+                    operand,
+                );
             }
             TokenType::CrudeByte => {
                 let byte = tok.code as u8;
@@ -3945,6 +4399,14 @@ fn prs_cc(
                     env.parse_depth -= 1;
                     return Err(cr);
                 }
+                note_cc_value(
+                    &mut items,
+                    prev_state,
+                    state,
+                    in_code,
+                    (tok_start, *p),
+                    operand,
+                );
             }
             TokenType::CodePoint => {
                 let in_code = tok.code;
@@ -3983,6 +4445,14 @@ fn prs_cc(
                     env.parse_depth -= 1;
                     return Err(cr);
                 }
+                note_cc_value(
+                    &mut items,
+                    prev_state,
+                    state,
+                    in_code,
+                    (tok_start, *p),
+                    operand,
+                );
             }
             TokenType::CcPosixBracketOpen => {
                 let cc = if use_work {
@@ -3990,11 +4460,15 @@ fn prs_cc(
                 } else {
                     node.as_cclass_mut().unwrap()
                 };
+                let name_start = *p;
                 let cr = prs_posix_bracket(cc, p, end, pattern, env);
                 if cr < 0 {
                     env.parse_depth -= 1;
                     return Err(cr);
                 }
+                note_cc_set(&mut items, (tok_start, *p), operand, |cc| {
+                    prs_posix_bracket(cc, &mut { name_start }, end, pattern, env)
+                });
                 // cc_cprop_next
                 let cr2 = cc_cprop_next(cc, &mut curr_code, &mut curr_type, &mut state, env);
                 if cr2 != 0 {
@@ -4015,6 +4489,9 @@ fn prs_cc(
                     env.parse_depth -= 1;
                     return Err(cr);
                 }
+                note_cc_set(&mut items, (tok_start, *p), operand, |cc| {
+                    add_ctype_to_cc(cc, ctype, not, env)
+                });
                 let cr2 = cc_cprop_next(cc, &mut curr_code, &mut curr_type, &mut state, env);
                 if cr2 != 0 {
                     env.parse_depth -= 1;
@@ -4037,6 +4514,10 @@ fn prs_cc(
                     env.parse_depth -= 1;
                     return Err(cr);
                 }
+                let not = tok.prop_not;
+                note_cc_set(&mut items, (tok_start, *p), operand, |cc| {
+                    add_ctype_to_cc(cc, ctype, not, env)
+                });
                 let cr2 = cc_cprop_next(cc, &mut curr_code, &mut curr_type, &mut state, env);
                 if cr2 != 0 {
                     env.parse_depth -= 1;
@@ -4044,6 +4525,7 @@ fn prs_cc(
                 }
             }
             TokenType::CcRange => {
+                let dash = (env.token_start.get(), *p);
                 if state == CS_VALUE {
                     r = fetch_token_cc(tok, p, end, pattern, env, CS_RANGE);
                     if r < 0 {
@@ -4053,6 +4535,9 @@ fn prs_cc(
                     fetched = true;
                     if tok.token_type == TokenType::CcClose || tok.token_type == TokenType::CcAnd {
                         // [x-] or [x-&&...] -> treat dash as literal
+                        if tok.token_type == TokenType::CcAnd {
+                            cc_esc_warn(env, pattern, "-", dash);
+                        }
                         let cc = if use_work {
                             &mut work_cc
                         } else {
@@ -4073,9 +4558,10 @@ fn prs_cc(
                             env.parse_depth -= 1;
                             return Err(cr);
                         }
+                        note_cc_value(&mut items, prev_state, state, '-' as u32, dash, operand);
                     } else if curr_type == CV_CPROP {
                         if is_syntax_bv(
-                            env.syntax,
+                            &env.syntax,
                             ONIG_SYN_ALLOW_CHAR_TYPE_FOLLOWED_BY_MINUS_IN_CC,
                         ) {
                             // Treat dash as literal: [\w-%] -> [\w\-\%]
@@ -4099,6 +4585,7 @@ fn prs_cc(
                                 env.parse_depth -= 1;
                                 return Err(cr);
                             }
+                            note_cc_value(&mut items, prev_state, state, '-' as u32, dash, operand);
                         } else {
                             env.parse_depth -= 1;
                             return Err(ONIGERR_UNMATCHED_RANGE_SPECIFIER_IN_CHAR_CLASS);
@@ -4129,8 +4616,10 @@ fn prs_cc(
                         env.parse_depth -= 1;
                         return Err(cr);
                     }
+                    note_cc_value(&mut items, prev_state, state, in_code, dash, operand);
                 } else if state == CS_RANGE {
                     // [!--] - literal dash in range context
+                    cc_esc_warn(env, pattern, "-", dash);
                     let in_code = '-' as u32;
                     let cc = if use_work {
                         &mut work_cc
@@ -4152,6 +4641,7 @@ fn prs_cc(
                         env.parse_depth -= 1;
                         return Err(cr);
                     }
+                    note_cc_value(&mut items, prev_state, state, in_code, dash, operand);
                 } else {
                     // CS_COMPLETE
                     r = fetch_token_cc(tok, p, end, pattern, env, state);
@@ -4182,8 +4672,10 @@ fn prs_cc(
                             env.parse_depth -= 1;
                             return Err(cr);
                         }
-                    } else if is_syntax_bv(env.syntax, ONIG_SYN_ALLOW_DOUBLE_RANGE_OP_IN_CC) {
+                        note_cc_value(&mut items, prev_state, state, '-' as u32, dash, operand);
+                    } else if is_syntax_bv(&env.syntax, ONIG_SYN_ALLOW_DOUBLE_RANGE_OP_IN_CC) {
                         // [0-9-a] allowed
+                        cc_esc_warn(env, pattern, "-", dash);
                         let cc = if use_work {
                             &mut work_cc
                         } else {
@@ -4204,6 +4696,7 @@ fn prs_cc(
                             env.parse_depth -= 1;
                             return Err(cr);
                         }
+                        note_cc_value(&mut items, prev_state, state, '-' as u32, dash, operand);
                     } else {
                         env.parse_depth -= 1;
                         return Err(ONIGERR_UNMATCHED_RANGE_SPECIFIER_IN_CHAR_CLASS);
@@ -4273,6 +4766,7 @@ fn prs_cc(
                 }
                 and_start = true;
                 state = CS_START;
+                operand += 1;
 
                 if let Some(ref mut pcc) = prev_cc {
                     let cc = if use_work {
@@ -4345,6 +4839,8 @@ fn prs_cc(
         }
     }
 
+    note_redundant_cc_items(items, env);
+
     // Final intersection merge
     if let Some(ref mut pcc) = prev_cc {
         let cc = if work_cc_active {
@@ -4369,47 +4865,25 @@ fn prs_cc(
     // Case-fold expansion: add fold equivalents for all codes in the class
     if opton_ignorecase(env.options) {
         let cc = node.as_cclass_mut().unwrap();
-        // Collect codes to add (to avoid borrow issues during iteration)
-        let mut codes_to_add: Vec<OnigCodePoint> = Vec::new();
-        // Collect multi-char fold alternatives (each is a Vec<u8> of encoded bytes)
+        let (codes, multis) = cc_case_fold_targets(cc, enc, env.case_fold_flag);
+        let mut scratch = env.cc_scratch.take();
+        add_codes_into_cc(cc, &codes, &mut scratch);
+        env.cc_scratch.set(scratch);
+
+        // Multi-char fold alternatives, as encoded bytes
         let mut multi_char_alts: Vec<Vec<u8>> = Vec::new();
-        enc.apply_all_case_fold(env.case_fold_flag, &mut |from: OnigCodePoint,
-                                                          to: &[OnigCodePoint]|
-         -> i32 {
-            // Check if 'from' is in the (non-negated) class (check both bitset and mbuf)
-            let in_bs = if (from as usize) < SINGLE_BYTE_SIZE {
-                bitset_at(&cc.bs, from as usize)
-            } else {
-                false
-            };
-            let in_mb = if let Some(ref mbuf) = cc.mbuf {
-                crate::regexec::is_in_code_range_bytes(&mbuf.data, from)
-            } else {
-                false
-            };
-            let in_class = in_bs || in_mb;
-            if in_class {
-                if to.len() == 1 {
-                    codes_to_add.push(to[0]);
-                } else {
-                    // Multi-char fold: encode all codepoints to bytes
-                    let mut buf = Vec::new();
-                    let mut tmp = [0u8; ONIGENC_CODE_TO_MBC_MAXLEN];
-                    for &cp in to {
-                        let len = enc.code_to_mbc(cp, &mut tmp);
-                        if len > 0 {
-                            buf.extend_from_slice(&tmp[..len as usize]);
-                        }
-                    }
-                    if !buf.is_empty() {
-                        multi_char_alts.push(buf);
-                    }
+        for to in multis {
+            let mut buf = Vec::new();
+            let mut tmp = [0u8; ONIGENC_CODE_TO_MBC_MAXLEN];
+            for &cp in to {
+                let len = enc.code_to_mbc(cp, &mut tmp);
+                if len > 0 {
+                    buf.extend_from_slice(&tmp[..len as usize]);
                 }
             }
-            0
-        });
-        for code in codes_to_add {
-            add_code_into_cc(cc, code, enc);
+            if !buf.is_empty() {
+                multi_char_alts.push(buf);
+            }
         }
 
         // If there are multi-char fold alternatives, wrap in Alt(CC, string1, ...)
@@ -4485,13 +4959,58 @@ fn callout_tag_entry(env: &mut ParseEnv, tag: &[u8], num: i32) {
     let reg = unsafe { &mut *env.reg };
     let ext = reg.extp.as_mut().unwrap();
     if ext.tag_table.is_none() {
-        ext.tag_table = Some(std::collections::HashMap::new());
+        ext.tag_table = Some(alloc::collections::BTreeMap::new());
     }
     ext.tag_table.as_mut().unwrap().insert(tag.to_vec(), num);
 }
 
 /// Parse `(*NAME[tag]{args})` callout-of-name.
 /// `p` points right after `*`. Returns (node, 1) on success.
+/// `(*NAME)` / `(*NAME{arg})` naming a handler registered in
+/// reg.opcodes. Returns None when no handler has that name. `c` is the
+/// delimiter that ended the name.
+#[cfg(feature = "unstable-opcodes")]
+fn prs_extension_of_name(
+    p: &mut usize,
+    end: usize,
+    pattern: &[u8],
+    env: &mut ParseEnv,
+    name: &[u8],
+    c: u32,
+    cterm: u32,
+) -> Result<Option<Box<Node>>, i32> {
+    let reg = unsafe { &mut *env.reg };
+    if !reg.opcodes.contains(name) {
+        return Ok(None);
+    }
+    let arg = if c == '{' as u32 {
+        let arg_start = *p;
+        loop {
+            if p_end(*p, end) {
+                return Err(ONIGERR_END_PATTERN_IN_GROUP);
+            }
+            let arg_end = *p;
+            if pfetch_s(p, pattern, end, env.enc) == '}' as u32 {
+                break &pattern[arg_start..arg_end];
+            }
+        }
+    } else if c == cterm {
+        &pattern[*p..*p]
+    } else {
+        return Err(ONIGERR_INVALID_CALLOUT_PATTERN); // no [tag]
+    };
+    if c == '{' as u32 {
+        if p_end(*p, end) {
+            return Err(ONIGERR_END_PATTERN_IN_GROUP);
+        }
+        if pfetch_s(p, pattern, end, env.enc) != cterm {
+            return Err(ONIGERR_INVALID_CALLOUT_PATTERN);
+        }
+    }
+    let num = reg.opcodes.add_use(name, arg);
+    Ok(Some(node_new_extension(num)))
+}
+
 fn prs_callout_of_name(
     p: &mut usize,
     end: usize,
@@ -4537,7 +5056,15 @@ fn prs_callout_of_name(
         (CALLOUT_BUILTIN_CMP, CALLOUT_IN_PROGRESS)
     } else if name == b"SKIP" {
         (CALLOUT_BUILTIN_SKIP, CALLOUT_IN_PROGRESS)
+    } else if name == b"TOTAL_COUNT" {
+        (CALLOUT_BUILTIN_TOTAL_COUNT, CALLOUT_IN_BOTH)
+    } else if name == b"ERROR" {
+        (CALLOUT_BUILTIN_ERROR, CALLOUT_IN_PROGRESS)
     } else {
+        #[cfg(feature = "unstable-opcodes")]
+        if let Some(node) = prs_extension_of_name(p, end, pattern, env, name, c, cterm)? {
+            return Ok(node);
+        }
         return Err(ONIGERR_UNDEFINED_CALLOUT_NAME);
     };
 
@@ -4631,12 +5158,20 @@ fn prs_callout_of_name(
                 final_args.push(CalloutArg::Char(b'X'));
             }
         }
-        CALLOUT_BUILTIN_COUNT => {
+        CALLOUT_BUILTIN_COUNT | CALLOUT_BUILTIN_TOTAL_COUNT => {
             // args: [CHAR]. Arg 0 defaults to '>'
             if final_args.is_empty() {
                 final_args.push(CalloutArg::Char(b'>'));
             }
         }
+        CALLOUT_BUILTIN_ERROR => {
+            // args: [LONG]. Arg 0 defaults to ONIG_ABORT
+            match final_args.as_slice() {
+                [] => final_args.push(CalloutArg::Long(ONIG_ABORT as i64)),
+                [CalloutArg::Long(_)] => {}
+                _ => return Err(ONIGERR_INVALID_CALLOUT_ARG),
+            }
+        }
         CALLOUT_BUILTIN_CMP => {
             // args: [TAG|LONG, STRING, TAG|LONG]. All 3 required
             if final_args.len() != 3 {
@@ -4739,7 +5274,7 @@ fn prs_callout_one_arg(
 }
 
 fn try_parse_i64(s: &[u8]) -> Option<i64> {
-    let s_str = std::str::from_utf8(s).ok()?;
+    let s_str = core::str::from_utf8(s).ok()?;
     s_str.trim().parse::<i64>().ok()
 }
 
@@ -4852,7 +5387,7 @@ fn prs_conditional(
 ) -> Result<(Box<Node>, i32), i32> {
     let enc = env.enc;
 
-    if !is_syntax_op2(env.syntax, ONIG_SYN_OP2_QMARK_LPAREN_IF_ELSE) {
+    if !is_syntax_op2(&env.syntax, ONIG_SYN_OP2_QMARK_LPAREN_IF_ELSE) {
         return Err(ONIGERR_UNDEFINED_GROUP_OPTION);
     }
 
@@ -4893,7 +5428,7 @@ fn prs_conditional(
                     return Err(ONIGERR_INVALID_BACKREF);
                 }
                 let backrefs = [num];
-                condition = node_new_backref(1, &backrefs, false, 0);
+                condition = node_new_backref(1, &backrefs, false, 0, env);
             } else {
                 // Named ref
                 let name = &pattern[name_start..name_end];
@@ -4904,8 +5439,9 @@ fn prs_conditional(
                     None
                 };
                 if let Some(nums) = group_nums {
-                    condition = node_new_backref(nums.len() as i32, &nums, true, 0);
+                    condition = node_new_backref(nums.len() as i32, &nums, true, 0, env);
                 } else {
+                    env.error_name.set(Some((name_start, name_end)));
                     return Err(ONIGERR_UNDEFINED_NAME_REFERENCE);
                 }
             }
@@ -4996,7 +5532,7 @@ fn prs_conditional(
                 0
             };
             let backrefs = [back_num];
-            condition = node_new_backref(1, &backrefs, false, nest_level);
+            condition = node_new_backref(1, &backrefs, false, nest_level, env);
             if found_level {
                 condition.status_add(ND_ST_NEST_LEVEL);
             }
@@ -5067,7 +5603,8 @@ fn prs_conditional(
         condition_is_checker = false;
 
         let cond_node;
-        if c == '?' as u32 && is_syntax_op2(env.syntax, ONIG_SYN_OP2_QMARK_BRACE_CALLOUT_CONTENTS) {
+        if c == '?' as u32 && is_syntax_op2(&env.syntax, ONIG_SYN_OP2_QMARK_BRACE_CALLOUT_CONTENTS)
+        {
             // Condition is callout of contents: (?(?{...})THEN|ELSE)
             if !p_end(*p, end) && ppeek(*p, pattern, end, enc) == '{' as u32 {
                 pinc(p, pattern, enc); // consume '{'
@@ -5082,7 +5619,8 @@ fn prs_conditional(
                 let (cn, _) = prs_alts(tok, term, p, end, pattern, env, false)?;
                 cond_node = cn;
             }
-        } else if c == '*' as u32 && is_syntax_op2(env.syntax, ONIG_SYN_OP2_ASTERISK_CALLOUT_NAME) {
+        } else if c == '*' as u32 && is_syntax_op2(&env.syntax, ONIG_SYN_OP2_ASTERISK_CALLOUT_NAME)
+        {
             // Callout-of-name condition: (?(*FAIL)then|else), (?(*MAX{2})then|else)
             cond_node = prs_callout_of_name(p, end, pattern, env, ')' as u32)?;
         } else {
@@ -5512,7 +6050,7 @@ fn prs_bag(
     let c = ppeek(*p, pattern, end, enc);
     let option = env.options;
 
-    if c == '?' as u32 && is_syntax_op2(env.syntax, ONIG_SYN_OP2_QMARK_GROUP_EFFECT) {
+    if c == '?' as u32 && is_syntax_op2(&env.syntax, ONIG_SYN_OP2_QMARK_GROUP_EFFECT) {
         pinc(p, pattern, enc); // skip '?'
         if p_end(*p, end) {
             return Err(ONIGERR_END_PATTERN_IN_GROUP);
@@ -5590,24 +6128,24 @@ fn prs_bag(
                     let (target, _) = prs_alts(tok, term, p, end, pattern, env, false)?;
                     np.set_body(Some(target));
                     return Ok((np, 0));
-                } else if is_syntax_op2(env.syntax, ONIG_SYN_OP2_QMARK_LT_NAMED_GROUP) {
+                } else if is_syntax_op2(&env.syntax, ONIG_SYN_OP2_QMARK_LT_NAMED_GROUP) {
                     // Named group (?<name>...)
                     return prs_named_group(tok, '<' as u32, term, p, end, pattern, env, false);
                 }
                 return Err(ONIGERR_UNDEFINED_GROUP_OPTION);
             }
             '\'' => {
-                if is_syntax_op2(env.syntax, ONIG_SYN_OP2_QMARK_LT_NAMED_GROUP) {
+                if is_syntax_op2(&env.syntax, ONIG_SYN_OP2_QMARK_LT_NAMED_GROUP) {
                     return prs_named_group(tok, '\'' as u32, term, p, end, pattern, env, false);
                 }
                 return Err(ONIGERR_UNDEFINED_GROUP_OPTION);
             }
             '@' => {
                 if USE_CAPTURE_HISTORY
-                    && is_syntax_op2(env.syntax, ONIG_SYN_OP2_ATMARK_CAPTURE_HISTORY)
+                    && is_syntax_op2(&env.syntax, ONIG_SYN_OP2_ATMARK_CAPTURE_HISTORY)
                 {
                     // (?@<name>...) or (?@'name'...) — named group with capture history
-                    if is_syntax_op2(env.syntax, ONIG_SYN_OP2_QMARK_LT_NAMED_GROUP)
+                    if is_syntax_op2(&env.syntax, ONIG_SYN_OP2_QMARK_LT_NAMED_GROUP)
                         && !p_end(*p, end)
                     {
                         let c2 = ppeek(*p, pattern, end, enc);
@@ -5639,7 +6177,7 @@ fn prs_bag(
                 return prs_conditional(tok, term, p, end, pattern, env);
             }
             'P' => {
-                if is_syntax_op2(env.syntax, ONIG_SYN_OP2_QMARK_CAPITAL_P_NAME) {
+                if is_syntax_op2(&env.syntax, ONIG_SYN_OP2_QMARK_CAPITAL_P_NAME) {
                     if !p_end(*p, end) {
                         let c2 = ppeek(*p, pattern, end, enc);
                         if c2 == '<' as u32 {
@@ -5673,6 +6211,7 @@ fn prs_bag(
                                                 &refs,
                                                 true,
                                                 level_val,
+                                                env,
                                             );
                                             if has_level {
                                                 // level-based backref (rare)
@@ -5684,6 +6223,7 @@ fn prs_bag(
                                             return Ok((np, 0));
                                         }
                                     }
+                                    env.error_name.set(Some((name_start, name_end)));
                                     return Err(ONIGERR_UNDEFINED_NAME_REFERENCE);
                                 }
                                 Err(e) => return Err(e),
@@ -5693,8 +6233,8 @@ fn prs_bag(
                             pinc(p, pattern, enc); // skip '>'
                             match fetch_name('(' as u32, p, end, pattern, env, false) {
                                 Ok((name_start, name_end, gnum, _num_type, _has_level, _level)) => {
-                                    let name = &pattern[name_start..name_end];
-                                    let np = node_new_call(name, gnum, false);
+                                    let np =
+                                        node_new_call(pattern, (name_start, name_end), gnum, false);
                                     env.num_call += 1;
                                     return Ok((np, 0));
                                 }
@@ -5709,7 +6249,7 @@ fn prs_bag(
                 return prs_options(tok, term, p, end, pattern, env);
             }
             '~' => {
-                if c < 128 && is_syntax_op2(env.syntax, ONIG_SYN_OP2_QMARK_TILDE_ABSENT_GROUP) {
+                if c < 128 && is_syntax_op2(&env.syntax, ONIG_SYN_OP2_QMARK_TILDE_ABSENT_GROUP) {
                     if p_end(*p, end) {
                         return Err(ONIGERR_END_PATTERN_IN_GROUP);
                     }
@@ -5788,7 +6328,7 @@ fn prs_bag(
             }
             '{' => {
                 // Callout of contents: (?{...})
-                if !is_syntax_op2(env.syntax, ONIG_SYN_OP2_QMARK_BRACE_CALLOUT_CONTENTS) {
+                if !is_syntax_op2(&env.syntax, ONIG_SYN_OP2_QMARK_BRACE_CALLOUT_CONTENTS) {
                     return Err(ONIGERR_UNDEFINED_GROUP_OPTION);
                 }
                 let node = prs_callout_of_contents(p, end, pattern, env, ')' as u32)?;
@@ -5800,7 +6340,7 @@ fn prs_bag(
                 return prs_options(tok, term, p, end, pattern, env);
             }
         }
-    } else if c == '*' as u32 && is_syntax_op2(env.syntax, ONIG_SYN_OP2_ASTERISK_CALLOUT_NAME) {
+    } else if c == '*' as u32 && is_syntax_op2(&env.syntax, ONIG_SYN_OP2_ASTERISK_CALLOUT_NAME) {
         // Callout of name: (*FAIL), (*MAX{2}), (*COUNT[AB]{X}), (*CMP{AB,<,CD})
         pinc(p, pattern, enc); // skip '*'
         let node = prs_callout_of_name(p, end, pattern, env, ')' as u32)?;
@@ -5850,7 +6390,7 @@ fn prs_named_group(
     // Add to name table
     if let Some(ref mut nt) = unsafe { &mut *env.reg }.name_table {
         let name = &pattern[name_start..name_end];
-        let allow = is_syntax_bv(env.syntax, ONIG_SYN_ALLOW_MULTIPLEX_DEFINITION_NAME);
+        let allow = is_syntax_bv(&env.syntax, ONIG_SYN_ALLOW_MULTIPLEX_DEFINITION_NAME);
         nt.add(name, num, allow).map_err(|e| e)?;
     }
 
@@ -5900,7 +6440,7 @@ fn prs_options(
     env: &mut ParseEnv,
 ) -> Result<(Box<Node>, i32), i32> {
     let enc = env.enc;
-    let syn = env.syntax;
+    let syn = &env.syntax;
     let mut option = env.options;
     let mut neg = false;
     let mut whole_options = OnigOptionType::empty();
@@ -6140,6 +6680,13 @@ fn prs_options(
 // Main Parser: recursive descent
 // ============================================================================
 
+/// Empty node with a zero-width span at `pos`.
+fn node_new_empty_at(pos: usize) -> Box<Node> {
+    let mut node = node_new_empty();
+    node.span = Some((pos, pos));
+    node
+}
+
 /// Parse a single expression element (atom + quantifier)
 fn prs_exp(
     tok: &mut PToken,
@@ -6150,28 +6697,58 @@ fn prs_exp(
     env: &mut ParseEnv,
     group_head: bool,
 ) -> Result<(Box<Node>, i32), i32> {
+    // The compile budget set by RegexBuilder.
+    let r = env.compile.get_mut().budget.tick();
+    if r != 0 {
+        return Err(r);
+    }
     let mut group = 0;
+    let start = tok.start;
+    let mut last_start = start;
 
     if tok.token_type as i32 == term {
-        return Ok((node_new_empty(), tok.token_type as i32));
+        return Ok((node_new_empty_at(start), tok.token_type as i32));
     }
 
     let parse_depth = env.parse_depth;
 
     let node: Box<Node> = match tok.token_type {
         TokenType::Alt | TokenType::Eot => {
-            return Ok((node_new_empty(), tok.token_type as i32));
+            return Ok((node_new_empty_at(start), tok.token_type as i32));
         }
         TokenType::SubexpOpen => {
-            let (node, bag_r) = prs_bag(tok, TokenType::SubexpClose as i32, p, end, pattern, env)?;
+            let (mut node, bag_r) =
+                prs_bag(tok, TokenType::SubexpClose as i32, p, end, pattern, env)?;
             if bag_r == 1 {
                 // Group-only (non-capturing (?:...) or similar)
+                // The body keeps its own span; only synthesized nodes take the group's.
+                if node.span.is_none() {
+                    node.span = Some((start, *p));
+                }
                 // fetch_token to advance past the SubexpClose consumed by prs_alts
                 let r = fetch_token(tok, p, end, pattern, env);
                 if r < 0 {
                     return Err(r);
                 }
-                return check_quantifier(node, tok, p, end, pattern, env, 1, parse_depth);
+                // At a group head an alternation group stays visible (C:
+                // node_new_group), so (?~|(?:a|b)) is not read as (?~|a|b).
+                let group = if group_head && matches!(node.inner, NodeInner::Alt(_)) {
+                    2
+                } else {
+                    1
+                };
+                return check_quantifier(
+                    node,
+                    tok,
+                    p,
+                    end,
+                    pattern,
+                    env,
+                    group,
+                    parse_depth,
+                    start,
+                    start,
+                );
             } else if bag_r == 2 {
                 // Option-only (?i) or (?Ii)
                 let bag_options = match node.as_bag() {
@@ -6187,7 +6764,7 @@ fn prs_exp(
                         return Err(ONIGERR_INVALID_GROUP_OPTION);
                     }
                 }
-                if is_syntax_bv(env.syntax, ONIG_SYN_ISOLATED_OPTION_CONTINUE_BRANCH) {
+                if is_syntax_bv(&env.syntax, ONIG_SYN_ISOLATED_OPTION_CONTINUE_BRANCH) {
                     // Perl/Java: just set options and continue branch
                     env.options = bag_options;
                     let r = fetch_token(tok, p, end, pattern, env);
@@ -6214,6 +6791,7 @@ fn prs_exp(
                     let (target, _) = prs_alts(tok, term, p, end, pattern, env, false)?;
                     env.options = prev;
                     np.set_body(Some(target));
+                    np.span = Some((start, tok.start));
                     return Ok((np, tok.token_type as i32));
                 }
             } else if node.has_status(ND_ST_WHOLE_OPTIONS) {
@@ -6222,6 +6800,7 @@ fn prs_exp(
                     return Err(ONIGERR_INVALID_GROUP_OPTION);
                 }
                 // Scoped whole options must be the only item in the branch
+                node.span = Some((start, *p));
                 let r = fetch_token(tok, p, end, pattern, env);
                 if r < 0 {
                     return Err(r);
@@ -6237,7 +6816,7 @@ fn prs_exp(
             node
         }
         TokenType::SubexpClose => {
-            if !is_syntax_bv(env.syntax, ONIG_SYN_ALLOW_UNMATCHED_CLOSE_SUBEXP) {
+            if !is_syntax_bv(&env.syntax, ONIG_SYN_ALLOW_UNMATCHED_CLOSE_SUBEXP) {
                 return Err(ONIGERR_UNMATCHED_CLOSE_PARENTHESIS);
             }
             // Treat as literal byte
@@ -6248,6 +6827,7 @@ fn prs_exp(
             if opton_ignorecase(env.options) {
                 np.status_add(ND_ST_IGNORECASE);
             }
+            let mut atom_end = *p;
             // Collect consecutive string tokens
             loop {
                 let r = fetch_token(tok, p, end, pattern, env);
@@ -6257,15 +6837,30 @@ fn prs_exp(
                 if tok.token_type != TokenType::String {
                     break;
                 }
+                last_start = tok.start;
                 node_str_cat(&mut np, &pattern[tok.backp..*p]);
+                atom_end = *p;
             }
+            np.span = Some((start, atom_end));
             // Check for quantifier
-            return check_quantifier(np, tok, p, end, pattern, env, group, parse_depth);
+            return check_quantifier(
+                np,
+                tok,
+                p,
+                end,
+                pattern,
+                env,
+                group,
+                parse_depth,
+                start,
+                last_start,
+            );
         }
         TokenType::CrudeByte => {
             let byte = tok.code as u8;
             let mut buf = vec![byte];
             let mut fetched_non_crude = false;
+            let mut atom_end = *p;
 
             if byte >= 0x80 {
                 let expected_len = env.enc.mbc_enc_len(&[byte]);
@@ -6278,6 +6873,7 @@ fn prs_exp(
                         }
                         if tok.token_type == TokenType::CrudeByte {
                             buf.push(tok.code as u8);
+                            atom_end = *p;
                         } else {
                             fetched_non_crude = true;
                             break;
@@ -6296,11 +6892,23 @@ fn prs_exp(
                 }
             }
 
-            let np = node_new_str_crude(&buf);
+            let mut np = node_new_str_crude(&buf);
 
             // If we already fetched a non-CrudeByte token, use it directly
             if fetched_non_crude {
-                return check_quantifier(np, tok, p, end, pattern, env, group, parse_depth);
+                np.span = Some((start, atom_end));
+                return check_quantifier(
+                    np,
+                    tok,
+                    p,
+                    end,
+                    pattern,
+                    env,
+                    group,
+                    parse_depth,
+                    start,
+                    start,
+                );
             }
             np
         }
@@ -6372,7 +6980,8 @@ fn prs_exp(
             } else {
                 tok.backref_refs.clone()
             };
-            let mut np = node_new_backref(back_num, &refs, tok.backref_by_name, tok.backref_level);
+            let mut np =
+                node_new_backref(back_num, &refs, tok.backref_by_name, tok.backref_level, env);
             if opton_ignorecase(env.options) {
                 np.status_add(ND_ST_IGNORECASE);
             }
@@ -6380,8 +6989,12 @@ fn prs_exp(
             np
         }
         TokenType::Call => {
-            let name = &pattern[tok.call_name_start..tok.call_name_end];
-            let np = node_new_call(name, tok.call_gnum, tok.call_by_number);
+            let np = node_new_call(
+                pattern,
+                (tok.call_name_start, tok.call_name_end),
+                tok.call_gnum,
+                tok.call_by_number,
+            );
             env.num_call += 1;
             if tok.call_by_number && tok.call_gnum == 0 {
                 env.flags |= PE_FLAG_HAS_CALL_ZERO;
@@ -6448,7 +7061,7 @@ fn prs_exp(
             // Collect all chars until \E
             let qstart = *p;
             let mut qend = end;
-            let esc = mc_esc(env.syntax);
+            let esc = mc_esc(&env.syntax);
             while !p_end(*p, end) {
                 let save = *p;
                 let mut pfv = *p;
@@ -6461,6 +7074,7 @@ fn prs_exp(
                         break;
                     }
                 }
+                last_start = save;
             }
             let mut np = node_new_str(&pattern[qstart..qend]);
             if opton_ignorecase(env.options) {
@@ -6469,14 +7083,14 @@ fn prs_exp(
             np
         }
         TokenType::Repeat | TokenType::Interval => {
-            if is_syntax_bv(env.syntax, ONIG_SYN_CONTEXT_INDEP_REPEAT_OPS) {
-                if is_syntax_bv(env.syntax, ONIG_SYN_CONTEXT_INVALID_REPEAT_OPS) {
+            if is_syntax_bv(&env.syntax, ONIG_SYN_CONTEXT_INDEP_REPEAT_OPS) {
+                if is_syntax_bv(&env.syntax, ONIG_SYN_CONTEXT_INVALID_REPEAT_OPS) {
                     return Err(ONIGERR_TARGET_OF_REPEAT_OPERATOR_NOT_SPECIFIED);
                 }
                 node_new_empty()
             } else {
                 if tok.token_type == TokenType::Interval
-                    && is_syntax_op(env.syntax, ONIG_SYN_OP_ESC_BRACE_INTERVAL)
+                    && is_syntax_op(&env.syntax, ONIG_SYN_OP_ESC_BRACE_INTERVAL)
                 {
                     // BRE \{n\} at start: strip backslashes to get literal {n}
                     let raw = &pattern[tok.backp..*p];
@@ -6492,15 +7106,31 @@ fn prs_exp(
         }
     };
 
+    let mut node = node;
+    node.span = Some((start, *p));
+
     // Fetch next token and check for quantifier
     let r = fetch_token(tok, p, end, pattern, env);
     if r < 0 {
         return Err(r);
     }
-    check_quantifier(node, tok, p, end, pattern, env, group, parse_depth)
+    check_quantifier(
+        node,
+        tok,
+        p,
+        end,
+        pattern,
+        env,
+        group,
+        parse_depth,
+        start,
+        last_start,
+    )
 }
 
-/// Check if current token is a quantifier and apply it to node
+/// Check if current token is a quantifier and apply it to node.
+/// `start` is where the quantified atom begins in the pattern and
+/// `last_start` where its last character begins (for split strings).
 fn check_quantifier(
     mut node: Box<Node>,
     tok: &mut PToken,
@@ -6510,16 +7140,21 @@ fn check_quantifier(
     env: &mut ParseEnv,
     group: i32,
     parse_depth: u32,
+    start: usize,
+    last_start: usize,
 ) -> Result<(Box<Node>, i32), i32> {
     let r = tok.token_type as i32;
 
     if tok.token_type == TokenType::Repeat || tok.token_type == TokenType::Interval {
         if is_invalid_quantifier_target(&node) {
-            if is_syntax_bv(env.syntax, ONIG_SYN_CONTEXT_INDEP_REPEAT_OPS) {
-                if is_syntax_bv(env.syntax, ONIG_SYN_CONTEXT_INVALID_REPEAT_OPS) {
+            if is_syntax_bv(&env.syntax, ONIG_SYN_CONTEXT_INDEP_REPEAT_OPS) {
+                if is_syntax_bv(&env.syntax, ONIG_SYN_CONTEXT_INVALID_REPEAT_OPS) {
                     return Err(ONIGERR_TARGET_OF_REPEAT_OPERATOR_INVALID);
                 }
             }
+            if group == 2 {
+                node = node_new_group(node);
+            }
             return Ok((node, r));
         }
 
@@ -6563,17 +7198,21 @@ fn check_quantifier(
                 unreachable!()
             };
 
+            let atom_end = node.span.map_or(last_start, |(_, e)| e);
+
             let mut prefix = node_new_str(&bytes[..split_pos]);
             if let NodeInner::String(ref mut psn) = prefix.inner {
                 psn.flag = flag;
             }
             prefix.status = status;
+            prefix.span = Some((start, last_start));
 
             let mut last_char = node_new_str(&bytes[split_pos..]);
             if let NodeInner::String(ref mut lsn) = last_char.inner {
                 lsn.flag = flag;
             }
             last_char.status = status;
+            last_char.span = Some((last_start, atom_end));
 
             (Some(prefix), last_char)
         } else {
@@ -6619,6 +7258,32 @@ fn check_quantifier(
                 }
                 qn.set_body(Some(target_node));
             } else {
+                if targetq_num >= 0
+                    && nestq_num >= 0
+                    && is_syntax_bv(&env.syntax, ONIG_SYN_WARN_REDUNDANT_NESTED_REPEAT)
+                {
+                    let reduce = REDUCE_TYPE_TABLE[targetq_num as usize][nestq_num as usize];
+                    let message = match reduce {
+                        RQ_ASIS => None,
+                        RQ_DEL => Some("redundant nested repeat operator".to_string()),
+                        _ => Some(format!(
+                            "nested repeat operator {} and {} was replaced with '{}' in regular expression",
+                            POPULAR_Q_STR[targetq_num as usize],
+                            POPULAR_Q_STR[nestq_num as usize],
+                            REDUCE_Q_STR[reduce as usize]
+                        )),
+                    };
+                    if let Some(message) = message {
+                        syntax_warn(
+                            env,
+                            pattern,
+                            Some(&VERB_WARN_FUNC),
+                            WarningKind::NestedRepeat,
+                            (start, *p),
+                            message,
+                        );
+                    }
+                }
                 // Both standard or both non-standard — try onig_reduce_nested_quantifier
                 qn.set_body(Some(target_node));
                 onig_reduce_nested_quantifier(&mut qn)?;
@@ -6627,8 +7292,16 @@ fn check_quantifier(
             qn.set_body(Some(target_node));
         }
 
+        let qstart = if prefix_node.is_some() {
+            last_start
+        } else {
+            start
+        };
+        qn.span = Some((qstart, *p));
+
         if tok.repeat_possessive {
             let mut en = node_new_bag(BagType::StopBacktrack);
+            en.span = qn.span;
             en.set_body(Some(qn));
             qn = en;
         }
@@ -6641,18 +7314,34 @@ fn check_quantifier(
 
         if let Some(prefix) = prefix_node {
             // Return List(prefix, quantified_last_char)
-            let (quant_node, r) = check_quantifier(qn, tok, p, end, pattern, env, 0, depth)?;
-            let result = node_new_list(prefix, Some(node_new_list(quant_node, None)));
+            let (quant_node, r) = check_quantifier(
+                qn, tok, p, end, pattern, env, 0, depth, last_start, last_start,
+            )?;
+            let span_end = quant_node.span.map_or(*p, |(_, e)| e);
+            let mut result = node_new_list(prefix, Some(node_new_list(quant_node, None)));
+            result.span = Some((start, span_end));
             return Ok((result, r));
         }
 
         // Recursively check for stacked quantifiers
-        return check_quantifier(qn, tok, p, end, pattern, env, 0, depth);
+        return check_quantifier(qn, tok, p, end, pattern, env, 0, depth, start, start);
     }
 
+    if group == 2 {
+        node = node_new_group(node);
+    }
     Ok((node, r))
 }
 
+/// Wrap a group body in a one-element list (C: node_new_group). Used for an
+/// unquantified group at a group head (`group == 2` in check_quantifier).
+fn node_new_group(target: Box<Node>) -> Box<Node> {
+    let span = target.span;
+    let mut node = node_new_list(target, None);
+    node.span = span;
+    node
+}
+
 /// Parse a branch (sequence of expressions)
 fn prs_branch(
     tok: &mut PToken,
@@ -6675,7 +7364,8 @@ fn prs_branch(
         return Ok((node, r));
     }
 
-    let top = node_new_list(node, None);
+    let span_start = node.span.map(|(s, _)| s);
+    let mut top = node_new_list(node, None);
     let mut headp: *mut Option<Box<Node>>;
     // We need to build a linked list. Use unsafe pointer to the cdr slot.
     unsafe {
@@ -6688,9 +7378,11 @@ fn prs_branch(
         }
     }
 
+    let mut span_end = None;
     while r != TokenType::Eot as i32 && r != term && r != TokenType::Alt as i32 {
         let (node2, r2) = prs_exp(tok, term, p, end, pattern, env, false)?;
         r = r2;
+        span_end = node2.span.map(|(_, e)| e);
 
         let new_cell = node_new_list(node2, None);
         unsafe {
@@ -6705,6 +7397,9 @@ fn prs_branch(
         }
     }
 
+    if let (Some(s), Some(e)) = (span_start, span_end) {
+        top.span = Some((s, e));
+    }
     env.parse_depth -= 1;
     Ok((top, r))
 }
@@ -6733,7 +7428,9 @@ fn prs_alts(
         env.parse_depth -= 1;
         return Ok((node, r));
     } else if r == TokenType::Alt as i32 {
-        let top = node_new_alt(node, None);
+        let span_start = node.span.map(|(s, _)| s);
+        let mut span_end = None;
+        let mut top = node_new_alt(node, None);
         let mut headp: *mut Option<Box<Node>>;
         unsafe {
             let top_ptr = &*top as *const Node as *mut Node;
@@ -6752,6 +7449,7 @@ fn prs_alts(
             }
             let (node2, r2) = prs_branch(tok, term, p, end, pattern, env, false)?;
             r = r2;
+            span_end = node2.span.map(|(_, e)| e);
 
             let new_cell = node_new_alt(node2, None);
             unsafe {
@@ -6773,6 +7471,9 @@ fn prs_alts(
             }
         }
 
+        if let (Some(s), Some(e)) = (span_start, span_end) {
+            top.span = Some((s, e));
+        }
         env.options = save_options;
         env.parse_depth -= 1;
         Ok((top, r))
@@ -6803,6 +7504,25 @@ fn prs_regexp(
     Ok(top)
 }
 
+/// Consume leading PCRE newline verbs such as `(*CRLF)` and return the
+/// offset where the pattern proper begins. Not in Oniguruma, where these
+/// would be undefined callout names; the last verb wins, as in PCRE.
+fn prs_newline_verbs(pattern: &[u8], reg: &mut RegexType) -> usize {
+    const VERBS: [(&[u8], OnigNewline); 5] = [
+        (b"(*CR)", OnigNewline::Cr),
+        (b"(*LF)", OnigNewline::Lf),
+        (b"(*CRLF)", OnigNewline::Crlf),
+        (b"(*ANYCRLF)", OnigNewline::AnyCrlf),
+        (b"(*ANY)", OnigNewline::Any),
+    ];
+    let mut p = 0;
+    while let Some((verb, newline)) = VERBS.iter().find(|(v, _)| pattern[p..].starts_with(v)) {
+        reg.newline = *newline;
+        p += verb.len();
+    }
+    p
+}
+
 // ============================================================================
 // Entry point: onig_parse_tree
 // ============================================================================
@@ -6820,16 +7540,26 @@ pub fn onig_parse_tree(
 
     // Clear name table
     reg.name_table = Some(NameTable::new());
+    let state = env.compile.get_mut();
+    state.error_span = None;
+    state.error_name = None;
+    state.warnings.clear();
+    #[cfg(feature = "unstable-opcodes")]
+    reg.opcodes.clear_uses();
 
     // Initialize parse environment
     env.clear();
     env.options = reg.options;
     env.case_fold_flag = reg.case_fold_flag;
     env.enc = reg.enc;
-    env.syntax = unsafe { &*reg.syntax };
+    env.syntax = reg.syntax.clone();
     env.pattern = pattern.as_ptr();
     env.pattern_end = unsafe { pattern.as_ptr().add(pattern.len()) };
     env.reg = reg as *mut RegexType;
+    env.cc_buf_hint = cc_buf_hint(pattern.len());
+    let mut scratch = env.cc_scratch.take();
+    scratch.reserve(env.cc_buf_hint / (SIZE_CODE_POINT * 2));
+    env.cc_scratch.set(scratch);
 
     // Validate pattern encoding
     if !env.enc.is_valid_mbc_string(pattern) {
@@ -6837,8 +7567,16 @@ pub fn onig_parse_tree(
     }
 
     let mut p: usize = 0;
+    if is_syntax_op2(&env.syntax, ONIG_SYN_OP2_ASTERISK_CALLOUT_NAME) {
+        p = prs_newline_verbs(pattern, reg);
+    }
     let end = pattern.len();
-    let mut root = prs_regexp(&mut p, end, pattern, env)?;
+    let mut root = prs_regexp(&mut p, end, pattern, env).inspect_err(|_| {
+        // The token that failed, up to where the parser gave up.
+        let mut state = env.compile.borrow_mut();
+        state.error_span = Some((env.token_start.get().min(p), p));
+        state.error_name = env.error_name.get();
+    })?;
 
     // Wrap entire pattern in memory group 0 for \g<0> self-calls
     if (env.flags & PE_FLAG_HAS_CALL_ZERO) != 0 {
@@ -6880,9 +7618,10 @@ mod tests {
             repeat_range: Vec::new(),
             enc: &crate::encodings::utf8::ONIG_ENCODING_UTF8,
             options: ONIG_OPTION_NONE,
-            syntax: &OnigSyntaxOniguruma as *const OnigSyntaxType,
+            syntax: OnigSyntaxOniguruma.clone(),
             case_fold_flag: ONIGENC_CASE_FOLD_MIN,
             name_table: None,
+            newline: OnigNewline::Lf,
             optimize: OptimizeType::None,
             threshold_len: 0,
             anchor: 0,
@@ -6890,12 +7629,25 @@ mod tests {
             anc_dist_max: 0,
             sub_anchor: 0,
             exact: Vec::new(),
+            exact_finder: None,
             map: [0u8; CHAR_MAP_SIZE],
             map_offset: 0,
             map_bytes: [0u8; 3],
             map_byte_count: 0,
             dist_min: 0,
             dist_max: 0,
+            req_exact: Vec::new(),
+            req_dist_min: 0,
+            req_dist_max: 0,
+            match_len_min: 0,
+            match_len_max: INFINITE_LEN,
+            group_parents: Vec::new(),
+            shift_or: None,
+            small_match: false,
+            #[cfg(feature = "unstable-opcodes")]
+            opcodes: Default::default(),
+            #[cfg(feature = "exec-stats")]
+            exec_stats: Default::default(),
             called_addrs: vec![],
             unset_call_addrs: vec![],
             extp: None,
@@ -6904,15 +7656,15 @@ mod tests {
             options: OnigOptionType::empty(),
             case_fold_flag: 0,
             enc: &crate::encodings::utf8::ONIG_ENCODING_UTF8,
-            syntax: &OnigSyntaxOniguruma,
+            syntax: OnigSyntaxOniguruma.clone(),
             cap_history: 0,
             backtrack_mem: 0,
             backrefed_mem: 0,
-            pattern: std::ptr::null(),
-            pattern_end: std::ptr::null(),
-            error: std::ptr::null(),
-            error_end: std::ptr::null(),
-            reg: std::ptr::null_mut(),
+            pattern: core::ptr::null(),
+            pattern_end: core::ptr::null(),
+            error: core::ptr::null(),
+            error_end: core::ptr::null(),
+            reg: core::ptr::null_mut(),
             num_call: 0,
             num_mem: 0,
             num_named: 0,
@@ -6927,6 +7679,12 @@ mod tests {
             unset_addr_list: None,
             parse_depth: 0,
             flags: 0,
+            token_start: Default::default(),
+            error_name: Default::default(),
+            cc_scratch: Default::default(),
+            cc_buf_hint: 0,
+            cc_redundant: None,
+            compile: Default::default(),
         };
         (reg, env)
     }
@@ -7304,4 +8062,54 @@ mod tests {
         let result = parse(b"a{5,2}");
         assert!(result.is_ok());
     }
+
+    // --- Case fold closure ---
+
+    #[test]
+    fn case_fold_table_is_cached() {
+        let enc: OnigEncoding = &crate::encodings::utf8::ONIG_ENCODING_UTF8;
+        let a = case_fold_table(enc, ONIGENC_CASE_FOLD_MIN);
+        let b = case_fold_table(enc, ONIGENC_CASE_FOLD_MIN);
+        assert!(core::ptr::eq(a, b));
+        assert!(a.singles.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn add_codes_into_cc_merges_ranges() {
+        let mut cc = CClassNode {
+            flags: 0,
+            bs: [0; BITSET_REAL_SIZE],
+            mbuf: None,
+        };
+        add_code_range_to_buf(&mut cc.mbuf, 0x100, 0x102);
+        add_codes_into_cc(&mut cc, &[0x41, 0x103, 0x105, 0x106], &mut Vec::new());
+        assert!(bitset_at(&cc.bs, 0x41));
+        let mbuf = cc.mbuf.as_ref().unwrap();
+        assert_eq!(bbuf_read_code_point(mbuf, 0), 2);
+        let ranges: Vec<_> = (1..5)
+            .map(|i| bbuf_read_code_point(mbuf, SIZE_CODE_POINT * i))
+            .collect();
+        assert_eq!(ranges, [0x100, 0x103, 0x105, 0x106]);
+    }
+
+    #[test]
+    fn add_code_range_to_buf_merges_in_place() {
+        let mut buf = Some(new_code_range_with_capacity(cc_buf_hint(64)));
+        for (from, to) in [
+            (0x300, 0x310),
+            (0x100, 0x110),
+            (0x200, 0x210),
+            (0x111, 0x1ff),
+        ] {
+            assert_eq!(add_code_range_to_buf(&mut buf, from, to), 0);
+        }
+        let bbuf = buf.as_ref().unwrap();
+        let n = bbuf_read_code_point(bbuf, 0) as usize;
+        let ranges: Vec<_> = (1..=n * 2)
+            .map(|i| bbuf_read_code_point(bbuf, SIZE_CODE_POINT * i))
+            .collect();
+        // As in C, a range absorbs the one it touches on the right only.
+        assert_eq!(ranges, [0x100, 0x110, 0x111, 0x210, 0x300, 0x310]);
+        assert_eq!(bbuf.data.len(), SIZE_CODE_POINT * 7);
+    }
 }
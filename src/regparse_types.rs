@@ -3,11 +3,15 @@
 
 #![allow(non_upper_case_globals)]
 
-use std::collections::HashMap;
+use alloc::collections::BTreeMap;
+use core::cell::{Cell, RefCell};
 
+use crate::memhook::{OnigBufferKind, Tracked};
 use crate::oniguruma::*;
 use crate::regenc::OnigEncoding;
 use crate::regint::*;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
 
 // === Node Type Enum ===
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -61,6 +65,8 @@ pub enum GimmickType {
     Save = 1,
     UpdateVar = 2,
     Callout = 3,
+    // Custom opcode (unstable-opcodes feature).
+    Extension = 4,
 }
 
 // === Body Empty Type ===
@@ -132,17 +138,23 @@ pub const PE_FLAG_HAS_ABSENT_STOPPER: u32 = 1 << 2;
 #[derive(Clone, Debug)]
 pub struct BBuf {
     pub data: Vec<u8>,
+    /// Bytes granted by the memory hook (Rust-only, see memhook.rs).
+    pub(crate) mem: Tracked,
 }
 
 impl BBuf {
     #[cfg_attr(coverage_nightly, coverage(off))]
     pub fn new() -> Self {
-        BBuf { data: Vec::new() }
+        BBuf {
+            data: Vec::new(),
+            mem: Tracked::new(OnigBufferKind::CodeRangeBuffer),
+        }
     }
 
     pub fn with_capacity(cap: usize) -> Self {
         BBuf {
             data: Vec::with_capacity(cap),
+            mem: Tracked::new(OnigBufferKind::CodeRangeBuffer),
         }
     }
 
@@ -163,9 +175,7 @@ impl BBuf {
 
     #[cfg_attr(coverage_nightly, coverage(off))]
     pub fn clone_from(other: &BBuf) -> Self {
-        BBuf {
-            data: other.data.clone(),
-        }
+        other.clone()
     }
 }
 
@@ -184,6 +194,9 @@ pub struct Node {
     pub status: u32,
     pub parent: *mut Node,
     pub inner: NodeInner,
+    /// Byte range of the pattern this node was parsed from (Rust-only, for
+    /// the `ast` module). `None` for nodes synthesized by the parser or compiler.
+    pub span: Option<(usize, usize)>,
 }
 
 pub enum NodeInner {
@@ -538,6 +551,10 @@ pub struct QuantNode {
     pub next_head_exact: Option<u8>,
     pub include_referred: i32,
     pub empty_status_mem: MemStatusType,
+    // The body may be empty, has no captures and leaves no
+    // backtrack entry behind, so its empty check keeps the iteration start
+    // in a per-match register instead of on the stack.
+    pub simple_empty_check: bool,
 }
 
 pub struct BagNode {
@@ -609,6 +626,8 @@ pub struct CallNode {
     pub by_number: bool,
     pub called_gnum: i32,
     pub name: Vec<u8>,
+    /// Byte range of `name` in the pattern, for error reporting.
+    pub name_span: (usize, usize),
     pub entry_count: i32,
     /// Raw pointer to the target BAG_MEMORY node (non-owning, for recursion detection)
     pub target_node: *mut Node,
@@ -669,6 +688,9 @@ pub struct PToken {
     pub code_point_continue: bool,
     pub base_num: i32,
     pub backp: usize,
+    // Rust-only: pattern offset where the token begins (after any skipped
+    // whitespace/comments), used for node spans.
+    pub start: usize,
     // Union field: code / byte (valid for TK_CHAR, TK_CODE_POINT, TK_CRUDE_BYTE)
     pub code: OnigCodePoint,
     // Union field: anchor / subtype (valid for TK_ANCHOR)
@@ -704,6 +726,7 @@ impl PToken {
             code_point_continue: false,
             base_num: 0,
             backp: 0,
+            start: 0,
             code: 0,
             anchor: 0,
             repeat_lower: 0,
@@ -754,8 +777,8 @@ pub struct MemEnv {
 impl Default for MemEnv {
     fn default() -> Self {
         MemEnv {
-            mem_node: std::ptr::null_mut(),
-            empty_repeat_node: std::ptr::null_mut(),
+            mem_node: core::ptr::null_mut(),
+            empty_repeat_node: core::ptr::null_mut(),
         }
     }
 }
@@ -787,7 +810,7 @@ pub struct ParseEnv {
     pub options: OnigOptionType,
     pub case_fold_flag: OnigCaseFoldType,
     pub enc: OnigEncoding,
-    pub syntax: &'static OnigSyntaxType,
+    pub syntax: OnigSyntaxType,
     pub cap_history: MemStatusType,
     pub backtrack_mem: MemStatusType,
     pub backrefed_mem: MemStatusType,
@@ -810,6 +833,22 @@ pub struct ParseEnv {
     pub unset_addr_list: Option<Vec<UnsetAddr>>,
     pub parse_depth: u32,
     pub flags: u32,
+    // Pattern offset of the token being parsed, and the name a
+    // name error refers to (C keeps the latter in error/error_end), for the
+    // location of syntax errors.
+    pub token_start: Cell<usize>,
+    pub error_name: Cell<Option<(usize, usize)>>,
+    // Range scratch shared by every class of the pattern (emptied
+    // before each use, capacity kept), and the initial byte capacity for a
+    // class's code range buffer, sized from the pattern length.
+    pub cc_scratch: Cell<Vec<(OnigCodePoint, OnigCodePoint)>>,
+    pub cc_buf_hint: usize,
+    // When set, the pattern ranges of character class items that
+    // add nothing to their class are collected here, for the linter.
+    pub cc_redundant: Option<Vec<(usize, usize)>>,
+    // Error location, warnings and limits of the compile, handed in and
+    // back by the onig_compile() family instead of living on the regex.
+    pub(crate) compile: RefCell<CompileState>,
 }
 
 // Safety: ParseEnv contains raw pointers used within the parser scope
@@ -821,8 +860,9 @@ unsafe impl Sync for ParseEnv {}
 pub fn node_new(inner: NodeInner) -> Box<Node> {
     Box::new(Node {
         status: 0,
-        parent: std::ptr::null_mut(),
+        parent: core::ptr::null_mut(),
         inner,
+        span: None,
     })
 }
 
@@ -872,6 +912,7 @@ pub fn node_new_backref(
     backrefs: &[i32],
     by_name: bool,
     nest_level: i32,
+    env: &ParseEnv,
 ) -> Box<Node> {
     let mut back_static = [0i32; ND_BACKREFS_SIZE];
     let back_dynamic = if backrefs.len() <= ND_BACKREFS_SIZE {
@@ -895,6 +936,12 @@ pub fn node_new_backref(
     if nest_level != 0 {
         node.status_add(ND_ST_NEST_LEVEL);
     }
+    for &r in backrefs {
+        if r <= env.num_mem && env.mem_env(r as usize).mem_node.is_null() {
+            node.status_add(ND_ST_RECURSION); // /...(\1).../
+            break;
+        }
+    }
     node
 }
 
@@ -909,6 +956,7 @@ pub fn node_new_quantifier(lower: i32, upper: i32, greedy: bool) -> Box<Node> {
         next_head_exact: None,
         include_referred: 0,
         empty_status_mem: 0,
+        simple_empty_check: false,
     }))
 }
 
@@ -1018,14 +1066,20 @@ pub fn node_new_alt(car: Box<Node>, cdr: Option<Box<Node>>) -> Box<Node> {
     node_new(NodeInner::Alt(ConsAltNode { car, cdr }))
 }
 
-pub fn node_new_call(name: &[u8], gnum: i32, by_number: bool) -> Box<Node> {
+pub fn node_new_call(
+    pattern: &[u8],
+    name_span: (usize, usize),
+    gnum: i32,
+    by_number: bool,
+) -> Box<Node> {
     let mut node = node_new(NodeInner::Call(CallNode {
         body: None,
         by_number,
         called_gnum: gnum,
-        name: name.to_vec(),
+        name: pattern[name_span.0..name_span.1].to_vec(),
+        name_span,
         entry_count: 0,
-        target_node: std::ptr::null_mut(),
+        target_node: core::ptr::null_mut(),
     }));
     if by_number {
         node.status_add(ND_ST_BY_NUMBER);
@@ -1042,6 +1096,17 @@ pub fn node_new_fail() -> Box<Node> {
     }))
 }
 
+/// A use of a custom opcode (see the `extension` module).
+#[cfg(feature = "unstable-opcodes")]
+pub fn node_new_extension(num: i32) -> Box<Node> {
+    node_new(NodeInner::Gimmick(GimmickNode {
+        gimmick_type: GimmickType::Extension,
+        detail_type: 0,
+        num, // use index in reg.opcodes
+        id: 0,
+    }))
+}
+
 pub fn node_new_callout(of: i32, num: i32, id: i32) -> Box<Node> {
     node_new(NodeInner::Gimmick(GimmickNode {
         gimmick_type: GimmickType::Callout,
@@ -1206,13 +1271,13 @@ pub struct NameEntry {
 }
 
 pub struct NameTable {
-    pub entries: HashMap<Vec<u8>, NameEntry>,
+    pub entries: BTreeMap<Vec<u8>, NameEntry>,
 }
 
 impl NameTable {
     pub fn new() -> Self {
         NameTable {
-            entries: HashMap::new(),
+            entries: BTreeMap::new(),
         }
     }
 
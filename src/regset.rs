@@ -1,10 +1,18 @@
 // regset.rs - Port of USE_REGSET section from regexec.c
 // Multi-regex search for syntax highlighters and text editors.
 
+use crate::api::MemoryBreakdown;
 use crate::oniguruma::*;
+use crate::regcomp::regex_memory_usage;
 use crate::regenc::OnigEncoding;
-use crate::regexec::{onig_match, onig_search, onig_search_with_param, OnigMatchParam};
+use crate::regexec::{
+    follows_newline, newline_before, onig_match, onig_match_inner, onig_match_with_state,
+    onig_search_with_param, onig_search_with_state, ExecState, OnigMatchParam,
+};
 use crate::regint::*;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+use alloc::sync::Arc;
 
 /// Search lead mode for regset search.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -21,7 +29,9 @@ pub enum OnigRegSetLead {
 }
 
 struct RegSetEntry {
-    reg: Box<RegexType>,
+    // Shared, so one compiled regex can serve several sets (see
+    // onig_regset_new_shared). The region is the entry's own search state.
+    reg: Arc<RegexType>,
     region: Option<OnigRegion>,
 }
 
@@ -47,6 +57,15 @@ fn enclen(enc: OnigEncoding, str_data: &[u8], s: usize) -> usize {
 /// Create a new regex set from an array of compiled regexes.
 /// Returns (Some(set), ONIG_NORMAL) on success, (None, error_code) on failure.
 pub fn onig_regset_new(regs: Vec<Box<RegexType>>) -> (Option<Box<OnigRegSet>>, i32) {
+    onig_regset_new_shared(regs.into_iter().map(Arc::from).collect())
+}
+
+/// Create a regex set over regexes that may also be used elsewhere, e.g.
+/// by the sets of other threads or by direct searches.
+///
+/// The set only reads its regexes; each set keeps its own
+/// regions, so sets sharing regexes can be searched concurrently.
+pub fn onig_regset_new_shared(regs: Vec<Arc<RegexType>>) -> (Option<Box<OnigRegSet>>, i32) {
     let mut set = Box::new(OnigRegSet {
         entries: Vec::new(),
         enc: &crate::encodings::utf8::ONIG_ENCODING_UTF8,
@@ -58,7 +77,7 @@ pub fn onig_regset_new(regs: Vec<Box<RegexType>>) -> (Option<Box<OnigRegSet>>, i
     });
 
     for reg in regs {
-        let r = onig_regset_add(&mut set, reg);
+        let r = regset_add(&mut set, reg);
         if r != ONIG_NORMAL {
             return (None, r);
         }
@@ -67,60 +86,82 @@ pub fn onig_regset_new(regs: Vec<Box<RegexType>>) -> (Option<Box<OnigRegSet>>, i
     (Some(set), ONIG_NORMAL)
 }
 
+/// Fold the optimization info of entry `at` into the set's lead-position
+/// data. The first entry resets it; later ones narrow it.
+fn update_regset_by_reg(set: &mut OnigRegSet, at: usize) {
+    let reg = &*set.entries[at].reg;
+    if at == 0 {
+        set.enc = reg.enc;
+        set.anchor = reg.anchor;
+        set.anc_dmin = reg.anc_dist_min;
+        set.anc_dmax = reg.anc_dist_max;
+        set.all_low_high = reg.optimize != OptimizeType::None && reg.dist_max != INFINITE_LEN;
+        set.anychar_inf = (reg.anchor & ANCR_ANYCHAR_INF) != 0;
+    } else {
+        let anchor = set.anchor & reg.anchor;
+        if anchor != 0 {
+            if reg.anc_dist_min < set.anc_dmin {
+                set.anc_dmin = reg.anc_dist_min;
+            }
+            if reg.anc_dist_max > set.anc_dmax {
+                set.anc_dmax = reg.anc_dist_max;
+            }
+        }
+        set.anchor = anchor;
+        if reg.optimize == OptimizeType::None || reg.dist_max == INFINITE_LEN {
+            set.all_low_high = false;
+        }
+        if (reg.anchor & ANCR_ANYCHAR_INF) != 0 {
+            set.anychar_inf = true;
+        }
+    }
+}
+
+/// Rebuild the lead-position data from the per-regex optimization info
+/// already computed at compile time. Nothing is recompiled.
+fn rebuild_regset_lead(set: &mut OnigRegSet) {
+    if set.entries.is_empty() {
+        set.anchor = 0;
+        set.anc_dmin = 0;
+        set.anc_dmax = 0;
+        set.all_low_high = false;
+        set.anychar_inf = false;
+        return;
+    }
+    for i in 0..set.entries.len() {
+        update_regset_by_reg(set, i);
+    }
+}
+
 /// Add a compiled regex to the set. Returns ONIG_NORMAL on success.
+///
+/// The lead-position data is narrowed in place; the other entries and
+/// their regions are left untouched.
 pub fn onig_regset_add(set: &mut OnigRegSet, reg: Box<RegexType>) -> i32 {
+    regset_add(set, Arc::from(reg))
+}
+
+fn regset_add(set: &mut OnigRegSet, reg: Arc<RegexType>) -> i32 {
     if opton_find_longest(reg.options) {
         return ONIGERR_INVALID_ARGUMENT;
     }
 
-    if !set.entries.is_empty() && !std::ptr::eq(reg.enc, set.enc) {
+    if !set.entries.is_empty() && !core::ptr::eq(reg.enc, set.enc) {
         return ONIGERR_INVALID_ARGUMENT;
     }
 
     let region = Some(OnigRegion::new());
     set.entries.push(RegSetEntry { reg, region });
-
-    // Recompute: pass field values to avoid borrow conflict
-    let n = set.entries.len();
-    let reg_ref = &*set.entries[n - 1].reg;
-    let anchor = reg_ref.anchor;
-    let anc_dist_min = reg_ref.anc_dist_min;
-    let anc_dist_max = reg_ref.anc_dist_max;
-    let optimize = reg_ref.optimize;
-    let dist_max = reg_ref.dist_max;
-
-    if n == 1 {
-        set.enc = reg_ref.enc;
-        set.anchor = anchor;
-        set.anc_dmin = anc_dist_min;
-        set.anc_dmax = anc_dist_max;
-        set.all_low_high = optimize != OptimizeType::None && dist_max != INFINITE_LEN;
-        set.anychar_inf = (anchor & ANCR_ANYCHAR_INF) != 0;
-    } else {
-        let new_anchor = set.anchor & anchor;
-        if new_anchor != 0 {
-            if anc_dist_min < set.anc_dmin {
-                set.anc_dmin = anc_dist_min;
-            }
-            if anc_dist_max > set.anc_dmax {
-                set.anc_dmax = anc_dist_max;
-            }
-        }
-        set.anchor = new_anchor;
-        if optimize == OptimizeType::None || dist_max == INFINITE_LEN {
-            set.all_low_high = false;
-        }
-        if (anchor & ANCR_ANYCHAR_INF) != 0 {
-            set.anychar_inf = true;
-        }
-    }
+    update_regset_by_reg(set, set.entries.len() - 1);
 
     ONIG_NORMAL
 }
 
 /// Replace a regex at index `at`, or remove it if `reg` is None.
 /// Returns ONIG_NORMAL on success.
-#[cfg_attr(coverage_nightly, coverage(off))]
+///
+/// The replaced entry keeps its region allocation, so a scanner that swaps
+/// patterns per line does not reallocate capture storage.
 pub fn onig_regset_replace(set: &mut OnigRegSet, at: usize, reg: Option<Box<RegexType>>) -> i32 {
     if at >= set.entries.len() {
         return ONIGERR_INVALID_ARGUMENT;
@@ -128,78 +169,48 @@ pub fn onig_regset_replace(set: &mut OnigRegSet, at: usize, reg: Option<Box<Rege
 
     match reg {
         None => {
-            // Remove entry at `at`
             set.entries.remove(at);
         }
         Some(reg) => {
             if opton_find_longest(reg.options) {
                 return ONIGERR_INVALID_ARGUMENT;
             }
-            if set.entries.len() > 1 && !std::ptr::eq(reg.enc, set.enc) {
+            if set.entries.len() > 1 && !core::ptr::eq(reg.enc, set.enc) {
                 return ONIGERR_INVALID_ARGUMENT;
             }
-            set.entries[at].reg = reg;
+            set.entries[at].reg = Arc::from(reg);
         }
     }
 
-    // Recompute aggregate fields from all entries
-    if !set.entries.is_empty() {
-        // Reset and recompute by replaying updates
-        let first_enc = set.entries[0].reg.enc;
-        set.enc = first_enc;
-        set.anchor = 0;
-        set.anc_dmin = 0;
-        set.anc_dmax = 0;
-        set.all_low_high = false;
-        set.anychar_inf = false;
+    rebuild_regset_lead(set);
+    ONIG_NORMAL
+}
 
-        // Temporarily collect reg references to avoid borrow issues
-        let reg_data: Vec<(i32, OnigLen, OnigLen, OptimizeType, OnigLen, i32)> = set
-            .entries
-            .iter()
-            .map(|e| {
-                (
-                    e.reg.anchor,
-                    e.reg.anc_dist_min,
-                    e.reg.anc_dist_max,
-                    e.reg.optimize,
-                    e.reg.dist_max,
-                    0, // placeholder
-                )
-            })
-            .collect();
-
-        for (i, (anchor, anc_dist_min, anc_dist_max, optimize, dist_max, _)) in
-            reg_data.iter().enumerate()
-        {
-            if i == 0 {
-                set.anchor = *anchor;
-                set.anc_dmin = *anc_dist_min;
-                set.anc_dmax = *anc_dist_max;
-                set.all_low_high = *optimize != OptimizeType::None && *dist_max != INFINITE_LEN;
-                set.anychar_inf = (*anchor & ANCR_ANYCHAR_INF) != 0;
-            } else {
-                let new_anchor = set.anchor & anchor;
-                if new_anchor != 0 {
-                    if *anc_dist_min < set.anc_dmin {
-                        set.anc_dmin = *anc_dist_min;
-                    }
-                    if *anc_dist_max > set.anc_dmax {
-                        set.anc_dmax = *anc_dist_max;
-                    }
-                }
-                set.anchor = new_anchor;
-                if *optimize == OptimizeType::None || *dist_max == INFINITE_LEN {
-                    set.all_low_high = false;
-                }
-                if (*anchor & ANCR_ANYCHAR_INF) != 0 {
-                    set.anychar_inf = true;
-                }
-            }
-        }
-    }
+/// Remove the regex at index `at` and hand it back to the caller.
+/// Later entries shift down by one. Returns None if `at` is out of range.
+///
+/// A regex the set shares with other holders (see
+/// [`onig_regset_new_shared`]) cannot be handed back as a `Box`: it stays
+/// in the set and None is returned. Use [`onig_regset_remove_shared`] for
+/// those.
+///
+/// Not in C: `onig_regset_replace(set, at, NULL)` frees the regex instead.
+pub fn onig_regset_remove(set: &mut OnigRegSet, at: usize) -> Option<Box<RegexType>> {
+    Arc::get_mut(&mut set.entries.get_mut(at)?.reg)?;
+    let reg = onig_regset_remove_shared(set, at)?;
+    Arc::try_unwrap(reg).ok().map(Box::new)
+}
 
-    ONIG_NORMAL
+/// Remove the regex at index `at`, shared or not, and hand back the
+/// set's reference to it. Later entries shift down by one. Returns None if
+/// `at` is out of range.
+pub fn onig_regset_remove_shared(set: &mut OnigRegSet, at: usize) -> Option<Arc<RegexType>> {
+    if at >= set.entries.len() {
+        return None;
+    }
+    let entry = set.entries.remove(at);
+    rebuild_regset_lead(set);
+    Some(entry.reg)
 }
 
 /// Return the number of regexes in the set.
@@ -214,6 +225,31 @@ pub fn onig_regset_get_regex(set: &OnigRegSet, at: usize) -> Option<&RegexType>
     set.entries.get(at).map(|e| e.reg.as_ref())
 }
 
+/// Estimate the bytes the set holds: its regexes, counted in full even when
+/// shared, and its regions under `search_state`.
+pub fn onig_regset_memory_usage(set: &OnigRegSet) -> MemoryBreakdown {
+    set.entries
+        .iter()
+        .map(|e| regex_memory_usage(&e.reg))
+        .sum::<MemoryBreakdown>()
+        + regset_state_memory_usage(set)
+}
+
+/// The set without its regexes: entries and regions.
+pub(crate) fn regset_state_memory_usage(set: &OnigRegSet) -> MemoryBreakdown {
+    MemoryBreakdown {
+        search_state: set
+            .entries
+            .iter()
+            .filter_map(|e| e.region.as_ref())
+            .map(|r| core::mem::size_of::<OnigRegion>() + r.spans_size())
+            .sum(),
+        other: core::mem::size_of::<OnigRegSet>()
+            + set.entries.capacity() * core::mem::size_of::<RegSetEntry>(),
+        ..Default::default()
+    }
+}
+
 /// Get a reference to the region at index `at`.
 #[cfg_attr(coverage_nightly, coverage(off))]
 pub fn onig_regset_get_region(set: &OnigRegSet, at: usize) -> Option<&OnigRegion> {
@@ -221,10 +257,14 @@ pub fn onig_regset_get_region(set: &OnigRegSet, at: usize) -> Option<&OnigRegion
 }
 
 /// Position-lead search: iterate positions, try each regex at each position.
+/// `\G` matches only at `begin_position`, the start the caller asked for.
+#[allow(clippy::too_many_arguments)]
 fn regset_search_body_position_lead(
     set: &mut OnigRegSet,
+    state: &mut ExecState,
     str_data: &[u8],
     end: usize,
+    begin_position: usize,
     start: usize,
     range: usize,
     option: OnigOptionType,
@@ -237,26 +277,29 @@ fn regset_search_body_position_lead(
     let prev_is_newline_check = set.anychar_inf;
 
     loop {
-        if s >= range {
+        // Like onig_search, also try `range` itself when it is the end of
+        // the string: an empty match such as /$/ can start there.
+        if s > range || (s == range && range != end) {
             break;
         }
 
-        let prev_is_newline = if prev_is_newline_check && s > 0 {
-            // Check if previous character is newline
-            s > 0 && str_data[s - 1] == b'\n'
-        } else {
-            true // default: allow matching
-        };
-
         for i in 0..n {
             // ANCR_ANYCHAR_INF optimization: skip if previous char is not newline
-            if (set.entries[i].reg.anchor & ANCR_ANYCHAR_INF) != 0 && !prev_is_newline {
+            // (per regex: each may select its own newline convention). Only
+            // a failed attempt at an earlier position makes this safe, so
+            // the search start is always tried.
+            if prev_is_newline_check
+                && s > start
+                && (set.entries[i].reg.anchor & ANCR_ANYCHAR_INF) != 0
+                && !follows_newline(&set.entries[i].reg, str_data, s, end)
+            {
                 continue;
             }
 
             let region = set.entries[i].region.take();
             let entry = &set.entries[i];
-            let (r, returned_region) = onig_match(&entry.reg, str_data, end, s, region, option);
+            let msa = state.prepare(&entry.reg, option, region, begin_position);
+            let (r, returned_region) = onig_match_inner(&entry.reg, str_data, end, s, msa);
             set.entries[i].region = returned_region;
 
             if r >= 0 {
@@ -268,6 +311,9 @@ fn regset_search_body_position_lead(
             }
         }
 
+        if s >= end {
+            break;
+        }
         s += enclen(enc, str_data, s);
     }
 
@@ -275,8 +321,10 @@ fn regset_search_body_position_lead(
 }
 
 /// Regex-lead search: iterate regexes, find earliest match.
+#[allow(clippy::too_many_arguments)]
 fn regset_search_body_regex_lead(
     set: &mut OnigRegSet,
+    state: &mut ExecState,
     str_data: &[u8],
     end: usize,
     start: usize,
@@ -291,8 +339,9 @@ fn regset_search_body_regex_lead(
 
     for i in 0..n {
         let region = set.entries[i].region.take();
-        let (r, returned_region) = onig_search(
+        let (r, returned_region) = onig_search_with_state(
             &set.entries[i].reg,
+            state,
             str_data,
             end,
             start,
@@ -335,12 +384,38 @@ pub fn onig_regset_search(
     range: usize,
     lead: OnigRegSetLead,
     option: OnigOptionType,
+) -> (i32, i32) {
+    let state = &mut ExecState::new();
+    onig_regset_search_with_state(set, state, str_data, end, start, range, lead, option)
+}
+
+/// onig_regset_search() with the scratch memory of `state`, which every
+/// regex of the set shares and which keeps its buffers for the next search
+/// (see [`ExecState`]).
+///
+/// The set holds the regions; its regexes are only read, so sets built
+/// with [`onig_regset_new_shared`] over the same regexes can be searched
+/// from several threads, each with its own set and state.
+#[allow(clippy::too_many_arguments)]
+pub fn onig_regset_search_with_state(
+    set: &mut OnigRegSet,
+    state: &mut ExecState,
+    str_data: &[u8],
+    end: usize,
+    start: usize,
+    range: usize,
+    lead: OnigRegSetLead,
+    option: OnigOptionType,
 ) -> (i32, i32) {
     let n = set.entries.len();
     if n == 0 {
         return (ONIG_MISMATCH, 0);
     }
 
+    if end > ONIG_MAX_INPUT_LEN {
+        return (ONIGERR_TOO_LONG_INPUT, 0);
+    }
+
     if start > end || start > str_data.len() {
         return (ONIG_MISMATCH, 0);
     }
@@ -363,8 +438,15 @@ pub fn onig_regset_search(
         for i in 0..n {
             if set.entries[i].reg.threshold_len == 0 {
                 let region = set.entries[i].region.take();
-                let (r, returned_region) =
-                    onig_match(&set.entries[i].reg, str_data, end, start, region, option);
+                let (r, returned_region) = onig_match_with_state(
+                    &set.entries[i].reg,
+                    state,
+                    str_data,
+                    end,
+                    start,
+                    region,
+                    option,
+                );
                 set.entries[i].region = returned_region;
                 if r >= 0 {
                     return (i as i32, start as i32);
@@ -411,9 +493,12 @@ pub fn onig_regset_search(
         } else if (set.anchor & ANCR_SEMI_END_BUF) != 0 {
             let max_semi_end = end;
             let mut min_semi_end = end;
-            if end > 0 && str_data[end - 1] == b'\n' {
-                min_semi_end = end - 1;
-            }
+            min_semi_end -= set
+                .entries
+                .iter()
+                .map(|e| newline_before(&e.reg, str_data, end))
+                .max()
+                .unwrap_or(0);
 
             if (max_semi_end as OnigLen) < set.anc_dmin {
                 return (ONIG_MISMATCH, 0);
@@ -435,9 +520,13 @@ pub fn onig_regset_search(
     }
 
     let (result, match_pos) = if lead == OnigRegSetLead::PositionLead {
-        regset_search_body_position_lead(set, str_data, end, cur_start, cur_range, option)
+        regset_search_body_position_lead(
+            set, state, str_data, end, start, cur_start, cur_range, option,
+        )
     } else {
-        regset_search_body_regex_lead(set, str_data, end, cur_start, orig_range, lead, option)
+        regset_search_body_regex_lead(
+            set, state, str_data, end, cur_start, orig_range, lead, option,
+        )
     };
 
     // Clear regions for non-matching regexes with FIND_NOT_EMPTY
@@ -456,6 +545,118 @@ pub fn onig_regset_search(
     (result, match_pos)
 }
 
+/// Search the set and report every regex that matches at the leftmost
+/// match position, not just the first one.
+///
+/// `matches` is cleared and filled with `(regex_index, match_length)` pairs
+/// in regex order; the region of each listed regex holds its captures.
+/// Returns the match position, ONIG_MISMATCH, or an error code.
+///
+/// Lets tokenizers apply longest-match or priority tie-breaking
+/// without running the set once per candidate.
+pub fn onig_regset_search_all(
+    set: &mut OnigRegSet,
+    str_data: &[u8],
+    end: usize,
+    start: usize,
+    range: usize,
+    option: OnigOptionType,
+    matches: &mut Vec<(usize, usize)>,
+) -> i32 {
+    matches.clear();
+
+    let state = &mut ExecState::new();
+    let (first, pos) = onig_regset_search_with_state(
+        set,
+        state,
+        str_data,
+        end,
+        start,
+        range,
+        OnigRegSetLead::PositionLead,
+        option,
+    );
+    if first < 0 {
+        return first;
+    }
+    let first = first as usize;
+    let at = pos as usize;
+
+    let len = set.entries[first]
+        .region
+        .as_ref()
+        .map_or(0, |region| region.end(0) as usize - at);
+    matches.push((first, len));
+
+    // Regexes before `first` were already tried at `at` and failed.
+    for i in first + 1..set.entries.len() {
+        let region = set.entries[i].region.take();
+        let (r, returned_region) = onig_match_with_state(
+            &set.entries[i].reg,
+            state,
+            str_data,
+            end,
+            at,
+            region,
+            option,
+        );
+        set.entries[i].region = returned_region;
+
+        if r >= 0 {
+            matches.push((i, r as usize));
+        } else if r != ONIG_MISMATCH {
+            matches.clear();
+            return r;
+        } else if let Some(ref mut region) = set.entries[i].region {
+            region.clear();
+        }
+    }
+
+    pos
+}
+
+/// Whether any regex in the set matches anywhere in `start..range`.
+///
+/// Returns the index of a matching regex, ONIG_MISMATCH, or an error
+/// code. Regexes are searched one after another, each with its own
+/// optimizer, and the first that matches ends the search: the index is
+/// not that of the leftmost match, and no region is filled (the regions
+/// of the set are left as they were).
+///
+/// For filters and routers that only need the boolean, where
+/// position-lead search tries every regex at every position.
+pub fn onig_regset_is_match_any(
+    set: &OnigRegSet,
+    str_data: &[u8],
+    end: usize,
+    start: usize,
+    range: usize,
+    option: OnigOptionType,
+) -> i32 {
+    if end > ONIG_MAX_INPUT_LEN {
+        return ONIGERR_TOO_LONG_INPUT;
+    }
+    if start > end || start > str_data.len() {
+        return ONIG_MISMATCH;
+    }
+    if !str_data.is_empty() && range < start {
+        return ONIGERR_INVALID_ARGUMENT;
+    }
+
+    let state = &mut ExecState::new();
+    for (i, entry) in set.entries.iter().enumerate() {
+        let (r, _) =
+            onig_search_with_state(&entry.reg, state, str_data, end, start, range, None, option);
+        if r >= 0 {
+            return i as i32;
+        }
+        if r != ONIG_MISMATCH {
+            return r;
+        }
+    }
+    ONIG_MISMATCH
+}
+
 /// Search the set with per-regex match parameters.
 #[cfg_attr(coverage_nightly, coverage(off))]
 pub fn onig_regset_search_with_param(
@@ -466,7 +667,7 @@ pub fn onig_regset_search_with_param(
     range: usize,
     lead: OnigRegSetLead,
     option: OnigOptionType,
-    mps: &[OnigMatchParam],
+    mps: &mut [OnigMatchParam],
 ) -> (i32, i32) {
     let n = set.entries.len();
     if n == 0 {
@@ -476,6 +677,10 @@ pub fn onig_regset_search_with_param(
         return (ONIGERR_INVALID_ARGUMENT, 0);
     }
 
+    if end > ONIG_MAX_INPUT_LEN {
+        return (ONIGERR_TOO_LONG_INPUT, 0);
+    }
+
     if start > end || start > str_data.len() {
         return (ONIG_MISMATCH, 0);
     }
@@ -529,7 +734,7 @@ pub fn onig_regset_search_with_param(
                 ep,
                 region,
                 option,
-                &mps[i],
+                &mut mps[i],
             );
             set.entries[i].region = returned_region;
 
@@ -554,7 +759,8 @@ pub fn onig_regset_search_with_param(
 
     // Position-lead with params: delegate to non-param position-lead
     // (params mainly affect limits which are checked within onig_match)
-    regset_search_body_position_lead(set, str_data, end, start, range, option)
+    let state = &mut ExecState::new();
+    regset_search_body_position_lead(set, state, str_data, end, start, start, range, option)
 }
 
 #[cfg(test)]
@@ -575,7 +781,7 @@ mod tests {
             Ok(r) => Box::new(r),
             Err(e) => panic!(
                 "failed to compile {:?}: error {}",
-                std::str::from_utf8(pattern),
+                core::str::from_utf8(pattern),
                 e
             ),
         }
@@ -602,6 +808,82 @@ mod tests {
         assert_eq!(pos, 3); // at position 3
     }
 
+    #[test]
+    fn regset_position_lead_empty_match_at_end() {
+        let (set, _) = onig_regset_new(vec![compile(b"x"), compile(b"$")]);
+        let mut set = set.unwrap();
+        let input = b"ab";
+        for start in 0..=input.len() {
+            let (idx, pos) = onig_regset_search(
+                &mut set,
+                input,
+                input.len(),
+                start,
+                input.len(),
+                OnigRegSetLead::PositionLead,
+                ONIG_OPTION_NONE,
+            );
+            assert_eq!((idx, pos), (1, 2));
+        }
+    }
+
+    #[test]
+    fn regset_position_lead_g_anchor_only_at_start() {
+        let (set, _) = onig_regset_new(vec![compile(b"\\Gb"), compile(b"-")]);
+        let mut set = set.unwrap();
+        let search = |set: &mut OnigRegSet, input: &[u8], start| {
+            onig_regset_search(
+                set,
+                input,
+                input.len(),
+                start,
+                input.len(),
+                OnigRegSetLead::PositionLead,
+                ONIG_OPTION_NONE,
+            )
+        };
+        assert_eq!(search(&mut set, b"ab", 0), (ONIG_MISMATCH, 0));
+        assert_eq!(search(&mut set, b"ab", 1), (0, 1));
+        assert_eq!(search(&mut set, b"ab-", 0), (1, 2));
+    }
+
+    #[test]
+    fn regset_position_lead_anychar_inf_tries_search_start() {
+        let (set, _) = onig_regset_new(vec![compile(b".*c"), compile(b"\\x{10FFFF}")]);
+        let mut set = set.unwrap();
+        let input = b"abc";
+        let (idx, pos) = onig_regset_search(
+            &mut set,
+            input,
+            input.len(),
+            1,
+            input.len(),
+            OnigRegSetLead::PositionLead,
+            ONIG_OPTION_NONE,
+        );
+        assert_eq!((idx, pos), (0, 1));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn regset_rejects_input_longer_than_i32_max() {
+        let (set, _) = onig_regset_new(vec![compile(b"abc")]);
+        let mut set = set.unwrap();
+        let input = vec![0u8; ONIG_MAX_INPUT_LEN + 1];
+        for lead in [OnigRegSetLead::PositionLead, OnigRegSetLead::RegexLead] {
+            let (idx, _) = onig_regset_search(
+                &mut set,
+                &input,
+                input.len(),
+                0,
+                input.len(),
+                lead,
+                ONIG_OPTION_NONE,
+            );
+            assert_eq!(idx, ONIGERR_TOO_LONG_INPUT);
+        }
+    }
+
     #[test]
     fn regset_basic_regex_lead() {
         let regs = vec![compile(b"abc"), compile(b"def"), compile(b"ghi")];
@@ -769,6 +1051,211 @@ mod tests {
         assert_eq!(pos, 0);
     }
 
+    #[test]
+    fn regset_remove_and_lead_data() {
+        let (set, r) = onig_regset_new(vec![compile(b"\\Aabc"), compile(b"\\Adef")]);
+        assert_eq!(r, ONIG_NORMAL);
+        let mut set = set.unwrap();
+        assert_ne!(set.anchor & ANCR_BEGIN_BUF, 0);
+
+        // An unanchored regex drops the shared anchor
+        assert_eq!(onig_regset_add(&mut set, compile(b"xyz")), ONIG_NORMAL);
+        assert_eq!(set.anchor & ANCR_BEGIN_BUF, 0);
+
+        // Removing it restores the anchor and returns the regex
+        let reg = onig_regset_remove(&mut set, 2).unwrap();
+        assert_eq!(onig_regset_number_of_regex(&set), 2);
+        assert_ne!(set.anchor & ANCR_BEGIN_BUF, 0);
+        assert!(onig_regset_remove(&mut set, 2).is_none());
+
+        // Replace keeps the index and narrows the lead data again
+        assert_eq!(onig_regset_replace(&mut set, 0, Some(reg)), ONIG_NORMAL);
+        assert_eq!(set.anchor & ANCR_BEGIN_BUF, 0);
+
+        let input = b"def xyz";
+        let (idx, pos) = onig_regset_search(
+            &mut set,
+            input,
+            input.len(),
+            0,
+            input.len(),
+            OnigRegSetLead::PositionLead,
+            ONIG_OPTION_NONE,
+        );
+        assert_eq!(idx, 1);
+        assert_eq!(pos, 0);
+
+        // Removing everything leaves an empty set that can be refilled
+        assert!(onig_regset_remove(&mut set, 0).is_some());
+        assert!(onig_regset_remove(&mut set, 0).is_some());
+        assert_eq!(set.anchor, 0);
+        assert_eq!(onig_regset_add(&mut set, compile(b"\\Aq")), ONIG_NORMAL);
+        assert_ne!(set.anchor & ANCR_BEGIN_BUF, 0);
+    }
+
+    #[test]
+    fn regset_shared_regexes() {
+        let regs: Vec<Arc<RegexType>> =
+            vec![Arc::from(compile(b"\\d+")), Arc::from(compile(b"[a-z]+"))];
+        let (a, r) = onig_regset_new_shared(regs.clone());
+        assert_eq!(r, ONIG_NORMAL);
+        let (b, _) = onig_regset_new_shared(regs.clone());
+        let (mut a, mut b) = (a.unwrap(), b.unwrap());
+        assert_eq!(Arc::strong_count(&regs[0]), 3);
+
+        // Each set has its own regions
+        let search = |set: &mut OnigRegSet, input: &[u8]| {
+            let end = input.len();
+            let lead = OnigRegSetLead::PositionLead;
+            onig_regset_search(set, input, end, 0, end, lead, ONIG_OPTION_NONE)
+        };
+        assert_eq!(search(&mut a, b"x 12"), (1, 0));
+        assert_eq!(search(&mut b, b"12 x"), (0, 0));
+        assert_eq!(onig_regset_get_region(&a, 1).unwrap().end(0), 1);
+        assert_eq!(onig_regset_get_region(&b, 0).unwrap().end(0), 2);
+
+        // A regex still held elsewhere is only handed back shared
+        assert!(onig_regset_remove(&mut a, 0).is_none());
+        assert_eq!(onig_regset_number_of_regex(&a), 2);
+        let digits = onig_regset_remove_shared(&mut a, 0).unwrap();
+        assert!(Arc::ptr_eq(&digits, &regs[0]));
+        assert_eq!(Arc::strong_count(&digits), 3);
+        assert_eq!(onig_regset_number_of_regex(&a), 1);
+        drop(b);
+        drop(regs);
+        assert!(onig_regset_remove(&mut a, 0).is_some());
+        assert!(onig_regset_remove(&mut a, 0).is_none());
+        assert!(onig_regset_remove_shared(&mut a, 0).is_none());
+    }
+
+    #[test]
+    fn regset_search_with_shared_state() {
+        let regs: Vec<Arc<RegexType>> =
+            vec![Arc::from(compile(b"(\\d)+")), Arc::from(compile(b"[a-z]+"))];
+        let (a, _) = onig_regset_new_shared(regs.clone());
+        let (b, _) = onig_regset_new_shared(regs);
+        let (mut a, mut b) = (a.unwrap(), b.unwrap());
+
+        // One state serves both sets, every lead and the empty string
+        let mut state = ExecState::new();
+        for lead in [OnigRegSetLead::PositionLead, OnigRegSetLead::RegexLead] {
+            let mut search = |set: &mut OnigRegSet, input: &[u8]| {
+                let end = input.len();
+                let option = ONIG_OPTION_NONE;
+                onig_regset_search_with_state(set, &mut state, input, end, 0, end, lead, option)
+            };
+            assert_eq!(search(&mut a, b"-- 42"), (0, 3));
+            assert_eq!(search(&mut b, b"-- ab"), (1, 3));
+            assert_eq!(onig_regset_get_region(&a, 0).unwrap().beg[1], 4);
+            assert_eq!(search(&mut a, b""), (ONIG_MISMATCH, 0));
+        }
+        assert!(state.heap_size() > 0);
+    }
+
+    #[test]
+    fn regset_is_match_any() {
+        let regs = vec![compile(b"\\d+"), compile(b"(a)z"), compile(b"^$")];
+        let (set, r) = onig_regset_new(regs);
+        assert_eq!(r, ONIG_NORMAL);
+        let mut set = set.unwrap();
+        let any = |set: &OnigRegSet, input: &[u8], start: usize| {
+            onig_regset_is_match_any(
+                set,
+                input,
+                input.len(),
+                start,
+                input.len(),
+                ONIG_OPTION_NONE,
+            )
+        };
+
+        // The first regex in set order, not the leftmost match
+        assert_eq!(any(&set, b"az 12", 0), 0);
+        assert_eq!(any(&set, b"az", 0), 1);
+        assert_eq!(any(&set, b"", 0), 2);
+        assert_eq!(any(&set, b"a 12", 2), 0);
+        assert_eq!(any(&set, b"a 12", 4), ONIG_MISMATCH);
+        assert_eq!(any(&set, b"xy", 0), ONIG_MISMATCH);
+
+        // Regions are not filled
+        let input = b"12az";
+        let (idx, _) = onig_regset_search(
+            &mut set,
+            input,
+            input.len(),
+            0,
+            input.len(),
+            OnigRegSetLead::RegexLead,
+            ONIG_OPTION_NONE,
+        );
+        assert_eq!(idx, 0);
+        assert_eq!(any(&set, b"xaz", 0), 1);
+        assert_eq!(onig_regset_get_region(&set, 0).unwrap().end(0), 2);
+        assert_eq!(onig_regset_get_region(&set, 1).unwrap().get(0), None);
+
+        let (empty, _) = onig_regset_new(Vec::new());
+        assert_eq!(any(&empty.unwrap(), b"x", 0), ONIG_MISMATCH);
+    }
+
+    #[test]
+    fn regset_search_all_at_position() {
+        let regs = vec![
+            compile(b"[a-z]+"),
+            compile(b"\\d+"),
+            compile(b"if"),
+            compile(b"i(f)x"),
+            compile(b"(?:if|i)(?=f)"),
+        ];
+        let (set, r) = onig_regset_new(regs);
+        assert_eq!(r, ONIG_NORMAL);
+        let mut set = set.unwrap();
+
+        let mut matches = Vec::new();
+        let input = b"12 iffy";
+        let pos = onig_regset_search_all(
+            &mut set,
+            input,
+            input.len(),
+            0,
+            input.len(),
+            ONIG_OPTION_NONE,
+            &mut matches,
+        );
+        assert_eq!(pos, 0);
+        assert_eq!(matches, vec![(1, 2)]);
+
+        let pos = onig_regset_search_all(
+            &mut set,
+            input,
+            input.len(),
+            2,
+            input.len(),
+            ONIG_OPTION_NONE,
+            &mut matches,
+        );
+        assert_eq!(pos, 3);
+        assert_eq!(matches, vec![(0, 4), (2, 2), (4, 2)]);
+        // Non-matching regexes have their regions cleared
+        assert_eq!(
+            onig_regset_get_region(&set, 3).unwrap().beg[0],
+            ONIG_REGION_NOTPOS
+        );
+        assert_eq!(onig_regset_get_region(&set, 2).unwrap().end[0], 5);
+
+        let input = b"   ";
+        let pos = onig_regset_search_all(
+            &mut set,
+            input,
+            input.len(),
+            0,
+            input.len(),
+            ONIG_OPTION_NONE,
+            &mut matches,
+        );
+        assert_eq!(pos, ONIG_MISMATCH);
+        assert!(matches.is_empty());
+    }
+
     #[test]
     fn regset_captures() {
         let regs = vec![compile(b"a(b)c"), compile(b"(d)(e)f")];
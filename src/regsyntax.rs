@@ -3,9 +3,8 @@
 
 #![allow(non_upper_case_globals)]
 
-use std::sync::RwLock;
-
 use crate::oniguruma::*;
+use crate::sys::RwLock;
 
 // === Composite Helper Constants (from regint.h) ===
 
@@ -56,6 +55,7 @@ const DEFAULT_META_CHAR_TABLE: OnigMetaCharTableType = OnigMetaCharTableType {
 
 // === Syntax Definitions ===
 
+#[cfg_attr(ffi_export, no_mangle)]
 pub static OnigSyntaxASIS: OnigSyntaxType = OnigSyntaxType {
     op: 0,
     op2: ONIG_SYN_OP2_INEFFECTIVE_ESCAPE,
@@ -64,6 +64,7 @@ pub static OnigSyntaxASIS: OnigSyntaxType = OnigSyntaxType {
     meta_char_table: DEFAULT_META_CHAR_TABLE,
 };
 
+#[cfg_attr(ffi_export, no_mangle)]
 pub static OnigSyntaxPosixBasic: OnigSyntaxType = OnigSyntaxType {
     op: SYN_POSIX_COMMON_OP | ONIG_SYN_OP_ESC_LPAREN_SUBEXP | ONIG_SYN_OP_ESC_BRACE_INTERVAL,
     op2: 0,
@@ -72,6 +73,7 @@ pub static OnigSyntaxPosixBasic: OnigSyntaxType = OnigSyntaxType {
     meta_char_table: DEFAULT_META_CHAR_TABLE,
 };
 
+#[cfg_attr(ffi_export, no_mangle)]
 pub static OnigSyntaxPosixExtended: OnigSyntaxType = OnigSyntaxType {
     op: SYN_POSIX_COMMON_OP
         | ONIG_SYN_OP_LPAREN_SUBEXP
@@ -89,6 +91,7 @@ pub static OnigSyntaxPosixExtended: OnigSyntaxType = OnigSyntaxType {
     meta_char_table: DEFAULT_META_CHAR_TABLE,
 };
 
+#[cfg_attr(ffi_export, no_mangle)]
 pub static OnigSyntaxEmacs: OnigSyntaxType = OnigSyntaxType {
     op: ONIG_SYN_OP_DOT_ANYCHAR
         | ONIG_SYN_OP_BRACKET_CC
@@ -107,6 +110,7 @@ pub static OnigSyntaxEmacs: OnigSyntaxType = OnigSyntaxType {
     meta_char_table: DEFAULT_META_CHAR_TABLE,
 };
 
+#[cfg_attr(ffi_export, no_mangle)]
 pub static OnigSyntaxGrep: OnigSyntaxType = OnigSyntaxType {
     op: ONIG_SYN_OP_DOT_ANYCHAR
         | ONIG_SYN_OP_BRACKET_CC
@@ -130,6 +134,7 @@ pub static OnigSyntaxGrep: OnigSyntaxType = OnigSyntaxType {
     meta_char_table: DEFAULT_META_CHAR_TABLE,
 };
 
+#[cfg_attr(ffi_export, no_mangle)]
 pub static OnigSyntaxGnuRegex: OnigSyntaxType = OnigSyntaxType {
     op: SYN_GNU_REGEX_OP,
     op2: 0,
@@ -138,6 +143,7 @@ pub static OnigSyntaxGnuRegex: OnigSyntaxType = OnigSyntaxType {
     meta_char_table: DEFAULT_META_CHAR_TABLE,
 };
 
+#[cfg_attr(ffi_export, no_mangle)]
 pub static OnigSyntaxJava: OnigSyntaxType = OnigSyntaxType {
     op: (SYN_GNU_REGEX_OP
         | ONIG_SYN_OP_QMARK_NON_GREEDY
@@ -164,6 +170,7 @@ pub static OnigSyntaxJava: OnigSyntaxType = OnigSyntaxType {
     meta_char_table: DEFAULT_META_CHAR_TABLE,
 };
 
+#[cfg_attr(ffi_export, no_mangle)]
 pub static OnigSyntaxPerl: OnigSyntaxType = OnigSyntaxType {
     op: (SYN_GNU_REGEX_OP
         | ONIG_SYN_OP_QMARK_NON_GREEDY
@@ -198,6 +205,7 @@ pub static OnigSyntaxPerl: OnigSyntaxType = OnigSyntaxType {
 };
 
 // Perl + named group
+#[cfg_attr(ffi_export, no_mangle)]
 pub static OnigSyntaxPerl_NG: OnigSyntaxType = OnigSyntaxType {
     op: (SYN_GNU_REGEX_OP
         | ONIG_SYN_OP_QMARK_NON_GREEDY
@@ -238,6 +246,7 @@ pub static OnigSyntaxPerl_NG: OnigSyntaxType = OnigSyntaxType {
 };
 
 // Python 3.9
+#[cfg_attr(ffi_export, no_mangle)]
 pub static OnigSyntaxPython: OnigSyntaxType = OnigSyntaxType {
     op: (SYN_GNU_REGEX_OP
         | ONIG_SYN_OP_QMARK_NON_GREEDY
@@ -265,6 +274,7 @@ pub static OnigSyntaxPython: OnigSyntaxType = OnigSyntaxType {
 };
 
 // Oniguruma (from regparse.c)
+#[cfg_attr(ffi_export, no_mangle)]
 pub static OnigSyntaxOniguruma: OnigSyntaxType = OnigSyntaxType {
     op: (SYN_GNU_REGEX_OP
         | ONIG_SYN_OP_QMARK_NON_GREEDY
@@ -314,6 +324,7 @@ pub static OnigSyntaxOniguruma: OnigSyntaxType = OnigSyntaxType {
 };
 
 // Ruby (from regparse.c)
+#[cfg_attr(ffi_export, no_mangle)]
 pub static OnigSyntaxRuby: OnigSyntaxType = OnigSyntaxType {
     op: (SYN_GNU_REGEX_OP
         | ONIG_SYN_OP_QMARK_NON_GREEDY
@@ -362,13 +373,13 @@ static ONIG_DEFAULT_SYNTAX: RwLock<&OnigSyntaxType> = RwLock::new(&OnigSyntaxOni
 #[cfg_attr(coverage_nightly, coverage(off))]
 pub fn onig_get_default_syntax() -> &'static OnigSyntaxType {
     // Safety: the RwLock always holds a &'static ref
-    *ONIG_DEFAULT_SYNTAX.read().unwrap()
+    *ONIG_DEFAULT_SYNTAX.read()
 }
 
 #[cfg_attr(coverage_nightly, coverage(off))]
 pub fn onig_set_default_syntax(syntax: Option<&'static OnigSyntaxType>) -> i32 {
     let syntax = syntax.unwrap_or(&OnigSyntaxOniguruma);
-    *ONIG_DEFAULT_SYNTAX.write().unwrap() = syntax;
+    *ONIG_DEFAULT_SYNTAX.write() = syntax;
     0
 }
 
@@ -434,3 +445,922 @@ pub fn onig_set_meta_char(syntax: &mut OnigSyntaxType, what: u32, code: OnigCode
     }
     0
 }
+
+// === Feature Matrix ===
+//
+// A readable view of the op/op2/behavior bits, so tools can show
+// which constructs a syntax accepts (and explain why a pattern was
+// rejected) without hard-coding the flag values.
+
+/// Which bit set of [`OnigSyntaxType`] a [`SyntaxFeature`] lives in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SyntaxFeatureGroup {
+    /// `OnigSyntaxType::op`
+    Op,
+    /// `OnigSyntaxType::op2`
+    Op2,
+    /// `OnigSyntaxType::behavior`
+    Behavior,
+}
+
+/// One syntax flag: its C constant name, where it lives and what it does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SyntaxFeature {
+    /// Name of the `ONIG_SYN_*` constant, e.g. `"ONIG_SYN_OP_DOT_ANYCHAR"`.
+    pub name: &'static str,
+    pub group: SyntaxFeatureGroup,
+    /// The flag value (a single bit).
+    pub bit: u32,
+    /// Short human-readable description.
+    pub description: &'static str,
+}
+
+const fn feature(
+    name: &'static str,
+    group: SyntaxFeatureGroup,
+    bit: u32,
+    description: &'static str,
+) -> SyntaxFeature {
+    SyntaxFeature {
+        name,
+        group,
+        bit,
+        description,
+    }
+}
+
+use SyntaxFeatureGroup::{Behavior, Op, Op2};
+
+/// Every syntax flag, in `op`, `op2`, `behavior` order.
+pub static SYNTAX_FEATURES: [SyntaxFeature; 88] = [
+    feature(
+        "ONIG_SYN_OP_VARIABLE_META_CHARACTERS",
+        Op,
+        ONIG_SYN_OP_VARIABLE_META_CHARACTERS,
+        "meta characters come from the syntax's meta char table (onig_set_meta_char)",
+    ),
+    feature(
+        "ONIG_SYN_OP_DOT_ANYCHAR",
+        Op,
+        ONIG_SYN_OP_DOT_ANYCHAR,
+        "`.` matches any character",
+    ),
+    feature(
+        "ONIG_SYN_OP_ASTERISK_ZERO_INF",
+        Op,
+        ONIG_SYN_OP_ASTERISK_ZERO_INF,
+        "`*` repeats zero or more times",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_ASTERISK_ZERO_INF",
+        Op,
+        ONIG_SYN_OP_ESC_ASTERISK_ZERO_INF,
+        "`\\*` repeats zero or more times",
+    ),
+    feature(
+        "ONIG_SYN_OP_PLUS_ONE_INF",
+        Op,
+        ONIG_SYN_OP_PLUS_ONE_INF,
+        "`+` repeats one or more times",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_PLUS_ONE_INF",
+        Op,
+        ONIG_SYN_OP_ESC_PLUS_ONE_INF,
+        "`\\+` repeats one or more times",
+    ),
+    feature(
+        "ONIG_SYN_OP_QMARK_ZERO_ONE",
+        Op,
+        ONIG_SYN_OP_QMARK_ZERO_ONE,
+        "`?` matches zero or one time",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_QMARK_ZERO_ONE",
+        Op,
+        ONIG_SYN_OP_ESC_QMARK_ZERO_ONE,
+        "`\\?` matches zero or one time",
+    ),
+    feature(
+        "ONIG_SYN_OP_BRACE_INTERVAL",
+        Op,
+        ONIG_SYN_OP_BRACE_INTERVAL,
+        "`{n,m}` interval repeats",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_BRACE_INTERVAL",
+        Op,
+        ONIG_SYN_OP_ESC_BRACE_INTERVAL,
+        "`\\{n,m\\}` interval repeats",
+    ),
+    feature(
+        "ONIG_SYN_OP_VBAR_ALT",
+        Op,
+        ONIG_SYN_OP_VBAR_ALT,
+        "`|` alternation",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_VBAR_ALT",
+        Op,
+        ONIG_SYN_OP_ESC_VBAR_ALT,
+        "`\\|` alternation",
+    ),
+    feature(
+        "ONIG_SYN_OP_LPAREN_SUBEXP",
+        Op,
+        ONIG_SYN_OP_LPAREN_SUBEXP,
+        "`(...)` groups",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_LPAREN_SUBEXP",
+        Op,
+        ONIG_SYN_OP_ESC_LPAREN_SUBEXP,
+        "`\\(...\\)` groups",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_AZ_BUF_ANCHOR",
+        Op,
+        ONIG_SYN_OP_ESC_AZ_BUF_ANCHOR,
+        "`\\A`, `\\Z`, `\\z` buffer anchors",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_CAPITAL_G_BEGIN_ANCHOR",
+        Op,
+        ONIG_SYN_OP_ESC_CAPITAL_G_BEGIN_ANCHOR,
+        "`\\G` search start anchor",
+    ),
+    feature(
+        "ONIG_SYN_OP_DECIMAL_BACKREF",
+        Op,
+        ONIG_SYN_OP_DECIMAL_BACKREF,
+        "`\\1`..`\\9` numbered backreferences",
+    ),
+    feature(
+        "ONIG_SYN_OP_BRACKET_CC",
+        Op,
+        ONIG_SYN_OP_BRACKET_CC,
+        "`[...]` character classes",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_W_WORD",
+        Op,
+        ONIG_SYN_OP_ESC_W_WORD,
+        "`\\w`, `\\W` word characters",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_LTGT_WORD_BEGIN_END",
+        Op,
+        ONIG_SYN_OP_ESC_LTGT_WORD_BEGIN_END,
+        "`\\<`, `\\>` word start and end",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_B_WORD_BOUND",
+        Op,
+        ONIG_SYN_OP_ESC_B_WORD_BOUND,
+        "`\\b`, `\\B` word boundaries",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_S_WHITE_SPACE",
+        Op,
+        ONIG_SYN_OP_ESC_S_WHITE_SPACE,
+        "`\\s`, `\\S` white space",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_D_DIGIT",
+        Op,
+        ONIG_SYN_OP_ESC_D_DIGIT,
+        "`\\d`, `\\D` digits",
+    ),
+    feature(
+        "ONIG_SYN_OP_LINE_ANCHOR",
+        Op,
+        ONIG_SYN_OP_LINE_ANCHOR,
+        "`^`, `$` line anchors",
+    ),
+    feature(
+        "ONIG_SYN_OP_POSIX_BRACKET",
+        Op,
+        ONIG_SYN_OP_POSIX_BRACKET,
+        "`[:alpha:]` POSIX brackets in classes",
+    ),
+    feature(
+        "ONIG_SYN_OP_QMARK_NON_GREEDY",
+        Op,
+        ONIG_SYN_OP_QMARK_NON_GREEDY,
+        "`*?`, `+?`, `??` lazy repeats",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_CONTROL_CHARS",
+        Op,
+        ONIG_SYN_OP_ESC_CONTROL_CHARS,
+        "`\\n`, `\\t`, `\\r`, ... control characters",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_C_CONTROL",
+        Op,
+        ONIG_SYN_OP_ESC_C_CONTROL,
+        "`\\cX` control characters",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_OCTAL3",
+        Op,
+        ONIG_SYN_OP_ESC_OCTAL3,
+        "`\\ooo` octal escapes",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_X_HEX2",
+        Op,
+        ONIG_SYN_OP_ESC_X_HEX2,
+        "`\\xHH` hex escapes",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_X_BRACE_HEX8",
+        Op,
+        ONIG_SYN_OP_ESC_X_BRACE_HEX8,
+        "`\\x{H...}` code point escapes",
+    ),
+    feature(
+        "ONIG_SYN_OP_ESC_O_BRACE_OCTAL",
+        Op,
+        ONIG_SYN_OP_ESC_O_BRACE_OCTAL,
+        "`\\o{O...}` octal code point escapes",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ESC_CAPITAL_Q_QUOTE",
+        Op2,
+        ONIG_SYN_OP2_ESC_CAPITAL_Q_QUOTE,
+        "`\\Q...\\E` literal quoting",
+    ),
+    feature(
+        "ONIG_SYN_OP2_QMARK_GROUP_EFFECT",
+        Op2,
+        ONIG_SYN_OP2_QMARK_GROUP_EFFECT,
+        "`(?...)` extended groups",
+    ),
+    feature(
+        "ONIG_SYN_OP2_OPTION_PERL",
+        Op2,
+        ONIG_SYN_OP2_OPTION_PERL,
+        "Perl inline options `(?imsx)`",
+    ),
+    feature(
+        "ONIG_SYN_OP2_OPTION_RUBY",
+        Op2,
+        ONIG_SYN_OP2_OPTION_RUBY,
+        "Ruby inline options `(?imx)`, `m` is dotall",
+    ),
+    feature(
+        "ONIG_SYN_OP2_PLUS_POSSESSIVE_REPEAT",
+        Op2,
+        ONIG_SYN_OP2_PLUS_POSSESSIVE_REPEAT,
+        "`*+`, `++`, `?+` possessive repeats",
+    ),
+    feature(
+        "ONIG_SYN_OP2_PLUS_POSSESSIVE_INTERVAL",
+        Op2,
+        ONIG_SYN_OP2_PLUS_POSSESSIVE_INTERVAL,
+        "`{n,m}+` possessive intervals",
+    ),
+    feature(
+        "ONIG_SYN_OP2_CCLASS_SET_OP",
+        Op2,
+        ONIG_SYN_OP2_CCLASS_SET_OP,
+        "`&&` and nested classes inside `[...]`",
+    ),
+    feature(
+        "ONIG_SYN_OP2_QMARK_LT_NAMED_GROUP",
+        Op2,
+        ONIG_SYN_OP2_QMARK_LT_NAMED_GROUP,
+        "`(?<name>...)` named groups",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ESC_K_NAMED_BACKREF",
+        Op2,
+        ONIG_SYN_OP2_ESC_K_NAMED_BACKREF,
+        "`\\k<name>` backreferences",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ESC_G_SUBEXP_CALL",
+        Op2,
+        ONIG_SYN_OP2_ESC_G_SUBEXP_CALL,
+        "`\\g<name>` subexpression calls",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ATMARK_CAPTURE_HISTORY",
+        Op2,
+        ONIG_SYN_OP2_ATMARK_CAPTURE_HISTORY,
+        "`(?@...)` capture history",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ESC_CAPITAL_C_BAR_CONTROL",
+        Op2,
+        ONIG_SYN_OP2_ESC_CAPITAL_C_BAR_CONTROL,
+        "`\\C-x` control characters",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ESC_CAPITAL_M_BAR_META",
+        Op2,
+        ONIG_SYN_OP2_ESC_CAPITAL_M_BAR_META,
+        "`\\M-x` meta characters",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ESC_V_VTAB",
+        Op2,
+        ONIG_SYN_OP2_ESC_V_VTAB,
+        "`\\v` vertical tab",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ESC_U_HEX4",
+        Op2,
+        ONIG_SYN_OP2_ESC_U_HEX4,
+        "`\\uHHHH` code point escapes",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ESC_GNU_BUF_ANCHOR",
+        Op2,
+        ONIG_SYN_OP2_ESC_GNU_BUF_ANCHOR,
+        "GNU `\\``, `\\'` buffer anchors",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ESC_P_BRACE_CHAR_PROPERTY",
+        Op2,
+        ONIG_SYN_OP2_ESC_P_BRACE_CHAR_PROPERTY,
+        "`\\p{...}`, `\\P{...}` properties",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ESC_P_BRACE_CIRCUMFLEX_NOT",
+        Op2,
+        ONIG_SYN_OP2_ESC_P_BRACE_CIRCUMFLEX_NOT,
+        "`\\p{^...}` negated properties",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ESC_H_XDIGIT",
+        Op2,
+        ONIG_SYN_OP2_ESC_H_XDIGIT,
+        "`\\h`, `\\H` hex digits",
+    ),
+    feature(
+        "ONIG_SYN_OP2_INEFFECTIVE_ESCAPE",
+        Op2,
+        ONIG_SYN_OP2_INEFFECTIVE_ESCAPE,
+        "`\\` is an ordinary character",
+    ),
+    feature(
+        "ONIG_SYN_OP2_QMARK_LPAREN_IF_ELSE",
+        Op2,
+        ONIG_SYN_OP2_QMARK_LPAREN_IF_ELSE,
+        "`(?(cond)yes|no)` conditionals",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ESC_CAPITAL_K_KEEP",
+        Op2,
+        ONIG_SYN_OP2_ESC_CAPITAL_K_KEEP,
+        "`\\K` keep",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ESC_CAPITAL_R_GENERAL_NEWLINE",
+        Op2,
+        ONIG_SYN_OP2_ESC_CAPITAL_R_GENERAL_NEWLINE,
+        "`\\R` general newline",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ESC_CAPITAL_N_O_SUPER_DOT",
+        Op2,
+        ONIG_SYN_OP2_ESC_CAPITAL_N_O_SUPER_DOT,
+        "`\\N` non-newline, `\\O` any character",
+    ),
+    feature(
+        "ONIG_SYN_OP2_QMARK_TILDE_ABSENT_GROUP",
+        Op2,
+        ONIG_SYN_OP2_QMARK_TILDE_ABSENT_GROUP,
+        "`(?~...)` absent groups",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ESC_X_Y_TEXT_SEGMENT",
+        Op2,
+        ONIG_SYN_OP2_ESC_X_Y_TEXT_SEGMENT,
+        "`\\X` text segments, `\\y`, `\\Y` boundaries",
+    ),
+    feature(
+        "ONIG_SYN_OP2_QMARK_PERL_SUBEXP_CALL",
+        Op2,
+        ONIG_SYN_OP2_QMARK_PERL_SUBEXP_CALL,
+        "`(?R)`, `(?&name)` Perl subexpression calls",
+    ),
+    feature(
+        "ONIG_SYN_OP2_QMARK_BRACE_CALLOUT_CONTENTS",
+        Op2,
+        ONIG_SYN_OP2_QMARK_BRACE_CALLOUT_CONTENTS,
+        "`(?{...})` callouts of contents",
+    ),
+    feature(
+        "ONIG_SYN_OP2_ASTERISK_CALLOUT_NAME",
+        Op2,
+        ONIG_SYN_OP2_ASTERISK_CALLOUT_NAME,
+        "`(*name)` callouts of name",
+    ),
+    feature(
+        "ONIG_SYN_OP2_OPTION_ONIGURUMA",
+        Op2,
+        ONIG_SYN_OP2_OPTION_ONIGURUMA,
+        "Oniguruma inline options `(?imxWDSPy)`",
+    ),
+    feature(
+        "ONIG_SYN_OP2_QMARK_CAPITAL_P_NAME",
+        Op2,
+        ONIG_SYN_OP2_QMARK_CAPITAL_P_NAME,
+        "`(?P<name>...)`, `(?P=name)`, `(?P>name)`",
+    ),
+    feature(
+        "ONIG_SYN_CONTEXT_INDEP_REPEAT_OPS",
+        Behavior,
+        ONIG_SYN_CONTEXT_INDEP_REPEAT_OPS,
+        "a repeat operator without a target is not a literal",
+    ),
+    feature(
+        "ONIG_SYN_CONTEXT_INVALID_REPEAT_OPS",
+        Behavior,
+        ONIG_SYN_CONTEXT_INVALID_REPEAT_OPS,
+        "a repeat operator without a target is an error",
+    ),
+    feature(
+        "ONIG_SYN_ALLOW_UNMATCHED_CLOSE_SUBEXP",
+        Behavior,
+        ONIG_SYN_ALLOW_UNMATCHED_CLOSE_SUBEXP,
+        "an unmatched `)` is a literal",
+    ),
+    feature(
+        "ONIG_SYN_ALLOW_INVALID_INTERVAL",
+        Behavior,
+        ONIG_SYN_ALLOW_INVALID_INTERVAL,
+        "a malformed `{...}` is a literal",
+    ),
+    feature(
+        "ONIG_SYN_ALLOW_INTERVAL_LOW_ABBREV",
+        Behavior,
+        ONIG_SYN_ALLOW_INTERVAL_LOW_ABBREV,
+        "`{,n}` means `{0,n}`",
+    ),
+    feature(
+        "ONIG_SYN_STRICT_CHECK_BACKREF",
+        Behavior,
+        ONIG_SYN_STRICT_CHECK_BACKREF,
+        "backreferences to undefined groups are errors",
+    ),
+    feature(
+        "ONIG_SYN_DIFFERENT_LEN_ALT_LOOK_BEHIND",
+        Behavior,
+        ONIG_SYN_DIFFERENT_LEN_ALT_LOOK_BEHIND,
+        "look-behind alternatives may differ in length",
+    ),
+    feature(
+        "ONIG_SYN_CAPTURE_ONLY_NAMED_GROUP",
+        Behavior,
+        ONIG_SYN_CAPTURE_ONLY_NAMED_GROUP,
+        "plain groups do not capture when named groups are present",
+    ),
+    feature(
+        "ONIG_SYN_ALLOW_MULTIPLEX_DEFINITION_NAME",
+        Behavior,
+        ONIG_SYN_ALLOW_MULTIPLEX_DEFINITION_NAME,
+        "several groups may share a name",
+    ),
+    feature(
+        "ONIG_SYN_FIXED_INTERVAL_IS_GREEDY_ONLY",
+        Behavior,
+        ONIG_SYN_FIXED_INTERVAL_IS_GREEDY_ONLY,
+        "`a{n}?` means `(?:a{n})?`",
+    ),
+    feature(
+        "ONIG_SYN_ISOLATED_OPTION_CONTINUE_BRANCH",
+        Behavior,
+        ONIG_SYN_ISOLATED_OPTION_CONTINUE_BRANCH,
+        "`(?i)` continues the current branch instead of opening a group",
+    ),
+    feature(
+        "ONIG_SYN_VARIABLE_LEN_LOOK_BEHIND",
+        Behavior,
+        ONIG_SYN_VARIABLE_LEN_LOOK_BEHIND,
+        "look-behind may have variable length",
+    ),
+    feature(
+        "ONIG_SYN_PYTHON",
+        Behavior,
+        ONIG_SYN_PYTHON,
+        "Python escapes: `\\Z` is end of buffer, `\\UHHHHHHHH`",
+    ),
+    feature(
+        "ONIG_SYN_WHOLE_OPTIONS",
+        Behavior,
+        ONIG_SYN_WHOLE_OPTIONS,
+        "whole-pattern options `(?C)`, `(?I)`, `(?L)`",
+    ),
+    feature(
+        "ONIG_SYN_BRE_ANCHOR_AT_EDGE_OF_SUBEXP",
+        Behavior,
+        ONIG_SYN_BRE_ANCHOR_AT_EDGE_OF_SUBEXP,
+        "`^`, `$` are anchors at group edges (BRE)",
+    ),
+    feature(
+        "ONIG_SYN_ESC_P_WITH_ONE_CHAR_PROP",
+        Behavior,
+        ONIG_SYN_ESC_P_WITH_ONE_CHAR_PROP,
+        "`\\pL` one-letter properties",
+    ),
+    feature(
+        "ONIG_SYN_NOT_NEWLINE_IN_NEGATIVE_CC",
+        Behavior,
+        ONIG_SYN_NOT_NEWLINE_IN_NEGATIVE_CC,
+        "`[^...]` does not match newline",
+    ),
+    feature(
+        "ONIG_SYN_BACKSLASH_ESCAPE_IN_CC",
+        Behavior,
+        ONIG_SYN_BACKSLASH_ESCAPE_IN_CC,
+        "`\\` escapes inside `[...]`",
+    ),
+    feature(
+        "ONIG_SYN_ALLOW_EMPTY_RANGE_IN_CC",
+        Behavior,
+        ONIG_SYN_ALLOW_EMPTY_RANGE_IN_CC,
+        "empty ranges like `[z-a]` are allowed",
+    ),
+    feature(
+        "ONIG_SYN_ALLOW_DOUBLE_RANGE_OP_IN_CC",
+        Behavior,
+        ONIG_SYN_ALLOW_DOUBLE_RANGE_OP_IN_CC,
+        "`[a-b-c]`: a second `-` is a literal",
+    ),
+    feature(
+        "ONIG_SYN_WARN_CC_OP_NOT_ESCAPED",
+        Behavior,
+        ONIG_SYN_WARN_CC_OP_NOT_ESCAPED,
+        "warn about unescaped `[` and `-` in classes",
+    ),
+    feature(
+        "ONIG_SYN_WARN_REDUNDANT_NESTED_REPEAT",
+        Behavior,
+        ONIG_SYN_WARN_REDUNDANT_NESTED_REPEAT,
+        "warn about redundant nested repeats like `(?:a*)+`",
+    ),
+    feature(
+        "ONIG_SYN_ALLOW_INVALID_CODE_END_OF_RANGE_IN_CC",
+        Behavior,
+        ONIG_SYN_ALLOW_INVALID_CODE_END_OF_RANGE_IN_CC,
+        "a class range may end in an invalid code",
+    ),
+    feature(
+        "ONIG_SYN_ALLOW_CHAR_TYPE_FOLLOWED_BY_MINUS_IN_CC",
+        Behavior,
+        ONIG_SYN_ALLOW_CHAR_TYPE_FOLLOWED_BY_MINUS_IN_CC,
+        "`[\\w-a]`: `-` after a character type is a literal",
+    ),
+    feature(
+        "ONIG_SYN_CONTEXT_INDEP_ANCHORS",
+        Behavior,
+        ONIG_SYN_CONTEXT_INDEP_ANCHORS,
+        "`^`, `$` are anchors anywhere in the pattern",
+    ),
+];
+
+/// The flags a syntax has set, as returned by [`OnigSyntaxType::features`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SyntaxFeatures {
+    op: u32,
+    op2: u32,
+    behavior: u32,
+}
+
+impl SyntaxFeatures {
+    /// Whether `feature` is set.
+    pub fn is_enabled(&self, feature: &SyntaxFeature) -> bool {
+        let bits = match feature.group {
+            Op => self.op,
+            Op2 => self.op2,
+            Behavior => self.behavior,
+        };
+        bits & feature.bit != 0
+    }
+
+    /// Whether the flag named `name` (an `ONIG_SYN_*` constant name) is
+    /// set, or `None` for an unknown name.
+    pub fn get(&self, name: &str) -> Option<bool> {
+        SYNTAX_FEATURES
+            .iter()
+            .find(|f| f.name == name)
+            .map(|f| self.is_enabled(f))
+    }
+
+    /// The flags that are set.
+    pub fn enabled(&self) -> impl Iterator<Item = &'static SyntaxFeature> + '_ {
+        SYNTAX_FEATURES.iter().filter(|f| self.is_enabled(f))
+    }
+
+    /// The flags that are not set.
+    pub fn disabled(&self) -> impl Iterator<Item = &'static SyntaxFeature> + '_ {
+        SYNTAX_FEATURES.iter().filter(|f| !self.is_enabled(f))
+    }
+}
+
+impl OnigSyntaxType {
+    /// The operators and behaviors this syntax enables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ferroni::regsyntax::{OnigSyntaxPosixBasic, OnigSyntaxRuby};
+    ///
+    /// let ruby = OnigSyntaxRuby.features();
+    /// assert_eq!(ruby.get("ONIG_SYN_OP2_QMARK_TILDE_ABSENT_GROUP"), Some(true));
+    /// let bre = OnigSyntaxPosixBasic.features();
+    /// assert_eq!(bre.get("ONIG_SYN_OP_VBAR_ALT"), Some(false));
+    /// for f in bre.enabled() {
+    ///     println!("{}: {}", f.name, f.description);
+    /// }
+    /// ```
+    pub fn features(&self) -> SyntaxFeatures {
+        SyntaxFeatures {
+            op: self.op,
+            op2: self.op2,
+            behavior: self.behavior,
+        }
+    }
+}
+
+// === Syntax Builder ===
+//
+// C callers copy a syntax with onig_copy_syntax() and poke its
+// fields. SyntaxBuilder does the same on an owned copy, but takes flags by
+// group and checks them against SYNTAX_FEATURES, and rejects meta char
+// tables the parser cannot tell apart.
+
+/// Derive a custom dialect from a built-in syntax.
+///
+/// The result is an owned [`OnigSyntaxType`] for
+/// [`RegexBuilder::syntax`](crate::api::RegexBuilder::syntax).
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::api::Regex;
+/// use ferroni::regsyntax::{OnigSyntaxPerl_NG, SyntaxBuilder};
+///
+/// // Perl with named groups, minus backreferences and callouts
+/// let untrusted = SyntaxBuilder::new(&OnigSyntaxPerl_NG)
+///     .backrefs(false)
+///     .callouts(false)
+///     .build()
+///     .unwrap();
+/// let syntax = std::sync::Arc::new(untrusted);
+/// let re = Regex::builder(r"(?<w>\w+)-\d").syntax(syntax.clone()).build();
+/// assert!(re.unwrap().is_match("abc-1"));
+/// // Without backreferences `\1` is an octal escape
+/// let re = Regex::builder(r"(a)\1").syntax(syntax).build().unwrap();
+/// assert!(!re.is_match("aa"));
+/// assert!(re.is_match("a\u{1}"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SyntaxBuilder {
+    syntax: OnigSyntaxType,
+    invalid: bool,
+}
+
+impl SyntaxBuilder {
+    /// Start from a copy of `base`, e.g. [`OnigSyntaxOniguruma`].
+    pub fn new(base: &OnigSyntaxType) -> Self {
+        SyntaxBuilder {
+            syntax: base.clone(),
+            invalid: false,
+        }
+    }
+
+    /// Turn on or off the flags `bits` of `group` (`ONIG_SYN_OP_*`,
+    /// `ONIG_SYN_OP2_*` or behavior constants). Bits that are not a flag of
+    /// the group make [`build`](Self::build) fail.
+    pub fn set(mut self, group: SyntaxFeatureGroup, bits: u32, on: bool) -> Self {
+        let known = SYNTAX_FEATURES
+            .iter()
+            .filter(|f| f.group == group)
+            .fold(0, |acc, f| acc | f.bit);
+        if bits & !known != 0 {
+            self.invalid = true;
+            return self;
+        }
+        let field = match group {
+            Op => &mut self.syntax.op,
+            Op2 => &mut self.syntax.op2,
+            Behavior => &mut self.syntax.behavior,
+        };
+        if on {
+            *field |= bits;
+        } else {
+            *field &= !bits;
+        }
+        self
+    }
+
+    /// Turn on the flag named `name` (an `ONIG_SYN_*` constant name, see
+    /// [`SYNTAX_FEATURES`]). An unknown name makes [`build`](Self::build)
+    /// fail.
+    pub fn enable(self, name: &str) -> Self {
+        self.set_named(name, true)
+    }
+
+    /// Turn off the flag named `name`; see [`enable`](Self::enable).
+    pub fn disable(self, name: &str) -> Self {
+        self.set_named(name, false)
+    }
+
+    fn set_named(mut self, name: &str, on: bool) -> Self {
+        match SYNTAX_FEATURES.iter().find(|f| f.name == name) {
+            Some(f) => self.set(f.group, f.bit, on),
+            None => {
+                self.invalid = true;
+                self
+            }
+        }
+    }
+
+    /// Allow or forbid backreferences: `\1`, `\k<name>` and Python's
+    /// `(?P=name)`. The last shares its flag with `(?P<name>...)`, so
+    /// forbidding backreferences also turns off that group form; the
+    /// `(?<name>...)` form stays if the base syntax has it.
+    pub fn backrefs(self, on: bool) -> Self {
+        self.set(Op, ONIG_SYN_OP_DECIMAL_BACKREF, on).set(
+            Op2,
+            ONIG_SYN_OP2_ESC_K_NAMED_BACKREF | ONIG_SYN_OP2_QMARK_CAPITAL_P_NAME,
+            on,
+        )
+    }
+
+    /// Allow or forbid callouts: `(?{...})` and `(*name)`. Custom opcodes
+    /// use the `(*name)` form too.
+    pub fn callouts(self, on: bool) -> Self {
+        self.set(
+            Op2,
+            ONIG_SYN_OP2_QMARK_BRACE_CALLOUT_CONTENTS | ONIG_SYN_OP2_ASTERISK_CALLOUT_NAME,
+            on,
+        )
+    }
+
+    /// Set the options every pattern of this syntax starts with.
+    pub fn options(mut self, options: OnigOptionType) -> Self {
+        self.syntax.options = options;
+        self
+    }
+
+    /// Set one meta char (`ONIG_META_CHAR_*`) to `code`, or to
+    /// [`ONIG_INEFFECTIVE_META_CHAR`] to remove it. Turns on
+    /// `ONIG_SYN_OP_VARIABLE_META_CHARACTERS`, without which the parser does
+    /// not read the table.
+    pub fn meta_char(mut self, what: u32, code: OnigCodePoint) -> Self {
+        if onig_set_meta_char(&mut self.syntax, what, code) != 0 {
+            self.invalid = true;
+        }
+        self.syntax.op |= ONIG_SYN_OP_VARIABLE_META_CHARACTERS;
+        self
+    }
+
+    /// The finished syntax, or [`RegexError::InvalidArgument`] if a flag
+    /// or meta char was unknown or two meta chars share a code.
+    ///
+    /// [`RegexError::InvalidArgument`]: crate::error::RegexError::InvalidArgument
+    pub fn build(self) -> Result<OnigSyntaxType, crate::error::RegexError> {
+        let t = &self.syntax.meta_char_table;
+        let codes = [
+            t.esc,
+            t.anychar,
+            t.anytime,
+            t.zero_or_one_time,
+            t.one_or_more_time,
+            t.anychar_anytime,
+        ];
+        let clash = (self.syntax.op & ONIG_SYN_OP_VARIABLE_META_CHARACTERS) != 0
+            && codes
+                .iter()
+                .enumerate()
+                .any(|(i, &c)| c != ONIG_INEFFECTIVE_META_CHAR && codes[i + 1..].contains(&c));
+        if self.invalid || clash {
+            return Err(crate::error::RegexError::InvalidArgument);
+        }
+        Ok(self.syntax)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feature_table_covers_every_flag_once() {
+        for group in [Op, Op2, Behavior] {
+            let mut seen = 0u32;
+            for f in SYNTAX_FEATURES.iter().filter(|f| f.group == group) {
+                assert_eq!(f.bit.count_ones(), 1, "{}", f.name);
+                assert_eq!(seen & f.bit, 0, "{}", f.name);
+                seen |= f.bit;
+            }
+            let expected = match group {
+                Op => u32::MAX,
+                Op2 => !(1 << 18),
+                Behavior => 0xffff | 0xff << 20 | 1 << 31,
+            };
+            assert_eq!(seen, expected, "{:?}", group);
+        }
+    }
+
+    #[test]
+    fn features_reflect_syntax_bits() {
+        let f = OnigSyntaxPerl_NG.features();
+        assert_eq!(f.get("ONIG_SYN_OP2_QMARK_LT_NAMED_GROUP"), Some(true));
+        assert_eq!(f.get("ONIG_SYN_OP2_OPTION_RUBY"), Some(false));
+        assert_eq!(f.get("ONIG_SYN_NO_SUCH_FLAG"), None);
+        let n = f.enabled().count();
+        assert_eq!(n + f.disabled().count(), SYNTAX_FEATURES.len());
+        assert_eq!(
+            n as u32,
+            OnigSyntaxPerl_NG.op.count_ones()
+                + OnigSyntaxPerl_NG.op2.count_ones()
+                + OnigSyntaxPerl_NG.behavior.count_ones()
+        );
+        assert_eq!(
+            OnigSyntaxASIS
+                .features()
+                .get("ONIG_SYN_OP2_INEFFECTIVE_ESCAPE"),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn variable_escape_meta_char() {
+        // The C API way: copy a syntax and change its escape to `%`
+        let mut syntax = OnigSyntaxRuby.clone();
+        onig_copy_syntax(&mut syntax, &OnigSyntaxOniguruma);
+        let op = onig_get_syntax_op(&syntax);
+        onig_set_syntax_op(&mut syntax, op | ONIG_SYN_OP_VARIABLE_META_CHARACTERS);
+        assert_eq!(
+            onig_set_meta_char(&mut syntax, ONIG_META_CHAR_ESCAPE, '%' as u32),
+            0
+        );
+        assert_eq!(
+            onig_set_meta_char(&mut syntax, 99, '#' as u32),
+            ONIGERR_INVALID_ARGUMENT
+        );
+
+        let syntax: &'static OnigSyntaxType = Box::leak(Box::new(syntax));
+        let find = |pattern: &str, text: &str| {
+            let re = crate::api::Regex::builder(pattern).syntax(syntax).build();
+            re.unwrap().find(text).map(|m| m.range())
+        };
+        assert_eq!(find("%d+", "ab 12"), Some(3..5));
+        assert_eq!(find("[%w]+%.", "x ab."), Some(2..5));
+        assert_eq!(find("(a)%1", "aa"), Some(0..2));
+        assert_eq!(find("%%", "100%"), Some(3..4));
+        assert_eq!(find(r"\d", r"1 \d"), Some(2..4));
+    }
+
+    #[test]
+    fn syntax_builder_toggles_flags() {
+        let s = SyntaxBuilder::new(&OnigSyntaxOniguruma)
+            .backrefs(false)
+            .callouts(false)
+            .enable("ONIG_SYN_OP2_ESC_CAPITAL_Q_QUOTE")
+            .disable("ONIG_SYN_OP_VBAR_ALT")
+            .build()
+            .unwrap();
+        let f = s.features();
+        assert_eq!(f.get("ONIG_SYN_OP_DECIMAL_BACKREF"), Some(false));
+        assert_eq!(f.get("ONIG_SYN_OP2_ESC_K_NAMED_BACKREF"), Some(false));
+        assert_eq!(f.get("ONIG_SYN_OP2_ASTERISK_CALLOUT_NAME"), Some(false));
+        assert_eq!(f.get("ONIG_SYN_OP2_ESC_CAPITAL_Q_QUOTE"), Some(true));
+        assert_eq!(f.get("ONIG_SYN_OP_VBAR_ALT"), Some(false));
+        assert_eq!(
+            f.get("ONIG_SYN_OP2_QMARK_LT_NAMED_GROUP"),
+            OnigSyntaxOniguruma
+                .features()
+                .get("ONIG_SYN_OP2_QMARK_LT_NAMED_GROUP")
+        );
+        assert_eq!(s.options, OnigSyntaxOniguruma.options);
+    }
+
+    #[test]
+    fn syntax_builder_rejects_bad_input() {
+        let base = &OnigSyntaxOniguruma;
+        let unknown = SyntaxBuilder::new(base).enable("ONIG_SYN_NO_SUCH_FLAG");
+        assert!(unknown.build().is_err());
+        let undefined_bit = SyntaxBuilder::new(base).set(Op2, 1 << 18, true);
+        assert!(undefined_bit.build().is_err());
+        let bad_meta = SyntaxBuilder::new(base).meta_char(99, '#' as u32);
+        assert!(bad_meta.build().is_err());
+
+        // The parser could not tell the escape from `.`
+        let clash = SyntaxBuilder::new(base).meta_char(ONIG_META_CHAR_ANYCHAR, '\\' as u32);
+        assert!(clash.build().is_err());
+        let s = SyntaxBuilder::new(base)
+            .meta_char(ONIG_META_CHAR_ESCAPE, '!' as u32)
+            .build()
+            .unwrap();
+        assert_eq!(s.meta_char_table.esc, '!' as u32);
+        assert_ne!(s.op & ONIG_SYN_OP_VARIABLE_META_CHARACTERS, 0);
+    }
+}
@@ -6,35 +6,64 @@
 // Scanner API design and test cases derived from vscode-oniguruma
 // (MIT License, Copyright (c) Microsoft Corporation).
 
+use alloc::sync::Arc;
+use core::ops::Range;
+use core::sync::atomic::Ordering;
+
 use smallvec::SmallVec;
 
+use crate::api::{check_haystack_len, MemoryBreakdown, Position};
 use crate::encodings::utf8::ONIG_ENCODING_UTF8;
 use crate::error::RegexError;
 use crate::oniguruma::*;
-use crate::regcomp::onig_new;
-use crate::regexec::{onig_search_with_msa, MatchArg};
-use crate::regint::RegexType;
-use crate::regset::{onig_regset_new, onig_regset_search, OnigRegSet, OnigRegSetLead};
+use crate::regcomp::{onig_new, regex_memory_usage};
+use crate::regexec::{onig_search_with_state, ExecState};
+use crate::regint::CompiledPattern;
+use crate::regset::{
+    onig_regset_new_shared, onig_regset_search_with_state, regset_state_memory_usage, OnigRegSet,
+    OnigRegSetLead,
+};
 use crate::regsyntax::*;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+use crate::sys::AtomicU64;
 
 /// Result of a capture group match.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CaptureIndex {
     /// Byte offset of the start of the capture.
-    pub start: usize,
+    pub start: Position,
     /// Byte offset of the end of the capture.
-    pub end: usize,
+    pub end: Position,
     /// Length of the capture in bytes (`end - start`).
     pub length: usize,
 }
 
+/// Capture group names of a pattern, indexed by group number.
+pub type CaptureNames = Arc<[Option<Box<str>>]>;
+
 /// Result of a scanner match.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScannerMatch {
     /// Index of the pattern that matched (0-based).
     pub index: usize,
     /// Capture group information. Index 0 is the full match.
     pub capture_indices: SmallVec<[CaptureIndex; 8]>,
+    /// Not in vscode-oniguruma: the name of each capture group (`None` for
+    /// unnamed ones), shared by all matches of the pattern. Only set when
+    /// `ScannerConfig::capture_names` is enabled.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub capture_names: Option<CaptureNames>,
+}
+
+impl ScannerMatch {
+    /// Name of capture group `group`, if names were requested and the group
+    /// is named.
+    pub fn capture_name(&self, group: usize) -> Option<&str> {
+        self.capture_names.as_ref()?.get(group)?.as_deref()
+    }
 }
 
 /// Options for `Scanner::find_next_match`, matching vscode-oniguruma's `FindOption`.
@@ -43,8 +72,13 @@ pub struct ScannerFindOptions(u32);
 
 impl ScannerFindOptions {
     pub const NONE: Self = Self(0);
+    /// The text does not start at the beginning of the document: `\A`
+    /// does not match at offset 0.
     pub const NOT_BEGIN_STRING: Self = Self(1);
+    /// The text does not run to the end of the document: `\z` and `\Z`
+    /// do not match at its end.
     pub const NOT_END_STRING: Self = Self(2);
+    /// `\G` does not match at the start position.
     pub const NOT_BEGIN_POSITION: Self = Self(4);
 
     /// Create from a raw bitmask.
@@ -52,6 +86,11 @@ impl ScannerFindOptions {
         Self(bits)
     }
 
+    /// The raw bitmask.
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
     fn to_onig_options(self) -> OnigOptionType {
         let mut opts = ONIG_OPTION_NONE;
         if self.0 & 1 != 0 {
@@ -67,6 +106,20 @@ impl ScannerFindOptions {
     }
 }
 
+impl core::ops::BitOr for ScannerFindOptions {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl core::ops::BitOrAssign for ScannerFindOptions {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
 /// Regex syntax variant, matching vscode-oniguruma's `Syntax` enum.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScannerSyntax {
@@ -121,6 +174,26 @@ impl Default for ScannerSyntax {
     }
 }
 
+/// How a `Scanner` treats a zero-length match at the end of the text.
+///
+/// A tokenizer that resumes at the end of the last match never advances
+/// past such a match, and loops forever unless it special-cases the end.
+/// `ReportOnce` moves that check into the scanner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScannerEmptyMatchAtEnd {
+    /// Report it on every call (vscode-oniguruma behavior).
+    #[default]
+    Repeat,
+    /// Report it once. Further calls on the same text that start at its end
+    /// return `None`, until a call on that text finds anything else.
+    ///
+    /// The text is identified by the `str_id` of the `*_with_id` methods, or
+    /// by the `OnigString` for the UTF-16 methods. Plain `&str` calls carry
+    /// no identity (two empty lines are indistinguishable), so they always
+    /// report the match.
+    ReportOnce,
+}
+
 /// Configuration for creating a `Scanner`, matching vscode-oniguruma's `IOnigScannerConfig`.
 #[derive(Debug, Clone)]
 pub struct ScannerConfig {
@@ -128,6 +201,11 @@ pub struct ScannerConfig {
     pub options: OnigOptionType,
     /// Regex syntax variant to use.
     pub syntax: ScannerSyntax,
+    /// Not in vscode-oniguruma: how zero-length matches at the end of the
+    /// text are reported.
+    pub empty_match_at_end: ScannerEmptyMatchAtEnd,
+    /// Not in vscode-oniguruma: fill `ScannerMatch::capture_names`.
+    pub capture_names: bool,
 }
 
 impl Default for ScannerConfig {
@@ -135,10 +213,89 @@ impl Default for ScannerConfig {
         ScannerConfig {
             options: ONIG_OPTION_NONE,
             syntax: ScannerSyntax::default(),
+            empty_match_at_end: ScannerEmptyMatchAtEnd::default(),
+            capture_names: false,
         }
     }
 }
 
+/// Not in vscode-oniguruma: a position in a slice of lines, and where `\G`
+/// may match, for [`Scanner::find_in_lines`].
+///
+/// Oniguruma matches `\G` at the start of every search unless
+/// `ONIG_OPTION_NOT_BEGIN_POSITION` is passed, so a tokenizer has to work
+/// out per call whether the search starts where the previous match ended.
+/// The cursor does this itself: `\G` matches only at the end of the last
+/// match, and a match that ends a line moves the anchor to the start of the
+/// next one (as a TextMate begin rule that captured the line end does).
+///
+/// # Example
+///
+/// ```
+/// use ferroni::scanner::{LineCursor, Scanner};
+///
+/// let mut scanner = Scanner::new(&[r"\G\w", "-"]).unwrap();
+/// let lines = ["ab-c", "d", " e"];
+/// let mut cursor = LineCursor::new();
+/// let mut found = Vec::new();
+/// while let Some((line, m)) = scanner.find_in_lines(&lines, &mut cursor) {
+///     found.push((line, m.capture_indices[0].start));
+/// }
+/// // `e` does not follow a match, so `\G` cannot match there.
+/// assert_eq!(found, [(0, 0), (0, 1), (0, 2), (0, 3), (1, 0)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineCursor {
+    line: usize,
+    pos: usize,
+    /// Line and byte offset where `\G` may match.
+    anchor: Option<(usize, usize)>,
+}
+
+impl LineCursor {
+    /// A cursor at the start of the first line, where `\G` matches.
+    pub fn new() -> Self {
+        LineCursor {
+            line: 0,
+            pos: 0,
+            anchor: Some((0, 0)),
+        }
+    }
+
+    /// The line the next search starts in.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The byte offset in [`line`](Self::line) the next search starts at.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Line and byte offset where `\G` may match, if anywhere.
+    pub fn anchor(&self) -> Option<(usize, usize)> {
+        self.anchor
+    }
+
+    /// Move the next search to byte offset `pos` of `line`, keeping the
+    /// anchor where it is.
+    pub fn seek(&mut self, line: usize, pos: usize) {
+        self.line = line;
+        self.pos = pos;
+    }
+
+    /// Let `\G` match at `anchor` instead (or nowhere, for `None`).
+    pub fn set_anchor(&mut self, anchor: Option<(usize, usize)>) {
+        self.anchor = anchor;
+    }
+}
+
+impl Default for LineCursor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// A string wrapper that maintains UTF-16 ↔ UTF-8 offset mappings.
 ///
 /// JavaScript strings are UTF-16 encoded, while Ferroni operates on UTF-8.
@@ -157,6 +314,9 @@ impl Default for ScannerConfig {
 /// ```
 pub struct OnigString {
     content: String,
+    /// Identity for `ScannerEmptyMatchAtEnd::ReportOnce`, distinct from any
+    /// caller `str_id` (top bit set).
+    id: u64,
     /// Maps UTF-16 code unit index → UTF-8 byte offset. Length = utf16_len + 1.
     utf16_to_utf8: Vec<usize>,
     /// Maps UTF-8 byte offset → UTF-16 code unit index. Length = utf8_len + 1.
@@ -198,8 +358,10 @@ impl OnigString {
         utf16_to_utf8.push(utf8_pos);
         utf8_to_utf16[utf8_pos] = utf16_len;
 
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
         OnigString {
             content: content.to_string(),
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed) | (1 << 63),
             utf16_to_utf8,
             utf8_to_utf16,
         }
@@ -257,6 +419,26 @@ impl CacheEntry {
             last_region: None,
         }
     }
+
+    /// An empty entry for the same pattern.
+    fn fresh(&self) -> Self {
+        CacheEntry {
+            has_g_anchor: self.has_g_anchor,
+            ..CacheEntry::new("")
+        }
+    }
+}
+
+/// The last search of a text with an id, for [`Scanner::rescan_from`].
+#[derive(Debug, Clone, Copy)]
+struct LastSearch {
+    /// Text id and find options.
+    key: (u64, u32),
+    start: usize,
+    /// Winning pattern and the start of its match.
+    best: Option<(usize, usize)>,
+    /// Whether the winner's region is in the RegSet or the cache entry.
+    via_regset: bool,
 }
 
 /// Threshold for switching between RegSet and per-regex search.
@@ -276,10 +458,47 @@ const MAX_REGSET_MATCH_INPUT_LEN: usize = 1000;
 /// assert_eq!(m.capture_indices[0].start, 0);
 /// assert_eq!(m.capture_indices[0].end, 5);
 /// ```
+///
+/// The `find_next_match*` methods panic if the text is longer than
+/// [`MAX_HAYSTACK_LEN`](crate::api::MAX_HAYSTACK_LEN).
+///
+/// Searching needs `&mut self` for the caches and VM state, but the
+/// compiled patterns are read-only and shared: a clone is cheap and shares
+/// them, so each worker thread can scan with its own clone of one scanner
+/// instead of compiling the grammar again.
+///
+/// ```
+/// use ferroni::scanner::{Scanner, ScannerFindOptions};
+///
+/// let scanner = Scanner::new(&["\\d+", "[a-z]+"]).unwrap();
+/// let workers: Vec<_> = (0..4)
+///     .map(|_| {
+///         let mut scanner = scanner.clone();
+///         std::thread::spawn(move || {
+///             scanner.find_next_match("abc", 0, ScannerFindOptions::NONE).map(|m| m.index)
+///         })
+///     })
+///     .collect();
+/// for worker in workers {
+///     assert_eq!(worker.join().unwrap(), Some(1));
+/// }
+/// ```
 pub struct Scanner {
-    regexes: Vec<Box<RegexType>>,
+    // Not in vscode-oniguruma: shared by clones and by `regset`.
+    regexes: Vec<Arc<CompiledPattern>>,
     caches: Vec<CacheEntry>,
     regset: Box<OnigRegSet>,
+    /// Search state shared by the per-regex searches and the RegSet.
+    state: ExecState,
+    empty_match_at_end: ScannerEmptyMatchAtEnd,
+    /// Text id and find options of the last call that reported a
+    /// zero-length match at the end of the text (for `ReportOnce`).
+    empty_end_reported: Option<(u64, u32)>,
+    /// Capture group names per pattern, if `ScannerConfig::capture_names`.
+    capture_names: Option<Vec<CaptureNames>>,
+    /// Not in vscode-oniguruma: the last search with a text id, while the
+    /// winner's region is still where it left it.
+    last_search: Option<LastSearch>,
 }
 
 impl Scanner {
@@ -300,6 +519,7 @@ impl Scanner {
     /// let config = ScannerConfig {
     ///     options: OnigOptionType::IGNORECASE,
     ///     syntax: ScannerSyntax::Oniguruma,
+    ///     ..Default::default()
     /// };
     /// let mut scanner = Scanner::with_config(&["hello"], &config).unwrap();
     /// let m = scanner.find_next_match("HELLO", 0, ScannerFindOptions::NONE);
@@ -311,34 +531,79 @@ impl Scanner {
 
         let mut regexes = Vec::with_capacity(patterns.len());
         let mut caches = Vec::with_capacity(patterns.len());
-        let mut regset_regs = Vec::with_capacity(patterns.len());
 
         for pattern in patterns {
-            // Compile once for the per-regex search path.
+            // Compiled once, for both the per-regex search path and the RegSet.
             let reg = onig_new(pattern.as_bytes(), options, &ONIG_ENCODING_UTF8, syntax)?;
-            regexes.push(Box::new(reg));
-
-            // Compile again for the RegSet (it takes ownership).
-            let reg2 = onig_new(pattern.as_bytes(), options, &ONIG_ENCODING_UTF8, syntax)?;
-            regset_regs.push(Box::new(reg2));
-
+            regexes.push(Arc::new(reg));
             caches.push(CacheEntry::new(pattern));
         }
 
-        let (regset, r) = onig_regset_new(regset_regs);
+        let (regset, r) = onig_regset_new_shared(regexes.clone());
         if r != ONIG_NORMAL {
             return Err(r.into());
         }
+        let capture_names = config
+            .capture_names
+            .then(|| regexes.iter().map(|reg| group_names(reg)).collect());
 
         Ok(Scanner {
             regexes,
             caches,
             regset: regset.unwrap(),
+            state: ExecState::new(),
+            empty_match_at_end: config.empty_match_at_end,
+            empty_end_reported: None,
+            capture_names,
+            last_search: None,
         })
     }
 
+    /// Pre-allocate search state for lines up to `max_line_len` bytes.
+    ///
+    /// Sizes every per-regex capture region, reserves the shared VM stack
+    /// for what the compiled programs can push over such a line, and runs each regex once over an empty string so that the first real
+    /// search (e.g. the first keystroke in an editor) doesn't pay these costs.
+    /// Optimization info (exact strings, char maps) is already computed at
+    /// compile time. Calling this is optional and never changes results.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ferroni::scanner::{Scanner, ScannerFindOptions};
+    ///
+    /// let mut scanner = Scanner::new(&["\\d+", "[a-z]+"]).unwrap();
+    /// scanner.warm_up(4096);
+    /// let m = scanner.find_next_match("abc", 0, ScannerFindOptions::NONE).unwrap();
+    /// assert_eq!(m.index, 1);
+    /// ```
+    pub fn warm_up(&mut self, max_line_len: usize) {
+        self.last_search = None;
+        for (reg, cache) in self.regexes.iter().zip(self.caches.iter_mut()) {
+            let region = cache.last_region.get_or_insert_with(OnigRegion::new);
+            region.resize(reg.num_mem + 1);
+            region.clear();
+        }
+
+        for reg in &self.regexes {
+            self.state.reserve(reg, max_line_len);
+        }
+
+        // Touch each regex's search path once. The cache entries are left
+        // untouched, so this probe can never produce a stale cache hit.
+        for reg in &self.regexes {
+            let region = Some(OnigRegion::new());
+            onig_search_with_state(reg, &mut self.state, b"", 0, 0, 0, region, ONIG_OPTION_NONE);
+        }
+    }
+
     /// Find the next match starting at `start_position` (byte offset).
     ///
+    /// As in vscode-oniguruma, the whole of `text` is searched from
+    /// `start_position`: look-behind, `\b` and `^` see the text before it,
+    /// and capture offsets are into `text`. Advance through a line by
+    /// passing a later `start_position`, not by slicing the line.
+    ///
     /// For short strings (<1000 bytes), uses the RegSet fast path.
     /// For longer strings, uses per-regex search (no caching without a string ID).
     pub fn find_next_match(
@@ -347,7 +612,7 @@ impl Scanner {
         start_position: usize,
         options: ScannerFindOptions,
     ) -> Option<ScannerMatch> {
-        self.find_next_match_inner(text, 0, start_position, options, false)
+        self.find_next_match_inner(text, None, start_position, options, false)
     }
 
     /// Find the next match with a string ID for caching.
@@ -361,7 +626,100 @@ impl Scanner {
         start_position: usize,
         options: ScannerFindOptions,
     ) -> Option<ScannerMatch> {
-        self.find_next_match_inner(text, str_id, start_position, options, true)
+        self.find_next_match_inner(text, Some(str_id), start_position, options, true)
+    }
+
+    /// Not in vscode-oniguruma: search `text` again from a later
+    /// `start_position`, as in a `while` rule that keeps scanning the same
+    /// line. Pass the `str_id` and options of the previous
+    /// [`find_next_match_with_id`](Self::find_next_match_with_id) or
+    /// `rescan_from` call on `text`.
+    ///
+    /// If the previous search started at or before `start_position` and
+    /// its match starts at or after it, that match is returned again
+    /// without running any pattern; if it found nothing, neither can this
+    /// one. Otherwise this searches like `find_next_match_with_id`, reusing
+    /// the capture regions. Patterns with `\G` always search, since where
+    /// they match depends on the start.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ferroni::scanner::{Scanner, ScannerFindOptions};
+    ///
+    /// let mut scanner = Scanner::new(&["end", "[a-z]+"]).unwrap();
+    /// let line = "  -- end";
+    /// let opts = ScannerFindOptions::NONE;
+    /// let m = scanner.find_next_match_with_id(line, 7, 0, opts).unwrap();
+    /// assert_eq!(m.capture_indices[0].start, 5);
+    /// // Still ahead of position 3: answered from the last search
+    /// assert_eq!(scanner.rescan_from(line, 7, 3, opts), Some(m));
+    /// assert_eq!(scanner.rescan_from(line, 7, 6, opts).unwrap().index, 1);
+    /// ```
+    pub fn rescan_from(
+        &mut self,
+        text: &str,
+        str_id: u64,
+        start_position: usize,
+        options: ScannerFindOptions,
+    ) -> Option<ScannerMatch> {
+        let key = (str_id, options.0);
+        let hit = match self.last_search {
+            Some(last)
+                if last.key == key
+                    && last.start <= start_position
+                    && !self.caches.iter().any(|c| c.has_g_anchor) =>
+            {
+                match last.best {
+                    None => return None,
+                    Some((index, pos)) if pos >= start_position => Some((index, last.via_regset)),
+                    Some(_) => None,
+                }
+            }
+            _ => None,
+        };
+        let Some((index, via_regset)) = hit else {
+            return self.find_next_match_inner(text, Some(str_id), start_position, options, true);
+        };
+        // As in find_next_region: an empty match at the end is not
+        // reported twice from the end
+        if self.empty_match_at_end == ScannerEmptyMatchAtEnd::ReportOnce
+            && start_position == text.len()
+            && self.empty_end_reported == Some(key)
+        {
+            return None;
+        }
+        let m = build_scanner_match(index, self.winning_region(index, via_regset)?);
+        Some(self.with_capture_names(m))
+    }
+
+    /// Not in vscode-oniguruma: like [`find_next_match`](Self::find_next_match),
+    /// but returns the winning pattern index and a borrow of its capture
+    /// region instead of copying the spans into a `ScannerMatch`. Unlike
+    /// `capture_indices`, groups that did not participate read as `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ferroni::scanner::{Scanner, ScannerFindOptions};
+    ///
+    /// let mut scanner = Scanner::new(&["\\d+", "([a-z])([0-9])?"]).unwrap();
+    /// let text = "--x";
+    /// let (index, region) = scanner
+    ///     .find_next_match_region(text, 0, ScannerFindOptions::NONE)
+    ///     .unwrap();
+    /// assert_eq!(index, 1);
+    /// assert_eq!(region.get(1), Some(2..3));
+    /// assert_eq!(region.get(2), None);
+    /// assert_eq!(region.slice(0, text.as_bytes()), Some(&b"x"[..]));
+    /// ```
+    pub fn find_next_match_region(
+        &mut self,
+        text: &str,
+        start_position: usize,
+        options: ScannerFindOptions,
+    ) -> Option<(usize, &OnigRegion)> {
+        self.find_next_region(text, None, start_position, options, false)
     }
 
     /// Find the next match using UTF-16 positions (for vscode-textmate/Shiki compatibility).
@@ -388,7 +746,13 @@ impl Scanner {
         options: ScannerFindOptions,
     ) -> Option<ScannerMatch> {
         let utf8_start = string.utf16_offset_to_utf8(start_position);
-        let m = self.find_next_match_inner(string.content(), 0, utf8_start, options, false)?;
+        let m = self.find_next_match_inner(
+            string.content(),
+            Some(string.id),
+            utf8_start,
+            options,
+            false,
+        )?;
         Some(convert_match_to_utf16(string, m))
     }
 
@@ -401,40 +765,253 @@ impl Scanner {
         options: ScannerFindOptions,
     ) -> Option<ScannerMatch> {
         let utf8_start = string.utf16_offset_to_utf8(start_position);
-        let m = self.find_next_match_inner(string.content(), str_id, utf8_start, options, true)?;
+        let m =
+            self.find_next_match_inner(string.content(), Some(str_id), utf8_start, options, true)?;
         Some(convert_match_to_utf16(string, m))
     }
 
+    /// Find the next match and write it into `buf` as a flat `i32` array,
+    /// avoiding a `ScannerMatch` allocation per call (e.g. across an FFI
+    /// boundary).
+    ///
+    /// The layout is `[pattern_index, group_count, start0, end0, start1, end1, ...]`
+    /// with byte offsets; groups that did not participate are `-1, -1`.
+    /// Returns the number of `i32` values the result needs, or `None` if
+    /// nothing matched. If `buf` is shorter than that, only the header is
+    /// written (when it fits); [`max_result_len`](Self::max_result_len) gives
+    /// a size that always suffices.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ferroni::scanner::{Scanner, ScannerFindOptions};
+    ///
+    /// let mut scanner = Scanner::new(&["\\d+", "([a-z])([0-9])?"]).unwrap();
+    /// let mut buf = vec![0; scanner.max_result_len()];
+    /// let n = scanner.find_next_match_into("--x", 0, ScannerFindOptions::NONE, &mut buf);
+    /// assert_eq!(n, Some(8));
+    /// assert_eq!(buf[..8], [1, 3, 2, 3, 2, 3, -1, -1]);
+    /// ```
+    pub fn find_next_match_into(
+        &mut self,
+        text: &str,
+        start_position: usize,
+        options: ScannerFindOptions,
+        buf: &mut [i32],
+    ) -> Option<usize> {
+        let (index, region) = self.find_next_region(text, None, start_position, options, false)?;
+        Some(write_flat_match(index, region, buf, |pos| pos))
+    }
+
+    /// Like [`find_next_match_into`](Self::find_next_match_into), with UTF-16
+    /// positions for both `start_position` and the written offsets.
+    pub fn find_next_match_utf16_into(
+        &mut self,
+        string: &OnigString,
+        start_position: usize,
+        options: ScannerFindOptions,
+        buf: &mut [i32],
+    ) -> Option<usize> {
+        let utf8_start = string.utf16_offset_to_utf8(start_position);
+        let (index, region) = self.find_next_region(
+            string.content(),
+            Some(string.id),
+            utf8_start,
+            options,
+            false,
+        )?;
+        Some(write_flat_match(index, region, buf, |pos| {
+            string.utf8_offset_to_utf16(pos)
+        }))
+    }
+
+    /// Estimate the bytes this scanner holds: its compiled patterns, and
+    /// its RegSet, caches and VM stack under `search_state`.
+    ///
+    /// Clones share the compiled patterns; each clone reports them, so for a
+    /// set of clones count the patterns once and `search_state` per clone.
+    ///
+    /// ```
+    /// use ferroni::scanner::{Scanner, ScannerFindOptions};
+    ///
+    /// let mut scanner = Scanner::new(&["\\d+", "[a-z]+"]).unwrap();
+    /// let before = scanner.memory_usage();
+    /// scanner.find_next_match("abc 42", 0, ScannerFindOptions::NONE);
+    /// let after = scanner.memory_usage();
+    /// assert_eq!(after.bytecode, before.bytecode);
+    /// assert!(after.search_state > before.search_state);
+    /// ```
+    pub fn memory_usage(&self) -> MemoryBreakdown {
+        use core::mem::size_of;
+
+        let mut m: MemoryBreakdown = self.regexes.iter().map(|r| regex_memory_usage(r)).sum();
+        m += regset_state_memory_usage(&self.regset);
+        m.search_state += self.caches.capacity() * size_of::<CacheEntry>()
+            + self
+                .caches
+                .iter()
+                .filter_map(|c| c.last_region.as_ref())
+                .map(OnigRegion::spans_size)
+                .sum::<usize>()
+            + self.state.heap_size();
+        m.other +=
+            size_of::<Scanner>() + self.regexes.capacity() * size_of::<Arc<CompiledPattern>>();
+        if let Some(names) = &self.capture_names {
+            m.names += names
+                .iter()
+                .map(|n| {
+                    core::mem::size_of_val(&**n)
+                        + n.iter().flatten().map(|s| s.len()).sum::<usize>()
+                })
+                .sum::<usize>();
+        }
+        m
+    }
+
+    /// Buffer length (in `i32` values) that fits any result of
+    /// [`find_next_match_into`](Self::find_next_match_into).
+    pub fn max_result_len(&self) -> usize {
+        let groups = self
+            .regexes
+            .iter()
+            .map(|r| r.num_mem + 1)
+            .max()
+            .unwrap_or(0);
+        2 + 2 * groups as usize
+    }
+
+    /// Not in vscode-oniguruma: find the next match in `lines`, starting at
+    /// `cursor` and moving on to later lines until something matches.
+    ///
+    /// Returns the line index and the match, with byte offsets into that
+    /// line. `ONIG_OPTION_NOT_BEGIN_POSITION` is passed for every search that
+    /// does not start at the cursor's anchor; afterwards the cursor sits at
+    /// the end of the match, which becomes the new anchor. After an empty
+    /// match the next search starts one character later.
+    pub fn find_in_lines(
+        &mut self,
+        lines: &[&str],
+        cursor: &mut LineCursor,
+    ) -> Option<(usize, ScannerMatch)> {
+        while let Some(text) = lines.get(cursor.line) {
+            let (line, start) = (cursor.line, cursor.pos);
+            if start <= text.len() {
+                let options = if cursor.anchor == Some((line, start)) {
+                    ScannerFindOptions::NONE
+                } else {
+                    ScannerFindOptions::NOT_BEGIN_POSITION
+                };
+                if let Some(m) = self.find_next_match_inner(text, None, start, options, false) {
+                    let CaptureIndex { start, end, .. } = m.capture_indices[0];
+                    cursor.anchor = Some((line, end));
+                    cursor.pos = if start == end {
+                        end + text[end..].chars().next().map_or(1, char::len_utf8)
+                    } else {
+                        end
+                    };
+                    return Some((line, m));
+                }
+            }
+            if cursor.anchor == Some((line, text.len())) {
+                cursor.anchor = Some((line + 1, 0));
+            }
+            cursor.seek(line + 1, 0);
+        }
+        None
+    }
+
     fn find_next_match_inner(
         &mut self,
         text: &str,
-        str_id: u64,
+        text_id: Option<u64>,
         start_position: usize,
         options: ScannerFindOptions,
         use_cache: bool,
     ) -> Option<ScannerMatch> {
+        let (index, region) =
+            self.find_next_region(text, text_id, start_position, options, use_cache)?;
+        let m = build_scanner_match(index, region);
+        Some(self.with_capture_names(m))
+    }
+
+    /// Add the capture group names of the pattern `m` is from, if enabled.
+    fn with_capture_names(&self, mut m: ScannerMatch) -> ScannerMatch {
+        m.capture_names = self
+            .capture_names
+            .as_ref()
+            .map(|names| names[m.index].clone());
+        m
+    }
+
+    /// The region the winning pattern `index` left its match in.
+    fn winning_region(&self, index: usize, via_regset: bool) -> Option<&OnigRegion> {
+        if via_regset {
+            crate::regset::onig_regset_get_region(&self.regset, index)
+        } else {
+            self.caches[index].last_region.as_ref()
+        }
+    }
+
+    /// Run the search and return the winning pattern index and its region.
+    ///
+    /// `text_id` identifies the text for `ScannerEmptyMatchAtEnd::ReportOnce`
+    /// and, when `use_cache` is set, keys the per-regex cache.
+    fn find_next_region(
+        &mut self,
+        text: &str,
+        text_id: Option<u64>,
+        start_position: usize,
+        options: ScannerFindOptions,
+        use_cache: bool,
+    ) -> Option<(usize, &OnigRegion)> {
         let str_data = text.as_bytes();
+        check_haystack_len(str_data);
         let end = str_data.len();
 
         if start_position > end {
             return None;
         }
 
+        let once = self.empty_match_at_end == ScannerEmptyMatchAtEnd::ReportOnce;
+        let key = text_id.map(|id| (id, options.0));
+        if once && start_position == end && key.is_some() && self.empty_end_reported == key {
+            return None;
+        }
+
         let onig_opts = options.to_onig_options();
 
-        if end < MAX_REGSET_MATCH_INPUT_LEN {
+        let use_regset = end < MAX_REGSET_MATCH_INPUT_LEN;
+        let found = if use_regset {
             self.search_regset(str_data, end, start_position, onig_opts)
         } else {
             self.search_per_regex(
                 str_data,
                 end,
                 start_position,
-                str_id,
+                text_id.unwrap_or(0),
                 options.0,
                 onig_opts,
                 use_cache,
             )
+        };
+
+        let best = found.and_then(|index| {
+            let region = self.winning_region(index, use_regset)?;
+            Some((index, region.beg(0) as usize))
+        });
+        self.last_search = key.map(|key| LastSearch {
+            key,
+            start: start_position,
+            best,
+            via_regset: use_regset,
+        });
+        let (index, _) = best?;
+        if once && key.is_some() {
+            let region = self.winning_region(index, use_regset)?;
+            let empty_at_end = region.beg(0) == end as i32 && region.end(0) == end as i32;
+            self.empty_end_reported = if empty_at_end { key } else { None };
         }
+        Some((index, self.winning_region(index, use_regset)?))
     }
 
     /// RegSet fast path for short strings.
@@ -444,9 +1021,10 @@ impl Scanner {
         end: usize,
         start: usize,
         option: OnigOptionType,
-    ) -> Option<ScannerMatch> {
-        let (idx, _pos) = onig_regset_search(
+    ) -> Option<usize> {
+        let (idx, _pos) = onig_regset_search_with_state(
             &mut self.regset,
+            &mut self.state,
             str_data,
             end,
             start,
@@ -458,18 +1036,15 @@ impl Scanner {
         if idx < 0 {
             return None;
         }
-
-        let regex_idx = idx as usize;
-        let region = crate::regset::onig_regset_get_region(&self.regset, regex_idx)?;
-        Some(build_scanner_match(regex_idx, region))
+        Some(idx as usize)
     }
 
     /// Per-regex search with caching for long strings.
     ///
     /// Regions are reused from cache entries to avoid per-call allocation.
-    /// A single MatchArg is reused across all regex iterations to avoid
-    /// repeated heap allocations for the VM stack.
-    /// The best match is read directly from the cache at the end (no cloning).
+    /// One ExecState is reused across all regex iterations and calls to
+    /// avoid repeated heap allocations for the VM stack.
+    /// Returns the index of the winning regex; its region stays in the cache.
     fn search_per_regex(
         &mut self,
         str_data: &[u8],
@@ -479,13 +1054,10 @@ impl Scanner {
         options_raw: u32,
         onig_opts: OnigOptionType,
         use_cache: bool,
-    ) -> Option<ScannerMatch> {
+    ) -> Option<usize> {
         let mut best_index: Option<usize> = None;
         let mut best_pos: usize = usize::MAX;
 
-        // Lazy MatchArg — only allocated on first cache miss (warm path: zero alloc)
-        let mut msa: Option<MatchArg> = None;
-
         for i in 0..self.regexes.len() {
             let cache = &self.caches[i];
 
@@ -518,13 +1090,16 @@ impl Scanner {
                 .take()
                 .unwrap_or_else(OnigRegion::new);
 
-            // Create MatchArg on first miss, reuse on subsequent misses
-            let msa =
-                msa.get_or_insert_with(|| MatchArg::new(&self.regexes[i], onig_opts, None, start));
-            msa.reset_for_search(&self.regexes[i], onig_opts, Some(region), start);
-
-            let (r, returned_region) =
-                onig_search_with_msa(&self.regexes[i], str_data, end, start, end, msa);
+            let (r, returned_region) = onig_search_with_state(
+                &self.regexes[i],
+                &mut self.state,
+                str_data,
+                end,
+                start,
+                end,
+                Some(region),
+                onig_opts,
+            );
 
             // Put region back in cache (no clone needed)
             let cache = &mut self.caches[i];
@@ -551,9 +1126,27 @@ impl Scanner {
             }
         }
 
-        let idx = best_index?;
-        let region = self.caches[idx].last_region.as_ref()?;
-        Some(build_scanner_match(idx, region))
+        best_index
+    }
+}
+
+impl Clone for Scanner {
+    /// A scanner over the same compiled patterns with its own search state:
+    /// empty caches, no record of a reported empty match at the end, and its
+    /// own RegSet regions and VM stack.
+    fn clone(&self) -> Self {
+        let (regset, _) = onig_regset_new_shared(self.regexes.clone());
+        Scanner {
+            regexes: self.regexes.clone(),
+            caches: self.caches.iter().map(CacheEntry::fresh).collect(),
+            // The patterns were accepted by with_config already
+            regset: regset.expect("scanner patterns form a valid RegSet"),
+            state: ExecState::new(),
+            empty_match_at_end: self.empty_match_at_end,
+            empty_end_reported: None,
+            capture_names: self.capture_names.clone(),
+            last_search: None,
+        }
     }
 }
 
@@ -562,12 +1155,8 @@ fn build_scanner_match(index: usize, region: &OnigRegion) -> ScannerMatch {
     let num_regs = region.num_regs as usize;
     let mut capture_indices = SmallVec::with_capacity(num_regs);
 
-    for i in 0..num_regs {
-        let beg = region.beg[i];
-        let end = region.end[i];
-        if beg >= 0 && end >= 0 {
-            let start = beg as usize;
-            let end = end as usize;
+    for r in region.iter() {
+        if let Some(Range { start, end }) = r {
             capture_indices.push(CaptureIndex {
                 start,
                 end,
@@ -586,7 +1175,51 @@ fn build_scanner_match(index: usize, region: &OnigRegion) -> ScannerMatch {
     ScannerMatch {
         index,
         capture_indices,
+        capture_names: None,
+    }
+}
+
+/// Name of each capture group of `reg`, indexed by group number.
+fn group_names(reg: &CompiledPattern) -> CaptureNames {
+    let mut names = vec![None; reg.num_mem as usize + 1];
+    if let Some(ref nt) = reg.name_table {
+        for entry in nt.entries.values() {
+            let name: Box<str> = String::from_utf8_lossy(&entry.name).into();
+            for &group in &entry.back_refs {
+                names[group as usize] = Some(name.clone());
+            }
+        }
+    }
+    names.into()
+}
+
+/// Write a match as `[index, group_count, start0, end0, ...]` into `buf`,
+/// mapping each byte offset through `map`. Returns the full result length.
+fn write_flat_match(
+    index: usize,
+    region: &OnigRegion,
+    buf: &mut [i32],
+    map: impl Fn(usize) -> usize,
+) -> usize {
+    let num_regs = region.num_regs as usize;
+    let len = 2 + 2 * num_regs;
+    if buf.len() >= 2 {
+        buf[0] = index as i32;
+        buf[1] = num_regs as i32;
     }
+    if buf.len() < len {
+        return len;
+    }
+
+    for (i, r) in region.iter().enumerate() {
+        let span = match r {
+            Some(Range { start, end }) => (map(start) as i32, map(end) as i32),
+            None => (ONIG_REGION_NOTPOS, ONIG_REGION_NOTPOS),
+        };
+        buf[2 + 2 * i] = span.0;
+        buf[3 + 2 * i] = span.1;
+    }
+    len
 }
 
 /// Convert a `ScannerMatch` with UTF-8 byte offsets to UTF-16 code unit offsets.
@@ -606,6 +1239,7 @@ fn convert_match_to_utf16(string: &OnigString, m: ScannerMatch) -> ScannerMatch
                 }
             })
             .collect(),
+        capture_names: m.capture_names,
     }
 }
 
@@ -633,6 +1267,7 @@ mod tests {
                     end: 4,
                     length: 3
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -644,6 +1279,7 @@ mod tests {
                     end: 8,
                     length: 2
                 }],
+                capture_names: None,
             })
         );
     }
@@ -665,6 +1301,7 @@ mod tests {
                     end: 3,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -676,6 +1313,7 @@ mod tests {
                     end: 6,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -687,6 +1325,7 @@ mod tests {
                     end: 9,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -712,6 +1351,7 @@ mod tests {
                     end: 9,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
 
@@ -727,6 +1367,7 @@ mod tests {
                     end: 2,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
     }
@@ -750,6 +1391,7 @@ mod tests {
                     end: 7,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
         // From byte 5 (='b'): Y at byte 6
@@ -762,6 +1404,7 @@ mod tests {
                     end: 7,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
         // From byte 6 (='Y'): Y at byte 6
@@ -774,6 +1417,7 @@ mod tests {
                     end: 7,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
         // From byte 7 (='X'): X at byte 7
@@ -786,6 +1430,7 @@ mod tests {
                     end: 8,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
     }
@@ -806,6 +1451,7 @@ mod tests {
                     end: 14,
                     length: 14
                 }],
+                capture_names: None,
             })
         );
     }
@@ -827,6 +1473,7 @@ mod tests {
                     end: 1,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
         // Start beyond end: no match
@@ -854,6 +1501,7 @@ mod tests {
                     end: 9,
                     length: 4
                 }],
+                capture_names: None,
             })
         );
     }
@@ -890,6 +1538,7 @@ mod tests {
                     end: 5,
                     length: 5
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -912,6 +1561,7 @@ mod tests {
                     end: 15,
                     length: 5
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -934,6 +1584,7 @@ mod tests {
                     end: 9,
                     length: 4
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -960,6 +1611,7 @@ mod tests {
                     end: 4,
                     length: 4
                 }],
+                capture_names: None,
             })
         );
     }
@@ -989,6 +1641,7 @@ mod tests {
                         length: 15
                     },
                 ],
+                capture_names: None,
             })
         );
     }
@@ -1020,6 +1673,7 @@ mod tests {
                     end: 4,
                     length: 3
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -1031,6 +1685,7 @@ mod tests {
                     end: 8,
                     length: 2
                 }],
+                capture_names: None,
             })
         );
     }
@@ -1054,6 +1709,7 @@ mod tests {
                     end: 3,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -1065,6 +1721,7 @@ mod tests {
                     end: 6,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -1076,6 +1733,7 @@ mod tests {
                     end: 9,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -1099,6 +1757,7 @@ mod tests {
                     end: 7,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
 
@@ -1113,6 +1772,7 @@ mod tests {
                     end: 2,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
     }
@@ -1135,6 +1795,7 @@ mod tests {
                     end: 5,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -1146,6 +1807,7 @@ mod tests {
                     end: 5,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -1157,6 +1819,7 @@ mod tests {
                     end: 5,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -1168,6 +1831,7 @@ mod tests {
                     end: 5,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -1179,6 +1843,7 @@ mod tests {
                     end: 6,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
     }
@@ -1198,6 +1863,7 @@ mod tests {
                     end: 7,
                     length: 7
                 }],
+                capture_names: None,
             })
         );
     }
@@ -1216,6 +1882,7 @@ mod tests {
                     end: 1,
                     length: 1
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -1242,6 +1909,7 @@ mod tests {
                     end: 9,
                     length: 4
                 }],
+                capture_names: None,
             })
         );
     }
@@ -1277,6 +1945,7 @@ mod tests {
                     end: 5,
                     length: 5
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -1299,6 +1968,7 @@ mod tests {
                     end: 15,
                     length: 5
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -1321,6 +1991,7 @@ mod tests {
                     end: 9,
                     length: 4
                 }],
+                capture_names: None,
             })
         );
         assert_eq!(
@@ -1512,4 +2183,370 @@ mod tests {
         assert_eq!(m.capture_indices[2].end, 0);
         assert_eq!(m.capture_indices[2].length, 0);
     }
+
+    #[test]
+    fn warm_up_preallocates_and_keeps_results() {
+        let long = format!("{}foo(bar)", " ".repeat(1200));
+        let mut cold = Scanner::new(&["foo\\((\\w+)\\)", "\\d+"]).unwrap();
+        let mut warm = Scanner::new(&["foo\\((\\w+)\\)", "\\d+"]).unwrap();
+        warm.warm_up(4096);
+        // `\w+` and `\d+` can leave a choice point per byte
+        let want = warm
+            .regexes
+            .iter()
+            .map(|reg| crate::regexec::stack_reservation(reg, 4096))
+            .max()
+            .unwrap();
+        assert!(want > 4096);
+        assert!(warm.state.stack_capacity() >= want);
+
+        // Without loops the line length does not matter
+        let mut literal = Scanner::new(&["foo", "ba[rz]"]).unwrap();
+        literal.warm_up(4096);
+        assert!(literal.state.stack_capacity() < 4096);
+        assert_eq!(warm.caches[0].last_region.as_ref().unwrap().num_regs, 2);
+
+        for text in [long.as_str(), "x foo(y) 1"] {
+            assert_eq!(
+                warm.find_next_match(text, 0, ScannerFindOptions::NONE),
+                cold.find_next_match(text, 0, ScannerFindOptions::NONE)
+            );
+        }
+    }
+
+    #[test]
+    fn warm_up_empty_scanner() {
+        let mut scanner = Scanner::new(&[]).unwrap();
+        scanner.warm_up(100);
+        assert!(scanner
+            .find_next_match("abc", 0, ScannerFindOptions::NONE)
+            .is_none());
+    }
+
+    #[test]
+    fn find_next_match_into_flat_layout() {
+        let mut scanner = Scanner::new(&["(a)(b)?(c)", "\\d+"]).unwrap();
+        assert_eq!(scanner.max_result_len(), 10);
+        let mut buf = vec![0; scanner.max_result_len()];
+
+        // Short string (RegSet path)
+        let n = scanner.find_next_match_into("xac", 0, ScannerFindOptions::NONE, &mut buf);
+        assert_eq!(n, Some(10));
+        assert_eq!(buf, [0, 4, 1, 3, 1, 2, -1, -1, 2, 3]);
+
+        // Long string (per-regex path)
+        let long = format!("{}42", " ".repeat(1200));
+        let n = scanner.find_next_match_into(&long, 0, ScannerFindOptions::NONE, &mut buf);
+        assert_eq!(n, Some(4));
+        assert_eq!(buf[..4], [1, 1, 1200, 1202]);
+
+        assert_eq!(
+            scanner.find_next_match_into("zz", 0, ScannerFindOptions::NONE, &mut buf),
+            None
+        );
+
+        // Too small: only the header is written
+        let mut small = [7; 3];
+        let n = scanner.find_next_match_into("ac", 0, ScannerFindOptions::NONE, &mut small);
+        assert_eq!(n, Some(10));
+        assert_eq!(small, [0, 4, 7]);
+    }
+
+    #[test]
+    fn find_next_match_utf16_into_matches_struct() {
+        let mut scanner = Scanner::new(&["b(c)?", "💻"]).unwrap();
+        let s = OnigString::new("a💻bc");
+        let mut buf = vec![0; scanner.max_result_len()];
+
+        let n = scanner
+            .find_next_match_utf16_into(&s, 2, ScannerFindOptions::NONE, &mut buf)
+            .unwrap();
+        let m = scanner
+            .find_next_match_utf16(&s, 2, ScannerFindOptions::NONE)
+            .unwrap();
+        assert_eq!(buf[..n], [0, 2, 3, 5, 4, 5]);
+        assert_eq!(buf[0] as usize, m.index);
+        assert_eq!(buf[2] as usize, m.capture_indices[0].start);
+        assert_eq!(buf[5] as usize, m.capture_indices[1].end);
+    }
+
+    fn report_once_scanner(patterns: &[&str]) -> Scanner {
+        let config = ScannerConfig {
+            empty_match_at_end: ScannerEmptyMatchAtEnd::ReportOnce,
+            ..Default::default()
+        };
+        Scanner::with_config(patterns, &config).unwrap()
+    }
+
+    #[test]
+    fn empty_match_at_end_repeats_by_default() {
+        let mut scanner = Scanner::new(&["$"]).unwrap();
+        for _ in 0..3 {
+            let m = scanner
+                .find_next_match_with_id("ab", 1, 2, ScannerFindOptions::NONE)
+                .unwrap();
+            assert_eq!(m.capture_indices[0].start, 2);
+            assert_eq!(m.capture_indices[0].end, 2);
+        }
+    }
+
+    #[test]
+    fn empty_match_at_end_reported_once() {
+        let mut scanner = report_once_scanner(&["x", "$"]);
+        let m = scanner
+            .find_next_match_with_id("ab", 1, 0, ScannerFindOptions::NONE)
+            .unwrap();
+        assert_eq!(
+            (m.capture_indices[0].start, m.capture_indices[0].end),
+            (2, 2)
+        );
+        assert!(scanner
+            .find_next_match_with_id("ab", 1, 2, ScannerFindOptions::NONE)
+            .is_none());
+        // Another text, or other options, report it again.
+        assert!(scanner
+            .find_next_match_with_id("ab", 2, 2, ScannerFindOptions::NONE)
+            .is_some());
+        assert!(scanner
+            .find_next_match_with_id("ab", 2, 2, ScannerFindOptions::NOT_BEGIN_STRING)
+            .is_some());
+        // A non-empty match on the text resets it.
+        let m = scanner
+            .find_next_match_with_id("abx", 3, 0, ScannerFindOptions::NONE)
+            .unwrap();
+        assert_eq!(m.index, 0);
+        assert!(scanner
+            .find_next_match_with_id("abx", 3, 3, ScannerFindOptions::NONE)
+            .is_some());
+        assert!(scanner
+            .find_next_match_with_id("abx", 3, 3, ScannerFindOptions::NONE)
+            .is_none());
+        // Plain &str calls carry no identity and always report.
+        for _ in 0..2 {
+            assert!(scanner
+                .find_next_match("ab", 2, ScannerFindOptions::NONE)
+                .is_some());
+        }
+    }
+
+    #[test]
+    fn empty_match_at_end_reported_once_utf16_and_long_text() {
+        let mut scanner = report_once_scanner(&["\\z"]);
+        let s = OnigString::new("a💻");
+        let t = OnigString::new("a💻");
+        assert_eq!(
+            scanner
+                .find_next_match_utf16(&s, 0, ScannerFindOptions::NONE)
+                .unwrap()
+                .capture_indices[0]
+                .start,
+            3
+        );
+        assert!(scanner
+            .find_next_match_utf16(&s, 3, ScannerFindOptions::NONE)
+            .is_none());
+        assert!(scanner
+            .find_next_match_utf16(&t, 3, ScannerFindOptions::NONE)
+            .is_some());
+
+        // Long texts take the per-regex path.
+        let long = "a".repeat(MAX_REGSET_MATCH_INPUT_LEN + 1);
+        let end = long.len();
+        assert!(scanner
+            .find_next_match_with_id(&long, 7, 0, ScannerFindOptions::NONE)
+            .is_some());
+        assert!(scanner
+            .find_next_match_with_id(&long, 7, end, ScannerFindOptions::NONE)
+            .is_none());
+    }
+
+    #[test]
+    fn capture_names_in_matches() {
+        let config = ScannerConfig {
+            capture_names: true,
+            ..Default::default()
+        };
+        let mut scanner =
+            Scanner::with_config(&["(?<key>\\w+)(:)", "(?<num>\\d+)|(?<num>x)"], &config).unwrap();
+        let m = scanner
+            .find_next_match("a: 1", 0, ScannerFindOptions::NONE)
+            .unwrap();
+        assert_eq!(m.capture_name(0), None);
+        assert_eq!(m.capture_name(1), Some("key"));
+        assert_eq!(m.capture_name(2), None);
+        assert_eq!(m.capture_name(3), None);
+
+        let m = scanner
+            .find_next_match_utf16(&OnigString::new("💻x"), 0, ScannerFindOptions::NONE)
+            .unwrap();
+        assert_eq!(m.index, 1);
+        assert_eq!(m.capture_indices[2].start, 2);
+        assert_eq!(m.capture_name(1), Some("num"));
+        assert_eq!(m.capture_name(2), Some("num"));
+
+        // Matches of one pattern share its name table.
+        let again = scanner
+            .find_next_match("7", 0, ScannerFindOptions::NONE)
+            .unwrap();
+        assert!(Arc::ptr_eq(
+            m.capture_names.as_ref().unwrap(),
+            again.capture_names.as_ref().unwrap()
+        ));
+
+        let mut plain = Scanner::new(&["(?<key>a)"]).unwrap();
+        let m = plain
+            .find_next_match("a", 0, ScannerFindOptions::NONE)
+            .unwrap();
+        assert_eq!(m.capture_names, None);
+    }
+
+    fn spans_in_lines(
+        scanner: &mut Scanner,
+        lines: &[&str],
+        cursor: &mut LineCursor,
+    ) -> Vec<(usize, usize, usize)> {
+        let mut found = Vec::new();
+        while let Some((line, m)) = scanner.find_in_lines(lines, cursor) {
+            found.push((line, m.capture_indices[0].start, m.capture_indices[0].end));
+        }
+        found
+    }
+
+    #[test]
+    fn find_in_lines_tracks_g_anchor() {
+        let mut scanner = Scanner::new(&["\\G-and", "x"]).unwrap();
+        let lines = ["x-and-and", "-and x ", "-and"];
+        let mut cursor = LineCursor::new();
+        assert_eq!(
+            spans_in_lines(&mut scanner, &lines, &mut cursor),
+            [(0, 0, 1), (0, 1, 5), (0, 5, 9), (1, 0, 4), (1, 5, 6)]
+        );
+        assert_eq!(cursor.anchor(), Some((1, 6)));
+        assert_eq!((cursor.line(), cursor.position()), (3, 0));
+
+        // Seeking elsewhere keeps `\G` at the last match end.
+        let mut cursor = LineCursor::new();
+        assert_eq!(scanner.find_in_lines(&lines, &mut cursor).unwrap().0, 0);
+        cursor.seek(0, 5);
+        assert_eq!(
+            spans_in_lines(&mut scanner, &lines, &mut cursor),
+            [(1, 5, 6)]
+        );
+
+        let mut cursor = LineCursor::new();
+        cursor.set_anchor(None);
+        assert_eq!(
+            spans_in_lines(&mut scanner, &["-and", "x"], &mut cursor),
+            [(1, 0, 1)]
+        );
+    }
+
+    #[test]
+    fn find_in_lines_steps_over_empty_matches() {
+        let mut scanner = Scanner::new(&["\\G", "b"]).unwrap();
+        let mut cursor = LineCursor::new();
+        assert_eq!(
+            spans_in_lines(&mut scanner, &["aé", "", "b"], &mut cursor),
+            [(0, 0, 0), (2, 0, 1), (2, 1, 1)]
+        );
+    }
+
+    #[test]
+    fn lookbehind_sees_text_before_start() {
+        let mut scanner = Scanner::new(&[r"(?<=\.)\s*(length)\b", r"(?<!\.)\b[a-z]+"]).unwrap();
+        let spans = |m: Option<ScannerMatch>| {
+            m.map(|m| {
+                (
+                    m.index,
+                    m.capture_indices[0].start,
+                    m.capture_indices[0].end,
+                )
+            })
+        };
+        // Both search paths: RegSet for short lines, per-regex for long ones
+        for pad in [0, MAX_REGSET_MATCH_INPUT_LEN] {
+            let text = format!("a.length b{}", " ".repeat(pad));
+            for start in [2, 3] {
+                let expected = if start == 2 { (0, 2, 8) } else { (1, 9, 10) };
+                let m = scanner.find_next_match(&text, start, ScannerFindOptions::NONE);
+                assert_eq!(spans(m), Some(expected), "pad {pad} start {start}");
+                let m = scanner.find_next_match_with_id(&text, 7, start, ScannerFindOptions::NONE);
+                assert_eq!(spans(m), Some(expected), "pad {pad} start {start}");
+            }
+            let string = OnigString::new(&text);
+            let m = scanner.find_next_match_utf16(&string, 2, ScannerFindOptions::NONE);
+            assert_eq!(spans(m), Some((0, 2, 8)), "pad {pad}");
+        }
+    }
+
+    #[test]
+    fn clone_shares_patterns() {
+        let mut scanner = Scanner::new(&[r"\d+", r"(?<w>[a-z]+)"]).unwrap();
+        assert!(scanner
+            .find_next_match("ab 1", 0, ScannerFindOptions::NONE)
+            .is_some());
+        let mut other = scanner.clone();
+        assert!(Arc::ptr_eq(&scanner.regexes[1], &other.regexes[1]));
+        // Scanner, clone and both RegSets hold the same compiled regex
+        assert_eq!(Arc::strong_count(&scanner.regexes[1]), 4);
+
+        // The clone starts with empty caches and searches on its own
+        assert!(other.caches.iter().all(|c| c.last_region.is_none()));
+        let m = other
+            .find_next_match("1 ab", 0, ScannerFindOptions::NONE)
+            .unwrap();
+        assert_eq!((m.index, m.capture_indices[0].end), (0, 1));
+        let m = scanner
+            .find_next_match("ab 1", 2, ScannerFindOptions::NONE)
+            .unwrap();
+        assert_eq!((m.index, m.capture_indices[0].start), (0, 3));
+    }
+
+    #[test]
+    fn clone_reports_empty_match_at_end_again() {
+        let mut scanner = report_once_scanner(&["$"]);
+        let opts = ScannerFindOptions::NONE;
+        assert!(scanner.find_next_match_with_id("ab", 1, 2, opts).is_some());
+        assert!(scanner.find_next_match_with_id("ab", 1, 2, opts).is_none());
+        let mut other = scanner.clone();
+        assert!(other.find_next_match_with_id("ab", 1, 2, opts).is_some());
+    }
+
+    #[test]
+    fn rescan_from_reuses_last_search() {
+        let opts = ScannerFindOptions::NONE;
+        let start = |m: Option<ScannerMatch>| m.map(|m| (m.index, m.capture_indices[0].start));
+        let long = format!("{}= x", " ".repeat(MAX_REGSET_MATCH_INPUT_LEN));
+        for text in ["  = x", long.as_str()] {
+            let at = text.len() - 3;
+            let mut scanner = Scanner::new(&["=", "[a-z]"]).unwrap();
+            assert_eq!(start(scanner.rescan_from(text, 1, 0, opts)), Some((0, at)));
+            // Answered without searching: a different text under the same
+            // id still gets the last match
+            assert_eq!(start(scanner.rescan_from("=", 1, at, opts)), Some((0, at)));
+            // Past the last match, or under another id or options, it searches
+            assert_eq!(
+                start(scanner.rescan_from(text, 1, at + 1, opts)),
+                Some((1, at + 2))
+            );
+            assert_eq!(start(scanner.rescan_from("=", 2, 0, opts)), Some((0, 0)));
+            let not_end = ScannerFindOptions::NOT_END_STRING;
+            assert_eq!(
+                start(scanner.rescan_from(text, 2, 1, not_end)),
+                Some((0, at))
+            );
+
+            // No match: none from later starts either
+            assert_eq!(scanner.find_next_match_with_id("  ", 3, 0, opts), None);
+            assert_eq!(scanner.rescan_from("=", 3, 1, opts), None);
+            // A search without an id forgets the last one
+            scanner.find_next_match("", 0, opts);
+            assert_eq!(start(scanner.rescan_from("=", 3, 0, opts)), Some((0, 0)));
+        }
+
+        // \G depends on the start, so it always searches
+        let mut scanner = Scanner::new(&[r"\Gb", "c"]).unwrap();
+        assert_eq!(start(scanner.rescan_from("abc", 1, 0, opts)), Some((1, 2)));
+        assert_eq!(start(scanner.rescan_from("abc", 1, 1, opts)), Some((0, 1)));
+    }
 }
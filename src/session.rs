@@ -0,0 +1,349 @@
+// session.rs - Forward-only scanning over a haystack that arrives in chunks.
+//
+// Oniguruma searches a complete buffer. A ScanSession keeps only
+// the tail of the stream that can still affect future matches and reports
+// a match as soon as no later input can change it, which is only sound for
+// patterns whose look-around and match length are bounded. Those bounds
+// come from the pattern's syntax tree (see `margins`).
+
+use core::ops::Range;
+
+use crate::api::Regex;
+use crate::ast::{self, AnchorKind, AstKind, AstNode, ClassKind, GroupKind};
+use crate::oniguruma::*;
+use crate::regexec::onig_search;
+use crate::regint::opton_find_longest;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+
+/// Longest UTF-8 encoding of one character.
+const MAX_CHAR_LEN: usize = 4;
+
+/// Longest text one pattern character can match: case folding can expand a
+/// character to up to three (e.g. `ﬃ` against `ffi`).
+const MAX_LITERAL_CHAR_LEN: usize = 3 * MAX_CHAR_LEN;
+
+/// Incremental, forward-only search over a stream of UTF-8 text.
+///
+/// Feed input with [`append`](Self::append); each call returns the matches
+/// that later input can no longer change. [`finish`](Self::finish) ends the
+/// stream and returns the rest. Together they report exactly what
+/// [`Regex::find_iter`] reports on the concatenated input, while only
+/// holding a bounded window of it.
+///
+/// Only patterns with a bounded match length and bounded look-around are
+/// supported: [`new`](Self::new) returns `None` for unbounded repeats of
+/// non-empty expressions, backreferences, subexpression calls, `\G`, `\X`,
+/// `\y`/`\Y`, absent groups and callouts, and for `ONIG_OPTION_FIND_LONGEST`.
+///
+/// ```
+/// use ferroni::api::Regex;
+/// use ferroni::session::ScanSession;
+///
+/// let re = Regex::new(r"\d{1,3}").unwrap();
+/// let mut session = ScanSession::new(&re).unwrap();
+/// let mut found = Vec::new();
+/// for chunk in ["12", "34 5", "6"] {
+///     found.extend(session.append(chunk.as_bytes()));
+/// }
+/// found.extend(session.finish());
+/// let ranges: Vec<_> = found.iter().map(|m| m.range()).collect();
+/// assert_eq!(ranges, [0..3, 3..4, 5..7]);
+///
+/// // Unbounded repeats can grow with every chunk.
+/// assert!(ScanSession::new(&Regex::new(r"\d+").unwrap()).is_none());
+/// ```
+pub struct ScanSession<'r> {
+    regex: &'r Regex,
+    /// Bytes past a start position that an attempt there may inspect.
+    ahead: usize,
+    /// Bytes before a start position that an attempt there may inspect.
+    behind: usize,
+    buf: Vec<u8>,
+    /// Stream offset of `buf[0]`.
+    base: u64,
+    /// Where the next search starts, relative to `buf` (as in `FindIter`).
+    last_end: usize,
+    last_was_empty: bool,
+}
+
+/// A match reported by a [`ScanSession`].
+///
+/// Positions are stream offsets, which can exceed `usize` on 32-bit
+/// targets. The matched bytes are copied out, since the session may
+/// already have dropped them from its buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionMatch {
+    start: u64,
+    end: u64,
+    bytes: Vec<u8>,
+}
+
+impl SessionMatch {
+    /// Stream offset of the start of the match.
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Stream offset of the end of the match.
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// Stream offsets of the match.
+    pub fn range(&self) -> Range<u64> {
+        self.start..self.end
+    }
+
+    /// The matched bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<'r> ScanSession<'r> {
+    /// Start a session for `regex`, or return `None` if the pattern is not
+    /// one whose matches can be decided from a bounded window.
+    pub fn new(regex: &'r Regex) -> Option<Self> {
+        let reg = &regex.inner;
+        if opton_find_longest(reg.options) {
+            return None;
+        }
+        let (_, ahead, behind) = if regex.literal {
+            let chars = String::from_utf8_lossy(&regex.pattern).chars().count();
+            (0, chars * MAX_LITERAL_CHAR_LEN, 0)
+        } else {
+            let tree = ast::parse_with_options(&regex.pattern, reg.options, &reg.syntax).ok()?;
+            margins(&tree.root)?
+        };
+        // The slack covers reading the character at the far end of the
+        // window, e.g. for `$` or `\b`.
+        Some(ScanSession {
+            regex,
+            ahead: ahead + MAX_CHAR_LEN,
+            behind: behind + MAX_CHAR_LEN,
+            buf: Vec::new(),
+            base: 0,
+            last_end: 0,
+            last_was_empty: false,
+        })
+    }
+
+    /// Add `bytes` to the stream and return the matches that are now final.
+    ///
+    /// `bytes` may end in the middle of a character.
+    pub fn append(&mut self, bytes: &[u8]) -> Vec<SessionMatch> {
+        self.buf.extend_from_slice(bytes);
+        let mut found = Vec::new();
+        // Search complete characters only. Attempts starting at or before
+        // `stable` see the same input no matter what is appended later.
+        let len = self.complete_len();
+        if let Some(stable) = len.checked_sub(self.ahead) {
+            while self.last_end <= stable {
+                if !self.next_match(len, Some(stable), &mut found) {
+                    // No match starts in last_end..=stable. `last_was_empty`
+                    // is false here: after an empty match at `last_end`, the
+                    // search finds that same match again.
+                    let mut p = stable + 1;
+                    while p < len && self.buf[p] & 0xC0 == 0x80 {
+                        p += 1;
+                    }
+                    self.last_end = p;
+                    break;
+                }
+            }
+        }
+        self.trim();
+        found
+    }
+
+    /// End the stream and return the remaining matches.
+    pub fn finish(mut self) -> Vec<SessionMatch> {
+        let mut found = Vec::new();
+        let len = self.buf.len();
+        while self.next_match(len, None, &mut found) {}
+        found
+    }
+
+    /// Length of `buf` without a trailing partial character.
+    fn complete_len(&self) -> usize {
+        let len = self.buf.len();
+        let lead = (len.saturating_sub(MAX_CHAR_LEN)..len)
+            .rev()
+            .find(|&i| self.buf[i] & 0xC0 != 0x80);
+        match lead {
+            Some(i) if i + self.regex.inner.enc.mbc_enc_len(&self.buf[i..]) > len => i,
+            _ => len,
+        }
+    }
+
+    /// Run one step of `FindIter`'s search loop over `buf[..len]`. Returns
+    /// false if there is no further match, or none starting at or before
+    /// `stable`.
+    fn next_match(
+        &mut self,
+        len: usize,
+        stable: Option<usize>,
+        found: &mut Vec<SessionMatch>,
+    ) -> bool {
+        if self.last_end > len {
+            return false;
+        }
+        let (r, region) = onig_search(
+            &self.regex.inner,
+            &self.buf,
+            len,
+            self.last_end,
+            len,
+            Some(OnigRegion::new()),
+            ONIG_OPTION_NONE,
+        );
+        let Some(Range { start, end }) = region.filter(|_| r >= 0).and_then(|r| r.get(0)) else {
+            return false;
+        };
+        if stable.is_some_and(|stable| start > stable) {
+            return false;
+        }
+
+        if start == end {
+            if self.last_was_empty {
+                if self.last_end >= len {
+                    return false;
+                }
+                self.last_end += self.regex.inner.enc.mbc_enc_len(&self.buf[self.last_end..]);
+                self.last_was_empty = false;
+                return true;
+            }
+            self.last_was_empty = true;
+        } else {
+            self.last_was_empty = false;
+        }
+        self.last_end = end;
+        found.push(SessionMatch {
+            start: self.base + start as u64,
+            end: self.base + end as u64,
+            bytes: self.buf[start..end].to_vec(),
+        });
+        true
+    }
+
+    /// Drop input that no future attempt can look at.
+    fn trim(&mut self) {
+        let mut cut = self
+            .last_end
+            .saturating_sub(self.behind)
+            .min(self.buf.len());
+        while cut > 0 && cut < self.buf.len() && self.buf[cut] & 0xC0 == 0x80 {
+            cut -= 1;
+        }
+        if cut > 0 {
+            self.buf.drain(..cut);
+            self.base += cut as u64;
+            self.last_end -= cut;
+        }
+    }
+}
+
+/// Bounds of what matching `node` at some position touches, in bytes: how
+/// much it can consume, how far past its start it can look (consumed input
+/// included), and how far before its start it can look. `None` if any of
+/// them is unbounded or cannot be told from the tree.
+fn margins(node: &AstNode) -> Option<(usize, usize, usize)> {
+    Some(match &node.kind {
+        AstKind::Empty => (0, 0, 0),
+        AstKind::Literal(s) => {
+            let len = s.chars().count() * MAX_LITERAL_CHAR_LEN;
+            (len, len, 0)
+        }
+        AstKind::Class(kind) => {
+            let len = match kind {
+                ClassKind::Set { .. } => MAX_LITERAL_CHAR_LEN,
+                ClassKind::Word { .. } | ClassKind::Any => MAX_CHAR_LEN,
+                ClassKind::Newline => 2 * MAX_CHAR_LEN,
+                ClassKind::Grapheme => return None,
+            };
+            (len, len, 0)
+        }
+        AstKind::Anchor(kind) => match kind {
+            AnchorKind::StartText | AnchorKind::EndText => (0, 0, 0),
+            AnchorKind::StartLine => (0, 0, MAX_CHAR_LEN),
+            AnchorKind::EndLine | AnchorKind::EndTextOptionalNewline => (0, MAX_CHAR_LEN, 0),
+            AnchorKind::WordBoundary
+            | AnchorKind::NotWordBoundary
+            | AnchorKind::WordStart
+            | AnchorKind::WordEnd => (0, MAX_CHAR_LEN, MAX_CHAR_LEN),
+            AnchorKind::SearchStart
+            | AnchorKind::TextSegmentBoundary
+            | AnchorKind::NotTextSegmentBoundary => return None,
+        },
+        AstKind::Group { kind, body } => {
+            let (consume, ahead, behind) = margins(body)?;
+            match kind {
+                GroupKind::LookAhead | GroupKind::NegativeLookAhead => (0, ahead, behind),
+                // The body ends at the current position.
+                GroupKind::LookBehind | GroupKind::NegativeLookBehind => {
+                    (0, ahead, consume.checked_add(behind)?)
+                }
+                _ => (consume, ahead, behind),
+            }
+        }
+        AstKind::Quantifier { max, body, .. } => {
+            let (consume, ahead, behind) = margins(body)?;
+            match *max {
+                Some(0) => (0, 0, 0),
+                Some(m) => {
+                    let before_last = consume.checked_mul(m as usize - 1)?;
+                    (
+                        consume.checked_mul(m as usize)?,
+                        before_last.checked_add(ahead)?,
+                        behind,
+                    )
+                }
+                None if consume == 0 => (0, ahead, behind),
+                None => return None,
+            }
+        }
+        AstKind::Conditional { condition, yes, no } => {
+            let no = match no {
+                Some(no) => margins(no)?,
+                None => (0, 0, 0),
+            };
+            let yes = match yes {
+                Some(yes) => margins(yes)?,
+                None => (0, 0, 0),
+            };
+            // Checking whether a group matched reads no input; any other
+            // condition is matched before the yes branch.
+            let yes = match condition.kind {
+                AstKind::Backref { .. } => yes,
+                _ => sequence([margins(condition)?, yes])?,
+            };
+            alternatives([yes, no])
+        }
+        AstKind::Concat(items) => sequence(items.iter().map(margins).collect::<Option<Vec<_>>>()?)?,
+        AstKind::Alternation(items) => {
+            alternatives(items.iter().map(margins).collect::<Option<Vec<_>>>()?)
+        }
+        AstKind::Backref { .. } | AstKind::Call { .. } | AstKind::Other => return None,
+    })
+}
+
+fn sequence(
+    items: impl IntoIterator<Item = (usize, usize, usize)>,
+) -> Option<(usize, usize, usize)> {
+    // An item may start anywhere between no and all of the preceding
+    // input consumed.
+    let (mut consume, mut ahead, mut behind) = (0usize, 0usize, 0usize);
+    for (c, a, b) in items {
+        ahead = ahead.max(consume.checked_add(a)?);
+        behind = behind.max(b);
+        consume = consume.checked_add(c)?;
+    }
+    Some((consume, ahead, behind))
+}
+
+fn alternatives(items: impl IntoIterator<Item = (usize, usize, usize)>) -> (usize, usize, usize) {
+    items.into_iter().fold((0, 0, 0), |acc, (c, a, b)| {
+        (acc.0.max(c), acc.1.max(a), acc.2.max(b))
+    })
+}
@@ -0,0 +1,210 @@
+// shiftor.rs - Bit-parallel Shift-Or search for short fixed patterns.
+//
+// A pattern that is just a sequence of at most 64 byte sets
+// (literal bytes and single-byte character classes, e.g. `u[0-9a-fA-F]{4}`
+// or `[-+*/]=`) needs no backtracking. Shift-Or runs it as a 64-bit state
+// word updated with one shift and one OR per input byte, instead of
+// starting the VM at every candidate position.
+//
+// Only the forward search in onig_search_inner() uses it; everything else
+// (onig_match, backward search, FIND_LONGEST, regsets) still runs the VM.
+
+use crate::oniguruma::*;
+use crate::regenc::OnigEncoding;
+use crate::regint::*;
+use crate::regparse_types::*;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+
+/// Most positions a Shift-Or pattern may have (bits of the state word).
+pub(crate) const SHIFT_OR_MAX_LEN: usize = 64;
+
+/// Compiled Shift-Or program.
+#[derive(Clone)]
+pub(crate) struct ShiftOr {
+    /// Bit i is clear where the byte may appear at position i.
+    masks: Box<[u64; 256]>,
+    len: usize,
+}
+
+impl ShiftOr {
+    /// Number of positions, which is also the match length in bytes.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Bytes of the mask table on the heap.
+    pub(crate) fn heap_size(&self) -> usize {
+        core::mem::size_of_val(&*self.masks)
+    }
+
+    /// Start of the first match lying within `text[start..end]`.
+    pub(crate) fn find(&self, text: &[u8], start: usize, end: usize) -> Option<usize> {
+        let accept = 1u64 << (self.len - 1);
+        let mut state = !0u64;
+        for (i, &b) in text[start..end].iter().enumerate() {
+            state = (state << 1) | self.masks[b as usize];
+            if state & accept == 0 {
+                return Some(start + i + 1 - self.len);
+            }
+        }
+        None
+    }
+}
+
+/// Build the Shift-Or program for the tuned tree `root`, or None if the
+/// pattern is not a fixed sequence of byte sets, is longer than
+/// SHIFT_OR_MAX_LEN, or is a plain literal (the exact-string optimizer
+/// already finds those without the VM doing real work).
+pub(crate) fn shift_or_from_tree(root: &Node, reg: &RegexType) -> Option<ShiftOr> {
+    if reg.num_mem != 0 || reg.anchor != 0 || opton_find_longest(reg.options) {
+        return None;
+    }
+    // Every byte offset is a candidate start, so ASCII bytes must never be
+    // part of a longer character. That holds for UTF-8 (on valid input) but
+    // not for e.g. Shift_JIS or UTF-16.
+    if reg.enc.max_enc_len() != 1 && reg.enc.name() != "UTF-8" {
+        return None;
+    }
+    let mut positions = Vec::new();
+    if !collect_positions(root, reg.enc, &mut positions) || positions.is_empty() {
+        return None;
+    }
+    if positions.iter().all(|set| set.count_ones() == 1) {
+        return None;
+    }
+
+    let mut masks = Box::new([!0u64; 256]);
+    for (i, set) in positions.iter().enumerate() {
+        for b in 0..256 {
+            if set.contains(b) {
+                masks[b] &= !(1u64 << i);
+            }
+        }
+    }
+    Some(ShiftOr {
+        masks,
+        len: positions.len(),
+    })
+}
+
+/// The bytes allowed at one position.
+#[derive(Clone, Copy)]
+struct ByteSet([u64; 4]);
+
+impl ByteSet {
+    const EMPTY: ByteSet = ByteSet([0; 4]);
+
+    fn insert(&mut self, b: usize) {
+        self.0[b / 64] |= 1 << (b % 64);
+    }
+
+    fn contains(&self, b: usize) -> bool {
+        self.0[b / 64] & (1 << (b % 64)) != 0
+    }
+
+    fn count_ones(&self) -> u32 {
+        self.0.iter().map(|w| w.count_ones()).sum()
+    }
+}
+
+/// Append the byte sets `node` matches, in order. Returns false if `node`
+/// does not match a fixed sequence of single bytes.
+fn collect_positions(node: &Node, enc: OnigEncoding, out: &mut Vec<ByteSet>) -> bool {
+    if node.has_status(ND_ST_IGNORECASE) {
+        return false;
+    }
+    // UTF-8: a class or type position may only hold ASCII bytes; strings
+    // contribute one position per byte.
+    let single_byte = enc.max_enc_len() == 1;
+    let byte_limit = if single_byte { 256 } else { 0x80 };
+    let ok = match &node.inner {
+        NodeInner::String(sn) => {
+            for &b in &sn.s {
+                let mut set = ByteSet::EMPTY;
+                set.insert(b as usize);
+                out.push(set);
+            }
+            true
+        }
+        NodeInner::CClass(cc) => {
+            if cc.mbuf.is_some() || (cc.is_not() && !single_byte) {
+                return false;
+            }
+            let mut set = ByteSet::EMPTY;
+            for b in 0..256 {
+                if bitset_at(&cc.bs, b) != cc.is_not() {
+                    if b >= byte_limit {
+                        return false;
+                    }
+                    set.insert(b);
+                }
+            }
+            out.push(set);
+            true
+        }
+        NodeInner::CType(ct) => {
+            if ct.ctype == CTYPE_ANYCHAR || !(ct.ascii_mode || single_byte) {
+                return false;
+            }
+            let mut set = ByteSet::EMPTY;
+            for b in 0..256 {
+                let is_ctype = (b < 0x80 || !ct.ascii_mode)
+                    && enc.is_code_ctype(b as OnigCodePoint, ct.ctype as u32);
+                if is_ctype != ct.not {
+                    if b >= byte_limit {
+                        return false;
+                    }
+                    set.insert(b);
+                }
+            }
+            out.push(set);
+            true
+        }
+        NodeInner::List(_) => {
+            let mut cur = node;
+            loop {
+                let Some(cons) = cur.as_cons() else {
+                    break false;
+                };
+                if !collect_positions(&cons.car, enc, out) {
+                    break false;
+                }
+                match &cons.cdr {
+                    Some(next) => cur = next,
+                    None => break true,
+                }
+            }
+        }
+        NodeInner::Quant(qn) if qn.lower == qn.upper => match &qn.body {
+            Some(body) => {
+                let before = out.len();
+                if !collect_positions(body, enc, out) {
+                    return false;
+                }
+                let unit = out[before..].to_vec();
+                for _ in 1..qn.lower {
+                    if out.len() + unit.len() > SHIFT_OR_MAX_LEN {
+                        return false;
+                    }
+                    out.extend_from_slice(&unit);
+                }
+                if qn.lower == 0 {
+                    out.truncate(before);
+                }
+                true
+            }
+            None => true,
+        },
+        // Options (with case folding excluded above) and atomic groups do
+        // not change what a fixed sequence matches.
+        NodeInner::Bag(bn) if matches!(bn.bag_type, BagType::Option | BagType::StopBacktrack) => {
+            match &bn.body {
+                Some(body) => collect_positions(body, enc, out),
+                None => true,
+            }
+        }
+        _ => false,
+    };
+    ok && out.len() <= SHIFT_OR_MAX_LEN
+}
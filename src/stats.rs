@@ -0,0 +1,254 @@
+// stats.rs - Execution counters of the match VM (exec-stats feature).
+//
+// A profile of what a workload makes the VM do, per compiled
+// regex -- how often each opcode ran, how deep the backtrack stack was at
+// each backtrack and how many match attempts (start positions) were tried.
+// Sampling profilers show that match_at() is hot, not which pattern or
+// which instruction makes it so.
+//
+// match_at() counts into a Tally on its own stack and adds it to the
+// regex's ExecCounters once per attempt, so the hot loop touches no shared
+// memory. Searches answered without the VM (Shift-Or programs, prefix-only
+// optimizations that reject the text) run no attempt and are not counted.
+
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+
+use core::fmt;
+use core::sync::atomic::Ordering;
+
+use crate::regint::*;
+use crate::sys::AtomicU64;
+
+/// Number of opcodes, indexable by `OpCode as usize`.
+pub(crate) const OPCODE_COUNT: usize = OpCode::EmptyCheckEndSimple as usize + 1;
+
+/// Backtrack depth buckets: bucket `i` holds depths of bit length `i`.
+const DEPTH_BUCKETS: usize = usize::BITS as usize + 1;
+
+/// Counters of one match attempt, in plain integers while the VM runs.
+pub(crate) struct Tally {
+    ops: [u64; OPCODE_COUNT],
+    depth: [u64; DEPTH_BUCKETS],
+}
+
+impl Tally {
+    pub(crate) fn new() -> Self {
+        Tally {
+            ops: [0; OPCODE_COUNT],
+            depth: [0; DEPTH_BUCKETS],
+        }
+    }
+
+    #[inline]
+    pub(crate) fn op(&mut self, opcode: OpCode) {
+        self.ops[opcode as usize] += 1;
+    }
+
+    /// A backtrack with `depth` entries on the stack (before the pop).
+    #[inline]
+    pub(crate) fn backtrack(&mut self, depth: usize) {
+        self.depth[bucket(depth)] += 1;
+    }
+}
+
+/// Bit length of `depth`: 0 -> 0, 1 -> 1, 2..=3 -> 2, 4..=7 -> 3, ...
+fn bucket(depth: usize) -> usize {
+    (usize::BITS - depth.leading_zeros()) as usize
+}
+
+/// Counters of one regex, summed over all attempts and threads.
+pub(crate) struct ExecCounters {
+    attempts: AtomicU64,
+    matches: AtomicU64,
+    ops: [AtomicU64; OPCODE_COUNT],
+    depth: [AtomicU64; DEPTH_BUCKETS],
+}
+
+impl Default for ExecCounters {
+    fn default() -> Self {
+        ExecCounters {
+            attempts: AtomicU64::new(0),
+            matches: AtomicU64::new(0),
+            ops: core::array::from_fn(|_| AtomicU64::new(0)),
+            depth: core::array::from_fn(|_| AtomicU64::new(0)),
+        }
+    }
+}
+
+impl ExecCounters {
+    /// Add one attempt that returned `result` (a match length, a mismatch
+    /// or an error).
+    pub(crate) fn add(&self, tally: &Tally, result: i32) {
+        self.attempts.fetch_add(1, Ordering::Relaxed);
+        if result >= 0 {
+            self.matches.fetch_add(1, Ordering::Relaxed);
+        }
+        for (counter, &n) in self.ops.iter().zip(&tally.ops) {
+            if n != 0 {
+                counter.fetch_add(n, Ordering::Relaxed);
+            }
+        }
+        for (counter, &n) in self.depth.iter().zip(&tally.depth) {
+            if n != 0 {
+                counter.fetch_add(n, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn reset(&self) {
+        let all = [&self.attempts, &self.matches];
+        for counter in all.into_iter().chain(&self.ops).chain(&self.depth) {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// What the VM did for one regex since it was compiled or last reset; see
+/// [`exec_stats`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecStats {
+    /// Match attempts: start positions the VM ran the program at.
+    pub match_attempts: u64,
+    /// Attempts that ended in a match.
+    pub matches: u64,
+    /// Backtracks: failed instructions that popped the stack.
+    pub backtracks: u64,
+    /// Executions per opcode, most executed first. Opcodes that never ran
+    /// are left out.
+    pub opcodes: Vec<(&'static str, u64)>,
+    /// Backtracks by stack depth at the time: entry `i` counts depths in
+    /// `2^(i-1)..2^i` (entry 0 counts depth 0). Empty buckets at the end are
+    /// cut off.
+    pub backtrack_depth: Vec<u64>,
+}
+
+impl ExecStats {
+    /// Opcode executions of all kinds.
+    pub fn instructions(&self) -> u64 {
+        self.opcodes.iter().map(|&(_, n)| n).sum()
+    }
+}
+
+impl fmt::Display for ExecStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "attempts: {}, matches: {}, instructions: {}, backtracks: {}",
+            self.match_attempts,
+            self.matches,
+            self.instructions(),
+            self.backtracks
+        )?;
+        for (name, n) in &self.opcodes {
+            writeln!(f, "  {:<24} {}", name, n)?;
+        }
+        for (i, n) in self.backtrack_depth.iter().enumerate() {
+            let (lo, hi) = if i == 0 {
+                (0, 0)
+            } else {
+                (1u128 << (i - 1), (1u128 << i) - 1)
+            };
+            writeln!(f, "  depth {:>10}..={:<10} {}", lo, hi, n)?;
+        }
+        Ok(())
+    }
+}
+
+/// The counters of `reg`. Counting is on with the `exec-stats` feature and
+/// covers every search and match through the VM, from all threads.
+pub fn exec_stats(reg: &RegexType) -> ExecStats {
+    let c = &reg.exec_stats;
+    let mut opcodes: Vec<(&'static str, u64)> = Vec::new();
+    for (i, counter) in c.ops.iter().enumerate() {
+        let n = counter.load(Ordering::Relaxed);
+        // An opcode that ran is in the program, which gives its name
+        if let Some(op) = reg.ops.iter().find(|op| op.opcode as usize == i) {
+            if n != 0 {
+                opcodes.push((crate::regcomp::op2name(op.opcode), n));
+            }
+        }
+    }
+    opcodes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let mut backtrack_depth: Vec<u64> = c.depth.iter().map(|d| d.load(Ordering::Relaxed)).collect();
+    while backtrack_depth.last() == Some(&0) {
+        backtrack_depth.pop();
+    }
+
+    ExecStats {
+        match_attempts: c.attempts.load(Ordering::Relaxed),
+        matches: c.matches.load(Ordering::Relaxed),
+        backtracks: backtrack_depth.iter().sum(),
+        opcodes,
+        backtrack_depth,
+    }
+}
+
+/// Zero the counters of `reg`, e.g. after a warm-up run.
+pub fn reset_exec_stats(reg: &RegexType) {
+    reg.exec_stats.reset();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::Regex;
+
+    #[test]
+    fn depth_buckets() {
+        assert_eq!(bucket(0), 0);
+        assert_eq!(bucket(1), 1);
+        assert_eq!(bucket(2), 2);
+        assert_eq!(bucket(3), 2);
+        assert_eq!(bucket(4), 3);
+        assert_eq!(bucket(usize::MAX), DEPTH_BUCKETS - 1);
+    }
+
+    #[test]
+    fn counts_attempts_opcodes_and_backtracks() {
+        let re = Regex::new(r"a+\d").unwrap();
+        assert_eq!(re.exec_stats(), ExecStats::default());
+
+        assert!(re.find("xaa1").is_some());
+        let stats = re.exec_stats();
+        assert!(stats.match_attempts >= 1);
+        assert_eq!(stats.matches, 1);
+        assert!(stats.opcodes.iter().all(|&(_, n)| n > 0));
+        assert!(stats.opcodes.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert!(stats.opcodes.iter().any(|&(name, _)| name == "end"));
+        assert_eq!(stats.backtracks, stats.backtrack_depth.iter().sum::<u64>());
+
+        // A failing search backtracks; the counts keep adding up
+        assert!(re.find("aaaa").is_none());
+        let after = re.exec_stats();
+        assert!(after.match_attempts > stats.match_attempts);
+        assert_eq!(after.matches, 1);
+        assert!(after.backtracks > stats.backtracks);
+        assert!(after.instructions() > stats.instructions());
+        assert!(after.to_string().starts_with("attempts: "));
+
+        reset_exec_stats(re.as_raw());
+        assert_eq!(re.exec_stats(), ExecStats::default());
+    }
+
+    #[test]
+    fn counts_small_programs() {
+        // onig_match() without a region takes match_at_small()
+        let re = Regex::new("x?xy").unwrap();
+        assert!(re.as_raw().small_match);
+        let (r, _) = crate::regexec::onig_match(
+            re.as_raw(),
+            b"xy",
+            2,
+            0,
+            None,
+            crate::oniguruma::ONIG_OPTION_NONE,
+        );
+        assert_eq!(r, 2);
+        let stats = re.exec_stats();
+        assert_eq!((stats.match_attempts, stats.matches), (1, 1));
+        assert_eq!(stats.backtracks, 1);
+        assert_eq!(stats.backtrack_depth, [0, 1]);
+    }
+}
@@ -0,0 +1,367 @@
+// sys.rs - Platform services for the global registries and limits.
+//
+// C Oniguruma keeps its registries (warning functions, callout
+// names, user-defined properties, case fold tables) in plain globals and
+// leaves locking to the host. Ferroni guards them with the types below, so
+// they are the only place the crate needs more than `core` and `alloc`.
+//
+// With the `std` feature (the default) these are std's locks, thread-locals
+// and clock. Without it the crate is `no_std + alloc`: locks spin on core
+// atomics, per-thread state is one value shared by all callers, and there
+// is no clock, so time limits never fire (retry limits still do).
+//
+// Single-threaded wasm (wasm32 without the `atomics` target feature) has
+// std but no threads, so it takes the spinning locks too (build.rs sets
+// `std_locks` only where std's locks are backed by threads). The clock
+// there comes from JavaScript, see wasm.rs.
+
+#[cfg(not(std_locks))]
+use core::cell::UnsafeCell;
+#[cfg(not(feature = "std"))]
+use core::mem::MaybeUninit;
+#[cfg(not(std_locks))]
+use core::ops::{Deref, DerefMut};
+#[cfg(not(feature = "std"))]
+use core::sync::atomic::AtomicU8;
+#[cfg(not(std_locks))]
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(not(feature = "std"))]
+use core::time::Duration;
+
+/// The alloc types std's prelude brings into every module.
+#[cfg(not(feature = "std"))]
+pub(crate) mod prelude {
+    pub use alloc::boxed::Box;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec::Vec;
+}
+
+// ---------------------------------------------------------------------------
+// Mutex
+// ---------------------------------------------------------------------------
+
+/// A mutual exclusion lock. Unlike std's, `lock` never fails: a panic while
+/// the lock was held leaves the registry as the panicking call left it.
+#[cfg(std_locks)]
+pub(crate) struct Mutex<T>(std::sync::Mutex<T>);
+
+#[cfg(std_locks)]
+pub(crate) type MutexGuard<'a, T> = std::sync::MutexGuard<'a, T>;
+
+#[cfg(std_locks)]
+impl<T> Mutex<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        Mutex(std::sync::Mutex::new(value))
+    }
+
+    pub(crate) fn lock(&self) -> MutexGuard<'_, T> {
+        self.0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// A mutual exclusion lock that spins until it is free.
+#[cfg(not(std_locks))]
+pub(crate) struct Mutex<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: `locked` gives one thread at a time access to `value`.
+#[cfg(not(std_locks))]
+unsafe impl<T: Send> Sync for Mutex<T> {}
+
+#[cfg(not(std_locks))]
+impl<T> Mutex<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        Mutex {
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub(crate) fn lock(&self) -> MutexGuard<'_, T> {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            while self.locked.load(Ordering::Relaxed) {
+                core::hint::spin_loop();
+            }
+        }
+        MutexGuard { lock: self }
+    }
+}
+
+/// Access to the value of a locked [`Mutex`]; unlocks when dropped.
+#[cfg(not(std_locks))]
+pub(crate) struct MutexGuard<'a, T> {
+    lock: &'a Mutex<T>,
+}
+
+#[cfg(not(std_locks))]
+impl<T> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the guard holds the lock.
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+#[cfg(not(std_locks))]
+impl<T> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: the guard holds the lock.
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+#[cfg(not(std_locks))]
+impl<T> Drop for MutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.locked.store(false, Ordering::Release);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// RwLock
+// ---------------------------------------------------------------------------
+
+/// A reader-writer lock; never fails, like [`Mutex`].
+#[cfg(std_locks)]
+pub(crate) struct RwLock<T>(std::sync::RwLock<T>);
+
+#[cfg(std_locks)]
+impl<T> RwLock<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        RwLock(std::sync::RwLock::new(value))
+    }
+
+    pub(crate) fn read(&self) -> std::sync::RwLockReadGuard<'_, T> {
+        self.0
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    pub(crate) fn write(&self) -> std::sync::RwLockWriteGuard<'_, T> {
+        self.0
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// A reader-writer lock. Without std readers exclude each other too: the
+/// guarded settings are read for an instant per compile.
+#[cfg(not(std_locks))]
+pub(crate) struct RwLock<T>(Mutex<T>);
+
+#[cfg(not(std_locks))]
+impl<T> RwLock<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        RwLock(Mutex::new(value))
+    }
+
+    pub(crate) fn read(&self) -> MutexGuard<'_, T> {
+        self.0.lock()
+    }
+
+    pub(crate) fn write(&self) -> MutexGuard<'_, T> {
+        self.0.lock()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// OnceLock
+// ---------------------------------------------------------------------------
+
+// Only used by the `compact-tables` property decoder.
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub(crate) use std::sync::OnceLock;
+
+/// A cell written once, by the first caller of `get_or_init`; later
+/// callers spin until that write is done.
+#[cfg(not(feature = "std"))]
+pub(crate) struct OnceLock<T> {
+    state: AtomicU8,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+#[cfg(not(feature = "std"))]
+const EMPTY: u8 = 0;
+#[cfg(not(feature = "std"))]
+const RUNNING: u8 = 1;
+#[cfg(not(feature = "std"))]
+const DONE: u8 = 2;
+
+// SAFETY: `value` is written once, before `state` becomes DONE, and only
+// read after that.
+#[cfg(not(feature = "std"))]
+unsafe impl<T: Send + Sync> Sync for OnceLock<T> {}
+
+#[cfg(not(feature = "std"))]
+impl<T> OnceLock<T> {
+    pub(crate) const fn new() -> Self {
+        OnceLock {
+            state: AtomicU8::new(EMPTY),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    pub(crate) fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        if self
+            .state
+            .compare_exchange(EMPTY, RUNNING, Ordering::Acquire, Ordering::Acquire)
+            .is_ok()
+        {
+            // SAFETY: only the caller that moved the state to RUNNING writes.
+            unsafe { (*self.value.get()).write(f()) };
+            self.state.store(DONE, Ordering::Release);
+        }
+        while self.state.load(Ordering::Acquire) != DONE {
+            core::hint::spin_loop();
+        }
+        // SAFETY: the state is DONE, so the value is written.
+        unsafe { (*self.value.get()).assume_init_ref() }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl<T> Drop for OnceLock<T> {
+    fn drop(&mut self) {
+        if *self.state.get_mut() == DONE {
+            // SAFETY: the state is DONE, so the value is written.
+            unsafe { self.value.get_mut().assume_init_drop() }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// AtomicU64
+// ---------------------------------------------------------------------------
+
+#[cfg(target_has_atomic = "64")]
+pub(crate) use core::sync::atomic::AtomicU64;
+
+/// `AtomicU64` for targets without 64-bit atomics (most 32-bit
+/// microcontrollers), as a `u64` behind a [`Mutex`].
+#[cfg(not(target_has_atomic = "64"))]
+pub(crate) struct AtomicU64(Mutex<u64>);
+
+#[cfg(not(target_has_atomic = "64"))]
+impl AtomicU64 {
+    pub(crate) const fn new(value: u64) -> Self {
+        AtomicU64(Mutex::new(value))
+    }
+
+    pub(crate) fn load(&self, _: core::sync::atomic::Ordering) -> u64 {
+        *self.0.lock()
+    }
+
+    pub(crate) fn store(&self, value: u64, _: core::sync::atomic::Ordering) {
+        *self.0.lock() = value;
+    }
+
+    pub(crate) fn fetch_add(&self, value: u64, _: core::sync::atomic::Ordering) -> u64 {
+        let mut n = self.0.lock();
+        let old = *n;
+        *n = old.wrapping_add(value);
+        old
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Per-thread state
+// ---------------------------------------------------------------------------
+
+/// Declare per-thread statics: std's `thread_local!` with `std`. Without
+/// it there are no threads to tell apart, so each static is one
+/// [`PerThread`] value shared by all callers. Either way it is accessed
+/// with `NAME.with(|value| ...)`.
+macro_rules! per_thread {
+    ($(#[$attr:meta])* static $name:ident: $t:ty = const { $init:expr };) => {
+        #[cfg(feature = "std")]
+        std::thread_local! {
+            $(#[$attr])* static $name: $t = const { $init };
+        }
+        #[cfg(not(feature = "std"))]
+        $(#[$attr])* static $name: $crate::sys::PerThread<$t> = $crate::sys::PerThread::new($init);
+    };
+}
+pub(crate) use per_thread;
+
+/// The value of a [`per_thread!`] static without std.
+#[cfg(not(feature = "std"))]
+pub(crate) struct PerThread<T>(Mutex<T>);
+
+#[cfg(not(feature = "std"))]
+impl<T> PerThread<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        PerThread(Mutex::new(value))
+    }
+
+    /// Call `f` with the value, locked for the duration of the call.
+    pub(crate) fn with<R>(&'static self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self.0.lock())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Clock
+// ---------------------------------------------------------------------------
+
+#[cfg(all(
+    feature = "std",
+    not(all(target_arch = "wasm32", target_os = "unknown", feature = "wasm"))
+))]
+pub(crate) use std::time::Instant;
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown", feature = "wasm"))]
+pub(crate) use crate::wasm::Instant;
+
+/// Without std there is no clock: no time ever elapses, so time limits
+/// never fire.
+#[cfg(not(feature = "std"))]
+#[derive(Clone, Copy)]
+pub(crate) struct Instant;
+
+#[cfg(not(feature = "std"))]
+impl Instant {
+    pub(crate) fn now() -> Self {
+        Instant
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locks_and_cells() {
+        static COUNT: Mutex<u32> = Mutex::new(0);
+        *COUNT.lock() += 1;
+        assert_eq!(*COUNT.lock(), 1);
+
+        let lock = RwLock::new(5);
+        *lock.write() += 1;
+        assert_eq!(*lock.read(), 6);
+
+        let once = OnceLock::new();
+        assert_eq!(*once.get_or_init(|| 7), 7);
+        assert_eq!(*once.get_or_init(|| 8), 7);
+
+        per_thread! {
+            static LOCAL: core::cell::Cell<u32> = const { core::cell::Cell::new(1) };
+        }
+        LOCAL.with(|c| c.set(c.get() + 1));
+        assert_eq!(LOCAL.with(|c| c.get()), 2);
+        assert!(Instant::now().elapsed() < core::time::Duration::from_secs(60));
+    }
+}
@@ -0,0 +1,294 @@
+// template.rs - Replacement templates for Regex::replace and replace_all.
+//
+// Oniguruma has no substitution API (Ruby's gsub lives in the
+// interpreter). A template is parsed and checked against one regex once --
+// every group number and name it refers to must exist -- so applying it to
+// many matches only copies literal text and group spans.
+
+use core::fmt;
+use core::ops::Range;
+
+use crate::api::{Captures, Regex};
+use crate::regexec::onig_name_to_group_numbers;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+
+/// A parsed replacement template.
+///
+/// `$n` and `${n}` insert group `n` (`$0` is the whole match), `${name}`
+/// the first group of that name that participated, and `$$` a literal `$`.
+/// Digits after `$` are read greedily: `$10` is group 10, `${1}0` is group
+/// 1 followed by `0`. A group that did not participate inserts nothing.
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::api::Regex;
+/// use ferroni::template::Template;
+///
+/// let re = Regex::new(r"(?<n>\d+)-(?<unit>[a-z]+)").unwrap();
+/// let t = Template::new("$$${unit}:$1", &re).unwrap();
+/// assert_eq!(re.replace_all("5-kg, 10-m", &t), "$kg:5, $m:10");
+///
+/// // Checked against the regex when parsed
+/// assert!(Template::new("$3", &re).is_err());
+/// assert!(Template::new("${size}", &re).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(Box<str>),
+    Group(usize),
+    /// The group numbers of a name, tried in order.
+    Name(Box<[usize]>),
+}
+
+/// Why a template was rejected by [`Template::new`]. Spans are byte ranges
+/// of the template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// `$` not followed by a digit, `{` or `$`.
+    DanglingDollar { at: usize },
+    /// `${` without a closing `}`, or with nothing inside.
+    BadBraces { span: Range<usize> },
+    /// A group number the regex does not have.
+    NoSuchGroup { group: usize, span: Range<usize> },
+    /// A group name the regex does not define.
+    NoSuchName { name: String, span: Range<usize> },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::DanglingDollar { at } => {
+                write!(f, "'$' at {} is not followed by a group or '$'", at)
+            }
+            TemplateError::BadBraces { span } => {
+                write!(f, "invalid '${{...}}' at {}..{}", span.start, span.end)
+            }
+            TemplateError::NoSuchGroup { group, .. } => write!(f, "no group {}", group),
+            TemplateError::NoSuchName { name, .. } => write!(f, "no group named '{}'", name),
+        }
+    }
+}
+
+impl core::error::Error for TemplateError {}
+
+impl Template {
+    /// Parse `template` and check its group references against `regex`, a
+    /// [`Regex`] or a [`bytes::Regex`](crate::bytes::Regex).
+    pub fn new<R: AsRef<Regex> + ?Sized>(
+        template: &str,
+        regex: &R,
+    ) -> Result<Template, TemplateError> {
+        let regex = regex.as_ref();
+        let bytes = template.as_bytes();
+        let groups = regex.captures_len();
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut i = 0;
+
+        while let Some(off) = template[i..].find('$') {
+            let at = i + off;
+            literal.push_str(&template[i..at]);
+            let (part, next) = match bytes.get(at + 1) {
+                Some(b'$') => {
+                    literal.push('$');
+                    i = at + 2;
+                    continue;
+                }
+                Some(b'0'..=b'9') => {
+                    let end = bytes[at + 1..]
+                        .iter()
+                        .position(|b| !b.is_ascii_digit())
+                        .map_or(bytes.len(), |n| at + 1 + n);
+                    (
+                        group_ref(&template[at + 1..end], at..end, regex, groups)?,
+                        end,
+                    )
+                }
+                Some(b'{') => {
+                    let end = match template[at + 2..].find('}') {
+                        Some(n) if n > 0 => at + 2 + n + 1,
+                        _ => {
+                            let end = template[at..].find('}').map_or(bytes.len(), |n| at + n + 1);
+                            return Err(TemplateError::BadBraces { span: at..end });
+                        }
+                    };
+                    (
+                        group_ref(&template[at + 2..end - 1], at..end, regex, groups)?,
+                        end,
+                    )
+                }
+                _ => return Err(TemplateError::DanglingDollar { at }),
+            };
+            if !literal.is_empty() {
+                parts.push(Part::Literal(core::mem::take(&mut literal).into()));
+            }
+            parts.push(part);
+            i = next;
+        }
+        literal.push_str(&template[i..]);
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal.into()));
+        }
+        Ok(Template { parts })
+    }
+
+    /// Append the replacement for `caps` to `dst`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an inserted group is not valid UTF-8.
+    pub fn expand(&self, caps: &Captures<'_>, dst: &mut String) {
+        self.expand_with(|i| caps.get(i).map(|m| m.as_str()), dst);
+    }
+
+    /// Append the replacement to `dst`, reading group `i` through `group`.
+    pub(crate) fn expand_with<'t>(
+        &self,
+        group: impl Fn(usize) -> Option<&'t str>,
+        dst: &mut String,
+    ) {
+        for part in &self.parts {
+            match part {
+                Part::Literal(s) => dst.push_str(s),
+                Part::Group(i) => dst.push_str(group(*i).unwrap_or("")),
+                Part::Name(nums) => {
+                    if let Some(s) = nums.iter().find_map(|&i| group(i)) {
+                        dst.push_str(s);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Like [`expand_with`](Self::expand_with), for byte haystacks.
+    pub(crate) fn expand_bytes_with<'t>(
+        &self,
+        group: impl Fn(usize) -> Option<&'t [u8]>,
+        dst: &mut Vec<u8>,
+    ) {
+        for part in &self.parts {
+            match part {
+                Part::Literal(s) => dst.extend_from_slice(s.as_bytes()),
+                Part::Group(i) => dst.extend_from_slice(group(*i).unwrap_or(b"")),
+                Part::Name(nums) => {
+                    if let Some(s) = nums.iter().find_map(|&i| group(i)) {
+                        dst.extend_from_slice(s);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether the template inserts no groups, so every replacement is the
+    /// same text.
+    pub fn is_literal(&self) -> bool {
+        self.parts.iter().all(|p| matches!(p, Part::Literal(_)))
+    }
+}
+
+/// Resolve the group number or name `r` (spanning `span` in the template).
+fn group_ref(
+    r: &str,
+    span: Range<usize>,
+    regex: &Regex,
+    groups: usize,
+) -> Result<Part, TemplateError> {
+    if r.bytes().all(|b| b.is_ascii_digit()) {
+        return match r.parse::<usize>() {
+            Ok(group) if group <= groups => Ok(Part::Group(group)),
+            _ => Err(TemplateError::NoSuchGroup {
+                group: r.parse().unwrap_or(usize::MAX),
+                span,
+            }),
+        };
+    }
+    match onig_name_to_group_numbers(regex.as_raw(), r.as_bytes()) {
+        Ok(nums) => Ok(Part::Name(nums.iter().map(|&n| n as usize).collect())),
+        Err(_) => Err(TemplateError::NoSuchName {
+            name: r.into(),
+            span,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(template: &str, pattern: &str) -> Result<Vec<Part>, TemplateError> {
+        Template::new(template, &Regex::new(pattern).unwrap()).map(|t| t.parts)
+    }
+
+    #[test]
+    fn parses_references_and_literals() {
+        use Part::*;
+        assert_eq!(
+            parts("a$1${2}b$$c$0", "(x)(y)").unwrap(),
+            [
+                Literal("a".into()),
+                Group(1),
+                Group(2),
+                Literal("b$c".into()),
+                Group(0),
+            ]
+        );
+        assert_eq!(parts("$$", "x").unwrap(), [Literal("$".into())]);
+        assert_eq!(parts("", "x").unwrap(), []);
+        // Greedy digits; braces end a reference
+        assert_eq!(
+            parts("$10", "x").unwrap_err(),
+            TemplateError::NoSuchGroup {
+                group: 10,
+                span: 0..3
+            }
+        );
+        assert_eq!(
+            parts("${1}0", "(x)").unwrap(),
+            [Group(1), Literal("0".into())]
+        );
+        assert_eq!(
+            parts("${n}é", "(?<n>a)|(?<n>b)").unwrap(),
+            [Name(vec![1, 2].into()), Literal("é".into())]
+        );
+    }
+
+    #[test]
+    fn rejects_bad_templates() {
+        assert_eq!(
+            parts("a$", "x").unwrap_err(),
+            TemplateError::DanglingDollar { at: 1 }
+        );
+        assert_eq!(
+            parts("$x", "x").unwrap_err(),
+            TemplateError::DanglingDollar { at: 0 }
+        );
+        assert_eq!(
+            parts("${1", "(x)").unwrap_err(),
+            TemplateError::BadBraces { span: 0..3 }
+        );
+        assert_eq!(
+            parts("${}x", "x").unwrap_err(),
+            TemplateError::BadBraces { span: 0..3 }
+        );
+        assert_eq!(
+            parts("-${nope}", "(?<n>x)").unwrap_err(),
+            TemplateError::NoSuchName {
+                name: "nope".into(),
+                span: 1..8
+            }
+        );
+        assert_eq!(
+            parts("$99999999999999999999999", "x")
+                .unwrap_err()
+                .to_string(),
+            format!("no group {}", usize::MAX)
+        );
+    }
+}
@@ -0,0 +1,308 @@
+// testgen.rs - Deterministic random pattern generator for differential testing.
+//
+// Produces patterns in ONIG_SYNTAX_ONIGURUMA that Ferroni compiles,
+// so a harness can feed the same pattern to Ferroni and to libonig and
+// compare results. Living in-crate keeps the generator in step with the
+// syntax the parser actually accepts; it is only built with the `testgen`
+// feature, so it stays out of the default API.
+
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+
+bitflags::bitflags! {
+    /// Constructs [`random_pattern`] may emit. Literals are always enabled.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Features: u32 {
+        /// Bracket classes, `.`, `\d`/`\w`/`\s` and `\p{...}` properties.
+        const CLASSES = 1 << 0;
+        /// Greedy, lazy and possessive quantifiers, including intervals.
+        const QUANTIFIERS = 1 << 1;
+        /// Capturing, non-capturing, atomic and `(?i:...)` groups.
+        const GROUPS = 1 << 2;
+        /// `|` between branches.
+        const ALTERNATION = 1 << 3;
+        /// Look-ahead and look-behind, positive and negative.
+        const LOOKAROUNDS = 1 << 4;
+        /// `^`, `$`, `\A`, `\z`, `\Z`, `\b`, `\B` and `\G`.
+        const ANCHORS = 1 << 5;
+        /// `\k<n>` references to earlier capture groups (needs `GROUPS`).
+        const BACKREFS = 1 << 6;
+    }
+}
+
+/// Maximum group/lookaround nesting depth of a generated pattern.
+const MAX_DEPTH: u32 = 3;
+
+const LITERALS: &[&str] = &["a", "b", "c", "x", "y", "1", " ", "é", "あ", "\\.", "\\*"];
+const CLASSES: &[&str] = &[
+    ".",
+    "\\d",
+    "\\w",
+    "\\s",
+    "\\W",
+    "[abc]",
+    "[^xy]",
+    "[a-c1]",
+    "[\\d_]",
+    "[[:alpha:]]",
+    "[a-z&&[^aeiou]]",
+    "\\p{L}",
+    "\\p{Greek}",
+    "\\P{N}",
+];
+const ANCHORS: &[&str] = &["^", "$", "\\A", "\\z", "\\Z", "\\b", "\\B", "\\G"];
+const QUANTIFIERS: &[&str] = &["*", "+", "?", "{2}", "{1,3}", "{2,}"];
+
+/// Return a pattern built from `features`, the same one for the same `seed`
+/// and `features` (within a Ferroni release).
+///
+/// Every result compiles with [`OnigSyntaxOniguruma`](crate::regsyntax::OnigSyntaxOniguruma)
+/// and no options.
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::prelude::*;
+/// use ferroni::testgen::{random_pattern, Features};
+///
+/// let pat = random_pattern(7, Features::all());
+/// assert_eq!(pat, random_pattern(7, Features::all()));
+/// assert!(Regex::new(&pat).is_ok());
+/// ```
+pub fn random_pattern(seed: u64, features: Features) -> String {
+    let mut gen = Generator {
+        state: seed,
+        features,
+        groups: 0,
+    };
+    gen.alternation(0).0
+}
+
+struct Generator {
+    state: u64,
+    features: Features,
+    /// Capture groups closed so far; `\k<1>`..`\k<groups>` all exist.
+    groups: u32,
+}
+
+impl Generator {
+    /// SplitMix64: small, fast and identical on every platform.
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.next() % 100 < percent
+    }
+
+    fn pick(&mut self, items: &[&'static str]) -> &'static str {
+        items[self.below(items.len())]
+    }
+
+    fn has(&self, f: Features) -> bool {
+        self.features.contains(f)
+    }
+
+    /// A `|`-separated list of sequences, and whether a quantifier may
+    /// follow it inside `(?:...)`: Oniguruma rejects that when any branch
+    /// consists only of anchors and lookarounds.
+    fn alternation(&mut self, depth: u32) -> (String, bool) {
+        let (mut s, mut quantifiable) = self.sequence(depth);
+        if self.has(Features::ALTERNATION) {
+            while self.chance(25) {
+                let (branch, q) = self.sequence(depth);
+                s.push('|');
+                s.push_str(&branch);
+                quantifiable &= q;
+            }
+        }
+        (s, quantifiable)
+    }
+
+    fn sequence(&mut self, depth: u32) -> (String, bool) {
+        let n = 1 + self.below(4);
+        let mut s = String::new();
+        let mut quantifiable = false;
+        for _ in 0..n {
+            let (item, q) = self.item(depth);
+            s.push_str(&item);
+            quantifiable |= q;
+        }
+        (s, quantifiable)
+    }
+
+    fn item(&mut self, depth: u32) -> (String, bool) {
+        let (mut s, quantifiable) = self.atom(depth);
+        if quantifiable && self.has(Features::QUANTIFIERS) && self.chance(30) {
+            s.push_str(self.pick(QUANTIFIERS));
+            match self.below(4) {
+                0 => s.push('?'),
+                // Possessive `{n,m}+` is a nested repeat in Oniguruma syntax.
+                1 if !s.ends_with('}') => s.push('+'),
+                _ => {}
+            }
+            // Keep the quantified atom a single unit for any outer quantifier.
+            s = format!("(?:{s})");
+        }
+        (s, quantifiable)
+    }
+
+    /// One atom and whether a quantifier may follow it.
+    fn atom(&mut self, depth: u32) -> (String, bool) {
+        let nested = depth < MAX_DEPTH;
+        let mut kinds = vec![0];
+        if self.has(Features::CLASSES) {
+            kinds.push(1);
+        }
+        if self.has(Features::GROUPS) && nested {
+            kinds.push(2);
+        }
+        if self.has(Features::LOOKAROUNDS) && nested {
+            kinds.push(3);
+        }
+        if self.has(Features::ANCHORS) {
+            kinds.push(4);
+        }
+        if self.has(Features::BACKREFS) && self.groups > 0 {
+            kinds.push(5);
+        }
+        match kinds[self.below(kinds.len())] {
+            1 => (self.pick(CLASSES).to_string(), true),
+            2 => self.group(depth + 1),
+            3 => (self.lookaround(depth + 1), false),
+            4 => (self.pick(ANCHORS).to_string(), false),
+            5 => {
+                let n = 1 + self.below(self.groups as usize);
+                (format!("\\k<{n}>"), true)
+            }
+            _ => (self.pick(LITERALS).to_string(), true),
+        }
+    }
+
+    /// A group, and whether a quantifier may follow it (only `(?:...)` is
+    /// transparent to the check).
+    fn group(&mut self, depth: u32) -> (String, bool) {
+        let kind = self.below(4);
+        let (body, quantifiable) = self.alternation(depth);
+        match kind {
+            0 => {
+                self.groups += 1;
+                (format!("({body})"), true)
+            }
+            1 => (format!("(?:{body})"), quantifiable),
+            2 => (format!("(?>{body})"), true),
+            _ => (format!("(?i:{body})"), true),
+        }
+    }
+
+    fn lookaround(&mut self, depth: u32) -> String {
+        match self.below(4) {
+            0 => format!("(?={})", self.lookahead_body(depth)),
+            1 => format!("(?!{})", self.lookahead_body(depth)),
+            2 => format!("(?<={})", self.lookbehind_body()),
+            _ => format!("(?<!{})", self.lookbehind_body()),
+        }
+    }
+
+    /// Look-ahead bodies must not capture, so references stay well defined.
+    fn lookahead_body(&mut self, depth: u32) -> String {
+        let saved = self.features;
+        self.features.remove(Features::GROUPS);
+        let (body, _) = self.alternation(depth);
+        self.features = saved;
+        body
+    }
+
+    /// Look-behind needs a bounded length: only unquantified literals and
+    /// classes, optionally as a top-level alternation.
+    fn lookbehind_body(&mut self) -> String {
+        let mut s = self.fixed_sequence();
+        if self.has(Features::ALTERNATION) && self.chance(30) {
+            s.push('|');
+            s.push_str(&self.fixed_sequence());
+        }
+        s
+    }
+
+    fn fixed_sequence(&mut self) -> String {
+        let n = 1 + self.below(3);
+        (0..n)
+            .map(|_| {
+                if self.has(Features::CLASSES) && self.chance(50) {
+                    self.pick(CLASSES)
+                } else {
+                    self.pick(LITERALS)
+                }
+            })
+            .collect()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encodings::utf8::ONIG_ENCODING_UTF8;
+    use crate::oniguruma::ONIG_OPTION_NONE;
+    use crate::regcomp::onig_new;
+    use crate::regsyntax::OnigSyntaxOniguruma;
+
+    fn compiles(pat: &str) -> bool {
+        onig_new(
+            pat.as_bytes(),
+            ONIG_OPTION_NONE,
+            &ONIG_ENCODING_UTF8,
+            &OnigSyntaxOniguruma,
+        )
+        .is_ok()
+    }
+
+    #[test]
+    fn deterministic_per_seed() {
+        let a: Vec<_> = (0..50)
+            .map(|s| random_pattern(s, Features::all()))
+            .collect();
+        let b: Vec<_> = (0..50)
+            .map(|s| random_pattern(s, Features::all()))
+            .collect();
+        assert_eq!(a, b);
+        assert!(a.iter().collect::<std::collections::HashSet<_>>().len() > 40);
+    }
+
+    #[test]
+    fn every_pattern_compiles() {
+        let mixes = [
+            Features::empty(),
+            Features::all(),
+            Features::GROUPS | Features::BACKREFS | Features::QUANTIFIERS,
+            Features::LOOKAROUNDS | Features::CLASSES | Features::ALTERNATION,
+        ];
+        for features in mixes {
+            for seed in 0..200 {
+                let pat = random_pattern(seed, features);
+                assert!(compiles(&pat), "seed {seed}, {features:?}: {pat:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn disabled_features_are_absent() {
+        for seed in 0..500 {
+            let pat = random_pattern(seed, Features::empty());
+            assert!(!pat.contains(['(', '[', '|', '^', '$']), "{pat:?}");
+            let pat = random_pattern(seed, Features::all() - Features::LOOKAROUNDS);
+            assert!(!pat.contains("(?=") && !pat.contains("(?<"), "{pat:?}");
+        }
+    }
+}
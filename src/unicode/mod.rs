@@ -4,14 +4,21 @@
 
 pub mod egcb_data;
 mod fold_data;
+#[cfg(feature = "compact-tables")]
+mod pack;
 mod property_data;
 pub mod wb_data;
 
+use core::ops::Range;
+
 use crate::oniguruma::*;
 use crate::regenc::*;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
 use egcb_data::{EgcbType, EGCB_RANGES};
 use fold_data::*;
-use property_data::{CODE_RANGES, CODE_RANGES_NUM, PROPERTY_NAMES};
+pub use property_data::UNICODE_VERSION;
+use property_data::{CODE_RANGES_NUM, PROPERTY_NAMES, SCRIPT_EXTENSIONS};
 use wb_data::{WbType, WB_RANGES};
 
 // === Unicode ISO 8859-1 Ctype Table ===
@@ -164,6 +171,14 @@ pub fn onigenc_unicode_mbc_case_fold(
     let p_start = *pp;
     *pp += len;
 
+    if (flag & ONIGENC_CASE_FOLD_TURKISH_AZERI) != 0 {
+        if code == 0x0049 {
+            return enc.code_to_mbc(0x0131, fold);
+        } else if code == 0x0130 {
+            return enc.code_to_mbc(0x0069, fold);
+        }
+    }
+
     if case_fold_is_not_ascii_only(flag) || code < 128 {
         if let Some((index, fold_len)) = unfold_key(code) {
             if fold_len == 1 {
@@ -321,16 +336,37 @@ pub fn onigenc_unicode_apply_all_case_fold(
     flag: OnigCaseFoldType,
     f: &mut dyn FnMut(OnigCodePoint, &[OnigCodePoint]) -> i32,
 ) -> i32 {
+    // The 26 ASCII pairs are all the fold table has left to
+    // give, so skip the walk over it.
+    if case_fold_is_ascii_letters_only(flag) {
+        return onigenc_ascii_apply_all_case_fold(flag, f);
+    }
+
     // Normal FOLDS1 entries
     let mut r = apply_case_fold1(flag, 0, FOLDS1_NORMAL_END_INDEX, f);
     if r != 0 {
         return r;
     }
 
-    // Locale entries (non-Turkish: include all)
-    r = apply_case_fold1(flag, FOLDS1_NORMAL_END_INDEX, FOLDS1_END_INDEX, f);
-    if r != 0 {
-        return r;
+    // Locale entries: Turkish/Azeri pairs I with dotless i and dotted I
+    // with i; otherwise the default I <-> i entries apply.
+    if (flag & ONIGENC_CASE_FOLD_TURKISH_AZERI) != 0 {
+        for (from, to) in [
+            (0x0049, 0x0131),
+            (0x0131, 0x0049),
+            (0x0069, 0x0130),
+            (0x0130, 0x0069),
+        ] {
+            r = f(from, &[to]);
+            if r != 0 {
+                return r;
+            }
+        }
+    } else {
+        r = apply_case_fold1(flag, FOLDS1_NORMAL_END_INDEX, FOLDS1_END_INDEX, f);
+        if r != 0 {
+            return r;
+        }
     }
 
     // Multi-char folds only if MULTI_CHAR flag is set
@@ -342,9 +378,11 @@ pub fn onigenc_unicode_apply_all_case_fold(
     if r != 0 {
         return r;
     }
-    r = apply_case_fold2(FOLDS2_NORMAL_END_INDEX, FOLDS2_END_INDEX, f);
-    if r != 0 {
-        return r;
+    if (flag & ONIGENC_CASE_FOLD_TURKISH_AZERI) == 0 {
+        r = apply_case_fold2(FOLDS2_NORMAL_END_INDEX, FOLDS2_END_INDEX, f);
+        if r != 0 {
+            return r;
+        }
     }
 
     r = apply_case_fold3(0, FOLDS3_NORMAL_END_INDEX, f);
@@ -364,6 +402,11 @@ pub fn onigenc_unicode_get_case_fold_codes_by_str(
     _end: usize,
     items: &mut [OnigCaseFoldCodeItem],
 ) -> i32 {
+    // Only A-Z and a-z fold to each other, no table lookups
+    if case_fold_is_ascii_letters_only(flag) {
+        return onigenc_ascii_get_case_fold_codes_by_str(flag, p, _end, items);
+    }
+
     let remaining = p.len(); // use slice length, not end parameter
     let mut n = 0usize; // number of items accumulated
 
@@ -379,6 +422,22 @@ pub fn onigenc_unicode_get_case_fold_codes_by_str(
     orig_codes[0] = code;
     lens[0] = len0;
 
+    if (flag & ONIGENC_CASE_FOLD_TURKISH_AZERI) != 0 {
+        let other = match code {
+            0x0049 => Some(0x0131),
+            0x0130 => Some(0x0069),
+            0x0131 => Some(0x0049),
+            0x0069 => Some(0x0130),
+            _ => None,
+        };
+        if let Some(other) = other {
+            items[0].byte_len = len0 as i32;
+            items[0].code_len = 1;
+            items[0].code[0] = other;
+            return 1;
+        }
+    }
+
     // Get canonical form of first codepoint
     let buk1 = unfold_key(orig_codes[0]);
     if let Some((index, fold_len)) = buk1 {
@@ -636,13 +695,251 @@ pub fn onigenc_unicode_get_case_fold_codes_by_str(
     n as i32
 }
 
+// === Case Conversion ===
+// Per-character lower/upper case mapping with the same flag semantics as
+// case folding. For fold-normalized keys use regenc::case_fold_str instead.
+
+/// Lowercase `s` one character at a time.
+///
+/// With `ONIGENC_CASE_FOLD_TURKISH_AZERI`, `I` maps to `ı` and `İ` to `i`;
+/// with `ONIGENC_CASE_FOLD_ASCII_ONLY`, non-ASCII characters are kept.
+pub fn to_lower(s: &str, flag: OnigCaseFoldType) -> String {
+    let turkish = (flag & ONIGENC_CASE_FOLD_TURKISH_AZERI) != 0;
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            'I' if turkish => out.push('ı'),
+            'İ' if turkish => out.push('i'),
+            c if case_fold_is_ascii_only(flag) && !c.is_ascii() => out.push(c),
+            c => out.extend(c.to_lowercase()),
+        }
+    }
+    out
+}
+
+/// Uppercase `s` one character at a time. Flags as for [`to_lower`], with
+/// Turkish/Azeri mapping `i` to `İ` and `ı` to `I`.
+pub fn to_upper(s: &str, flag: OnigCaseFoldType) -> String {
+    let turkish = (flag & ONIGENC_CASE_FOLD_TURKISH_AZERI) != 0;
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            'i' if turkish => out.push('İ'),
+            'ı' if turkish => out.push('I'),
+            c if case_fold_is_ascii_only(flag) && !c.is_ascii() => out.push(c),
+            c => out.extend(c.to_uppercase()),
+        }
+    }
+    out
+}
+
+/// Case map one character for regenc::onig_case_map: the full mapping the
+/// ONIGENC_CASE_* bits in `flags` select, or `c` itself. DOWNCASE wins over
+/// UPCASE for a character that has both (swap case).
+pub(crate) fn case_map_char(c: char, flags: OnigCaseFoldType, out: &mut Vec<OnigCodePoint>) {
+    let turkish = (flags & ONIGENC_CASE_FOLD_TURKISH_AZERI) != 0;
+    let down = (flags & ONIGENC_CASE_DOWNCASE) != 0;
+    let title = (flags & ONIGENC_CASE_TITLECASE) != 0;
+    let up = title || (flags & ONIGENC_CASE_UPCASE) != 0;
+    match c {
+        'I' if turkish && down => out.push('ı' as u32),
+        'İ' if turkish && down => out.push('i' as u32),
+        'i' if turkish && up => out.push('İ' as u32),
+        _ => {
+            let lower = c.to_lowercase();
+            if down && !(lower.len() == 1 && lower.clone().eq([c])) {
+                out.extend(lower.map(u32::from));
+            } else if title {
+                title_case(c, out);
+            } else if up {
+                out.extend(c.to_uppercase().map(u32::from));
+            } else {
+                out.push(c as u32);
+            }
+        }
+    }
+}
+
+/// Titlecase mapping of `c` (UnicodeData plus SpecialCasing).
+fn title_case(c: char, out: &mut Vec<OnigCodePoint>) {
+    let single = match c {
+        '\u{1C4}'..='\u{1C6}' => Some('\u{1C5}'),
+        '\u{1C7}'..='\u{1C9}' => Some('\u{1C8}'),
+        '\u{1CA}'..='\u{1CC}' => Some('\u{1CB}'),
+        '\u{1F1}'..='\u{1F3}' => Some('\u{1F2}'),
+        // Georgian Mkhedruli uppercases to Mtavruli but titlecases to itself.
+        '\u{10D0}'..='\u{10FA}' | '\u{10FD}'..='\u{10FF}' => Some(c),
+        _ => None,
+    };
+    if let Some(t) = single {
+        out.push(t as u32);
+        return;
+    }
+    let upper = c.to_uppercase();
+    if upper.len() == 1 {
+        out.extend(upper.map(u32::from));
+        return;
+    }
+    if ('\u{1F80}'..='\u{1FFF}').contains(&c) {
+        // Greek with ypogegrammeni keeps the iota subscript: the capital
+        // with prosgegrammeni if there is one, else U+0345 instead of IOTA.
+        let lower: Vec<char> = c.to_lowercase().collect();
+        if let Some(t) = ('\u{1F88}'..='\u{1FFC}').find(|t| {
+            !t.is_lowercase()
+                && t.to_uppercase().len() > 1
+                && t.to_lowercase().eq(lower.iter().copied())
+        }) {
+            out.push(t as u32);
+            return;
+        }
+        let start = out.len();
+        out.extend(upper.map(u32::from));
+        if out.len() > start + 1 && out[out.len() - 1] == 0x399 {
+            *out.last_mut().unwrap() = 0x345;
+        }
+        return;
+    }
+    // Ligatures and sharp s: the first cased letter uppercase, the rest
+    // lowercase ("ß" -> "Ss", "ﬁ" -> "Fi", "ŉ" -> "ʼN").
+    let mut seen_cased = false;
+    for u in upper {
+        if seen_cased {
+            out.extend(u.to_lowercase().map(u32::from));
+        } else {
+            out.push(u as u32);
+            seen_cased = u.is_uppercase() || u.is_lowercase();
+        }
+    }
+}
+
+/// Every string an IGNORECASE literal `s` matches under `flag`, in the
+/// form the compiler expands it into (see unravel_case_fold_string in
+/// regcomp.rs): at each character, the original plus the case fold
+/// alternatives the encoding lists for it. The first variant is `s` itself.
+///
+/// Variants are produced lazily; [`FoldVariants::total`] counts them
+/// without enumerating.
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::encodings::utf8::ONIG_ENCODING_UTF8;
+/// use ferroni::oniguruma::ONIGENC_CASE_FOLD_MIN;
+/// use ferroni::unicode::fold_variants;
+///
+/// let variants = fold_variants(b"ok", &ONIG_ENCODING_UTF8, ONIGENC_CASE_FOLD_MIN);
+/// // k also folds from KELVIN SIGN
+/// assert_eq!(variants.total(), Some(6));
+/// let all: Vec<String> = variants.map(|v| String::from_utf8(v).unwrap()).collect();
+/// assert_eq!(all[..3], ["ok", "oK", "o\u{212a}"]);
+/// ```
+pub fn fold_variants(s: &[u8], enc: OnigEncoding, flag: OnigCaseFoldType) -> FoldVariants {
+    let mut items = vec![
+        OnigCaseFoldCodeItem {
+            byte_len: 0,
+            code_len: 0,
+            code: [0; ONIGENC_MAX_COMP_CASE_FOLD_CODE_LEN],
+        };
+        ONIGENC_GET_CASE_FOLD_CODES_MAX_NUM
+    ];
+    let mut choices: Vec<Vec<Vec<u8>>> = Vec::new();
+    let mut buf = [0u8; ONIGENC_CODE_TO_MBC_MAXLEN];
+    let mut pos = 0;
+    while pos < s.len() {
+        let one_len = enc.mbc_enc_len(&s[pos..]);
+        // A truncated trailing sequence is kept as is.
+        if pos + one_len > s.len() {
+            choices.push(vec![s[pos..].to_vec()]);
+            break;
+        }
+        let n = enc.get_case_fold_codes_by_str(flag, &s[pos..], s.len() - pos, &mut items);
+        let items = &items[..n.max(0) as usize];
+        // Single code point folds replace one character; multi-code-point
+        // ones all span the same bytes of `s` (e.g. "ss" for ß).
+        let span = if items.iter().all(|it| it.code_len == 1) {
+            one_len
+        } else {
+            items[0].byte_len as usize
+        };
+        let mut here = vec![s[pos..pos + span].to_vec()];
+        for it in items {
+            let mut variant = Vec::new();
+            for &code in &it.code[..it.code_len as usize] {
+                let len = enc.code_to_mbc(code, &mut buf) as usize;
+                variant.extend_from_slice(&buf[..len]);
+            }
+            if !here.contains(&variant) {
+                here.push(variant);
+            }
+        }
+        choices.push(here);
+        pos += span;
+    }
+    FoldVariants {
+        next: Some(vec![0; choices.len()]),
+        choices,
+    }
+}
+
+/// Iterator over the case variants of a string, from [`fold_variants`].
+#[derive(Debug, Clone)]
+pub struct FoldVariants {
+    /// Alternatives for each piece of the string, original first.
+    choices: Vec<Vec<Vec<u8>>>,
+    /// Choice per piece for the next variant; None when done.
+    next: Option<Vec<usize>>,
+}
+
+impl FoldVariants {
+    /// Number of variants, or None if it does not fit in a usize. Counts
+    /// all variants, including ones already returned.
+    pub fn total(&self) -> Option<usize> {
+        self.choices
+            .iter()
+            .try_fold(1usize, |acc, c| acc.checked_mul(c.len()))
+    }
+}
+
+impl Iterator for FoldVariants {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Vec<u8>> {
+        let idx = self.next.as_mut()?;
+        let variant = self
+            .choices
+            .iter()
+            .zip(idx.iter())
+            .flat_map(|(c, &i)| c[i].iter().copied())
+            .collect();
+        // Advance like an odometer, last piece fastest
+        let mut k = idx.len();
+        loop {
+            if k == 0 {
+                self.next = None;
+                break;
+            }
+            k -= 1;
+            idx[k] += 1;
+            if idx[k] < self.choices[k].len() {
+                break;
+            }
+            idx[k] = 0;
+        }
+        Some(variant)
+    }
+}
+
 // === User-Defined Unicode Properties ===
 // Port of C's UserDefinedPropertyValue + onig_unicode_define_user_property
 
-use std::sync::Mutex;
+use core::cell::RefCell;
+
+use crate::sys::{per_thread, AtomicU64, Mutex};
 
-/// Maximum number of user-defined properties (matches C's USER_DEFINED_PROPERTY_MAX_NUM).
-const USER_DEFINED_PROPERTY_MAX_NUM: usize = 32;
+/// Ctype of the first property scoped to one compile. Global properties
+/// take the ctypes between CODE_RANGES_NUM and this.
+/// C caps the registry at USER_DEFINED_PROPERTY_MAX_NUM = 32.
+const SCOPED_PROPERTY_CTYPE_BASE: usize = 1 << 20;
 
 struct UserProperty {
     /// Normalized name (lowercase, no spaces/hyphens/underscores).
@@ -651,7 +948,26 @@ struct UserProperty {
     ranges: Vec<OnigCodePoint>,
 }
 
-static USER_DEFINED_PROPERTIES: Mutex<Vec<UserProperty>> = Mutex::new(Vec::new());
+/// Global properties by ctype - CODE_RANGES_NUM. An undefined property
+/// leaves a `None`: slots are never reused, so a ctype never changes
+/// meaning.
+static USER_DEFINED_PROPERTIES: Mutex<Vec<Option<UserProperty>>> = Mutex::new(Vec::new());
+
+/// Bumped whenever a global property is defined or undefined.
+static USER_PROPERTY_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// How many times the global user-defined properties have changed. A
+/// pattern compiled at one generation may resolve `\p{...}` differently at
+/// another; `RegexCache` keys its entries on it.
+pub(crate) fn user_property_generation() -> u64 {
+    USER_PROPERTY_GENERATION.load(core::sync::atomic::Ordering::Acquire)
+}
+
+per_thread! {
+    /// Properties visible to the compile running on this thread only
+    /// (`RegexBuilder::property`), by ctype - SCOPED_PROPERTY_CTYPE_BASE.
+    static SCOPED_PROPERTIES: RefCell<Vec<UserProperty>> = const { RefCell::new(Vec::new()) };
+}
 
 /// Normalize a property name: strip spaces/hyphens/underscores, lowercase.
 /// Returns None if the name contains non-ASCII bytes or exceeds buffer size.
@@ -674,33 +990,116 @@ fn normalize_property_name(name: &[u8]) -> Option<Vec<u8>> {
 }
 
 /// Register a user-defined Unicode property with associated code point ranges.
-/// Ranges should be in `[start, end, start, end, ...]` pair format.
+/// Ranges should be sorted, in `[start, end, start, end, ...]` pair format.
 /// Returns `Ok(())` on success, or `Err(error_code)` on failure.
 #[cfg_attr(coverage_nightly, coverage(off))]
 pub fn onig_unicode_define_user_property(name: &[u8], ranges: &[OnigCodePoint]) -> Result<(), i32> {
     let normalized = normalize_property_name(name).ok_or(ONIGERR_INVALID_CHAR_PROPERTY_NAME)?;
 
-    let mut props = USER_DEFINED_PROPERTIES.lock().unwrap();
+    let mut props = USER_DEFINED_PROPERTIES.lock();
 
     // Check for duplicate
-    for prop in props.iter() {
-        if prop.name == normalized {
-            return Err(ONIGERR_INVALID_CHAR_PROPERTY_NAME);
-        }
+    if props.iter().flatten().any(|prop| prop.name == normalized) {
+        return Err(ONIGERR_INVALID_CHAR_PROPERTY_NAME);
     }
 
-    if props.len() >= USER_DEFINED_PROPERTY_MAX_NUM {
+    if CODE_RANGES_NUM + props.len() >= SCOPED_PROPERTY_CTYPE_BASE {
         return Err(ONIGERR_TOO_MANY_USER_DEFINED_OBJECTS);
     }
 
-    props.push(UserProperty {
+    props.push(Some(UserProperty {
         name: normalized,
         ranges: ranges.to_vec(),
-    });
+    }));
+    USER_PROPERTY_GENERATION.fetch_add(1, core::sync::atomic::Ordering::Release);
+
+    Ok(())
+}
 
+/// Remove a property registered with [`onig_unicode_define_user_property`].
+/// Regexes already compiled keep matching it; new patterns using the name
+/// fail with `ONIGERR_INVALID_CHAR_PROPERTY_NAME` until it is defined
+/// again.
+pub fn onig_unicode_undefine_user_property(name: &[u8]) -> Result<(), i32> {
+    let normalized = normalize_property_name(name).ok_or(ONIGERR_INVALID_CHAR_PROPERTY_NAME)?;
+    let mut props = USER_DEFINED_PROPERTIES.lock();
+    let slot = props
+        .iter_mut()
+        .find(|prop| prop.as_ref().is_some_and(|prop| prop.name == normalized))
+        .ok_or(ONIGERR_INVALID_CHAR_PROPERTY_NAME)?;
+    *slot = None;
+    USER_PROPERTY_GENERATION.fetch_add(1, core::sync::atomic::Ordering::Release);
     Ok(())
 }
 
+/// Run `f` (a compile) with `props` visible as user-defined properties on
+/// this thread, shadowing global ones of the same name.
+pub(crate) fn with_scoped_properties<R>(
+    props: &[(Vec<u8>, Vec<OnigCodePoint>)],
+    f: impl FnOnce() -> R,
+) -> Result<R, i32> {
+    let mut scoped = Vec::with_capacity(props.len());
+    for (name, ranges) in props {
+        let name = normalize_property_name(name).ok_or(ONIGERR_INVALID_CHAR_PROPERTY_NAME)?;
+        if scoped.iter().any(|prop: &UserProperty| prop.name == name) {
+            return Err(ONIGERR_INVALID_CHAR_PROPERTY_NAME);
+        }
+        scoped.push(UserProperty {
+            name,
+            ranges: ranges.clone(),
+        });
+    }
+
+    struct Restore(Vec<UserProperty>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED_PROPERTIES.with(|s| *s.borrow_mut() = core::mem::take(&mut self.0));
+        }
+    }
+    let _restore = Restore(SCOPED_PROPERTIES.with(|s| s.replace(scoped)));
+    Ok(f())
+}
+
+/// Ctype of a user-defined property with normalized name `key`.
+fn user_property_to_ctype(key: &[u8]) -> Option<usize> {
+    let scoped = SCOPED_PROPERTIES.with(|s| s.borrow().iter().position(|prop| prop.name == key));
+    if let Some(i) = scoped {
+        return Some(SCOPED_PROPERTY_CTYPE_BASE + i);
+    }
+    let props = USER_DEFINED_PROPERTIES.lock();
+    props
+        .iter()
+        .position(|prop| prop.as_ref().is_some_and(|prop| prop.name == key))
+        .map(|i| CODE_RANGES_NUM + i)
+}
+
+/// Call `f` with the ranges of user-defined property `ctype`, if it exists.
+fn with_user_property<R>(ctype: usize, f: impl FnOnce(&[OnigCodePoint]) -> R) -> Option<R> {
+    if ctype >= SCOPED_PROPERTY_CTYPE_BASE {
+        SCOPED_PROPERTIES.with(|s| {
+            let scoped = s.borrow();
+            scoped
+                .get(ctype - SCOPED_PROPERTY_CTYPE_BASE)
+                .map(|prop| f(&prop.ranges))
+        })
+    } else {
+        let props = USER_DEFINED_PROPERTIES.lock();
+        props
+            .get(ctype - CODE_RANGES_NUM)?
+            .as_ref()
+            .map(|prop| f(&prop.ranges))
+    }
+}
+
+/// Copy of the ranges of user-defined property `ctype`, for the parser:
+/// unlike the built-in tables they cannot be lent out as `'static`.
+pub(crate) fn user_property_code_range(ctype: u32) -> Option<Vec<OnigCodePoint>> {
+    if (ctype as usize) < CODE_RANGES_NUM {
+        return None;
+    }
+    with_user_property(ctype as usize, |ranges| ranges.to_vec())
+}
+
 // === Unicode Property Functions ===
 
 /// Convert Unicode property name to ctype.
@@ -723,23 +1122,55 @@ pub fn onigenc_unicode_property_name_to_ctype(p: &[u8]) -> i32 {
         len += 1;
     }
     let key = &buf[..len];
+    // `sc=Value` / `scx=Value`, with `:` or the long property names
+    if let Some(i) = key.iter().position(|&b| b == b'=' || b == b':') {
+        return script_property_to_ctype(&key[..i], &key[i + 1..]);
+    }
     // Binary search on sorted PROPERTY_NAMES
     match PROPERTY_NAMES.binary_search_by_key(&key, |(name, _)| name.as_bytes()) {
         Ok(idx) => PROPERTY_NAMES[idx].1 as i32,
-        Err(_) => {
-            // Check user-defined properties
-            if let Ok(props) = USER_DEFINED_PROPERTIES.lock() {
-                for (i, prop) in props.iter().enumerate() {
-                    if prop.name == key {
-                        return (CODE_RANGES_NUM + i) as i32;
-                    }
-                }
-            }
-            ONIGERR_INVALID_CHAR_PROPERTY_NAME
-        }
+        Err(_) => match user_property_to_ctype(key) {
+            Some(ctype) => ctype as i32,
+            None => ONIGERR_INVALID_CHAR_PROPERTY_NAME,
+        },
     }
 }
 
+/// Resolve a normalized `sc`/`scx` property and script name to the ctype of
+/// the script's Script or Script_Extensions ranges.
+fn script_property_to_ctype(prop: &[u8], value: &[u8]) -> i32 {
+    let extensions = match prop {
+        b"sc" | b"script" => false,
+        b"scx" | b"scriptextensions" => true,
+        _ => return ONIGERR_INVALID_CHAR_PROPERTY_NAME,
+    };
+    let Ok(idx) = PROPERTY_NAMES.binary_search_by_key(&value, |(name, _)| name.as_bytes()) else {
+        return ONIGERR_INVALID_CHAR_PROPERTY_NAME;
+    };
+    let script = PROPERTY_NAMES[idx].1;
+    match SCRIPT_EXTENSIONS.binary_search_by_key(&script, |&(sc, _)| sc) {
+        Ok(i) if extensions => SCRIPT_EXTENSIONS[i].1 as i32,
+        Ok(_) => script as i32,
+        Err(_) => ONIGERR_INVALID_CHAR_PROPERTY_NAME,
+    }
+}
+
+/// Code range pairs of a built-in ctype.
+#[cfg(not(feature = "compact-tables"))]
+#[inline]
+fn code_ranges(ctype: usize) -> &'static [OnigCodePoint] {
+    property_data::CODE_RANGES[ctype]
+}
+
+/// Code range pairs of a built-in ctype, unpacked on first use.
+#[cfg(feature = "compact-tables")]
+fn code_ranges(ctype: usize) -> &'static [OnigCodePoint] {
+    use crate::sys::OnceLock;
+    static DECODED: [OnceLock<Box<[OnigCodePoint]>>; CODE_RANGES_NUM] =
+        [const { OnceLock::new() }; CODE_RANGES_NUM];
+    DECODED[ctype].get_or_init(|| pack::unpack(property_data::CODE_RANGES_PACKED[ctype]))
+}
+
 /// Check if code point is of the given Unicode ctype.
 /// Port of onigenc_unicode_is_code_ctype from unicode.c
 pub fn onigenc_unicode_is_code_ctype(code: OnigCodePoint, ctype: u32) -> bool {
@@ -750,29 +1181,25 @@ pub fn onigenc_unicode_is_code_ctype(code: OnigCodePoint, ctype: u32) -> bool {
 
     if (ctype as usize) >= CODE_RANGES_NUM {
         // Check user-defined properties
-        let user_idx = (ctype as usize) - CODE_RANGES_NUM;
-        if let Ok(props) = USER_DEFINED_PROPERTIES.lock() {
-            if user_idx < props.len() {
-                let ranges = &props[user_idx].ranges;
-                let n = ranges.len() / 2;
-                let mut low = 0usize;
-                let mut high = n;
-                while low < high {
-                    let mid = (low + high) / 2;
-                    if code > ranges[mid * 2 + 1] {
-                        low = mid + 1;
-                    } else {
-                        high = mid;
-                    }
+        return with_user_property(ctype as usize, |ranges| {
+            let n = ranges.len() / 2;
+            let mut low = 0usize;
+            let mut high = n;
+            while low < high {
+                let mid = (low + high) / 2;
+                if code > ranges[mid * 2 + 1] {
+                    low = mid + 1;
+                } else {
+                    high = mid;
                 }
-                return low < n && code >= ranges[low * 2];
             }
-        }
-        return false;
+            low < n && code >= ranges[low * 2]
+        })
+        .unwrap_or(false);
     }
 
     // Binary search on code range pairs
-    let ranges = CODE_RANGES[ctype as usize];
+    let ranges = code_ranges(ctype as usize);
     let n = ranges.len() / 2;
     let mut low = 0usize;
     let mut high = n;
@@ -792,10 +1219,10 @@ pub fn onigenc_unicode_is_code_ctype(code: OnigCodePoint, ctype: u32) -> bool {
 pub fn onigenc_unicode_ctype_code_range(ctype: u32) -> Option<&'static [OnigCodePoint]> {
     if (ctype as usize) >= CODE_RANGES_NUM {
         // User-defined properties cannot return &'static references since they
-        // are dynamically allocated. Callers should use is_code_ctype instead.
+        // are dynamically allocated. The parser uses user_property_code_range.
         return None;
     }
-    Some(CODE_RANGES[ctype as usize])
+    Some(code_ranges(ctype as usize))
 }
 
 // ============================================================================
@@ -1321,3 +1748,271 @@ pub fn onigenc_wb_is_break_position(
     // WB999: Any / Any
     true
 }
+
+// ============================================================================
+// Segmentation iterators
+// ============================================================================
+
+/// Iterator over the byte ranges between consecutive break positions,
+/// returned by [`graphemes`] and [`words`].
+pub struct Segments<'a> {
+    enc: OnigEncoding,
+    data: &'a [u8],
+    pos: usize,
+    is_break: fn(OnigEncoding, &[u8], usize, usize, usize) -> bool,
+}
+
+impl Iterator for Segments<'_> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        let end = self.data.len();
+        if self.pos >= end {
+            return None;
+        }
+        let start = self.pos;
+        let mut p = start;
+        loop {
+            p = (p + self.enc.mbc_enc_len(&self.data[p..])).min(end);
+            if p >= end || (self.is_break)(self.enc, self.data, p, 0, end) {
+                break;
+            }
+        }
+        self.pos = p;
+        Some(start..p)
+    }
+}
+
+impl core::iter::FusedIterator for Segments<'_> {}
+
+/// Split `data` into extended grapheme clusters, the units `\X` matches.
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::encodings::utf8::ONIG_ENCODING_UTF8;
+/// use ferroni::unicode::graphemes;
+///
+/// let s = "e\u{301}🇩🇪!";
+/// let parts: Vec<&str> = graphemes(&ONIG_ENCODING_UTF8, s.as_bytes())
+///     .map(|r| &s[r])
+///     .collect();
+/// assert_eq!(parts, ["e\u{301}", "🇩🇪", "!"]);
+/// ```
+pub fn graphemes(enc: OnigEncoding, data: &[u8]) -> Segments<'_> {
+    Segments {
+        enc,
+        data,
+        pos: 0,
+        is_break: onigenc_egcb_is_break_position,
+    }
+}
+
+/// Split `data` at Unicode word boundaries, the positions `\y{w}` matches.
+/// Words, runs of whitespace and each punctuation character all come out
+/// as separate segments.
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::encodings::utf8::ONIG_ENCODING_UTF8;
+/// use ferroni::unicode::words;
+///
+/// let s = "can't stop, 3.14";
+/// let parts: Vec<&str> = words(&ONIG_ENCODING_UTF8, s.as_bytes())
+///     .map(|r| &s[r])
+///     .collect();
+/// assert_eq!(parts, ["can't", " ", "stop", ",", " ", "3.14"]);
+/// ```
+pub fn words(enc: OnigEncoding, data: &[u8]) -> Segments<'_> {
+    Segments {
+        enc,
+        data,
+        pos: 0,
+        is_break: onigenc_wb_is_break_position,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encodings::utf8::ONIG_ENCODING_UTF8;
+
+    #[test]
+    fn unicode_16_data() {
+        assert_eq!(UNICODE_VERSION, (16, 0, 0));
+        let is = |c: u32, name: &str| {
+            let ctype = onigenc_unicode_property_name_to_ctype(name.as_bytes());
+            onigenc_unicode_is_code_ctype(c, ctype as u32)
+        };
+        // GARAY CAPITAL LETTER A, FACE WITH BAGS UNDER EYES (both new in 16.0)
+        assert!(is(0x10D50, "Garay") && is(0x10D50, "Lu"));
+        assert!(is(0x1FAE9, "Emoji") && is(0x1FAE9, "Extended_Pictographic"));
+        assert!(!is(0x1FAEA, "Emoji"));
+    }
+
+    #[test]
+    fn script_extensions_properties() {
+        let ctype = |name: &str| onigenc_unicode_property_name_to_ctype(name.as_bytes());
+        let is = |c: char, name: &str| onigenc_unicode_is_code_ctype(c as u32, ctype(name) as u32);
+
+        assert_eq!(ctype("sc=Greek"), ctype("Greek"));
+        assert_eq!(ctype("Script_Extensions=Greek"), ctype("scx:Grek"));
+        // U+0342 COMBINING GREEK PERISPOMENI is Inherited, used by Greek.
+        assert!(!is('\u{342}', "Greek") && is('\u{342}', "scx=Greek"));
+        assert!(!is('\u{342}', "scx=Inherited") && is('\u{342}', "sc=Inherited"));
+        // U+3001 IDEOGRAPHIC COMMA is Common, shared by Han and the kana.
+        for script in ["scx:Han", "scx:Hiragana", "scx:Katakana"] {
+            assert!(is('、', script), "{script}");
+        }
+        assert!(is('、', "Common") && !is('、', "scx=Common"));
+        assert!(is('α', "scx=Greek") && !is('a', "scx=Greek"));
+
+        for bad in ["scx=Lu", "scx=", "scx=Klingon", "gc=Greek", "=Greek"] {
+            assert_eq!(ctype(bad), ONIGERR_INVALID_CHAR_PROPERTY_NAME, "{bad}");
+        }
+    }
+
+    #[test]
+    fn user_properties_grow_and_undefine() {
+        let ctype = |name: &str| onigenc_unicode_property_name_to_ctype(name.as_bytes());
+        // Past C's limit of 32.
+        for i in 0..40 {
+            let c = 0x4e00 + i;
+            onig_unicode_define_user_property(format!("ut_many_{i}").as_bytes(), &[c, c]).unwrap();
+        }
+        let last = ctype("UT many 39");
+        assert!(onigenc_unicode_is_code_ctype(0x4e00 + 39, last as u32));
+        assert!(!onigenc_unicode_is_code_ctype(0x4e00, last as u32));
+        assert_eq!(
+            onig_unicode_define_user_property(b"ut_many_0", &[0x41, 0x41]),
+            Err(ONIGERR_INVALID_CHAR_PROPERTY_NAME)
+        );
+
+        // An undefined ctype stays dead, even after the name is reused.
+        onig_unicode_undefine_user_property(b"UT-MANY-39").unwrap();
+        assert_eq!(ctype("ut_many_39"), ONIGERR_INVALID_CHAR_PROPERTY_NAME);
+        assert!(!onigenc_unicode_is_code_ctype(0x4e00 + 39, last as u32));
+        assert_eq!(
+            onig_unicode_undefine_user_property(b"ut_many_39"),
+            Err(ONIGERR_INVALID_CHAR_PROPERTY_NAME)
+        );
+        onig_unicode_define_user_property(b"ut_many_39", &[0x41, 0x41]).unwrap();
+        assert_ne!(ctype("ut_many_39"), last);
+        assert_eq!(
+            user_property_code_range(ctype("ut_many_39") as u32),
+            Some(vec![0x41, 0x41])
+        );
+    }
+
+    #[test]
+    fn scoped_user_properties() {
+        let ctype = |name: &str| onigenc_unicode_property_name_to_ctype(name.as_bytes());
+        onig_unicode_define_user_property(b"ut_scoped", &[0x61, 0x61]).unwrap();
+        let global = ctype("ut_scoped");
+        let props = [
+            (b"ut_scoped".to_vec(), vec![0x62, 0x62]),
+            (b"ut_local".to_vec(), vec![0x63, 0x63]),
+        ];
+        with_scoped_properties(&props, || {
+            assert!(onigenc_unicode_is_code_ctype(
+                0x62,
+                ctype("ut_scoped") as u32
+            ));
+            assert!(onigenc_unicode_is_code_ctype(
+                0x63,
+                ctype("UT_Local") as u32
+            ));
+            // Other threads only see the global registry.
+            #[cfg(feature = "std")]
+            std::thread::spawn(move || {
+                assert_eq!(onigenc_unicode_property_name_to_ctype(b"ut_scoped"), global);
+                assert!(onigenc_unicode_property_name_to_ctype(b"ut_local") < 0);
+            })
+            .join()
+            .unwrap();
+        })
+        .unwrap();
+        assert_eq!(ctype("ut_scoped"), global);
+        assert_eq!(ctype("ut_local"), ONIGERR_INVALID_CHAR_PROPERTY_NAME);
+
+        let dup = [(b"ut_x".to_vec(), vec![]), (b"UT-X".to_vec(), vec![])];
+        assert_eq!(
+            with_scoped_properties(&dup, || ()),
+            Err(ONIGERR_INVALID_CHAR_PROPERTY_NAME)
+        );
+    }
+
+    #[test]
+    fn test_to_lower_to_upper() {
+        let tr = ONIGENC_CASE_FOLD_MIN | ONIGENC_CASE_FOLD_TURKISH_AZERI;
+        assert_eq!(to_lower("ÀÉ Iİ", ONIGENC_CASE_FOLD_MIN), "àé ii\u{307}");
+        assert_eq!(to_lower("Iİ", tr), "ıi");
+        assert_eq!(to_upper("ıi", tr), "Iİ");
+        assert_eq!(to_upper("straße", ONIGENC_CASE_FOLD_MIN), "STRASSE");
+        assert_eq!(to_upper("éa", ONIGENC_CASE_FOLD_ASCII_ONLY), "éA");
+
+        // Lowercasing never changes the fold key.
+        let enc = &ONIG_ENCODING_UTF8;
+        for s in ["Ǆemal", "ΣΊΣΥΦΟΣ", "Straße"] {
+            let lower = to_lower(s, ONIGENC_CASE_FOLD_MIN);
+            assert_eq!(
+                case_fold_str(enc, ONIGENC_CASE_FOLD_MIN, lower.as_bytes()),
+                case_fold_str(enc, ONIGENC_CASE_FOLD_MIN, s.as_bytes())
+            );
+        }
+    }
+
+    #[test]
+    fn fold_variants_match_ignorecase_literal() {
+        use crate::api::Regex;
+        use std::collections::HashSet;
+        let enc = &ONIG_ENCODING_UTF8;
+        let flag = ONIGENC_CASE_FOLD_MIN;
+        for s in ["Straße", "ss", "Ǆ", "ΣΑΣ", "fi", "a-1", ""] {
+            let re = Regex::new(&format!("(?i)\\A{s}\\z")).unwrap();
+            let variants = fold_variants(s.as_bytes(), enc, flag);
+            let total = variants.total().unwrap();
+            let all: Vec<_> = variants.collect();
+            assert_eq!(all.len(), total, "{s}");
+            assert_eq!(all[0], s.as_bytes());
+            assert_eq!(all.iter().collect::<HashSet<_>>().len(), total, "{s}");
+            for v in &all {
+                let v = core::str::from_utf8(v).unwrap();
+                assert!(re.is_match(v), "{s:?} does not match {v:?}");
+            }
+        }
+
+        let count = |s: &str, flag| fold_variants(s.as_bytes(), enc, flag).total();
+        // ß: itself, ẞ, and s/S/ſ twice
+        assert_eq!(count("ß", flag), Some(11));
+        assert_eq!(count("ab", ONIGENC_CASE_FOLD_ASCII_ONLY), Some(4));
+        assert_eq!(count("é", ONIGENC_CASE_FOLD_ASCII_ONLY), Some(1));
+        assert_eq!(count(&"a".repeat(100), flag), None);
+    }
+
+    #[test]
+    fn graphemes_agree_with_backslash_x() {
+        use crate::api::Regex;
+        let s = "a\r\nb\u{1F469}\u{200D}\u{1F4BB}\u{1F1EF}\u{1F1F5}\u{1F1FA}\u{1F1F8}\u{AC00}\u{11A8}x\u{308}";
+        let segs: Vec<_> = graphemes(&ONIG_ENCODING_UTF8, s.as_bytes()).collect();
+        let re = Regex::new(r"\X").unwrap();
+        let matched: Vec<_> = re.find_iter(s).map(|m| m.range()).collect();
+        assert_eq!(segs, matched);
+        assert_eq!(segs.len(), 8);
+    }
+
+    #[test]
+    fn segments_edge_cases() {
+        let enc = &ONIG_ENCODING_UTF8;
+        assert_eq!(graphemes(enc, b"").next(), None);
+        // A truncated trailing sequence stays in bounds.
+        assert_eq!(graphemes(enc, b"a\xe3\x81").last(), Some(1..3));
+        let parts: Vec<_> = words(enc, "日本 ok".as_bytes()).collect();
+        assert_eq!(parts.last(), Some(&(7..9)));
+    }
+}
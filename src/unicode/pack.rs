@@ -0,0 +1,88 @@
+// unicode/pack.rs - Delta/varint packing for code range tables.
+//
+// With the `compact-tables` feature the property tables are
+// stored as byte streams built at compile time by `pack()` and decoded on
+// first use by `unpack()`. A code range array is a sorted list of
+// (start, end) pairs, so each value is stored as its distance from the
+// previous one in LEB128 form: most ranges take 2-3 bytes instead of 8.
+
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+
+/// Number of bytes `pack(v)` produces.
+pub const fn packed_len(v: &[u32]) -> usize {
+    let mut len = 0;
+    let mut prev = 0;
+    let mut i = 0;
+    while i < v.len() {
+        len += varint_len(v[i] - prev);
+        prev = v[i];
+        i += 1;
+    }
+    len
+}
+
+/// Encode a non-decreasing `v` as LEB128 deltas. `N` must be `packed_len(v)`.
+pub const fn pack<const N: usize>(v: &[u32]) -> [u8; N] {
+    let mut out = [0u8; N];
+    let mut o = 0;
+    let mut prev = 0;
+    let mut i = 0;
+    while i < v.len() {
+        let mut d = v[i] - prev;
+        prev = v[i];
+        while d >= 0x80 {
+            out[o] = (d as u8 & 0x7f) | 0x80;
+            o += 1;
+            d >>= 7;
+        }
+        out[o] = d as u8;
+        o += 1;
+        i += 1;
+    }
+    assert!(o == N);
+    out
+}
+
+/// Decode a stream produced by [`pack`].
+pub fn unpack(bytes: &[u8]) -> Box<[u32]> {
+    let mut out = Vec::with_capacity(bytes.len() / 2);
+    let mut prev = 0u32;
+    let mut d = 0u32;
+    let mut shift = 0;
+    for &b in bytes {
+        d |= ((b & 0x7f) as u32) << shift;
+        if b & 0x80 != 0 {
+            shift += 7;
+            continue;
+        }
+        prev += d;
+        out.push(prev);
+        d = 0;
+        shift = 0;
+    }
+    out.into_boxed_slice()
+}
+
+const fn varint_len(mut d: u32) -> usize {
+    let mut n = 1;
+    while d >= 0x80 {
+        d >>= 7;
+        n += 1;
+    }
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_round_trip() {
+        const V: [u32; 6] = [0x00, 0x7f, 0x80, 0x3fff, 0x4000, 0x10ffff];
+        const P: [u8; packed_len(&V)] = pack(&V);
+        assert_eq!(P.len(), 1 + 1 + 1 + 2 + 1 + 3);
+        assert_eq!(&*unpack(&P), &V);
+        assert!(unpack(&[]).is_empty());
+    }
+}
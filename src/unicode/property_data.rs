@@ -1,17 +1,24 @@
 //! Auto-generated Unicode property data. Do not edit.
 //! Generated from oniguruma-orig/src/unicode_property_data.c
 //! by scripts/gen_unicode_property_data.py
+//! (CR_Scx_* and SCRIPT_EXTENSIONS from the UCD's ScriptExtensions.txt)
 
 #![allow(dead_code, non_upper_case_globals)]
 
-pub const CODE_RANGES_NUM: usize = 629;
+#[cfg(feature = "compact-tables")]
+use super::pack::{pack, packed_len};
+
+/// Unicode version of all the tables (ONIG_UNICODE_VERSION_* in C).
+pub const UNICODE_VERSION: (u8, u8, u8) = (16, 0, 0);
+
+pub const CODE_RANGES_NUM: usize = 729;
 
 // --- Code Range Arrays ---
 // Each array contains pairs of (start, end) code points.
 
-static CR_NEWLINE: [u32; 2] = [0x00000a, 0x00000a];
+const CR_NEWLINE: [u32; 2] = [0x00000a, 0x00000a];
 
-static CR_Alpha: [u32; 1514] = [
+const CR_Alpha: [u32; 1514] = [
     0x000041, 0x00005a, 0x000061, 0x00007a, 0x0000aa, 0x0000aa, 0x0000b5, 0x0000b5, 0x0000ba,
     0x0000ba, 0x0000c0, 0x0000d6, 0x0000d8, 0x0000f6, 0x0000f8, 0x0002c1, 0x0002c6, 0x0002d1,
     0x0002e0, 0x0002e4, 0x0002ec, 0x0002ec, 0x0002ee, 0x0002ee, 0x000345, 0x000345, 0x000363,
@@ -183,14 +190,14 @@ static CR_Alpha: [u32; 1514] = [
     0x031350, 0x0323af,
 ];
 
-static CR_Blank: [u32; 16] = [
+const CR_Blank: [u32; 16] = [
     0x000009, 0x000009, 0x000020, 0x000020, 0x0000a0, 0x0000a0, 0x001680, 0x001680, 0x002000,
     0x00200a, 0x00202f, 0x00202f, 0x00205f, 0x00205f, 0x003000, 0x003000,
 ];
 
-static CR_Cntrl: [u32; 4] = [0x000000, 0x00001f, 0x00007f, 0x00009f];
+const CR_Cntrl: [u32; 4] = [0x000000, 0x00001f, 0x00007f, 0x00009f];
 
-static CR_Digit: [u32; 142] = [
+const CR_Digit: [u32; 142] = [
     0x000030, 0x000039, 0x000660, 0x000669, 0x0006f0, 0x0006f9, 0x0007c0, 0x0007c9, 0x000966,
     0x00096f, 0x0009e6, 0x0009ef, 0x000a66, 0x000a6f, 0x000ae6, 0x000aef, 0x000b66, 0x000b6f,
     0x000be6, 0x000bef, 0x000c66, 0x000c6f, 0x000ce6, 0x000cef, 0x000d66, 0x000d6f, 0x000de6,
@@ -209,7 +216,7 @@ static CR_Digit: [u32; 142] = [
     0x01e4f9, 0x01e5f1, 0x01e5fa, 0x01e950, 0x01e959, 0x01fbf0, 0x01fbf9,
 ];
 
-static CR_Graph: [u32; 1474] = [
+const CR_Graph: [u32; 1474] = [
     0x000021, 0x00007e, 0x0000a1, 0x000377, 0x00037a, 0x00037f, 0x000384, 0x00038a, 0x00038c,
     0x00038c, 0x00038e, 0x0003a1, 0x0003a3, 0x00052f, 0x000531, 0x000556, 0x000559, 0x00058a,
     0x00058d, 0x00058f, 0x000591, 0x0005c7, 0x0005d0, 0x0005ea, 0x0005ef, 0x0005f4, 0x000600,
@@ -376,7 +383,7 @@ static CR_Graph: [u32; 1474] = [
     0x0e007f, 0x0e0100, 0x0e01ef, 0x0f0000, 0x0ffffd, 0x100000, 0x10fffd,
 ];
 
-static CR_Lower: [u32; 1350] = [
+const CR_Lower: [u32; 1350] = [
     0x000061, 0x00007a, 0x0000aa, 0x0000aa, 0x0000b5, 0x0000b5, 0x0000ba, 0x0000ba, 0x0000df,
     0x0000f6, 0x0000f8, 0x0000ff, 0x000101, 0x000101, 0x000103, 0x000103, 0x000105, 0x000105,
     0x000107, 0x000107, 0x000109, 0x000109, 0x00010b, 0x00010b, 0x00010d, 0x00010d, 0x00010f,
@@ -529,7 +536,7 @@ static CR_Lower: [u32; 1350] = [
     0x01df09, 0x01df0b, 0x01df1e, 0x01df25, 0x01df2a, 0x01e030, 0x01e06d, 0x01e922, 0x01e943,
 ];
 
-static CR_Print: [u32; 1466] = [
+const CR_Print: [u32; 1466] = [
     0x000020, 0x00007e, 0x0000a0, 0x000377, 0x00037a, 0x00037f, 0x000384, 0x00038a, 0x00038c,
     0x00038c, 0x00038e, 0x0003a1, 0x0003a3, 0x00052f, 0x000531, 0x000556, 0x000559, 0x00058a,
     0x00058d, 0x00058f, 0x000591, 0x0005c7, 0x0005d0, 0x0005ea, 0x0005ef, 0x0005f4, 0x000600,
@@ -695,7 +702,7 @@ static CR_Print: [u32; 1466] = [
     0x0e0020, 0x0e007f, 0x0e0100, 0x0e01ef, 0x0f0000, 0x0ffffd, 0x100000, 0x10fffd,
 ];
 
-static CR_PosixPunct: [u32; 698] = [
+const CR_PosixPunct: [u32; 698] = [
     0x000021, 0x00002f, 0x00003a, 0x000040, 0x00005b, 0x000060, 0x00007b, 0x00007e, 0x0000a1,
     0x0000a9, 0x0000ab, 0x0000ac, 0x0000ae, 0x0000b1, 0x0000b4, 0x0000b4, 0x0000b6, 0x0000b8,
     0x0000bb, 0x0000bb, 0x0000bf, 0x0000bf, 0x0000d7, 0x0000d7, 0x0000f7, 0x0000f7, 0x0002c2,
@@ -776,13 +783,13 @@ static CR_PosixPunct: [u32; 698] = [
     0x01faf8, 0x01fb00, 0x01fb92, 0x01fb94, 0x01fbef,
 ];
 
-static CR_Space: [u32; 20] = [
+const CR_Space: [u32; 20] = [
     0x000009, 0x00000d, 0x000020, 0x000020, 0x000085, 0x000085, 0x0000a0, 0x0000a0, 0x001680,
     0x001680, 0x002000, 0x00200a, 0x002028, 0x002029, 0x00202f, 0x00202f, 0x00205f, 0x00205f,
     0x003000, 0x003000,
 ];
 
-static CR_Upper: [u32; 1312] = [
+const CR_Upper: [u32; 1312] = [
     0x000041, 0x00005a, 0x0000c0, 0x0000d6, 0x0000d8, 0x0000de, 0x000100, 0x000100, 0x000102,
     0x000102, 0x000104, 0x000104, 0x000106, 0x000106, 0x000108, 0x000108, 0x00010a, 0x00010a,
     0x00010c, 0x00010c, 0x00010e, 0x00010e, 0x000110, 0x000110, 0x000112, 0x000112, 0x000114,
@@ -931,9 +938,9 @@ static CR_Upper: [u32; 1312] = [
     0x01e921, 0x01f130, 0x01f149, 0x01f150, 0x01f169, 0x01f170, 0x01f189,
 ];
 
-static CR_XDigit: [u32; 6] = [0x000030, 0x000039, 0x000041, 0x000046, 0x000061, 0x000066];
+const CR_XDigit: [u32; 6] = [0x000030, 0x000039, 0x000041, 0x000046, 0x000061, 0x000066];
 
-static CR_Word: [u32; 1590] = [
+const CR_Word: [u32; 1590] = [
     0x000030, 0x000039, 0x000041, 0x00005a, 0x00005f, 0x00005f, 0x000061, 0x00007a, 0x0000aa,
     0x0000aa, 0x0000b5, 0x0000b5, 0x0000ba, 0x0000ba, 0x0000c0, 0x0000d6, 0x0000d8, 0x0000f6,
     0x0000f8, 0x0002c1, 0x0002c6, 0x0002d1, 0x0002e0, 0x0002e4, 0x0002ec, 0x0002ec, 0x0002ee,
@@ -1113,7 +1120,7 @@ static CR_Word: [u32; 1590] = [
     0x030000, 0x03134a, 0x031350, 0x0323af, 0x0e0100, 0x0e01ef,
 ];
 
-static CR_Alnum: [u32; 1604] = [
+const CR_Alnum: [u32; 1604] = [
     0x000030, 0x000039, 0x000041, 0x00005a, 0x000061, 0x00007a, 0x0000aa, 0x0000aa, 0x0000b5,
     0x0000b5, 0x0000ba, 0x0000ba, 0x0000c0, 0x0000d6, 0x0000d8, 0x0000f6, 0x0000f8, 0x0002c1,
     0x0002c6, 0x0002d1, 0x0002e0, 0x0002e4, 0x0002ec, 0x0002ec, 0x0002ee, 0x0002ee, 0x000345,
@@ -1295,17 +1302,17 @@ static CR_Alnum: [u32; 1604] = [
     0x031350, 0x0323af,
 ];
 
-static CR_ASCII: [u32; 2] = [0x000000, 0x00007f];
+const CR_ASCII: [u32; 2] = [0x000000, 0x00007f];
 
-static CR_Adlam: [u32; 6] = [0x01e900, 0x01e94b, 0x01e950, 0x01e959, 0x01e95e, 0x01e95f];
+const CR_Adlam: [u32; 6] = [0x01e900, 0x01e94b, 0x01e950, 0x01e959, 0x01e95e, 0x01e95f];
 
-static CR_Ahom: [u32; 6] = [0x011700, 0x01171a, 0x01171d, 0x01172b, 0x011730, 0x011746];
+const CR_Ahom: [u32; 6] = [0x011700, 0x01171a, 0x01171d, 0x01172b, 0x011730, 0x011746];
 
-static CR_Anatolian_Hieroglyphs: [u32; 2] = [0x014400, 0x014646];
+const CR_Anatolian_Hieroglyphs: [u32; 2] = [0x014400, 0x014646];
 
-static CR_Any: [u32; 2] = [0x000000, 0x10ffff];
+const CR_Any: [u32; 2] = [0x000000, 0x10ffff];
 
-static CR_Arabic: [u32; 118] = [
+const CR_Arabic: [u32; 118] = [
     0x000600, 0x000604, 0x000606, 0x00060b, 0x00060d, 0x00061a, 0x00061c, 0x00061e, 0x000620,
     0x00063f, 0x000641, 0x00064a, 0x000656, 0x00066f, 0x000671, 0x0006dc, 0x0006de, 0x0006ff,
     0x000750, 0x00077f, 0x000870, 0x00088e, 0x000890, 0x000891, 0x000897, 0x0008e1, 0x0008e3,
@@ -1322,11 +1329,11 @@ static CR_Arabic: [u32; 118] = [
     0x01eef1,
 ];
 
-static CR_Armenian: [u32; 8] = [
+const CR_Armenian: [u32; 8] = [
     0x000531, 0x000556, 0x000559, 0x00058a, 0x00058d, 0x00058f, 0x00fb13, 0x00fb17,
 ];
 
-static CR_Assigned: [u32; 1462] = [
+const CR_Assigned: [u32; 1462] = [
     0x000000, 0x000377, 0x00037a, 0x00037f, 0x000384, 0x00038a, 0x00038c, 0x00038c, 0x00038e,
     0x0003a1, 0x0003a3, 0x00052f, 0x000531, 0x000556, 0x000559, 0x00058a, 0x00058d, 0x00058f,
     0x000591, 0x0005c7, 0x0005d0, 0x0005ea, 0x0005ef, 0x0005f4, 0x000600, 0x00070d, 0x00070f,
@@ -1492,42 +1499,42 @@ static CR_Assigned: [u32; 1462] = [
     0x0f0000, 0x0ffffd, 0x100000, 0x10fffd,
 ];
 
-static CR_Avestan: [u32; 4] = [0x010b00, 0x010b35, 0x010b39, 0x010b3f];
+const CR_Avestan: [u32; 4] = [0x010b00, 0x010b35, 0x010b39, 0x010b3f];
 
-static CR_Balinese: [u32; 4] = [0x001b00, 0x001b4c, 0x001b4e, 0x001b7f];
+const CR_Balinese: [u32; 4] = [0x001b00, 0x001b4c, 0x001b4e, 0x001b7f];
 
-static CR_Bamum: [u32; 4] = [0x00a6a0, 0x00a6f7, 0x016800, 0x016a38];
+const CR_Bamum: [u32; 4] = [0x00a6a0, 0x00a6f7, 0x016800, 0x016a38];
 
-static CR_Bassa_Vah: [u32; 4] = [0x016ad0, 0x016aed, 0x016af0, 0x016af5];
+const CR_Bassa_Vah: [u32; 4] = [0x016ad0, 0x016aed, 0x016af0, 0x016af5];
 
-static CR_Batak: [u32; 4] = [0x001bc0, 0x001bf3, 0x001bfc, 0x001bff];
+const CR_Batak: [u32; 4] = [0x001bc0, 0x001bf3, 0x001bfc, 0x001bff];
 
-static CR_Bengali: [u32; 28] = [
+const CR_Bengali: [u32; 28] = [
     0x000980, 0x000983, 0x000985, 0x00098c, 0x00098f, 0x000990, 0x000993, 0x0009a8, 0x0009aa,
     0x0009b0, 0x0009b2, 0x0009b2, 0x0009b6, 0x0009b9, 0x0009bc, 0x0009c4, 0x0009c7, 0x0009c8,
     0x0009cb, 0x0009ce, 0x0009d7, 0x0009d7, 0x0009dc, 0x0009dd, 0x0009df, 0x0009e3, 0x0009e6,
     0x0009fe,
 ];
 
-static CR_Bhaiksuki: [u32; 8] = [
+const CR_Bhaiksuki: [u32; 8] = [
     0x011c00, 0x011c08, 0x011c0a, 0x011c36, 0x011c38, 0x011c45, 0x011c50, 0x011c6c,
 ];
 
-static CR_Bidi_Control: [u32; 8] = [
+const CR_Bidi_Control: [u32; 8] = [
     0x00061c, 0x00061c, 0x00200e, 0x00200f, 0x00202a, 0x00202e, 0x002066, 0x002069,
 ];
 
-static CR_Bopomofo: [u32; 6] = [0x0002ea, 0x0002eb, 0x003105, 0x00312f, 0x0031a0, 0x0031bf];
+const CR_Bopomofo: [u32; 6] = [0x0002ea, 0x0002eb, 0x003105, 0x00312f, 0x0031a0, 0x0031bf];
 
-static CR_Brahmi: [u32; 6] = [0x011000, 0x01104d, 0x011052, 0x011075, 0x01107f, 0x01107f];
+const CR_Brahmi: [u32; 6] = [0x011000, 0x01104d, 0x011052, 0x011075, 0x01107f, 0x01107f];
 
-static CR_Braille: [u32; 2] = [0x002800, 0x0028ff];
+const CR_Braille: [u32; 2] = [0x002800, 0x0028ff];
 
-static CR_Buginese: [u32; 4] = [0x001a00, 0x001a1b, 0x001a1e, 0x001a1f];
+const CR_Buginese: [u32; 4] = [0x001a00, 0x001a1b, 0x001a1e, 0x001a1f];
 
-static CR_Buhid: [u32; 2] = [0x001740, 0x001753];
+const CR_Buhid: [u32; 2] = [0x001740, 0x001753];
 
-static CR_C: [u32; 1472] = [
+const CR_C: [u32; 1472] = [
     0x000000, 0x00001f, 0x00007f, 0x00009f, 0x0000ad, 0x0000ad, 0x000378, 0x000379, 0x000380,
     0x000383, 0x00038b, 0x00038b, 0x00038d, 0x00038d, 0x0003a2, 0x0003a2, 0x000530, 0x000530,
     0x000557, 0x000558, 0x00058b, 0x00058c, 0x000590, 0x000590, 0x0005c8, 0x0005cf, 0x0005eb,
@@ -1694,12 +1701,12 @@ static CR_C: [u32; 1472] = [
     0x03134f, 0x0323b0, 0x0e00ff, 0x0e01f0, 0x10ffff,
 ];
 
-static CR_Canadian_Aboriginal: [u32; 6] =
+const CR_Canadian_Aboriginal: [u32; 6] =
     [0x001400, 0x00167f, 0x0018b0, 0x0018f5, 0x011ab0, 0x011abf];
 
-static CR_Carian: [u32; 2] = [0x0102a0, 0x0102d0];
+const CR_Carian: [u32; 2] = [0x0102a0, 0x0102d0];
 
-static CR_Case_Ignorable: [u32; 904] = [
+const CR_Case_Ignorable: [u32; 904] = [
     0x000027, 0x000027, 0x00002e, 0x00002e, 0x00003a, 0x00003a, 0x00005e, 0x00005e, 0x000060,
     0x000060, 0x0000a8, 0x0000a8, 0x0000ad, 0x0000ad, 0x0000af, 0x0000af, 0x0000b4, 0x0000b4,
     0x0000b7, 0x0000b8, 0x0002b0, 0x00036f, 0x000374, 0x000375, 0x00037a, 0x00037a, 0x000384,
@@ -1803,7 +1810,7 @@ static CR_Case_Ignorable: [u32; 904] = [
     0x0e0020, 0x0e007f, 0x0e0100, 0x0e01ef,
 ];
 
-static CR_Cased: [u32; 318] = [
+const CR_Cased: [u32; 318] = [
     0x000041, 0x00005a, 0x000061, 0x00007a, 0x0000aa, 0x0000aa, 0x0000b5, 0x0000b5, 0x0000ba,
     0x0000ba, 0x0000c0, 0x0000d6, 0x0000d8, 0x0000f6, 0x0000f8, 0x0001ba, 0x0001bc, 0x0001bf,
     0x0001c4, 0x000293, 0x000295, 0x0002b8, 0x0002c0, 0x0002c1, 0x0002e0, 0x0002e4, 0x000345,
@@ -1842,9 +1849,9 @@ static CR_Cased: [u32; 318] = [
     0x01f169, 0x01f170, 0x01f189,
 ];
 
-static CR_Caucasian_Albanian: [u32; 4] = [0x010530, 0x010563, 0x01056f, 0x01056f];
+const CR_Caucasian_Albanian: [u32; 4] = [0x010530, 0x010563, 0x01056f, 0x01056f];
 
-static CR_Cf: [u32; 42] = [
+const CR_Cf: [u32; 42] = [
     0x0000ad, 0x0000ad, 0x000600, 0x000605, 0x00061c, 0x00061c, 0x0006dd, 0x0006dd, 0x00070f,
     0x00070f, 0x000890, 0x000891, 0x0008e2, 0x0008e2, 0x00180e, 0x00180e, 0x00200b, 0x00200f,
     0x00202a, 0x00202e, 0x002060, 0x002064, 0x002066, 0x00206f, 0x00feff, 0x00feff, 0x00fff9,
@@ -1852,13 +1859,13 @@ static CR_Cf: [u32; 42] = [
     0x01d173, 0x01d17a, 0x0e0001, 0x0e0001, 0x0e0020, 0x0e007f,
 ];
 
-static CR_Chakma: [u32; 4] = [0x011100, 0x011134, 0x011136, 0x011147];
+const CR_Chakma: [u32; 4] = [0x011100, 0x011134, 0x011136, 0x011147];
 
-static CR_Cham: [u32; 8] = [
+const CR_Cham: [u32; 8] = [
     0x00aa00, 0x00aa36, 0x00aa40, 0x00aa4d, 0x00aa50, 0x00aa59, 0x00aa5c, 0x00aa5f,
 ];
 
-static CR_Changes_When_Casefolded: [u32; 1252] = [
+const CR_Changes_When_Casefolded: [u32; 1252] = [
     0x000041, 0x00005a, 0x0000b5, 0x0000b5, 0x0000c0, 0x0000d6, 0x0000d8, 0x0000df, 0x000100,
     0x000100, 0x000102, 0x000102, 0x000104, 0x000104, 0x000106, 0x000106, 0x000108, 0x000108,
     0x00010a, 0x00010a, 0x00010c, 0x00010c, 0x00010e, 0x00010e, 0x000110, 0x000110, 0x000112,
@@ -2001,7 +2008,7 @@ static CR_Changes_When_Casefolded: [u32; 1252] = [
     0x01e921,
 ];
 
-static CR_Changes_When_Casemapped: [u32; 262] = [
+const CR_Changes_When_Casemapped: [u32; 262] = [
     0x000041, 0x00005a, 0x000061, 0x00007a, 0x0000b5, 0x0000b5, 0x0000c0, 0x0000d6, 0x0000d8,
     0x0000f6, 0x0000f8, 0x000137, 0x000139, 0x00018c, 0x00018e, 0x0001a9, 0x0001ac, 0x0001b9,
     0x0001bc, 0x0001bd, 0x0001bf, 0x0001bf, 0x0001c4, 0x000220, 0x000222, 0x000233, 0x00023a,
@@ -2034,7 +2041,7 @@ static CR_Changes_When_Casemapped: [u32; 262] = [
     0x01e943,
 ];
 
-static CR_Changes_When_Lowercased: [u32; 1228] = [
+const CR_Changes_When_Lowercased: [u32; 1228] = [
     0x000041, 0x00005a, 0x0000c0, 0x0000d6, 0x0000d8, 0x0000de, 0x000100, 0x000100, 0x000102,
     0x000102, 0x000104, 0x000104, 0x000106, 0x000106, 0x000108, 0x000108, 0x00010a, 0x00010a,
     0x00010c, 0x00010c, 0x00010e, 0x00010e, 0x000110, 0x000110, 0x000112, 0x000112, 0x000114,
@@ -2174,7 +2181,7 @@ static CR_Changes_When_Lowercased: [u32; 1228] = [
     0x016e40, 0x016e5f, 0x01e900, 0x01e921,
 ];
 
-static CR_Changes_When_Titlecased: [u32; 1258] = [
+const CR_Changes_When_Titlecased: [u32; 1258] = [
     0x000061, 0x00007a, 0x0000b5, 0x0000b5, 0x0000df, 0x0000f6, 0x0000f8, 0x0000ff, 0x000101,
     0x000101, 0x000103, 0x000103, 0x000105, 0x000105, 0x000107, 0x000107, 0x000109, 0x000109,
     0x00010b, 0x00010b, 0x00010d, 0x00010d, 0x00010f, 0x00010f, 0x000111, 0x000111, 0x000113,
@@ -2317,7 +2324,7 @@ static CR_Changes_When_Titlecased: [u32; 1258] = [
     0x010d85, 0x0118c0, 0x0118df, 0x016e60, 0x016e7f, 0x01e922, 0x01e943,
 ];
 
-static CR_Changes_When_Uppercased: [u32; 1260] = [
+const CR_Changes_When_Uppercased: [u32; 1260] = [
     0x000061, 0x00007a, 0x0000b5, 0x0000b5, 0x0000df, 0x0000f6, 0x0000f8, 0x0000ff, 0x000101,
     0x000101, 0x000103, 0x000103, 0x000105, 0x000105, 0x000107, 0x000107, 0x000109, 0x000109,
     0x00010b, 0x00010b, 0x00010d, 0x00010d, 0x00010f, 0x00010f, 0x000111, 0x000111, 0x000113,
@@ -2460,11 +2467,11 @@ static CR_Changes_When_Uppercased: [u32; 1260] = [
     0x010cf2, 0x010d70, 0x010d85, 0x0118c0, 0x0118df, 0x016e60, 0x016e7f, 0x01e922, 0x01e943,
 ];
 
-static CR_Cherokee: [u32; 6] = [0x0013a0, 0x0013f5, 0x0013f8, 0x0013fd, 0x00ab70, 0x00abbf];
+const CR_Cherokee: [u32; 6] = [0x0013a0, 0x0013f5, 0x0013f8, 0x0013fd, 0x00ab70, 0x00abbf];
 
-static CR_Chorasmian: [u32; 2] = [0x010fb0, 0x010fcb];
+const CR_Chorasmian: [u32; 2] = [0x010fb0, 0x010fcb];
 
-static CR_Cn: [u32; 1462] = [
+const CR_Cn: [u32; 1462] = [
     0x000378, 0x000379, 0x000380, 0x000383, 0x00038b, 0x00038b, 0x00038d, 0x00038d, 0x0003a2,
     0x0003a2, 0x000530, 0x000530, 0x000557, 0x000558, 0x00058b, 0x00058c, 0x000590, 0x000590,
     0x0005c8, 0x0005cf, 0x0005eb, 0x0005ee, 0x0005f5, 0x0005ff, 0x00070e, 0x00070e, 0x00074b,
@@ -2630,9 +2637,9 @@ static CR_Cn: [u32; 1462] = [
     0x0ffffe, 0x0fffff, 0x10fffe, 0x10ffff,
 ];
 
-static CR_Co: [u32; 6] = [0x00e000, 0x00f8ff, 0x0f0000, 0x0ffffd, 0x100000, 0x10fffd];
+const CR_Co: [u32; 6] = [0x00e000, 0x00f8ff, 0x0f0000, 0x0ffffd, 0x100000, 0x10fffd];
 
-static CR_Common: [u32; 348] = [
+const CR_Common: [u32; 348] = [
     0x000000, 0x000040, 0x00005b, 0x000060, 0x00007b, 0x0000a9, 0x0000ab, 0x0000b9, 0x0000bb,
     0x0000bf, 0x0000d7, 0x0000d7, 0x0000f7, 0x0000f7, 0x0002b9, 0x0002df, 0x0002e5, 0x0002e9,
     0x0002ec, 0x0002ff, 0x000374, 0x000374, 0x00037e, 0x00037e, 0x000385, 0x000385, 0x000387,
@@ -2674,28 +2681,28 @@ static CR_Common: [u32; 348] = [
     0x01fb94, 0x01fbf9, 0x0e0001, 0x0e0001, 0x0e0020, 0x0e007f,
 ];
 
-static CR_Coptic: [u32; 6] = [0x0003e2, 0x0003ef, 0x002c80, 0x002cf3, 0x002cf9, 0x002cff];
+const CR_Coptic: [u32; 6] = [0x0003e2, 0x0003ef, 0x002c80, 0x002cf3, 0x002cf9, 0x002cff];
 
-static CR_Cs: [u32; 2] = [0x00d800, 0x00dfff];
+const CR_Cs: [u32; 2] = [0x00d800, 0x00dfff];
 
-static CR_Cuneiform: [u32; 8] = [
+const CR_Cuneiform: [u32; 8] = [
     0x012000, 0x012399, 0x012400, 0x01246e, 0x012470, 0x012474, 0x012480, 0x012543,
 ];
 
-static CR_Cypriot: [u32; 12] = [
+const CR_Cypriot: [u32; 12] = [
     0x010800, 0x010805, 0x010808, 0x010808, 0x01080a, 0x010835, 0x010837, 0x010838, 0x01083c,
     0x01083c, 0x01083f, 0x01083f,
 ];
 
-static CR_Cypro_Minoan: [u32; 2] = [0x012f90, 0x012ff2];
+const CR_Cypro_Minoan: [u32; 2] = [0x012f90, 0x012ff2];
 
-static CR_Cyrillic: [u32; 20] = [
+const CR_Cyrillic: [u32; 20] = [
     0x000400, 0x000484, 0x000487, 0x00052f, 0x001c80, 0x001c8a, 0x001d2b, 0x001d2b, 0x001d78,
     0x001d78, 0x002de0, 0x002dff, 0x00a640, 0x00a69f, 0x00fe2e, 0x00fe2f, 0x01e030, 0x01e06d,
     0x01e08f, 0x01e08f,
 ];
 
-static CR_Dash: [u32; 48] = [
+const CR_Dash: [u32; 48] = [
     0x00002d, 0x00002d, 0x00058a, 0x00058a, 0x0005be, 0x0005be, 0x001400, 0x001400, 0x001806,
     0x001806, 0x002010, 0x002015, 0x002053, 0x002053, 0x00207b, 0x00207b, 0x00208b, 0x00208b,
     0x002212, 0x002212, 0x002e17, 0x002e17, 0x002e1a, 0x002e1a, 0x002e3a, 0x002e3b, 0x002e40,
@@ -2704,26 +2711,26 @@ static CR_Dash: [u32; 48] = [
     0x010d6e, 0x010ead, 0x010ead,
 ];
 
-static CR_Default_Ignorable_Code_Point: [u32; 34] = [
+const CR_Default_Ignorable_Code_Point: [u32; 34] = [
     0x0000ad, 0x0000ad, 0x00034f, 0x00034f, 0x00061c, 0x00061c, 0x00115f, 0x001160, 0x0017b4,
     0x0017b5, 0x00180b, 0x00180f, 0x00200b, 0x00200f, 0x00202a, 0x00202e, 0x002060, 0x00206f,
     0x003164, 0x003164, 0x00fe00, 0x00fe0f, 0x00feff, 0x00feff, 0x00ffa0, 0x00ffa0, 0x00fff0,
     0x00fff8, 0x01bca0, 0x01bca3, 0x01d173, 0x01d17a, 0x0e0000, 0x0e0fff,
 ];
 
-static CR_Deprecated: [u32; 16] = [
+const CR_Deprecated: [u32; 16] = [
     0x000149, 0x000149, 0x000673, 0x000673, 0x000f77, 0x000f77, 0x000f79, 0x000f79, 0x0017a3,
     0x0017a4, 0x00206a, 0x00206f, 0x002329, 0x00232a, 0x0e0001, 0x0e0001,
 ];
 
-static CR_Deseret: [u32; 2] = [0x010400, 0x01044f];
+const CR_Deseret: [u32; 2] = [0x010400, 0x01044f];
 
-static CR_Devanagari: [u32; 10] = [
+const CR_Devanagari: [u32; 10] = [
     0x000900, 0x000950, 0x000955, 0x000963, 0x000966, 0x00097f, 0x00a8e0, 0x00a8ff, 0x011b00,
     0x011b09,
 ];
 
-static CR_Diacritic: [u32; 428] = [
+const CR_Diacritic: [u32; 428] = [
     0x00005e, 0x00005e, 0x000060, 0x000060, 0x0000a8, 0x0000a8, 0x0000af, 0x0000af, 0x0000b4,
     0x0000b4, 0x0000b7, 0x0000b8, 0x0002b0, 0x00034e, 0x000350, 0x000357, 0x00035d, 0x000362,
     0x000374, 0x000375, 0x00037a, 0x00037a, 0x000384, 0x000385, 0x000483, 0x000487, 0x000559,
@@ -2774,25 +2781,25 @@ static CR_Diacritic: [u32; 428] = [
     0x01e8d6, 0x01e944, 0x01e946, 0x01e948, 0x01e94a,
 ];
 
-static CR_Dives_Akuru: [u32; 16] = [
+const CR_Dives_Akuru: [u32; 16] = [
     0x011900, 0x011906, 0x011909, 0x011909, 0x01190c, 0x011913, 0x011915, 0x011916, 0x011918,
     0x011935, 0x011937, 0x011938, 0x01193b, 0x011946, 0x011950, 0x011959,
 ];
 
-static CR_Dogra: [u32; 2] = [0x011800, 0x01183b];
+const CR_Dogra: [u32; 2] = [0x011800, 0x01183b];
 
-static CR_Duployan: [u32; 10] = [
+const CR_Duployan: [u32; 10] = [
     0x01bc00, 0x01bc6a, 0x01bc70, 0x01bc7c, 0x01bc80, 0x01bc88, 0x01bc90, 0x01bc99, 0x01bc9c,
     0x01bc9f,
 ];
 
-static CR_Egyptian_Hieroglyphs: [u32; 4] = [0x013000, 0x013455, 0x013460, 0x0143fa];
+const CR_Egyptian_Hieroglyphs: [u32; 4] = [0x013000, 0x013455, 0x013460, 0x0143fa];
 
-static CR_Elbasan: [u32; 2] = [0x010500, 0x010527];
+const CR_Elbasan: [u32; 2] = [0x010500, 0x010527];
 
-static CR_Elymaic: [u32; 2] = [0x010fe0, 0x010ff6];
+const CR_Elymaic: [u32; 2] = [0x010fe0, 0x010ff6];
 
-static CR_Emoji: [u32; 300] = [
+const CR_Emoji: [u32; 300] = [
     0x000023, 0x000023, 0x00002a, 0x00002a, 0x000030, 0x000039, 0x0000a9, 0x0000a9, 0x0000ae,
     0x0000ae, 0x00203c, 0x00203c, 0x002049, 0x002049, 0x002122, 0x002122, 0x002139, 0x002139,
     0x002194, 0x002199, 0x0021a9, 0x0021aa, 0x00231a, 0x00231b, 0x002328, 0x002328, 0x0023cf,
@@ -2829,15 +2836,15 @@ static CR_Emoji: [u32; 300] = [
     0x01fae9, 0x01faf0, 0x01faf8,
 ];
 
-static CR_Emoji_Component: [u32; 20] = [
+const CR_Emoji_Component: [u32; 20] = [
     0x000023, 0x000023, 0x00002a, 0x00002a, 0x000030, 0x000039, 0x00200d, 0x00200d, 0x0020e3,
     0x0020e3, 0x00fe0f, 0x00fe0f, 0x01f1e6, 0x01f1ff, 0x01f3fb, 0x01f3ff, 0x01f9b0, 0x01f9b3,
     0x0e0020, 0x0e007f,
 ];
 
-static CR_Emoji_Modifier: [u32; 2] = [0x01f3fb, 0x01f3ff];
+const CR_Emoji_Modifier: [u32; 2] = [0x01f3fb, 0x01f3ff];
 
-static CR_Emoji_Modifier_Base: [u32; 80] = [
+const CR_Emoji_Modifier_Base: [u32; 80] = [
     0x00261d, 0x00261d, 0x0026f9, 0x0026f9, 0x00270a, 0x00270d, 0x01f385, 0x01f385, 0x01f3c2,
     0x01f3c4, 0x01f3c7, 0x01f3c7, 0x01f3ca, 0x01f3cc, 0x01f442, 0x01f443, 0x01f446, 0x01f450,
     0x01f466, 0x01f478, 0x01f47c, 0x01f47c, 0x01f481, 0x01f483, 0x01f485, 0x01f487, 0x01f48f,
@@ -2849,7 +2856,7 @@ static CR_Emoji_Modifier_Base: [u32; 80] = [
     0x01f9cd, 0x01f9cf, 0x01f9d1, 0x01f9dd, 0x01fac3, 0x01fac5, 0x01faf0, 0x01faf8,
 ];
 
-static CR_Emoji_Presentation: [u32; 160] = [
+const CR_Emoji_Presentation: [u32; 160] = [
     0x00231a, 0x00231b, 0x0023e9, 0x0023ec, 0x0023f0, 0x0023f0, 0x0023f3, 0x0023f3, 0x0025fd,
     0x0025fe, 0x002614, 0x002615, 0x002648, 0x002653, 0x00267f, 0x00267f, 0x002693, 0x002693,
     0x0026a1, 0x0026a1, 0x0026aa, 0x0026ab, 0x0026bd, 0x0026be, 0x0026c4, 0x0026c5, 0x0026ce,
@@ -2870,7 +2877,7 @@ static CR_Emoji_Presentation: [u32; 160] = [
     0x01fac6, 0x01face, 0x01fadc, 0x01fadf, 0x01fae9, 0x01faf0, 0x01faf8,
 ];
 
-static CR_Ethiopic: [u32; 72] = [
+const CR_Ethiopic: [u32; 72] = [
     0x001200, 0x001248, 0x00124a, 0x00124d, 0x001250, 0x001256, 0x001258, 0x001258, 0x00125a,
     0x00125d, 0x001260, 0x001288, 0x00128a, 0x00128d, 0x001290, 0x0012b0, 0x0012b2, 0x0012b5,
     0x0012b8, 0x0012be, 0x0012c0, 0x0012c0, 0x0012c2, 0x0012c5, 0x0012c8, 0x0012d6, 0x0012d8,
@@ -2881,7 +2888,7 @@ static CR_Ethiopic: [u32; 72] = [
     0x00ab2e, 0x01e7e0, 0x01e7e6, 0x01e7e8, 0x01e7eb, 0x01e7ed, 0x01e7ee, 0x01e7f0, 0x01e7fe,
 ];
 
-static CR_Extended_Pictographic: [u32; 156] = [
+const CR_Extended_Pictographic: [u32; 156] = [
     0x0000a9, 0x0000a9, 0x0000ae, 0x0000ae, 0x00203c, 0x00203c, 0x002049, 0x002049, 0x002122,
     0x002122, 0x002139, 0x002139, 0x002194, 0x002199, 0x0021a9, 0x0021aa, 0x00231a, 0x00231b,
     0x002328, 0x002328, 0x002388, 0x002388, 0x0023cf, 0x0023cf, 0x0023e9, 0x0023f3, 0x0023f8,
@@ -2902,7 +2909,7 @@ static CR_Extended_Pictographic: [u32; 156] = [
     0x01faff, 0x01fc00, 0x01fffd,
 ];
 
-static CR_Extender: [u32; 82] = [
+const CR_Extender: [u32; 82] = [
     0x0000b7, 0x0000b7, 0x0002d0, 0x0002d1, 0x000640, 0x000640, 0x0007fa, 0x0007fa, 0x000a71,
     0x000a71, 0x000afb, 0x000afb, 0x000b55, 0x000b55, 0x000e46, 0x000e46, 0x000ec6, 0x000ec6,
     0x00180a, 0x00180a, 0x001843, 0x001843, 0x001aa7, 0x001aa7, 0x001c36, 0x001c36, 0x001c7b,
@@ -2915,29 +2922,29 @@ static CR_Extender: [u32; 82] = [
     0x01e946,
 ];
 
-static CR_Garay: [u32; 6] = [0x010d40, 0x010d65, 0x010d69, 0x010d85, 0x010d8e, 0x010d8f];
+const CR_Garay: [u32; 6] = [0x010d40, 0x010d65, 0x010d69, 0x010d85, 0x010d8e, 0x010d8f];
 
-static CR_Georgian: [u32; 20] = [
+const CR_Georgian: [u32; 20] = [
     0x0010a0, 0x0010c5, 0x0010c7, 0x0010c7, 0x0010cd, 0x0010cd, 0x0010d0, 0x0010fa, 0x0010fc,
     0x0010ff, 0x001c90, 0x001cba, 0x001cbd, 0x001cbf, 0x002d00, 0x002d25, 0x002d27, 0x002d27,
     0x002d2d, 0x002d2d,
 ];
 
-static CR_Glagolitic: [u32; 12] = [
+const CR_Glagolitic: [u32; 12] = [
     0x002c00, 0x002c5f, 0x01e000, 0x01e006, 0x01e008, 0x01e018, 0x01e01b, 0x01e021, 0x01e023,
     0x01e024, 0x01e026, 0x01e02a,
 ];
 
-static CR_Gothic: [u32; 2] = [0x010330, 0x01034a];
+const CR_Gothic: [u32; 2] = [0x010330, 0x01034a];
 
-static CR_Grantha: [u32; 30] = [
+const CR_Grantha: [u32; 30] = [
     0x011300, 0x011303, 0x011305, 0x01130c, 0x01130f, 0x011310, 0x011313, 0x011328, 0x01132a,
     0x011330, 0x011332, 0x011333, 0x011335, 0x011339, 0x01133c, 0x011344, 0x011347, 0x011348,
     0x01134b, 0x01134d, 0x011350, 0x011350, 0x011357, 0x011357, 0x01135d, 0x011363, 0x011366,
     0x01136c, 0x011370, 0x011374,
 ];
 
-static CR_Grapheme_Base: [u32; 1788] = [
+const CR_Grapheme_Base: [u32; 1788] = [
     0x000020, 0x00007e, 0x0000a0, 0x0000ac, 0x0000ae, 0x0002ff, 0x000370, 0x000377, 0x00037a,
     0x00037f, 0x000384, 0x00038a, 0x00038c, 0x00038c, 0x00038e, 0x0003a1, 0x0003a3, 0x000482,
     0x00048a, 0x00052f, 0x000531, 0x000556, 0x000559, 0x00058a, 0x00058d, 0x00058f, 0x0005be,
@@ -3139,7 +3146,7 @@ static CR_Grapheme_Base: [u32; 1788] = [
     0x02f800, 0x02fa1d, 0x030000, 0x03134a, 0x031350, 0x0323af,
 ];
 
-static CR_Grapheme_Extend: [u32; 750] = [
+const CR_Grapheme_Extend: [u32; 750] = [
     0x000300, 0x00036f, 0x000483, 0x000489, 0x000591, 0x0005bd, 0x0005bf, 0x0005bf, 0x0005c1,
     0x0005c2, 0x0005c4, 0x0005c5, 0x0005c7, 0x0005c7, 0x000610, 0x00061a, 0x00064b, 0x00065f,
     0x000670, 0x000670, 0x0006d6, 0x0006dc, 0x0006df, 0x0006e4, 0x0006e7, 0x0006e8, 0x0006ea,
@@ -3226,7 +3233,7 @@ static CR_Grapheme_Extend: [u32; 750] = [
     0x0e007f, 0x0e0100, 0x0e01ef,
 ];
 
-static CR_Grapheme_Link: [u32; 116] = [
+const CR_Grapheme_Link: [u32; 116] = [
     0x00094d, 0x00094d, 0x0009cd, 0x0009cd, 0x000a4d, 0x000a4d, 0x000acd, 0x000acd, 0x000b4d,
     0x000b4d, 0x000bcd, 0x000bcd, 0x000c4d, 0x000c4d, 0x000ccd, 0x000ccd, 0x000d3b, 0x000d3c,
     0x000d4d, 0x000d4d, 0x000dca, 0x000dca, 0x000e3a, 0x000e3a, 0x000eba, 0x000eba, 0x000f84,
@@ -3242,7 +3249,7 @@ static CR_Grapheme_Link: [u32; 116] = [
     0x011d44, 0x011d45, 0x011d97, 0x011d97, 0x011f41, 0x011f42, 0x01612f, 0x01612f,
 ];
 
-static CR_Greek: [u32; 72] = [
+const CR_Greek: [u32; 72] = [
     0x000370, 0x000373, 0x000375, 0x000377, 0x00037a, 0x00037d, 0x00037f, 0x00037f, 0x000384,
     0x000384, 0x000386, 0x000386, 0x000388, 0x00038a, 0x00038c, 0x00038c, 0x00038e, 0x0003a1,
     0x0003a3, 0x0003e1, 0x0003f0, 0x0003ff, 0x001d26, 0x001d2a, 0x001d5d, 0x001d61, 0x001d66,
@@ -3253,28 +3260,28 @@ static CR_Greek: [u32; 72] = [
     0x002126, 0x00ab65, 0x00ab65, 0x010140, 0x01018e, 0x0101a0, 0x0101a0, 0x01d200, 0x01d245,
 ];
 
-static CR_Gujarati: [u32; 28] = [
+const CR_Gujarati: [u32; 28] = [
     0x000a81, 0x000a83, 0x000a85, 0x000a8d, 0x000a8f, 0x000a91, 0x000a93, 0x000aa8, 0x000aaa,
     0x000ab0, 0x000ab2, 0x000ab3, 0x000ab5, 0x000ab9, 0x000abc, 0x000ac5, 0x000ac7, 0x000ac9,
     0x000acb, 0x000acd, 0x000ad0, 0x000ad0, 0x000ae0, 0x000ae3, 0x000ae6, 0x000af1, 0x000af9,
     0x000aff,
 ];
 
-static CR_Gunjala_Gondi: [u32; 12] = [
+const CR_Gunjala_Gondi: [u32; 12] = [
     0x011d60, 0x011d65, 0x011d67, 0x011d68, 0x011d6a, 0x011d8e, 0x011d90, 0x011d91, 0x011d93,
     0x011d98, 0x011da0, 0x011da9,
 ];
 
-static CR_Gurmukhi: [u32; 32] = [
+const CR_Gurmukhi: [u32; 32] = [
     0x000a01, 0x000a03, 0x000a05, 0x000a0a, 0x000a0f, 0x000a10, 0x000a13, 0x000a28, 0x000a2a,
     0x000a30, 0x000a32, 0x000a33, 0x000a35, 0x000a36, 0x000a38, 0x000a39, 0x000a3c, 0x000a3c,
     0x000a3e, 0x000a42, 0x000a47, 0x000a48, 0x000a4b, 0x000a4d, 0x000a51, 0x000a51, 0x000a59,
     0x000a5c, 0x000a5e, 0x000a5e, 0x000a66, 0x000a76,
 ];
 
-static CR_Gurung_Khema: [u32; 2] = [0x016100, 0x016139];
+const CR_Gurung_Khema: [u32; 2] = [0x016100, 0x016139];
 
-static CR_Han: [u32; 44] = [
+const CR_Han: [u32; 44] = [
     0x002e80, 0x002e99, 0x002e9b, 0x002ef3, 0x002f00, 0x002fd5, 0x003005, 0x003005, 0x003007,
     0x003007, 0x003021, 0x003029, 0x003038, 0x00303b, 0x003400, 0x004dbf, 0x004e00, 0x009fff,
     0x00f900, 0x00fa6d, 0x00fa70, 0x00fad9, 0x016fe2, 0x016fe3, 0x016ff0, 0x016ff1, 0x020000,
@@ -3282,61 +3289,61 @@ static CR_Han: [u32; 44] = [
     0x02ebf0, 0x02ee5d, 0x02f800, 0x02fa1d, 0x030000, 0x03134a, 0x031350, 0x0323af,
 ];
 
-static CR_Hangul: [u32; 28] = [
+const CR_Hangul: [u32; 28] = [
     0x001100, 0x0011ff, 0x00302e, 0x00302f, 0x003131, 0x00318e, 0x003200, 0x00321e, 0x003260,
     0x00327e, 0x00a960, 0x00a97c, 0x00ac00, 0x00d7a3, 0x00d7b0, 0x00d7c6, 0x00d7cb, 0x00d7fb,
     0x00ffa0, 0x00ffbe, 0x00ffc2, 0x00ffc7, 0x00ffca, 0x00ffcf, 0x00ffd2, 0x00ffd7, 0x00ffda,
     0x00ffdc,
 ];
 
-static CR_Hanifi_Rohingya: [u32; 4] = [0x010d00, 0x010d27, 0x010d30, 0x010d39];
+const CR_Hanifi_Rohingya: [u32; 4] = [0x010d00, 0x010d27, 0x010d30, 0x010d39];
 
-static CR_Hanunoo: [u32; 2] = [0x001720, 0x001734];
+const CR_Hanunoo: [u32; 2] = [0x001720, 0x001734];
 
-static CR_Hatran: [u32; 6] = [0x0108e0, 0x0108f2, 0x0108f4, 0x0108f5, 0x0108fb, 0x0108ff];
+const CR_Hatran: [u32; 6] = [0x0108e0, 0x0108f2, 0x0108f4, 0x0108f5, 0x0108fb, 0x0108ff];
 
-static CR_Hebrew: [u32; 18] = [
+const CR_Hebrew: [u32; 18] = [
     0x000591, 0x0005c7, 0x0005d0, 0x0005ea, 0x0005ef, 0x0005f4, 0x00fb1d, 0x00fb36, 0x00fb38,
     0x00fb3c, 0x00fb3e, 0x00fb3e, 0x00fb40, 0x00fb41, 0x00fb43, 0x00fb44, 0x00fb46, 0x00fb4f,
 ];
 
-static CR_Hex_Digit: [u32; 12] = [
+const CR_Hex_Digit: [u32; 12] = [
     0x000030, 0x000039, 0x000041, 0x000046, 0x000061, 0x000066, 0x00ff10, 0x00ff19, 0x00ff21,
     0x00ff26, 0x00ff41, 0x00ff46,
 ];
 
-static CR_Hiragana: [u32; 12] = [
+const CR_Hiragana: [u32; 12] = [
     0x003041, 0x003096, 0x00309d, 0x00309f, 0x01b001, 0x01b11f, 0x01b132, 0x01b132, 0x01b150,
     0x01b152, 0x01f200, 0x01f200,
 ];
 
-static CR_Hyphen: [u32; 20] = [
+const CR_Hyphen: [u32; 20] = [
     0x00002d, 0x00002d, 0x0000ad, 0x0000ad, 0x00058a, 0x00058a, 0x001806, 0x001806, 0x002010,
     0x002011, 0x002e17, 0x002e17, 0x0030fb, 0x0030fb, 0x00fe63, 0x00fe63, 0x00ff0d, 0x00ff0d,
     0x00ff65, 0x00ff65,
 ];
 
-static CR_IDS_Binary_Operator: [u32; 6] =
+const CR_IDS_Binary_Operator: [u32; 6] =
     [0x002ff0, 0x002ff1, 0x002ff4, 0x002ffd, 0x0031ef, 0x0031ef];
 
-static CR_IDS_Trinary_Operator: [u32; 2] = [0x002ff2, 0x002ff3];
+const CR_IDS_Trinary_Operator: [u32; 2] = [0x002ff2, 0x002ff3];
 
-static CR_IDS_Unary_Operator: [u32; 2] = [0x002ffe, 0x002fff];
+const CR_IDS_Unary_Operator: [u32; 2] = [0x002ffe, 0x002fff];
 
-static CR_ID_Compat_Math_Continue: [u32; 36] = [
+const CR_ID_Compat_Math_Continue: [u32; 36] = [
     0x0000b2, 0x0000b3, 0x0000b9, 0x0000b9, 0x002070, 0x002070, 0x002074, 0x00207e, 0x002080,
     0x00208e, 0x002202, 0x002202, 0x002207, 0x002207, 0x00221e, 0x00221e, 0x01d6c1, 0x01d6c1,
     0x01d6db, 0x01d6db, 0x01d6fb, 0x01d6fb, 0x01d715, 0x01d715, 0x01d735, 0x01d735, 0x01d74f,
     0x01d74f, 0x01d76f, 0x01d76f, 0x01d789, 0x01d789, 0x01d7a9, 0x01d7a9, 0x01d7c3, 0x01d7c3,
 ];
 
-static CR_ID_Compat_Math_Start: [u32; 26] = [
+const CR_ID_Compat_Math_Start: [u32; 26] = [
     0x002202, 0x002202, 0x002207, 0x002207, 0x00221e, 0x00221e, 0x01d6c1, 0x01d6c1, 0x01d6db,
     0x01d6db, 0x01d6fb, 0x01d6fb, 0x01d715, 0x01d715, 0x01d735, 0x01d735, 0x01d74f, 0x01d74f,
     0x01d76f, 0x01d76f, 0x01d789, 0x01d789, 0x01d7a9, 0x01d7a9, 0x01d7c3, 0x01d7c3,
 ];
 
-static CR_ID_Continue: [u32; 1586] = [
+const CR_ID_Continue: [u32; 1586] = [
     0x000030, 0x000039, 0x000041, 0x00005a, 0x00005f, 0x00005f, 0x000061, 0x00007a, 0x0000aa,
     0x0000aa, 0x0000b5, 0x0000b5, 0x0000b7, 0x0000b7, 0x0000ba, 0x0000ba, 0x0000c0, 0x0000d6,
     0x0000d8, 0x0000f6, 0x0000f8, 0x0002c1, 0x0002c6, 0x0002d1, 0x0002e0, 0x0002e4, 0x0002ec,
@@ -3516,7 +3523,7 @@ static CR_ID_Continue: [u32; 1586] = [
     0x0e0100, 0x0e01ef,
 ];
 
-static CR_ID_Start: [u32; 1354] = [
+const CR_ID_Start: [u32; 1354] = [
     0x000041, 0x00005a, 0x000061, 0x00007a, 0x0000aa, 0x0000aa, 0x0000b5, 0x0000b5, 0x0000ba,
     0x0000ba, 0x0000c0, 0x0000d6, 0x0000d8, 0x0000f6, 0x0000f8, 0x0002c1, 0x0002c6, 0x0002d1,
     0x0002e0, 0x0002e4, 0x0002ec, 0x0002ec, 0x0002ee, 0x0002ee, 0x000370, 0x000374, 0x000376,
@@ -3670,7 +3677,7 @@ static CR_ID_Start: [u32; 1354] = [
     0x030000, 0x03134a, 0x031350, 0x0323af,
 ];
 
-static CR_Ideographic: [u32; 42] = [
+const CR_Ideographic: [u32; 42] = [
     0x003006, 0x003007, 0x003021, 0x003029, 0x003038, 0x00303a, 0x003400, 0x004dbf, 0x004e00,
     0x009fff, 0x00f900, 0x00fa6d, 0x00fa70, 0x00fad9, 0x016fe4, 0x016fe4, 0x017000, 0x0187f7,
     0x018800, 0x018cd5, 0x018cff, 0x018d08, 0x01b170, 0x01b2fb, 0x020000, 0x02a6df, 0x02a700,
@@ -3678,9 +3685,9 @@ static CR_Ideographic: [u32; 42] = [
     0x02f800, 0x02fa1d, 0x030000, 0x03134a, 0x031350, 0x0323af,
 ];
 
-static CR_Imperial_Aramaic: [u32; 4] = [0x010840, 0x010855, 0x010857, 0x01085f];
+const CR_Imperial_Aramaic: [u32; 4] = [0x010840, 0x010855, 0x010857, 0x01085f];
 
-static CR_InCB: [u32; 586] = [
+const CR_InCB: [u32; 586] = [
     0x00094d, 0x00094d, 0x0009cd, 0x0009cd, 0x000acd, 0x000acd, 0x000b4d, 0x000b4d, 0x000c4d,
     0x000c4d, 0x000d4d, 0x000d4d, 0x000d57, 0x000d57, 0x000d62, 0x000d63, 0x000d81, 0x000d81,
     0x000dca, 0x000dca, 0x000dcf, 0x000dcf, 0x000dd2, 0x000dd4, 0x000dd6, 0x000dd6, 0x000ddf,
@@ -3749,7 +3756,7 @@ static CR_InCB: [u32; 586] = [
     0x0e01ef,
 ];
 
-static CR_Inherited: [u32; 58] = [
+const CR_Inherited: [u32; 58] = [
     0x000300, 0x00036f, 0x000485, 0x000486, 0x00064b, 0x000655, 0x000670, 0x000670, 0x000951,
     0x000954, 0x001ab0, 0x001ace, 0x001cd0, 0x001cd2, 0x001cd4, 0x001ce0, 0x001ce2, 0x001ce8,
     0x001ced, 0x001ced, 0x001cf4, 0x001cf4, 0x001cf8, 0x001cf9, 0x001dc0, 0x001dff, 0x00200c,
@@ -3759,52 +3766,52 @@ static CR_Inherited: [u32; 58] = [
     0x01d1aa, 0x01d1ad, 0x0e0100, 0x0e01ef,
 ];
 
-static CR_Inscriptional_Pahlavi: [u32; 4] = [0x010b60, 0x010b72, 0x010b78, 0x010b7f];
+const CR_Inscriptional_Pahlavi: [u32; 4] = [0x010b60, 0x010b72, 0x010b78, 0x010b7f];
 
-static CR_Inscriptional_Parthian: [u32; 4] = [0x010b40, 0x010b55, 0x010b58, 0x010b5f];
+const CR_Inscriptional_Parthian: [u32; 4] = [0x010b40, 0x010b55, 0x010b58, 0x010b5f];
 
-static CR_Javanese: [u32; 6] = [0x00a980, 0x00a9cd, 0x00a9d0, 0x00a9d9, 0x00a9de, 0x00a9df];
+const CR_Javanese: [u32; 6] = [0x00a980, 0x00a9cd, 0x00a9d0, 0x00a9d9, 0x00a9de, 0x00a9df];
 
-static CR_Join_Control: [u32; 2] = [0x00200c, 0x00200d];
+const CR_Join_Control: [u32; 2] = [0x00200c, 0x00200d];
 
-static CR_Kaithi: [u32; 4] = [0x011080, 0x0110c2, 0x0110cd, 0x0110cd];
+const CR_Kaithi: [u32; 4] = [0x011080, 0x0110c2, 0x0110cd, 0x0110cd];
 
-static CR_Kannada: [u32; 26] = [
+const CR_Kannada: [u32; 26] = [
     0x000c80, 0x000c8c, 0x000c8e, 0x000c90, 0x000c92, 0x000ca8, 0x000caa, 0x000cb3, 0x000cb5,
     0x000cb9, 0x000cbc, 0x000cc4, 0x000cc6, 0x000cc8, 0x000cca, 0x000ccd, 0x000cd5, 0x000cd6,
     0x000cdd, 0x000cde, 0x000ce0, 0x000ce3, 0x000ce6, 0x000cef, 0x000cf1, 0x000cf3,
 ];
 
-static CR_Katakana: [u32; 28] = [
+const CR_Katakana: [u32; 28] = [
     0x0030a1, 0x0030fa, 0x0030fd, 0x0030ff, 0x0031f0, 0x0031ff, 0x0032d0, 0x0032fe, 0x003300,
     0x003357, 0x00ff66, 0x00ff6f, 0x00ff71, 0x00ff9d, 0x01aff0, 0x01aff3, 0x01aff5, 0x01affb,
     0x01affd, 0x01affe, 0x01b000, 0x01b000, 0x01b120, 0x01b122, 0x01b155, 0x01b155, 0x01b164,
     0x01b167,
 ];
 
-static CR_Kawi: [u32; 6] = [0x011f00, 0x011f10, 0x011f12, 0x011f3a, 0x011f3e, 0x011f5a];
+const CR_Kawi: [u32; 6] = [0x011f00, 0x011f10, 0x011f12, 0x011f3a, 0x011f3e, 0x011f5a];
 
-static CR_Kayah_Li: [u32; 4] = [0x00a900, 0x00a92d, 0x00a92f, 0x00a92f];
+const CR_Kayah_Li: [u32; 4] = [0x00a900, 0x00a92d, 0x00a92f, 0x00a92f];
 
-static CR_Kharoshthi: [u32; 16] = [
+const CR_Kharoshthi: [u32; 16] = [
     0x010a00, 0x010a03, 0x010a05, 0x010a06, 0x010a0c, 0x010a13, 0x010a15, 0x010a17, 0x010a19,
     0x010a35, 0x010a38, 0x010a3a, 0x010a3f, 0x010a48, 0x010a50, 0x010a58,
 ];
 
-static CR_Khitan_Small_Script: [u32; 6] =
+const CR_Khitan_Small_Script: [u32; 6] =
     [0x016fe4, 0x016fe4, 0x018b00, 0x018cd5, 0x018cff, 0x018cff];
 
-static CR_Khmer: [u32; 8] = [
+const CR_Khmer: [u32; 8] = [
     0x001780, 0x0017dd, 0x0017e0, 0x0017e9, 0x0017f0, 0x0017f9, 0x0019e0, 0x0019ff,
 ];
 
-static CR_Khojki: [u32; 4] = [0x011200, 0x011211, 0x011213, 0x011241];
+const CR_Khojki: [u32; 4] = [0x011200, 0x011211, 0x011213, 0x011241];
 
-static CR_Khudawadi: [u32; 4] = [0x0112b0, 0x0112ea, 0x0112f0, 0x0112f9];
+const CR_Khudawadi: [u32; 4] = [0x0112b0, 0x0112ea, 0x0112f0, 0x0112f9];
 
-static CR_Kirat_Rai: [u32; 2] = [0x016d40, 0x016d79];
+const CR_Kirat_Rai: [u32; 2] = [0x016d40, 0x016d79];
 
-static CR_L: [u32; 1354] = [
+const CR_L: [u32; 1354] = [
     0x000041, 0x00005a, 0x000061, 0x00007a, 0x0000aa, 0x0000aa, 0x0000b5, 0x0000b5, 0x0000ba,
     0x0000ba, 0x0000c0, 0x0000d6, 0x0000d8, 0x0000f6, 0x0000f8, 0x0002c1, 0x0002c6, 0x0002d1,
     0x0002e0, 0x0002e4, 0x0002ec, 0x0002ec, 0x0002ee, 0x0002ee, 0x000370, 0x000374, 0x000376,
@@ -3958,7 +3965,7 @@ static CR_L: [u32; 1354] = [
     0x030000, 0x03134a, 0x031350, 0x0323af,
 ];
 
-static CR_LC: [u32; 290] = [
+const CR_LC: [u32; 290] = [
     0x000041, 0x00005a, 0x000061, 0x00007a, 0x0000b5, 0x0000b5, 0x0000c0, 0x0000d6, 0x0000d8,
     0x0000f6, 0x0000f8, 0x0001ba, 0x0001bc, 0x0001bf, 0x0001c4, 0x000293, 0x000295, 0x0002af,
     0x000370, 0x000373, 0x000376, 0x000377, 0x00037b, 0x00037d, 0x00037f, 0x00037f, 0x000386,
@@ -3994,13 +4001,13 @@ static CR_LC: [u32; 290] = [
     0x01e900, 0x01e943,
 ];
 
-static CR_Lao: [u32; 22] = [
+const CR_Lao: [u32; 22] = [
     0x000e81, 0x000e82, 0x000e84, 0x000e84, 0x000e86, 0x000e8a, 0x000e8c, 0x000ea3, 0x000ea5,
     0x000ea5, 0x000ea7, 0x000ebd, 0x000ec0, 0x000ec4, 0x000ec6, 0x000ec6, 0x000ec8, 0x000ece,
     0x000ed0, 0x000ed9, 0x000edc, 0x000edf,
 ];
 
-static CR_Latin: [u32; 78] = [
+const CR_Latin: [u32; 78] = [
     0x000041, 0x00005a, 0x000061, 0x00007a, 0x0000aa, 0x0000aa, 0x0000ba, 0x0000ba, 0x0000c0,
     0x0000d6, 0x0000d8, 0x0000f6, 0x0000f8, 0x0002b8, 0x0002e0, 0x0002e4, 0x001d00, 0x001d25,
     0x001d2c, 0x001d5c, 0x001d62, 0x001d65, 0x001d6b, 0x001d77, 0x001d79, 0x001dbe, 0x001e00,
@@ -4012,23 +4019,23 @@ static CR_Latin: [u32; 78] = [
     0x0107b2, 0x0107ba, 0x01df00, 0x01df1e, 0x01df25, 0x01df2a,
 ];
 
-static CR_Lepcha: [u32; 6] = [0x001c00, 0x001c37, 0x001c3b, 0x001c49, 0x001c4d, 0x001c4f];
+const CR_Lepcha: [u32; 6] = [0x001c00, 0x001c37, 0x001c3b, 0x001c49, 0x001c4d, 0x001c4f];
 
-static CR_Limbu: [u32; 10] = [
+const CR_Limbu: [u32; 10] = [
     0x001900, 0x00191e, 0x001920, 0x00192b, 0x001930, 0x00193b, 0x001940, 0x001940, 0x001944,
     0x00194f,
 ];
 
-static CR_Linear_A: [u32; 6] = [0x010600, 0x010736, 0x010740, 0x010755, 0x010760, 0x010767];
+const CR_Linear_A: [u32; 6] = [0x010600, 0x010736, 0x010740, 0x010755, 0x010760, 0x010767];
 
-static CR_Linear_B: [u32; 14] = [
+const CR_Linear_B: [u32; 14] = [
     0x010000, 0x01000b, 0x01000d, 0x010026, 0x010028, 0x01003a, 0x01003c, 0x01003d, 0x01003f,
     0x01004d, 0x010050, 0x01005d, 0x010080, 0x0100fa,
 ];
 
-static CR_Lisu: [u32; 4] = [0x00a4d0, 0x00a4ff, 0x011fb0, 0x011fb0];
+const CR_Lisu: [u32; 4] = [0x00a4d0, 0x00a4ff, 0x011fb0, 0x011fb0];
 
-static CR_Ll: [u32; 1324] = [
+const CR_Ll: [u32; 1324] = [
     0x000061, 0x00007a, 0x0000b5, 0x0000b5, 0x0000df, 0x0000f6, 0x0000f8, 0x0000ff, 0x000101,
     0x000101, 0x000103, 0x000103, 0x000105, 0x000105, 0x000107, 0x000107, 0x000109, 0x000109,
     0x00010b, 0x00010b, 0x00010d, 0x00010d, 0x00010f, 0x00010f, 0x000111, 0x000111, 0x000113,
@@ -4179,7 +4186,7 @@ static CR_Ll: [u32; 1324] = [
     0x01e943,
 ];
 
-static CR_Lm: [u32; 150] = [
+const CR_Lm: [u32; 150] = [
     0x0002b0, 0x0002c1, 0x0002c6, 0x0002d1, 0x0002e0, 0x0002e4, 0x0002ec, 0x0002ec, 0x0002ee,
     0x0002ee, 0x000374, 0x000374, 0x00037a, 0x00037a, 0x000559, 0x000559, 0x000640, 0x000640,
     0x0006e5, 0x0006e6, 0x0007f4, 0x0007f5, 0x0007fa, 0x0007fa, 0x00081a, 0x00081a, 0x000824,
@@ -4199,7 +4206,7 @@ static CR_Lm: [u32; 150] = [
     0x01e137, 0x01e13d, 0x01e4eb, 0x01e4eb, 0x01e94b, 0x01e94b,
 ];
 
-static CR_Lo: [u32; 1056] = [
+const CR_Lo: [u32; 1056] = [
     0x0000aa, 0x0000aa, 0x0000ba, 0x0000ba, 0x0001bb, 0x0001bb, 0x0001c0, 0x0001c3, 0x000294,
     0x000294, 0x0005d0, 0x0005ea, 0x0005ef, 0x0005f2, 0x000620, 0x00063f, 0x000641, 0x00064a,
     0x00066e, 0x00066f, 0x000671, 0x0006d3, 0x0006d5, 0x0006d5, 0x0006ee, 0x0006ef, 0x0006fa,
@@ -4320,18 +4327,18 @@ static CR_Lo: [u32; 1056] = [
     0x03134a, 0x031350, 0x0323af,
 ];
 
-static CR_Logical_Order_Exception: [u32; 14] = [
+const CR_Logical_Order_Exception: [u32; 14] = [
     0x000e40, 0x000e44, 0x000ec0, 0x000ec4, 0x0019b5, 0x0019b7, 0x0019ba, 0x0019ba, 0x00aab5,
     0x00aab6, 0x00aab9, 0x00aab9, 0x00aabb, 0x00aabc,
 ];
 
-static CR_Lt: [u32; 20] = [
+const CR_Lt: [u32; 20] = [
     0x0001c5, 0x0001c5, 0x0001c8, 0x0001c8, 0x0001cb, 0x0001cb, 0x0001f2, 0x0001f2, 0x001f88,
     0x001f8f, 0x001f98, 0x001f9f, 0x001fa8, 0x001faf, 0x001fbc, 0x001fbc, 0x001fcc, 0x001fcc,
     0x001ffc, 0x001ffc,
 ];
 
-static CR_Lu: [u32; 1302] = [
+const CR_Lu: [u32; 1302] = [
     0x000041, 0x00005a, 0x0000c0, 0x0000d6, 0x0000d8, 0x0000de, 0x000100, 0x000100, 0x000102,
     0x000102, 0x000104, 0x000104, 0x000106, 0x000106, 0x000108, 0x000108, 0x00010a, 0x00010a,
     0x00010c, 0x00010c, 0x00010e, 0x00010e, 0x000110, 0x000110, 0x000112, 0x000112, 0x000114,
@@ -4479,11 +4486,11 @@ static CR_Lu: [u32; 1302] = [
     0x01d790, 0x01d7a8, 0x01d7ca, 0x01d7ca, 0x01e900, 0x01e921,
 ];
 
-static CR_Lycian: [u32; 2] = [0x010280, 0x01029c];
+const CR_Lycian: [u32; 2] = [0x010280, 0x01029c];
 
-static CR_Lydian: [u32; 4] = [0x010920, 0x010939, 0x01093f, 0x01093f];
+const CR_Lydian: [u32; 4] = [0x010920, 0x010939, 0x01093f, 0x01093f];
 
-static CR_M: [u32; 642] = [
+const CR_M: [u32; 642] = [
     0x000300, 0x00036f, 0x000483, 0x000489, 0x000591, 0x0005bd, 0x0005bf, 0x0005bf, 0x0005c1,
     0x0005c2, 0x0005c4, 0x0005c5, 0x0005c7, 0x0005c7, 0x000610, 0x00061a, 0x00064b, 0x00065f,
     0x000670, 0x000670, 0x0006d6, 0x0006dc, 0x0006df, 0x0006e4, 0x0006e7, 0x0006e8, 0x0006ea,
@@ -4558,27 +4565,27 @@ static CR_M: [u32; 642] = [
     0x01e94a, 0x0e0100, 0x0e01ef,
 ];
 
-static CR_Mahajani: [u32; 2] = [0x011150, 0x011176];
+const CR_Mahajani: [u32; 2] = [0x011150, 0x011176];
 
-static CR_Makasar: [u32; 2] = [0x011ee0, 0x011ef8];
+const CR_Makasar: [u32; 2] = [0x011ee0, 0x011ef8];
 
-static CR_Malayalam: [u32; 14] = [
+const CR_Malayalam: [u32; 14] = [
     0x000d00, 0x000d0c, 0x000d0e, 0x000d10, 0x000d12, 0x000d44, 0x000d46, 0x000d48, 0x000d4a,
     0x000d4f, 0x000d54, 0x000d63, 0x000d66, 0x000d7f,
 ];
 
-static CR_Mandaic: [u32; 4] = [0x000840, 0x00085b, 0x00085e, 0x00085e];
+const CR_Mandaic: [u32; 4] = [0x000840, 0x00085b, 0x00085e, 0x00085e];
 
-static CR_Manichaean: [u32; 4] = [0x010ac0, 0x010ae6, 0x010aeb, 0x010af6];
+const CR_Manichaean: [u32; 4] = [0x010ac0, 0x010ae6, 0x010aeb, 0x010af6];
 
-static CR_Marchen: [u32; 6] = [0x011c70, 0x011c8f, 0x011c92, 0x011ca7, 0x011ca9, 0x011cb6];
+const CR_Marchen: [u32; 6] = [0x011c70, 0x011c8f, 0x011c92, 0x011ca7, 0x011ca9, 0x011cb6];
 
-static CR_Masaram_Gondi: [u32; 14] = [
+const CR_Masaram_Gondi: [u32; 14] = [
     0x011d00, 0x011d06, 0x011d08, 0x011d09, 0x011d0b, 0x011d36, 0x011d3a, 0x011d3a, 0x011d3c,
     0x011d3d, 0x011d3f, 0x011d47, 0x011d50, 0x011d59,
 ];
 
-static CR_Math: [u32; 278] = [
+const CR_Math: [u32; 278] = [
     0x00002b, 0x00002b, 0x00003c, 0x00003e, 0x00005e, 0x00005e, 0x00007c, 0x00007c, 0x00007e,
     0x00007e, 0x0000ac, 0x0000ac, 0x0000b1, 0x0000b1, 0x0000d7, 0x0000d7, 0x0000f7, 0x0000f7,
     0x0003d0, 0x0003d2, 0x0003d5, 0x0003d5, 0x0003f0, 0x0003f1, 0x0003f4, 0x0003f6, 0x000606,
@@ -4612,7 +4619,7 @@ static CR_Math: [u32; 278] = [
     0x01eea1, 0x01eea3, 0x01eea5, 0x01eea9, 0x01eeab, 0x01eebb, 0x01eef0, 0x01eef1,
 ];
 
-static CR_Mc: [u32; 380] = [
+const CR_Mc: [u32; 380] = [
     0x000903, 0x000903, 0x00093b, 0x00093b, 0x00093e, 0x000940, 0x000949, 0x00094c, 0x00094e,
     0x00094f, 0x000982, 0x000983, 0x0009be, 0x0009c0, 0x0009c7, 0x0009c8, 0x0009cb, 0x0009cc,
     0x0009d7, 0x0009d7, 0x000a03, 0x000a03, 0x000a3e, 0x000a40, 0x000a83, 0x000a83, 0x000abe,
@@ -4658,24 +4665,24 @@ static CR_Mc: [u32; 380] = [
     0x01d16d, 0x01d172,
 ];
 
-static CR_Me: [u32; 10] = [
+const CR_Me: [u32; 10] = [
     0x000488, 0x000489, 0x001abe, 0x001abe, 0x0020dd, 0x0020e0, 0x0020e2, 0x0020e4, 0x00a670,
     0x00a672,
 ];
 
-static CR_Medefaidrin: [u32; 2] = [0x016e40, 0x016e9a];
+const CR_Medefaidrin: [u32; 2] = [0x016e40, 0x016e9a];
 
-static CR_Meetei_Mayek: [u32; 6] = [0x00aae0, 0x00aaf6, 0x00abc0, 0x00abed, 0x00abf0, 0x00abf9];
+const CR_Meetei_Mayek: [u32; 6] = [0x00aae0, 0x00aaf6, 0x00abc0, 0x00abed, 0x00abf0, 0x00abf9];
 
-static CR_Mende_Kikakui: [u32; 4] = [0x01e800, 0x01e8c4, 0x01e8c7, 0x01e8d6];
+const CR_Mende_Kikakui: [u32; 4] = [0x01e800, 0x01e8c4, 0x01e8c7, 0x01e8d6];
 
-static CR_Meroitic_Cursive: [u32; 6] = [0x0109a0, 0x0109b7, 0x0109bc, 0x0109cf, 0x0109d2, 0x0109ff];
+const CR_Meroitic_Cursive: [u32; 6] = [0x0109a0, 0x0109b7, 0x0109bc, 0x0109cf, 0x0109d2, 0x0109ff];
 
-static CR_Meroitic_Hieroglyphs: [u32; 2] = [0x010980, 0x01099f];
+const CR_Meroitic_Hieroglyphs: [u32; 2] = [0x010980, 0x01099f];
 
-static CR_Miao: [u32; 6] = [0x016f00, 0x016f4a, 0x016f4f, 0x016f87, 0x016f8f, 0x016f9f];
+const CR_Miao: [u32; 6] = [0x016f00, 0x016f4a, 0x016f4f, 0x016f87, 0x016f8f, 0x016f9f];
 
-static CR_Mn: [u32; 714] = [
+const CR_Mn: [u32; 714] = [
     0x000300, 0x00036f, 0x000483, 0x000487, 0x000591, 0x0005bd, 0x0005bf, 0x0005bf, 0x0005c1,
     0x0005c2, 0x0005c4, 0x0005c5, 0x0005c7, 0x0005c7, 0x000610, 0x00061a, 0x00064b, 0x00065f,
     0x000670, 0x000670, 0x0006d6, 0x0006dc, 0x0006df, 0x0006e4, 0x0006e7, 0x0006e8, 0x0006ea,
@@ -4758,30 +4765,30 @@ static CR_Mn: [u32; 714] = [
     0x01e94a, 0x0e0100, 0x0e01ef,
 ];
 
-static CR_Modi: [u32; 4] = [0x011600, 0x011644, 0x011650, 0x011659];
+const CR_Modi: [u32; 4] = [0x011600, 0x011644, 0x011650, 0x011659];
 
-static CR_Modifier_Combining_Mark: [u32; 18] = [
+const CR_Modifier_Combining_Mark: [u32; 18] = [
     0x000654, 0x000655, 0x000658, 0x000658, 0x0006dc, 0x0006dc, 0x0006e3, 0x0006e3, 0x0006e7,
     0x0006e8, 0x0008ca, 0x0008cb, 0x0008cd, 0x0008cf, 0x0008d3, 0x0008d3, 0x0008f3, 0x0008f3,
 ];
 
-static CR_Mongolian: [u32; 12] = [
+const CR_Mongolian: [u32; 12] = [
     0x001800, 0x001801, 0x001804, 0x001804, 0x001806, 0x001819, 0x001820, 0x001878, 0x001880,
     0x0018aa, 0x011660, 0x01166c,
 ];
 
-static CR_Mro: [u32; 6] = [0x016a40, 0x016a5e, 0x016a60, 0x016a69, 0x016a6e, 0x016a6f];
+const CR_Mro: [u32; 6] = [0x016a40, 0x016a5e, 0x016a60, 0x016a69, 0x016a6e, 0x016a6f];
 
-static CR_Multani: [u32; 10] = [
+const CR_Multani: [u32; 10] = [
     0x011280, 0x011286, 0x011288, 0x011288, 0x01128a, 0x01128d, 0x01128f, 0x01129d, 0x01129f,
     0x0112a9,
 ];
 
-static CR_Myanmar: [u32; 8] = [
+const CR_Myanmar: [u32; 8] = [
     0x001000, 0x00109f, 0x00a9e0, 0x00a9fe, 0x00aa60, 0x00aa7f, 0x0116d0, 0x0116e3,
 ];
 
-static CR_N: [u32; 288] = [
+const CR_N: [u32; 288] = [
     0x000030, 0x000039, 0x0000b2, 0x0000b3, 0x0000b9, 0x0000b9, 0x0000bc, 0x0000be, 0x000660,
     0x000669, 0x0006f0, 0x0006f9, 0x0007c0, 0x0007c9, 0x000966, 0x00096f, 0x0009e6, 0x0009ef,
     0x0009f4, 0x0009f9, 0x000a66, 0x000a6f, 0x000ae6, 0x000aef, 0x000b66, 0x000b6f, 0x000b72,
@@ -4816,27 +4823,27 @@ static CR_N: [u32; 288] = [
     0x01ecb4, 0x01ed01, 0x01ed2d, 0x01ed2f, 0x01ed3d, 0x01f100, 0x01f10c, 0x01fbf0, 0x01fbf9,
 ];
 
-static CR_Nabataean: [u32; 4] = [0x010880, 0x01089e, 0x0108a7, 0x0108af];
+const CR_Nabataean: [u32; 4] = [0x010880, 0x01089e, 0x0108a7, 0x0108af];
 
-static CR_Nag_Mundari: [u32; 2] = [0x01e4d0, 0x01e4f9];
+const CR_Nag_Mundari: [u32; 2] = [0x01e4d0, 0x01e4f9];
 
-static CR_Nandinagari: [u32; 6] = [0x0119a0, 0x0119a7, 0x0119aa, 0x0119d7, 0x0119da, 0x0119e4];
+const CR_Nandinagari: [u32; 6] = [0x0119a0, 0x0119a7, 0x0119aa, 0x0119d7, 0x0119da, 0x0119e4];
 
-static CR_New_Tai_Lue: [u32; 8] = [
+const CR_New_Tai_Lue: [u32; 8] = [
     0x001980, 0x0019ab, 0x0019b0, 0x0019c9, 0x0019d0, 0x0019da, 0x0019de, 0x0019df,
 ];
 
-static CR_Newa: [u32; 4] = [0x011400, 0x01145b, 0x01145d, 0x011461];
+const CR_Newa: [u32; 4] = [0x011400, 0x01145b, 0x01145d, 0x011461];
 
-static CR_Nko: [u32; 4] = [0x0007c0, 0x0007fa, 0x0007fd, 0x0007ff];
+const CR_Nko: [u32; 4] = [0x0007c0, 0x0007fa, 0x0007fd, 0x0007ff];
 
-static CR_Nl: [u32; 24] = [
+const CR_Nl: [u32; 24] = [
     0x0016ee, 0x0016f0, 0x002160, 0x002182, 0x002185, 0x002188, 0x003007, 0x003007, 0x003021,
     0x003029, 0x003038, 0x00303a, 0x00a6e6, 0x00a6ef, 0x010140, 0x010174, 0x010341, 0x010341,
     0x01034a, 0x01034a, 0x0103d1, 0x0103d5, 0x012400, 0x01246e,
 ];
 
-static CR_No: [u32; 144] = [
+const CR_No: [u32; 144] = [
     0x0000b2, 0x0000b3, 0x0000b9, 0x0000b9, 0x0000bc, 0x0000be, 0x0009f4, 0x0009f9, 0x000b72,
     0x000b77, 0x000bf0, 0x000bf2, 0x000c78, 0x000c7e, 0x000d58, 0x000d5e, 0x000d70, 0x000d78,
     0x000f2a, 0x000f33, 0x001369, 0x00137c, 0x0017f0, 0x0017f9, 0x0019da, 0x0019da, 0x002070,
@@ -4855,55 +4862,55 @@ static CR_No: [u32; 144] = [
     0x01ecaf, 0x01ecb1, 0x01ecb4, 0x01ed01, 0x01ed2d, 0x01ed2f, 0x01ed3d, 0x01f100, 0x01f10c,
 ];
 
-static CR_Noncharacter_Code_Point: [u32; 36] = [
+const CR_Noncharacter_Code_Point: [u32; 36] = [
     0x00fdd0, 0x00fdef, 0x00fffe, 0x00ffff, 0x01fffe, 0x01ffff, 0x02fffe, 0x02ffff, 0x03fffe,
     0x03ffff, 0x04fffe, 0x04ffff, 0x05fffe, 0x05ffff, 0x06fffe, 0x06ffff, 0x07fffe, 0x07ffff,
     0x08fffe, 0x08ffff, 0x09fffe, 0x09ffff, 0x0afffe, 0x0affff, 0x0bfffe, 0x0bffff, 0x0cfffe,
     0x0cffff, 0x0dfffe, 0x0dffff, 0x0efffe, 0x0effff, 0x0ffffe, 0x0fffff, 0x10fffe, 0x10ffff,
 ];
 
-static CR_Nushu: [u32; 4] = [0x016fe1, 0x016fe1, 0x01b170, 0x01b2fb];
+const CR_Nushu: [u32; 4] = [0x016fe1, 0x016fe1, 0x01b170, 0x01b2fb];
 
-static CR_Nyiakeng_Puachue_Hmong: [u32; 8] = [
+const CR_Nyiakeng_Puachue_Hmong: [u32; 8] = [
     0x01e100, 0x01e12c, 0x01e130, 0x01e13d, 0x01e140, 0x01e149, 0x01e14e, 0x01e14f,
 ];
 
-static CR_Ogham: [u32; 2] = [0x001680, 0x00169c];
+const CR_Ogham: [u32; 2] = [0x001680, 0x00169c];
 
-static CR_Ol_Chiki: [u32; 2] = [0x001c50, 0x001c7f];
+const CR_Ol_Chiki: [u32; 2] = [0x001c50, 0x001c7f];
 
-static CR_Ol_Onal: [u32; 4] = [0x01e5d0, 0x01e5fa, 0x01e5ff, 0x01e5ff];
+const CR_Ol_Onal: [u32; 4] = [0x01e5d0, 0x01e5fa, 0x01e5ff, 0x01e5ff];
 
-static CR_Old_Hungarian: [u32; 6] = [0x010c80, 0x010cb2, 0x010cc0, 0x010cf2, 0x010cfa, 0x010cff];
+const CR_Old_Hungarian: [u32; 6] = [0x010c80, 0x010cb2, 0x010cc0, 0x010cf2, 0x010cfa, 0x010cff];
 
-static CR_Old_Italic: [u32; 4] = [0x010300, 0x010323, 0x01032d, 0x01032f];
+const CR_Old_Italic: [u32; 4] = [0x010300, 0x010323, 0x01032d, 0x01032f];
 
-static CR_Old_North_Arabian: [u32; 2] = [0x010a80, 0x010a9f];
+const CR_Old_North_Arabian: [u32; 2] = [0x010a80, 0x010a9f];
 
-static CR_Old_Permic: [u32; 2] = [0x010350, 0x01037a];
+const CR_Old_Permic: [u32; 2] = [0x010350, 0x01037a];
 
-static CR_Old_Persian: [u32; 4] = [0x0103a0, 0x0103c3, 0x0103c8, 0x0103d5];
+const CR_Old_Persian: [u32; 4] = [0x0103a0, 0x0103c3, 0x0103c8, 0x0103d5];
 
-static CR_Old_Sogdian: [u32; 2] = [0x010f00, 0x010f27];
+const CR_Old_Sogdian: [u32; 2] = [0x010f00, 0x010f27];
 
-static CR_Old_South_Arabian: [u32; 2] = [0x010a60, 0x010a7f];
+const CR_Old_South_Arabian: [u32; 2] = [0x010a60, 0x010a7f];
 
-static CR_Old_Turkic: [u32; 2] = [0x010c00, 0x010c48];
+const CR_Old_Turkic: [u32; 2] = [0x010c00, 0x010c48];
 
-static CR_Old_Uyghur: [u32; 2] = [0x010f70, 0x010f89];
+const CR_Old_Uyghur: [u32; 2] = [0x010f70, 0x010f89];
 
-static CR_Oriya: [u32; 28] = [
+const CR_Oriya: [u32; 28] = [
     0x000b01, 0x000b03, 0x000b05, 0x000b0c, 0x000b0f, 0x000b10, 0x000b13, 0x000b28, 0x000b2a,
     0x000b30, 0x000b32, 0x000b33, 0x000b35, 0x000b39, 0x000b3c, 0x000b44, 0x000b47, 0x000b48,
     0x000b4b, 0x000b4d, 0x000b55, 0x000b57, 0x000b5c, 0x000b5d, 0x000b5f, 0x000b63, 0x000b66,
     0x000b77,
 ];
 
-static CR_Osage: [u32; 4] = [0x0104b0, 0x0104d3, 0x0104d8, 0x0104fb];
+const CR_Osage: [u32; 4] = [0x0104b0, 0x0104d3, 0x0104d8, 0x0104fb];
 
-static CR_Osmanya: [u32; 4] = [0x010480, 0x01049d, 0x0104a0, 0x0104a9];
+const CR_Osmanya: [u32; 4] = [0x010480, 0x01049d, 0x0104a0, 0x0104a9];
 
-static CR_Other_Alphabetic: [u32; 500] = [
+const CR_Other_Alphabetic: [u32; 500] = [
     0x000345, 0x000345, 0x000363, 0x00036f, 0x0005b0, 0x0005bd, 0x0005bf, 0x0005bf, 0x0005c1,
     0x0005c2, 0x0005c4, 0x0005c5, 0x0005c7, 0x0005c7, 0x000610, 0x00061a, 0x00064b, 0x000657,
     0x000659, 0x00065f, 0x000670, 0x000670, 0x0006d6, 0x0006dc, 0x0006e1, 0x0006e4, 0x0006e7,
@@ -4962,13 +4969,13 @@ static CR_Other_Alphabetic: [u32; 500] = [
     0x01f149, 0x01f150, 0x01f169, 0x01f170, 0x01f189,
 ];
 
-static CR_Other_Default_Ignorable_Code_Point: [u32; 22] = [
+const CR_Other_Default_Ignorable_Code_Point: [u32; 22] = [
     0x00034f, 0x00034f, 0x00115f, 0x001160, 0x0017b4, 0x0017b5, 0x002065, 0x002065, 0x003164,
     0x003164, 0x00ffa0, 0x00ffa0, 0x00fff0, 0x00fff8, 0x0e0000, 0x0e0000, 0x0e0002, 0x0e001f,
     0x0e0080, 0x0e00ff, 0x0e01f0, 0x0e0fff,
 ];
 
-static CR_Other_Grapheme_Extend: [u32; 98] = [
+const CR_Other_Grapheme_Extend: [u32; 98] = [
     0x0009be, 0x0009be, 0x0009d7, 0x0009d7, 0x000b3e, 0x000b3e, 0x000b57, 0x000b57, 0x000bbe,
     0x000bbe, 0x000bd7, 0x000bd7, 0x000cc0, 0x000cc0, 0x000cc2, 0x000cc2, 0x000cc7, 0x000cc8,
     0x000cca, 0x000ccb, 0x000cd5, 0x000cd6, 0x000d3e, 0x000d3e, 0x000d57, 0x000d57, 0x000dcf,
@@ -4982,16 +4989,16 @@ static CR_Other_Grapheme_Extend: [u32; 98] = [
     0x016ff0, 0x016ff1, 0x01d165, 0x01d166, 0x01d16d, 0x01d172, 0x0e0020, 0x0e007f,
 ];
 
-static CR_Other_ID_Continue: [u32; 14] = [
+const CR_Other_ID_Continue: [u32; 14] = [
     0x0000b7, 0x0000b7, 0x000387, 0x000387, 0x001369, 0x001371, 0x0019da, 0x0019da, 0x00200c,
     0x00200d, 0x0030fb, 0x0030fb, 0x00ff65, 0x00ff65,
 ];
 
-static CR_Other_ID_Start: [u32; 8] = [
+const CR_Other_ID_Start: [u32; 8] = [
     0x001885, 0x001886, 0x002118, 0x002118, 0x00212e, 0x00212e, 0x00309b, 0x00309c,
 ];
 
-static CR_Other_Lowercase: [u32; 56] = [
+const CR_Other_Lowercase: [u32; 56] = [
     0x0000aa, 0x0000aa, 0x0000ba, 0x0000ba, 0x0002b0, 0x0002b8, 0x0002c0, 0x0002c1, 0x0002e0,
     0x0002e4, 0x000345, 0x000345, 0x00037a, 0x00037a, 0x0010fc, 0x0010fc, 0x001d2c, 0x001d6a,
     0x001d78, 0x001d78, 0x001d9b, 0x001dbf, 0x002071, 0x002071, 0x00207f, 0x00207f, 0x002090,
@@ -5001,7 +5008,7 @@ static CR_Other_Lowercase: [u32; 56] = [
     0x01e030, 0x01e06d,
 ];
 
-static CR_Other_Math: [u32; 268] = [
+const CR_Other_Math: [u32; 268] = [
     0x00005e, 0x00005e, 0x0003d0, 0x0003d2, 0x0003d5, 0x0003d5, 0x0003f0, 0x0003f1, 0x0003f4,
     0x0003f5, 0x002016, 0x002016, 0x002032, 0x002034, 0x002040, 0x002040, 0x002061, 0x002064,
     0x00207d, 0x00207e, 0x00208d, 0x00208e, 0x0020d0, 0x0020dc, 0x0020e1, 0x0020e1, 0x0020e5,
@@ -5034,12 +5041,12 @@ static CR_Other_Math: [u32; 268] = [
     0x01ee9b, 0x01eea1, 0x01eea3, 0x01eea5, 0x01eea9, 0x01eeab, 0x01eebb,
 ];
 
-static CR_Other_Uppercase: [u32; 10] = [
+const CR_Other_Uppercase: [u32; 10] = [
     0x002160, 0x00216f, 0x0024b6, 0x0024cf, 0x01f130, 0x01f149, 0x01f150, 0x01f169, 0x01f170,
     0x01f189,
 ];
 
-static CR_P: [u32; 396] = [
+const CR_P: [u32; 396] = [
     0x000021, 0x000023, 0x000025, 0x00002a, 0x00002c, 0x00002f, 0x00003a, 0x00003b, 0x00003f,
     0x000040, 0x00005b, 0x00005d, 0x00005f, 0x00005f, 0x00007b, 0x00007b, 0x00007d, 0x00007d,
     0x0000a1, 0x0000a1, 0x0000a7, 0x0000a7, 0x0000ab, 0x0000ab, 0x0000b6, 0x0000b7, 0x0000bb,
@@ -5086,14 +5093,14 @@ static CR_P: [u32; 396] = [
     0x016fe2, 0x01bc9f, 0x01bc9f, 0x01da87, 0x01da8b, 0x01e5ff, 0x01e5ff, 0x01e95e, 0x01e95f,
 ];
 
-static CR_Pahawh_Hmong: [u32; 10] = [
+const CR_Pahawh_Hmong: [u32; 10] = [
     0x016b00, 0x016b45, 0x016b50, 0x016b59, 0x016b5b, 0x016b61, 0x016b63, 0x016b77, 0x016b7d,
     0x016b8f,
 ];
 
-static CR_Palmyrene: [u32; 2] = [0x010860, 0x01087f];
+const CR_Palmyrene: [u32; 2] = [0x010860, 0x01087f];
 
-static CR_Pattern_Syntax: [u32; 56] = [
+const CR_Pattern_Syntax: [u32; 56] = [
     0x000021, 0x00002f, 0x00003a, 0x000040, 0x00005b, 0x00005e, 0x000060, 0x000060, 0x00007b,
     0x00007e, 0x0000a1, 0x0000a7, 0x0000a9, 0x0000a9, 0x0000ab, 0x0000ac, 0x0000ae, 0x0000ae,
     0x0000b0, 0x0000b1, 0x0000b6, 0x0000b6, 0x0000bb, 0x0000bb, 0x0000bf, 0x0000bf, 0x0000d7,
@@ -5103,19 +5110,19 @@ static CR_Pattern_Syntax: [u32; 56] = [
     0x00fe45, 0x00fe46,
 ];
 
-static CR_Pattern_White_Space: [u32; 10] = [
+const CR_Pattern_White_Space: [u32; 10] = [
     0x000009, 0x00000d, 0x000020, 0x000020, 0x000085, 0x000085, 0x00200e, 0x00200f, 0x002028,
     0x002029,
 ];
 
-static CR_Pau_Cin_Hau: [u32; 2] = [0x011ac0, 0x011af8];
+const CR_Pau_Cin_Hau: [u32; 2] = [0x011ac0, 0x011af8];
 
-static CR_Pc: [u32; 12] = [
+const CR_Pc: [u32; 12] = [
     0x00005f, 0x00005f, 0x00203f, 0x002040, 0x002054, 0x002054, 0x00fe33, 0x00fe34, 0x00fe4d,
     0x00fe4f, 0x00ff3f, 0x00ff3f,
 ];
 
-static CR_Pd: [u32; 40] = [
+const CR_Pd: [u32; 40] = [
     0x00002d, 0x00002d, 0x00058a, 0x00058a, 0x0005be, 0x0005be, 0x001400, 0x001400, 0x001806,
     0x001806, 0x002010, 0x002015, 0x002e17, 0x002e17, 0x002e1a, 0x002e1a, 0x002e3a, 0x002e3b,
     0x002e40, 0x002e40, 0x002e5d, 0x002e5d, 0x00301c, 0x00301c, 0x003030, 0x003030, 0x0030a0,
@@ -5123,7 +5130,7 @@ static CR_Pd: [u32; 40] = [
     0x010d6e, 0x010d6e, 0x010ead, 0x010ead,
 ];
 
-static CR_Pe: [u32; 152] = [
+const CR_Pe: [u32; 152] = [
     0x000029, 0x000029, 0x00005d, 0x00005d, 0x00007d, 0x00007d, 0x000f3b, 0x000f3b, 0x000f3d,
     0x000f3d, 0x00169c, 0x00169c, 0x002046, 0x002046, 0x00207e, 0x00207e, 0x00208e, 0x00208e,
     0x002309, 0x002309, 0x00230b, 0x00230b, 0x00232a, 0x00232a, 0x002769, 0x002769, 0x00276b,
@@ -5143,23 +5150,23 @@ static CR_Pe: [u32; 152] = [
     0x00ff3d, 0x00ff3d, 0x00ff5d, 0x00ff5d, 0x00ff60, 0x00ff60, 0x00ff63, 0x00ff63,
 ];
 
-static CR_Pf: [u32; 20] = [
+const CR_Pf: [u32; 20] = [
     0x0000bb, 0x0000bb, 0x002019, 0x002019, 0x00201d, 0x00201d, 0x00203a, 0x00203a, 0x002e03,
     0x002e03, 0x002e05, 0x002e05, 0x002e0a, 0x002e0a, 0x002e0d, 0x002e0d, 0x002e1d, 0x002e1d,
     0x002e21, 0x002e21,
 ];
 
-static CR_Phags_Pa: [u32; 2] = [0x00a840, 0x00a877];
+const CR_Phags_Pa: [u32; 2] = [0x00a840, 0x00a877];
 
-static CR_Phoenician: [u32; 4] = [0x010900, 0x01091b, 0x01091f, 0x01091f];
+const CR_Phoenician: [u32; 4] = [0x010900, 0x01091b, 0x01091f, 0x01091f];
 
-static CR_Pi: [u32; 22] = [
+const CR_Pi: [u32; 22] = [
     0x0000ab, 0x0000ab, 0x002018, 0x002018, 0x00201b, 0x00201c, 0x00201f, 0x00201f, 0x002039,
     0x002039, 0x002e02, 0x002e02, 0x002e04, 0x002e04, 0x002e09, 0x002e09, 0x002e0c, 0x002e0c,
     0x002e1c, 0x002e1c, 0x002e20, 0x002e20,
 ];
 
-static CR_Po: [u32; 386] = [
+const CR_Po: [u32; 386] = [
     0x000021, 0x000023, 0x000025, 0x000027, 0x00002a, 0x00002a, 0x00002c, 0x00002c, 0x00002e,
     0x00002f, 0x00003a, 0x00003b, 0x00003f, 0x000040, 0x00005c, 0x00005c, 0x0000a1, 0x0000a1,
     0x0000a7, 0x0000a7, 0x0000b6, 0x0000b7, 0x0000bf, 0x0000bf, 0x00037e, 0x00037e, 0x000387,
@@ -5205,12 +5212,12 @@ static CR_Po: [u32; 386] = [
     0x01bc9f, 0x01bc9f, 0x01da87, 0x01da8b, 0x01e5ff, 0x01e5ff, 0x01e95e, 0x01e95f,
 ];
 
-static CR_Prepended_Concatenation_Mark: [u32; 14] = [
+const CR_Prepended_Concatenation_Mark: [u32; 14] = [
     0x000600, 0x000605, 0x0006dd, 0x0006dd, 0x00070f, 0x00070f, 0x000890, 0x000891, 0x0008e2,
     0x0008e2, 0x0110bd, 0x0110bd, 0x0110cd, 0x0110cd,
 ];
 
-static CR_Ps: [u32; 158] = [
+const CR_Ps: [u32; 158] = [
     0x000028, 0x000028, 0x00005b, 0x00005b, 0x00007b, 0x00007b, 0x000f3a, 0x000f3a, 0x000f3c,
     0x000f3c, 0x00169b, 0x00169b, 0x00201a, 0x00201a, 0x00201e, 0x00201e, 0x002045, 0x002045,
     0x00207d, 0x00207d, 0x00208d, 0x00208d, 0x002308, 0x002308, 0x00230a, 0x00230a, 0x002329,
@@ -5231,23 +5238,23 @@ static CR_Ps: [u32; 158] = [
     0x00ff5b, 0x00ff5f, 0x00ff5f, 0x00ff62, 0x00ff62,
 ];
 
-static CR_Psalter_Pahlavi: [u32; 6] = [0x010b80, 0x010b91, 0x010b99, 0x010b9c, 0x010ba9, 0x010baf];
+const CR_Psalter_Pahlavi: [u32; 6] = [0x010b80, 0x010b91, 0x010b99, 0x010b9c, 0x010ba9, 0x010baf];
 
-static CR_Quotation_Mark: [u32; 26] = [
+const CR_Quotation_Mark: [u32; 26] = [
     0x000022, 0x000022, 0x000027, 0x000027, 0x0000ab, 0x0000ab, 0x0000bb, 0x0000bb, 0x002018,
     0x00201f, 0x002039, 0x00203a, 0x002e42, 0x002e42, 0x00300c, 0x00300f, 0x00301d, 0x00301f,
     0x00fe41, 0x00fe44, 0x00ff02, 0x00ff02, 0x00ff07, 0x00ff07, 0x00ff62, 0x00ff63,
 ];
 
-static CR_Radical: [u32; 6] = [0x002e80, 0x002e99, 0x002e9b, 0x002ef3, 0x002f00, 0x002fd5];
+const CR_Radical: [u32; 6] = [0x002e80, 0x002e99, 0x002e9b, 0x002ef3, 0x002f00, 0x002fd5];
 
-static CR_Regional_Indicator: [u32; 2] = [0x01f1e6, 0x01f1ff];
+const CR_Regional_Indicator: [u32; 2] = [0x01f1e6, 0x01f1ff];
 
-static CR_Rejang: [u32; 4] = [0x00a930, 0x00a953, 0x00a95f, 0x00a95f];
+const CR_Rejang: [u32; 4] = [0x00a930, 0x00a953, 0x00a95f, 0x00a95f];
 
-static CR_Runic: [u32; 4] = [0x0016a0, 0x0016ea, 0x0016ee, 0x0016f8];
+const CR_Runic: [u32; 4] = [0x0016a0, 0x0016ea, 0x0016ee, 0x0016f8];
 
-static CR_S: [u32; 472] = [
+const CR_S: [u32; 472] = [
     0x000024, 0x000024, 0x00002b, 0x00002b, 0x00003c, 0x00003e, 0x00005e, 0x00005e, 0x000060,
     0x000060, 0x00007c, 0x00007c, 0x00007e, 0x00007e, 0x0000a2, 0x0000a6, 0x0000a8, 0x0000a9,
     0x0000ac, 0x0000ac, 0x0000ae, 0x0000b1, 0x0000b4, 0x0000b4, 0x0000b8, 0x0000b8, 0x0000d7,
@@ -5303,11 +5310,11 @@ static CR_S: [u32; 472] = [
     0x01fb00, 0x01fb92, 0x01fb94, 0x01fbef,
 ];
 
-static CR_Samaritan: [u32; 4] = [0x000800, 0x00082d, 0x000830, 0x00083e];
+const CR_Samaritan: [u32; 4] = [0x000800, 0x00082d, 0x000830, 0x00083e];
 
-static CR_Saurashtra: [u32; 4] = [0x00a880, 0x00a8c5, 0x00a8ce, 0x00a8d9];
+const CR_Saurashtra: [u32; 4] = [0x00a880, 0x00a8c5, 0x00a8ce, 0x00a8d9];
 
-static CR_Sc: [u32; 42] = [
+const CR_Sc: [u32; 42] = [
     0x000024, 0x000024, 0x0000a2, 0x0000a5, 0x00058f, 0x00058f, 0x00060b, 0x00060b, 0x0007fe,
     0x0007ff, 0x0009f2, 0x0009f3, 0x0009fb, 0x0009fb, 0x000af1, 0x000af1, 0x000bf9, 0x000bf9,
     0x000e3f, 0x000e3f, 0x0017db, 0x0017db, 0x0020a0, 0x0020c0, 0x00a838, 0x00a838, 0x00fdfc,
@@ -5315,7 +5322,7 @@ static CR_Sc: [u32; 42] = [
     0x011fdd, 0x011fe0, 0x01e2ff, 0x01e2ff, 0x01ecb0, 0x01ecb0,
 ];
 
-static CR_Sentence_Terminal: [u32; 176] = [
+const CR_Sentence_Terminal: [u32; 176] = [
     0x000021, 0x000021, 0x00002e, 0x00002e, 0x00003f, 0x00003f, 0x000589, 0x000589, 0x00061d,
     0x00061f, 0x0006d4, 0x0006d4, 0x000700, 0x000702, 0x0007f9, 0x0007f9, 0x000837, 0x000837,
     0x000839, 0x000839, 0x00083d, 0x00083e, 0x000964, 0x000965, 0x00104a, 0x00104b, 0x001362,
@@ -5338,21 +5345,21 @@ static CR_Sentence_Terminal: [u32; 176] = [
     0x016e98, 0x01bc9f, 0x01bc9f, 0x01da88, 0x01da88,
 ];
 
-static CR_Sharada: [u32; 2] = [0x011180, 0x0111df];
+const CR_Sharada: [u32; 2] = [0x011180, 0x0111df];
 
-static CR_Shavian: [u32; 2] = [0x010450, 0x01047f];
+const CR_Shavian: [u32; 2] = [0x010450, 0x01047f];
 
-static CR_Siddham: [u32; 4] = [0x011580, 0x0115b5, 0x0115b8, 0x0115dd];
+const CR_Siddham: [u32; 4] = [0x011580, 0x0115b5, 0x0115b8, 0x0115dd];
 
-static CR_SignWriting: [u32; 6] = [0x01d800, 0x01da8b, 0x01da9b, 0x01da9f, 0x01daa1, 0x01daaf];
+const CR_SignWriting: [u32; 6] = [0x01d800, 0x01da8b, 0x01da9b, 0x01da9f, 0x01daa1, 0x01daaf];
 
-static CR_Sinhala: [u32; 26] = [
+const CR_Sinhala: [u32; 26] = [
     0x000d81, 0x000d83, 0x000d85, 0x000d96, 0x000d9a, 0x000db1, 0x000db3, 0x000dbb, 0x000dbd,
     0x000dbd, 0x000dc0, 0x000dc6, 0x000dca, 0x000dca, 0x000dcf, 0x000dd4, 0x000dd6, 0x000dd6,
     0x000dd8, 0x000ddf, 0x000de6, 0x000def, 0x000df2, 0x000df4, 0x0111e1, 0x0111f4,
 ];
 
-static CR_Sk: [u32; 62] = [
+const CR_Sk: [u32; 62] = [
     0x00005e, 0x00005e, 0x000060, 0x000060, 0x0000a8, 0x0000a8, 0x0000af, 0x0000af, 0x0000b4,
     0x0000b4, 0x0000b8, 0x0000b8, 0x0002c2, 0x0002c5, 0x0002d2, 0x0002df, 0x0002e5, 0x0002eb,
     0x0002ed, 0x0002ed, 0x0002ef, 0x0002ff, 0x000375, 0x000375, 0x000384, 0x000385, 0x000888,
@@ -5362,7 +5369,7 @@ static CR_Sk: [u32; 62] = [
     0x00ff3e, 0x00ff3e, 0x00ff40, 0x00ff40, 0x00ffe3, 0x00ffe3, 0x01f3fb, 0x01f3ff,
 ];
 
-static CR_Sm: [u32; 130] = [
+const CR_Sm: [u32; 130] = [
     0x00002b, 0x00002b, 0x00003c, 0x00003e, 0x00007c, 0x00007c, 0x00007e, 0x00007e, 0x0000ac,
     0x0000ac, 0x0000b1, 0x0000b1, 0x0000d7, 0x0000d7, 0x0000f7, 0x0000f7, 0x0003f6, 0x0003f6,
     0x000606, 0x000608, 0x002044, 0x002044, 0x002052, 0x002052, 0x00207a, 0x00207c, 0x00208a,
@@ -5380,7 +5387,7 @@ static CR_Sm: [u32; 130] = [
     0x01d7c3, 0x01d7c3, 0x01eef0, 0x01eef1,
 ];
 
-static CR_So: [u32; 374] = [
+const CR_So: [u32; 374] = [
     0x0000a6, 0x0000a6, 0x0000a9, 0x0000a9, 0x0000ae, 0x0000ae, 0x0000b0, 0x0000b0, 0x000482,
     0x000482, 0x00058d, 0x00058e, 0x00060e, 0x00060f, 0x0006de, 0x0006de, 0x0006e9, 0x0006e9,
     0x0006fd, 0x0006fe, 0x0007f6, 0x0007f6, 0x0009fa, 0x0009fa, 0x000b70, 0x000b70, 0x000bf3,
@@ -5425,7 +5432,7 @@ static CR_So: [u32; 374] = [
     0x01faf8, 0x01fb00, 0x01fb92, 0x01fb94, 0x01fbef,
 ];
 
-static CR_Soft_Dotted: [u32; 68] = [
+const CR_Soft_Dotted: [u32; 68] = [
     0x000069, 0x00006a, 0x00012f, 0x00012f, 0x000249, 0x000249, 0x000268, 0x000268, 0x00029d,
     0x00029d, 0x0002b2, 0x0002b2, 0x0003f3, 0x0003f3, 0x000456, 0x000456, 0x000458, 0x000458,
     0x001d62, 0x001d62, 0x001d96, 0x001d96, 0x001da4, 0x001da4, 0x001da8, 0x001da8, 0x001e2d,
@@ -5436,57 +5443,57 @@ static CR_Soft_Dotted: [u32; 68] = [
     0x01df1a, 0x01e04c, 0x01e04d, 0x01e068, 0x01e068,
 ];
 
-static CR_Sogdian: [u32; 2] = [0x010f30, 0x010f59];
+const CR_Sogdian: [u32; 2] = [0x010f30, 0x010f59];
 
-static CR_Sora_Sompeng: [u32; 4] = [0x0110d0, 0x0110e8, 0x0110f0, 0x0110f9];
+const CR_Sora_Sompeng: [u32; 4] = [0x0110d0, 0x0110e8, 0x0110f0, 0x0110f9];
 
-static CR_Soyombo: [u32; 2] = [0x011a50, 0x011aa2];
+const CR_Soyombo: [u32; 2] = [0x011a50, 0x011aa2];
 
-static CR_Sundanese: [u32; 4] = [0x001b80, 0x001bbf, 0x001cc0, 0x001cc7];
+const CR_Sundanese: [u32; 4] = [0x001b80, 0x001bbf, 0x001cc0, 0x001cc7];
 
-static CR_Sunuwar: [u32; 4] = [0x011bc0, 0x011be1, 0x011bf0, 0x011bf9];
+const CR_Sunuwar: [u32; 4] = [0x011bc0, 0x011be1, 0x011bf0, 0x011bf9];
 
-static CR_Syloti_Nagri: [u32; 2] = [0x00a800, 0x00a82c];
+const CR_Syloti_Nagri: [u32; 2] = [0x00a800, 0x00a82c];
 
-static CR_Syriac: [u32; 8] = [
+const CR_Syriac: [u32; 8] = [
     0x000700, 0x00070d, 0x00070f, 0x00074a, 0x00074d, 0x00074f, 0x000860, 0x00086a,
 ];
 
-static CR_Tagalog: [u32; 4] = [0x001700, 0x001715, 0x00171f, 0x00171f];
+const CR_Tagalog: [u32; 4] = [0x001700, 0x001715, 0x00171f, 0x00171f];
 
-static CR_Tagbanwa: [u32; 6] = [0x001760, 0x00176c, 0x00176e, 0x001770, 0x001772, 0x001773];
+const CR_Tagbanwa: [u32; 6] = [0x001760, 0x00176c, 0x00176e, 0x001770, 0x001772, 0x001773];
 
-static CR_Tai_Le: [u32; 4] = [0x001950, 0x00196d, 0x001970, 0x001974];
+const CR_Tai_Le: [u32; 4] = [0x001950, 0x00196d, 0x001970, 0x001974];
 
-static CR_Tai_Tham: [u32; 10] = [
+const CR_Tai_Tham: [u32; 10] = [
     0x001a20, 0x001a5e, 0x001a60, 0x001a7c, 0x001a7f, 0x001a89, 0x001a90, 0x001a99, 0x001aa0,
     0x001aad,
 ];
 
-static CR_Tai_Viet: [u32; 4] = [0x00aa80, 0x00aac2, 0x00aadb, 0x00aadf];
+const CR_Tai_Viet: [u32; 4] = [0x00aa80, 0x00aac2, 0x00aadb, 0x00aadf];
 
-static CR_Takri: [u32; 4] = [0x011680, 0x0116b9, 0x0116c0, 0x0116c9];
+const CR_Takri: [u32; 4] = [0x011680, 0x0116b9, 0x0116c0, 0x0116c9];
 
-static CR_Tamil: [u32; 36] = [
+const CR_Tamil: [u32; 36] = [
     0x000b82, 0x000b83, 0x000b85, 0x000b8a, 0x000b8e, 0x000b90, 0x000b92, 0x000b95, 0x000b99,
     0x000b9a, 0x000b9c, 0x000b9c, 0x000b9e, 0x000b9f, 0x000ba3, 0x000ba4, 0x000ba8, 0x000baa,
     0x000bae, 0x000bb9, 0x000bbe, 0x000bc2, 0x000bc6, 0x000bc8, 0x000bca, 0x000bcd, 0x000bd0,
     0x000bd0, 0x000bd7, 0x000bd7, 0x000be6, 0x000bfa, 0x011fc0, 0x011ff1, 0x011fff, 0x011fff,
 ];
 
-static CR_Tangsa: [u32; 4] = [0x016a70, 0x016abe, 0x016ac0, 0x016ac9];
+const CR_Tangsa: [u32; 4] = [0x016a70, 0x016abe, 0x016ac0, 0x016ac9];
 
-static CR_Tangut: [u32; 8] = [
+const CR_Tangut: [u32; 8] = [
     0x016fe0, 0x016fe0, 0x017000, 0x0187f7, 0x018800, 0x018aff, 0x018d00, 0x018d08,
 ];
 
-static CR_Telugu: [u32; 26] = [
+const CR_Telugu: [u32; 26] = [
     0x000c00, 0x000c0c, 0x000c0e, 0x000c10, 0x000c12, 0x000c28, 0x000c2a, 0x000c39, 0x000c3c,
     0x000c44, 0x000c46, 0x000c48, 0x000c4a, 0x000c4d, 0x000c55, 0x000c56, 0x000c58, 0x000c5a,
     0x000c5d, 0x000c5d, 0x000c60, 0x000c63, 0x000c66, 0x000c6f, 0x000c77, 0x000c7f,
 ];
 
-static CR_Terminal_Punctuation: [u32; 232] = [
+const CR_Terminal_Punctuation: [u32; 232] = [
     0x000021, 0x000021, 0x00002c, 0x00002c, 0x00002e, 0x00002e, 0x00003a, 0x00003b, 0x00003f,
     0x00003f, 0x00037e, 0x00037e, 0x000387, 0x000387, 0x000589, 0x000589, 0x0005c3, 0x0005c3,
     0x00060c, 0x00060c, 0x00061b, 0x00061b, 0x00061d, 0x00061f, 0x0006d4, 0x0006d4, 0x000700,
@@ -5515,39 +5522,39 @@ static CR_Terminal_Punctuation: [u32; 232] = [
     0x016d6f, 0x016e97, 0x016e98, 0x01bc9f, 0x01bc9f, 0x01da87, 0x01da8a,
 ];
 
-static CR_Thaana: [u32; 2] = [0x000780, 0x0007b1];
+const CR_Thaana: [u32; 2] = [0x000780, 0x0007b1];
 
-static CR_Thai: [u32; 4] = [0x000e01, 0x000e3a, 0x000e40, 0x000e5b];
+const CR_Thai: [u32; 4] = [0x000e01, 0x000e3a, 0x000e40, 0x000e5b];
 
-static CR_Tibetan: [u32; 14] = [
+const CR_Tibetan: [u32; 14] = [
     0x000f00, 0x000f47, 0x000f49, 0x000f6c, 0x000f71, 0x000f97, 0x000f99, 0x000fbc, 0x000fbe,
     0x000fcc, 0x000fce, 0x000fd4, 0x000fd9, 0x000fda,
 ];
 
-static CR_Tifinagh: [u32; 6] = [0x002d30, 0x002d67, 0x002d6f, 0x002d70, 0x002d7f, 0x002d7f];
+const CR_Tifinagh: [u32; 6] = [0x002d30, 0x002d67, 0x002d6f, 0x002d70, 0x002d7f, 0x002d7f];
 
-static CR_Tirhuta: [u32; 4] = [0x011480, 0x0114c7, 0x0114d0, 0x0114d9];
+const CR_Tirhuta: [u32; 4] = [0x011480, 0x0114c7, 0x0114d0, 0x0114d9];
 
-static CR_Todhri: [u32; 2] = [0x0105c0, 0x0105f3];
+const CR_Todhri: [u32; 2] = [0x0105c0, 0x0105f3];
 
-static CR_Toto: [u32; 2] = [0x01e290, 0x01e2ae];
+const CR_Toto: [u32; 2] = [0x01e290, 0x01e2ae];
 
-static CR_Tulu_Tigalari: [u32; 22] = [
+const CR_Tulu_Tigalari: [u32; 22] = [
     0x011380, 0x011389, 0x01138b, 0x01138b, 0x01138e, 0x01138e, 0x011390, 0x0113b5, 0x0113b7,
     0x0113c0, 0x0113c2, 0x0113c2, 0x0113c5, 0x0113c5, 0x0113c7, 0x0113ca, 0x0113cc, 0x0113d5,
     0x0113d7, 0x0113d8, 0x0113e1, 0x0113e2,
 ];
 
-static CR_Ugaritic: [u32; 4] = [0x010380, 0x01039d, 0x01039f, 0x01039f];
+const CR_Ugaritic: [u32; 4] = [0x010380, 0x01039d, 0x01039f, 0x01039f];
 
-static CR_Unified_Ideograph: [u32; 34] = [
+const CR_Unified_Ideograph: [u32; 34] = [
     0x003400, 0x004dbf, 0x004e00, 0x009fff, 0x00fa0e, 0x00fa0f, 0x00fa11, 0x00fa11, 0x00fa13,
     0x00fa14, 0x00fa1f, 0x00fa1f, 0x00fa21, 0x00fa21, 0x00fa23, 0x00fa24, 0x00fa27, 0x00fa29,
     0x020000, 0x02a6df, 0x02a700, 0x02b739, 0x02b740, 0x02b81d, 0x02b820, 0x02cea1, 0x02ceb0,
     0x02ebe0, 0x02ebf0, 0x02ee5d, 0x030000, 0x03134a, 0x031350, 0x0323af,
 ];
 
-static CR_Unknown: [u32; 1458] = [
+const CR_Unknown: [u32; 1458] = [
     0x000378, 0x000379, 0x000380, 0x000383, 0x00038b, 0x00038b, 0x00038d, 0x00038d, 0x0003a2,
     0x0003a2, 0x000530, 0x000530, 0x000557, 0x000558, 0x00058b, 0x00058c, 0x000590, 0x000590,
     0x0005c8, 0x0005cf, 0x0005eb, 0x0005ee, 0x0005f5, 0x0005ff, 0x00070e, 0x00070e, 0x00074b,
@@ -5712,22 +5719,22 @@ static CR_Unknown: [u32; 1458] = [
     0x03134f, 0x0323b0, 0x0e0000, 0x0e0002, 0x0e001f, 0x0e0080, 0x0e00ff, 0x0e01f0, 0x10ffff,
 ];
 
-static CR_Vai: [u32; 2] = [0x00a500, 0x00a62b];
+const CR_Vai: [u32; 2] = [0x00a500, 0x00a62b];
 
-static CR_Variation_Selector: [u32; 8] = [
+const CR_Variation_Selector: [u32; 8] = [
     0x00180b, 0x00180d, 0x00180f, 0x00180f, 0x00fe00, 0x00fe0f, 0x0e0100, 0x0e01ef,
 ];
 
-static CR_Vithkuqi: [u32; 16] = [
+const CR_Vithkuqi: [u32; 16] = [
     0x010570, 0x01057a, 0x01057c, 0x01058a, 0x01058c, 0x010592, 0x010594, 0x010595, 0x010597,
     0x0105a1, 0x0105a3, 0x0105b1, 0x0105b3, 0x0105b9, 0x0105bb, 0x0105bc,
 ];
 
-static CR_Wancho: [u32; 4] = [0x01e2c0, 0x01e2f9, 0x01e2ff, 0x01e2ff];
+const CR_Wancho: [u32; 4] = [0x01e2c0, 0x01e2f9, 0x01e2ff, 0x01e2ff];
 
-static CR_Warang_Citi: [u32; 4] = [0x0118a0, 0x0118f2, 0x0118ff, 0x0118ff];
+const CR_Warang_Citi: [u32; 4] = [0x0118a0, 0x0118f2, 0x0118ff, 0x0118ff];
 
-static CR_XID_Continue: [u32; 1600] = [
+const CR_XID_Continue: [u32; 1600] = [
     0x000030, 0x000039, 0x000041, 0x00005a, 0x00005f, 0x00005f, 0x000061, 0x00007a, 0x0000aa,
     0x0000aa, 0x0000b5, 0x0000b5, 0x0000b7, 0x0000b7, 0x0000ba, 0x0000ba, 0x0000c0, 0x0000d6,
     0x0000d8, 0x0000f6, 0x0000f8, 0x0002c1, 0x0002c6, 0x0002d1, 0x0002e0, 0x0002e4, 0x0002ec,
@@ -5908,7 +5915,7 @@ static CR_XID_Continue: [u32; 1600] = [
     0x02fa1d, 0x030000, 0x03134a, 0x031350, 0x0323af, 0x0e0100, 0x0e01ef,
 ];
 
-static CR_XID_Start: [u32; 1368] = [
+const CR_XID_Start: [u32; 1368] = [
     0x000041, 0x00005a, 0x000061, 0x00007a, 0x0000aa, 0x0000aa, 0x0000b5, 0x0000b5, 0x0000ba,
     0x0000ba, 0x0000c0, 0x0000d6, 0x0000d8, 0x0000f6, 0x0000f8, 0x0002c1, 0x0002c6, 0x0002d1,
     0x0002e0, 0x0002e4, 0x0002ec, 0x0002ec, 0x0002ee, 0x0002ee, 0x000370, 0x000374, 0x000376,
@@ -6063,683 +6070,683 @@ static CR_XID_Start: [u32; 1368] = [
     0x02ebe0, 0x02ebf0, 0x02ee5d, 0x02f800, 0x02fa1d, 0x030000, 0x03134a, 0x031350, 0x0323af,
 ];
 
-static CR_Yezidi: [u32; 6] = [0x010e80, 0x010ea9, 0x010eab, 0x010ead, 0x010eb0, 0x010eb1];
+const CR_Yezidi: [u32; 6] = [0x010e80, 0x010ea9, 0x010eab, 0x010ead, 0x010eb0, 0x010eb1];
 
-static CR_Yi: [u32; 4] = [0x00a000, 0x00a48c, 0x00a490, 0x00a4c6];
+const CR_Yi: [u32; 4] = [0x00a000, 0x00a48c, 0x00a490, 0x00a4c6];
 
-static CR_Z: [u32; 16] = [
+const CR_Z: [u32; 16] = [
     0x000020, 0x000020, 0x0000a0, 0x0000a0, 0x001680, 0x001680, 0x002000, 0x00200a, 0x002028,
     0x002029, 0x00202f, 0x00202f, 0x00205f, 0x00205f, 0x003000, 0x003000,
 ];
 
-static CR_Zanabazar_Square: [u32; 2] = [0x011a00, 0x011a47];
+const CR_Zanabazar_Square: [u32; 2] = [0x011a00, 0x011a47];
 
-static CR_Zl: [u32; 2] = [0x002028, 0x002028];
+const CR_Zl: [u32; 2] = [0x002028, 0x002028];
 
-static CR_Zp: [u32; 2] = [0x002029, 0x002029];
+const CR_Zp: [u32; 2] = [0x002029, 0x002029];
 
-static CR_Zs: [u32; 14] = [
+const CR_Zs: [u32; 14] = [
     0x000020, 0x000020, 0x0000a0, 0x0000a0, 0x001680, 0x001680, 0x002000, 0x00200a, 0x00202f,
     0x00202f, 0x00205f, 0x00205f, 0x003000, 0x003000,
 ];
 
-static CR_In_Latin_1_Supplement: [u32; 2] = [0x000080, 0x0000ff];
+const CR_In_Latin_1_Supplement: [u32; 2] = [0x000080, 0x0000ff];
 
-static CR_In_Latin_Extended_A: [u32; 2] = [0x000100, 0x00017f];
+const CR_In_Latin_Extended_A: [u32; 2] = [0x000100, 0x00017f];
 
-static CR_In_Latin_Extended_B: [u32; 2] = [0x000180, 0x00024f];
+const CR_In_Latin_Extended_B: [u32; 2] = [0x000180, 0x00024f];
 
-static CR_In_IPA_Extensions: [u32; 2] = [0x000250, 0x0002af];
+const CR_In_IPA_Extensions: [u32; 2] = [0x000250, 0x0002af];
 
-static CR_In_Spacing_Modifier_Letters: [u32; 2] = [0x0002b0, 0x0002ff];
+const CR_In_Spacing_Modifier_Letters: [u32; 2] = [0x0002b0, 0x0002ff];
 
-static CR_In_Combining_Diacritical_Marks: [u32; 2] = [0x000300, 0x00036f];
+const CR_In_Combining_Diacritical_Marks: [u32; 2] = [0x000300, 0x00036f];
 
-static CR_In_Greek_and_Coptic: [u32; 2] = [0x000370, 0x0003ff];
+const CR_In_Greek_and_Coptic: [u32; 2] = [0x000370, 0x0003ff];
 
-static CR_In_Cyrillic: [u32; 2] = [0x000400, 0x0004ff];
+const CR_In_Cyrillic: [u32; 2] = [0x000400, 0x0004ff];
 
-static CR_In_Cyrillic_Supplement: [u32; 2] = [0x000500, 0x00052f];
+const CR_In_Cyrillic_Supplement: [u32; 2] = [0x000500, 0x00052f];
 
-static CR_In_Armenian: [u32; 2] = [0x000530, 0x00058f];
+const CR_In_Armenian: [u32; 2] = [0x000530, 0x00058f];
 
-static CR_In_Hebrew: [u32; 2] = [0x000590, 0x0005ff];
+const CR_In_Hebrew: [u32; 2] = [0x000590, 0x0005ff];
 
-static CR_In_Arabic: [u32; 2] = [0x000600, 0x0006ff];
+const CR_In_Arabic: [u32; 2] = [0x000600, 0x0006ff];
 
-static CR_In_Syriac: [u32; 2] = [0x000700, 0x00074f];
+const CR_In_Syriac: [u32; 2] = [0x000700, 0x00074f];
 
-static CR_In_Arabic_Supplement: [u32; 2] = [0x000750, 0x00077f];
+const CR_In_Arabic_Supplement: [u32; 2] = [0x000750, 0x00077f];
 
-static CR_In_Thaana: [u32; 2] = [0x000780, 0x0007bf];
+const CR_In_Thaana: [u32; 2] = [0x000780, 0x0007bf];
 
-static CR_In_NKo: [u32; 2] = [0x0007c0, 0x0007ff];
+const CR_In_NKo: [u32; 2] = [0x0007c0, 0x0007ff];
 
-static CR_In_Samaritan: [u32; 2] = [0x000800, 0x00083f];
+const CR_In_Samaritan: [u32; 2] = [0x000800, 0x00083f];
 
-static CR_In_Mandaic: [u32; 2] = [0x000840, 0x00085f];
+const CR_In_Mandaic: [u32; 2] = [0x000840, 0x00085f];
 
-static CR_In_Syriac_Supplement: [u32; 2] = [0x000860, 0x00086f];
+const CR_In_Syriac_Supplement: [u32; 2] = [0x000860, 0x00086f];
 
-static CR_In_Arabic_Extended_B: [u32; 2] = [0x000870, 0x00089f];
+const CR_In_Arabic_Extended_B: [u32; 2] = [0x000870, 0x00089f];
 
-static CR_In_Arabic_Extended_A: [u32; 2] = [0x0008a0, 0x0008ff];
+const CR_In_Arabic_Extended_A: [u32; 2] = [0x0008a0, 0x0008ff];
 
-static CR_In_Devanagari: [u32; 2] = [0x000900, 0x00097f];
+const CR_In_Devanagari: [u32; 2] = [0x000900, 0x00097f];
 
-static CR_In_Bengali: [u32; 2] = [0x000980, 0x0009ff];
+const CR_In_Bengali: [u32; 2] = [0x000980, 0x0009ff];
 
-static CR_In_Gurmukhi: [u32; 2] = [0x000a00, 0x000a7f];
+const CR_In_Gurmukhi: [u32; 2] = [0x000a00, 0x000a7f];
 
-static CR_In_Gujarati: [u32; 2] = [0x000a80, 0x000aff];
+const CR_In_Gujarati: [u32; 2] = [0x000a80, 0x000aff];
 
-static CR_In_Oriya: [u32; 2] = [0x000b00, 0x000b7f];
+const CR_In_Oriya: [u32; 2] = [0x000b00, 0x000b7f];
 
-static CR_In_Tamil: [u32; 2] = [0x000b80, 0x000bff];
+const CR_In_Tamil: [u32; 2] = [0x000b80, 0x000bff];
 
-static CR_In_Telugu: [u32; 2] = [0x000c00, 0x000c7f];
+const CR_In_Telugu: [u32; 2] = [0x000c00, 0x000c7f];
 
-static CR_In_Kannada: [u32; 2] = [0x000c80, 0x000cff];
+const CR_In_Kannada: [u32; 2] = [0x000c80, 0x000cff];
 
-static CR_In_Malayalam: [u32; 2] = [0x000d00, 0x000d7f];
+const CR_In_Malayalam: [u32; 2] = [0x000d00, 0x000d7f];
 
-static CR_In_Sinhala: [u32; 2] = [0x000d80, 0x000dff];
+const CR_In_Sinhala: [u32; 2] = [0x000d80, 0x000dff];
 
-static CR_In_Thai: [u32; 2] = [0x000e00, 0x000e7f];
+const CR_In_Thai: [u32; 2] = [0x000e00, 0x000e7f];
 
-static CR_In_Lao: [u32; 2] = [0x000e80, 0x000eff];
+const CR_In_Lao: [u32; 2] = [0x000e80, 0x000eff];
 
-static CR_In_Tibetan: [u32; 2] = [0x000f00, 0x000fff];
+const CR_In_Tibetan: [u32; 2] = [0x000f00, 0x000fff];
 
-static CR_In_Myanmar: [u32; 2] = [0x001000, 0x00109f];
+const CR_In_Myanmar: [u32; 2] = [0x001000, 0x00109f];
 
-static CR_In_Georgian: [u32; 2] = [0x0010a0, 0x0010ff];
+const CR_In_Georgian: [u32; 2] = [0x0010a0, 0x0010ff];
 
-static CR_In_Hangul_Jamo: [u32; 2] = [0x001100, 0x0011ff];
+const CR_In_Hangul_Jamo: [u32; 2] = [0x001100, 0x0011ff];
 
-static CR_In_Ethiopic: [u32; 2] = [0x001200, 0x00137f];
+const CR_In_Ethiopic: [u32; 2] = [0x001200, 0x00137f];
 
-static CR_In_Ethiopic_Supplement: [u32; 2] = [0x001380, 0x00139f];
+const CR_In_Ethiopic_Supplement: [u32; 2] = [0x001380, 0x00139f];
 
-static CR_In_Cherokee: [u32; 2] = [0x0013a0, 0x0013ff];
+const CR_In_Cherokee: [u32; 2] = [0x0013a0, 0x0013ff];
 
-static CR_In_Unified_Canadian_Aboriginal_Syllabics: [u32; 2] = [0x001400, 0x00167f];
+const CR_In_Unified_Canadian_Aboriginal_Syllabics: [u32; 2] = [0x001400, 0x00167f];
 
-static CR_In_Ogham: [u32; 2] = [0x001680, 0x00169f];
+const CR_In_Ogham: [u32; 2] = [0x001680, 0x00169f];
 
-static CR_In_Runic: [u32; 2] = [0x0016a0, 0x0016ff];
+const CR_In_Runic: [u32; 2] = [0x0016a0, 0x0016ff];
 
-static CR_In_Tagalog: [u32; 2] = [0x001700, 0x00171f];
+const CR_In_Tagalog: [u32; 2] = [0x001700, 0x00171f];
 
-static CR_In_Hanunoo: [u32; 2] = [0x001720, 0x00173f];
+const CR_In_Hanunoo: [u32; 2] = [0x001720, 0x00173f];
 
-static CR_In_Buhid: [u32; 2] = [0x001740, 0x00175f];
+const CR_In_Buhid: [u32; 2] = [0x001740, 0x00175f];
 
-static CR_In_Tagbanwa: [u32; 2] = [0x001760, 0x00177f];
+const CR_In_Tagbanwa: [u32; 2] = [0x001760, 0x00177f];
 
-static CR_In_Khmer: [u32; 2] = [0x001780, 0x0017ff];
+const CR_In_Khmer: [u32; 2] = [0x001780, 0x0017ff];
 
-static CR_In_Mongolian: [u32; 2] = [0x001800, 0x0018af];
+const CR_In_Mongolian: [u32; 2] = [0x001800, 0x0018af];
 
-static CR_In_Unified_Canadian_Aboriginal_Syllabics_Extended: [u32; 2] = [0x0018b0, 0x0018ff];
+const CR_In_Unified_Canadian_Aboriginal_Syllabics_Extended: [u32; 2] = [0x0018b0, 0x0018ff];
 
-static CR_In_Limbu: [u32; 2] = [0x001900, 0x00194f];
+const CR_In_Limbu: [u32; 2] = [0x001900, 0x00194f];
 
-static CR_In_Tai_Le: [u32; 2] = [0x001950, 0x00197f];
+const CR_In_Tai_Le: [u32; 2] = [0x001950, 0x00197f];
 
-static CR_In_New_Tai_Lue: [u32; 2] = [0x001980, 0x0019df];
+const CR_In_New_Tai_Lue: [u32; 2] = [0x001980, 0x0019df];
 
-static CR_In_Khmer_Symbols: [u32; 2] = [0x0019e0, 0x0019ff];
+const CR_In_Khmer_Symbols: [u32; 2] = [0x0019e0, 0x0019ff];
 
-static CR_In_Buginese: [u32; 2] = [0x001a00, 0x001a1f];
+const CR_In_Buginese: [u32; 2] = [0x001a00, 0x001a1f];
 
-static CR_In_Tai_Tham: [u32; 2] = [0x001a20, 0x001aaf];
+const CR_In_Tai_Tham: [u32; 2] = [0x001a20, 0x001aaf];
 
-static CR_In_Combining_Diacritical_Marks_Extended: [u32; 2] = [0x001ab0, 0x001aff];
+const CR_In_Combining_Diacritical_Marks_Extended: [u32; 2] = [0x001ab0, 0x001aff];
 
-static CR_In_Balinese: [u32; 2] = [0x001b00, 0x001b7f];
+const CR_In_Balinese: [u32; 2] = [0x001b00, 0x001b7f];
 
-static CR_In_Sundanese: [u32; 2] = [0x001b80, 0x001bbf];
+const CR_In_Sundanese: [u32; 2] = [0x001b80, 0x001bbf];
 
-static CR_In_Batak: [u32; 2] = [0x001bc0, 0x001bff];
+const CR_In_Batak: [u32; 2] = [0x001bc0, 0x001bff];
 
-static CR_In_Lepcha: [u32; 2] = [0x001c00, 0x001c4f];
+const CR_In_Lepcha: [u32; 2] = [0x001c00, 0x001c4f];
 
-static CR_In_Cyrillic_Extended_C: [u32; 2] = [0x001c80, 0x001c8f];
+const CR_In_Cyrillic_Extended_C: [u32; 2] = [0x001c80, 0x001c8f];
 
-static CR_In_Georgian_Extended: [u32; 2] = [0x001c90, 0x001cbf];
+const CR_In_Georgian_Extended: [u32; 2] = [0x001c90, 0x001cbf];
 
-static CR_In_Sundanese_Supplement: [u32; 2] = [0x001cc0, 0x001ccf];
+const CR_In_Sundanese_Supplement: [u32; 2] = [0x001cc0, 0x001ccf];
 
-static CR_In_Vedic_Extensions: [u32; 2] = [0x001cd0, 0x001cff];
+const CR_In_Vedic_Extensions: [u32; 2] = [0x001cd0, 0x001cff];
 
-static CR_In_Phonetic_Extensions: [u32; 2] = [0x001d00, 0x001d7f];
+const CR_In_Phonetic_Extensions: [u32; 2] = [0x001d00, 0x001d7f];
 
-static CR_In_Phonetic_Extensions_Supplement: [u32; 2] = [0x001d80, 0x001dbf];
+const CR_In_Phonetic_Extensions_Supplement: [u32; 2] = [0x001d80, 0x001dbf];
 
-static CR_In_Combining_Diacritical_Marks_Supplement: [u32; 2] = [0x001dc0, 0x001dff];
+const CR_In_Combining_Diacritical_Marks_Supplement: [u32; 2] = [0x001dc0, 0x001dff];
 
-static CR_In_Latin_Extended_Additional: [u32; 2] = [0x001e00, 0x001eff];
+const CR_In_Latin_Extended_Additional: [u32; 2] = [0x001e00, 0x001eff];
 
-static CR_In_Greek_Extended: [u32; 2] = [0x001f00, 0x001fff];
+const CR_In_Greek_Extended: [u32; 2] = [0x001f00, 0x001fff];
 
-static CR_In_General_Punctuation: [u32; 2] = [0x002000, 0x00206f];
+const CR_In_General_Punctuation: [u32; 2] = [0x002000, 0x00206f];
 
-static CR_In_Superscripts_and_Subscripts: [u32; 2] = [0x002070, 0x00209f];
+const CR_In_Superscripts_and_Subscripts: [u32; 2] = [0x002070, 0x00209f];
 
-static CR_In_Currency_Symbols: [u32; 2] = [0x0020a0, 0x0020cf];
+const CR_In_Currency_Symbols: [u32; 2] = [0x0020a0, 0x0020cf];
 
-static CR_In_Combining_Diacritical_Marks_for_Symbols: [u32; 2] = [0x0020d0, 0x0020ff];
+const CR_In_Combining_Diacritical_Marks_for_Symbols: [u32; 2] = [0x0020d0, 0x0020ff];
 
-static CR_In_Letterlike_Symbols: [u32; 2] = [0x002100, 0x00214f];
+const CR_In_Letterlike_Symbols: [u32; 2] = [0x002100, 0x00214f];
 
-static CR_In_Number_Forms: [u32; 2] = [0x002150, 0x00218f];
+const CR_In_Number_Forms: [u32; 2] = [0x002150, 0x00218f];
 
-static CR_In_Arrows: [u32; 2] = [0x002190, 0x0021ff];
+const CR_In_Arrows: [u32; 2] = [0x002190, 0x0021ff];
 
-static CR_In_Mathematical_Operators: [u32; 2] = [0x002200, 0x0022ff];
+const CR_In_Mathematical_Operators: [u32; 2] = [0x002200, 0x0022ff];
 
-static CR_In_Miscellaneous_Technical: [u32; 2] = [0x002300, 0x0023ff];
+const CR_In_Miscellaneous_Technical: [u32; 2] = [0x002300, 0x0023ff];
 
-static CR_In_Control_Pictures: [u32; 2] = [0x002400, 0x00243f];
+const CR_In_Control_Pictures: [u32; 2] = [0x002400, 0x00243f];
 
-static CR_In_Optical_Character_Recognition: [u32; 2] = [0x002440, 0x00245f];
+const CR_In_Optical_Character_Recognition: [u32; 2] = [0x002440, 0x00245f];
 
-static CR_In_Enclosed_Alphanumerics: [u32; 2] = [0x002460, 0x0024ff];
+const CR_In_Enclosed_Alphanumerics: [u32; 2] = [0x002460, 0x0024ff];
 
-static CR_In_Box_Drawing: [u32; 2] = [0x002500, 0x00257f];
+const CR_In_Box_Drawing: [u32; 2] = [0x002500, 0x00257f];
 
-static CR_In_Block_Elements: [u32; 2] = [0x002580, 0x00259f];
+const CR_In_Block_Elements: [u32; 2] = [0x002580, 0x00259f];
 
-static CR_In_Geometric_Shapes: [u32; 2] = [0x0025a0, 0x0025ff];
+const CR_In_Geometric_Shapes: [u32; 2] = [0x0025a0, 0x0025ff];
 
-static CR_In_Miscellaneous_Symbols: [u32; 2] = [0x002600, 0x0026ff];
+const CR_In_Miscellaneous_Symbols: [u32; 2] = [0x002600, 0x0026ff];
 
-static CR_In_Dingbats: [u32; 2] = [0x002700, 0x0027bf];
+const CR_In_Dingbats: [u32; 2] = [0x002700, 0x0027bf];
 
-static CR_In_Miscellaneous_Mathematical_Symbols_A: [u32; 2] = [0x0027c0, 0x0027ef];
+const CR_In_Miscellaneous_Mathematical_Symbols_A: [u32; 2] = [0x0027c0, 0x0027ef];
 
-static CR_In_Supplemental_Arrows_A: [u32; 2] = [0x0027f0, 0x0027ff];
+const CR_In_Supplemental_Arrows_A: [u32; 2] = [0x0027f0, 0x0027ff];
 
-static CR_In_Supplemental_Arrows_B: [u32; 2] = [0x002900, 0x00297f];
+const CR_In_Supplemental_Arrows_B: [u32; 2] = [0x002900, 0x00297f];
 
-static CR_In_Miscellaneous_Mathematical_Symbols_B: [u32; 2] = [0x002980, 0x0029ff];
+const CR_In_Miscellaneous_Mathematical_Symbols_B: [u32; 2] = [0x002980, 0x0029ff];
 
-static CR_In_Supplemental_Mathematical_Operators: [u32; 2] = [0x002a00, 0x002aff];
+const CR_In_Supplemental_Mathematical_Operators: [u32; 2] = [0x002a00, 0x002aff];
 
-static CR_In_Miscellaneous_Symbols_and_Arrows: [u32; 2] = [0x002b00, 0x002bff];
+const CR_In_Miscellaneous_Symbols_and_Arrows: [u32; 2] = [0x002b00, 0x002bff];
 
-static CR_In_Glagolitic: [u32; 2] = [0x002c00, 0x002c5f];
+const CR_In_Glagolitic: [u32; 2] = [0x002c00, 0x002c5f];
 
-static CR_In_Latin_Extended_C: [u32; 2] = [0x002c60, 0x002c7f];
+const CR_In_Latin_Extended_C: [u32; 2] = [0x002c60, 0x002c7f];
 
-static CR_In_Coptic: [u32; 2] = [0x002c80, 0x002cff];
+const CR_In_Coptic: [u32; 2] = [0x002c80, 0x002cff];
 
-static CR_In_Georgian_Supplement: [u32; 2] = [0x002d00, 0x002d2f];
+const CR_In_Georgian_Supplement: [u32; 2] = [0x002d00, 0x002d2f];
 
-static CR_In_Tifinagh: [u32; 2] = [0x002d30, 0x002d7f];
+const CR_In_Tifinagh: [u32; 2] = [0x002d30, 0x002d7f];
 
-static CR_In_Ethiopic_Extended: [u32; 2] = [0x002d80, 0x002ddf];
+const CR_In_Ethiopic_Extended: [u32; 2] = [0x002d80, 0x002ddf];
 
-static CR_In_Cyrillic_Extended_A: [u32; 2] = [0x002de0, 0x002dff];
+const CR_In_Cyrillic_Extended_A: [u32; 2] = [0x002de0, 0x002dff];
 
-static CR_In_Supplemental_Punctuation: [u32; 2] = [0x002e00, 0x002e7f];
+const CR_In_Supplemental_Punctuation: [u32; 2] = [0x002e00, 0x002e7f];
 
-static CR_In_CJK_Radicals_Supplement: [u32; 2] = [0x002e80, 0x002eff];
+const CR_In_CJK_Radicals_Supplement: [u32; 2] = [0x002e80, 0x002eff];
 
-static CR_In_Kangxi_Radicals: [u32; 2] = [0x002f00, 0x002fdf];
+const CR_In_Kangxi_Radicals: [u32; 2] = [0x002f00, 0x002fdf];
 
-static CR_In_Ideographic_Description_Characters: [u32; 2] = [0x002ff0, 0x002fff];
+const CR_In_Ideographic_Description_Characters: [u32; 2] = [0x002ff0, 0x002fff];
 
-static CR_In_CJK_Symbols_and_Punctuation: [u32; 2] = [0x003000, 0x00303f];
+const CR_In_CJK_Symbols_and_Punctuation: [u32; 2] = [0x003000, 0x00303f];
 
-static CR_In_Hiragana: [u32; 2] = [0x003040, 0x00309f];
+const CR_In_Hiragana: [u32; 2] = [0x003040, 0x00309f];
 
-static CR_In_Katakana: [u32; 2] = [0x0030a0, 0x0030ff];
+const CR_In_Katakana: [u32; 2] = [0x0030a0, 0x0030ff];
 
-static CR_In_Bopomofo: [u32; 2] = [0x003100, 0x00312f];
+const CR_In_Bopomofo: [u32; 2] = [0x003100, 0x00312f];
 
-static CR_In_Hangul_Compatibility_Jamo: [u32; 2] = [0x003130, 0x00318f];
+const CR_In_Hangul_Compatibility_Jamo: [u32; 2] = [0x003130, 0x00318f];
 
-static CR_In_Kanbun: [u32; 2] = [0x003190, 0x00319f];
+const CR_In_Kanbun: [u32; 2] = [0x003190, 0x00319f];
 
-static CR_In_Bopomofo_Extended: [u32; 2] = [0x0031a0, 0x0031bf];
+const CR_In_Bopomofo_Extended: [u32; 2] = [0x0031a0, 0x0031bf];
 
-static CR_In_CJK_Strokes: [u32; 2] = [0x0031c0, 0x0031ef];
+const CR_In_CJK_Strokes: [u32; 2] = [0x0031c0, 0x0031ef];
 
-static CR_In_Katakana_Phonetic_Extensions: [u32; 2] = [0x0031f0, 0x0031ff];
+const CR_In_Katakana_Phonetic_Extensions: [u32; 2] = [0x0031f0, 0x0031ff];
 
-static CR_In_Enclosed_CJK_Letters_and_Months: [u32; 2] = [0x003200, 0x0032ff];
+const CR_In_Enclosed_CJK_Letters_and_Months: [u32; 2] = [0x003200, 0x0032ff];
 
-static CR_In_CJK_Compatibility: [u32; 2] = [0x003300, 0x0033ff];
+const CR_In_CJK_Compatibility: [u32; 2] = [0x003300, 0x0033ff];
 
-static CR_In_CJK_Unified_Ideographs_Extension_A: [u32; 2] = [0x003400, 0x004dbf];
+const CR_In_CJK_Unified_Ideographs_Extension_A: [u32; 2] = [0x003400, 0x004dbf];
 
-static CR_In_Yijing_Hexagram_Symbols: [u32; 2] = [0x004dc0, 0x004dff];
+const CR_In_Yijing_Hexagram_Symbols: [u32; 2] = [0x004dc0, 0x004dff];
 
-static CR_In_CJK_Unified_Ideographs: [u32; 2] = [0x004e00, 0x009fff];
+const CR_In_CJK_Unified_Ideographs: [u32; 2] = [0x004e00, 0x009fff];
 
-static CR_In_Yi_Syllables: [u32; 2] = [0x00a000, 0x00a48f];
+const CR_In_Yi_Syllables: [u32; 2] = [0x00a000, 0x00a48f];
 
-static CR_In_Yi_Radicals: [u32; 2] = [0x00a490, 0x00a4cf];
+const CR_In_Yi_Radicals: [u32; 2] = [0x00a490, 0x00a4cf];
 
-static CR_In_Lisu: [u32; 2] = [0x00a4d0, 0x00a4ff];
+const CR_In_Lisu: [u32; 2] = [0x00a4d0, 0x00a4ff];
 
-static CR_In_Vai: [u32; 2] = [0x00a500, 0x00a63f];
+const CR_In_Vai: [u32; 2] = [0x00a500, 0x00a63f];
 
-static CR_In_Cyrillic_Extended_B: [u32; 2] = [0x00a640, 0x00a69f];
+const CR_In_Cyrillic_Extended_B: [u32; 2] = [0x00a640, 0x00a69f];
 
-static CR_In_Bamum: [u32; 2] = [0x00a6a0, 0x00a6ff];
+const CR_In_Bamum: [u32; 2] = [0x00a6a0, 0x00a6ff];
 
-static CR_In_Modifier_Tone_Letters: [u32; 2] = [0x00a700, 0x00a71f];
+const CR_In_Modifier_Tone_Letters: [u32; 2] = [0x00a700, 0x00a71f];
 
-static CR_In_Latin_Extended_D: [u32; 2] = [0x00a720, 0x00a7ff];
+const CR_In_Latin_Extended_D: [u32; 2] = [0x00a720, 0x00a7ff];
 
-static CR_In_Syloti_Nagri: [u32; 2] = [0x00a800, 0x00a82f];
+const CR_In_Syloti_Nagri: [u32; 2] = [0x00a800, 0x00a82f];
 
-static CR_In_Common_Indic_Number_Forms: [u32; 2] = [0x00a830, 0x00a83f];
+const CR_In_Common_Indic_Number_Forms: [u32; 2] = [0x00a830, 0x00a83f];
 
-static CR_In_Phags_pa: [u32; 2] = [0x00a840, 0x00a87f];
+const CR_In_Phags_pa: [u32; 2] = [0x00a840, 0x00a87f];
 
-static CR_In_Saurashtra: [u32; 2] = [0x00a880, 0x00a8df];
+const CR_In_Saurashtra: [u32; 2] = [0x00a880, 0x00a8df];
 
-static CR_In_Devanagari_Extended: [u32; 2] = [0x00a8e0, 0x00a8ff];
+const CR_In_Devanagari_Extended: [u32; 2] = [0x00a8e0, 0x00a8ff];
 
-static CR_In_Kayah_Li: [u32; 2] = [0x00a900, 0x00a92f];
+const CR_In_Kayah_Li: [u32; 2] = [0x00a900, 0x00a92f];
 
-static CR_In_Rejang: [u32; 2] = [0x00a930, 0x00a95f];
+const CR_In_Rejang: [u32; 2] = [0x00a930, 0x00a95f];
 
-static CR_In_Hangul_Jamo_Extended_A: [u32; 2] = [0x00a960, 0x00a97f];
+const CR_In_Hangul_Jamo_Extended_A: [u32; 2] = [0x00a960, 0x00a97f];
 
-static CR_In_Javanese: [u32; 2] = [0x00a980, 0x00a9df];
+const CR_In_Javanese: [u32; 2] = [0x00a980, 0x00a9df];
 
-static CR_In_Myanmar_Extended_B: [u32; 2] = [0x00a9e0, 0x00a9ff];
+const CR_In_Myanmar_Extended_B: [u32; 2] = [0x00a9e0, 0x00a9ff];
 
-static CR_In_Cham: [u32; 2] = [0x00aa00, 0x00aa5f];
+const CR_In_Cham: [u32; 2] = [0x00aa00, 0x00aa5f];
 
-static CR_In_Myanmar_Extended_A: [u32; 2] = [0x00aa60, 0x00aa7f];
+const CR_In_Myanmar_Extended_A: [u32; 2] = [0x00aa60, 0x00aa7f];
 
-static CR_In_Tai_Viet: [u32; 2] = [0x00aa80, 0x00aadf];
+const CR_In_Tai_Viet: [u32; 2] = [0x00aa80, 0x00aadf];
 
-static CR_In_Meetei_Mayek_Extensions: [u32; 2] = [0x00aae0, 0x00aaff];
+const CR_In_Meetei_Mayek_Extensions: [u32; 2] = [0x00aae0, 0x00aaff];
 
-static CR_In_Ethiopic_Extended_A: [u32; 2] = [0x00ab00, 0x00ab2f];
+const CR_In_Ethiopic_Extended_A: [u32; 2] = [0x00ab00, 0x00ab2f];
 
-static CR_In_Latin_Extended_E: [u32; 2] = [0x00ab30, 0x00ab6f];
+const CR_In_Latin_Extended_E: [u32; 2] = [0x00ab30, 0x00ab6f];
 
-static CR_In_Cherokee_Supplement: [u32; 2] = [0x00ab70, 0x00abbf];
+const CR_In_Cherokee_Supplement: [u32; 2] = [0x00ab70, 0x00abbf];
 
-static CR_In_Meetei_Mayek: [u32; 2] = [0x00abc0, 0x00abff];
+const CR_In_Meetei_Mayek: [u32; 2] = [0x00abc0, 0x00abff];
 
-static CR_In_Hangul_Syllables: [u32; 2] = [0x00ac00, 0x00d7af];
+const CR_In_Hangul_Syllables: [u32; 2] = [0x00ac00, 0x00d7af];
 
-static CR_In_Hangul_Jamo_Extended_B: [u32; 2] = [0x00d7b0, 0x00d7ff];
+const CR_In_Hangul_Jamo_Extended_B: [u32; 2] = [0x00d7b0, 0x00d7ff];
 
-static CR_In_High_Surrogates: [u32; 2] = [0x00d800, 0x00db7f];
+const CR_In_High_Surrogates: [u32; 2] = [0x00d800, 0x00db7f];
 
-static CR_In_High_Private_Use_Surrogates: [u32; 2] = [0x00db80, 0x00dbff];
+const CR_In_High_Private_Use_Surrogates: [u32; 2] = [0x00db80, 0x00dbff];
 
-static CR_In_Low_Surrogates: [u32; 2] = [0x00dc00, 0x00dfff];
+const CR_In_Low_Surrogates: [u32; 2] = [0x00dc00, 0x00dfff];
 
-static CR_In_Private_Use_Area: [u32; 2] = [0x00e000, 0x00f8ff];
+const CR_In_Private_Use_Area: [u32; 2] = [0x00e000, 0x00f8ff];
 
-static CR_In_CJK_Compatibility_Ideographs: [u32; 2] = [0x00f900, 0x00faff];
+const CR_In_CJK_Compatibility_Ideographs: [u32; 2] = [0x00f900, 0x00faff];
 
-static CR_In_Alphabetic_Presentation_Forms: [u32; 2] = [0x00fb00, 0x00fb4f];
+const CR_In_Alphabetic_Presentation_Forms: [u32; 2] = [0x00fb00, 0x00fb4f];
 
-static CR_In_Arabic_Presentation_Forms_A: [u32; 2] = [0x00fb50, 0x00fdff];
+const CR_In_Arabic_Presentation_Forms_A: [u32; 2] = [0x00fb50, 0x00fdff];
 
-static CR_In_Variation_Selectors: [u32; 2] = [0x00fe00, 0x00fe0f];
+const CR_In_Variation_Selectors: [u32; 2] = [0x00fe00, 0x00fe0f];
 
-static CR_In_Vertical_Forms: [u32; 2] = [0x00fe10, 0x00fe1f];
+const CR_In_Vertical_Forms: [u32; 2] = [0x00fe10, 0x00fe1f];
 
-static CR_In_Combining_Half_Marks: [u32; 2] = [0x00fe20, 0x00fe2f];
+const CR_In_Combining_Half_Marks: [u32; 2] = [0x00fe20, 0x00fe2f];
 
-static CR_In_CJK_Compatibility_Forms: [u32; 2] = [0x00fe30, 0x00fe4f];
+const CR_In_CJK_Compatibility_Forms: [u32; 2] = [0x00fe30, 0x00fe4f];
 
-static CR_In_Small_Form_Variants: [u32; 2] = [0x00fe50, 0x00fe6f];
+const CR_In_Small_Form_Variants: [u32; 2] = [0x00fe50, 0x00fe6f];
 
-static CR_In_Arabic_Presentation_Forms_B: [u32; 2] = [0x00fe70, 0x00feff];
+const CR_In_Arabic_Presentation_Forms_B: [u32; 2] = [0x00fe70, 0x00feff];
 
-static CR_In_Halfwidth_and_Fullwidth_Forms: [u32; 2] = [0x00ff00, 0x00ffef];
+const CR_In_Halfwidth_and_Fullwidth_Forms: [u32; 2] = [0x00ff00, 0x00ffef];
 
-static CR_In_Specials: [u32; 2] = [0x00fff0, 0x00ffff];
+const CR_In_Specials: [u32; 2] = [0x00fff0, 0x00ffff];
 
-static CR_In_Linear_B_Syllabary: [u32; 2] = [0x010000, 0x01007f];
+const CR_In_Linear_B_Syllabary: [u32; 2] = [0x010000, 0x01007f];
 
-static CR_In_Linear_B_Ideograms: [u32; 2] = [0x010080, 0x0100ff];
+const CR_In_Linear_B_Ideograms: [u32; 2] = [0x010080, 0x0100ff];
 
-static CR_In_Aegean_Numbers: [u32; 2] = [0x010100, 0x01013f];
+const CR_In_Aegean_Numbers: [u32; 2] = [0x010100, 0x01013f];
 
-static CR_In_Ancient_Greek_Numbers: [u32; 2] = [0x010140, 0x01018f];
+const CR_In_Ancient_Greek_Numbers: [u32; 2] = [0x010140, 0x01018f];
 
-static CR_In_Ancient_Symbols: [u32; 2] = [0x010190, 0x0101cf];
+const CR_In_Ancient_Symbols: [u32; 2] = [0x010190, 0x0101cf];
 
-static CR_In_Phaistos_Disc: [u32; 2] = [0x0101d0, 0x0101ff];
+const CR_In_Phaistos_Disc: [u32; 2] = [0x0101d0, 0x0101ff];
 
-static CR_In_Lycian: [u32; 2] = [0x010280, 0x01029f];
+const CR_In_Lycian: [u32; 2] = [0x010280, 0x01029f];
 
-static CR_In_Carian: [u32; 2] = [0x0102a0, 0x0102df];
+const CR_In_Carian: [u32; 2] = [0x0102a0, 0x0102df];
 
-static CR_In_Coptic_Epact_Numbers: [u32; 2] = [0x0102e0, 0x0102ff];
+const CR_In_Coptic_Epact_Numbers: [u32; 2] = [0x0102e0, 0x0102ff];
 
-static CR_In_Old_Italic: [u32; 2] = [0x010300, 0x01032f];
+const CR_In_Old_Italic: [u32; 2] = [0x010300, 0x01032f];
 
-static CR_In_Gothic: [u32; 2] = [0x010330, 0x01034f];
+const CR_In_Gothic: [u32; 2] = [0x010330, 0x01034f];
 
-static CR_In_Old_Permic: [u32; 2] = [0x010350, 0x01037f];
+const CR_In_Old_Permic: [u32; 2] = [0x010350, 0x01037f];
 
-static CR_In_Ugaritic: [u32; 2] = [0x010380, 0x01039f];
+const CR_In_Ugaritic: [u32; 2] = [0x010380, 0x01039f];
 
-static CR_In_Old_Persian: [u32; 2] = [0x0103a0, 0x0103df];
+const CR_In_Old_Persian: [u32; 2] = [0x0103a0, 0x0103df];
 
-static CR_In_Osmanya: [u32; 2] = [0x010480, 0x0104af];
+const CR_In_Osmanya: [u32; 2] = [0x010480, 0x0104af];
 
-static CR_In_Osage: [u32; 2] = [0x0104b0, 0x0104ff];
+const CR_In_Osage: [u32; 2] = [0x0104b0, 0x0104ff];
 
-static CR_In_Elbasan: [u32; 2] = [0x010500, 0x01052f];
+const CR_In_Elbasan: [u32; 2] = [0x010500, 0x01052f];
 
-static CR_In_Caucasian_Albanian: [u32; 2] = [0x010530, 0x01056f];
+const CR_In_Caucasian_Albanian: [u32; 2] = [0x010530, 0x01056f];
 
-static CR_In_Vithkuqi: [u32; 2] = [0x010570, 0x0105bf];
+const CR_In_Vithkuqi: [u32; 2] = [0x010570, 0x0105bf];
 
-static CR_In_Todhri: [u32; 2] = [0x0105c0, 0x0105ff];
+const CR_In_Todhri: [u32; 2] = [0x0105c0, 0x0105ff];
 
-static CR_In_Linear_A: [u32; 2] = [0x010600, 0x01077f];
+const CR_In_Linear_A: [u32; 2] = [0x010600, 0x01077f];
 
-static CR_In_Latin_Extended_F: [u32; 2] = [0x010780, 0x0107bf];
+const CR_In_Latin_Extended_F: [u32; 2] = [0x010780, 0x0107bf];
 
-static CR_In_Cypriot_Syllabary: [u32; 2] = [0x010800, 0x01083f];
+const CR_In_Cypriot_Syllabary: [u32; 2] = [0x010800, 0x01083f];
 
-static CR_In_Imperial_Aramaic: [u32; 2] = [0x010840, 0x01085f];
+const CR_In_Imperial_Aramaic: [u32; 2] = [0x010840, 0x01085f];
 
-static CR_In_Nabataean: [u32; 2] = [0x010880, 0x0108af];
+const CR_In_Nabataean: [u32; 2] = [0x010880, 0x0108af];
 
-static CR_In_Hatran: [u32; 2] = [0x0108e0, 0x0108ff];
+const CR_In_Hatran: [u32; 2] = [0x0108e0, 0x0108ff];
 
-static CR_In_Phoenician: [u32; 2] = [0x010900, 0x01091f];
+const CR_In_Phoenician: [u32; 2] = [0x010900, 0x01091f];
 
-static CR_In_Lydian: [u32; 2] = [0x010920, 0x01093f];
+const CR_In_Lydian: [u32; 2] = [0x010920, 0x01093f];
 
-static CR_In_Meroitic_Cursive: [u32; 2] = [0x0109a0, 0x0109ff];
+const CR_In_Meroitic_Cursive: [u32; 2] = [0x0109a0, 0x0109ff];
 
-static CR_In_Kharoshthi: [u32; 2] = [0x010a00, 0x010a5f];
+const CR_In_Kharoshthi: [u32; 2] = [0x010a00, 0x010a5f];
 
-static CR_In_Manichaean: [u32; 2] = [0x010ac0, 0x010aff];
+const CR_In_Manichaean: [u32; 2] = [0x010ac0, 0x010aff];
 
-static CR_In_Avestan: [u32; 2] = [0x010b00, 0x010b3f];
+const CR_In_Avestan: [u32; 2] = [0x010b00, 0x010b3f];
 
-static CR_In_Inscriptional_Parthian: [u32; 2] = [0x010b40, 0x010b5f];
+const CR_In_Inscriptional_Parthian: [u32; 2] = [0x010b40, 0x010b5f];
 
-static CR_In_Inscriptional_Pahlavi: [u32; 2] = [0x010b60, 0x010b7f];
+const CR_In_Inscriptional_Pahlavi: [u32; 2] = [0x010b60, 0x010b7f];
 
-static CR_In_Psalter_Pahlavi: [u32; 2] = [0x010b80, 0x010baf];
+const CR_In_Psalter_Pahlavi: [u32; 2] = [0x010b80, 0x010baf];
 
-static CR_In_Old_Turkic: [u32; 2] = [0x010c00, 0x010c4f];
+const CR_In_Old_Turkic: [u32; 2] = [0x010c00, 0x010c4f];
 
-static CR_In_Old_Hungarian: [u32; 2] = [0x010c80, 0x010cff];
+const CR_In_Old_Hungarian: [u32; 2] = [0x010c80, 0x010cff];
 
-static CR_In_Hanifi_Rohingya: [u32; 2] = [0x010d00, 0x010d3f];
+const CR_In_Hanifi_Rohingya: [u32; 2] = [0x010d00, 0x010d3f];
 
-static CR_In_Garay: [u32; 2] = [0x010d40, 0x010d8f];
+const CR_In_Garay: [u32; 2] = [0x010d40, 0x010d8f];
 
-static CR_In_Rumi_Numeral_Symbols: [u32; 2] = [0x010e60, 0x010e7f];
+const CR_In_Rumi_Numeral_Symbols: [u32; 2] = [0x010e60, 0x010e7f];
 
-static CR_In_Yezidi: [u32; 2] = [0x010e80, 0x010ebf];
+const CR_In_Yezidi: [u32; 2] = [0x010e80, 0x010ebf];
 
-static CR_In_Arabic_Extended_C: [u32; 2] = [0x010ec0, 0x010eff];
+const CR_In_Arabic_Extended_C: [u32; 2] = [0x010ec0, 0x010eff];
 
-static CR_In_Old_Sogdian: [u32; 2] = [0x010f00, 0x010f2f];
+const CR_In_Old_Sogdian: [u32; 2] = [0x010f00, 0x010f2f];
 
-static CR_In_Sogdian: [u32; 2] = [0x010f30, 0x010f6f];
+const CR_In_Sogdian: [u32; 2] = [0x010f30, 0x010f6f];
 
-static CR_In_Old_Uyghur: [u32; 2] = [0x010f70, 0x010faf];
+const CR_In_Old_Uyghur: [u32; 2] = [0x010f70, 0x010faf];
 
-static CR_In_Chorasmian: [u32; 2] = [0x010fb0, 0x010fdf];
+const CR_In_Chorasmian: [u32; 2] = [0x010fb0, 0x010fdf];
 
-static CR_In_Elymaic: [u32; 2] = [0x010fe0, 0x010fff];
+const CR_In_Elymaic: [u32; 2] = [0x010fe0, 0x010fff];
 
-static CR_In_Brahmi: [u32; 2] = [0x011000, 0x01107f];
+const CR_In_Brahmi: [u32; 2] = [0x011000, 0x01107f];
 
-static CR_In_Kaithi: [u32; 2] = [0x011080, 0x0110cf];
+const CR_In_Kaithi: [u32; 2] = [0x011080, 0x0110cf];
 
-static CR_In_Sora_Sompeng: [u32; 2] = [0x0110d0, 0x0110ff];
+const CR_In_Sora_Sompeng: [u32; 2] = [0x0110d0, 0x0110ff];
 
-static CR_In_Chakma: [u32; 2] = [0x011100, 0x01114f];
+const CR_In_Chakma: [u32; 2] = [0x011100, 0x01114f];
 
-static CR_In_Mahajani: [u32; 2] = [0x011150, 0x01117f];
+const CR_In_Mahajani: [u32; 2] = [0x011150, 0x01117f];
 
-static CR_In_Sinhala_Archaic_Numbers: [u32; 2] = [0x0111e0, 0x0111ff];
+const CR_In_Sinhala_Archaic_Numbers: [u32; 2] = [0x0111e0, 0x0111ff];
 
-static CR_In_Khojki: [u32; 2] = [0x011200, 0x01124f];
+const CR_In_Khojki: [u32; 2] = [0x011200, 0x01124f];
 
-static CR_In_Multani: [u32; 2] = [0x011280, 0x0112af];
+const CR_In_Multani: [u32; 2] = [0x011280, 0x0112af];
 
-static CR_In_Khudawadi: [u32; 2] = [0x0112b0, 0x0112ff];
+const CR_In_Khudawadi: [u32; 2] = [0x0112b0, 0x0112ff];
 
-static CR_In_Grantha: [u32; 2] = [0x011300, 0x01137f];
+const CR_In_Grantha: [u32; 2] = [0x011300, 0x01137f];
 
-static CR_In_Tulu_Tigalari: [u32; 2] = [0x011380, 0x0113ff];
+const CR_In_Tulu_Tigalari: [u32; 2] = [0x011380, 0x0113ff];
 
-static CR_In_Newa: [u32; 2] = [0x011400, 0x01147f];
+const CR_In_Newa: [u32; 2] = [0x011400, 0x01147f];
 
-static CR_In_Tirhuta: [u32; 2] = [0x011480, 0x0114df];
+const CR_In_Tirhuta: [u32; 2] = [0x011480, 0x0114df];
 
-static CR_In_Siddham: [u32; 2] = [0x011580, 0x0115ff];
+const CR_In_Siddham: [u32; 2] = [0x011580, 0x0115ff];
 
-static CR_In_Modi: [u32; 2] = [0x011600, 0x01165f];
+const CR_In_Modi: [u32; 2] = [0x011600, 0x01165f];
 
-static CR_In_Mongolian_Supplement: [u32; 2] = [0x011660, 0x01167f];
+const CR_In_Mongolian_Supplement: [u32; 2] = [0x011660, 0x01167f];
 
-static CR_In_Takri: [u32; 2] = [0x011680, 0x0116cf];
+const CR_In_Takri: [u32; 2] = [0x011680, 0x0116cf];
 
-static CR_In_Myanmar_Extended_C: [u32; 2] = [0x0116d0, 0x0116ff];
+const CR_In_Myanmar_Extended_C: [u32; 2] = [0x0116d0, 0x0116ff];
 
-static CR_In_Ahom: [u32; 2] = [0x011700, 0x01174f];
+const CR_In_Ahom: [u32; 2] = [0x011700, 0x01174f];
 
-static CR_In_Dogra: [u32; 2] = [0x011800, 0x01184f];
+const CR_In_Dogra: [u32; 2] = [0x011800, 0x01184f];
 
-static CR_In_Warang_Citi: [u32; 2] = [0x0118a0, 0x0118ff];
+const CR_In_Warang_Citi: [u32; 2] = [0x0118a0, 0x0118ff];
 
-static CR_In_Dives_Akuru: [u32; 2] = [0x011900, 0x01195f];
+const CR_In_Dives_Akuru: [u32; 2] = [0x011900, 0x01195f];
 
-static CR_In_Nandinagari: [u32; 2] = [0x0119a0, 0x0119ff];
+const CR_In_Nandinagari: [u32; 2] = [0x0119a0, 0x0119ff];
 
-static CR_In_Zanabazar_Square: [u32; 2] = [0x011a00, 0x011a4f];
+const CR_In_Zanabazar_Square: [u32; 2] = [0x011a00, 0x011a4f];
 
-static CR_In_Soyombo: [u32; 2] = [0x011a50, 0x011aaf];
+const CR_In_Soyombo: [u32; 2] = [0x011a50, 0x011aaf];
 
-static CR_In_Unified_Canadian_Aboriginal_Syllabics_Extended_A: [u32; 2] = [0x011ab0, 0x011abf];
+const CR_In_Unified_Canadian_Aboriginal_Syllabics_Extended_A: [u32; 2] = [0x011ab0, 0x011abf];
 
-static CR_In_Pau_Cin_Hau: [u32; 2] = [0x011ac0, 0x011aff];
+const CR_In_Pau_Cin_Hau: [u32; 2] = [0x011ac0, 0x011aff];
 
-static CR_In_Devanagari_Extended_A: [u32; 2] = [0x011b00, 0x011b5f];
+const CR_In_Devanagari_Extended_A: [u32; 2] = [0x011b00, 0x011b5f];
 
-static CR_In_Sunuwar: [u32; 2] = [0x011bc0, 0x011bff];
+const CR_In_Sunuwar: [u32; 2] = [0x011bc0, 0x011bff];
 
-static CR_In_Bhaiksuki: [u32; 2] = [0x011c00, 0x011c6f];
+const CR_In_Bhaiksuki: [u32; 2] = [0x011c00, 0x011c6f];
 
-static CR_In_Marchen: [u32; 2] = [0x011c70, 0x011cbf];
+const CR_In_Marchen: [u32; 2] = [0x011c70, 0x011cbf];
 
-static CR_In_Masaram_Gondi: [u32; 2] = [0x011d00, 0x011d5f];
+const CR_In_Masaram_Gondi: [u32; 2] = [0x011d00, 0x011d5f];
 
-static CR_In_Gunjala_Gondi: [u32; 2] = [0x011d60, 0x011daf];
+const CR_In_Gunjala_Gondi: [u32; 2] = [0x011d60, 0x011daf];
 
-static CR_In_Makasar: [u32; 2] = [0x011ee0, 0x011eff];
+const CR_In_Makasar: [u32; 2] = [0x011ee0, 0x011eff];
 
-static CR_In_Kawi: [u32; 2] = [0x011f00, 0x011f5f];
+const CR_In_Kawi: [u32; 2] = [0x011f00, 0x011f5f];
 
-static CR_In_Lisu_Supplement: [u32; 2] = [0x011fb0, 0x011fbf];
+const CR_In_Lisu_Supplement: [u32; 2] = [0x011fb0, 0x011fbf];
 
-static CR_In_Tamil_Supplement: [u32; 2] = [0x011fc0, 0x011fff];
+const CR_In_Tamil_Supplement: [u32; 2] = [0x011fc0, 0x011fff];
 
-static CR_In_Cuneiform: [u32; 2] = [0x012000, 0x0123ff];
+const CR_In_Cuneiform: [u32; 2] = [0x012000, 0x0123ff];
 
-static CR_In_Cuneiform_Numbers_and_Punctuation: [u32; 2] = [0x012400, 0x01247f];
+const CR_In_Cuneiform_Numbers_and_Punctuation: [u32; 2] = [0x012400, 0x01247f];
 
-static CR_In_Early_Dynastic_Cuneiform: [u32; 2] = [0x012480, 0x01254f];
+const CR_In_Early_Dynastic_Cuneiform: [u32; 2] = [0x012480, 0x01254f];
 
-static CR_In_Cypro_Minoan: [u32; 2] = [0x012f90, 0x012fff];
+const CR_In_Cypro_Minoan: [u32; 2] = [0x012f90, 0x012fff];
 
-static CR_In_Egyptian_Hieroglyphs: [u32; 2] = [0x013000, 0x01342f];
+const CR_In_Egyptian_Hieroglyphs: [u32; 2] = [0x013000, 0x01342f];
 
-static CR_In_Egyptian_Hieroglyph_Format_Controls: [u32; 2] = [0x013430, 0x01345f];
+const CR_In_Egyptian_Hieroglyph_Format_Controls: [u32; 2] = [0x013430, 0x01345f];
 
-static CR_In_Egyptian_Hieroglyphs_Extended_A: [u32; 2] = [0x013460, 0x0143ff];
+const CR_In_Egyptian_Hieroglyphs_Extended_A: [u32; 2] = [0x013460, 0x0143ff];
 
-static CR_In_Anatolian_Hieroglyphs: [u32; 2] = [0x014400, 0x01467f];
+const CR_In_Anatolian_Hieroglyphs: [u32; 2] = [0x014400, 0x01467f];
 
-static CR_In_Gurung_Khema: [u32; 2] = [0x016100, 0x01613f];
+const CR_In_Gurung_Khema: [u32; 2] = [0x016100, 0x01613f];
 
-static CR_In_Bamum_Supplement: [u32; 2] = [0x016800, 0x016a3f];
+const CR_In_Bamum_Supplement: [u32; 2] = [0x016800, 0x016a3f];
 
-static CR_In_Mro: [u32; 2] = [0x016a40, 0x016a6f];
+const CR_In_Mro: [u32; 2] = [0x016a40, 0x016a6f];
 
-static CR_In_Tangsa: [u32; 2] = [0x016a70, 0x016acf];
+const CR_In_Tangsa: [u32; 2] = [0x016a70, 0x016acf];
 
-static CR_In_Bassa_Vah: [u32; 2] = [0x016ad0, 0x016aff];
+const CR_In_Bassa_Vah: [u32; 2] = [0x016ad0, 0x016aff];
 
-static CR_In_Pahawh_Hmong: [u32; 2] = [0x016b00, 0x016b8f];
+const CR_In_Pahawh_Hmong: [u32; 2] = [0x016b00, 0x016b8f];
 
-static CR_In_Kirat_Rai: [u32; 2] = [0x016d40, 0x016d7f];
+const CR_In_Kirat_Rai: [u32; 2] = [0x016d40, 0x016d7f];
 
-static CR_In_Medefaidrin: [u32; 2] = [0x016e40, 0x016e9f];
+const CR_In_Medefaidrin: [u32; 2] = [0x016e40, 0x016e9f];
 
-static CR_In_Miao: [u32; 2] = [0x016f00, 0x016f9f];
+const CR_In_Miao: [u32; 2] = [0x016f00, 0x016f9f];
 
-static CR_In_Ideographic_Symbols_and_Punctuation: [u32; 2] = [0x016fe0, 0x016fff];
+const CR_In_Ideographic_Symbols_and_Punctuation: [u32; 2] = [0x016fe0, 0x016fff];
 
-static CR_In_Tangut: [u32; 2] = [0x017000, 0x0187ff];
+const CR_In_Tangut: [u32; 2] = [0x017000, 0x0187ff];
 
-static CR_In_Tangut_Components: [u32; 2] = [0x018800, 0x018aff];
+const CR_In_Tangut_Components: [u32; 2] = [0x018800, 0x018aff];
 
-static CR_In_Khitan_Small_Script: [u32; 2] = [0x018b00, 0x018cff];
+const CR_In_Khitan_Small_Script: [u32; 2] = [0x018b00, 0x018cff];
 
-static CR_In_Tangut_Supplement: [u32; 2] = [0x018d00, 0x018d7f];
+const CR_In_Tangut_Supplement: [u32; 2] = [0x018d00, 0x018d7f];
 
-static CR_In_Kana_Extended_B: [u32; 2] = [0x01aff0, 0x01afff];
+const CR_In_Kana_Extended_B: [u32; 2] = [0x01aff0, 0x01afff];
 
-static CR_In_Kana_Supplement: [u32; 2] = [0x01b000, 0x01b0ff];
+const CR_In_Kana_Supplement: [u32; 2] = [0x01b000, 0x01b0ff];
 
-static CR_In_Kana_Extended_A: [u32; 2] = [0x01b100, 0x01b12f];
+const CR_In_Kana_Extended_A: [u32; 2] = [0x01b100, 0x01b12f];
 
-static CR_In_Small_Kana_Extension: [u32; 2] = [0x01b130, 0x01b16f];
+const CR_In_Small_Kana_Extension: [u32; 2] = [0x01b130, 0x01b16f];
 
-static CR_In_Nushu: [u32; 2] = [0x01b170, 0x01b2ff];
+const CR_In_Nushu: [u32; 2] = [0x01b170, 0x01b2ff];
 
-static CR_In_Duployan: [u32; 2] = [0x01bc00, 0x01bc9f];
+const CR_In_Duployan: [u32; 2] = [0x01bc00, 0x01bc9f];
 
-static CR_In_Shorthand_Format_Controls: [u32; 2] = [0x01bca0, 0x01bcaf];
+const CR_In_Shorthand_Format_Controls: [u32; 2] = [0x01bca0, 0x01bcaf];
 
-static CR_In_Symbols_for_Legacy_Computing_Supplement: [u32; 2] = [0x01cc00, 0x01cebf];
+const CR_In_Symbols_for_Legacy_Computing_Supplement: [u32; 2] = [0x01cc00, 0x01cebf];
 
-static CR_In_Znamenny_Musical_Notation: [u32; 2] = [0x01cf00, 0x01cfcf];
+const CR_In_Znamenny_Musical_Notation: [u32; 2] = [0x01cf00, 0x01cfcf];
 
-static CR_In_Byzantine_Musical_Symbols: [u32; 2] = [0x01d000, 0x01d0ff];
+const CR_In_Byzantine_Musical_Symbols: [u32; 2] = [0x01d000, 0x01d0ff];
 
-static CR_In_Musical_Symbols: [u32; 2] = [0x01d100, 0x01d1ff];
+const CR_In_Musical_Symbols: [u32; 2] = [0x01d100, 0x01d1ff];
 
-static CR_In_Ancient_Greek_Musical_Notation: [u32; 2] = [0x01d200, 0x01d24f];
+const CR_In_Ancient_Greek_Musical_Notation: [u32; 2] = [0x01d200, 0x01d24f];
 
-static CR_In_Kaktovik_Numerals: [u32; 2] = [0x01d2c0, 0x01d2df];
+const CR_In_Kaktovik_Numerals: [u32; 2] = [0x01d2c0, 0x01d2df];
 
-static CR_In_Mayan_Numerals: [u32; 2] = [0x01d2e0, 0x01d2ff];
+const CR_In_Mayan_Numerals: [u32; 2] = [0x01d2e0, 0x01d2ff];
 
-static CR_In_Tai_Xuan_Jing_Symbols: [u32; 2] = [0x01d300, 0x01d35f];
+const CR_In_Tai_Xuan_Jing_Symbols: [u32; 2] = [0x01d300, 0x01d35f];
 
-static CR_In_Counting_Rod_Numerals: [u32; 2] = [0x01d360, 0x01d37f];
+const CR_In_Counting_Rod_Numerals: [u32; 2] = [0x01d360, 0x01d37f];
 
-static CR_In_Mathematical_Alphanumeric_Symbols: [u32; 2] = [0x01d400, 0x01d7ff];
+const CR_In_Mathematical_Alphanumeric_Symbols: [u32; 2] = [0x01d400, 0x01d7ff];
 
-static CR_In_Sutton_SignWriting: [u32; 2] = [0x01d800, 0x01daaf];
+const CR_In_Sutton_SignWriting: [u32; 2] = [0x01d800, 0x01daaf];
 
-static CR_In_Latin_Extended_G: [u32; 2] = [0x01df00, 0x01dfff];
+const CR_In_Latin_Extended_G: [u32; 2] = [0x01df00, 0x01dfff];
 
-static CR_In_Glagolitic_Supplement: [u32; 2] = [0x01e000, 0x01e02f];
+const CR_In_Glagolitic_Supplement: [u32; 2] = [0x01e000, 0x01e02f];
 
-static CR_In_Cyrillic_Extended_D: [u32; 2] = [0x01e030, 0x01e08f];
+const CR_In_Cyrillic_Extended_D: [u32; 2] = [0x01e030, 0x01e08f];
 
-static CR_In_Nyiakeng_Puachue_Hmong: [u32; 2] = [0x01e100, 0x01e14f];
+const CR_In_Nyiakeng_Puachue_Hmong: [u32; 2] = [0x01e100, 0x01e14f];
 
-static CR_In_Toto: [u32; 2] = [0x01e290, 0x01e2bf];
+const CR_In_Toto: [u32; 2] = [0x01e290, 0x01e2bf];
 
-static CR_In_Wancho: [u32; 2] = [0x01e2c0, 0x01e2ff];
+const CR_In_Wancho: [u32; 2] = [0x01e2c0, 0x01e2ff];
 
-static CR_In_Nag_Mundari: [u32; 2] = [0x01e4d0, 0x01e4ff];
+const CR_In_Nag_Mundari: [u32; 2] = [0x01e4d0, 0x01e4ff];
 
-static CR_In_Ol_Onal: [u32; 2] = [0x01e5d0, 0x01e5ff];
+const CR_In_Ol_Onal: [u32; 2] = [0x01e5d0, 0x01e5ff];
 
-static CR_In_Ethiopic_Extended_B: [u32; 2] = [0x01e7e0, 0x01e7ff];
+const CR_In_Ethiopic_Extended_B: [u32; 2] = [0x01e7e0, 0x01e7ff];
 
-static CR_In_Mende_Kikakui: [u32; 2] = [0x01e800, 0x01e8df];
+const CR_In_Mende_Kikakui: [u32; 2] = [0x01e800, 0x01e8df];
 
-static CR_In_Adlam: [u32; 2] = [0x01e900, 0x01e95f];
+const CR_In_Adlam: [u32; 2] = [0x01e900, 0x01e95f];
 
-static CR_In_Indic_Siyaq_Numbers: [u32; 2] = [0x01ec70, 0x01ecbf];
+const CR_In_Indic_Siyaq_Numbers: [u32; 2] = [0x01ec70, 0x01ecbf];
 
-static CR_In_Ottoman_Siyaq_Numbers: [u32; 2] = [0x01ed00, 0x01ed4f];
+const CR_In_Ottoman_Siyaq_Numbers: [u32; 2] = [0x01ed00, 0x01ed4f];
 
-static CR_In_Arabic_Mathematical_Alphabetic_Symbols: [u32; 2] = [0x01ee00, 0x01eeff];
+const CR_In_Arabic_Mathematical_Alphabetic_Symbols: [u32; 2] = [0x01ee00, 0x01eeff];
 
-static CR_In_Mahjong_Tiles: [u32; 2] = [0x01f000, 0x01f02f];
+const CR_In_Mahjong_Tiles: [u32; 2] = [0x01f000, 0x01f02f];
 
-static CR_In_Domino_Tiles: [u32; 2] = [0x01f030, 0x01f09f];
+const CR_In_Domino_Tiles: [u32; 2] = [0x01f030, 0x01f09f];
 
-static CR_In_Playing_Cards: [u32; 2] = [0x01f0a0, 0x01f0ff];
+const CR_In_Playing_Cards: [u32; 2] = [0x01f0a0, 0x01f0ff];
 
-static CR_In_Enclosed_Alphanumeric_Supplement: [u32; 2] = [0x01f100, 0x01f1ff];
+const CR_In_Enclosed_Alphanumeric_Supplement: [u32; 2] = [0x01f100, 0x01f1ff];
 
-static CR_In_Enclosed_Ideographic_Supplement: [u32; 2] = [0x01f200, 0x01f2ff];
+const CR_In_Enclosed_Ideographic_Supplement: [u32; 2] = [0x01f200, 0x01f2ff];
 
-static CR_In_Miscellaneous_Symbols_and_Pictographs: [u32; 2] = [0x01f300, 0x01f5ff];
+const CR_In_Miscellaneous_Symbols_and_Pictographs: [u32; 2] = [0x01f300, 0x01f5ff];
 
-static CR_In_Emoticons: [u32; 2] = [0x01f600, 0x01f64f];
+const CR_In_Emoticons: [u32; 2] = [0x01f600, 0x01f64f];
 
-static CR_In_Ornamental_Dingbats: [u32; 2] = [0x01f650, 0x01f67f];
+const CR_In_Ornamental_Dingbats: [u32; 2] = [0x01f650, 0x01f67f];
 
-static CR_In_Transport_and_Map_Symbols: [u32; 2] = [0x01f680, 0x01f6ff];
+const CR_In_Transport_and_Map_Symbols: [u32; 2] = [0x01f680, 0x01f6ff];
 
-static CR_In_Alchemical_Symbols: [u32; 2] = [0x01f700, 0x01f77f];
+const CR_In_Alchemical_Symbols: [u32; 2] = [0x01f700, 0x01f77f];
 
-static CR_In_Geometric_Shapes_Extended: [u32; 2] = [0x01f780, 0x01f7ff];
+const CR_In_Geometric_Shapes_Extended: [u32; 2] = [0x01f780, 0x01f7ff];
 
-static CR_In_Supplemental_Arrows_C: [u32; 2] = [0x01f800, 0x01f8ff];
+const CR_In_Supplemental_Arrows_C: [u32; 2] = [0x01f800, 0x01f8ff];
 
-static CR_In_Supplemental_Symbols_and_Pictographs: [u32; 2] = [0x01f900, 0x01f9ff];
+const CR_In_Supplemental_Symbols_and_Pictographs: [u32; 2] = [0x01f900, 0x01f9ff];
 
-static CR_In_Chess_Symbols: [u32; 2] = [0x01fa00, 0x01fa6f];
+const CR_In_Chess_Symbols: [u32; 2] = [0x01fa00, 0x01fa6f];
 
-static CR_In_Symbols_and_Pictographs_Extended_A: [u32; 2] = [0x01fa70, 0x01faff];
+const CR_In_Symbols_and_Pictographs_Extended_A: [u32; 2] = [0x01fa70, 0x01faff];
 
-static CR_In_Symbols_for_Legacy_Computing: [u32; 2] = [0x01fb00, 0x01fbff];
+const CR_In_Symbols_for_Legacy_Computing: [u32; 2] = [0x01fb00, 0x01fbff];
 
-static CR_In_CJK_Unified_Ideographs_Extension_B: [u32; 2] = [0x020000, 0x02a6df];
+const CR_In_CJK_Unified_Ideographs_Extension_B: [u32; 2] = [0x020000, 0x02a6df];
 
-static CR_In_CJK_Unified_Ideographs_Extension_C: [u32; 2] = [0x02a700, 0x02b73f];
+const CR_In_CJK_Unified_Ideographs_Extension_C: [u32; 2] = [0x02a700, 0x02b73f];
 
-static CR_In_CJK_Unified_Ideographs_Extension_D: [u32; 2] = [0x02b740, 0x02b81f];
+const CR_In_CJK_Unified_Ideographs_Extension_D: [u32; 2] = [0x02b740, 0x02b81f];
 
-static CR_In_CJK_Unified_Ideographs_Extension_E: [u32; 2] = [0x02b820, 0x02ceaf];
+const CR_In_CJK_Unified_Ideographs_Extension_E: [u32; 2] = [0x02b820, 0x02ceaf];
 
-static CR_In_CJK_Unified_Ideographs_Extension_F: [u32; 2] = [0x02ceb0, 0x02ebef];
+const CR_In_CJK_Unified_Ideographs_Extension_F: [u32; 2] = [0x02ceb0, 0x02ebef];
 
-static CR_In_CJK_Unified_Ideographs_Extension_I: [u32; 2] = [0x02ebf0, 0x02ee5f];
+const CR_In_CJK_Unified_Ideographs_Extension_I: [u32; 2] = [0x02ebf0, 0x02ee5f];
 
-static CR_In_CJK_Compatibility_Ideographs_Supplement: [u32; 2] = [0x02f800, 0x02fa1f];
+const CR_In_CJK_Compatibility_Ideographs_Supplement: [u32; 2] = [0x02f800, 0x02fa1f];
 
-static CR_In_CJK_Unified_Ideographs_Extension_G: [u32; 2] = [0x030000, 0x03134f];
+const CR_In_CJK_Unified_Ideographs_Extension_G: [u32; 2] = [0x030000, 0x03134f];
 
-static CR_In_CJK_Unified_Ideographs_Extension_H: [u32; 2] = [0x031350, 0x0323af];
+const CR_In_CJK_Unified_Ideographs_Extension_H: [u32; 2] = [0x031350, 0x0323af];
 
-static CR_In_Tags: [u32; 2] = [0x0e0000, 0x0e007f];
+const CR_In_Tags: [u32; 2] = [0x0e0000, 0x0e007f];
 
-static CR_In_Variation_Selectors_Supplement: [u32; 2] = [0x0e0100, 0x0e01ef];
+const CR_In_Variation_Selectors_Supplement: [u32; 2] = [0x0e0100, 0x0e01ef];
 
-static CR_In_Supplementary_Private_Use_Area_A: [u32; 2] = [0x0f0000, 0x0fffff];
+const CR_In_Supplementary_Private_Use_Area_A: [u32; 2] = [0x0f0000, 0x0fffff];
 
-static CR_In_Supplementary_Private_Use_Area_B: [u32; 2] = [0x100000, 0x10ffff];
+const CR_In_Supplementary_Private_Use_Area_B: [u32; 2] = [0x100000, 0x10ffff];
 
-static CR_In_No_Block: [u32; 102] = [
+const CR_In_No_Block: [u32; 102] = [
     0x002fe0, 0x002fef, 0x010200, 0x01027f, 0x0103e0, 0x0103ff, 0x0107c0, 0x0107ff, 0x0108b0,
     0x0108df, 0x010940, 0x01097f, 0x010aa0, 0x010abf, 0x010bb0, 0x010bff, 0x010c50, 0x010c7f,
     0x010d90, 0x010e5f, 0x011250, 0x01127f, 0x0114e0, 0x01157f, 0x011750, 0x0117ff, 0x011850,
@@ -6754,8 +6761,564 @@ static CR_In_No_Block: [u32; 102] = [
     0x0e00ff, 0x0e01f0, 0x0effff,
 ];
 
+const CR_Scx_Adlam: [u32; 14] = [
+    0x00061f, 0x00061f, 0x000640, 0x000640, 0x00204f, 0x00204f, 0x002e41, 0x002e41, 0x01e900,
+    0x01e94b, 0x01e950, 0x01e959, 0x01e95e, 0x01e95f,
+];
+
+const CR_Scx_Arabic: [u32; 110] = [
+    0x000600, 0x000604, 0x000606, 0x0006dc, 0x0006de, 0x0006ff, 0x000750, 0x00077f, 0x000870,
+    0x00088e, 0x000890, 0x000891, 0x000897, 0x0008e1, 0x0008e3, 0x0008ff, 0x00204f, 0x00204f,
+    0x002e41, 0x002e41, 0x00fb50, 0x00fbc2, 0x00fbd3, 0x00fd8f, 0x00fd92, 0x00fdc7, 0x00fdcf,
+    0x00fdcf, 0x00fdf0, 0x00fdff, 0x00fe70, 0x00fe74, 0x00fe76, 0x00fefc, 0x0102e0, 0x0102fb,
+    0x010e60, 0x010e7e, 0x010ec2, 0x010ec4, 0x010efc, 0x010eff, 0x01ee00, 0x01ee03, 0x01ee05,
+    0x01ee1f, 0x01ee21, 0x01ee22, 0x01ee24, 0x01ee24, 0x01ee27, 0x01ee27, 0x01ee29, 0x01ee32,
+    0x01ee34, 0x01ee37, 0x01ee39, 0x01ee39, 0x01ee3b, 0x01ee3b, 0x01ee42, 0x01ee42, 0x01ee47,
+    0x01ee47, 0x01ee49, 0x01ee49, 0x01ee4b, 0x01ee4b, 0x01ee4d, 0x01ee4f, 0x01ee51, 0x01ee52,
+    0x01ee54, 0x01ee54, 0x01ee57, 0x01ee57, 0x01ee59, 0x01ee59, 0x01ee5b, 0x01ee5b, 0x01ee5d,
+    0x01ee5d, 0x01ee5f, 0x01ee5f, 0x01ee61, 0x01ee62, 0x01ee64, 0x01ee64, 0x01ee67, 0x01ee6a,
+    0x01ee6c, 0x01ee72, 0x01ee74, 0x01ee77, 0x01ee79, 0x01ee7c, 0x01ee7e, 0x01ee7e, 0x01ee80,
+    0x01ee89, 0x01ee8b, 0x01ee9b, 0x01eea1, 0x01eea3, 0x01eea5, 0x01eea9, 0x01eeab, 0x01eebb,
+    0x01eef0, 0x01eef1,
+];
+
+const CR_Scx_Armenian: [u32; 10] = [
+    0x000308, 0x000308, 0x000531, 0x000556, 0x000559, 0x00058a, 0x00058d, 0x00058f, 0x00fb13,
+    0x00fb17,
+];
+
+const CR_Scx_Avestan: [u32; 8] = [
+    0x0000b7, 0x0000b7, 0x002e30, 0x002e31, 0x010b00, 0x010b35, 0x010b39, 0x010b3f,
+];
+
+const CR_Scx_Bengali: [u32; 54] = [
+    0x0002bc, 0x0002bc, 0x000951, 0x000952, 0x000964, 0x000965, 0x000980, 0x000983, 0x000985,
+    0x00098c, 0x00098f, 0x000990, 0x000993, 0x0009a8, 0x0009aa, 0x0009b0, 0x0009b2, 0x0009b2,
+    0x0009b6, 0x0009b9, 0x0009bc, 0x0009c4, 0x0009c7, 0x0009c8, 0x0009cb, 0x0009ce, 0x0009d7,
+    0x0009d7, 0x0009dc, 0x0009dd, 0x0009df, 0x0009e3, 0x0009e6, 0x0009fe, 0x001cd0, 0x001cd0,
+    0x001cd2, 0x001cd2, 0x001cd5, 0x001cd6, 0x001cd8, 0x001cd8, 0x001ce1, 0x001ce1, 0x001cea,
+    0x001cea, 0x001ced, 0x001ced, 0x001cf2, 0x001cf2, 0x001cf5, 0x001cf7, 0x00a8f1, 0x00a8f1,
+];
+
+const CR_Scx_Bopomofo: [u32; 30] = [
+    0x0002c7, 0x0002c7, 0x0002c9, 0x0002cb, 0x0002d9, 0x0002d9, 0x0002ea, 0x0002eb, 0x003001,
+    0x003003, 0x003008, 0x003011, 0x003013, 0x00301f, 0x00302a, 0x00302d, 0x003030, 0x003030,
+    0x003037, 0x003037, 0x0030fb, 0x0030fb, 0x003105, 0x00312f, 0x0031a0, 0x0031bf, 0x00fe45,
+    0x00fe46, 0x00ff61, 0x00ff65,
+];
+
+const CR_Scx_Buginese: [u32; 6] = [0x001a00, 0x001a1b, 0x001a1e, 0x001a1f, 0x00a9cf, 0x00a9cf];
+
+const CR_Scx_Buhid: [u32; 4] = [0x001735, 0x001736, 0x001740, 0x001753];
+
+const CR_Scx_Carian: [u32; 10] = [
+    0x0000b7, 0x0000b7, 0x00205a, 0x00205a, 0x00205d, 0x00205d, 0x002e31, 0x002e31, 0x0102a0,
+    0x0102d0,
+];
+
+const CR_Scx_Caucasian_Albanian: [u32; 10] = [
+    0x000304, 0x000304, 0x000331, 0x000331, 0x00035e, 0x00035e, 0x010530, 0x010563, 0x01056f,
+    0x01056f,
+];
+
+const CR_Scx_Chakma: [u32; 8] = [
+    0x0009e6, 0x0009ef, 0x001040, 0x001049, 0x011100, 0x011134, 0x011136, 0x011147,
+];
+
+const CR_Scx_Cherokee: [u32; 16] = [
+    0x000300, 0x000302, 0x000304, 0x000304, 0x00030b, 0x00030c, 0x000323, 0x000324, 0x000330,
+    0x000331, 0x0013a0, 0x0013f5, 0x0013f8, 0x0013fd, 0x00ab70, 0x00abbf,
+];
+
+const CR_Scx_Common: [u32; 318] = [
+    0x000000, 0x000040, 0x00005b, 0x000060, 0x00007b, 0x0000a9, 0x0000ab, 0x0000b6, 0x0000b8,
+    0x0000b9, 0x0000bb, 0x0000bf, 0x0000d7, 0x0000d7, 0x0000f7, 0x0000f7, 0x0002b9, 0x0002bb,
+    0x0002bd, 0x0002c6, 0x0002c8, 0x0002c8, 0x0002cc, 0x0002cc, 0x0002ce, 0x0002d6, 0x0002d8,
+    0x0002d8, 0x0002da, 0x0002df, 0x0002e5, 0x0002e9, 0x0002ec, 0x0002ff, 0x00037e, 0x00037e,
+    0x000385, 0x000385, 0x000387, 0x000387, 0x000605, 0x000605, 0x0006dd, 0x0006dd, 0x0008e2,
+    0x0008e2, 0x000e3f, 0x000e3f, 0x000fd5, 0x000fd8, 0x002000, 0x00200b, 0x00200e, 0x00202e,
+    0x002030, 0x00204e, 0x002050, 0x002059, 0x00205b, 0x00205c, 0x00205e, 0x002064, 0x002066,
+    0x002070, 0x002074, 0x00207e, 0x002080, 0x00208e, 0x0020a0, 0x0020c0, 0x002100, 0x002125,
+    0x002127, 0x002129, 0x00212c, 0x002131, 0x002133, 0x00214d, 0x00214f, 0x00215f, 0x002189,
+    0x00218b, 0x002190, 0x002429, 0x002440, 0x00244a, 0x002460, 0x0027ff, 0x002900, 0x002b73,
+    0x002b76, 0x002b95, 0x002b97, 0x002bff, 0x002e00, 0x002e16, 0x002e18, 0x002e2f, 0x002e32,
+    0x002e3b, 0x002e3d, 0x002e40, 0x002e42, 0x002e42, 0x002e44, 0x002e5d, 0x003000, 0x003000,
+    0x003004, 0x003004, 0x003012, 0x003012, 0x003020, 0x003020, 0x003036, 0x003036, 0x003248,
+    0x00325f, 0x00327f, 0x00327f, 0x0032b1, 0x0032bf, 0x0032cc, 0x0032cf, 0x003371, 0x00337a,
+    0x003380, 0x0033df, 0x0033ff, 0x0033ff, 0x004dc0, 0x004dff, 0x00a708, 0x00a721, 0x00a788,
+    0x00a78a, 0x00ab5b, 0x00ab5b, 0x00ab6a, 0x00ab6b, 0x00fe10, 0x00fe19, 0x00fe30, 0x00fe44,
+    0x00fe47, 0x00fe52, 0x00fe54, 0x00fe66, 0x00fe68, 0x00fe6b, 0x00feff, 0x00feff, 0x00ff01,
+    0x00ff20, 0x00ff3b, 0x00ff40, 0x00ff5b, 0x00ff60, 0x00ffe0, 0x00ffe6, 0x00ffe8, 0x00ffee,
+    0x00fff9, 0x00fffd, 0x010190, 0x01019c, 0x0101d0, 0x0101fc, 0x01cc00, 0x01ccf9, 0x01cd00,
+    0x01ceb3, 0x01cf50, 0x01cfc3, 0x01d000, 0x01d0f5, 0x01d100, 0x01d126, 0x01d129, 0x01d166,
+    0x01d16a, 0x01d17a, 0x01d183, 0x01d184, 0x01d18c, 0x01d1a9, 0x01d1ae, 0x01d1ea, 0x01d2c0,
+    0x01d2d3, 0x01d2e0, 0x01d2f3, 0x01d300, 0x01d356, 0x01d372, 0x01d378, 0x01d400, 0x01d454,
+    0x01d456, 0x01d49c, 0x01d49e, 0x01d49f, 0x01d4a2, 0x01d4a2, 0x01d4a5, 0x01d4a6, 0x01d4a9,
+    0x01d4ac, 0x01d4ae, 0x01d4b9, 0x01d4bb, 0x01d4bb, 0x01d4bd, 0x01d4c3, 0x01d4c5, 0x01d505,
+    0x01d507, 0x01d50a, 0x01d50d, 0x01d514, 0x01d516, 0x01d51c, 0x01d51e, 0x01d539, 0x01d53b,
+    0x01d53e, 0x01d540, 0x01d544, 0x01d546, 0x01d546, 0x01d54a, 0x01d550, 0x01d552, 0x01d6a5,
+    0x01d6a8, 0x01d7cb, 0x01d7ce, 0x01d7ff, 0x01ec71, 0x01ecb4, 0x01ed01, 0x01ed3d, 0x01f000,
+    0x01f02b, 0x01f030, 0x01f093, 0x01f0a0, 0x01f0ae, 0x01f0b1, 0x01f0bf, 0x01f0c1, 0x01f0cf,
+    0x01f0d1, 0x01f0f5, 0x01f100, 0x01f1ad, 0x01f1e6, 0x01f1ff, 0x01f201, 0x01f202, 0x01f210,
+    0x01f23b, 0x01f240, 0x01f248, 0x01f260, 0x01f265, 0x01f300, 0x01f6d7, 0x01f6dc, 0x01f6ec,
+    0x01f6f0, 0x01f6fc, 0x01f700, 0x01f776, 0x01f77b, 0x01f7d9, 0x01f7e0, 0x01f7eb, 0x01f7f0,
+    0x01f7f0, 0x01f800, 0x01f80b, 0x01f810, 0x01f847, 0x01f850, 0x01f859, 0x01f860, 0x01f887,
+    0x01f890, 0x01f8ad, 0x01f8b0, 0x01f8bb, 0x01f8c0, 0x01f8c1, 0x01f900, 0x01fa53, 0x01fa60,
+    0x01fa6d, 0x01fa70, 0x01fa7c, 0x01fa80, 0x01fa89, 0x01fa8f, 0x01fac6, 0x01face, 0x01fadc,
+    0x01fadf, 0x01fae9, 0x01faf0, 0x01faf8, 0x01fb00, 0x01fb92, 0x01fb94, 0x01fbf9, 0x0e0001,
+    0x0e0001, 0x0e0020, 0x0e007f,
+];
+
+const CR_Scx_Coptic: [u32; 20] = [
+    0x0000b7, 0x0000b7, 0x000300, 0x000300, 0x000304, 0x000305, 0x000307, 0x000307, 0x000374,
+    0x000375, 0x0003e2, 0x0003ef, 0x002c80, 0x002cf3, 0x002cf9, 0x002cff, 0x002e17, 0x002e17,
+    0x0102e0, 0x0102fb,
+];
+
+const CR_Scx_Cypriot: [u32; 18] = [
+    0x010100, 0x010102, 0x010107, 0x010133, 0x010137, 0x01013f, 0x010800, 0x010805, 0x010808,
+    0x010808, 0x01080a, 0x010835, 0x010837, 0x010838, 0x01083c, 0x01083c, 0x01083f, 0x01083f,
+];
+
+const CR_Scx_Cypro_Minoan: [u32; 4] = [0x010100, 0x010101, 0x012f90, 0x012ff2];
+
+const CR_Scx_Cyrillic: [u32; 36] = [
+    0x0002bc, 0x0002bc, 0x000300, 0x000302, 0x000304, 0x000304, 0x000306, 0x000306, 0x000308,
+    0x000308, 0x00030b, 0x00030b, 0x000311, 0x000311, 0x000400, 0x00052f, 0x001c80, 0x001c8a,
+    0x001d2b, 0x001d2b, 0x001d78, 0x001d78, 0x001df8, 0x001df8, 0x002de0, 0x002dff, 0x002e43,
+    0x002e43, 0x00a640, 0x00a69f, 0x00fe2e, 0x00fe2f, 0x01e030, 0x01e06d, 0x01e08f, 0x01e08f,
+];
+
+const CR_Scx_Devanagari: [u32; 18] = [
+    0x0002bc, 0x0002bc, 0x000900, 0x000952, 0x000955, 0x00097f, 0x001cd0, 0x001cf6, 0x001cf8,
+    0x001cf9, 0x0020f0, 0x0020f0, 0x00a830, 0x00a839, 0x00a8e0, 0x00a8ff, 0x011b00, 0x011b09,
+];
+
+const CR_Scx_Dogra: [u32; 6] = [0x000964, 0x00096f, 0x00a830, 0x00a839, 0x011800, 0x01183b];
+
+const CR_Scx_Duployan: [u32; 20] = [
+    0x0000b7, 0x0000b7, 0x000307, 0x000308, 0x00030a, 0x00030a, 0x000323, 0x000324, 0x002e3c,
+    0x002e3c, 0x01bc00, 0x01bc6a, 0x01bc70, 0x01bc7c, 0x01bc80, 0x01bc88, 0x01bc90, 0x01bc99,
+    0x01bc9c, 0x01bca3,
+];
+
+const CR_Scx_Elbasan: [u32; 6] = [0x0000b7, 0x0000b7, 0x000305, 0x000305, 0x010500, 0x010527];
+
+const CR_Scx_Ethiopic: [u32; 74] = [
+    0x00030e, 0x00030e, 0x001200, 0x001248, 0x00124a, 0x00124d, 0x001250, 0x001256, 0x001258,
+    0x001258, 0x00125a, 0x00125d, 0x001260, 0x001288, 0x00128a, 0x00128d, 0x001290, 0x0012b0,
+    0x0012b2, 0x0012b5, 0x0012b8, 0x0012be, 0x0012c0, 0x0012c0, 0x0012c2, 0x0012c5, 0x0012c8,
+    0x0012d6, 0x0012d8, 0x001310, 0x001312, 0x001315, 0x001318, 0x00135a, 0x00135d, 0x00137c,
+    0x001380, 0x001399, 0x002d80, 0x002d96, 0x002da0, 0x002da6, 0x002da8, 0x002dae, 0x002db0,
+    0x002db6, 0x002db8, 0x002dbe, 0x002dc0, 0x002dc6, 0x002dc8, 0x002dce, 0x002dd0, 0x002dd6,
+    0x002dd8, 0x002dde, 0x00ab01, 0x00ab06, 0x00ab09, 0x00ab0e, 0x00ab11, 0x00ab16, 0x00ab20,
+    0x00ab26, 0x00ab28, 0x00ab2e, 0x01e7e0, 0x01e7e6, 0x01e7e8, 0x01e7eb, 0x01e7ed, 0x01e7ee,
+    0x01e7f0, 0x01e7fe,
+];
+
+const CR_Scx_Garay: [u32; 12] = [
+    0x00060c, 0x00060c, 0x00061b, 0x00061b, 0x00061f, 0x00061f, 0x010d40, 0x010d65, 0x010d69,
+    0x010d85, 0x010d8e, 0x010d8f,
+];
+
+const CR_Scx_Georgian: [u32; 26] = [
+    0x0000b7, 0x0000b7, 0x000589, 0x000589, 0x0010a0, 0x0010c5, 0x0010c7, 0x0010c7, 0x0010cd,
+    0x0010cd, 0x0010d0, 0x0010ff, 0x001c90, 0x001cba, 0x001cbd, 0x001cbf, 0x00205a, 0x00205a,
+    0x002d00, 0x002d25, 0x002d27, 0x002d27, 0x002d2d, 0x002d2d, 0x002e31, 0x002e31,
+];
+
+const CR_Scx_Glagolitic: [u32; 32] = [
+    0x0000b7, 0x0000b7, 0x000303, 0x000303, 0x000305, 0x000305, 0x000484, 0x000484, 0x000487,
+    0x000487, 0x000589, 0x000589, 0x0010fb, 0x0010fb, 0x00205a, 0x00205a, 0x002c00, 0x002c5f,
+    0x002e43, 0x002e43, 0x00a66f, 0x00a66f, 0x01e000, 0x01e006, 0x01e008, 0x01e018, 0x01e01b,
+    0x01e021, 0x01e023, 0x01e024, 0x01e026, 0x01e02a,
+];
+
+const CR_Scx_Gothic: [u32; 10] = [
+    0x0000b7, 0x0000b7, 0x000304, 0x000305, 0x000308, 0x000308, 0x000331, 0x000331, 0x010330,
+    0x01034a,
+];
+
+const CR_Scx_Grantha: [u32; 50] = [
+    0x000951, 0x000952, 0x000964, 0x000965, 0x000be6, 0x000bf3, 0x001cd0, 0x001cd0, 0x001cd2,
+    0x001cd3, 0x001cf2, 0x001cf4, 0x001cf8, 0x001cf9, 0x0020f0, 0x0020f0, 0x011300, 0x011303,
+    0x011305, 0x01130c, 0x01130f, 0x011310, 0x011313, 0x011328, 0x01132a, 0x011330, 0x011332,
+    0x011333, 0x011335, 0x011339, 0x01133b, 0x011344, 0x011347, 0x011348, 0x01134b, 0x01134d,
+    0x011350, 0x011350, 0x011357, 0x011357, 0x01135d, 0x011363, 0x011366, 0x01136c, 0x011370,
+    0x011374, 0x011fd0, 0x011fd1, 0x011fd3, 0x011fd3,
+];
+
+const CR_Scx_Greek: [u32; 88] = [
+    0x0000b7, 0x0000b7, 0x000300, 0x000301, 0x000304, 0x000304, 0x000306, 0x000306, 0x000308,
+    0x000308, 0x000313, 0x000313, 0x000342, 0x000342, 0x000345, 0x000345, 0x000370, 0x000377,
+    0x00037a, 0x00037d, 0x00037f, 0x00037f, 0x000384, 0x000384, 0x000386, 0x000386, 0x000388,
+    0x00038a, 0x00038c, 0x00038c, 0x00038e, 0x0003a1, 0x0003a3, 0x0003e1, 0x0003f0, 0x0003ff,
+    0x001d26, 0x001d2a, 0x001d5d, 0x001d61, 0x001d66, 0x001d6a, 0x001dbf, 0x001dc1, 0x001f00,
+    0x001f15, 0x001f18, 0x001f1d, 0x001f20, 0x001f45, 0x001f48, 0x001f4d, 0x001f50, 0x001f57,
+    0x001f59, 0x001f59, 0x001f5b, 0x001f5b, 0x001f5d, 0x001f5d, 0x001f5f, 0x001f7d, 0x001f80,
+    0x001fb4, 0x001fb6, 0x001fc4, 0x001fc6, 0x001fd3, 0x001fd6, 0x001fdb, 0x001fdd, 0x001fef,
+    0x001ff2, 0x001ff4, 0x001ff6, 0x001ffe, 0x00205d, 0x00205d, 0x002126, 0x002126, 0x00ab65,
+    0x00ab65, 0x010140, 0x01018e, 0x0101a0, 0x0101a0, 0x01d200, 0x01d245,
+];
+
+const CR_Scx_Gujarati: [u32; 34] = [
+    0x000951, 0x000952, 0x000964, 0x000965, 0x000a81, 0x000a83, 0x000a85, 0x000a8d, 0x000a8f,
+    0x000a91, 0x000a93, 0x000aa8, 0x000aaa, 0x000ab0, 0x000ab2, 0x000ab3, 0x000ab5, 0x000ab9,
+    0x000abc, 0x000ac5, 0x000ac7, 0x000ac9, 0x000acb, 0x000acd, 0x000ad0, 0x000ad0, 0x000ae0,
+    0x000ae3, 0x000ae6, 0x000af1, 0x000af9, 0x000aff, 0x00a830, 0x00a839,
+];
+
+const CR_Scx_Gunjala_Gondi: [u32; 16] = [
+    0x0000b7, 0x0000b7, 0x000964, 0x000965, 0x011d60, 0x011d65, 0x011d67, 0x011d68, 0x011d6a,
+    0x011d8e, 0x011d90, 0x011d91, 0x011d93, 0x011d98, 0x011da0, 0x011da9,
+];
+
+const CR_Scx_Gurmukhi: [u32; 38] = [
+    0x000951, 0x000952, 0x000964, 0x000965, 0x000a01, 0x000a03, 0x000a05, 0x000a0a, 0x000a0f,
+    0x000a10, 0x000a13, 0x000a28, 0x000a2a, 0x000a30, 0x000a32, 0x000a33, 0x000a35, 0x000a36,
+    0x000a38, 0x000a39, 0x000a3c, 0x000a3c, 0x000a3e, 0x000a42, 0x000a47, 0x000a48, 0x000a4b,
+    0x000a4d, 0x000a51, 0x000a51, 0x000a59, 0x000a5c, 0x000a5e, 0x000a5e, 0x000a66, 0x000a76,
+    0x00a830, 0x00a839,
+];
+
+const CR_Scx_Gurung_Khema: [u32; 4] = [0x000965, 0x000965, 0x016100, 0x016139];
+
+const CR_Scx_Han: [u32; 84] = [
+    0x0000b7, 0x0000b7, 0x002e80, 0x002e99, 0x002e9b, 0x002ef3, 0x002f00, 0x002fd5, 0x002ff0,
+    0x002fff, 0x003001, 0x003003, 0x003005, 0x003011, 0x003013, 0x00301f, 0x003021, 0x00302d,
+    0x003030, 0x003030, 0x003037, 0x00303f, 0x0030fb, 0x0030fb, 0x003190, 0x00319f, 0x0031c0,
+    0x0031e5, 0x0031ef, 0x0031ef, 0x003220, 0x003247, 0x003280, 0x0032b0, 0x0032c0, 0x0032cb,
+    0x0032ff, 0x0032ff, 0x003358, 0x003370, 0x00337b, 0x00337f, 0x0033e0, 0x0033fe, 0x003400,
+    0x004dbf, 0x004e00, 0x009fff, 0x00a700, 0x00a707, 0x00f900, 0x00fa6d, 0x00fa70, 0x00fad9,
+    0x00fe45, 0x00fe46, 0x00ff61, 0x00ff65, 0x016fe2, 0x016fe3, 0x016ff0, 0x016ff1, 0x01d360,
+    0x01d371, 0x01f250, 0x01f251, 0x020000, 0x02a6df, 0x02a700, 0x02b739, 0x02b740, 0x02b81d,
+    0x02b820, 0x02cea1, 0x02ceb0, 0x02ebe0, 0x02ebf0, 0x02ee5d, 0x02f800, 0x02fa1d, 0x030000,
+    0x03134a, 0x031350, 0x0323af,
+];
+
+const CR_Scx_Hangul: [u32; 42] = [
+    0x001100, 0x0011ff, 0x003001, 0x003003, 0x003008, 0x003011, 0x003013, 0x00301f, 0x00302e,
+    0x003030, 0x003037, 0x003037, 0x0030fb, 0x0030fb, 0x003131, 0x00318e, 0x003200, 0x00321e,
+    0x003260, 0x00327e, 0x00a960, 0x00a97c, 0x00ac00, 0x00d7a3, 0x00d7b0, 0x00d7c6, 0x00d7cb,
+    0x00d7fb, 0x00fe45, 0x00fe46, 0x00ff61, 0x00ff65, 0x00ffa0, 0x00ffbe, 0x00ffc2, 0x00ffc7,
+    0x00ffca, 0x00ffcf, 0x00ffd2, 0x00ffd7, 0x00ffda, 0x00ffdc,
+];
+
+const CR_Scx_Hanifi_Rohingya: [u32; 14] = [
+    0x00060c, 0x00060c, 0x00061b, 0x00061b, 0x00061f, 0x00061f, 0x000640, 0x000640, 0x0006d4,
+    0x0006d4, 0x010d00, 0x010d27, 0x010d30, 0x010d39,
+];
+
+const CR_Scx_Hanunoo: [u32; 2] = [0x001720, 0x001736];
+
+const CR_Scx_Hebrew: [u32; 20] = [
+    0x000307, 0x000308, 0x000591, 0x0005c7, 0x0005d0, 0x0005ea, 0x0005ef, 0x0005f4, 0x00fb1d,
+    0x00fb36, 0x00fb38, 0x00fb3c, 0x00fb3e, 0x00fb3e, 0x00fb40, 0x00fb41, 0x00fb43, 0x00fb44,
+    0x00fb46, 0x00fb4f,
+];
+
+const CR_Scx_Hiragana: [u32; 34] = [
+    0x003001, 0x003003, 0x003008, 0x003011, 0x003013, 0x00301f, 0x003030, 0x003035, 0x003037,
+    0x003037, 0x00303c, 0x00303d, 0x003041, 0x003096, 0x003099, 0x0030a0, 0x0030fb, 0x0030fc,
+    0x00fe45, 0x00fe46, 0x00ff61, 0x00ff65, 0x00ff70, 0x00ff70, 0x00ff9e, 0x00ff9f, 0x01b001,
+    0x01b11f, 0x01b132, 0x01b132, 0x01b150, 0x01b152, 0x01f200, 0x01f200,
+];
+
+const CR_Scx_Inherited: [u32; 56] = [
+    0x00030f, 0x00030f, 0x000312, 0x000312, 0x000314, 0x00031f, 0x000321, 0x000322, 0x000326,
+    0x00032c, 0x00032f, 0x00032f, 0x000332, 0x000341, 0x000343, 0x000344, 0x000346, 0x000357,
+    0x000359, 0x00035d, 0x00035f, 0x000362, 0x000953, 0x000954, 0x001ab0, 0x001ace, 0x001dc2,
+    0x001df7, 0x001df9, 0x001df9, 0x001dfb, 0x001dff, 0x00200c, 0x00200d, 0x0020d0, 0x0020ef,
+    0x00fe00, 0x00fe0f, 0x00fe20, 0x00fe2d, 0x0101fd, 0x0101fd, 0x01cf00, 0x01cf2d, 0x01cf30,
+    0x01cf46, 0x01d167, 0x01d169, 0x01d17b, 0x01d182, 0x01d185, 0x01d18b, 0x01d1aa, 0x01d1ad,
+    0x0e0100, 0x0e01ef,
+];
+
+const CR_Scx_Javanese: [u32; 6] = [0x00a980, 0x00a9cd, 0x00a9cf, 0x00a9d9, 0x00a9de, 0x00a9df];
+
+const CR_Scx_Kaithi: [u32; 10] = [
+    0x000966, 0x00096f, 0x002e31, 0x002e31, 0x00a830, 0x00a839, 0x011080, 0x0110c2, 0x0110cd,
+    0x0110cd,
+];
+
+const CR_Scx_Kannada: [u32; 42] = [
+    0x000951, 0x000952, 0x000964, 0x000965, 0x000c80, 0x000c8c, 0x000c8e, 0x000c90, 0x000c92,
+    0x000ca8, 0x000caa, 0x000cb3, 0x000cb5, 0x000cb9, 0x000cbc, 0x000cc4, 0x000cc6, 0x000cc8,
+    0x000cca, 0x000ccd, 0x000cd5, 0x000cd6, 0x000cdd, 0x000cde, 0x000ce0, 0x000ce3, 0x000ce6,
+    0x000cef, 0x000cf1, 0x000cf3, 0x001cd0, 0x001cd0, 0x001cd2, 0x001cd3, 0x001cda, 0x001cda,
+    0x001cf2, 0x001cf2, 0x001cf4, 0x001cf4, 0x00a830, 0x00a835,
+];
+
+const CR_Scx_Katakana: [u32; 44] = [
+    0x000305, 0x000305, 0x000323, 0x000323, 0x003001, 0x003003, 0x003008, 0x003011, 0x003013,
+    0x00301f, 0x003030, 0x003035, 0x003037, 0x003037, 0x00303c, 0x00303d, 0x003099, 0x00309c,
+    0x0030a0, 0x0030ff, 0x0031f0, 0x0031ff, 0x0032d0, 0x0032fe, 0x003300, 0x003357, 0x00fe45,
+    0x00fe46, 0x00ff61, 0x00ff9f, 0x01aff0, 0x01aff3, 0x01aff5, 0x01affb, 0x01affd, 0x01affe,
+    0x01b000, 0x01b000, 0x01b120, 0x01b122, 0x01b155, 0x01b155, 0x01b164, 0x01b167,
+];
+
+const CR_Scx_Kayah_Li: [u32; 2] = [0x00a900, 0x00a92f];
+
+const CR_Scx_Khojki: [u32; 8] = [
+    0x000ae6, 0x000aef, 0x00a830, 0x00a839, 0x011200, 0x011211, 0x011213, 0x011241,
+];
+
+const CR_Scx_Khudawadi: [u32; 8] = [
+    0x000964, 0x000965, 0x00a830, 0x00a839, 0x0112b0, 0x0112ea, 0x0112f0, 0x0112f9,
+];
+
+const CR_Scx_Latin: [u32; 130] = [
+    0x000041, 0x00005a, 0x000061, 0x00007a, 0x0000aa, 0x0000aa, 0x0000b7, 0x0000b7, 0x0000ba,
+    0x0000ba, 0x0000c0, 0x0000d6, 0x0000d8, 0x0000f6, 0x0000f8, 0x0002b8, 0x0002bc, 0x0002bc,
+    0x0002c7, 0x0002c7, 0x0002c9, 0x0002cb, 0x0002cd, 0x0002cd, 0x0002d7, 0x0002d7, 0x0002d9,
+    0x0002d9, 0x0002e0, 0x0002e4, 0x000300, 0x00030e, 0x000310, 0x000311, 0x000313, 0x000313,
+    0x000320, 0x000320, 0x000323, 0x000325, 0x00032d, 0x00032e, 0x000330, 0x000331, 0x000358,
+    0x000358, 0x00035e, 0x00035e, 0x000363, 0x00036f, 0x000485, 0x000486, 0x000951, 0x000952,
+    0x0010fb, 0x0010fb, 0x001d00, 0x001d25, 0x001d2c, 0x001d5c, 0x001d62, 0x001d65, 0x001d6b,
+    0x001d77, 0x001d79, 0x001dbe, 0x001df8, 0x001df8, 0x001e00, 0x001eff, 0x00202f, 0x00202f,
+    0x002071, 0x002071, 0x00207f, 0x00207f, 0x002090, 0x00209c, 0x0020f0, 0x0020f0, 0x00212a,
+    0x00212b, 0x002132, 0x002132, 0x00214e, 0x00214e, 0x002160, 0x002188, 0x002c60, 0x002c7f,
+    0x002e17, 0x002e17, 0x00a700, 0x00a707, 0x00a722, 0x00a787, 0x00a78b, 0x00a7cd, 0x00a7d0,
+    0x00a7d1, 0x00a7d3, 0x00a7d3, 0x00a7d5, 0x00a7dc, 0x00a7f2, 0x00a7ff, 0x00a92e, 0x00a92e,
+    0x00ab30, 0x00ab5a, 0x00ab5c, 0x00ab64, 0x00ab66, 0x00ab69, 0x00fb00, 0x00fb06, 0x00ff21,
+    0x00ff3a, 0x00ff41, 0x00ff5a, 0x010780, 0x010785, 0x010787, 0x0107b0, 0x0107b2, 0x0107ba,
+    0x01df00, 0x01df1e, 0x01df25, 0x01df2a,
+];
+
+const CR_Scx_Limbu: [u32; 12] = [
+    0x000965, 0x000965, 0x001900, 0x00191e, 0x001920, 0x00192b, 0x001930, 0x00193b, 0x001940,
+    0x001940, 0x001944, 0x00194f,
+];
+
+const CR_Scx_Linear_A: [u32; 8] = [
+    0x010107, 0x010133, 0x010600, 0x010736, 0x010740, 0x010755, 0x010760, 0x010767,
+];
+
+const CR_Scx_Linear_B: [u32; 20] = [
+    0x010000, 0x01000b, 0x01000d, 0x010026, 0x010028, 0x01003a, 0x01003c, 0x01003d, 0x01003f,
+    0x01004d, 0x010050, 0x01005d, 0x010080, 0x0100fa, 0x010100, 0x010102, 0x010107, 0x010133,
+    0x010137, 0x01013f,
+];
+
+const CR_Scx_Lisu: [u32; 10] = [
+    0x0002bc, 0x0002bc, 0x0002cd, 0x0002cd, 0x00300a, 0x00300b, 0x00a4d0, 0x00a4ff, 0x011fb0,
+    0x011fb0,
+];
+
+const CR_Scx_Lycian: [u32; 4] = [0x00205a, 0x00205a, 0x010280, 0x01029c];
+
+const CR_Scx_Lydian: [u32; 8] = [
+    0x0000b7, 0x0000b7, 0x002e31, 0x002e31, 0x010920, 0x010939, 0x01093f, 0x01093f,
+];
+
+const CR_Scx_Mahajani: [u32; 8] = [
+    0x0000b7, 0x0000b7, 0x000964, 0x00096f, 0x00a830, 0x00a839, 0x011150, 0x011176,
+];
+
+const CR_Scx_Malayalam: [u32; 24] = [
+    0x000951, 0x000952, 0x000964, 0x000965, 0x000d00, 0x000d0c, 0x000d0e, 0x000d10, 0x000d12,
+    0x000d44, 0x000d46, 0x000d48, 0x000d4a, 0x000d4f, 0x000d54, 0x000d63, 0x000d66, 0x000d7f,
+    0x001cda, 0x001cda, 0x001cf2, 0x001cf2, 0x00a830, 0x00a832,
+];
+
+const CR_Scx_Mandaic: [u32; 6] = [0x000640, 0x000640, 0x000840, 0x00085b, 0x00085e, 0x00085e];
+
+const CR_Scx_Manichaean: [u32; 6] = [0x000640, 0x000640, 0x010ac0, 0x010ae6, 0x010aeb, 0x010af6];
+
+const CR_Scx_Masaram_Gondi: [u32; 16] = [
+    0x000964, 0x000965, 0x011d00, 0x011d06, 0x011d08, 0x011d09, 0x011d0b, 0x011d36, 0x011d3a,
+    0x011d3a, 0x011d3c, 0x011d3d, 0x011d3f, 0x011d47, 0x011d50, 0x011d59,
+];
+
+const CR_Scx_Meroitic_Hieroglyphs: [u32; 4] = [0x00205d, 0x00205d, 0x010980, 0x01099f];
+
+const CR_Scx_Modi: [u32; 6] = [0x00a830, 0x00a839, 0x011600, 0x011644, 0x011650, 0x011659];
+
+const CR_Scx_Mongolian: [u32; 14] = [
+    0x001800, 0x001819, 0x001820, 0x001878, 0x001880, 0x0018aa, 0x00202f, 0x00202f, 0x003001,
+    0x003002, 0x003008, 0x00300b, 0x011660, 0x01166c,
+];
+
+const CR_Scx_Multani: [u32; 12] = [
+    0x000a66, 0x000a6f, 0x011280, 0x011286, 0x011288, 0x011288, 0x01128a, 0x01128d, 0x01128f,
+    0x01129d, 0x01129f, 0x0112a9,
+];
+
+const CR_Scx_Myanmar: [u32; 10] = [
+    0x001000, 0x00109f, 0x00a92e, 0x00a92e, 0x00a9e0, 0x00a9fe, 0x00aa60, 0x00aa7f, 0x0116d0,
+    0x0116e3,
+];
+
+const CR_Scx_Nandinagari: [u32; 18] = [
+    0x000964, 0x000965, 0x000ce6, 0x000cef, 0x001ce9, 0x001ce9, 0x001cf2, 0x001cf2, 0x001cfa,
+    0x001cfa, 0x00a830, 0x00a835, 0x0119a0, 0x0119a7, 0x0119aa, 0x0119d7, 0x0119da, 0x0119e4,
+];
+
+const CR_Scx_Nko: [u32; 12] = [
+    0x00060c, 0x00060c, 0x00061b, 0x00061b, 0x00061f, 0x00061f, 0x0007c0, 0x0007fa, 0x0007fd,
+    0x0007ff, 0x00fd3e, 0x00fd3f,
+];
+
+const CR_Scx_Ol_Onal: [u32; 6] = [0x000964, 0x000965, 0x01e5d0, 0x01e5fa, 0x01e5ff, 0x01e5ff];
+
+const CR_Scx_Old_Hungarian: [u32; 14] = [
+    0x00205a, 0x00205a, 0x00205d, 0x00205d, 0x002e31, 0x002e31, 0x002e41, 0x002e41, 0x010c80,
+    0x010cb2, 0x010cc0, 0x010cf2, 0x010cfa, 0x010cff,
+];
+
+const CR_Scx_Old_Permic: [u32; 12] = [
+    0x0000b7, 0x0000b7, 0x000300, 0x000300, 0x000306, 0x000308, 0x000313, 0x000313, 0x000483,
+    0x000483, 0x010350, 0x01037a,
+];
+
+const CR_Scx_Old_Turkic: [u32; 6] = [0x00205a, 0x00205a, 0x002e30, 0x002e30, 0x010c00, 0x010c48];
+
+const CR_Scx_Old_Uyghur: [u32; 6] = [0x000640, 0x000640, 0x010af2, 0x010af2, 0x010f70, 0x010f89];
+
+const CR_Scx_Oriya: [u32; 36] = [
+    0x000951, 0x000952, 0x000964, 0x000965, 0x000b01, 0x000b03, 0x000b05, 0x000b0c, 0x000b0f,
+    0x000b10, 0x000b13, 0x000b28, 0x000b2a, 0x000b30, 0x000b32, 0x000b33, 0x000b35, 0x000b39,
+    0x000b3c, 0x000b44, 0x000b47, 0x000b48, 0x000b4b, 0x000b4d, 0x000b55, 0x000b57, 0x000b5c,
+    0x000b5d, 0x000b5f, 0x000b63, 0x000b66, 0x000b77, 0x001cda, 0x001cda, 0x001cf2, 0x001cf2,
+];
+
+const CR_Scx_Osage: [u32; 12] = [
+    0x000301, 0x000301, 0x000304, 0x000304, 0x00030b, 0x00030b, 0x000358, 0x000358, 0x0104b0,
+    0x0104d3, 0x0104d8, 0x0104fb,
+];
+
+const CR_Scx_Phags_Pa: [u32; 10] = [
+    0x001802, 0x001803, 0x001805, 0x001805, 0x00202f, 0x00202f, 0x003002, 0x003002, 0x00a840,
+    0x00a877,
+];
+
+const CR_Scx_Psalter_Pahlavi: [u32; 8] = [
+    0x000640, 0x000640, 0x010b80, 0x010b91, 0x010b99, 0x010b9c, 0x010ba9, 0x010baf,
+];
+
+const CR_Scx_Runic: [u32; 2] = [0x0016a0, 0x0016f8];
+
+const CR_Scx_Samaritan: [u32; 6] = [0x000800, 0x00082d, 0x000830, 0x00083e, 0x002e31, 0x002e31];
+
+const CR_Scx_Sharada: [u32; 16] = [
+    0x000951, 0x000951, 0x001cd7, 0x001cd7, 0x001cd9, 0x001cd9, 0x001cdc, 0x001cdd, 0x001ce0,
+    0x001ce0, 0x00a830, 0x00a835, 0x00a838, 0x00a838, 0x011180, 0x0111df,
+];
+
+const CR_Scx_Shavian: [u32; 4] = [0x0000b7, 0x0000b7, 0x010450, 0x01047f];
+
+const CR_Scx_Sinhala: [u32; 30] = [
+    0x000964, 0x000965, 0x000d81, 0x000d83, 0x000d85, 0x000d96, 0x000d9a, 0x000db1, 0x000db3,
+    0x000dbb, 0x000dbd, 0x000dbd, 0x000dc0, 0x000dc6, 0x000dca, 0x000dca, 0x000dcf, 0x000dd4,
+    0x000dd6, 0x000dd6, 0x000dd8, 0x000ddf, 0x000de6, 0x000def, 0x000df2, 0x000df4, 0x001cf2,
+    0x001cf2, 0x0111e1, 0x0111f4,
+];
+
+const CR_Scx_Sogdian: [u32; 4] = [0x000640, 0x000640, 0x010f30, 0x010f59];
+
+const CR_Scx_Sunuwar: [u32; 16] = [
+    0x000300, 0x000301, 0x000303, 0x000303, 0x00030d, 0x00030d, 0x000310, 0x000310, 0x00032d,
+    0x00032d, 0x000331, 0x000331, 0x011bc0, 0x011be1, 0x011bf0, 0x011bf9,
+];
+
+const CR_Scx_Syloti_Nagri: [u32; 6] = [0x000964, 0x000965, 0x0009e6, 0x0009ef, 0x00a800, 0x00a82c];
+
+const CR_Scx_Syriac: [u32; 38] = [
+    0x000303, 0x000304, 0x000307, 0x000308, 0x00030a, 0x00030a, 0x000320, 0x000320, 0x000323,
+    0x000325, 0x00032d, 0x00032e, 0x000330, 0x000330, 0x00060c, 0x00060c, 0x00061b, 0x00061c,
+    0x00061f, 0x00061f, 0x000640, 0x000640, 0x00064b, 0x000655, 0x000670, 0x000670, 0x000700,
+    0x00070d, 0x00070f, 0x00074a, 0x00074d, 0x00074f, 0x000860, 0x00086a, 0x001df8, 0x001df8,
+    0x001dfa, 0x001dfa,
+];
+
+const CR_Scx_Tagalog: [u32; 6] = [0x001700, 0x001715, 0x00171f, 0x00171f, 0x001735, 0x001736];
+
+const CR_Scx_Tagbanwa: [u32; 8] = [
+    0x001735, 0x001736, 0x001760, 0x00176c, 0x00176e, 0x001770, 0x001772, 0x001773,
+];
+
+const CR_Scx_Tai_Le: [u32; 12] = [
+    0x000300, 0x000301, 0x000307, 0x000308, 0x00030c, 0x00030c, 0x001040, 0x001049, 0x001950,
+    0x00196d, 0x001970, 0x001974,
+];
+
+const CR_Scx_Takri: [u32; 8] = [
+    0x000964, 0x000965, 0x00a830, 0x00a839, 0x011680, 0x0116b9, 0x0116c0, 0x0116c9,
+];
+
+const CR_Scx_Tamil: [u32; 50] = [
+    0x000951, 0x000952, 0x000964, 0x000965, 0x000b82, 0x000b83, 0x000b85, 0x000b8a, 0x000b8e,
+    0x000b90, 0x000b92, 0x000b95, 0x000b99, 0x000b9a, 0x000b9c, 0x000b9c, 0x000b9e, 0x000b9f,
+    0x000ba3, 0x000ba4, 0x000ba8, 0x000baa, 0x000bae, 0x000bb9, 0x000bbe, 0x000bc2, 0x000bc6,
+    0x000bc8, 0x000bca, 0x000bcd, 0x000bd0, 0x000bd0, 0x000bd7, 0x000bd7, 0x000be6, 0x000bfa,
+    0x001cda, 0x001cda, 0x00a8f3, 0x00a8f3, 0x011301, 0x011301, 0x011303, 0x011303, 0x01133b,
+    0x01133c, 0x011fc0, 0x011ff1, 0x011fff, 0x011fff,
+];
+
+const CR_Scx_Tangut: [u32; 12] = [
+    0x002ff0, 0x002fff, 0x0031ef, 0x0031ef, 0x016fe0, 0x016fe0, 0x017000, 0x0187f7, 0x018800,
+    0x018aff, 0x018d00, 0x018d08,
+];
+
+const CR_Scx_Telugu: [u32; 34] = [
+    0x000951, 0x000952, 0x000964, 0x000965, 0x000c00, 0x000c0c, 0x000c0e, 0x000c10, 0x000c12,
+    0x000c28, 0x000c2a, 0x000c39, 0x000c3c, 0x000c44, 0x000c46, 0x000c48, 0x000c4a, 0x000c4d,
+    0x000c55, 0x000c56, 0x000c58, 0x000c5a, 0x000c5d, 0x000c5d, 0x000c60, 0x000c63, 0x000c66,
+    0x000c6f, 0x000c77, 0x000c7f, 0x001cda, 0x001cda, 0x001cf2, 0x001cf2,
+];
+
+const CR_Scx_Thaana: [u32; 14] = [
+    0x00060c, 0x00060c, 0x00061b, 0x00061c, 0x00061f, 0x00061f, 0x000660, 0x000669, 0x000780,
+    0x0007b1, 0x00fdf2, 0x00fdf2, 0x00fdfd, 0x00fdfd,
+];
+
+const CR_Scx_Thai: [u32; 12] = [
+    0x0002bc, 0x0002bc, 0x0002d7, 0x0002d7, 0x000303, 0x000303, 0x000331, 0x000331, 0x000e01,
+    0x000e3a, 0x000e40, 0x000e5b,
+];
+
+const CR_Scx_Tibetan: [u32; 16] = [
+    0x000f00, 0x000f47, 0x000f49, 0x000f6c, 0x000f71, 0x000f97, 0x000f99, 0x000fbc, 0x000fbe,
+    0x000fcc, 0x000fce, 0x000fd4, 0x000fd9, 0x000fda, 0x003008, 0x00300b,
+];
+
+const CR_Scx_Tifinagh: [u32; 14] = [
+    0x000302, 0x000302, 0x000304, 0x000304, 0x000307, 0x000307, 0x000309, 0x000309, 0x002d30,
+    0x002d67, 0x002d6f, 0x002d70, 0x002d7f, 0x002d7f,
+];
+
+const CR_Scx_Tirhuta: [u32; 12] = [
+    0x000951, 0x000952, 0x000964, 0x000965, 0x001cf2, 0x001cf2, 0x00a830, 0x00a839, 0x011480,
+    0x0114c7, 0x0114d0, 0x0114d9,
+];
+
+const CR_Scx_Todhri: [u32; 14] = [
+    0x000301, 0x000301, 0x000304, 0x000304, 0x000307, 0x000307, 0x000311, 0x000311, 0x000313,
+    0x000313, 0x00035e, 0x00035e, 0x0105c0, 0x0105f3,
+];
+
+const CR_Scx_Toto: [u32; 4] = [0x0002bc, 0x0002bc, 0x01e290, 0x01e2ae];
+
+const CR_Scx_Tulu_Tigalari: [u32; 32] = [
+    0x000ce6, 0x000cef, 0x001cf2, 0x001cf2, 0x001cf4, 0x001cf4, 0x00a830, 0x00a835, 0x00a8f1,
+    0x00a8f1, 0x011380, 0x011389, 0x01138b, 0x01138b, 0x01138e, 0x01138e, 0x011390, 0x0113b5,
+    0x0113b7, 0x0113c0, 0x0113c2, 0x0113c2, 0x0113c5, 0x0113c5, 0x0113c7, 0x0113ca, 0x0113cc,
+    0x0113d5, 0x0113d7, 0x0113d8, 0x0113e1, 0x0113e2,
+];
+
+const CR_Scx_Yezidi: [u32; 14] = [
+    0x00060c, 0x00060c, 0x00061b, 0x00061b, 0x00061f, 0x00061f, 0x000660, 0x000669, 0x010e80,
+    0x010ea9, 0x010eab, 0x010ead, 0x010eb0, 0x010eb1,
+];
+
+const CR_Scx_Yi: [u32; 14] = [
+    0x003001, 0x003002, 0x003008, 0x003011, 0x003014, 0x00301b, 0x0030fb, 0x0030fb, 0x00a000,
+    0x00a48c, 0x00a490, 0x00a4c6, 0x00ff61, 0x00ff65,
+];
+
 // --- Index: ctype -> code ranges ---
-pub static CODE_RANGES: [&[u32]; 629] = [
+#[cfg(not(feature = "compact-tables"))]
+pub static CODE_RANGES: [&[u32]; 729] = [
     &CR_NEWLINE,                                             // 0: CR_NEWLINE
     &CR_Alpha,                                               // 1: CR_Alpha
     &CR_Blank,                                               // 2: CR_Blank
@@ -7385,6 +7948,848 @@ pub static CODE_RANGES: [&[u32]; 629] = [
     &CR_In_Supplementary_Private_Use_Area_A,        // 626: CR_In_Supplementary_Private_Use_Area_A
     &CR_In_Supplementary_Private_Use_Area_B,        // 627: CR_In_Supplementary_Private_Use_Area_B
     &CR_In_No_Block,                                // 628: CR_In_No_Block
+    &CR_Scx_Adlam,                                  // 629: CR_Scx_Adlam
+    &CR_Scx_Arabic,                                 // 630: CR_Scx_Arabic
+    &CR_Scx_Armenian,                               // 631: CR_Scx_Armenian
+    &CR_Scx_Avestan,                                // 632: CR_Scx_Avestan
+    &CR_Scx_Bengali,                                // 633: CR_Scx_Bengali
+    &CR_Scx_Bopomofo,                               // 634: CR_Scx_Bopomofo
+    &CR_Scx_Buginese,                               // 635: CR_Scx_Buginese
+    &CR_Scx_Buhid,                                  // 636: CR_Scx_Buhid
+    &CR_Scx_Carian,                                 // 637: CR_Scx_Carian
+    &CR_Scx_Caucasian_Albanian,                     // 638: CR_Scx_Caucasian_Albanian
+    &CR_Scx_Chakma,                                 // 639: CR_Scx_Chakma
+    &CR_Scx_Cherokee,                               // 640: CR_Scx_Cherokee
+    &CR_Scx_Common,                                 // 641: CR_Scx_Common
+    &CR_Scx_Coptic,                                 // 642: CR_Scx_Coptic
+    &CR_Scx_Cypriot,                                // 643: CR_Scx_Cypriot
+    &CR_Scx_Cypro_Minoan,                           // 644: CR_Scx_Cypro_Minoan
+    &CR_Scx_Cyrillic,                               // 645: CR_Scx_Cyrillic
+    &CR_Scx_Devanagari,                             // 646: CR_Scx_Devanagari
+    &CR_Scx_Dogra,                                  // 647: CR_Scx_Dogra
+    &CR_Scx_Duployan,                               // 648: CR_Scx_Duployan
+    &CR_Scx_Elbasan,                                // 649: CR_Scx_Elbasan
+    &CR_Scx_Ethiopic,                               // 650: CR_Scx_Ethiopic
+    &CR_Scx_Garay,                                  // 651: CR_Scx_Garay
+    &CR_Scx_Georgian,                               // 652: CR_Scx_Georgian
+    &CR_Scx_Glagolitic,                             // 653: CR_Scx_Glagolitic
+    &CR_Scx_Gothic,                                 // 654: CR_Scx_Gothic
+    &CR_Scx_Grantha,                                // 655: CR_Scx_Grantha
+    &CR_Scx_Greek,                                  // 656: CR_Scx_Greek
+    &CR_Scx_Gujarati,                               // 657: CR_Scx_Gujarati
+    &CR_Scx_Gunjala_Gondi,                          // 658: CR_Scx_Gunjala_Gondi
+    &CR_Scx_Gurmukhi,                               // 659: CR_Scx_Gurmukhi
+    &CR_Scx_Gurung_Khema,                           // 660: CR_Scx_Gurung_Khema
+    &CR_Scx_Han,                                    // 661: CR_Scx_Han
+    &CR_Scx_Hangul,                                 // 662: CR_Scx_Hangul
+    &CR_Scx_Hanifi_Rohingya,                        // 663: CR_Scx_Hanifi_Rohingya
+    &CR_Scx_Hanunoo,                                // 664: CR_Scx_Hanunoo
+    &CR_Scx_Hebrew,                                 // 665: CR_Scx_Hebrew
+    &CR_Scx_Hiragana,                               // 666: CR_Scx_Hiragana
+    &CR_Scx_Inherited,                              // 667: CR_Scx_Inherited
+    &CR_Scx_Javanese,                               // 668: CR_Scx_Javanese
+    &CR_Scx_Kaithi,                                 // 669: CR_Scx_Kaithi
+    &CR_Scx_Kannada,                                // 670: CR_Scx_Kannada
+    &CR_Scx_Katakana,                               // 671: CR_Scx_Katakana
+    &CR_Scx_Kayah_Li,                               // 672: CR_Scx_Kayah_Li
+    &CR_Scx_Khojki,                                 // 673: CR_Scx_Khojki
+    &CR_Scx_Khudawadi,                              // 674: CR_Scx_Khudawadi
+    &CR_Scx_Latin,                                  // 675: CR_Scx_Latin
+    &CR_Scx_Limbu,                                  // 676: CR_Scx_Limbu
+    &CR_Scx_Linear_A,                               // 677: CR_Scx_Linear_A
+    &CR_Scx_Linear_B,                               // 678: CR_Scx_Linear_B
+    &CR_Scx_Lisu,                                   // 679: CR_Scx_Lisu
+    &CR_Scx_Lycian,                                 // 680: CR_Scx_Lycian
+    &CR_Scx_Lydian,                                 // 681: CR_Scx_Lydian
+    &CR_Scx_Mahajani,                               // 682: CR_Scx_Mahajani
+    &CR_Scx_Malayalam,                              // 683: CR_Scx_Malayalam
+    &CR_Scx_Mandaic,                                // 684: CR_Scx_Mandaic
+    &CR_Scx_Manichaean,                             // 685: CR_Scx_Manichaean
+    &CR_Scx_Masaram_Gondi,                          // 686: CR_Scx_Masaram_Gondi
+    &CR_Scx_Meroitic_Hieroglyphs,                   // 687: CR_Scx_Meroitic_Hieroglyphs
+    &CR_Scx_Modi,                                   // 688: CR_Scx_Modi
+    &CR_Scx_Mongolian,                              // 689: CR_Scx_Mongolian
+    &CR_Scx_Multani,                                // 690: CR_Scx_Multani
+    &CR_Scx_Myanmar,                                // 691: CR_Scx_Myanmar
+    &CR_Scx_Nandinagari,                            // 692: CR_Scx_Nandinagari
+    &CR_Scx_Nko,                                    // 693: CR_Scx_Nko
+    &CR_Scx_Ol_Onal,                                // 694: CR_Scx_Ol_Onal
+    &CR_Scx_Old_Hungarian,                          // 695: CR_Scx_Old_Hungarian
+    &CR_Scx_Old_Permic,                             // 696: CR_Scx_Old_Permic
+    &CR_Scx_Old_Turkic,                             // 697: CR_Scx_Old_Turkic
+    &CR_Scx_Old_Uyghur,                             // 698: CR_Scx_Old_Uyghur
+    &CR_Scx_Oriya,                                  // 699: CR_Scx_Oriya
+    &CR_Scx_Osage,                                  // 700: CR_Scx_Osage
+    &CR_Scx_Phags_Pa,                               // 701: CR_Scx_Phags_Pa
+    &CR_Scx_Psalter_Pahlavi,                        // 702: CR_Scx_Psalter_Pahlavi
+    &CR_Scx_Runic,                                  // 703: CR_Scx_Runic
+    &CR_Scx_Samaritan,                              // 704: CR_Scx_Samaritan
+    &CR_Scx_Sharada,                                // 705: CR_Scx_Sharada
+    &CR_Scx_Shavian,                                // 706: CR_Scx_Shavian
+    &CR_Scx_Sinhala,                                // 707: CR_Scx_Sinhala
+    &CR_Scx_Sogdian,                                // 708: CR_Scx_Sogdian
+    &CR_Scx_Sunuwar,                                // 709: CR_Scx_Sunuwar
+    &CR_Scx_Syloti_Nagri,                           // 710: CR_Scx_Syloti_Nagri
+    &CR_Scx_Syriac,                                 // 711: CR_Scx_Syriac
+    &CR_Scx_Tagalog,                                // 712: CR_Scx_Tagalog
+    &CR_Scx_Tagbanwa,                               // 713: CR_Scx_Tagbanwa
+    &CR_Scx_Tai_Le,                                 // 714: CR_Scx_Tai_Le
+    &CR_Scx_Takri,                                  // 715: CR_Scx_Takri
+    &CR_Scx_Tamil,                                  // 716: CR_Scx_Tamil
+    &CR_Scx_Tangut,                                 // 717: CR_Scx_Tangut
+    &CR_Scx_Telugu,                                 // 718: CR_Scx_Telugu
+    &CR_Scx_Thaana,                                 // 719: CR_Scx_Thaana
+    &CR_Scx_Thai,                                   // 720: CR_Scx_Thai
+    &CR_Scx_Tibetan,                                // 721: CR_Scx_Tibetan
+    &CR_Scx_Tifinagh,                               // 722: CR_Scx_Tifinagh
+    &CR_Scx_Tirhuta,                                // 723: CR_Scx_Tirhuta
+    &CR_Scx_Todhri,                                 // 724: CR_Scx_Todhri
+    &CR_Scx_Toto,                                   // 725: CR_Scx_Toto
+    &CR_Scx_Tulu_Tigalari,                          // 726: CR_Scx_Tulu_Tigalari
+    &CR_Scx_Yezidi,                                 // 727: CR_Scx_Yezidi
+    &CR_Scx_Yi,                                     // 728: CR_Scx_Yi
+];
+
+#[cfg(feature = "compact-tables")]
+macro_rules! packed {
+    ($cr:ident) => {{
+        const P: [u8; packed_len(&$cr)] = pack(&$cr);
+        &P
+    }};
+}
+
+// Same index, each array delta/varint packed (see unicode/pack.rs).
+#[cfg(feature = "compact-tables")]
+pub static CODE_RANGES_PACKED: [&[u8]; 729] = [
+    packed!(CR_NEWLINE),                                             // 0: CR_NEWLINE
+    packed!(CR_Alpha),                                               // 1: CR_Alpha
+    packed!(CR_Blank),                                               // 2: CR_Blank
+    packed!(CR_Cntrl),                                               // 3: CR_Cntrl
+    packed!(CR_Digit),                                               // 4: CR_Digit
+    packed!(CR_Graph),                                               // 5: CR_Graph
+    packed!(CR_Lower),                                               // 6: CR_Lower
+    packed!(CR_Print),                                               // 7: CR_Print
+    packed!(CR_PosixPunct),                                          // 8: CR_PosixPunct
+    packed!(CR_Space),                                               // 9: CR_Space
+    packed!(CR_Upper),                                               // 10: CR_Upper
+    packed!(CR_XDigit),                                              // 11: CR_XDigit
+    packed!(CR_Word),                                                // 12: CR_Word
+    packed!(CR_Alnum),                                               // 13: CR_Alnum
+    packed!(CR_ASCII),                                               // 14: CR_ASCII
+    packed!(CR_XDigit),                                              // 15: CR_ASCII_Hex_Digit
+    packed!(CR_Adlam),                                               // 16: CR_Adlam
+    packed!(CR_Ahom),                                                // 17: CR_Ahom
+    packed!(CR_Alpha),                                               // 18: CR_Alphabetic
+    packed!(CR_Anatolian_Hieroglyphs),                               // 19: CR_Anatolian_Hieroglyphs
+    packed!(CR_Any),                                                 // 20: CR_Any
+    packed!(CR_Arabic),                                              // 21: CR_Arabic
+    packed!(CR_Armenian),                                            // 22: CR_Armenian
+    packed!(CR_Assigned),                                            // 23: CR_Assigned
+    packed!(CR_Avestan),                                             // 24: CR_Avestan
+    packed!(CR_Balinese),                                            // 25: CR_Balinese
+    packed!(CR_Bamum),                                               // 26: CR_Bamum
+    packed!(CR_Bassa_Vah),                                           // 27: CR_Bassa_Vah
+    packed!(CR_Batak),                                               // 28: CR_Batak
+    packed!(CR_Bengali),                                             // 29: CR_Bengali
+    packed!(CR_Bhaiksuki),                                           // 30: CR_Bhaiksuki
+    packed!(CR_Bidi_Control),                                        // 31: CR_Bidi_Control
+    packed!(CR_Bopomofo),                                            // 32: CR_Bopomofo
+    packed!(CR_Brahmi),                                              // 33: CR_Brahmi
+    packed!(CR_Braille),                                             // 34: CR_Braille
+    packed!(CR_Buginese),                                            // 35: CR_Buginese
+    packed!(CR_Buhid),                                               // 36: CR_Buhid
+    packed!(CR_C),                                                   // 37: CR_C
+    packed!(CR_Canadian_Aboriginal),                                 // 38: CR_Canadian_Aboriginal
+    packed!(CR_Carian),                                              // 39: CR_Carian
+    packed!(CR_Case_Ignorable),                                      // 40: CR_Case_Ignorable
+    packed!(CR_Cased),                                               // 41: CR_Cased
+    packed!(CR_Caucasian_Albanian),                                  // 42: CR_Caucasian_Albanian
+    packed!(CR_Cntrl),                                               // 43: CR_Cc
+    packed!(CR_Cf),                                                  // 44: CR_Cf
+    packed!(CR_Chakma),                                              // 45: CR_Chakma
+    packed!(CR_Cham),                                                // 46: CR_Cham
+    packed!(CR_Changes_When_Casefolded), // 47: CR_Changes_When_Casefolded
+    packed!(CR_Changes_When_Casemapped), // 48: CR_Changes_When_Casemapped
+    packed!(CR_Changes_When_Lowercased), // 49: CR_Changes_When_Lowercased
+    packed!(CR_Changes_When_Titlecased), // 50: CR_Changes_When_Titlecased
+    packed!(CR_Changes_When_Uppercased), // 51: CR_Changes_When_Uppercased
+    packed!(CR_Cherokee),                // 52: CR_Cherokee
+    packed!(CR_Chorasmian),              // 53: CR_Chorasmian
+    packed!(CR_Cn),                      // 54: CR_Cn
+    packed!(CR_Co),                      // 55: CR_Co
+    packed!(CR_Common),                  // 56: CR_Common
+    packed!(CR_Coptic),                  // 57: CR_Coptic
+    packed!(CR_Cs),                      // 58: CR_Cs
+    packed!(CR_Cuneiform),               // 59: CR_Cuneiform
+    packed!(CR_Cypriot),                 // 60: CR_Cypriot
+    packed!(CR_Cypro_Minoan),            // 61: CR_Cypro_Minoan
+    packed!(CR_Cyrillic),                // 62: CR_Cyrillic
+    packed!(CR_Dash),                    // 63: CR_Dash
+    packed!(CR_Default_Ignorable_Code_Point), // 64: CR_Default_Ignorable_Code_Point
+    packed!(CR_Deprecated),              // 65: CR_Deprecated
+    packed!(CR_Deseret),                 // 66: CR_Deseret
+    packed!(CR_Devanagari),              // 67: CR_Devanagari
+    packed!(CR_Diacritic),               // 68: CR_Diacritic
+    packed!(CR_Dives_Akuru),             // 69: CR_Dives_Akuru
+    packed!(CR_Dogra),                   // 70: CR_Dogra
+    packed!(CR_Duployan),                // 71: CR_Duployan
+    packed!(CR_Egyptian_Hieroglyphs),    // 72: CR_Egyptian_Hieroglyphs
+    packed!(CR_Elbasan),                 // 73: CR_Elbasan
+    packed!(CR_Elymaic),                 // 74: CR_Elymaic
+    packed!(CR_Emoji),                   // 75: CR_Emoji
+    packed!(CR_Emoji_Component),         // 76: CR_Emoji_Component
+    packed!(CR_Emoji_Modifier),          // 77: CR_Emoji_Modifier
+    packed!(CR_Emoji_Modifier_Base),     // 78: CR_Emoji_Modifier_Base
+    packed!(CR_Emoji_Presentation),      // 79: CR_Emoji_Presentation
+    packed!(CR_Ethiopic),                // 80: CR_Ethiopic
+    packed!(CR_Extended_Pictographic),   // 81: CR_Extended_Pictographic
+    packed!(CR_Extender),                // 82: CR_Extender
+    packed!(CR_Garay),                   // 83: CR_Garay
+    packed!(CR_Georgian),                // 84: CR_Georgian
+    packed!(CR_Glagolitic),              // 85: CR_Glagolitic
+    packed!(CR_Gothic),                  // 86: CR_Gothic
+    packed!(CR_Grantha),                 // 87: CR_Grantha
+    packed!(CR_Grapheme_Base),           // 88: CR_Grapheme_Base
+    packed!(CR_Grapheme_Extend),         // 89: CR_Grapheme_Extend
+    packed!(CR_Grapheme_Link),           // 90: CR_Grapheme_Link
+    packed!(CR_Greek),                   // 91: CR_Greek
+    packed!(CR_Gujarati),                // 92: CR_Gujarati
+    packed!(CR_Gunjala_Gondi),           // 93: CR_Gunjala_Gondi
+    packed!(CR_Gurmukhi),                // 94: CR_Gurmukhi
+    packed!(CR_Gurung_Khema),            // 95: CR_Gurung_Khema
+    packed!(CR_Han),                     // 96: CR_Han
+    packed!(CR_Hangul),                  // 97: CR_Hangul
+    packed!(CR_Hanifi_Rohingya),         // 98: CR_Hanifi_Rohingya
+    packed!(CR_Hanunoo),                 // 99: CR_Hanunoo
+    packed!(CR_Hatran),                  // 100: CR_Hatran
+    packed!(CR_Hebrew),                  // 101: CR_Hebrew
+    packed!(CR_Hex_Digit),               // 102: CR_Hex_Digit
+    packed!(CR_Hiragana),                // 103: CR_Hiragana
+    packed!(CR_Hyphen),                  // 104: CR_Hyphen
+    packed!(CR_IDS_Binary_Operator),     // 105: CR_IDS_Binary_Operator
+    packed!(CR_IDS_Trinary_Operator),    // 106: CR_IDS_Trinary_Operator
+    packed!(CR_IDS_Unary_Operator),      // 107: CR_IDS_Unary_Operator
+    packed!(CR_ID_Compat_Math_Continue), // 108: CR_ID_Compat_Math_Continue
+    packed!(CR_ID_Compat_Math_Start),    // 109: CR_ID_Compat_Math_Start
+    packed!(CR_ID_Continue),             // 110: CR_ID_Continue
+    packed!(CR_ID_Start),                // 111: CR_ID_Start
+    packed!(CR_Ideographic),             // 112: CR_Ideographic
+    packed!(CR_Imperial_Aramaic),        // 113: CR_Imperial_Aramaic
+    packed!(CR_InCB),                    // 114: CR_InCB
+    packed!(CR_Inherited),               // 115: CR_Inherited
+    packed!(CR_Inscriptional_Pahlavi),   // 116: CR_Inscriptional_Pahlavi
+    packed!(CR_Inscriptional_Parthian),  // 117: CR_Inscriptional_Parthian
+    packed!(CR_Javanese),                // 118: CR_Javanese
+    packed!(CR_Join_Control),            // 119: CR_Join_Control
+    packed!(CR_Kaithi),                  // 120: CR_Kaithi
+    packed!(CR_Kannada),                 // 121: CR_Kannada
+    packed!(CR_Katakana),                // 122: CR_Katakana
+    packed!(CR_Kawi),                    // 123: CR_Kawi
+    packed!(CR_Kayah_Li),                // 124: CR_Kayah_Li
+    packed!(CR_Kharoshthi),              // 125: CR_Kharoshthi
+    packed!(CR_Khitan_Small_Script),     // 126: CR_Khitan_Small_Script
+    packed!(CR_Khmer),                   // 127: CR_Khmer
+    packed!(CR_Khojki),                  // 128: CR_Khojki
+    packed!(CR_Khudawadi),               // 129: CR_Khudawadi
+    packed!(CR_Kirat_Rai),               // 130: CR_Kirat_Rai
+    packed!(CR_L),                       // 131: CR_L
+    packed!(CR_LC),                      // 132: CR_LC
+    packed!(CR_Lao),                     // 133: CR_Lao
+    packed!(CR_Latin),                   // 134: CR_Latin
+    packed!(CR_Lepcha),                  // 135: CR_Lepcha
+    packed!(CR_Limbu),                   // 136: CR_Limbu
+    packed!(CR_Linear_A),                // 137: CR_Linear_A
+    packed!(CR_Linear_B),                // 138: CR_Linear_B
+    packed!(CR_Lisu),                    // 139: CR_Lisu
+    packed!(CR_Ll),                      // 140: CR_Ll
+    packed!(CR_Lm),                      // 141: CR_Lm
+    packed!(CR_Lo),                      // 142: CR_Lo
+    packed!(CR_Logical_Order_Exception), // 143: CR_Logical_Order_Exception
+    packed!(CR_Lower),                   // 144: CR_Lowercase
+    packed!(CR_Lt),                      // 145: CR_Lt
+    packed!(CR_Lu),                      // 146: CR_Lu
+    packed!(CR_Lycian),                  // 147: CR_Lycian
+    packed!(CR_Lydian),                  // 148: CR_Lydian
+    packed!(CR_M),                       // 149: CR_M
+    packed!(CR_Mahajani),                // 150: CR_Mahajani
+    packed!(CR_Makasar),                 // 151: CR_Makasar
+    packed!(CR_Malayalam),               // 152: CR_Malayalam
+    packed!(CR_Mandaic),                 // 153: CR_Mandaic
+    packed!(CR_Manichaean),              // 154: CR_Manichaean
+    packed!(CR_Marchen),                 // 155: CR_Marchen
+    packed!(CR_Masaram_Gondi),           // 156: CR_Masaram_Gondi
+    packed!(CR_Math),                    // 157: CR_Math
+    packed!(CR_Mc),                      // 158: CR_Mc
+    packed!(CR_Me),                      // 159: CR_Me
+    packed!(CR_Medefaidrin),             // 160: CR_Medefaidrin
+    packed!(CR_Meetei_Mayek),            // 161: CR_Meetei_Mayek
+    packed!(CR_Mende_Kikakui),           // 162: CR_Mende_Kikakui
+    packed!(CR_Meroitic_Cursive),        // 163: CR_Meroitic_Cursive
+    packed!(CR_Meroitic_Hieroglyphs),    // 164: CR_Meroitic_Hieroglyphs
+    packed!(CR_Miao),                    // 165: CR_Miao
+    packed!(CR_Mn),                      // 166: CR_Mn
+    packed!(CR_Modi),                    // 167: CR_Modi
+    packed!(CR_Modifier_Combining_Mark), // 168: CR_Modifier_Combining_Mark
+    packed!(CR_Mongolian),               // 169: CR_Mongolian
+    packed!(CR_Mro),                     // 170: CR_Mro
+    packed!(CR_Multani),                 // 171: CR_Multani
+    packed!(CR_Myanmar),                 // 172: CR_Myanmar
+    packed!(CR_N),                       // 173: CR_N
+    packed!(CR_Nabataean),               // 174: CR_Nabataean
+    packed!(CR_Nag_Mundari),             // 175: CR_Nag_Mundari
+    packed!(CR_Nandinagari),             // 176: CR_Nandinagari
+    packed!(CR_Digit),                   // 177: CR_Nd
+    packed!(CR_New_Tai_Lue),             // 178: CR_New_Tai_Lue
+    packed!(CR_Newa),                    // 179: CR_Newa
+    packed!(CR_Nko),                     // 180: CR_Nko
+    packed!(CR_Nl),                      // 181: CR_Nl
+    packed!(CR_No),                      // 182: CR_No
+    packed!(CR_Noncharacter_Code_Point), // 183: CR_Noncharacter_Code_Point
+    packed!(CR_Nushu),                   // 184: CR_Nushu
+    packed!(CR_Nyiakeng_Puachue_Hmong),  // 185: CR_Nyiakeng_Puachue_Hmong
+    packed!(CR_Ogham),                   // 186: CR_Ogham
+    packed!(CR_Ol_Chiki),                // 187: CR_Ol_Chiki
+    packed!(CR_Ol_Onal),                 // 188: CR_Ol_Onal
+    packed!(CR_Old_Hungarian),           // 189: CR_Old_Hungarian
+    packed!(CR_Old_Italic),              // 190: CR_Old_Italic
+    packed!(CR_Old_North_Arabian),       // 191: CR_Old_North_Arabian
+    packed!(CR_Old_Permic),              // 192: CR_Old_Permic
+    packed!(CR_Old_Persian),             // 193: CR_Old_Persian
+    packed!(CR_Old_Sogdian),             // 194: CR_Old_Sogdian
+    packed!(CR_Old_South_Arabian),       // 195: CR_Old_South_Arabian
+    packed!(CR_Old_Turkic),              // 196: CR_Old_Turkic
+    packed!(CR_Old_Uyghur),              // 197: CR_Old_Uyghur
+    packed!(CR_Oriya),                   // 198: CR_Oriya
+    packed!(CR_Osage),                   // 199: CR_Osage
+    packed!(CR_Osmanya),                 // 200: CR_Osmanya
+    packed!(CR_Other_Alphabetic),        // 201: CR_Other_Alphabetic
+    packed!(CR_Other_Default_Ignorable_Code_Point), // 202: CR_Other_Default_Ignorable_Code_Point
+    packed!(CR_Other_Grapheme_Extend),   // 203: CR_Other_Grapheme_Extend
+    packed!(CR_Other_ID_Continue),       // 204: CR_Other_ID_Continue
+    packed!(CR_Other_ID_Start),          // 205: CR_Other_ID_Start
+    packed!(CR_Other_Lowercase),         // 206: CR_Other_Lowercase
+    packed!(CR_Other_Math),              // 207: CR_Other_Math
+    packed!(CR_Other_Uppercase),         // 208: CR_Other_Uppercase
+    packed!(CR_P),                       // 209: CR_P
+    packed!(CR_Pahawh_Hmong),            // 210: CR_Pahawh_Hmong
+    packed!(CR_Palmyrene),               // 211: CR_Palmyrene
+    packed!(CR_Pattern_Syntax),          // 212: CR_Pattern_Syntax
+    packed!(CR_Pattern_White_Space),     // 213: CR_Pattern_White_Space
+    packed!(CR_Pau_Cin_Hau),             // 214: CR_Pau_Cin_Hau
+    packed!(CR_Pc),                      // 215: CR_Pc
+    packed!(CR_Pd),                      // 216: CR_Pd
+    packed!(CR_Pe),                      // 217: CR_Pe
+    packed!(CR_Pf),                      // 218: CR_Pf
+    packed!(CR_Phags_Pa),                // 219: CR_Phags_Pa
+    packed!(CR_Phoenician),              // 220: CR_Phoenician
+    packed!(CR_Pi),                      // 221: CR_Pi
+    packed!(CR_Po),                      // 222: CR_Po
+    packed!(CR_Prepended_Concatenation_Mark), // 223: CR_Prepended_Concatenation_Mark
+    packed!(CR_Ps),                      // 224: CR_Ps
+    packed!(CR_Psalter_Pahlavi),         // 225: CR_Psalter_Pahlavi
+    packed!(CR_Quotation_Mark),          // 226: CR_Quotation_Mark
+    packed!(CR_Radical),                 // 227: CR_Radical
+    packed!(CR_Regional_Indicator),      // 228: CR_Regional_Indicator
+    packed!(CR_Rejang),                  // 229: CR_Rejang
+    packed!(CR_Runic),                   // 230: CR_Runic
+    packed!(CR_S),                       // 231: CR_S
+    packed!(CR_Samaritan),               // 232: CR_Samaritan
+    packed!(CR_Saurashtra),              // 233: CR_Saurashtra
+    packed!(CR_Sc),                      // 234: CR_Sc
+    packed!(CR_Sentence_Terminal),       // 235: CR_Sentence_Terminal
+    packed!(CR_Sharada),                 // 236: CR_Sharada
+    packed!(CR_Shavian),                 // 237: CR_Shavian
+    packed!(CR_Siddham),                 // 238: CR_Siddham
+    packed!(CR_SignWriting),             // 239: CR_SignWriting
+    packed!(CR_Sinhala),                 // 240: CR_Sinhala
+    packed!(CR_Sk),                      // 241: CR_Sk
+    packed!(CR_Sm),                      // 242: CR_Sm
+    packed!(CR_So),                      // 243: CR_So
+    packed!(CR_Soft_Dotted),             // 244: CR_Soft_Dotted
+    packed!(CR_Sogdian),                 // 245: CR_Sogdian
+    packed!(CR_Sora_Sompeng),            // 246: CR_Sora_Sompeng
+    packed!(CR_Soyombo),                 // 247: CR_Soyombo
+    packed!(CR_Sundanese),               // 248: CR_Sundanese
+    packed!(CR_Sunuwar),                 // 249: CR_Sunuwar
+    packed!(CR_Syloti_Nagri),            // 250: CR_Syloti_Nagri
+    packed!(CR_Syriac),                  // 251: CR_Syriac
+    packed!(CR_Tagalog),                 // 252: CR_Tagalog
+    packed!(CR_Tagbanwa),                // 253: CR_Tagbanwa
+    packed!(CR_Tai_Le),                  // 254: CR_Tai_Le
+    packed!(CR_Tai_Tham),                // 255: CR_Tai_Tham
+    packed!(CR_Tai_Viet),                // 256: CR_Tai_Viet
+    packed!(CR_Takri),                   // 257: CR_Takri
+    packed!(CR_Tamil),                   // 258: CR_Tamil
+    packed!(CR_Tangsa),                  // 259: CR_Tangsa
+    packed!(CR_Tangut),                  // 260: CR_Tangut
+    packed!(CR_Telugu),                  // 261: CR_Telugu
+    packed!(CR_Terminal_Punctuation),    // 262: CR_Terminal_Punctuation
+    packed!(CR_Thaana),                  // 263: CR_Thaana
+    packed!(CR_Thai),                    // 264: CR_Thai
+    packed!(CR_Tibetan),                 // 265: CR_Tibetan
+    packed!(CR_Tifinagh),                // 266: CR_Tifinagh
+    packed!(CR_Tirhuta),                 // 267: CR_Tirhuta
+    packed!(CR_Todhri),                  // 268: CR_Todhri
+    packed!(CR_Toto),                    // 269: CR_Toto
+    packed!(CR_Tulu_Tigalari),           // 270: CR_Tulu_Tigalari
+    packed!(CR_Ugaritic),                // 271: CR_Ugaritic
+    packed!(CR_Unified_Ideograph),       // 272: CR_Unified_Ideograph
+    packed!(CR_Unknown),                 // 273: CR_Unknown
+    packed!(CR_Upper),                   // 274: CR_Uppercase
+    packed!(CR_Vai),                     // 275: CR_Vai
+    packed!(CR_Variation_Selector),      // 276: CR_Variation_Selector
+    packed!(CR_Vithkuqi),                // 277: CR_Vithkuqi
+    packed!(CR_Wancho),                  // 278: CR_Wancho
+    packed!(CR_Warang_Citi),             // 279: CR_Warang_Citi
+    packed!(CR_Space),                   // 280: CR_White_Space
+    packed!(CR_XID_Continue),            // 281: CR_XID_Continue
+    packed!(CR_XID_Start),               // 282: CR_XID_Start
+    packed!(CR_Yezidi),                  // 283: CR_Yezidi
+    packed!(CR_Yi),                      // 284: CR_Yi
+    packed!(CR_Z),                       // 285: CR_Z
+    packed!(CR_Zanabazar_Square),        // 286: CR_Zanabazar_Square
+    packed!(CR_Zl),                      // 287: CR_Zl
+    packed!(CR_Zp),                      // 288: CR_Zp
+    packed!(CR_Zs),                      // 289: CR_Zs
+    packed!(CR_ASCII),                   // 290: CR_In_Basic_Latin
+    packed!(CR_In_Latin_1_Supplement),   // 291: CR_In_Latin_1_Supplement
+    packed!(CR_In_Latin_Extended_A),     // 292: CR_In_Latin_Extended_A
+    packed!(CR_In_Latin_Extended_B),     // 293: CR_In_Latin_Extended_B
+    packed!(CR_In_IPA_Extensions),       // 294: CR_In_IPA_Extensions
+    packed!(CR_In_Spacing_Modifier_Letters), // 295: CR_In_Spacing_Modifier_Letters
+    packed!(CR_In_Combining_Diacritical_Marks), // 296: CR_In_Combining_Diacritical_Marks
+    packed!(CR_In_Greek_and_Coptic),     // 297: CR_In_Greek_and_Coptic
+    packed!(CR_In_Cyrillic),             // 298: CR_In_Cyrillic
+    packed!(CR_In_Cyrillic_Supplement),  // 299: CR_In_Cyrillic_Supplement
+    packed!(CR_In_Armenian),             // 300: CR_In_Armenian
+    packed!(CR_In_Hebrew),               // 301: CR_In_Hebrew
+    packed!(CR_In_Arabic),               // 302: CR_In_Arabic
+    packed!(CR_In_Syriac),               // 303: CR_In_Syriac
+    packed!(CR_In_Arabic_Supplement),    // 304: CR_In_Arabic_Supplement
+    packed!(CR_In_Thaana),               // 305: CR_In_Thaana
+    packed!(CR_In_NKo),                  // 306: CR_In_NKo
+    packed!(CR_In_Samaritan),            // 307: CR_In_Samaritan
+    packed!(CR_In_Mandaic),              // 308: CR_In_Mandaic
+    packed!(CR_In_Syriac_Supplement),    // 309: CR_In_Syriac_Supplement
+    packed!(CR_In_Arabic_Extended_B),    // 310: CR_In_Arabic_Extended_B
+    packed!(CR_In_Arabic_Extended_A),    // 311: CR_In_Arabic_Extended_A
+    packed!(CR_In_Devanagari),           // 312: CR_In_Devanagari
+    packed!(CR_In_Bengali),              // 313: CR_In_Bengali
+    packed!(CR_In_Gurmukhi),             // 314: CR_In_Gurmukhi
+    packed!(CR_In_Gujarati),             // 315: CR_In_Gujarati
+    packed!(CR_In_Oriya),                // 316: CR_In_Oriya
+    packed!(CR_In_Tamil),                // 317: CR_In_Tamil
+    packed!(CR_In_Telugu),               // 318: CR_In_Telugu
+    packed!(CR_In_Kannada),              // 319: CR_In_Kannada
+    packed!(CR_In_Malayalam),            // 320: CR_In_Malayalam
+    packed!(CR_In_Sinhala),              // 321: CR_In_Sinhala
+    packed!(CR_In_Thai),                 // 322: CR_In_Thai
+    packed!(CR_In_Lao),                  // 323: CR_In_Lao
+    packed!(CR_In_Tibetan),              // 324: CR_In_Tibetan
+    packed!(CR_In_Myanmar),              // 325: CR_In_Myanmar
+    packed!(CR_In_Georgian),             // 326: CR_In_Georgian
+    packed!(CR_In_Hangul_Jamo),          // 327: CR_In_Hangul_Jamo
+    packed!(CR_In_Ethiopic),             // 328: CR_In_Ethiopic
+    packed!(CR_In_Ethiopic_Supplement),  // 329: CR_In_Ethiopic_Supplement
+    packed!(CR_In_Cherokee),             // 330: CR_In_Cherokee
+    packed!(CR_In_Unified_Canadian_Aboriginal_Syllabics), // 331: CR_In_Unified_Canadian_Aboriginal_Syllabics
+    packed!(CR_In_Ogham),                                 // 332: CR_In_Ogham
+    packed!(CR_In_Runic),                                 // 333: CR_In_Runic
+    packed!(CR_In_Tagalog),                               // 334: CR_In_Tagalog
+    packed!(CR_In_Hanunoo),                               // 335: CR_In_Hanunoo
+    packed!(CR_In_Buhid),                                 // 336: CR_In_Buhid
+    packed!(CR_In_Tagbanwa),                              // 337: CR_In_Tagbanwa
+    packed!(CR_In_Khmer),                                 // 338: CR_In_Khmer
+    packed!(CR_In_Mongolian),                             // 339: CR_In_Mongolian
+    packed!(CR_In_Unified_Canadian_Aboriginal_Syllabics_Extended), // 340: CR_In_Unified_Canadian_Aboriginal_Syllabics_Extended
+    packed!(CR_In_Limbu),                                          // 341: CR_In_Limbu
+    packed!(CR_In_Tai_Le),                                         // 342: CR_In_Tai_Le
+    packed!(CR_In_New_Tai_Lue),                                    // 343: CR_In_New_Tai_Lue
+    packed!(CR_In_Khmer_Symbols),                                  // 344: CR_In_Khmer_Symbols
+    packed!(CR_In_Buginese),                                       // 345: CR_In_Buginese
+    packed!(CR_In_Tai_Tham),                                       // 346: CR_In_Tai_Tham
+    packed!(CR_In_Combining_Diacritical_Marks_Extended), // 347: CR_In_Combining_Diacritical_Marks_Extended
+    packed!(CR_In_Balinese),                             // 348: CR_In_Balinese
+    packed!(CR_In_Sundanese),                            // 349: CR_In_Sundanese
+    packed!(CR_In_Batak),                                // 350: CR_In_Batak
+    packed!(CR_In_Lepcha),                               // 351: CR_In_Lepcha
+    packed!(CR_Ol_Chiki),                                // 352: CR_In_Ol_Chiki
+    packed!(CR_In_Cyrillic_Extended_C),                  // 353: CR_In_Cyrillic_Extended_C
+    packed!(CR_In_Georgian_Extended),                    // 354: CR_In_Georgian_Extended
+    packed!(CR_In_Sundanese_Supplement),                 // 355: CR_In_Sundanese_Supplement
+    packed!(CR_In_Vedic_Extensions),                     // 356: CR_In_Vedic_Extensions
+    packed!(CR_In_Phonetic_Extensions),                  // 357: CR_In_Phonetic_Extensions
+    packed!(CR_In_Phonetic_Extensions_Supplement), // 358: CR_In_Phonetic_Extensions_Supplement
+    packed!(CR_In_Combining_Diacritical_Marks_Supplement), // 359: CR_In_Combining_Diacritical_Marks_Supplement
+    packed!(CR_In_Latin_Extended_Additional),              // 360: CR_In_Latin_Extended_Additional
+    packed!(CR_In_Greek_Extended),                         // 361: CR_In_Greek_Extended
+    packed!(CR_In_General_Punctuation),                    // 362: CR_In_General_Punctuation
+    packed!(CR_In_Superscripts_and_Subscripts),            // 363: CR_In_Superscripts_and_Subscripts
+    packed!(CR_In_Currency_Symbols),                       // 364: CR_In_Currency_Symbols
+    packed!(CR_In_Combining_Diacritical_Marks_for_Symbols), // 365: CR_In_Combining_Diacritical_Marks_for_Symbols
+    packed!(CR_In_Letterlike_Symbols),                      // 366: CR_In_Letterlike_Symbols
+    packed!(CR_In_Number_Forms),                            // 367: CR_In_Number_Forms
+    packed!(CR_In_Arrows),                                  // 368: CR_In_Arrows
+    packed!(CR_In_Mathematical_Operators),                  // 369: CR_In_Mathematical_Operators
+    packed!(CR_In_Miscellaneous_Technical),                 // 370: CR_In_Miscellaneous_Technical
+    packed!(CR_In_Control_Pictures),                        // 371: CR_In_Control_Pictures
+    packed!(CR_In_Optical_Character_Recognition), // 372: CR_In_Optical_Character_Recognition
+    packed!(CR_In_Enclosed_Alphanumerics),        // 373: CR_In_Enclosed_Alphanumerics
+    packed!(CR_In_Box_Drawing),                   // 374: CR_In_Box_Drawing
+    packed!(CR_In_Block_Elements),                // 375: CR_In_Block_Elements
+    packed!(CR_In_Geometric_Shapes),              // 376: CR_In_Geometric_Shapes
+    packed!(CR_In_Miscellaneous_Symbols),         // 377: CR_In_Miscellaneous_Symbols
+    packed!(CR_In_Dingbats),                      // 378: CR_In_Dingbats
+    packed!(CR_In_Miscellaneous_Mathematical_Symbols_A), // 379: CR_In_Miscellaneous_Mathematical_Symbols_A
+    packed!(CR_In_Supplemental_Arrows_A),                // 380: CR_In_Supplemental_Arrows_A
+    packed!(CR_Braille),                                 // 381: CR_In_Braille_Patterns
+    packed!(CR_In_Supplemental_Arrows_B),                // 382: CR_In_Supplemental_Arrows_B
+    packed!(CR_In_Miscellaneous_Mathematical_Symbols_B), // 383: CR_In_Miscellaneous_Mathematical_Symbols_B
+    packed!(CR_In_Supplemental_Mathematical_Operators), // 384: CR_In_Supplemental_Mathematical_Operators
+    packed!(CR_In_Miscellaneous_Symbols_and_Arrows), // 385: CR_In_Miscellaneous_Symbols_and_Arrows
+    packed!(CR_In_Glagolitic),                       // 386: CR_In_Glagolitic
+    packed!(CR_In_Latin_Extended_C),                 // 387: CR_In_Latin_Extended_C
+    packed!(CR_In_Coptic),                           // 388: CR_In_Coptic
+    packed!(CR_In_Georgian_Supplement),              // 389: CR_In_Georgian_Supplement
+    packed!(CR_In_Tifinagh),                         // 390: CR_In_Tifinagh
+    packed!(CR_In_Ethiopic_Extended),                // 391: CR_In_Ethiopic_Extended
+    packed!(CR_In_Cyrillic_Extended_A),              // 392: CR_In_Cyrillic_Extended_A
+    packed!(CR_In_Supplemental_Punctuation),         // 393: CR_In_Supplemental_Punctuation
+    packed!(CR_In_CJK_Radicals_Supplement),          // 394: CR_In_CJK_Radicals_Supplement
+    packed!(CR_In_Kangxi_Radicals),                  // 395: CR_In_Kangxi_Radicals
+    packed!(CR_In_Ideographic_Description_Characters), // 396: CR_In_Ideographic_Description_Characters
+    packed!(CR_In_CJK_Symbols_and_Punctuation),        // 397: CR_In_CJK_Symbols_and_Punctuation
+    packed!(CR_In_Hiragana),                           // 398: CR_In_Hiragana
+    packed!(CR_In_Katakana),                           // 399: CR_In_Katakana
+    packed!(CR_In_Bopomofo),                           // 400: CR_In_Bopomofo
+    packed!(CR_In_Hangul_Compatibility_Jamo),          // 401: CR_In_Hangul_Compatibility_Jamo
+    packed!(CR_In_Kanbun),                             // 402: CR_In_Kanbun
+    packed!(CR_In_Bopomofo_Extended),                  // 403: CR_In_Bopomofo_Extended
+    packed!(CR_In_CJK_Strokes),                        // 404: CR_In_CJK_Strokes
+    packed!(CR_In_Katakana_Phonetic_Extensions),       // 405: CR_In_Katakana_Phonetic_Extensions
+    packed!(CR_In_Enclosed_CJK_Letters_and_Months),    // 406: CR_In_Enclosed_CJK_Letters_and_Months
+    packed!(CR_In_CJK_Compatibility),                  // 407: CR_In_CJK_Compatibility
+    packed!(CR_In_CJK_Unified_Ideographs_Extension_A), // 408: CR_In_CJK_Unified_Ideographs_Extension_A
+    packed!(CR_In_Yijing_Hexagram_Symbols),            // 409: CR_In_Yijing_Hexagram_Symbols
+    packed!(CR_In_CJK_Unified_Ideographs),             // 410: CR_In_CJK_Unified_Ideographs
+    packed!(CR_In_Yi_Syllables),                       // 411: CR_In_Yi_Syllables
+    packed!(CR_In_Yi_Radicals),                        // 412: CR_In_Yi_Radicals
+    packed!(CR_In_Lisu),                               // 413: CR_In_Lisu
+    packed!(CR_In_Vai),                                // 414: CR_In_Vai
+    packed!(CR_In_Cyrillic_Extended_B),                // 415: CR_In_Cyrillic_Extended_B
+    packed!(CR_In_Bamum),                              // 416: CR_In_Bamum
+    packed!(CR_In_Modifier_Tone_Letters),              // 417: CR_In_Modifier_Tone_Letters
+    packed!(CR_In_Latin_Extended_D),                   // 418: CR_In_Latin_Extended_D
+    packed!(CR_In_Syloti_Nagri),                       // 419: CR_In_Syloti_Nagri
+    packed!(CR_In_Common_Indic_Number_Forms),          // 420: CR_In_Common_Indic_Number_Forms
+    packed!(CR_In_Phags_pa),                           // 421: CR_In_Phags_pa
+    packed!(CR_In_Saurashtra),                         // 422: CR_In_Saurashtra
+    packed!(CR_In_Devanagari_Extended),                // 423: CR_In_Devanagari_Extended
+    packed!(CR_In_Kayah_Li),                           // 424: CR_In_Kayah_Li
+    packed!(CR_In_Rejang),                             // 425: CR_In_Rejang
+    packed!(CR_In_Hangul_Jamo_Extended_A),             // 426: CR_In_Hangul_Jamo_Extended_A
+    packed!(CR_In_Javanese),                           // 427: CR_In_Javanese
+    packed!(CR_In_Myanmar_Extended_B),                 // 428: CR_In_Myanmar_Extended_B
+    packed!(CR_In_Cham),                               // 429: CR_In_Cham
+    packed!(CR_In_Myanmar_Extended_A),                 // 430: CR_In_Myanmar_Extended_A
+    packed!(CR_In_Tai_Viet),                           // 431: CR_In_Tai_Viet
+    packed!(CR_In_Meetei_Mayek_Extensions),            // 432: CR_In_Meetei_Mayek_Extensions
+    packed!(CR_In_Ethiopic_Extended_A),                // 433: CR_In_Ethiopic_Extended_A
+    packed!(CR_In_Latin_Extended_E),                   // 434: CR_In_Latin_Extended_E
+    packed!(CR_In_Cherokee_Supplement),                // 435: CR_In_Cherokee_Supplement
+    packed!(CR_In_Meetei_Mayek),                       // 436: CR_In_Meetei_Mayek
+    packed!(CR_In_Hangul_Syllables),                   // 437: CR_In_Hangul_Syllables
+    packed!(CR_In_Hangul_Jamo_Extended_B),             // 438: CR_In_Hangul_Jamo_Extended_B
+    packed!(CR_In_High_Surrogates),                    // 439: CR_In_High_Surrogates
+    packed!(CR_In_High_Private_Use_Surrogates),        // 440: CR_In_High_Private_Use_Surrogates
+    packed!(CR_In_Low_Surrogates),                     // 441: CR_In_Low_Surrogates
+    packed!(CR_In_Private_Use_Area),                   // 442: CR_In_Private_Use_Area
+    packed!(CR_In_CJK_Compatibility_Ideographs),       // 443: CR_In_CJK_Compatibility_Ideographs
+    packed!(CR_In_Alphabetic_Presentation_Forms),      // 444: CR_In_Alphabetic_Presentation_Forms
+    packed!(CR_In_Arabic_Presentation_Forms_A),        // 445: CR_In_Arabic_Presentation_Forms_A
+    packed!(CR_In_Variation_Selectors),                // 446: CR_In_Variation_Selectors
+    packed!(CR_In_Vertical_Forms),                     // 447: CR_In_Vertical_Forms
+    packed!(CR_In_Combining_Half_Marks),               // 448: CR_In_Combining_Half_Marks
+    packed!(CR_In_CJK_Compatibility_Forms),            // 449: CR_In_CJK_Compatibility_Forms
+    packed!(CR_In_Small_Form_Variants),                // 450: CR_In_Small_Form_Variants
+    packed!(CR_In_Arabic_Presentation_Forms_B),        // 451: CR_In_Arabic_Presentation_Forms_B
+    packed!(CR_In_Halfwidth_and_Fullwidth_Forms),      // 452: CR_In_Halfwidth_and_Fullwidth_Forms
+    packed!(CR_In_Specials),                           // 453: CR_In_Specials
+    packed!(CR_In_Linear_B_Syllabary),                 // 454: CR_In_Linear_B_Syllabary
+    packed!(CR_In_Linear_B_Ideograms),                 // 455: CR_In_Linear_B_Ideograms
+    packed!(CR_In_Aegean_Numbers),                     // 456: CR_In_Aegean_Numbers
+    packed!(CR_In_Ancient_Greek_Numbers),              // 457: CR_In_Ancient_Greek_Numbers
+    packed!(CR_In_Ancient_Symbols),                    // 458: CR_In_Ancient_Symbols
+    packed!(CR_In_Phaistos_Disc),                      // 459: CR_In_Phaistos_Disc
+    packed!(CR_In_Lycian),                             // 460: CR_In_Lycian
+    packed!(CR_In_Carian),                             // 461: CR_In_Carian
+    packed!(CR_In_Coptic_Epact_Numbers),               // 462: CR_In_Coptic_Epact_Numbers
+    packed!(CR_In_Old_Italic),                         // 463: CR_In_Old_Italic
+    packed!(CR_In_Gothic),                             // 464: CR_In_Gothic
+    packed!(CR_In_Old_Permic),                         // 465: CR_In_Old_Permic
+    packed!(CR_In_Ugaritic),                           // 466: CR_In_Ugaritic
+    packed!(CR_In_Old_Persian),                        // 467: CR_In_Old_Persian
+    packed!(CR_Deseret),                               // 468: CR_In_Deseret
+    packed!(CR_Shavian),                               // 469: CR_In_Shavian
+    packed!(CR_In_Osmanya),                            // 470: CR_In_Osmanya
+    packed!(CR_In_Osage),                              // 471: CR_In_Osage
+    packed!(CR_In_Elbasan),                            // 472: CR_In_Elbasan
+    packed!(CR_In_Caucasian_Albanian),                 // 473: CR_In_Caucasian_Albanian
+    packed!(CR_In_Vithkuqi),                           // 474: CR_In_Vithkuqi
+    packed!(CR_In_Todhri),                             // 475: CR_In_Todhri
+    packed!(CR_In_Linear_A),                           // 476: CR_In_Linear_A
+    packed!(CR_In_Latin_Extended_F),                   // 477: CR_In_Latin_Extended_F
+    packed!(CR_In_Cypriot_Syllabary),                  // 478: CR_In_Cypriot_Syllabary
+    packed!(CR_In_Imperial_Aramaic),                   // 479: CR_In_Imperial_Aramaic
+    packed!(CR_Palmyrene),                             // 480: CR_In_Palmyrene
+    packed!(CR_In_Nabataean),                          // 481: CR_In_Nabataean
+    packed!(CR_In_Hatran),                             // 482: CR_In_Hatran
+    packed!(CR_In_Phoenician),                         // 483: CR_In_Phoenician
+    packed!(CR_In_Lydian),                             // 484: CR_In_Lydian
+    packed!(CR_Meroitic_Hieroglyphs),                  // 485: CR_In_Meroitic_Hieroglyphs
+    packed!(CR_In_Meroitic_Cursive),                   // 486: CR_In_Meroitic_Cursive
+    packed!(CR_In_Kharoshthi),                         // 487: CR_In_Kharoshthi
+    packed!(CR_Old_South_Arabian),                     // 488: CR_In_Old_South_Arabian
+    packed!(CR_Old_North_Arabian),                     // 489: CR_In_Old_North_Arabian
+    packed!(CR_In_Manichaean),                         // 490: CR_In_Manichaean
+    packed!(CR_In_Avestan),                            // 491: CR_In_Avestan
+    packed!(CR_In_Inscriptional_Parthian),             // 492: CR_In_Inscriptional_Parthian
+    packed!(CR_In_Inscriptional_Pahlavi),              // 493: CR_In_Inscriptional_Pahlavi
+    packed!(CR_In_Psalter_Pahlavi),                    // 494: CR_In_Psalter_Pahlavi
+    packed!(CR_In_Old_Turkic),                         // 495: CR_In_Old_Turkic
+    packed!(CR_In_Old_Hungarian),                      // 496: CR_In_Old_Hungarian
+    packed!(CR_In_Hanifi_Rohingya),                    // 497: CR_In_Hanifi_Rohingya
+    packed!(CR_In_Garay),                              // 498: CR_In_Garay
+    packed!(CR_In_Rumi_Numeral_Symbols),               // 499: CR_In_Rumi_Numeral_Symbols
+    packed!(CR_In_Yezidi),                             // 500: CR_In_Yezidi
+    packed!(CR_In_Arabic_Extended_C),                  // 501: CR_In_Arabic_Extended_C
+    packed!(CR_In_Old_Sogdian),                        // 502: CR_In_Old_Sogdian
+    packed!(CR_In_Sogdian),                            // 503: CR_In_Sogdian
+    packed!(CR_In_Old_Uyghur),                         // 504: CR_In_Old_Uyghur
+    packed!(CR_In_Chorasmian),                         // 505: CR_In_Chorasmian
+    packed!(CR_In_Elymaic),                            // 506: CR_In_Elymaic
+    packed!(CR_In_Brahmi),                             // 507: CR_In_Brahmi
+    packed!(CR_In_Kaithi),                             // 508: CR_In_Kaithi
+    packed!(CR_In_Sora_Sompeng),                       // 509: CR_In_Sora_Sompeng
+    packed!(CR_In_Chakma),                             // 510: CR_In_Chakma
+    packed!(CR_In_Mahajani),                           // 511: CR_In_Mahajani
+    packed!(CR_Sharada),                               // 512: CR_In_Sharada
+    packed!(CR_In_Sinhala_Archaic_Numbers),            // 513: CR_In_Sinhala_Archaic_Numbers
+    packed!(CR_In_Khojki),                             // 514: CR_In_Khojki
+    packed!(CR_In_Multani),                            // 515: CR_In_Multani
+    packed!(CR_In_Khudawadi),                          // 516: CR_In_Khudawadi
+    packed!(CR_In_Grantha),                            // 517: CR_In_Grantha
+    packed!(CR_In_Tulu_Tigalari),                      // 518: CR_In_Tulu_Tigalari
+    packed!(CR_In_Newa),                               // 519: CR_In_Newa
+    packed!(CR_In_Tirhuta),                            // 520: CR_In_Tirhuta
+    packed!(CR_In_Siddham),                            // 521: CR_In_Siddham
+    packed!(CR_In_Modi),                               // 522: CR_In_Modi
+    packed!(CR_In_Mongolian_Supplement),               // 523: CR_In_Mongolian_Supplement
+    packed!(CR_In_Takri),                              // 524: CR_In_Takri
+    packed!(CR_In_Myanmar_Extended_C),                 // 525: CR_In_Myanmar_Extended_C
+    packed!(CR_In_Ahom),                               // 526: CR_In_Ahom
+    packed!(CR_In_Dogra),                              // 527: CR_In_Dogra
+    packed!(CR_In_Warang_Citi),                        // 528: CR_In_Warang_Citi
+    packed!(CR_In_Dives_Akuru),                        // 529: CR_In_Dives_Akuru
+    packed!(CR_In_Nandinagari),                        // 530: CR_In_Nandinagari
+    packed!(CR_In_Zanabazar_Square),                   // 531: CR_In_Zanabazar_Square
+    packed!(CR_In_Soyombo),                            // 532: CR_In_Soyombo
+    packed!(CR_In_Unified_Canadian_Aboriginal_Syllabics_Extended_A), // 533: CR_In_Unified_Canadian_Aboriginal_Syllabics_Extended_A
+    packed!(CR_In_Pau_Cin_Hau),                                      // 534: CR_In_Pau_Cin_Hau
+    packed!(CR_In_Devanagari_Extended_A), // 535: CR_In_Devanagari_Extended_A
+    packed!(CR_In_Sunuwar),               // 536: CR_In_Sunuwar
+    packed!(CR_In_Bhaiksuki),             // 537: CR_In_Bhaiksuki
+    packed!(CR_In_Marchen),               // 538: CR_In_Marchen
+    packed!(CR_In_Masaram_Gondi),         // 539: CR_In_Masaram_Gondi
+    packed!(CR_In_Gunjala_Gondi),         // 540: CR_In_Gunjala_Gondi
+    packed!(CR_In_Makasar),               // 541: CR_In_Makasar
+    packed!(CR_In_Kawi),                  // 542: CR_In_Kawi
+    packed!(CR_In_Lisu_Supplement),       // 543: CR_In_Lisu_Supplement
+    packed!(CR_In_Tamil_Supplement),      // 544: CR_In_Tamil_Supplement
+    packed!(CR_In_Cuneiform),             // 545: CR_In_Cuneiform
+    packed!(CR_In_Cuneiform_Numbers_and_Punctuation), // 546: CR_In_Cuneiform_Numbers_and_Punctuation
+    packed!(CR_In_Early_Dynastic_Cuneiform),          // 547: CR_In_Early_Dynastic_Cuneiform
+    packed!(CR_In_Cypro_Minoan),                      // 548: CR_In_Cypro_Minoan
+    packed!(CR_In_Egyptian_Hieroglyphs),              // 549: CR_In_Egyptian_Hieroglyphs
+    packed!(CR_In_Egyptian_Hieroglyph_Format_Controls), // 550: CR_In_Egyptian_Hieroglyph_Format_Controls
+    packed!(CR_In_Egyptian_Hieroglyphs_Extended_A), // 551: CR_In_Egyptian_Hieroglyphs_Extended_A
+    packed!(CR_In_Anatolian_Hieroglyphs),           // 552: CR_In_Anatolian_Hieroglyphs
+    packed!(CR_In_Gurung_Khema),                    // 553: CR_In_Gurung_Khema
+    packed!(CR_In_Bamum_Supplement),                // 554: CR_In_Bamum_Supplement
+    packed!(CR_In_Mro),                             // 555: CR_In_Mro
+    packed!(CR_In_Tangsa),                          // 556: CR_In_Tangsa
+    packed!(CR_In_Bassa_Vah),                       // 557: CR_In_Bassa_Vah
+    packed!(CR_In_Pahawh_Hmong),                    // 558: CR_In_Pahawh_Hmong
+    packed!(CR_In_Kirat_Rai),                       // 559: CR_In_Kirat_Rai
+    packed!(CR_In_Medefaidrin),                     // 560: CR_In_Medefaidrin
+    packed!(CR_In_Miao),                            // 561: CR_In_Miao
+    packed!(CR_In_Ideographic_Symbols_and_Punctuation), // 562: CR_In_Ideographic_Symbols_and_Punctuation
+    packed!(CR_In_Tangut),                              // 563: CR_In_Tangut
+    packed!(CR_In_Tangut_Components),                   // 564: CR_In_Tangut_Components
+    packed!(CR_In_Khitan_Small_Script),                 // 565: CR_In_Khitan_Small_Script
+    packed!(CR_In_Tangut_Supplement),                   // 566: CR_In_Tangut_Supplement
+    packed!(CR_In_Kana_Extended_B),                     // 567: CR_In_Kana_Extended_B
+    packed!(CR_In_Kana_Supplement),                     // 568: CR_In_Kana_Supplement
+    packed!(CR_In_Kana_Extended_A),                     // 569: CR_In_Kana_Extended_A
+    packed!(CR_In_Small_Kana_Extension),                // 570: CR_In_Small_Kana_Extension
+    packed!(CR_In_Nushu),                               // 571: CR_In_Nushu
+    packed!(CR_In_Duployan),                            // 572: CR_In_Duployan
+    packed!(CR_In_Shorthand_Format_Controls),           // 573: CR_In_Shorthand_Format_Controls
+    packed!(CR_In_Symbols_for_Legacy_Computing_Supplement), // 574: CR_In_Symbols_for_Legacy_Computing_Supplement
+    packed!(CR_In_Znamenny_Musical_Notation),               // 575: CR_In_Znamenny_Musical_Notation
+    packed!(CR_In_Byzantine_Musical_Symbols),               // 576: CR_In_Byzantine_Musical_Symbols
+    packed!(CR_In_Musical_Symbols),                         // 577: CR_In_Musical_Symbols
+    packed!(CR_In_Ancient_Greek_Musical_Notation), // 578: CR_In_Ancient_Greek_Musical_Notation
+    packed!(CR_In_Kaktovik_Numerals),              // 579: CR_In_Kaktovik_Numerals
+    packed!(CR_In_Mayan_Numerals),                 // 580: CR_In_Mayan_Numerals
+    packed!(CR_In_Tai_Xuan_Jing_Symbols),          // 581: CR_In_Tai_Xuan_Jing_Symbols
+    packed!(CR_In_Counting_Rod_Numerals),          // 582: CR_In_Counting_Rod_Numerals
+    packed!(CR_In_Mathematical_Alphanumeric_Symbols), // 583: CR_In_Mathematical_Alphanumeric_Symbols
+    packed!(CR_In_Sutton_SignWriting),                // 584: CR_In_Sutton_SignWriting
+    packed!(CR_In_Latin_Extended_G),                  // 585: CR_In_Latin_Extended_G
+    packed!(CR_In_Glagolitic_Supplement),             // 586: CR_In_Glagolitic_Supplement
+    packed!(CR_In_Cyrillic_Extended_D),               // 587: CR_In_Cyrillic_Extended_D
+    packed!(CR_In_Nyiakeng_Puachue_Hmong),            // 588: CR_In_Nyiakeng_Puachue_Hmong
+    packed!(CR_In_Toto),                              // 589: CR_In_Toto
+    packed!(CR_In_Wancho),                            // 590: CR_In_Wancho
+    packed!(CR_In_Nag_Mundari),                       // 591: CR_In_Nag_Mundari
+    packed!(CR_In_Ol_Onal),                           // 592: CR_In_Ol_Onal
+    packed!(CR_In_Ethiopic_Extended_B),               // 593: CR_In_Ethiopic_Extended_B
+    packed!(CR_In_Mende_Kikakui),                     // 594: CR_In_Mende_Kikakui
+    packed!(CR_In_Adlam),                             // 595: CR_In_Adlam
+    packed!(CR_In_Indic_Siyaq_Numbers),               // 596: CR_In_Indic_Siyaq_Numbers
+    packed!(CR_In_Ottoman_Siyaq_Numbers),             // 597: CR_In_Ottoman_Siyaq_Numbers
+    packed!(CR_In_Arabic_Mathematical_Alphabetic_Symbols), // 598: CR_In_Arabic_Mathematical_Alphabetic_Symbols
+    packed!(CR_In_Mahjong_Tiles),                          // 599: CR_In_Mahjong_Tiles
+    packed!(CR_In_Domino_Tiles),                           // 600: CR_In_Domino_Tiles
+    packed!(CR_In_Playing_Cards),                          // 601: CR_In_Playing_Cards
+    packed!(CR_In_Enclosed_Alphanumeric_Supplement), // 602: CR_In_Enclosed_Alphanumeric_Supplement
+    packed!(CR_In_Enclosed_Ideographic_Supplement),  // 603: CR_In_Enclosed_Ideographic_Supplement
+    packed!(CR_In_Miscellaneous_Symbols_and_Pictographs), // 604: CR_In_Miscellaneous_Symbols_and_Pictographs
+    packed!(CR_In_Emoticons),                             // 605: CR_In_Emoticons
+    packed!(CR_In_Ornamental_Dingbats),                   // 606: CR_In_Ornamental_Dingbats
+    packed!(CR_In_Transport_and_Map_Symbols),             // 607: CR_In_Transport_and_Map_Symbols
+    packed!(CR_In_Alchemical_Symbols),                    // 608: CR_In_Alchemical_Symbols
+    packed!(CR_In_Geometric_Shapes_Extended),             // 609: CR_In_Geometric_Shapes_Extended
+    packed!(CR_In_Supplemental_Arrows_C),                 // 610: CR_In_Supplemental_Arrows_C
+    packed!(CR_In_Supplemental_Symbols_and_Pictographs), // 611: CR_In_Supplemental_Symbols_and_Pictographs
+    packed!(CR_In_Chess_Symbols),                        // 612: CR_In_Chess_Symbols
+    packed!(CR_In_Symbols_and_Pictographs_Extended_A), // 613: CR_In_Symbols_and_Pictographs_Extended_A
+    packed!(CR_In_Symbols_for_Legacy_Computing),       // 614: CR_In_Symbols_for_Legacy_Computing
+    packed!(CR_In_CJK_Unified_Ideographs_Extension_B), // 615: CR_In_CJK_Unified_Ideographs_Extension_B
+    packed!(CR_In_CJK_Unified_Ideographs_Extension_C), // 616: CR_In_CJK_Unified_Ideographs_Extension_C
+    packed!(CR_In_CJK_Unified_Ideographs_Extension_D), // 617: CR_In_CJK_Unified_Ideographs_Extension_D
+    packed!(CR_In_CJK_Unified_Ideographs_Extension_E), // 618: CR_In_CJK_Unified_Ideographs_Extension_E
+    packed!(CR_In_CJK_Unified_Ideographs_Extension_F), // 619: CR_In_CJK_Unified_Ideographs_Extension_F
+    packed!(CR_In_CJK_Unified_Ideographs_Extension_I), // 620: CR_In_CJK_Unified_Ideographs_Extension_I
+    packed!(CR_In_CJK_Compatibility_Ideographs_Supplement), // 621: CR_In_CJK_Compatibility_Ideographs_Supplement
+    packed!(CR_In_CJK_Unified_Ideographs_Extension_G), // 622: CR_In_CJK_Unified_Ideographs_Extension_G
+    packed!(CR_In_CJK_Unified_Ideographs_Extension_H), // 623: CR_In_CJK_Unified_Ideographs_Extension_H
+    packed!(CR_In_Tags),                               // 624: CR_In_Tags
+    packed!(CR_In_Variation_Selectors_Supplement),     // 625: CR_In_Variation_Selectors_Supplement
+    packed!(CR_In_Supplementary_Private_Use_Area_A), // 626: CR_In_Supplementary_Private_Use_Area_A
+    packed!(CR_In_Supplementary_Private_Use_Area_B), // 627: CR_In_Supplementary_Private_Use_Area_B
+    packed!(CR_In_No_Block),                         // 628: CR_In_No_Block
+    packed!(CR_Scx_Adlam),                           // 629: CR_Scx_Adlam
+    packed!(CR_Scx_Arabic),                          // 630: CR_Scx_Arabic
+    packed!(CR_Scx_Armenian),                        // 631: CR_Scx_Armenian
+    packed!(CR_Scx_Avestan),                         // 632: CR_Scx_Avestan
+    packed!(CR_Scx_Bengali),                         // 633: CR_Scx_Bengali
+    packed!(CR_Scx_Bopomofo),                        // 634: CR_Scx_Bopomofo
+    packed!(CR_Scx_Buginese),                        // 635: CR_Scx_Buginese
+    packed!(CR_Scx_Buhid),                           // 636: CR_Scx_Buhid
+    packed!(CR_Scx_Carian),                          // 637: CR_Scx_Carian
+    packed!(CR_Scx_Caucasian_Albanian),              // 638: CR_Scx_Caucasian_Albanian
+    packed!(CR_Scx_Chakma),                          // 639: CR_Scx_Chakma
+    packed!(CR_Scx_Cherokee),                        // 640: CR_Scx_Cherokee
+    packed!(CR_Scx_Common),                          // 641: CR_Scx_Common
+    packed!(CR_Scx_Coptic),                          // 642: CR_Scx_Coptic
+    packed!(CR_Scx_Cypriot),                         // 643: CR_Scx_Cypriot
+    packed!(CR_Scx_Cypro_Minoan),                    // 644: CR_Scx_Cypro_Minoan
+    packed!(CR_Scx_Cyrillic),                        // 645: CR_Scx_Cyrillic
+    packed!(CR_Scx_Devanagari),                      // 646: CR_Scx_Devanagari
+    packed!(CR_Scx_Dogra),                           // 647: CR_Scx_Dogra
+    packed!(CR_Scx_Duployan),                        // 648: CR_Scx_Duployan
+    packed!(CR_Scx_Elbasan),                         // 649: CR_Scx_Elbasan
+    packed!(CR_Scx_Ethiopic),                        // 650: CR_Scx_Ethiopic
+    packed!(CR_Scx_Garay),                           // 651: CR_Scx_Garay
+    packed!(CR_Scx_Georgian),                        // 652: CR_Scx_Georgian
+    packed!(CR_Scx_Glagolitic),                      // 653: CR_Scx_Glagolitic
+    packed!(CR_Scx_Gothic),                          // 654: CR_Scx_Gothic
+    packed!(CR_Scx_Grantha),                         // 655: CR_Scx_Grantha
+    packed!(CR_Scx_Greek),                           // 656: CR_Scx_Greek
+    packed!(CR_Scx_Gujarati),                        // 657: CR_Scx_Gujarati
+    packed!(CR_Scx_Gunjala_Gondi),                   // 658: CR_Scx_Gunjala_Gondi
+    packed!(CR_Scx_Gurmukhi),                        // 659: CR_Scx_Gurmukhi
+    packed!(CR_Scx_Gurung_Khema),                    // 660: CR_Scx_Gurung_Khema
+    packed!(CR_Scx_Han),                             // 661: CR_Scx_Han
+    packed!(CR_Scx_Hangul),                          // 662: CR_Scx_Hangul
+    packed!(CR_Scx_Hanifi_Rohingya),                 // 663: CR_Scx_Hanifi_Rohingya
+    packed!(CR_Scx_Hanunoo),                         // 664: CR_Scx_Hanunoo
+    packed!(CR_Scx_Hebrew),                          // 665: CR_Scx_Hebrew
+    packed!(CR_Scx_Hiragana),                        // 666: CR_Scx_Hiragana
+    packed!(CR_Scx_Inherited),                       // 667: CR_Scx_Inherited
+    packed!(CR_Scx_Javanese),                        // 668: CR_Scx_Javanese
+    packed!(CR_Scx_Kaithi),                          // 669: CR_Scx_Kaithi
+    packed!(CR_Scx_Kannada),                         // 670: CR_Scx_Kannada
+    packed!(CR_Scx_Katakana),                        // 671: CR_Scx_Katakana
+    packed!(CR_Scx_Kayah_Li),                        // 672: CR_Scx_Kayah_Li
+    packed!(CR_Scx_Khojki),                          // 673: CR_Scx_Khojki
+    packed!(CR_Scx_Khudawadi),                       // 674: CR_Scx_Khudawadi
+    packed!(CR_Scx_Latin),                           // 675: CR_Scx_Latin
+    packed!(CR_Scx_Limbu),                           // 676: CR_Scx_Limbu
+    packed!(CR_Scx_Linear_A),                        // 677: CR_Scx_Linear_A
+    packed!(CR_Scx_Linear_B),                        // 678: CR_Scx_Linear_B
+    packed!(CR_Scx_Lisu),                            // 679: CR_Scx_Lisu
+    packed!(CR_Scx_Lycian),                          // 680: CR_Scx_Lycian
+    packed!(CR_Scx_Lydian),                          // 681: CR_Scx_Lydian
+    packed!(CR_Scx_Mahajani),                        // 682: CR_Scx_Mahajani
+    packed!(CR_Scx_Malayalam),                       // 683: CR_Scx_Malayalam
+    packed!(CR_Scx_Mandaic),                         // 684: CR_Scx_Mandaic
+    packed!(CR_Scx_Manichaean),                      // 685: CR_Scx_Manichaean
+    packed!(CR_Scx_Masaram_Gondi),                   // 686: CR_Scx_Masaram_Gondi
+    packed!(CR_Scx_Meroitic_Hieroglyphs),            // 687: CR_Scx_Meroitic_Hieroglyphs
+    packed!(CR_Scx_Modi),                            // 688: CR_Scx_Modi
+    packed!(CR_Scx_Mongolian),                       // 689: CR_Scx_Mongolian
+    packed!(CR_Scx_Multani),                         // 690: CR_Scx_Multani
+    packed!(CR_Scx_Myanmar),                         // 691: CR_Scx_Myanmar
+    packed!(CR_Scx_Nandinagari),                     // 692: CR_Scx_Nandinagari
+    packed!(CR_Scx_Nko),                             // 693: CR_Scx_Nko
+    packed!(CR_Scx_Ol_Onal),                         // 694: CR_Scx_Ol_Onal
+    packed!(CR_Scx_Old_Hungarian),                   // 695: CR_Scx_Old_Hungarian
+    packed!(CR_Scx_Old_Permic),                      // 696: CR_Scx_Old_Permic
+    packed!(CR_Scx_Old_Turkic),                      // 697: CR_Scx_Old_Turkic
+    packed!(CR_Scx_Old_Uyghur),                      // 698: CR_Scx_Old_Uyghur
+    packed!(CR_Scx_Oriya),                           // 699: CR_Scx_Oriya
+    packed!(CR_Scx_Osage),                           // 700: CR_Scx_Osage
+    packed!(CR_Scx_Phags_Pa),                        // 701: CR_Scx_Phags_Pa
+    packed!(CR_Scx_Psalter_Pahlavi),                 // 702: CR_Scx_Psalter_Pahlavi
+    packed!(CR_Scx_Runic),                           // 703: CR_Scx_Runic
+    packed!(CR_Scx_Samaritan),                       // 704: CR_Scx_Samaritan
+    packed!(CR_Scx_Sharada),                         // 705: CR_Scx_Sharada
+    packed!(CR_Scx_Shavian),                         // 706: CR_Scx_Shavian
+    packed!(CR_Scx_Sinhala),                         // 707: CR_Scx_Sinhala
+    packed!(CR_Scx_Sogdian),                         // 708: CR_Scx_Sogdian
+    packed!(CR_Scx_Sunuwar),                         // 709: CR_Scx_Sunuwar
+    packed!(CR_Scx_Syloti_Nagri),                    // 710: CR_Scx_Syloti_Nagri
+    packed!(CR_Scx_Syriac),                          // 711: CR_Scx_Syriac
+    packed!(CR_Scx_Tagalog),                         // 712: CR_Scx_Tagalog
+    packed!(CR_Scx_Tagbanwa),                        // 713: CR_Scx_Tagbanwa
+    packed!(CR_Scx_Tai_Le),                          // 714: CR_Scx_Tai_Le
+    packed!(CR_Scx_Takri),                           // 715: CR_Scx_Takri
+    packed!(CR_Scx_Tamil),                           // 716: CR_Scx_Tamil
+    packed!(CR_Scx_Tangut),                          // 717: CR_Scx_Tangut
+    packed!(CR_Scx_Telugu),                          // 718: CR_Scx_Telugu
+    packed!(CR_Scx_Thaana),                          // 719: CR_Scx_Thaana
+    packed!(CR_Scx_Thai),                            // 720: CR_Scx_Thai
+    packed!(CR_Scx_Tibetan),                         // 721: CR_Scx_Tibetan
+    packed!(CR_Scx_Tifinagh),                        // 722: CR_Scx_Tifinagh
+    packed!(CR_Scx_Tirhuta),                         // 723: CR_Scx_Tirhuta
+    packed!(CR_Scx_Todhri),                          // 724: CR_Scx_Todhri
+    packed!(CR_Scx_Toto),                            // 725: CR_Scx_Toto
+    packed!(CR_Scx_Tulu_Tigalari),                   // 726: CR_Scx_Tulu_Tigalari
+    packed!(CR_Scx_Yezidi),                          // 727: CR_Scx_Yezidi
+    packed!(CR_Scx_Yi),                              // 728: CR_Scx_Yi
 ];
 
 // --- Property name lookup table (sorted, normalized) ---
@@ -8277,3 +9682,179 @@ pub static PROPERTY_NAMES: [(&str, u16); 886] = [
     ("zyyy", 56),
     ("zzzz", 273),
 ];
+
+// --- Script -> Script_Extensions ctype (sorted by script ctype) ---
+// Scripts whose Script_Extensions equal their Script ranges map to themselves.
+pub static SCRIPT_EXTENSIONS: [(u16, u16); 171] = [
+    (16, 629),
+    (17, 17),
+    (19, 19),
+    (21, 630),
+    (22, 631),
+    (24, 632),
+    (25, 25),
+    (26, 26),
+    (27, 27),
+    (28, 28),
+    (29, 633),
+    (30, 30),
+    (32, 634),
+    (33, 33),
+    (34, 34),
+    (35, 635),
+    (36, 636),
+    (38, 38),
+    (39, 637),
+    (42, 638),
+    (45, 639),
+    (46, 46),
+    (52, 640),
+    (53, 53),
+    (56, 641),
+    (57, 642),
+    (59, 59),
+    (60, 643),
+    (61, 644),
+    (62, 645),
+    (66, 66),
+    (67, 646),
+    (69, 69),
+    (70, 647),
+    (71, 648),
+    (72, 72),
+    (73, 649),
+    (74, 74),
+    (80, 650),
+    (83, 651),
+    (84, 652),
+    (85, 653),
+    (86, 654),
+    (87, 655),
+    (91, 656),
+    (92, 657),
+    (93, 658),
+    (94, 659),
+    (95, 660),
+    (96, 661),
+    (97, 662),
+    (98, 663),
+    (99, 664),
+    (100, 100),
+    (101, 665),
+    (103, 666),
+    (113, 113),
+    (115, 667),
+    (116, 116),
+    (117, 117),
+    (118, 668),
+    (120, 669),
+    (121, 670),
+    (122, 671),
+    (123, 123),
+    (124, 672),
+    (125, 125),
+    (126, 126),
+    (127, 127),
+    (128, 673),
+    (129, 674),
+    (130, 130),
+    (133, 133),
+    (134, 675),
+    (135, 135),
+    (136, 676),
+    (137, 677),
+    (138, 678),
+    (139, 679),
+    (147, 680),
+    (148, 681),
+    (150, 682),
+    (151, 151),
+    (152, 683),
+    (153, 684),
+    (154, 685),
+    (155, 155),
+    (156, 686),
+    (160, 160),
+    (161, 161),
+    (162, 162),
+    (163, 163),
+    (164, 687),
+    (165, 165),
+    (167, 688),
+    (169, 689),
+    (170, 170),
+    (171, 690),
+    (172, 691),
+    (174, 174),
+    (175, 175),
+    (176, 692),
+    (178, 178),
+    (179, 179),
+    (180, 693),
+    (184, 184),
+    (185, 185),
+    (186, 186),
+    (187, 187),
+    (188, 694),
+    (189, 695),
+    (190, 190),
+    (191, 191),
+    (192, 696),
+    (193, 193),
+    (194, 194),
+    (195, 195),
+    (196, 697),
+    (197, 698),
+    (198, 699),
+    (199, 700),
+    (200, 200),
+    (210, 210),
+    (211, 211),
+    (214, 214),
+    (219, 701),
+    (220, 220),
+    (225, 702),
+    (229, 229),
+    (230, 703),
+    (232, 704),
+    (233, 233),
+    (236, 705),
+    (237, 706),
+    (238, 238),
+    (239, 239),
+    (240, 707),
+    (245, 708),
+    (246, 246),
+    (247, 247),
+    (248, 248),
+    (249, 709),
+    (250, 710),
+    (251, 711),
+    (252, 712),
+    (253, 713),
+    (254, 714),
+    (255, 255),
+    (256, 256),
+    (257, 715),
+    (258, 716),
+    (259, 259),
+    (260, 717),
+    (261, 718),
+    (263, 719),
+    (264, 720),
+    (265, 721),
+    (266, 722),
+    (267, 723),
+    (268, 724),
+    (269, 725),
+    (270, 726),
+    (271, 271),
+    (273, 273),
+    (275, 275),
+    (277, 277),
+    (278, 278),
+    (279, 279),
+    (283, 727),
+    (284, 728),
+    (286, 286),
+];
@@ -0,0 +1,170 @@
+// validate.rs - Check a pattern and describe it without building a matcher.
+//
+// Services that accept regexes from users want to reject bad
+// patterns and estimate their cost up front. validate() runs the full
+// parse and compile, so it reports every error Regex::new would, but hands
+// back only a summary instead of a Regex.
+
+use crate::error::RegexError;
+use crate::oniguruma::*;
+use crate::regcomp::{
+    compile_error, onig_compile_inspect, onig_get_default_case_fold_flag, onig_reg_init,
+};
+use crate::regenc::OnigEncoding;
+use crate::regint::*;
+use crate::regparse_types::*;
+#[cfg(not(feature = "std"))]
+use crate::sys::prelude::*;
+
+/// Parse and compile `pattern` and describe it.
+///
+/// Returns the same errors compiling the pattern with `syntax` and `enc`
+/// would, without keeping the compiled program or allocating any match
+/// state.
+///
+/// # Examples
+///
+/// ```
+/// use ferroni::encodings::utf8::ONIG_ENCODING_UTF8;
+/// use ferroni::regsyntax::OnigSyntaxOniguruma;
+/// use ferroni::validate::Features;
+///
+/// let check = |p: &str| ferroni::validate(p.as_bytes(), &OnigSyntaxOniguruma, &ONIG_ENCODING_UTF8);
+///
+/// let info = check(r"(?<q>['\x22])\w{1,8}\k<q>").unwrap();
+/// assert_eq!(info.captures, 1);
+/// assert_eq!(info.names, [("q".to_string(), vec![1])]);
+/// // \w is up to 4 bytes, and the backreference may be empty
+/// assert_eq!((info.min_len, info.max_len), (2, Some(37)));
+/// assert_eq!(info.features, Features::BACKREF);
+///
+/// // Found by the compiler, not the parser
+/// assert!(ferroni::parse(r"(?<n>\g<n>)", &OnigSyntaxOniguruma).is_ok());
+/// assert!(check(r"(?<n>\g<n>)").is_err());
+/// ```
+pub fn validate(
+    pattern: &[u8],
+    syntax: &OnigSyntaxType,
+    enc: OnigEncoding,
+) -> Result<PatternInfo, RegexError> {
+    let mut reg = onig_reg_init(
+        ONIG_OPTION_NONE,
+        onig_get_default_case_fold_flag(),
+        enc,
+        syntax,
+    )?;
+    let mut features = Features::empty();
+    let mut state = CompileState::default();
+    let r = onig_compile_inspect(&mut reg, pattern, &mut state, |root| {
+        collect_features(root, &mut features)
+    });
+    if r != 0 {
+        return Err(compile_error(r, &state, pattern));
+    }
+
+    let mut entries: Vec<_> = match reg.name_table {
+        Some(ref nt) => nt.entries.values().collect(),
+        None => Vec::new(),
+    };
+    entries.sort_by_key(|e| e.back_refs.first().copied());
+    let names = entries
+        .into_iter()
+        .map(|e| {
+            let groups = e.back_refs.iter().map(|&n| n as usize).collect();
+            (String::from_utf8_lossy(&e.name).into_owned(), groups)
+        })
+        .collect();
+
+    Ok(PatternInfo {
+        captures: reg.num_mem as usize,
+        names,
+        min_len: reg.match_len_min as usize,
+        max_len: (reg.match_len_max != INFINITE_LEN).then_some(reg.match_len_max as usize),
+        features,
+    })
+}
+
+/// Summary of a pattern, as returned by [`validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PatternInfo {
+    /// Number of capture groups, not counting the whole match.
+    pub captures: usize,
+    /// Group names with the groups each one refers to, in group order.
+    pub names: Vec<(String, Vec<usize>)>,
+    /// Shortest match, in bytes.
+    pub min_len: usize,
+    /// Longest match in bytes, or `None` if unbounded.
+    pub max_len: Option<usize>,
+    /// Constructs the pattern uses.
+    pub features: Features,
+}
+
+bitflags::bitflags! {
+    /// Constructs a pattern uses that affect how it can be run, as reported
+    /// by [`validate`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub struct Features: u32 {
+        /// Backreferences (`\1`, `\k<name>`).
+        const BACKREF = 1 << 0;
+        /// Look-ahead (`(?=...)`, `(?!...)`).
+        const LOOK_AHEAD = 1 << 1;
+        /// Look-behind (`(?<=...)`, `(?<!...)`).
+        const LOOK_BEHIND = 1 << 2;
+        /// Subexpression calls (`\g<name>`), possibly recursive.
+        const SUBEXP_CALL = 1 << 3;
+        /// Callouts (`(?{...})`, `(*NAME)`).
+        const CALLOUT = 1 << 4;
+        /// Conditionals (`(?(cond)yes|no)`).
+        const CONDITIONAL = 1 << 5;
+    }
+}
+
+fn collect_features(node: &Node, out: &mut Features) {
+    match &node.inner {
+        NodeInner::List(cons) | NodeInner::Alt(cons) => {
+            collect_features(&cons.car, out);
+            if let Some(ref next) = cons.cdr {
+                collect_features(next, out);
+            }
+        }
+        NodeInner::Quant(ref qn) => {
+            if let Some(ref body) = qn.body {
+                collect_features(body, out);
+            }
+        }
+        NodeInner::Anchor(ref an) => {
+            if an.anchor_type & (ANCR_PREC_READ | ANCR_PREC_READ_NOT) != 0 {
+                *out |= Features::LOOK_AHEAD;
+            }
+            if an.anchor_type & (ANCR_LOOK_BEHIND | ANCR_LOOK_BEHIND_NOT) != 0 {
+                *out |= Features::LOOK_BEHIND;
+            }
+            if let Some(ref body) = an.body {
+                collect_features(body, out);
+            }
+        }
+        NodeInner::Bag(ref bn) => {
+            if let Some(ref body) = bn.body {
+                collect_features(body, out);
+            }
+            if let BagData::IfElse {
+                ref then_node,
+                ref else_node,
+            } = bn.bag_data
+            {
+                *out |= Features::CONDITIONAL;
+                for branch in [then_node, else_node].into_iter().flatten() {
+                    collect_features(branch, out);
+                }
+            }
+        }
+        // The condition of (?(<name>)...) is a backref node that only checks
+        NodeInner::BackRef(_) if !node.has_status(ND_ST_CHECKER) => *out |= Features::BACKREF,
+        NodeInner::Call(_) => *out |= Features::SUBEXP_CALL,
+        NodeInner::Gimmick(ref gn) if gn.gimmick_type == GimmickType::Callout => {
+            *out |= Features::CALLOUT;
+        }
+        _ => {}
+    }
+}
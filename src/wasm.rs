@@ -0,0 +1,172 @@
+// wasm.rs - JavaScript bindings for the Scanner (wasm-bindgen).
+//
+// Mirrors vscode-oniguruma's `OnigScanner` / `OnigString` classes so
+// vscode-textmate-based highlighters (e.g. Shiki) can use Ferroni in the
+// browser. Positions are UTF-16 code units, as in JavaScript strings.
+// Gated behind the `wasm` Cargo feature. Build with:
+//
+//     cargo rustc --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//     wasm-bindgen --target web target/wasm32-unknown-unknown/release/ferroni.wasm --out-dir pkg
+//
+// Nothing here needs std threading. The global settings are atomics, and
+// the registries (callout names, user-defined properties, case fold tables)
+// use sys.rs's core-atomics locks on wasm32 without the `atomics` target
+// feature rather than std's Mutex. `std::time::Instant` panics on
+// wasm32-unknown-unknown, so the time-limit check uses the JavaScript
+// clock instead (see `Instant` below). CI runs `cargo check` for this
+// target.
+
+use wasm_bindgen::prelude::*;
+
+use crate::scanner::{self, ScannerFindOptions, ScannerMatch};
+
+/// A string prepared for repeated scanning (UTF-16 offset tables built once).
+#[wasm_bindgen]
+pub struct OnigString {
+    inner: scanner::OnigString,
+}
+
+#[wasm_bindgen]
+impl OnigString {
+    #[wasm_bindgen(constructor)]
+    pub fn new(content: &str) -> OnigString {
+        OnigString {
+            inner: scanner::OnigString::new(content),
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn content(&self) -> String {
+        self.inner.content().to_string()
+    }
+
+    /// Release the string (vscode-oniguruma compatibility; same as `free()`).
+    pub fn dispose(self) {}
+}
+
+/// One capture group of an [`OnigMatch`], in UTF-16 code units.
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct OnigCaptureIndex {
+    pub start: u32,
+    pub end: u32,
+    pub length: u32,
+}
+
+/// Result of `OnigScanner.findNextMatchSync`.
+#[wasm_bindgen(getter_with_clone)]
+pub struct OnigMatch {
+    pub index: u32,
+    #[wasm_bindgen(js_name = captureIndices)]
+    pub capture_indices: Vec<OnigCaptureIndex>,
+}
+
+impl From<ScannerMatch> for OnigMatch {
+    fn from(m: ScannerMatch) -> Self {
+        OnigMatch {
+            index: m.index as u32,
+            capture_indices: m
+                .capture_indices
+                .iter()
+                .map(|c| OnigCaptureIndex {
+                    start: c.start as u32,
+                    end: c.end as u32,
+                    length: c.length as u32,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Multi-pattern scanner, API-compatible with vscode-oniguruma's `OnigScanner`.
+#[wasm_bindgen]
+pub struct OnigScanner {
+    inner: scanner::Scanner,
+}
+
+#[wasm_bindgen]
+impl OnigScanner {
+    #[wasm_bindgen(constructor)]
+    pub fn new(patterns: Vec<String>) -> Result<OnigScanner, JsError> {
+        let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+        let inner = scanner::Scanner::new(&patterns).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(OnigScanner { inner })
+    }
+
+    /// Find the next match at or after `start_position` (UTF-16 code units).
+    /// `options` is a bitmask of vscode-oniguruma's `FindOption` values.
+    #[wasm_bindgen(js_name = findNextMatchSync)]
+    pub fn find_next_match_sync(
+        &mut self,
+        string: &OnigString,
+        start_position: u32,
+        options: u32,
+    ) -> Option<OnigMatch> {
+        self.inner
+            .find_next_match_utf16(
+                &string.inner,
+                start_position as usize,
+                ScannerFindOptions::from_bits(options),
+            )
+            .map(OnigMatch::from)
+    }
+
+    /// Like `findNextMatchSync`, for a plain JavaScript string.
+    #[wasm_bindgen(js_name = findNextMatchSyncString)]
+    pub fn find_next_match_sync_string(
+        &mut self,
+        string: &str,
+        start_position: u32,
+        options: u32,
+    ) -> Option<OnigMatch> {
+        self.find_next_match_sync(&OnigString::new(string), start_position, options)
+    }
+
+    /// Release the scanner (vscode-oniguruma compatibility; same as `free()`).
+    pub fn dispose(self) {}
+}
+
+/// Stand-in for `std::time::Instant` on wasm32-unknown-unknown, based on
+/// `Date.now()`. Only used by the match time limit.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub(crate) struct Instant(f64);
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl Instant {
+    pub(crate) fn now() -> Self {
+        Instant(js_sys::Date::now())
+    }
+
+    pub(crate) fn elapsed(&self) -> std::time::Duration {
+        let ms = (js_sys::Date::now() - self.0).max(0.0);
+        std::time::Duration::from_millis(ms as u64)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scanner_reports_utf16_positions() {
+        let mut scanner = OnigScanner::new(vec!["b(c)?".to_string()]).unwrap();
+        let m = scanner
+            .find_next_match_sync_string("a💻bc", 0, 0)
+            .expect("match");
+        assert_eq!(m.index, 0);
+        let spans: Vec<_> = m
+            .capture_indices
+            .iter()
+            .map(|c| (c.start, c.end, c.length))
+            .collect();
+        assert_eq!(spans, vec![(3, 5, 2), (4, 5, 1)]);
+
+        let s = OnigString::new("xbx");
+        assert_eq!(s.content(), "xbx");
+        assert!(scanner.find_next_match_sync(&s, 2, 0).is_none());
+    }
+}
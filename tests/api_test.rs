@@ -1,7 +1,7 @@
 // api_test.rs - Integration tests for the idiomatic Rust API.
 
-use ferroni::api::{Regex, RegexBuilder};
-use ferroni::error::RegexError;
+use ferroni::api::{Anchors, Backend, Prefilter, Regex, RegexBuilder, LITERAL_PRESCAN_MIN_LEN};
+use ferroni::error::{RegexError, SyntaxErrorKind, WarningKind};
 use ferroni::prelude::*;
 
 // === Regex::new ===
@@ -20,6 +20,50 @@ fn unicode_pattern() {
     assert_eq!(m.as_str(), "せかい");
 }
 
+#[test]
+fn script_extensions_pattern() {
+    // The ideographic comma and the prolonged sound mark are Common, but
+    // their Script_Extensions include Han/Hiragana.
+    let re = Regex::new(r"[\p{scx=Han}\p{scx=Hiragana}]+").unwrap();
+    assert_eq!(
+        re.find("ok 漢字、ひらがなー!").unwrap().as_str(),
+        "漢字、ひらがなー"
+    );
+    let re = Regex::new(r"[\p{Han}\p{Hiragana}]+").unwrap();
+    assert_eq!(re.find("ok 漢字、ひらがなー!").unwrap().as_str(), "漢字");
+}
+
+#[test]
+fn user_defined_properties() {
+    use ferroni::unicode::{
+        onig_unicode_define_user_property, onig_unicode_undefine_user_property,
+    };
+
+    onig_unicode_define_user_property(b"ApiTestGreekish", &[0x41, 0x42, 0x3b1, 0x3b3]).unwrap();
+    let re = Regex::new(r"\p{ApiTestGreekish}+").unwrap();
+    assert_eq!(re.find("xxABαβγδ").unwrap().as_str(), "ABαβγ");
+    let re = Regex::new(r"[^\p{ApiTestGreekish}z]+").unwrap();
+    assert_eq!(re.find("ABαδzy").unwrap().as_str(), "δ");
+
+    // Compiled regexes keep their ranges after the property goes away.
+    let cache = ferroni::api::RegexCache::new(4);
+    assert!(cache.get(r"\p{ApiTestGreekish}").is_ok());
+    onig_unicode_undefine_user_property(b"ApiTestGreekish").unwrap();
+    assert!(Regex::new(r"\p{ApiTestGreekish}").is_err());
+    assert!(cache.get(r"\p{ApiTestGreekish}").is_err());
+    assert!(re.is_match("δ"));
+
+    let re = Regex::builder(r"\p{ApiTestLocal}+")
+        .property(
+            "ApiTestLocal",
+            &['ω'..='ω', 'α'..='γ', 'β'..='δ', 'z'..='a'],
+        )
+        .build()
+        .unwrap();
+    assert_eq!(re.find("abγδεω").unwrap().as_str(), "γδ");
+    assert!(Regex::new(r"\p{ApiTestLocal}").is_err());
+}
+
 #[test]
 fn no_match_returns_none() {
     let re = Regex::new(r"xyz").unwrap();
@@ -50,6 +94,237 @@ fn invalid_pattern_empty_char_class() {
     assert!(matches!(err, RegexError::Syntax { .. }));
 }
 
+#[test]
+fn build_with_warnings_reports_rewrites() {
+    use ferroni::regsyntax::OnigSyntaxPerl;
+
+    let warnings = |p: &str| {
+        let (_, w) = Regex::builder(p).build_with_warnings().unwrap();
+        w.into_iter()
+            .map(|w| (w.kind(), w.span()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(warnings(r"a(?:b*)+c"), [(WarningKind::NestedRepeat, 1..8)]);
+    assert_eq!(warnings(r"(?:b+)??"), []);
+    assert_eq!(warnings(r"x{1,y}"), [(WarningKind::InvalidInterval, 1..2)]);
+    assert_eq!(
+        warnings(r"[0-9-a]x]"),
+        [
+            (WarningKind::UnescapedMetaChar, 4..5),
+            (WarningKind::UnescapedMetaChar, 8..9)
+        ]
+    );
+    assert_eq!(warnings(r"\d+"), []);
+
+    // Only syntaxes with the ONIG_SYN_WARN_* flags warn about these
+    let (_, w) = Regex::builder(r"(?:b*)+[0-9-a]")
+        .syntax(&OnigSyntaxPerl)
+        .build_with_warnings()
+        .unwrap();
+    assert!(w.is_empty());
+}
+
+#[test]
+fn custom_syntax() {
+    use ferroni::regsyntax::{OnigSyntaxOniguruma, SyntaxBuilder};
+
+    let strict = || {
+        SyntaxBuilder::new(&OnigSyntaxOniguruma)
+            .backrefs(false)
+            .callouts(false)
+            .build()
+            .unwrap()
+    };
+    let built = |p: &str| Regex::builder(p).syntax(strict()).build();
+    assert!(built(r"(?<y>\d{4})-\d\d").unwrap().is_match("2024-05"));
+    assert!(built(r"(?{foo})a").is_err());
+    assert!(built(r"(*FAIL)").is_err());
+    assert!(Regex::new(r"(*FAIL)").is_ok());
+    assert!(!built(r"(?<q>x)\k<q>").unwrap().is_match("xx"));
+
+    // The regex keeps an owned syntax alive after the builder is gone
+    let shared = std::sync::Arc::new(strict());
+    let re = Regex::builder(r"\d+").syntax(shared.clone()).build();
+    drop(shared);
+    assert_eq!(re.unwrap().find("ab 12").unwrap().as_str(), "12");
+}
+
+#[test]
+fn ascii_classes_against_ruby() {
+    use ferroni::api::Classes;
+
+    // What Ruby (Onigmo, UTF-8 string, no `(?u)`) answers for each pattern.
+    // Ruby makes \w, \d and \s ASCII and leaves \b, POSIX brackets and
+    // properties Unicode.
+    let ruby = Classes::WORD | Classes::DIGIT | Classes::SPACE;
+    let same_as_ruby = [
+        (r"\w", "é", false),
+        (r"\W", "é", true),
+        (r"\d", "\u{661}", false),
+        (r"\s", "\u{a0}", false),
+        (r"\h", "F", true),
+        (r"[[:alpha:]]", "é", true),
+        (r"\p{Alpha}", "é", true),
+        (r"\p{Greek}", "λ", true),
+        (r"(?i)\w", "É", false),
+    ];
+    for (pattern, text, expected) in same_as_ruby {
+        let re = Regex::builder(pattern).ascii_classes(ruby).build().unwrap();
+        assert_eq!(re.is_match(text), expected, "{pattern} on {text:?}");
+    }
+
+    // Oniguruma's *_IS_ASCII options also cover these; Ruby says true
+    let differs_from_ruby = [
+        (r"\bé", "é"),
+        (r"[[:word:]]", "é"),
+        (r"\p{Word}", "é"),
+        (r"[[:digit:]]", "\u{661}"),
+        (r"[[:space:]]", "\u{a0}"),
+    ];
+    for (pattern, text) in differs_from_ruby {
+        let re = Regex::builder(pattern).ascii_classes(ruby).build().unwrap();
+        assert!(!re.is_match(text), "{pattern} on {text:?}");
+        assert!(Regex::new(pattern).unwrap().is_match(text));
+    }
+
+    // POSIX takes word, digit and space with it; empty restores Unicode
+    let posix = Regex::builder(r"\w").ascii_classes(Classes::POSIX).build();
+    assert!(!posix.unwrap().is_match("é"));
+    let reset = Regex::builder(r"\w")
+        .ascii_classes(Classes::all())
+        .ascii_classes(Classes::empty())
+        .build();
+    assert!(reset.unwrap().is_match("é"));
+}
+
+#[test]
+fn leftmost_longest() {
+    let posix = |p: &str| Regex::builder(p).leftmost_longest(true).build().unwrap();
+
+    let re = posix("(foo|foobar)(bar)?");
+    let caps = re.captures("a foobar").unwrap();
+    assert_eq!(caps.get(0).unwrap().range(), 2..8);
+    // Groups come from the first path to the longest match, not from
+    // POSIX's longest-subexpression rule
+    assert_eq!(caps.get(1).unwrap().as_str(), "foo");
+    assert_eq!(caps.get(2).unwrap().as_str(), "bar");
+
+    assert_eq!(posix("x*|y+").find("yyy").unwrap().as_str(), "yyy");
+    let all: Vec<&str> = posix("a|ab")
+        .find_iter("ab a ab")
+        .map(|m| m.as_str())
+        .collect();
+    assert_eq!(all, ["ab", "a", "ab"]);
+    assert!(posix("a|ab").is_match("xab"));
+    assert!(posix("ab|cd").find("xyz").is_none());
+
+    // FIND_LONGEST alone takes the longest match in the whole range
+    let text = "abc defg hij";
+    assert_eq!(posix(r"\w+").find(text).unwrap().range(), 0..3);
+    let anywhere = Regex::builder(r"\w+")
+        .option(ferroni::oniguruma::ONIG_OPTION_FIND_LONGEST)
+        .build();
+    assert_eq!(anywhere.unwrap().find(text).unwrap().range(), 4..8);
+    let off = Regex::builder(r"(?:a|ab)")
+        .leftmost_longest(true)
+        .leftmost_longest(false);
+    assert_eq!(off.build().unwrap().find("ab").unwrap().as_str(), "a");
+}
+
+#[test]
+fn compile_limits() {
+    use std::time::Duration;
+
+    let sized = |p: &str, bytes: usize| Regex::builder(p).max_compiled_bytes(bytes).build();
+    assert!(sized(r"(\d{3})-\1", 4096).is_ok());
+    let err = sized(r"(\d{3})-\1", 256).unwrap_err();
+    assert_eq!(err, RegexError::CompiledSizeLimitOver);
+    assert_eq!(err.to_string(), "compiled size limit over");
+    let literal = RegexBuilder::new(&"x".repeat(1000))
+        .literal(true)
+        .max_compiled_bytes(512);
+    assert_eq!(
+        literal.build().unwrap_err(),
+        RegexError::CompiledSizeLimitOver
+    );
+
+    let alts = vec!["abc"; 3000].join("|");
+    let timed = |timeout| Regex::builder(&alts).compile_timeout(timeout).build();
+    assert_eq!(
+        timed(Duration::ZERO).unwrap_err(),
+        RegexError::CompileTimeLimitOver
+    );
+    assert!(timed(Duration::from_secs(60)).unwrap().is_match("xabcx"));
+}
+
+#[test]
+fn syntax_error_span_and_kind() {
+    let err = Regex::new(r"\d+(?<year>\d{4})-\k<yaer>").unwrap_err();
+    assert_eq!(err.kind(), Some(SyntaxErrorKind::Reference));
+    assert_eq!(err.span(), Some(21..25));
+    assert_eq!(
+        err.to_string(),
+        "syntax error: undefined name <yaer> reference\n    \\d+(?<year>\\d{4})-\\k<yaer>\n                         ^^^^"
+    );
+
+    let err = Regex::new(r"ab|*").unwrap_err();
+    assert_eq!(err.kind(), Some(SyntaxErrorKind::Quantifier));
+    assert_eq!(err.span(), Some(3..4));
+
+    let err = RegexBuilder::new("a # (\n  )")
+        .option(ferroni::oniguruma::ONIG_OPTION_EXTEND)
+        .build()
+        .unwrap_err();
+    assert_eq!(err.span(), Some(8..9));
+    assert!(err.to_string().ends_with("\n      )\n      ^"));
+
+    // Found after parsing: no location.
+    let err = Regex::new(r"(?<=a|\g<0>)").unwrap_err();
+    assert_eq!(err.span(), None);
+
+    let err = ferroni::parse(r"[a-\d]", &ferroni::regsyntax::OnigSyntaxOniguruma).unwrap_err();
+    assert_eq!(err.kind(), Some(SyntaxErrorKind::CharClass));
+    assert!(err.span().is_some());
+}
+
+#[test]
+fn validate_describes_pattern() {
+    use ferroni::encodings::ascii::ONIG_ENCODING_ASCII;
+    use ferroni::encodings::utf8::ONIG_ENCODING_UTF8;
+    use ferroni::regsyntax::{OnigSyntaxOniguruma, OnigSyntaxPerl};
+    use ferroni::validate::Features;
+
+    let info = ferroni::validate(
+        br"(a)(?<x>b)(?=c)(?<!d)(?(<x>)e|\g<x>)(?{f})",
+        &OnigSyntaxOniguruma,
+        &ONIG_ENCODING_UTF8,
+    )
+    .unwrap();
+    // Plain groups do not capture once there are named ones
+    assert_eq!(info.captures, 1);
+    assert_eq!(info.names, [("x".to_string(), vec![1])]);
+    assert_eq!(
+        info.features,
+        Features::LOOK_AHEAD
+            | Features::LOOK_BEHIND
+            | Features::CONDITIONAL
+            | Features::SUBEXP_CALL
+            | Features::CALLOUT
+    );
+
+    let info = ferroni::validate(br"(a+)\1?", &OnigSyntaxPerl, &ONIG_ENCODING_ASCII).unwrap();
+    assert_eq!(info.captures, 1);
+    assert!(info.names.is_empty());
+    assert_eq!((info.min_len, info.max_len), (1, None));
+    assert_eq!(info.features, Features::BACKREF);
+
+    let err =
+        ferroni::validate(br"(?<x>a)\k<y>", &OnigSyntaxOniguruma, &ONIG_ENCODING_UTF8).unwrap_err();
+    assert_eq!(err.kind(), Some(SyntaxErrorKind::Reference));
+    assert_eq!(err.span(), Some(10..11));
+    assert!(ferroni::validate(b"\xff", &OnigSyntaxOniguruma, &ONIG_ENCODING_UTF8).is_err());
+}
+
 // === Regex::is_match ===
 
 #[test]
@@ -126,6 +401,26 @@ fn captures_len() {
     assert_eq!(re.captures_len(), 4);
 }
 
+#[test]
+fn group_parents() {
+    let re = Regex::new(r"((a)(?=(b))(c(d)?)+)(e)").unwrap();
+    assert_eq!(
+        re.group_parents(),
+        [None, None, Some(1), Some(1), Some(1), Some(4), None]
+    );
+
+    // Unnamed groups stop capturing once a named group exists.
+    let re = Regex::new(r"(?<o>(x)(?<i>y))").unwrap();
+    assert_eq!(re.group_parents(), [None, None, Some(1)]);
+
+    // Conditionals, calls to the whole pattern and literals.
+    let re = Regex::new(r"(a)?(?(1)(b)|(c))(?:x\g<0>)?").unwrap();
+    assert_eq!(re.group_parents(), [None, None, None, None]);
+    let re = Regex::new(r"(a(?(\w)(x)|(y)))").unwrap();
+    assert_eq!(re.group_parents(), [None, None, Some(1), Some(1)]);
+    assert_eq!(Regex::literal("(a)").unwrap().group_parents(), [None]);
+}
+
 #[test]
 fn captures_iter() {
     let re = Regex::new(r"(a)(b)").unwrap();
@@ -137,6 +432,187 @@ fn captures_iter() {
     assert_eq!(items[2].unwrap().as_str(), "b");
 }
 
+#[test]
+fn captures_to_spans() {
+    let re = Regex::new(r"(?<a>x)|(?<a>y)(?<b>z)?").unwrap();
+    let spans = re.captures("--y").unwrap().to_spans();
+    assert_eq!(spans.spans, vec![Some((2, 3)), None, Some((2, 3)), None]);
+    assert_eq!(
+        spans.names,
+        vec![("a".to_string(), vec![1, 2]), ("b".to_string(), vec![3])]
+    );
+    assert_eq!(spans.name("a"), Some(2..3));
+    assert_eq!(spans.get(3), None);
+    assert_eq!(spans.len(), 4);
+}
+
+#[test]
+fn captures_to_map() {
+    use ferroni::oniguruma::{OnigRegion, ONIG_OPTION_NONE};
+    use ferroni::regexec::onig_search;
+
+    let re = Regex::new(r"(?<a>x)|(?<a>y)(?<b>z)?").unwrap();
+    let map = re.captures("--y").unwrap().to_map();
+    let mut entries = map.into_iter().collect::<Vec<_>>();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    let expected = [("a".to_string(), Some(2..3)), ("b".to_string(), None)];
+    assert_eq!(entries, expected);
+    assert!(Regex::new(r"(x)")
+        .unwrap()
+        .captures("x")
+        .unwrap()
+        .to_map()
+        .is_empty());
+
+    // The same from a region filled by the C-style API
+    let text = b"xz";
+    let (r, region) = onig_search(
+        re.as_raw(),
+        text,
+        text.len(),
+        0,
+        text.len(),
+        Some(OnigRegion::new()),
+        ONIG_OPTION_NONE,
+    );
+    assert_eq!(r, 0);
+    let map = region.unwrap().named_spans(re.as_raw());
+    assert_eq!(map["a"], Some(0..1));
+    assert_eq!(map["b"], None);
+}
+
+#[test]
+fn region_spans_roundtrip() {
+    use ferroni::oniguruma::{OnigRegion, ONIGERR_INVALID_ARGUMENT};
+
+    let spans = vec![Some((1, 5)), None, Some((2, 2))];
+    let region = OnigRegion::from_spans(&spans).unwrap();
+    assert_eq!(region.num_regs, 3);
+    assert_eq!(region.beg[1], -1);
+    assert_eq!(Vec::from(&region), spans);
+    assert_eq!(
+        OnigRegion::from_spans(&[Some((0, u32::MAX))]).err(),
+        Some(ONIGERR_INVALID_ARGUMENT)
+    );
+}
+
+#[test]
+fn region_ranges_and_slices() {
+    use ferroni::oniguruma::OnigRegion;
+
+    let region = OnigRegion::from_spans(&[Some((1, 5)), None, Some((2, 2))]).unwrap();
+    assert_eq!(region.get(0), Some(1..5));
+    assert_eq!(region.get(1), None);
+    assert_eq!(region.get(3), None);
+    assert_eq!(
+        region.iter().collect::<Vec<_>>(),
+        vec![Some(1..5), None, Some(2..2)]
+    );
+
+    let haystack = b"abcdef";
+    assert_eq!(region.slice(0, haystack), Some(&b"bcde"[..]));
+    assert_eq!(region.slice(1, haystack), None);
+    assert_eq!(region.slice(2, haystack), Some(&b""[..]));
+    assert_eq!(region.slice(0, b"abc"), None);
+}
+
+#[test]
+fn char_offsets() {
+    use ferroni::scanner::{Scanner, ScannerFindOptions};
+
+    let text = "ä = \"💻\" + x";
+    let chars = CharIndex::utf8(text, CharUnit::Char);
+    let utf16 = CharIndex::utf8(text, CharUnit::Utf16);
+
+    let re = Regex::new(r#"(\w+) = "([^"]*)"(?: \+ (\d+))?"#).unwrap();
+    let caps = re.captures(text).unwrap();
+    let m = caps.get(2).unwrap();
+    assert_eq!(m.range(), 6..10);
+    assert_eq!((m.char_start(&chars), m.char_end(&chars)), (5, 6));
+    assert_eq!(m.char_range(&utf16), 5..7);
+
+    let words: Vec<_> = Regex::new(r"\S+")
+        .unwrap()
+        .find_iter(text)
+        .map(|m| m.char_range(&utf16))
+        .collect();
+    assert_eq!(words, [0..1, 2..3, 4..8, 9..10, 11..12]);
+
+    let mut scanner = Scanner::new(&[r#""(.)""#, r"(\d)|(x)"]).unwrap();
+    let (_, region) = scanner
+        .find_next_match_region(text, 0, ScannerFindOptions::NONE)
+        .unwrap();
+    assert_eq!(
+        region.char_spans(&utf16).collect::<Vec<_>>(),
+        [Some(4..8), Some(5..7)]
+    );
+    let (_, region) = scanner
+        .find_next_match_region(text, 12, ScannerFindOptions::NONE)
+        .unwrap();
+    assert_eq!(
+        region.char_spans(&chars).collect::<Vec<_>>(),
+        [Some(10..11), None, Some(10..11)]
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn capture_spans_serde_roundtrip() {
+    use ferroni::api::CaptureSpans;
+    use ferroni::oniguruma::OnigRegion;
+
+    let re = Regex::new(r"(?<k>\w+)=(?<v>\d+)?").unwrap();
+    let spans = re.captures("key=").unwrap().to_spans();
+    let json = serde_json::to_string(&spans).unwrap();
+    assert_eq!(
+        json,
+        r#"{"spans":[[0,4],[0,3],null],"names":[["k",[1]],["v",[2]]]}"#
+    );
+    assert_eq!(serde_json::from_str::<CaptureSpans>(&json).unwrap(), spans);
+
+    let region: OnigRegion = serde_json::from_str("[[0,4],null]").unwrap();
+    assert_eq!(serde_json::to_string(&region).unwrap(), "[[0,4],null]");
+    assert!(serde_json::from_str::<OnigRegion>("[[0,4294967295]]").is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn match_captures_and_errors_serialize() {
+    use ferroni::api::CaptureSpans;
+    use ferroni::scanner::ScannerMatch;
+
+    let re = Regex::new(r"(?<n>\d+)").unwrap();
+    let m = re.find("ab 12").unwrap();
+    let json = serde_json::to_string(&m).unwrap();
+    assert_eq!(json, r#"{"start":3,"end":5}"#);
+    assert_eq!(
+        serde_json::from_str::<std::ops::Range<usize>>(&json).unwrap(),
+        3..5
+    );
+
+    let caps = re.captures("ab 12").unwrap();
+    let json = serde_json::to_string(&caps).unwrap();
+    assert_eq!(
+        serde_json::from_str::<CaptureSpans>(&json).unwrap(),
+        caps.to_spans()
+    );
+
+    let err = Regex::new("(").unwrap_err();
+    let json = serde_json::to_string(&err).unwrap();
+    assert_eq!(serde_json::from_str::<RegexError>(&json).unwrap(), err);
+
+    let mut scanner = Scanner::new(&["b(c)"]).unwrap();
+    let sm = scanner
+        .find_next_match("abc", 0, ScannerFindOptions::NONE)
+        .unwrap();
+    let json = serde_json::to_string(&sm).unwrap();
+    assert_eq!(
+        json,
+        r#"{"index":0,"capture_indices":[{"start":1,"end":3,"length":2},{"start":2,"end":3,"length":1}]}"#
+    );
+    assert_eq!(serde_json::from_str::<ScannerMatch>(&json).unwrap(), sm);
+}
+
 // === Regex::find_iter ===
 
 #[test]
@@ -175,6 +651,145 @@ fn find_iter_overlapping_region() {
     assert_eq!(results, vec!["a", "bb", "ccc"]);
 }
 
+#[test]
+fn find_ending_at() {
+    let span = |p: &str, text: &str, end: usize| {
+        let re = Regex::new(p).unwrap();
+        re.find_ending_at(text, end).map(|m| m.range())
+    };
+    // Backtracks into the alternative that ends at `end`
+    assert_eq!(span("ab|abc", "xabcd", 4), Some(1..4));
+    assert_eq!(span("ab|abc", "xabcd", 3), Some(1..3));
+    // Leftmost start wins
+    assert_eq!(span(r"a+", "baaab", 4), Some(1..4));
+    assert_eq!(span(r"[a-z]{1,3}", "abcdef", 6), Some(3..6));
+    // Anchors and boundaries see the text on both sides of `end`
+    assert_eq!(span(r"\bfoo\b", "foobar foo", 3), None);
+    assert_eq!(span(r"\bfoo\b", "foobar foo", 10), Some(7..10));
+    assert_eq!(span(r"o$", "foo\nbar", 3), Some(2..3));
+    // Empty matches and multi-byte characters
+    assert_eq!(span(r"x*", "abc", 2), Some(2..2));
+    assert_eq!(span(r".", "aé", 3), Some(1..3));
+    assert_eq!(span(r"é", "aé", 2), None);
+
+    // A bounded pattern is only tried near `end`
+    let text = "#".repeat(100_000) + "0x1F";
+    let re = Regex::new(r"0x[0-9A-F]{1,8}").unwrap();
+    assert_eq!(
+        re.find_ending_at(&text, text.len()).unwrap().start(),
+        100_000
+    );
+    assert!(re.find_ending_at(&text, text.len() - 1).is_some());
+    assert!(re.find_ending_at(&text, 50_000).is_none());
+}
+
+#[test]
+fn find_at_and_captures_at() {
+    let span = |p: &str, text: &str, at: usize| {
+        let re = Regex::new(p).unwrap();
+        re.find_at(text, at).map(|m| m.range())
+    };
+    // Look-behind and word boundaries see the text before `at`
+    assert_eq!(span(r"(?<=a)b", "ab", 1), Some(1..2));
+    assert_eq!(span(r"\bb", "ab b", 1), Some(3..4));
+    // `^` and `\A` are not at `at`; `\G` is
+    assert_eq!(span(r"^b", "ab\nb", 1), Some(3..4));
+    assert_eq!(span(r"\Ab", "ab", 1), None);
+    assert_eq!(span(r"\Gb", "abb", 1), Some(1..2));
+    assert_eq!(span(r"\Gb", "acb", 1), None);
+    // Empty match at the end, and a start past every match
+    assert_eq!(span(r"$", "ab", 2), Some(2..2));
+    assert_eq!(span(r"a", "ab", 1), None);
+
+    let re = Regex::new(r"(?<k>\w+)=(?<v>\d+)").unwrap();
+    let text = "x=1 y=22";
+    let caps = re.captures_at(text, 1).unwrap();
+    assert_eq!(caps.get(0).unwrap().range(), 4..8);
+    assert_eq!(caps.name("v").unwrap().as_str(), "22");
+    assert_eq!(
+        re.captures_at(text, 0).unwrap().to_spans(),
+        re.captures(text).unwrap().to_spans()
+    );
+}
+
+#[test]
+#[should_panic(expected = "is past the end of the haystack")]
+fn find_at_past_end_panics() {
+    Regex::new("a").unwrap().find_at("abc", 4);
+}
+
+#[test]
+fn find_at_most_n_agrees_with_find_iter() {
+    for (pattern, text) in [
+        (r"\w+", "a bb ccc"),
+        (r"x*", "axxb"),
+        (r"\b", "ab cd"),
+        (r"z", "abc"),
+    ] {
+        let re = Regex::new(pattern).unwrap();
+        let all: Vec<_> = re.find_iter(text).map(|m| m.range()).collect();
+        for n in 0..=all.len() + 1 {
+            let (matches, more) = re.find_at_most_n(text, n);
+            let got: Vec<_> = matches.iter().map(|m| m.range()).collect();
+            assert_eq!(got, all[..n.min(all.len())], "{pattern} n={n}");
+            assert_eq!(more, n < all.len(), "{pattern} n={n}");
+        }
+    }
+}
+
+#[test]
+fn captures_at_most_n() {
+    let re = Regex::new(r"(?<k>\w)=(?<v>\d)?").unwrap();
+    let (caps, more) = re.captures_at_most_n("a=1 b= c=3", 2);
+    assert!(more);
+    assert_eq!(caps.len(), 2);
+    assert_eq!(caps[0].name("k").unwrap().as_str(), "a");
+    assert_eq!(caps[0].name("v").unwrap().as_str(), "1");
+    assert_eq!(caps[1].name("k").unwrap().as_str(), "b");
+    assert!(caps[1].name("v").is_none());
+
+    let (caps, more) = re.captures_at_most_n("a=1 b= c=3", 5);
+    assert_eq!(caps.len(), 3);
+    assert!(!more);
+}
+
+#[test]
+fn find_all_within_stops_at_budget() {
+    let re = Regex::new(r"\w+").unwrap();
+    let size = std::mem::size_of::<Match>();
+    let all = re.find_all_within("a bb ccc", usize::MAX).unwrap();
+    assert_eq!(all.len(), 3);
+    assert_eq!(re.find_all_within("a bb ccc", 3 * size).unwrap().len(), 3);
+
+    let err = re.find_all_within("a bb ccc", 3 * size - 1).unwrap_err();
+    assert_eq!(err.limit, 3 * size - 1);
+    let got: Vec<_> = err.partial.iter().map(|m| m.as_str()).collect();
+    assert_eq!(got, ["a", "bb"]);
+
+    let err = re.find_all_within("a bb ccc", 0).unwrap_err();
+    assert!(err.partial.is_empty());
+    assert!(re.find_all_within("", 0).unwrap().is_empty());
+}
+
+#[test]
+fn captures_all_within_counts_group_spans() {
+    let few = Regex::new(r"(\w)=(\d)").unwrap();
+    let many = Regex::new(r"(\w)=(\d)()()()()").unwrap();
+    let text = "a=1 b=2 c=3";
+    let one = few.captures_all_within(text, usize::MAX).unwrap();
+    assert_eq!(one.len(), 3);
+    assert_eq!(one[2].get(1).unwrap().as_str(), "c");
+
+    // A budget that fits all three matches of `few` is too small for the
+    // wider region of `many`.
+    let budget = 3 * (std::mem::size_of::<Captures>() + 3 * 2 * std::mem::size_of::<i32>());
+    assert_eq!(few.captures_all_within(text, budget).unwrap().len(), 3);
+    let err = many.captures_all_within(text, budget).unwrap_err();
+    assert!(err.partial.len() < 3);
+    assert_eq!(err.partial[0].get(2).unwrap().as_str(), "1");
+    assert!(err.to_string().contains(&budget.to_string()));
+}
+
 // === RegexBuilder ===
 
 #[test]
@@ -187,6 +802,49 @@ fn builder_case_insensitive() {
     assert!(re.is_match("HeLlO"));
 }
 
+#[test]
+fn builder_ascii_case_insensitive() {
+    let ascii = |p: &str| {
+        Regex::builder(p)
+            .ascii_case_insensitive(true)
+            .build()
+            .unwrap()
+    };
+    let full = |p: &str| Regex::builder(p).case_insensitive(true).build().unwrap();
+
+    // ASCII letters fold as with full case folding
+    for (p, text) in [
+        ("hello", "HeLLo"),
+        ("[a-f]+x", "-- dEaDbEeFX"),
+        ("[^k]", "K"),
+        (r"\Aab|cd\z", "xCD"),
+        ("(?i:q)z", "QZ"),
+    ] {
+        assert_eq!(
+            ascii(p).find(text).map(|m| m.range()),
+            full(p).find(text).map(|m| m.range()),
+            "{p} on {text}"
+        );
+    }
+    // Nothing else does
+    assert!(ascii("k").is_match("K"));
+    assert!(!ascii("k").is_match("\u{212A}")); // KELVIN SIGN
+    assert!(full("k").is_match("\u{212A}"));
+    assert!(!ascii("[a-zé]").is_match("É"));
+    assert!(!ascii(r"[\p{Ll}]").is_match("Σ"));
+    assert!(full(r"[\p{Ll}]").is_match("Σ"));
+    assert!(!ascii("ss").is_match("ß"));
+    assert!(ascii("ß").is_match("ß"));
+
+    // false turns case-insensitive matching off again
+    let re = Regex::builder("a")
+        .ascii_case_insensitive(true)
+        .ascii_case_insensitive(false)
+        .build()
+        .unwrap();
+    assert!(!re.is_match("A"));
+}
+
 #[test]
 fn builder_dot_matches_newline() {
     let re = Regex::builder(r"a.b")
@@ -235,6 +893,110 @@ fn builder_chaining() {
     assert!(re.is_match("HELLO WORLD"));
 }
 
+#[test]
+fn builder_literal_case_insensitive() {
+    let re = Regex::builder("a.B*")
+        .literal(true)
+        .case_insensitive(true)
+        .extended(true)
+        .build()
+        .unwrap();
+    assert_eq!(re.find("xA.b*").unwrap().as_str(), "A.b*");
+    assert!(!re.is_match("aXbb"));
+}
+
+#[test]
+fn builder_turkish_azeri_case_fold() {
+    use ferroni::oniguruma::{ONIGENC_CASE_FOLD_MIN, ONIGENC_CASE_FOLD_TURKISH_AZERI};
+    let tr = ONIGENC_CASE_FOLD_MIN | ONIGENC_CASE_FOLD_TURKISH_AZERI;
+    let build = |pat: &str, literal: bool| {
+        Regex::builder(pat)
+            .literal(literal)
+            .case_insensitive(true)
+            .case_fold_flag(tr)
+            .build()
+            .unwrap()
+    };
+
+    for (pat, yes, no) in [
+        ("I", "ı", "i"),
+        ("i", "İ", "I"),
+        ("ı", "I", "i"),
+        ("[I]", "ı", "i"),
+        ("[a-z]", "İ", "I"),
+    ] {
+        let re = build(pat, false);
+        assert!(re.is_match(yes), "{pat} ~ {yes}");
+        assert!(!re.is_match(no), "{pat} !~ {no}");
+    }
+    assert!(build("Diyarbakır", true).is_match("DİYARBAKIR"));
+
+    // Without the flag the default I <-> i pairing applies.
+    let re = Regex::builder("I").case_insensitive(true).build().unwrap();
+    assert!(re.is_match("i") && !re.is_match("ı"));
+}
+
+#[test]
+fn regex_reports_case_fold_flag() {
+    use ferroni::oniguruma::{ONIGENC_CASE_FOLD_MIN, ONIGENC_CASE_FOLD_TURKISH_AZERI};
+    use ferroni::regcomp::onig_get_default_case_fold_flag;
+    let tr = ONIGENC_CASE_FOLD_MIN | ONIGENC_CASE_FOLD_TURKISH_AZERI;
+    let re = Regex::builder("i").case_fold_flag(tr).build().unwrap();
+    assert_eq!(re.case_fold_flag(), tr);
+    let re = Regex::new("i").unwrap();
+    assert_eq!(re.case_fold_flag(), onig_get_default_case_fold_flag());
+}
+
+#[test]
+fn text_segment_precedence() {
+    use ferroni::oniguruma::OnigTextSegment::{ExtendedGraphemeCluster, Word};
+    use ferroni::regcomp::{onig_get_default_text_segment, onig_set_default_text_segment};
+    let text = "ab cd";
+    let first = |re: &Regex| re.find(text).unwrap().as_str().to_string();
+
+    assert_eq!(onig_get_default_text_segment(), ExtendedGraphemeCluster);
+    let re = Regex::new(r"\X").unwrap();
+    assert_eq!(re.text_segment(), ExtendedGraphemeCluster);
+    assert_eq!(first(&re), "a");
+
+    // The global default only applies to patterns compiled after it is set.
+    let cache = ferroni::api::RegexCache::new(4);
+    let cached = cache.get(r"\X").unwrap();
+    onig_set_default_text_segment(Word);
+    let word = Regex::new(r"\X").unwrap();
+    let cached_word = cache.get(r"\X").unwrap();
+    let builder = Regex::builder(r"\X")
+        .text_segment(ExtendedGraphemeCluster)
+        .build()
+        .unwrap();
+    let inline = Regex::new(r"(?y{g})\X").unwrap();
+    onig_set_default_text_segment(ExtendedGraphemeCluster);
+
+    assert_eq!(word.text_segment(), Word);
+    assert_eq!(first(&word), "ab");
+    // ...and the compile cache does not hand out the other one
+    assert_eq!(first(&cached), "a");
+    assert_eq!(first(&cached_word), "ab");
+    assert_eq!(cache.len(), 2);
+    assert_eq!(first(&re), "a");
+    assert_eq!(builder.text_segment(), ExtendedGraphemeCluster);
+    assert_eq!(first(&builder), "a");
+    assert_eq!(first(&inline), "a");
+
+    let re = Regex::builder(r"(?y{g})\X")
+        .text_segment(Word)
+        .build()
+        .unwrap();
+    assert_eq!(re.text_segment(), Word);
+    assert_eq!(first(&re), "a");
+}
+
+#[test]
+fn literal_with_whitespace_and_escapes() {
+    let re = Regex::literal(r"C:\dir name\").unwrap();
+    assert!(re.is_match(r"path C:\dir name\file"));
+}
+
 // === Byte API ===
 
 #[test]
@@ -252,6 +1014,309 @@ fn is_match_bytes() {
     assert!(!re.is_match_bytes(b"goodbye"));
 }
 
+// === Literal pre-scan ===
+
+fn long_log(tail: &str) -> String {
+    let mut log = "lorem ipsum dolor sit amet\n".repeat(LITERAL_PRESCAN_MIN_LEN / 27 + 1);
+    log.push_str(tail);
+    log
+}
+
+#[test]
+fn literal_prescan_agrees_with_plain_search() {
+    let patterns = [
+        r"[a-z]{1,8} ERROR \d+",
+        r"\d{2}:\d\d ERROR",
+        r"(?<=disk )ERROR",
+        r"ERROR.*timeout",
+        r"(?i)error",
+        r"^ERROR$",
+        r"ERR(OR|ROR)",
+    ];
+    let texts = [
+        long_log("12:34 disk ERROR 42\n"),
+        long_log("ERROR\n"),
+        long_log("no alerts here\n"),
+        long_log("ERROR after timeout"),
+    ];
+    for pattern in patterns {
+        let plain = Regex::new(pattern).unwrap();
+        let prescan = Regex::builder(pattern)
+            .literal_prescan(true)
+            .build()
+            .unwrap();
+        for text in &texts {
+            assert_eq!(
+                prescan.is_match(text),
+                plain.is_match(text),
+                "pattern {:?}",
+                pattern
+            );
+        }
+    }
+}
+
+#[test]
+fn literal_prescan_stats() {
+    let re = Regex::builder(r"\d{2}:\d\d ERROR")
+        .literal_prescan(true)
+        .build()
+        .unwrap();
+
+    let (found, stats) = re.is_match_with_stats(&long_log("12:34 ERROR\n"));
+    assert!(found);
+    assert!(stats.prescan_used);
+    assert_eq!(stats.prescan_windows, 1);
+
+    // Literal absent: the matcher never runs.
+    let (found, stats) = re.is_match_with_stats(&long_log("12:34 WARN\n"));
+    assert!(!found);
+    assert!(stats.prescan_used);
+    assert_eq!(stats.vm_searches, 0);
+
+    // Short haystacks and builders without the option skip the pre-scan.
+    let (_, stats) = re.is_match_with_stats("12:34 ERROR");
+    assert!(!stats.prescan_used);
+    let plain = Regex::new(r"\d{2}:\d\d ERROR").unwrap();
+    let (_, stats) = plain.is_match_with_stats(&long_log("12:34 ERROR\n"));
+    assert!(!stats.prescan_used);
+    assert_eq!(stats.vm_searches, 1);
+}
+
+// === Haystack length ===
+
+#[test]
+fn max_haystack_len_matches_engine_positions() {
+    assert_eq!(MAX_HAYSTACK_LEN, i32::MAX as Position);
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+#[should_panic(expected = "exceeds MAX_HAYSTACK_LEN")]
+fn haystack_over_2gb_panics() {
+    // Zeroed allocation: pages are never touched, the length check fires first.
+    let text = vec![0u8; MAX_HAYSTACK_LEN + 1];
+    Regex::new("a").unwrap().is_match_bytes(&text);
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+#[should_panic(expected = "exceeds MAX_HAYSTACK_LEN")]
+fn find_iter_over_2gb_panics() {
+    let text = vec![0u8; MAX_HAYSTACK_LEN + 1];
+    let _ = Regex::new("a").unwrap().find_iter_bytes(&text);
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn every_search_method_checks_haystack_len() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let bytes = vec![0u8; MAX_HAYSTACK_LEN + 1];
+    // SAFETY: NUL bytes are valid UTF-8; validating would touch every page.
+    let text = unsafe { std::str::from_utf8_unchecked(&bytes) };
+    let re = Regex::new("a").unwrap();
+    let bre = ferroni::bytes::Regex::new(b"a").unwrap();
+    let t = Template::new("b", &re).unwrap();
+
+    let methods: Vec<(&str, Box<dyn Fn()>)> = vec![
+        (
+            "find",
+            Box::new(|| {
+                let _ = re.find(text);
+            }),
+        ),
+        (
+            "find_bytes",
+            Box::new(|| {
+                let _ = re.find_bytes(&bytes);
+            }),
+        ),
+        (
+            "find_at",
+            Box::new(|| {
+                let _ = re.find_at(text, 0);
+            }),
+        ),
+        (
+            "find_ending_at",
+            Box::new(|| {
+                let _ = re.find_ending_at(text, 0);
+            }),
+        ),
+        (
+            "is_match",
+            Box::new(|| {
+                let _ = re.is_match(text);
+            }),
+        ),
+        (
+            "is_match_with_stats",
+            Box::new(|| {
+                let _ = re.is_match_with_stats(text);
+            }),
+        ),
+        (
+            "captures",
+            Box::new(|| {
+                let _ = re.captures(text);
+            }),
+        ),
+        (
+            "captures_bytes",
+            Box::new(|| {
+                let _ = re.captures_bytes(&bytes);
+            }),
+        ),
+        (
+            "captures_at",
+            Box::new(|| {
+                let _ = re.captures_at(text, 0);
+            }),
+        ),
+        (
+            "find_iter",
+            Box::new(|| {
+                let _ = re.find_iter(text);
+            }),
+        ),
+        (
+            "split",
+            Box::new(|| {
+                let _ = re.split(text);
+            }),
+        ),
+        (
+            "split_inclusive",
+            Box::new(|| {
+                let _ = re.split_inclusive(text);
+            }),
+        ),
+        (
+            "split_keep",
+            Box::new(|| {
+                let _ = re.split_keep(text);
+            }),
+        ),
+        (
+            "replace",
+            Box::new(|| {
+                let _ = re.replace(text, &t);
+            }),
+        ),
+        (
+            "replace_all",
+            Box::new(|| {
+                let _ = re.replace_all(text, &t);
+            }),
+        ),
+        (
+            "find_at_most_n",
+            Box::new(|| {
+                let _ = re.find_at_most_n(text, 1);
+            }),
+        ),
+        (
+            "find_at_most_n_bytes",
+            Box::new(|| {
+                let _ = re.find_at_most_n_bytes(&bytes, 1);
+            }),
+        ),
+        (
+            "captures_at_most_n",
+            Box::new(|| {
+                let _ = re.captures_at_most_n(text, 1);
+            }),
+        ),
+        (
+            "captures_at_most_n_bytes",
+            Box::new(|| {
+                let _ = re.captures_at_most_n_bytes(&bytes, 1);
+            }),
+        ),
+        (
+            "find_all_within",
+            Box::new(|| {
+                let _ = re.find_all_within(text, 1);
+            }),
+        ),
+        (
+            "find_all_within_bytes",
+            Box::new(|| {
+                let _ = re.find_all_within_bytes(&bytes, 1);
+            }),
+        ),
+        (
+            "captures_all_within",
+            Box::new(|| {
+                let _ = re.captures_all_within(text, 1);
+            }),
+        ),
+        (
+            "captures_all_within_bytes",
+            Box::new(|| {
+                let _ = re.captures_all_within_bytes(&bytes, 1);
+            }),
+        ),
+        (
+            "bytes::is_match",
+            Box::new(|| {
+                let _ = bre.is_match(&bytes);
+            }),
+        ),
+        (
+            "bytes::find",
+            Box::new(|| {
+                let _ = bre.find(&bytes);
+            }),
+        ),
+        (
+            "bytes::find_at",
+            Box::new(|| {
+                let _ = bre.find_at(&bytes, 0);
+            }),
+        ),
+        (
+            "bytes::find_iter",
+            Box::new(|| {
+                let _ = bre.find_iter(&bytes);
+            }),
+        ),
+        (
+            "bytes::captures",
+            Box::new(|| {
+                let _ = bre.captures(&bytes);
+            }),
+        ),
+        (
+            "bytes::captures_at",
+            Box::new(|| {
+                let _ = bre.captures_at(&bytes, 0);
+            }),
+        ),
+        (
+            "bytes::replace",
+            Box::new(|| {
+                let _ = bre.replace(&bytes, &t);
+            }),
+        ),
+        (
+            "bytes::replace_all",
+            Box::new(|| {
+                let _ = bre.replace_all(&bytes, &t);
+            }),
+        ),
+    ];
+    for (name, call) in &methods {
+        let err = catch_unwind(AssertUnwindSafe(call)).expect_err(name);
+        let msg = err
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .unwrap_or("");
+        assert!(msg.contains("exceeds MAX_HAYSTACK_LEN"), "{name}: {msg}");
+    }
+}
+
 // === RegexError ===
 
 #[test]
@@ -357,3 +1422,907 @@ fn captures_debug_impl() {
     let dbg = format!("{:?}", caps);
     assert!(!dbg.is_empty());
 }
+
+#[test]
+fn newline_verbs() {
+    let find =
+        |pattern: &str, text: &str| Regex::new(pattern).unwrap().find(text).map(|m| m.range());
+
+    // CR: `^` after a lone CR, `.*` stops at CR
+    assert_eq!(find(r"^b", "a\rb"), None);
+    assert_eq!(find(r"(*CR)^b", "a\rb"), Some(2..3));
+    assert_eq!(find(r"(*CR)^\n", "a\r\n"), Some(2..3));
+    assert_eq!(find(r"(*CR)a.*", "abc\rdef"), Some(0..3));
+
+    // CRLF: only the pair is a newline
+    assert_eq!(find(r"(*CRLF)a.$", "ab\r\n"), Some(0..2));
+    assert_eq!(find(r"(*CRLF)a.", "a\r\n"), None);
+    assert_eq!(find(r"(*CRLF)a.", "a\nb"), Some(0..2));
+    assert_eq!(find(r"ab\Z", "ab\r\n"), None);
+    assert_eq!(find(r"(*CRLF)ab\Z", "ab\r\n"), Some(0..2));
+
+    // ANYCRLF: CR, LF and CRLF, never splitting a CRLF
+    assert_eq!(find(r"(*ANYCRLF)^c", "a\r\nc"), Some(3..4));
+    assert_eq!(find(r"(*ANYCRLF)^\n", "a\r\n"), None);
+    assert_eq!(find(r"(*ANYCRLF)a$", "a\rb"), Some(0..1));
+
+    // ANY: also VT, FF, NEL, LS and PS
+    assert_eq!(find(r"(*ANY)^x", "a\u{2028}x"), Some(4..5));
+    assert_eq!(find(r"a.b", "a\u{85}b"), Some(0..4));
+    assert_eq!(find(r"(*ANY)a.b", "a\u{85}b"), None);
+    assert_eq!(find(r"(*ANY).*z", "ab\u{0c}cz"), Some(3..5));
+
+    // The last verb wins; LF restores the default
+    assert_eq!(find(r"(*CR)(*LF)^b", "a\rb"), None);
+
+    // A builder default that a verb overrides
+    let re = Regex::builder(r"^b")
+        .newline(ferroni::oniguruma::OnigNewline::Cr)
+        .build()
+        .unwrap();
+    assert!(re.is_match("a\rb"));
+    let re = Regex::builder(r"(*LF)^b")
+        .newline(ferroni::oniguruma::OnigNewline::Cr)
+        .build()
+        .unwrap();
+    assert!(!re.is_match("a\rb"));
+}
+
+#[test]
+fn newline_crlf_option() {
+    use ferroni::oniguruma::{OnigNewline, ONIG_OPTION_NEWLINE_CRLF, ONIG_OPTION_NONE};
+
+    // Windows log lines: `$` ends each line before its CRLF
+    let log = "INFO start\r\nWARN disk\r\nINFO done\r\n";
+    let lines = |re: &Regex| -> Vec<&str> { re.find_iter(log).map(|m| m.as_str()).collect() };
+    let lf = Regex::new(r"^WARN .*$").unwrap();
+    assert_eq!(lines(&lf), ["WARN disk\r"]);
+    let crlf = Regex::builder(r"^WARN .*$")
+        .option(ONIG_OPTION_NEWLINE_CRLF)
+        .build()
+        .unwrap();
+    assert_eq!(lines(&crlf), ["WARN disk"]);
+    let re = Regex::builder(r"\w+$")
+        .option(ONIG_OPTION_NEWLINE_CRLF)
+        .build()
+        .unwrap();
+    assert_eq!(lines(&re), ["start", "disk", "done"]);
+
+    // The option bit works for onig_new() callers too
+    let reg = ferroni::regcomp::onig_new(
+        b"k\\Z",
+        ONIG_OPTION_NEWLINE_CRLF,
+        &ferroni::encodings::utf8::ONIG_ENCODING_UTF8,
+        &ferroni::regsyntax::OnigSyntaxOniguruma,
+    )
+    .unwrap();
+    let text = b"disk\r\n";
+    let (r, _) = ferroni::regexec::onig_search(
+        &reg,
+        text,
+        text.len(),
+        0,
+        text.len(),
+        None,
+        ONIG_OPTION_NONE,
+    );
+    assert_eq!(r, 3);
+
+    // RegexBuilder::newline and a verb in the pattern take precedence
+    let re = Regex::builder(r"^b")
+        .option(ONIG_OPTION_NEWLINE_CRLF)
+        .newline(OnigNewline::Cr)
+        .build()
+        .unwrap();
+    assert!(re.is_match("a\rb"));
+    let re = Regex::builder(r"(*LF)a$")
+        .option(ONIG_OPTION_NEWLINE_CRLF)
+        .build()
+        .unwrap();
+    assert!(!re.is_match("a\r\n"));
+}
+
+#[test]
+fn general_newline_under_each_convention() {
+    use ferroni::oniguruma::OnigNewline;
+
+    // A document mixing Unix, Windows, old Mac and Unicode line ends
+    let doc = "a\nb\r\nc\rd\u{2028}e\r\n\r\nf";
+    let build = |pattern: &str, newline: OnigNewline| {
+        Regex::builder(pattern).newline(newline).build().unwrap()
+    };
+    let split = |newline: OnigNewline| -> Vec<&str> {
+        let mut lines = Vec::new();
+        let mut last = 0;
+        for m in build(r"\R", newline).find_iter(doc) {
+            lines.push(&doc[last..m.start()]);
+            last = m.end();
+        }
+        lines.push(&doc[last..]);
+        lines
+    };
+    let find =
+        |pattern: &str, newline: OnigNewline| build(pattern, newline).find(doc).map(|m| m.range());
+    let line_ends = |newline: OnigNewline| -> String {
+        build(r"(?m)\w$", newline)
+            .find_iter(doc)
+            .map(|m| m.as_str())
+            .collect()
+    };
+
+    // (convention, `d` + `\N*` and `d` + `.*`, words before `$`)
+    let cases = [
+        (OnigNewline::Lf, 7..13, "af"),
+        (OnigNewline::Cr, 7..12, "bcef"),
+        (OnigNewline::Crlf, 7..12, "bef"),
+        (OnigNewline::AnyCrlf, 7..12, "abcef"),
+        (OnigNewline::Any, 7..8, "abcdef"),
+    ];
+    for (newline, rest_of_line, ends) in cases {
+        // \R matches every line end under every convention, CRLF as one
+        assert_eq!(
+            split(newline),
+            ["a", "b", "c", "d", "e", "", "f"],
+            "{newline:?}"
+        );
+        assert_eq!(find(r"e\R+", newline), Some(11..16), "{newline:?}");
+        assert!(!build(r"\R{2}", newline).is_match("\r\n"), "{newline:?}");
+        // \N, `.` and `$` follow the convention
+        assert_eq!(
+            find(r"d\N*", newline),
+            Some(rest_of_line.clone()),
+            "{newline:?}"
+        );
+        assert_eq!(find(r"d.*", newline), Some(rest_of_line), "{newline:?}");
+        assert_eq!(line_ends(newline), ends, "{newline:?}");
+    }
+}
+
+#[test]
+fn name_table_and_group_name() {
+    let re = Regex::new(r"(a)(?<x>b)(?<y>c)(?<x>d)").unwrap();
+    let names: Vec<_> = re.name_table().collect();
+    assert_eq!(names, vec![("x", vec![1, 3]), ("y", vec![2])]);
+    assert_eq!(re.group_name(0), None);
+    assert_eq!(re.group_name(1), Some("x"));
+    assert_eq!(re.group_name(2), Some("y"));
+    assert_eq!(re.group_name(3), Some("x"));
+    assert_eq!(re.group_name(4), None);
+
+    let re = Regex::new(r"(a)(b)").unwrap();
+    assert_eq!(re.name_table().count(), 0);
+    assert_eq!(re.group_name(1), None);
+}
+
+#[test]
+fn capture_names_and_group_index() {
+    let re = Regex::new(r"(a)(?<x>b)(?<y>c)(?<x>d)").unwrap();
+    let names: Vec<_> = re
+        .capture_names()
+        .map(|(name, groups)| (name, groups.len(), groups.collect::<Vec<u32>>()))
+        .collect();
+    assert_eq!(names, [("x", 2, vec![1, 3]), ("y", 1, vec![2])]);
+    assert_eq!(re.group_index("x"), Some(1));
+    assert_eq!(re.group_index("y"), Some(2));
+    assert_eq!(re.group_index("z"), None);
+
+    // Group numbers index straight into the captures
+    let caps = re.captures("abcd").unwrap();
+    let firsts: Vec<_> = re
+        .capture_names()
+        .map(|(name, mut groups)| {
+            let first = groups.next().unwrap() as usize;
+            (name, caps.get(first).unwrap().as_str())
+        })
+        .collect();
+    assert_eq!(firsts, [("x", "b"), ("y", "c")]);
+
+    let re = Regex::new(r"(a)(b)").unwrap();
+    assert_eq!(re.capture_names().count(), 0);
+    assert_eq!(re.group_index("a"), None);
+}
+
+#[test]
+fn debug_dumps() {
+    let re = Regex::new(r"(?<x>a|b)*\k<x>c\z").unwrap();
+    let bytecode = re.debug_bytecode();
+    assert!(bytecode.contains("optimize: STR_FAST\n"));
+    assert!(bytecode.contains("anchor: [end-buf](1, inf)"));
+    assert!(bytecode.contains("exact: [c]: length: 1, dist: (0, inf)"));
+    assert!(bytecode.contains("   0: push:+8 => 8\n"));
+    assert!(bytecode.contains("   8: backref1\n"));
+    assert!(bytecode.ends_with("  11: end\n"));
+    assert_eq!(
+        re.debug_ast(),
+        "<list>\n   <quantifier>{0,-1}\n      <bag> memory:1\n         <alt>\n            \
+         <string>a\n            <string>b\n   <backref> 1\n   <string>c\n   <anchor> end buf\n"
+    );
+
+    let re = Regex::new(r"[xy]z|q").unwrap();
+    assert!(re.debug_bytecode().contains("map: n=2"));
+
+    // Literal regexes dump the tree they were compiled from, not a re-parse.
+    let re = Regex::literal(r"a(\").unwrap();
+    assert_eq!(re.debug_ast(), "<string>a(\\\n");
+}
+
+#[test]
+fn optimize_info() {
+    let info = Regex::new(r"[0-9]{2,4}xyz\z").unwrap().optimize_info();
+    assert_eq!(info.anchors, Anchors::END_BUF);
+    assert_eq!((info.min_len, info.max_len), (5, Some(7)));
+    assert_eq!(
+        info.prefilter,
+        Prefilter::Exact {
+            literal: b"xyz".to_vec(),
+            min_distance: 2,
+            max_distance: Some(4),
+        }
+    );
+    assert_eq!(info.first_bytes(), None);
+
+    let info = Regex::new(r"^(?:foo|bar)").unwrap().optimize_info();
+    assert_eq!(info.sub_anchors, Anchors::BEGIN_LINE);
+    assert_eq!(info.first_bytes(), Some(vec![b'b', b'f']));
+
+    let info = Regex::new(r"(?m).*x").unwrap().optimize_info();
+    assert!(info.anchors.contains(Anchors::ANYCHAR_INF_ML));
+    assert_eq!(info.max_len, None);
+
+    let info = Regex::new(r"a?").unwrap().optimize_info();
+    assert_eq!(info.prefilter, Prefilter::None);
+    assert_eq!((info.min_len, info.max_len), (0, Some(1)));
+}
+
+#[test]
+fn shift_or_backend() {
+    let backend = |p: &str| Regex::new(p).unwrap().optimize_info().backend;
+    assert_eq!(
+        backend(r"\\u[0-9a-fA-F]{4}"),
+        Backend::ShiftOr { positions: 6 }
+    );
+    assert_eq!(backend(r"[-+*/]="), Backend::ShiftOr { positions: 2 });
+    assert_eq!(
+        backend(r"(?-i:[0-9]{2})(?>:)(?D)\d"),
+        Backend::ShiftOr { positions: 4 }
+    );
+    assert_eq!(backend("é[0-9]"), Backend::ShiftOr { positions: 3 });
+    // Plain literal, capture, alternation, case folding, non-ASCII
+    for p in [
+        "abc",
+        r"(\d)\d",
+        r"a|\d",
+        r"(?i)x\d",
+        r"\d\d",
+        r"[é][0-9]",
+        r"^[0-9]",
+    ] {
+        assert_eq!(backend(p), Backend::Vm, "{p}");
+    }
+
+    // Same matches as the VM runs for the capturing version
+    let text = "é\\u00e9 + \\u12 x\\uABCD\\uabcdé 1+=2 \\u0041";
+    for p in [r"\\u[0-9a-fA-F]{4}", r"[-+*/]=", r"[a-f][0-9]", r"é\\"] {
+        let so = Regex::new(p).unwrap();
+        let vm = Regex::new(&format!("({p})")).unwrap();
+        assert_eq!(vm.optimize_info().backend, Backend::Vm);
+        let spans = |re: &Regex| re.find_iter(text).map(|m| m.range()).collect::<Vec<_>>();
+        assert_eq!(spans(&so), spans(&vm), "{p}");
+        for end in [5, 12, 13, text.len()] {
+            let hay = &text.as_bytes()[..end];
+            assert_eq!(so.is_match_bytes(hay), vm.is_match_bytes(hay), "{p} {end}");
+        }
+    }
+}
+
+#[test]
+fn analyze_complexity() {
+    use ferroni::analysis::{FindingKind, Risk};
+
+    let findings = |p: &str| {
+        let report = Regex::new(p).unwrap().analyze_complexity();
+        let found: Vec<_> = report
+            .findings
+            .iter()
+            .map(|f| (f.risk, f.kind, f.span.clone(), f.conflict.clone()))
+            .collect();
+        assert_eq!(
+            report.risk,
+            found.iter().map(|f| f.0).max().unwrap_or(Risk::Low)
+        );
+        found
+    };
+
+    assert_eq!(
+        findings(r"x(a+)+y"),
+        [(Risk::Exponential, FindingKind::NestedQuantifier, 1..6, 2..4)]
+    );
+    assert_eq!(
+        findings(r"(?:[a-z]+|\d+)*!"),
+        [(
+            Risk::Exponential,
+            FindingKind::NestedQuantifier,
+            0..15,
+            3..9
+        )]
+    );
+    assert_eq!(
+        findings(r"(a+){2,5}"),
+        [(Risk::Polynomial, FindingKind::NestedQuantifier, 0..9, 1..3)]
+    );
+    assert_eq!(
+        findings(r"(?i)(A|a)+"),
+        [(
+            Risk::Exponential,
+            FindingKind::OverlappingAlternatives,
+            4..10,
+            7..8
+        )]
+    );
+    assert_eq!(
+        findings(r"^\s*(.*?)\s*$"),
+        [
+            (
+                Risk::Polynomial,
+                FindingKind::AdjacentQuantifiers,
+                1..4,
+                5..8
+            ),
+            (
+                Risk::Polynomial,
+                FindingKind::AdjacentQuantifiers,
+                5..8,
+                9..12
+            ),
+        ]
+    );
+    // Inside look-around too
+    assert_eq!(findings(r"(?<=(a+)+)b")[0].2, 4..9);
+
+    // Atomic groups, possessive quantifiers, disjoint alternatives and
+    // repeats that cannot run into the next iteration
+    for p in [
+        r"(?>a|ab)*c",
+        r"(a+)++",
+        r"(a++)+",
+        r#""(?:\\.|[^"\\])*""#,
+        r"(a+b)+",
+        r"([^,]*,)*x",
+        r"\d+\.\d+",
+        "abc",
+    ] {
+        assert_eq!(findings(p), [], "{p}");
+    }
+    let literal = Regex::literal("(a+)+").unwrap();
+    assert_eq!(literal.analyze_complexity().risk, Risk::Low);
+}
+
+#[test]
+fn lint() {
+    use ferroni::lint::LintKind;
+
+    let lints = |p: &str| -> Vec<(LintKind, String)> {
+        Regex::new(p)
+            .unwrap()
+            .lint()
+            .into_iter()
+            .map(|l| (l.kind, p[l.span].to_string()))
+            .collect()
+    };
+
+    // Names used by backreferences, calls and conditionals
+    for p in [
+        r"(?<q>a)\k<q>",
+        r"(?<n>a){0}\g<n>",
+        r"(?<n>a)(?(<n>)b|c)",
+        r"(?<n>a)|\g<1>",
+    ] {
+        assert_eq!(lints(p), [], "{p}");
+    }
+    assert_eq!(
+        lints(r"(?<a>x)|(?<a>y)"),
+        [
+            (LintKind::UnusedName, "(?<a>x)".into()),
+            (LintKind::DuplicateName, "(?<a>y)".into())
+        ]
+    );
+    assert_eq!(
+        lints(r"(?<a>x)(?<a>y)\k<a>"),
+        [(LintKind::DuplicateName, "(?<a>y)".into())]
+    );
+
+    // Empty repeats and alternations are reported once, at the outside
+    assert_eq!(lints(r"x()+"), [(LintKind::EmptyRepeat, "()+".into())]);
+    assert_eq!(lints(r"(|)*"), [(LintKind::EmptyRepeat, "(|)*".into())]);
+    assert_eq!(
+        lints(r"a(?:|)b"),
+        [(LintKind::EmptyAlternation, "|".into())]
+    );
+    assert_eq!(
+        lints(r"(?<n>)*"),
+        [
+            (LintKind::UnusedName, "(?<n>)".into()),
+            (LintKind::EmptyRepeat, "(?<n>)*".into())
+        ]
+    );
+    for p in [r"(?:a|)*", r"a{0}", r"\R*", r"\X+"] {
+        assert_eq!(lints(p), [], "{p}");
+    }
+
+    // Class items covered by the rest of their operand
+    let redundant = |p: &str| -> Vec<String> {
+        lints(p)
+            .into_iter()
+            .map(|(kind, s)| {
+                assert_eq!(kind, LintKind::RedundantClassItem, "{p}");
+                s
+            })
+            .collect()
+    };
+    assert_eq!(redundant(r"[aa]"), ["a"]);
+    assert_eq!(redundant(r"[^a-cb]"), ["b"]);
+    assert_eq!(redundant(r"[0-9\d]"), ["0-9"]);
+    assert_eq!(redundant(r"[[:alpha:]a-z]"), ["a-z"]);
+    assert_eq!(redundant(r"[\p{L}\p{Lu}é]"), [r"\p{Lu}", "é"]);
+    assert_eq!(redundant(r"[-a-]"), ["-"]);
+    for p in [
+        r"[\w\W]",
+        r"[a-z&&a-c]",
+        r"[a[a]]",
+        r"[!--]",
+        r"(?i)[a-zA-Z]",
+    ] {
+        assert!(redundant(p).is_empty(), "{p}");
+    }
+
+    assert_eq!(Regex::literal("[aa]").unwrap().lint(), []);
+}
+
+#[test]
+fn line_anchored_keyword_alternation() {
+    let re = Regex::new(r"^[ \t]*(?:color|colour|margin)\b").unwrap();
+    let text = "a {\n  colour: red;\nx margin: 0;\n\tmargin: 1;\ncolor\n";
+    let starts: Vec<_> = re.find_iter(text).map(|m| m.start()).collect();
+    assert_eq!(starts, vec![4, 32, 44]);
+
+    // A search starting mid-line skips to the next line start
+    let lines = "x color\ncolor";
+    let (pos, _) = ferroni::regexec::onig_search(
+        re.as_raw(),
+        lines.as_bytes(),
+        lines.len(),
+        2,
+        lines.len(),
+        None,
+        ferroni::oniguruma::ONIG_OPTION_NONE,
+    );
+    assert_eq!(pos, 8);
+
+    // The skip follows the newline convention
+    let re = Regex::new(r"(*CRLF)^(?:a|b)").unwrap();
+    let starts: Vec<_> = re.find_iter("b\na\r\nb").map(|m| m.start()).collect();
+    assert_eq!(starts, vec![0, 5]);
+}
+
+#[test]
+fn find_iter_last_positions_and_reset() {
+    let re = Regex::new(r"(?<k>[a-z]+)=(?<v>\d+)?").unwrap();
+    let mut it = re.find_iter("a=1 b= c=3");
+    assert_eq!(it.last_match_end(), None);
+    assert_eq!(it.last_group(0), None);
+
+    assert_eq!(it.next().unwrap().range(), 0..3);
+    assert_eq!(it.next().unwrap().range(), 4..6);
+    assert_eq!(it.last_match_end(), Some(6));
+    assert_eq!(it.last_group(1), Some(4..5));
+    // Group 2 did not participate, so it keeps its previous span
+    assert_eq!(it.last_group(2), Some(2..3));
+    assert_eq!(it.last_group(3), None);
+
+    assert_eq!(it.next().unwrap().range(), 7..10);
+    assert!(it.next().is_none());
+    assert_eq!(it.last_match_end(), Some(10));
+
+    // Reuse on a new haystack
+    let text = String::from("zz=9");
+    it.reset(&text);
+    assert_eq!(it.last_match_end(), None);
+    assert_eq!(it.last_group(2), None);
+    let all: Vec<_> = it.by_ref().map(|m| m.range()).collect();
+    assert_eq!(all, vec![0..4]);
+    assert_eq!(it.last_group(2), Some(3..4));
+
+    // Empty matches still advance after a reset
+    let re = Regex::new(r"x*").unwrap();
+    let mut it = re.find_iter("ab");
+    assert_eq!(it.by_ref().count(), 3);
+    it.reset_bytes(b"xa");
+    let all: Vec<_> = it.map(|m| m.range()).collect();
+    assert_eq!(all, vec![0..1, 1..1, 2..2]);
+}
+
+#[cfg(feature = "unstable-opcodes")]
+#[test]
+fn custom_opcode_backtracks_over_ends() {
+    use ferroni::extension::{OpcodeContext, OpcodeHandler};
+    use std::sync::Arc;
+
+    /// Consumes up to `arg` bytes, longest first.
+    struct UpTo;
+    impl OpcodeHandler for UpTo {
+        fn execute(&self, cx: &OpcodeContext<'_>, ends: &mut Vec<usize>) {
+            let n: usize = std::str::from_utf8(cx.arg()).unwrap().parse().unwrap();
+            let max = (cx.pos() + n).min(cx.text().len());
+            ends.extend((cx.pos()..=max).rev());
+        }
+    }
+
+    let build = |p: &str| Regex::builder(p).opcode("UPTO", Arc::new(UpTo)).build();
+
+    let re = build(r"a(*UPTO{3})c").unwrap();
+    assert_eq!(re.find("xabcbcd").unwrap().as_str(), "abcbc");
+    assert!(!re.is_match("xabbbbc"));
+
+    let re = build(r"^(?:(*UPTO{1})x)+$").unwrap();
+    assert!(re.is_match("axbxx"));
+    assert!(!re.is_match("abx"));
+
+    // Not in look-behind, and only names that were registered
+    assert!(build(r"(?<=(*UPTO{1}))a").is_err());
+    assert!(build(r"(*UPTO[tag]{1})").is_err());
+    assert!(Regex::new(r"(*UPTO{1})").is_err());
+}
+
+#[test]
+fn one_regex_many_threads() {
+    use std::sync::Arc;
+
+    fn send_sync<T: Send + Sync>() {}
+    send_sync::<Regex>();
+    send_sync::<ferroni::scanner::Scanner>();
+    send_sync::<ferroni::regint::RegexType>();
+
+    // Compiled once, matched concurrently: search state is per call
+    let syntax = ferroni::regsyntax::SyntaxBuilder::new(&ferroni::regsyntax::OnigSyntaxOniguruma)
+        .build()
+        .unwrap();
+    let re = Arc::new(
+        Regex::builder(r"(\w+)@(\w+)")
+            .syntax(syntax)
+            .build()
+            .unwrap(),
+    );
+    let workers: Vec<_> = (0..8)
+        .map(|i| {
+            let re = Arc::clone(&re);
+            std::thread::spawn(move || {
+                let text = format!("{} user{i}@host{i} end", "x ".repeat(i * 100));
+                (0..50)
+                    .map(|_| {
+                        re.captures(&text)
+                            .unwrap()
+                            .get(2)
+                            .unwrap()
+                            .as_str()
+                            .to_owned()
+                    })
+                    .all(|host| host == format!("host{i}"))
+            })
+        })
+        .collect();
+    for worker in workers {
+        assert!(worker.join().unwrap());
+    }
+}
+
+#[test]
+fn compiled_regex_outlives_its_syntax() {
+    use ferroni::regsyntax::{onig_set_meta_char, onig_set_syntax_op, OnigSyntaxOniguruma};
+
+    // A syntax on the stack, dropped before the regex is searched on
+    // another thread: the regex holds its own copy.
+    let reg = {
+        let mut syntax = OnigSyntaxOniguruma.clone();
+        let op = syntax.op | ferroni::oniguruma::ONIG_SYN_OP_VARIABLE_META_CHARACTERS;
+        onig_set_syntax_op(&mut syntax, op);
+        onig_set_meta_char(
+            &mut syntax,
+            ferroni::oniguruma::ONIG_META_CHAR_ESCAPE,
+            '%' as u32,
+        );
+        ferroni::regcomp::onig_new(
+            b"%d+",
+            ferroni::oniguruma::ONIG_OPTION_NONE,
+            &ferroni::encodings::utf8::ONIG_ENCODING_UTF8,
+            &syntax,
+        )
+        .unwrap()
+    };
+    let worker = std::thread::spawn(move || {
+        let text = b"ab 12";
+        let (r, _) = ferroni::regexec::onig_search(
+            &reg,
+            text,
+            text.len(),
+            0,
+            text.len(),
+            None,
+            ferroni::oniguruma::ONIG_OPTION_NONE,
+        );
+        (
+            r,
+            ferroni::regexec::onig_get_syntax(&reg).meta_char_table.esc,
+        )
+    });
+    assert_eq!(worker.join().unwrap(), (3, '%' as u32));
+}
+
+#[test]
+fn memory_usage() {
+    use ferroni::regset::{onig_regset_memory_usage, onig_regset_new};
+    use ferroni::scanner::{Scanner, ScannerFindOptions};
+
+    let re = Regex::new(r"(?<key>[\p{L}_]+)=(\d{1,4})").unwrap();
+    let m = re.memory_usage();
+    assert!(m.bytecode > 0 && m.char_classes > 0 && m.names > 0 && m.optimization > 0);
+    assert_eq!(m.search_state, 0);
+    assert_eq!(
+        m.total(),
+        m.bytecode + m.char_classes + m.names + m.optimization + m.other
+    );
+    // A longer pattern of the same kind costs more
+    let longer = Regex::new(&r"(?<key>[\p{L}_]+)=(\d{1,4})".repeat(5)).unwrap();
+    assert!(longer.memory_usage().bytecode > m.bytecode);
+
+    // A RegSet reports its regexes plus regions
+    let patterns = ["[a-z]+", r"\d+"];
+    let regs: Vec<_> = patterns
+        .iter()
+        .map(|p| {
+            Box::new(
+                ferroni::regcomp::onig_new(
+                    p.as_bytes(),
+                    ferroni::oniguruma::ONIG_OPTION_NONE,
+                    &ferroni::encodings::utf8::ONIG_ENCODING_UTF8,
+                    &ferroni::regsyntax::OnigSyntaxOniguruma,
+                )
+                .unwrap(),
+            )
+        })
+        .collect();
+    let (set, _) = onig_regset_new(regs);
+    let set_usage = onig_regset_memory_usage(&set.unwrap());
+    let regexes: usize = patterns
+        .iter()
+        .map(|p| Regex::new(p).unwrap().memory_usage().bytecode)
+        .sum();
+    assert_eq!(set_usage.bytecode, regexes);
+    assert!(set_usage.search_state > 0);
+
+    // A Scanner counts its patterns once, although its RegSet shares them
+    let mut scanner = Scanner::new(&patterns).unwrap();
+    assert_eq!(scanner.memory_usage().bytecode, regexes);
+    scanner.find_next_match(&"x".repeat(2000), 0, ScannerFindOptions::NONE);
+    let used = scanner.memory_usage();
+    assert!(used.search_state > set_usage.search_state);
+    assert_eq!(scanner.clone().memory_usage().bytecode, regexes);
+}
+
+#[test]
+fn regex_cache() {
+    use ferroni::regsyntax::{OnigSyntaxPerl, SyntaxBuilder};
+    use std::sync::Arc;
+
+    let cache = RegexCache::new(2);
+    let a = cache.get("a+").unwrap();
+    assert!(Arc::ptr_eq(&a, &cache.get("a+").unwrap()));
+
+    // Options and syntax are part of the key; equal syntaxes share entries
+    let ci = cache
+        .get_with(
+            "a+",
+            ferroni::oniguruma::ONIG_OPTION_IGNORECASE,
+            &OnigSyntaxPerl,
+        )
+        .unwrap();
+    assert!(!Arc::ptr_eq(&a, &ci) && ci.is_match("A"));
+    let perl = SyntaxBuilder::new(&OnigSyntaxPerl).build().unwrap();
+    let same = cache
+        .get_with("a+", ferroni::oniguruma::ONIG_OPTION_IGNORECASE, perl)
+        .unwrap();
+    assert!(Arc::ptr_eq(&ci, &same));
+    assert_eq!(cache.len(), 2);
+
+    // "a+" was used before the Perl entry, so it goes first
+    cache.get("b").unwrap();
+    assert_eq!(cache.len(), 2);
+    assert!(!Arc::ptr_eq(&a, &cache.get("a+").unwrap()));
+    // ...and now the Perl entry was least recently used
+    let again = cache
+        .get_with(
+            "a+",
+            ferroni::oniguruma::ONIG_OPTION_IGNORECASE,
+            &OnigSyntaxPerl,
+        )
+        .unwrap();
+    assert!(!Arc::ptr_eq(&ci, &again));
+
+    // Errors are returned, not kept
+    assert!(cache.get("(").is_err());
+    assert_eq!(cache.len(), 2);
+    cache.clear();
+    assert!(cache.is_empty());
+    assert!(RegexCache::new(0).get("x").is_ok());
+
+    // Shared between threads
+    let cache = Arc::new(RegexCache::new(8));
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            let cache = Arc::clone(&cache);
+            std::thread::spawn(move || {
+                (0..100).all(|i| {
+                    let text = format!("x{}", i % 10);
+                    cache.get(&text).unwrap().is_match(&text)
+                })
+            })
+        })
+        .collect();
+    for worker in workers {
+        assert!(worker.join().unwrap());
+    }
+    assert_eq!(cache.len(), 8);
+    assert!(Arc::ptr_eq(
+        &Regex::new_cached("q").unwrap(),
+        &Regex::new_cached("q").unwrap()
+    ));
+}
+
+#[test]
+fn split_variants() {
+    let re = Regex::new(",").unwrap();
+    assert_eq!(re.split("").collect::<Vec<_>>(), [""]);
+    assert_eq!(
+        re.split(",a,,b,").collect::<Vec<_>>(),
+        ["", "a", "", "b", ""]
+    );
+    assert_eq!(re.split_inclusive("").count(), 0);
+    assert_eq!(
+        re.split_inclusive(",a,,b").collect::<Vec<_>>(),
+        [",", "a,", ",", "b"]
+    );
+
+    // Empty matches split between characters, as find_iter finds them
+    let re = Regex::new("x*").unwrap();
+    assert_eq!(re.split("aé").collect::<Vec<_>>(), ["", "a", "é", ""]);
+    assert_eq!(re.split_inclusive("aé").collect::<Vec<_>>(), ["", "a", "é"]);
+
+    // Delimiters come with their groups; unmatched groups are None
+    let re = Regex::new(r"(?<op>[-+])|(?<ws>\s+)").unwrap();
+    let text = "+a - b";
+    let mut texts = Vec::new();
+    let mut delimiters = Vec::new();
+    let mut joined = String::new();
+    for (i, item) in re.split_keep(text).enumerate() {
+        match item {
+            SplitItem::Text(t) => {
+                assert_eq!(i % 2, 0);
+                texts.push(t);
+                joined.push_str(t);
+            }
+            SplitItem::Delimiter(caps) => {
+                assert_eq!(i % 2, 1);
+                let whole = caps.get(0).unwrap();
+                joined.push_str(whole.as_str());
+                delimiters.push((
+                    whole.range(),
+                    caps.name("op").map(|m| m.as_str()),
+                    caps.name("ws").map(|m| m.as_str()),
+                ));
+            }
+        }
+    }
+    assert_eq!(joined, text);
+    assert_eq!(texts, ["", "a", "", "", "b"]);
+    assert_eq!(
+        delimiters,
+        [
+            (0..1, Some("+"), None),
+            (2..3, None, Some(" ")),
+            (3..4, Some("-"), None),
+            (4..5, None, Some(" ")),
+        ]
+    );
+}
+
+#[test]
+fn replace_with_template() {
+    let re = Regex::new(r"(?<y>\d{4})-(?<m>\d\d)(?:-(?<d>\d\d))?").unwrap();
+    let t = Template::new("${m}/${d}/${y}", &re).unwrap();
+    // Numbers are checked too: there are three groups
+    assert!(Template::new("$3", &re).is_ok());
+    assert!(Template::new("$4", &re).is_err());
+    assert!(!t.is_literal());
+    assert_eq!(
+        re.replace_all("2024-05-17, 2023-12", &t),
+        "05/17/2024, 12//2023"
+    );
+    assert_eq!(re.replace("2024-05, 2023-12", &t), "05//2024, 2023-12");
+
+    // No match borrows the text
+    assert!(matches!(
+        re.replace_all("none", &t),
+        std::borrow::Cow::Borrowed("none")
+    ));
+
+    // Empty matches are replaced where find_iter finds them
+    let re = Regex::new("x*").unwrap();
+    let t = Template::new("[$0]", &re).unwrap();
+    assert_eq!(re.replace_all("axxé", &t), "[]a[xx][]é[]");
+
+    // expand() works on Captures from any search
+    let re = Regex::new(r"(\w)(\w)").unwrap();
+    let t = Template::new("$2$1", &re).unwrap();
+    let mut out = String::new();
+    for caps in re.captures_at_most_n("abcd", 10).0 {
+        t.expand(&caps, &mut out);
+    }
+    assert_eq!(out, "badc");
+
+    let err = Template::new("$1$", &re).unwrap_err();
+    assert_eq!(err, TemplateError::DanglingDollar { at: 2 });
+    assert_eq!(
+        err.to_string(),
+        "'$' at 2 is not followed by a group or '$'"
+    );
+}
+
+#[test]
+fn bytes_regex_over_binary() {
+    use ferroni::bytes;
+
+    // Every byte is one character: `.` and `[^...]` match invalid UTF-8
+    let re = bytes::Regex::new(br"\x7fELF(.)[^\x00]+").unwrap();
+    let hay = b"junk\x7fELF\x02\xff\xfe\x00rest";
+    let m = re.find(hay).unwrap();
+    assert_eq!(m.range(), 4..11);
+    assert_eq!(
+        re.captures(hay).unwrap().get(1).unwrap().as_bytes(),
+        b"\x02"
+    );
+    // UTF-8 sequences are two characters, not one
+    assert!(!Regex::new("^..$").unwrap().is_match("é"));
+    assert!(bytes::Regex::new(b"^..$").unwrap().is_match("é".as_bytes()));
+
+    // Text embedded in binary
+    let re = bytes::Regex::new(br"(?<k>[a-z]+)=(?<v>\d+)").unwrap();
+    let hay = b"\x00\x80a=1\xc0\xffbb=22\x01";
+    let found: Vec<_> = re.find_iter(hay).map(|m| m.as_bytes()).collect();
+    assert_eq!(found, [&b"a=1"[..], b"bb=22"]);
+    assert_eq!(
+        re.captures_at(hay, 5).unwrap().name("k").unwrap().range(),
+        7..9
+    );
+    assert!(re.find_at(hay, 11).is_none());
+
+    // Replacement keeps the bytes around the matches
+    let t = Template::new("${v}:${k}", &re).unwrap();
+    assert_eq!(
+        re.replace_all(hay, &t),
+        &b"\x00\x801:a\xc0\xff22:bb\x01"[..]
+    );
+    assert_eq!(re.replace(hay, &t), &b"\x00\x801:a\xc0\xffbb=22\x01"[..]);
+    assert!(matches!(
+        re.replace_all(b"\xff", &t),
+        std::borrow::Cow::Borrowed(b"\xff")
+    ));
+
+    // Builder options, and converting a regex built with another encoding
+    let re = bytes::Regex::builder(b"abc")
+        .case_insensitive(true)
+        .build_bytes()
+        .unwrap();
+    assert!(re.is_match(b"\xffABC"));
+    let re = bytes::Regex::from(Regex::new("é+").unwrap());
+    assert_eq!(re.find(b"\xff\xc3\xa9\xc3\xa9").unwrap().range(), 1..5);
+    assert_eq!(re.as_regex().captures_len(), 0);
+}
@@ -546,6 +546,47 @@ fn python_unicode_escape_invalid() {
     );
 }
 
+// POSIX BRE rules shared by POSIX Basic and Grep.
+fn test_bre_intervals(syn: &OnigSyntaxType) {
+    x2_syn(syn, b"a\\{2\\}", b"aaa", 0, 2);
+    x2_syn(syn, b"a\\{2,\\}", b"aaa", 0, 3);
+    x2_syn(syn, b"a\\{1,2\\}", b"aaa", 0, 2);
+    x2_syn(syn, b"\\(ab\\)\\{2\\}", b"ababab", 0, 4);
+    n_syn(syn, b"a\\{3\\}", b"aa");
+    x2_syn(syn, b"a{2}", b"a{2}", 0, 4);
+    e_syn(
+        syn,
+        b"a\\{2,1\\}",
+        b"",
+        ONIGERR_UPPER_SMALLER_THAN_LOWER_IN_REPEAT_RANGE,
+    );
+    e_syn(syn, b"a\\{1", b"", ONIGERR_INVALID_REPEAT_RANGE_PATTERN);
+    e_syn(syn, b"a\\{x\\}", b"", ONIGERR_INVALID_REPEAT_RANGE_PATTERN);
+}
+
+fn test_bre_backrefs(syn: &OnigSyntaxType) {
+    x2_syn(syn, b"\\(a\\)\\1", b"aa", 0, 2);
+    x3_syn(syn, b"\\(a*\\)b\\1", b"aabaa", 0, 2, 1);
+    x2_syn(
+        syn,
+        b"\\(a\\)\\(b\\)\\(c\\)\\(d\\)\\(e\\)\\(f\\)\\(g\\)\\(h\\)\\(i\\)\\9\\1",
+        b"abcdefghiia",
+        0,
+        11,
+    );
+    n_syn(syn, b"\\(a\\)\\1", b"ab");
+    x2_syn(syn, b"\\(^a\\)\\1", b"aa", 0, 2);
+    e_syn(syn, b"a\\1", b"", ONIGERR_INVALID_BACKREF);
+}
+
+fn test_bre_literal_star(syn: &OnigSyntaxType) {
+    x2_syn(syn, b"*a", b"*a", 0, 2);
+    x2_syn(syn, b"\\(*a\\)", b"*a", 0, 2);
+    x2_syn(syn, b"^*a", b"*a", 0, 2);
+    x2_syn(syn, b"a**", b"aaa", 0, 3);
+    x2_syn(syn, b"a\\+", b"a+", 0, 2);
+}
+
 // ============================================================================
 // POSIX Basic syntax
 // ============================================================================
@@ -555,6 +596,28 @@ fn posix_basic_bre_anchors() {
     test_bre_anchors(&OnigSyntaxPosixBasic);
 }
 
+#[test]
+fn posix_basic_intervals() {
+    test_bre_intervals(&OnigSyntaxPosixBasic);
+}
+
+#[test]
+fn posix_basic_backrefs() {
+    test_bre_backrefs(&OnigSyntaxPosixBasic);
+}
+
+#[test]
+fn posix_basic_literal_star() {
+    test_bre_literal_star(&OnigSyntaxPosixBasic);
+}
+
+#[test]
+fn posix_basic_no_gnu_escapes() {
+    x2_syn(&OnigSyntaxPosixBasic, b"a\\?", b"a?", 0, 2);
+    x2_syn(&OnigSyntaxPosixBasic, b"a\\|b", b"a|b", 0, 3);
+    x2_syn(&OnigSyntaxPosixBasic, b"\\<a\\>", b"<a>", 0, 3);
+}
+
 // ============================================================================
 // Grep syntax
 // ============================================================================
@@ -564,6 +627,38 @@ fn grep_bre_anchors() {
     test_bre_anchors(&OnigSyntaxGrep);
 }
 
+#[test]
+fn grep_intervals() {
+    test_bre_intervals(&OnigSyntaxGrep);
+}
+
+#[test]
+fn grep_backrefs() {
+    test_bre_backrefs(&OnigSyntaxGrep);
+    x2_syn(&OnigSyntaxGrep, b"\\(a\\|b\\)*\\1", b"abb", 0, 3);
+}
+
+#[test]
+fn grep_literal_star_after_alternation() {
+    x2_syn(&OnigSyntaxGrep, b"x\\|*b", b"*b", 0, 2);
+}
+
+#[test]
+fn grep_word_begin_end() {
+    x2_syn(&OnigSyntaxGrep, b"\\<a", b"ba a", 3, 4);
+    x2_syn(&OnigSyntaxGrep, b"a\\>", b"ab a", 3, 4);
+    x2_syn(&OnigSyntaxGrep, b"\\<ab\\>", b"xab ab", 4, 6);
+    x2_syn(&OnigSyntaxGrep, b"\\<", b" a", 1, 1);
+    n_syn(&OnigSyntaxGrep, b"\\>", b" ");
+}
+
+#[test]
+fn grep_plus_question_escapes() {
+    x2_syn(&OnigSyntaxGrep, b"a\\+", b"aaa", 0, 3);
+    x2_syn(&OnigSyntaxGrep, b"ba\\?", b"b", 0, 1);
+    x2_syn(&OnigSyntaxGrep, b"a+", b"a+", 0, 2);
+}
+
 #[test]
 fn grep_alternation() {
     x2_syn(&OnigSyntaxGrep, b"zz\\|^ab", b"ab", 0, 2);
@@ -651,3 +746,63 @@ fn perl_ng_relative_call() {
     x2_syn(&OnigSyntaxPerl_NG, b"(?+1)(abc)", b"abcabc", 0, 6);
     x2_syn(&OnigSyntaxPerl_NG, b"(abc)(?1)", b"abcabc", 0, 6);
 }
+
+#[test]
+fn perl_ng_recursive_calls() {
+    x2_syn(
+        &OnigSyntaxPerl_NG,
+        br"\((?:[^()]|(?R))*\)",
+        b"x(a(b)c)",
+        1,
+        8,
+    );
+    x2_syn(&OnigSyntaxPerl_NG, b"a(?R)?b", b"aaabbb", 0, 6);
+    e_syn(
+        &OnigSyntaxPerl_NG,
+        b"(?R)?x",
+        b"",
+        ONIGERR_NEVER_ENDING_RECURSION,
+    );
+    e_syn(
+        &OnigSyntaxPerl_NG,
+        b"(?Rx)",
+        b"",
+        ONIGERR_UNDEFINED_GROUP_OPTION,
+    );
+    x2_syn(
+        &OnigSyntaxPerl_NG,
+        br"(?<p>\[(?:[^\[\]]|(?&p))*\])",
+        b"x[a[b]]",
+        1,
+        7,
+    );
+    x2_syn(&OnigSyntaxPerl_NG, b"(a(?-1)?b)", b"aaabbb", 0, 6);
+    // Mutual recursion through named and relative calls
+    x2_syn(
+        &OnigSyntaxPerl_NG,
+        b"(?<a>a(?&b)?)(?<b>b(?&a)?)",
+        b"ababab",
+        0,
+        6,
+    );
+    x2_syn(
+        &OnigSyntaxPerl_NG,
+        br"^(?:(?<e>\((?&l)\)|n)(?<l>(?&e)(?:,(?&e))*)?)$",
+        b"((n,n),(n))",
+        0,
+        11,
+    );
+    x2_syn(&OnigSyntaxPerl_NG, b"(a(?+1)?)(b(?-2)?)", b"abab", 0, 4);
+    e_syn(
+        &OnigSyntaxPerl_NG,
+        b"(?&nope)a",
+        b"",
+        ONIGERR_UNDEFINED_NAME_REFERENCE,
+    );
+    e_syn(
+        &OnigSyntaxPerl_NG,
+        b"(?2)(a)",
+        b"",
+        ONIGERR_UNDEFINED_GROUP_REFERENCE,
+    );
+}
@@ -9481,6 +9481,49 @@ fn absent_range_complex_no_match() {
     );
 }
 
+// --- Absent with a group at its head (not in C's test suite) ---
+// A non-capturing alternation group directly after `(?~|` is one argument,
+// not the absent|expr split. Expected values checked against Oniguruma 6.9.8.
+
+#[test]
+fn absent_range_cutter_alt_group() {
+    x2(b"(?~|(?:c|x))", b"cx", 0, 0);
+    x2(b"(?~|(?:cc|x))", b"cx", 0, 0);
+    x2(b"(?~|(?:a|a))", b"a", 0, 0);
+    x2(b"((?~|(?:(?:x|c)|b)))", b"xxxcbb", 0, 0);
+    x2(b"(?~|(?:ab|cd)+)x", b"cdx", 2, 3);
+    n(b"(?~|(?:ab|cd))ab", b"xcdab");
+}
+
+#[test]
+fn absent_expr_split_without_group() {
+    x2(b"(?~|cc|x)", b"cx", 1, 2);
+    x2(b"(?:(?:(?~|\\w|\\w).|(?~|(?:ab|\\O)))){2}", b"bcx", 0, 0);
+}
+
+#[test]
+fn ignorecase_latin1_string() {
+    // U+0080..U+00FF take two bytes in UTF-8, so their folds must not go
+    // into the single-byte bitset.
+    x2("(?i)é".as_bytes(), "xÉ".as_bytes(), 1, 3);
+    x2("(?i)Éa".as_bytes(), "xéA".as_bytes(), 1, 4);
+    x2("(?i)(?<=é)x".as_bytes(), "Éx".as_bytes(), 2, 3);
+    x2("(?i)ÿ".as_bytes(), "Ÿ".as_bytes(), 0, 2);
+}
+
+#[test]
+fn alternation_common_prefix() {
+    // Alternatives sharing a literal prefix are factored at compile time;
+    // priority and group numbers must not change.
+    x3(b"ab(c)|ab(d)", b"abd", 2, 3, 2);
+    x2(b"foo|foobar", b"foobar", 0, 3);
+    x2(b"(?:foo|foobar)$", b"foobar", 0, 6);
+    x2(b"ab|ab|ac", b"ac", 0, 2);
+    x2(b"xa(?=y)|xa(?=z)|xab", b"xab", 0, 3);
+    x2("éa|éb|e".as_bytes(), "éb".as_bytes(), 0, 3);
+    n(b"ab|ab", b"ac");
+}
+
 // --- Japanese lookahead/lookbehind (C lines 955-958) ---
 
 #[test]
@@ -0,0 +1,107 @@
+// memhook_test.rs - The global memory hook: accounting and refusal.
+//
+// The hook is process-global, so every test takes LOCK while one is
+// installed.
+
+use std::sync::atomic::{AtomicIsize, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use ferroni::error::RegexError;
+use ferroni::memhook::{onig_set_memory_hook, OnigBufferKind};
+use ferroni::oniguruma::*;
+use ferroni::prelude::*;
+use ferroni::regexec::onig_search;
+
+static LOCK: Mutex<()> = Mutex::new(());
+/// Bytes granted and not yet freed, over all kinds.
+static OUTSTANDING: AtomicIsize = AtomicIsize::new(0);
+/// Grow requests granted so far.
+static GRANTED: AtomicUsize = AtomicUsize::new(0);
+/// Largest size a buffer may grow to.
+static LIMIT: AtomicUsize = AtomicUsize::new(usize::MAX);
+static REFUSED: Mutex<Vec<OnigBufferKind>> = Mutex::new(Vec::new());
+
+fn hook(kind: OnigBufferKind, old: usize, new: usize) -> bool {
+    if new > LIMIT.load(Ordering::Relaxed) {
+        REFUSED.lock().unwrap().push(kind);
+        return false;
+    }
+    if new > old {
+        GRANTED.fetch_add(1, Ordering::Relaxed);
+    }
+    OUTSTANDING.fetch_add(new as isize - old as isize, Ordering::Relaxed);
+    true
+}
+
+fn with_hook(limit: usize, f: impl FnOnce()) -> Vec<OnigBufferKind> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    LIMIT.store(limit, Ordering::Relaxed);
+    REFUSED.lock().unwrap().clear();
+    onig_set_memory_hook(Some(hook));
+    f();
+    onig_set_memory_hook(None);
+    std::mem::take(&mut *REFUSED.lock().unwrap())
+}
+
+#[test]
+fn accounting_returns_to_zero() {
+    let refused = with_hook(usize::MAX, || {
+        let before = OUTSTANDING.load(Ordering::Relaxed);
+        let granted = GRANTED.load(Ordering::Relaxed);
+        // Code range and case fold buffers are freed once compiled, the
+        // backtrack stack when the search returns.
+        let re = Regex::new(r"(?i)straße[\x{100}-\x{200}\x{300}]|(?:a|b)*c").unwrap();
+        let hay = "ab".repeat(10_000) + "c";
+        assert_eq!(re.find(&hay).unwrap().range(), 0..hay.len());
+        assert!(GRANTED.load(Ordering::Relaxed) >= granted + 3);
+        assert_eq!(OUTSTANDING.load(Ordering::Relaxed), before);
+    });
+    assert!(refused.is_empty());
+}
+
+#[test]
+fn refused_stack_growth_fails_the_search() {
+    let refused = with_hook(64 * 1024, || {
+        let re = ferroni::regcomp::onig_new(
+            b"(?:a|b)*c",
+            ONIG_OPTION_NONE,
+            &ferroni::encodings::utf8::ONIG_ENCODING_UTF8,
+            &ferroni::regsyntax::OnigSyntaxOniguruma,
+        )
+        .unwrap();
+        let hay = "ab".repeat(100_000) + "c";
+        let (r, _) = onig_search(
+            &re,
+            hay.as_bytes(),
+            hay.len(),
+            0,
+            hay.len(),
+            None,
+            ONIG_OPTION_NONE,
+        );
+        assert_eq!(r, ONIGERR_MEMORY);
+        // Short inputs stay within the cap.
+        let (r, _) = onig_search(&re, b"abc", 3, 0, 3, None, ONIG_OPTION_NONE);
+        assert_eq!(r, 0);
+    });
+    assert_eq!(refused, [OnigBufferKind::BacktrackStack]);
+}
+
+#[test]
+fn refused_code_range_fails_compilation() {
+    let refused = with_hook(16, || {
+        let err = Regex::new(r"[\x{100}-\x{200}\x{300}\x{400}]").unwrap_err();
+        assert_eq!(err, RegexError::Memory);
+    });
+    assert_eq!(refused, [OnigBufferKind::CodeRangeBuffer]);
+}
+
+#[test]
+fn refused_case_fold_scratch_fails_compilation() {
+    let refused = with_hook(0, || {
+        assert_eq!(Regex::new("(?i)straße").unwrap_err(), RegexError::Memory);
+        // Patterns without these buffers are unaffected.
+        assert!(Regex::new("abc").is_ok());
+    });
+    assert_eq!(refused, [OnigBufferKind::CaseFoldScratch]);
+}
@@ -0,0 +1,166 @@
+// session_test.rs - Property tests: feeding a haystack to a ScanSession in
+// arbitrary chunks must report the same matches as find_iter on the whole
+// haystack.
+
+use proptest::prelude::*;
+
+use ferroni::api::{Regex, RegexBuilder};
+use ferroni::oniguruma::*;
+use ferroni::session::{ScanSession, SessionMatch};
+
+fn atom() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[abxy]".prop_map(|s| s.to_string()),
+        Just("ab".to_string()),
+        Just("xay".to_string()),
+        Just(".".to_string()),
+        Just("[^b\\n]".to_string()),
+        Just("\\d".to_string()),
+        Just("\\w".to_string()),
+        Just("\\s".to_string()),
+        Just("^".to_string()),
+        Just("$".to_string()),
+        Just("\\A".to_string()),
+        Just("\\z".to_string()),
+        Just("\\Z".to_string()),
+        Just("\\b".to_string()),
+        Just("\\B".to_string()),
+        Just("é".to_string()),
+        Just("(?i:xyab)".to_string()),
+        Just("\\R".to_string()),
+    ]
+}
+
+fn pattern() -> impl Strategy<Value = String> {
+    atom().prop_recursive(4, 24, 4, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 1..4).prop_map(|v| v.concat()),
+            (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("(?:{a}|{b})")),
+            inner.clone().prop_map(|a| format!("({a})")),
+            (
+                inner.clone(),
+                prop_oneof![Just("?"), Just("??"), Just("{2,3}"), Just("{0,2}")]
+            )
+                .prop_map(|(a, q)| format!("(?:{a}){q}")),
+            inner.clone().prop_map(|a| format!("(?={a})")),
+            inner.clone().prop_map(|a| format!("(?!{a})")),
+            inner.clone().prop_map(|a| format!("(?<={a})")),
+            inner.clone().prop_map(|a| format!("(?>{a})")),
+            inner.prop_map(|a| format!("(?i:{a})")),
+        ]
+    })
+}
+
+fn haystack() -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop_oneof![
+            Just("a"),
+            Just("b"),
+            Just("x"),
+            Just("y"),
+            Just("A"),
+            Just("1"),
+            Just(" "),
+            Just("\n"),
+            Just("\r\n"),
+            Just("é"),
+            Just("xYAb"),
+        ],
+        0..80,
+    )
+    .prop_map(|v| v.concat())
+}
+
+/// Feed `hay` in chunks ending at `cuts` (byte offsets, any order).
+fn scan(re: &Regex, hay: &[u8], cuts: &[usize]) -> Option<Vec<SessionMatch>> {
+    let mut session = ScanSession::new(re)?;
+    let mut cuts: Vec<usize> = cuts.iter().map(|&c| c % (hay.len() + 1)).collect();
+    cuts.sort_unstable();
+    let mut found = Vec::new();
+    let mut at = 0;
+    for cut in cuts {
+        found.extend(session.append(&hay[at..cut]));
+        at = cut;
+    }
+    found.extend(session.append(&hay[at..]));
+    found.extend(session.finish());
+    Some(found)
+}
+
+fn expected(re: &Regex, hay: &str) -> Vec<(u64, u64)> {
+    re.find_iter(hay)
+        .map(|m| (m.start() as u64, m.end() as u64))
+        .collect()
+}
+
+fn ranges(found: &[SessionMatch]) -> Vec<(u64, u64)> {
+    found.iter().map(|m| (m.start(), m.end())).collect()
+}
+
+proptest! {
+    #[test]
+    fn chunked_scan_equals_find_iter(
+        pat in pattern(),
+        hay in haystack(),
+        cuts in prop::collection::vec(any::<usize>(), 0..12),
+        multiline in any::<bool>(),
+    ) {
+        let option = if multiline { ONIG_OPTION_MULTILINE } else { ONIG_OPTION_NONE };
+        let Ok(re) = RegexBuilder::new(&pat).option(option).build() else { return Ok(()); };
+        let Some(found) = scan(&re, hay.as_bytes(), &cuts) else { return Ok(()); };
+        prop_assert_eq!(ranges(&found), expected(&re, &hay), "pattern {:?} on {:?}", pat, hay);
+        for m in &found {
+            prop_assert_eq!(m.as_bytes(), &hay.as_bytes()[m.start() as usize..m.end() as usize]);
+        }
+    }
+}
+
+#[test]
+fn long_stream_reports_every_match() {
+    let re = Regex::new(r"\bab\d{2}").unwrap();
+    let mut session = ScanSession::new(&re).unwrap();
+    let mut found = Vec::new();
+    for i in 0..10_000u64 {
+        found.extend(session.append(format!("ab{:02} ", i % 100).as_bytes()));
+    }
+    found.extend(session.finish());
+    assert_eq!(found.len(), 10_000);
+    assert_eq!(found[9_999].range(), 49_995..49_999);
+    assert_eq!(found[9_999].as_bytes(), b"ab99");
+}
+
+#[test]
+fn chunks_may_split_characters() {
+    let re = Regex::new("éa|a").unwrap();
+    let hay = "aéaéé a".as_bytes();
+    let cuts: Vec<usize> = (0..=hay.len()).collect();
+    assert_eq!(
+        ranges(&scan(&re, hay, &cuts).unwrap()),
+        [(0, 1), (1, 4), (9, 10)]
+    );
+}
+
+#[test]
+fn unsupported_patterns() {
+    for pat in [
+        r"a+",
+        r"a*",
+        r"(a)\1",
+        r"(?<x>a)\g<x>",
+        r"\Ga",
+        r"\X",
+        r"a\y",
+        r"(?~a)",
+    ] {
+        let re = Regex::new(pat).unwrap();
+        assert!(ScanSession::new(&re).is_none(), "{pat}");
+    }
+    let re = RegexBuilder::new("a|ab")
+        .option(ONIG_OPTION_FIND_LONGEST)
+        .build()
+        .unwrap();
+    assert!(ScanSession::new(&re).is_none());
+    // Unbounded repeats of zero-width bodies are fine.
+    let re = Regex::new(r"(?:x{0})*a").unwrap();
+    assert!(ScanSession::new(&re).is_some());
+}
@@ -0,0 +1,212 @@
+// window_test.rs - Property tests: searching a slice `&hay[a..b]` must give
+// the same result as searching the full buffer with end=b, start=a, range=b.
+//
+// Patterns avoid constructs that look before `a` (`^`, `\A`, `\b`, `\B`,
+// look-behind), since those legitimately see different context.
+
+use proptest::prelude::*;
+
+use ferroni::encodings::utf8::ONIG_ENCODING_UTF8;
+use ferroni::oniguruma::*;
+use ferroni::regexec::{onig_match, onig_search};
+use ferroni::regint::RegexType;
+use ferroni::regsyntax::OnigSyntaxOniguruma;
+
+fn compile(pattern: &str, option: OnigOptionType) -> Option<RegexType> {
+    ferroni::regcomp::onig_new(
+        pattern.as_bytes(),
+        option,
+        &ONIG_ENCODING_UTF8,
+        &OnigSyntaxOniguruma,
+    )
+    .ok()
+}
+
+/// Match result with positions relative to the window start.
+fn relative(r: i32, region: Option<OnigRegion>, base: usize) -> (i32, Vec<(i32, i32)>) {
+    let shift = |p: i32| if p < 0 { p } else { p - base as i32 };
+    let spans = match region {
+        Some(region) if r >= 0 => (0..region.num_regs as usize)
+            .map(|i| (shift(region.beg[i]), shift(region.end[i])))
+            .collect(),
+        _ => Vec::new(),
+    };
+    (r, spans)
+}
+
+fn atom() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[abxy]".prop_map(|s| s.to_string()),
+        Just("ab".to_string()),
+        Just("xay".to_string()),
+        Just(".".to_string()),
+        Just("[a-c]".to_string()),
+        Just("[^b\\n]".to_string()),
+        Just("\\d".to_string()),
+        Just("\\w".to_string()),
+        Just("\\s".to_string()),
+        Just("\\n".to_string()),
+        Just("$".to_string()),
+        Just("\\z".to_string()),
+        Just("\\Z".to_string()),
+        Just("\\G".to_string()),
+        Just("é".to_string()),
+        Just("abxab".to_string()),
+        Just("(?i:xyxyab)".to_string()),
+        Just("\\1".to_string()),
+        Just("\\R".to_string()),
+        Just("\\X".to_string()),
+    ]
+}
+
+fn pattern() -> impl Strategy<Value = String> {
+    atom().prop_recursive(4, 24, 4, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 1..4).prop_map(|v| v.concat()),
+            (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("(?:{a}|{b})")),
+            inner.clone().prop_map(|a| format!("({a})")),
+            (
+                inner.clone(),
+                prop_oneof![
+                    Just("*"),
+                    Just("+"),
+                    Just("?"),
+                    Just("*?"),
+                    Just("+?"),
+                    Just("{2,3}"),
+                    Just("{1,}"),
+                ]
+            )
+                .prop_map(|(a, q)| format!("(?:{a}){q}")),
+            inner.clone().prop_map(|a| format!("(?={a})")),
+            inner.clone().prop_map(|a| format!("(?!{a})")),
+            inner.clone().prop_map(|a| format!("(?>{a})")),
+            inner.clone().prop_map(|a| format!("(?i:{a})")),
+            inner.prop_map(|a| format!("(?:{a})++")),
+        ]
+    })
+}
+
+fn haystack() -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop_oneof![
+            Just("a"),
+            Just("b"),
+            Just("x"),
+            Just("y"),
+            Just("A"),
+            Just("1"),
+            Just(" "),
+            Just("\n"),
+            Just("é"),
+            Just("ab"),
+            Just("abxab"),
+            Just("xYxyAB"),
+        ],
+        0..40,
+    )
+    .prop_map(|v| v.concat())
+}
+
+/// A haystack plus a window on character boundaries.
+fn windowed() -> impl Strategy<Value = (String, usize, usize)> {
+    haystack().prop_flat_map(|hay| {
+        let bounds: Vec<usize> = hay
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(hay.len()))
+            .collect();
+        let n = bounds.len();
+        (Just(hay), 0..n, 0..n).prop_map(move |(hay, i, j)| {
+            let (i, j) = if i <= j { (i, j) } else { (j, i) };
+            (hay, bounds[i], bounds[j])
+        })
+    })
+}
+
+proptest! {
+    #[test]
+    fn search_slice_equals_window(
+        pat in pattern(),
+        (hay, a, b) in windowed(),
+        multiline in any::<bool>(),
+    ) {
+        let option = if multiline { ONIG_OPTION_MULTILINE } else { ONIG_OPTION_NONE };
+        let Some(reg) = compile(&pat, option) else { return Ok(()); };
+        let hay = hay.as_bytes();
+        let window = &hay[a..b];
+
+        let (r, region) = onig_search(
+            &reg,
+            window,
+            window.len(),
+            0,
+            window.len(),
+            Some(OnigRegion::new()),
+            ONIG_OPTION_NONE,
+        );
+        let sliced = relative(r, region, 0);
+
+        let (r, region) = onig_search(&reg, hay, b, a, b, Some(OnigRegion::new()), ONIG_OPTION_NONE);
+        let (r, spans) = relative(r, region, a);
+        let full = (if r >= 0 { r - a as i32 } else { r }, spans);
+
+        prop_assert_eq!(sliced, full, "pattern {:?} on {:?}[{}..{}]", pat, hay, a, b);
+    }
+
+    #[test]
+    fn match_slice_equals_window(
+        pat in pattern(),
+        (hay, a, b) in windowed(),
+    ) {
+        let Some(reg) = compile(&pat, ONIG_OPTION_NONE) else { return Ok(()); };
+        let hay = hay.as_bytes();
+        let window = &hay[a..b];
+
+        let (r, region) = onig_match(&reg, window, window.len(), 0, Some(OnigRegion::new()), ONIG_OPTION_NONE);
+        let sliced = relative(r, region, 0);
+
+        let (r, region) = onig_match(&reg, hay, b, a, Some(OnigRegion::new()), ONIG_OPTION_NONE);
+        let full = relative(r, region, a);
+
+        prop_assert_eq!(sliced, full, "pattern {:?} on {:?}[{}..{}]", pat, hay, a, b);
+    }
+}
+
+// === Regressions (found by the properties above) ===
+
+#[test]
+fn window_ignores_bytes_past_end() {
+    let reg = compile("ab$", ONIG_OPTION_NONE).unwrap();
+    let hay = b"xxabab";
+    let (r, _) = onig_search(&reg, hay, 4, 0, 4, None, ONIG_OPTION_NONE);
+    assert_eq!(r, 2);
+}
+
+#[test]
+fn capture_inside_negative_lookahead_is_unset() {
+    let reg = compile("(?!(?!(a)))a", ONIG_OPTION_NONE).unwrap();
+    let (r, region) = onig_search(
+        &reg,
+        b"xa",
+        2,
+        0,
+        2,
+        Some(OnigRegion::new()),
+        ONIG_OPTION_NONE,
+    );
+    assert_eq!(relative(r, region, 0), (1, vec![(1, 2), (-1, -1)]));
+}
+
+#[test]
+fn backref_inside_own_group_compiles() {
+    for pat in [r"(\1)", r"(a\1)", r"(a|\1)b"] {
+        let reg = compile(pat, ONIG_OPTION_NONE).unwrap();
+        let (r, _) = onig_search(&reg, b"ab", 2, 0, 2, None, ONIG_OPTION_NONE);
+        assert_eq!(
+            r,
+            if pat == r"(a|\1)b" { 0 } else { ONIG_MISMATCH },
+            "{pat}"
+        );
+    }
+}